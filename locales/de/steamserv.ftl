@@ -0,0 +1,949 @@
+## Allgemein
+
+offline-error = Im Offline-Modus nicht möglich: { $action }. Starte ohne --offline oder setze `offline = false` in der Konfiguration.
+action-install-server = Gameserver installieren
+action-nat-check = Erreichbarkeit der Ports prüfen
+action-lancache-check = den Lancache prüfen
+action-dns-update = dynamischen DNS-Eintrag aktualisieren
+action-sync-profiles = Community-Profile synchronisieren
+action-update-server = Gameserver aktualisieren
+action-download-steamcmd = SteamCMD herunterladen
+action-update-cache = Server-Cache aktualisieren
+action-resolve-steamid = eine benutzerdefinierte Profil-URL auflösen
+action-check-updates = nach Updates suchen
+action-install-deps = Pakete installieren
+config-dir-not-found = Das Konfigurationsverzeichnis wurde nicht gefunden
+cache-dir-not-found = Das Cache-Verzeichnis wurde nicht gefunden
+command-required = Nach der Einrichtung ist ein Befehl erforderlich, --help listet alle Befehle auf
+
+## Einrichtung
+
+init-welcome = Du verwendest steamserv zum ersten Mal. Die folgenden Schritte richten deine Umgebung ein.
+init-steamcmd-installed = Ist SteamCMD bereits installiert?
+init-steamcmd-path = Bitte gib den Pfad zur SteamCMD-Programmdatei ein:
+init-steamcmd-path-help = Das ist der Pfad zu deiner SteamCMD-Programmdatei
+init-steamcmd-offline = SteamCMD kann im Offline-Modus nicht heruntergeladen werden
+init-steamcmd-required = SteamCMD wird für steamserv benötigt
+init-host-arch = Dieser Host ist { $arch }, SteamCMD und die Gameserver sind x86-Programme und brauchen einen Emulator.
+init-emulator = SteamCMD und die Gameserver mit { $emulator } ausführen?
+init-install-path = Bitte gib den Pfad zum Installationsverzeichnis der Server ein:
+init-install-path-help = In diesem Verzeichnis werden die Server installiert.
+init-install-path-placeholder = z.B. /home/user/servers
+init-bench = Die Schreibgeschwindigkeit dieses Verzeichnisses messen?
+init-bench-help = Schreibt eine Testdatei mit 256 MiB, um zu prüfen, ob die Festplatte für große Updates schnell genug ist.
+init-bench-result = Das Verzeichnis schreibt { $speed }. { $verdict }
+init-bench-keep = Dieses Verzeichnis trotzdem verwenden?
+init-cache-offline = Der Offline-Modus ist aktiv, der Server-Cache wird nicht erstellt.
+init-cache-create = Erstelle den Server-Cache...
+init-complete = Einrichtung abgeschlossen! Du kannst steamserv jetzt verwenden.
+steamcmd-install-path = Bitte gib den Pfad zum SteamCMD-Installationsverzeichnis ein:
+steamcmd-install-path-help = In diesem Verzeichnis wird SteamCMD installiert.
+steamcmd-install-path-placeholder = z.B. /home/user/steamcmd
+steamcmd-install-confirm = Möchtest du SteamCMD jetzt installieren?
+steamcmd-download-progress = Lade SteamCMD herunter
+steamcmd-checksum-missing = Es ist keine SHA-256-Prüfsumme konfiguriert, der Download konnte nicht überprüft werden ({ $checksum }). Trotzdem ausführen?
+steamcmd-checksum-missing-help = Setze `steamcmd_sha256` in der Konfiguration, um den Download zu prüfen, oder `steamcmd_allow_unverified = true`, um diese Frage zu überspringen
+steamcmd-unverified = Der ungeprüfte SteamCMD-Download wird nicht ausgeführt. Setze nach Prüfung der Prüfsumme `steamcmd_sha256 = "{ $checksum }"` in der Konfiguration oder `steamcmd_allow_unverified = true`.
+download-checksum-mismatch = Prüfsumme stimmt nicht überein: erwartet { $expected }, erhalten { $actual }
+download-https-required = Download von { $url } ohne HTTPS verweigert
+download-retrying = Download unterbrochen ({ $error }), Versuch { $attempt }/{ $max }...
+download-failed = Download nach { $retries } Versuchen fehlgeschlagen: { $error }
+download-incomplete = Unvollständiger Download: { $received } von { $total } Bytes empfangen
+steamcmd-extract-progress = Entpacke SteamCMD
+steamcmd-extract-failed = SteamCMD konnte nicht entpackt werden
+steamcmd-init-failed = SteamCMD konnte nicht initialisiert werden
+cache-update-progress = Aktualisiere den Server-Cache
+cache-names-progress = Englische Namen lokalisierter Server werden nachgeschlagen
+configuring = Konfiguriere...
+
+## Serververwaltung
+
+prompt-server-name = Bitte gib den Namen des Gameservers ein:
+prompt-server-name-placeholder = z.B. TestServer
+prompt-server-name-help = Das ist der Name des Ordners deines Gameservers.
+prompt-steam-username = Bitte gib deinen Steam-Benutzernamen ein:
+prompt-steam-password = Bitte gib das Passwort deines Steam-Kontos ein.
+prompt-app-id = Bitte gib die Steam App ID des Gameservers ein.
+login-select = Bitte wähle deine Anmeldung
+login-select-help = Welche dieser Anmeldungen möchtest du verwenden?
+login-anonymous = anonym
+login-steam-account = Steam-Konto
+install-welcome = Willkommen zur Installationsanleitung
+install-confirm-name = Möchtest du den Server mit dem Namen { $name } installieren?
+install-confirm-app = Möchtest du den Server für { $app } installieren?
+install-platform-undetected = Die Plattform konnte nicht erkannt werden
+install-checking-license = Prüfe, ob das Konto die App besitzt...
+install-seeding = Kopiere die Dateien von { $server } aus { $path }, SteamCMD lädt nur die Unterschiede herunter...
+install-seed-failed = Die Dateien konnten nicht kopiert werden ({ $status }), alles wird heruntergeladen
+install-seed-running = { $server } läuft und kann seine Dateien während der Kopie ändern, alles wird heruntergeladen
+install-seed-saves = { $count } Spielstand-Datei(en) des anderen Servers wurden nicht übernommen
+steamcmd-probing = Prüfe die Funktionen von SteamCMD...
+steamcmd-probe-failed = Die Funktionen von SteamCMD konnten nicht geprüft werden, eine aktuelle Version wird angenommen: { $error }
+install-not-owned = Das Konto { $username } besitzt die App { $app_id } nicht. Melde dich mit dem Konto an, das das Spiel gekauft hat, über die Steam-Familienbibliothek geteilte Spiele können nicht mit SteamCMD heruntergeladen werden.
+install-success = Der Server wurde erfolgreich installiert.
+install-batch-progress = Installiere { $server } ({ $app_id }) ({ $index }/{ $count })
+install-batch-installed = installiert
+install-batch-failed = { $failed } von { $count } Gameservern konnten nicht installiert werden
+install-batch-server-name = --server-name kann nur bei der Installation eines einzelnen Gameservers verwendet werden, benenne die Server stattdessen in einer Liste mit --from-file
+install-list-empty = Die Liste enthält keine Gameserver zum Installieren
+install-list-read-failed = Die Liste { $path } konnte nicht gelesen werden: { $error }
+install-header-result = ERGEBNIS
+install-retry = SteamCMD ist fehlgeschlagen, neuer Versuch ({ $attempt }/{ $retries })
+install-outage-retry = Steam ist nicht erreichbar, neuer Versuch in { $seconds }s ({ $attempt }/{ $retries })
+install-executable-missing = Das Profil { $profile } startet { $executable }, das es in der Installation nicht gibt.
+install-executable-no-profile = Für dieses Spiel gibt es noch kein Spielprofil, daher weiß steamserv nicht, wie es gestartet wird.
+install-executable-select = Welche ausführbare Datei startet den Server?
+install-executable-select-help = Die wahrscheinlichen Server-Binaries stehen oben, die Auswahl wird als Benutzerprofil gespeichert.
+install-executable-skip = Keine davon, später entscheiden
+install-executable-exists = Das Profil { $path } existiert bereits, füge die ausführbare Datei selbst hinzu:
+install-executable-next = Ergänze die Ports und Startargumente mit `steamserv profile create { $path }` oder indem du das Profil bearbeitest.
+app-not-found = Der Server wurde nicht gefunden
+app-info-unavailable = SteamCMD hat keine App-Informationen für { $app_id } ausgegeben
+app-info-no-branch = Die App-Informationen von { $app_id } enthalten keinen Build für den Branch { $branch }
+update-select = Bitte wähle den Gameserver, der aktualisiert werden soll
+update-select-help = Welchen dieser Gameserver möchtest du aktualisieren?
+update-success = Der Server wurde erfolgreich aktualisiert.
+uninstall-select = Bitte wähle den Gameserver, der deinstalliert werden soll
+uninstall-select-help = Welchen dieser Gameserver möchtest du deinstallieren?
+uninstall-confirm = Bist du sicher, dass du den Server { $server } deinstallieren möchtest?
+uninstall-success = Der Server wurde erfolgreich deinstalliert.
+
+## Liste
+
+list-installed-title = Installierte Server:
+list-available-title = Verfügbare Server:
+list-header-app-id = APP ID
+list-header-name = NAME
+list-header-path = PFAD
+list-header-owner = BESITZER
+list-offline-stale = Warnung: Der Offline-Modus ist aktiv und der Server-Cache wurde zuletzt am { $date } aktualisiert. Die Liste ist möglicherweise veraltet.
+list-new-servers = Neu seit dem letzten Update ({ $count }):
+list-new-servers-notification = { $count } neue dedizierte Server: { $servers }
+
+## Diagnose
+
+doctor-steamcmd = SteamCMD
+doctor-steamcmd-build = SteamCMD-Version
+doctor-steamcmd-version = Version { $version }
+doctor-steamcmd-outdated = veralteter Build ohne -beta oder Workshop-Downloads, aktualisiere ihn, indem du SteamCMD einmal ohne Argumente startest
+doctor-install-path = Installationspfad
+doctor-network = Netzwerk
+doctor-proxy = Proxy
+doctor-http-client = HTTP-Client
+doctor-missing = { $path } existiert nicht
+doctor-install-path-missing = { $path } existiert noch nicht und wird bei der Installation erstellt
+doctor-offline = der Offline-Modus ist aktiv
+doctor-no-proxy = kein Proxy konfiguriert
+doctor-reachable = erreichbar ({ $status })
+doctor-unexpected-status = erreichbar, aber Antwort { $status }
+doctor-failed = { $count } Prüfung(en) fehlgeschlagen
+
+## Instanzen
+
+server-not-installed = Der Server { $server } ist nicht installiert
+instance-added = Instanz { $instance } hinzugefügt.
+instance-removed = Instanz { $instance } entfernt.
+instance-exists = Die Instanz { $instance } existiert bereits
+instance-not-found = Der Server { $server } hat keine Instanz { $instance }
+instance-port-used = Port { $port } wird bereits von { $instance } verwendet
+instance-target-invalid = { $target } ist keine Instanz, verwende die Form server/instanz
+instance-variable-invalid = { $variable } ist keine Variable, verwende die Form SCHLÜSSEL=WERT
+instance-remove-confirm = Bist du sicher, dass du die Instanz { $instance } von { $server } entfernen möchtest? Die Installation bleibt erhalten.
+update-instance-shared = Die Instanz { $instance } teilt sich die Installation von { $server }, { $server } wird aktualisiert.
+update-mods-incompatible = Diese Mods von { $server } sind wahrscheinlich nicht mit Build { $build } vom { $date } kompatibel:
+update-mods-force = Aktualisiere zuerst die Mods oder führe das Update mit --force aus
+update-mods-check-failed = Die Mods konnten nicht mit dem neuen Build abgeglichen werden: { $error }
+update-available = Update für { $server }: Build { $installed } → { $build } vom { $date }
+update-download-size = Geschätzter Download: bis zu { $size }
+update-download-unknown = Die Downloadgröße konnte nicht geschätzt werden.
+update-confirm = Das Update jetzt installieren?
+update-cancelled = Update abgebrochen.
+update-up-to-date = { $server } ist bereits auf dem neuesten Build, die Installation wird überprüft.
+update-check-failed = Es konnte nicht nach einem neuen Build gesucht werden: { $error }
+update-staging-copy = Die Installation von { $server } wird für das vorbereitete Update kopiert...
+update-staging-copy-failed = Die Installation konnte nicht nach { $path } kopiert werden: { $status }
+update-staging-invalid = Das vorbereitete Update von { $server } ist unvollständig oder hat den falschen Build, es wurde verworfen
+update-staged = Das Update von { $server } ist vorbereitet und wird beim nächsten Start oder Neustart angewendet
+update-snapshot-offer = Die Installation liegt auf { $fs }. Vor dem Update einen Snapshot erstellen?
+update-snapshot-offer-help = Zurücksetzen mit `steamserv-rs rollback <server> --snapshot`. Setze `before_update` in der [snapshots]-Konfiguration, um nicht mehr gefragt zu werden.
+update-all-confirm = Jetzt alle { $count } Gameserver aktualisieren?
+update-all-failed = { $count } Update(s) fehlgeschlagen, siehe den Bericht oben
+update-all-deferred = { $count } Update(s) verschoben, weil Steam nicht erreichbar war, führe das Update später erneut aus
+update-report = Update aller Server nach { $time } abgeschlossen: { $updated } aktualisiert, { $current } aktuell, { $failed } fehlgeschlagen, { $skipped } übersprungen, { $deferred } verschoben, geschätzter Download { $size }
+update-report-current = { $server }: aktuell auf Build { $build }
+update-report-updated = { $server }: Build { $from } → { $to }
+update-report-failed = { $server }: fehlgeschlagen, { $reason }
+update-report-skipped = { $server }: übersprungen, { $players } Spieler online
+update-report-deferred = { $server }: verschoben, Steam war nicht erreichbar
+list-header-instance = INSTANZ
+list-header-port = PORT
+list-header-world = WELT
+list-header-config-file = KONFIGURATIONSDATEI
+
+## Spielprofile
+
+profile-not-found = Für die App { $app_id } existiert kein Spielprofil
+
+## Tokens
+
+token-stored = Login-Token für { $target } gespeichert.
+token-created = Login-Token { $token } für { $target } erstellt (Gameserver-Konto { $steamid }).
+token-not-source = Warnung: { $server } ist kein Source-Server, der Token wird nicht zum Startbefehl hinzugefügt.
+list-header-server = SERVER
+list-header-token = TOKEN
+
+## Workshop-Mods
+
+action-download-mods = Workshop-Mods herunterladen
+mods-installed = Workshop-Inhalt { $id } installiert ({ $links } Verknüpfung(en) erstellt).
+mods-removed = Workshop-Inhalt { $id } entfernt.
+mods-not-installed = Workshop-Inhalt { $id } ist nicht installiert.
+mods-none = Der Server { $server } hat keine Workshop-Mods.
+mods-unsupported = Das Spielprofil von { $server } unterstützt keine Workshop-Inhalte
+mods-download-failed = Die Workshop-Inhalte konnten nicht heruntergeladen werden
+mods-steamcmd-unsupported = Dieses SteamCMD kann keine Workshop-Inhalte herunterladen, aktualisiere es, indem du es einmal ohne Argumente startest
+mods-link-exists = Workshop-Inhalt konnte nicht verknüpft werden, { $path } existiert bereits und ist keine Verknüpfung
+mods-older-than-build = seit { $date } nicht aktualisiert, vor dem neuen Build
+mods-older-version = gibt die Spielversion { $version } an, andere Mods bereits { $newest }
+list-header-workshop-id = WORKSHOP-ID
+list-header-updated = AKTUALISIERT
+list-header-links = LINKS
+
+## Processes
+
+process-started = { $target } wurde mit PID { $pid } gestartet, die Ausgabe wird nach { $log } geschrieben.
+process-stopped = { $target } wurde gestoppt.
+process-not-running = { $target } läuft nicht.
+process-already-running = { $target } läuft bereits mit PID { $pid }
+stop-rcon-command = Sende RCON-Befehl: { $command }
+stop-rcon-failed = Die Stopp-Befehle konnten nicht gesendet werden: { $error }
+stop-signal = Sende Signal { $signal }
+stop-waiting = Warte bis zu { $seconds } Sekunden, bis { $target } heruntergefahren ist...
+stop-fallback = Der Server wurde nicht rechtzeitig heruntergefahren, sende Signal { $signal }
+stop-failed = { $target } (PID { $pid }) wurde nicht beendet
+signal-unknown = Unbekanntes Signal { $signal }
+status-header-state = STATUS
+status-header-pid = PID
+status-running = läuft
+status-stopped = gestoppt
+status-public-address = Öffentliche Adresse: { $hostname } ({ $ip }, vor { $age } aktualisiert)
+status-public-hostname = Öffentliche Adresse: { $hostname }
+status-maintenance = Wartung
+status-placeholder = Platzhalter
+
+## RCON
+
+rcon-stored = RCON-Einstellungen für { $target } gespeichert.
+rcon-not-configured = Keine RCON-Einstellungen für { $target }, verwende `steamserv rcon set`
+rcon-address-invalid = Der RCON-Host { $host } konnte nicht aufgelöst werden
+rcon-auth-failed = Das RCON-Passwort wurde abgelehnt
+rcon-packet-invalid = Ungültiges RCON-Paket empfangen
+rcon-broadcast-sent = Nachricht an die Spieler von { $target } gesendet.
+broadcast-no-command = Das Spiel von { $target } hat keinen Broadcast-Befehl, setze `command` in der Ankündigung
+
+## Readiness checks
+
+readiness-executable = Programmdatei
+readiness-not-executable = { $path } ist nicht ausführbar
+readiness-compat-tool = Wine/Proton
+readiness-compat-tool-missing = { $tool } wurde nicht gefunden, installiere Wine oder setze `compat_tool` in der Konfiguration
+readiness-port = Port { $port }
+readiness-port-free = frei
+readiness-port-used = bereits belegt
+readiness-config-file = Konfigurationsdatei
+readiness-failed = { $target } ist nicht startbereit
+readiness-ready = { $target } ist startbereit.
+
+## Supervisor
+
+daemon-started = Supervisor gestartet, die laufenden Server werden überwacht.
+daemon-check-error = { $target } konnte nicht geprüft werden: { $error }
+daemon-check-failed = Zustandsprüfung von { $target } fehlgeschlagen ({ $failures }/{ $max }): { $error }
+daemon-server-exited = { $target } wurde unerwartet beendet (PID { $pid })
+daemon-restarting = { $target } wird neu gestartet...
+daemon-restart-blackout = { $target } wird während der Sperrzeit { $blackout } nicht neu gestartet
+daemon-restarted = { $target } wurde mit PID { $pid } neu gestartet.
+daemon-notification = steamserv: { $target } ist nicht funktionsfähig: { $error }
+daemon-notified = Benachrichtigung gesendet.
+daemon-recovery-failed = Wiederherstellung von { $target } fehlgeschlagen: { $error }
+daemon-hook-run = Hook { $script } ausgeführt.
+daemon-hook-failed = Hook { $script } ist mit { $status } fehlgeschlagen
+daemon-announced = Ankündigung an { $target } gesendet: { $message }
+daemon-announce-failed = Ankündigung an { $target } fehlgeschlagen: { $error }
+daemon-log-rotated = Log { $path } rotiert
+daemon-log-error = Rotation der Logs fehlgeschlagen: { $error }
+daemon-maps-rotated = { $target } auf die Karte { $map } gewechselt
+daemon-maps-failed = Kartenwechsel von { $target } fehlgeschlagen: { $error }
+daemon-alert-invalid = Das Alarmmuster { $pattern } ist kein gültiger regulärer Ausdruck: { $error }
+daemon-alert-error = Die Log-Alarme von { $target } konnten nicht geprüft werden: { $error }
+daemon-alert-failed = Alarmaktion für { $target } fehlgeschlagen: { $error }
+daemon-bundle-created = Support-Paket von { $target } unter { $path } gespeichert
+daemon-bundle-failed = Das Support-Paket von { $target } konnte nicht geschrieben werden: { $error }
+daemon-memory-invalid = Das Speicherlimit { $limit } ist keine gültige Größe
+daemon-memory-exceeded = Der Speicher lag { $minutes } Minuten über { $limit }
+daemon-memory-deferred = { $target } liegt über dem Speicherlimit, der Neustart wartet bis zu { $minutes } Minuten, bis { $players } Spieler gegangen sind
+daemon-memory-scheduled = { $target } liegt über dem Speicherlimit und startet in { $minutes } Minuten neu
+daemon-memory-warning = Der Server startet in { $minutes } Minuten neu
+daemon-paused-all = Automatisierung für alle Server pausiert, der Supervisor sammelt bis `steamserv daemon resume` nur Metriken.
+daemon-paused-server = Automatisierung für { $server } pausiert.
+daemon-resumed-all = Automatisierung fortgesetzt.
+daemon-resumed-server = Automatisierung für { $server } fortgesetzt.
+daemon-not-paused = Die Automatisierung ist nicht pausiert.
+daemon-not-paused-server = Die Automatisierung von { $server } ist nicht pausiert.
+daemon-still-paused = Die Automatisierung aller Server ist weiterhin pausiert.
+daemon-server-paused = { $server } bleibt pausiert.
+schedule-invalid = Ungültiger Zeitplan { $schedule } von { $target }, erwartet werden fünf Felder wie `*/30 * * * *`
+probe-no-port = Für die Prüfung ist kein Port konfiguriert
+probe-a2s-failed = A2S-Abfrage auf Port { $port } fehlgeschlagen: { $error }
+probe-tcp-failed = TCP-Verbindung zu Port { $port } fehlgeschlagen: { $error }
+probe-http-failed = HTTP-Prüfung von { $url } fehlgeschlagen: { $error }
+probe-log-matched = Log-Muster gefunden: { $line }
+query-address-invalid = Der Host { $host } konnte nicht aufgelöst werden
+query-invalid-response = Der Server hat eine ungültige Antwort auf die Abfrage gesendet
+history-empty = Keine Ereignisse aufgezeichnet.
+history-header-time = ZEIT
+history-header-event = EREIGNIS
+history-header-message = MELDUNG
+
+## Metrics
+
+daemon-sample-error = Die Messwerte von { $target } konnten nicht erfasst werden: { $error }
+period-invalid = { $period } ist kein gültiger Zeitraum, verwende z. B. 30m, 24h oder 7d
+stats-empty = In den letzten { $period } wurden keine Messwerte von { $target } erfasst, läuft `steamserv daemon run`?
+stats-header = Messwerte von { $target } in den letzten { $period } ({ $count } Messungen)
+stats-players = Spieler
+stats-fps = FPS
+stats-summary = Ø { $average }, max. { $max }
+stats-summary-limit = Ø { $average }, max. { $max } von { $limit }
+stats-range = { $start } – { $end }
+
+## Config backups
+
+config-backups-empty = Noch keine Konfigurationssicherungen, sie werden bei jeder Änderung der Konfiguration angelegt
+config-backup-not-found = Es gibt keine Konfigurationssicherung namens { $backup }, siehe `steamserv config backups`
+config-restore-select = Wähle die wiederherzustellende Sicherung
+config-restore-select-help = Die neuesten Sicherungen stehen oben, die aktuelle Konfiguration wird vor dem Wiederherstellen gesichert
+config-restored = Konfiguration aus { $backup } wiederhergestellt.
+
+## Secrets
+
+secrets-passphrase = Passphrase für die Geheimnisse in der Konfiguration:
+secrets-passphrase-help = Setze STEAMSERV_PASSPHRASE, um die Passphrase ohne Abfrage zu verwenden, z. B. für den Supervisor
+secrets-encrypt-failed = Ein Geheimnis der Konfiguration konnte nicht verschlüsselt werden
+secrets-decrypt-failed = Ein Geheimnis der Konfiguration konnte nicht entschlüsselt werden, sind Schlüssel oder Passphrase korrekt?
+secrets-value-invalid = Die Konfiguration enthält einen ungültigen verschlüsselten Wert
+secrets-salt-missing = Die Einstellungen der Geheimnisse enthalten kein Salt, führe `steamserv secrets enable --passphrase` erneut aus
+secrets-keyring-invalid = Der Schlüssel im Schlüsselbund des Systems ist ungültig
+secrets-keyring-unavailable = Der Schlüsselbund des Systems ist nicht verfügbar ({ $error }), verwende stattdessen `steamserv secrets enable --passphrase`
+secrets-enabled = { $count } Geheimnisse in der Konfiguration verschlüsselt.
+secrets-enabled-backups = Ältere Konfigurationssicherungen können die Geheimnisse noch im Klartext enthalten, entferne sie bei Bedarf aus dem Verzeichnis config-backups.
+secrets-disabled = Die Geheimnisse werden wieder im Klartext gespeichert.
+secrets-key-keyring = Schlüsselbund des Systems
+secrets-key-passphrase = Passphrase
+secrets-status-enabled = Geheimnisse werden mit einem Schlüssel aus: { $key } verschlüsselt.
+secrets-status-disabled = Geheimnisse werden im Klartext gespeichert.
+secrets-status-count = { $encrypted } von { $total } Geheimnissen in der Konfiguration sind verschlüsselt.
+secrets-value = Wert des Geheimnisses { $name }:
+secrets-name-invalid = { $name } ist kein gültiger Name für ein Geheimnis, verwende Buchstaben, Ziffern, _ und -
+secrets-stored = Geheimnis { $name } gespeichert, verweise mit { $reference } in Umgebungsvariablen darauf.
+secrets-stored-plain = Die Geheimnisse werden als Klartext gespeichert, führe `steamserv secrets enable` aus, um sie zu verschlüsseln.
+secrets-removed = Geheimnis { $name } entfernt.
+secrets-unknown = Das Geheimnis { $name } existiert nicht, speichere es mit `steamserv secrets set { $name }`
+secrets-in-args = Das Startargument { $arg } verweist auf ein Geheimnis, Startargumente können andere Benutzer lesen. Übergib das Geheimnis stattdessen in einer Umgebungsvariable, z.B. mit `steamserv env set`.
+secrets-none = Es sind keine benannten Geheimnisse gespeichert.
+
+## Timeouts
+
+timeout-expired = { $operation } wurde nicht innerhalb von { $seconds } Sekunden beendet und abgebrochen, siehe `[timeouts]` in der Konfiguration
+
+## Maintenance
+
+maintenance-on = { $server } ist in Wartung, der Supervisor lässt den Server bis `steamserv maintenance off { $server }` in Ruhe.
+maintenance-off = Die Wartung von { $server } ist beendet.
+maintenance-active = { $server } ist in Wartung ({ $reason }), beende sie mit `steamserv maintenance off { $server }`
+maintenance-already-on = { $server } ist bereits in Wartung
+maintenance-not-on = { $server } ist nicht in Wartung
+maintenance-placeholder-unsupported = Das Spielprofil von { $server } hat keine Wartungsargumente für einen Platzhalter
+maintenance-placeholder-started = Wartungsplatzhalter mit PID { $pid } gestartet.
+
+## New server wizard
+
+new-welcome = Richten wir einen neuen Gameserver ein.
+new-cache-empty = Der Server-Cache ist leer, führe zuerst online `steamserv list` aus
+new-select-app = Wähle den Gameserver:
+new-select-app-help = Tippen zum Suchen, mit den Pfeiltasten auswählen
+new-server-exists = Ein Server namens { $server } ist bereits installiert
+new-install-root = Installationsverzeichnis:
+new-branch = Branch:
+new-branch-help = Leer lassen für den öffentlichen Branch
+new-no-profile = Für { $app } gibt es kein Spielprofil, daher werden die Starteinstellungen und der Start übersprungen.
+new-port = Spielport:
+new-world = Weltname:
+new-config-file = Konfigurationsdatei:
+new-variable = Wert von { $variable }:
+new-variable-help = Leer lassen, um das Startargument wegzulassen
+new-mods = Workshop-Mods:
+new-mods-help = Workshop-IDs durch Kommas getrennt, leer lassen für keine
+new-mod-id-invalid = { $id } ist keine gültige Workshop-ID
+new-start = Den Server nach der Installation starten?
+new-summary-app = App-ID:            { $app_id }
+new-summary-path = Installationspfad: { $path }
+new-summary-branch = Branch:            { $branch }
+new-summary-login = Anmeldung:         { $user }
+new-summary-instance = Instanz:           { $target } auf Port { $port }
+new-summary-mods = Mods:              { $mods }
+new-summary-start-yes = Starten:           ja
+new-summary-start-no = Starten:           nein
+new-confirm = Den Server mit diesen Einstellungen einrichten?
+new-cancelled = Abgebrochen, es wurde nichts installiert.
+
+## Templates
+
+template-not-found = Es gibt keine Vorlage namens { $template }
+template-no-port = Ab Port { $port } wurde kein freier Portbereich gefunden
+template-provisioning = Richte { $server } ein ({ $index }/{ $count })
+template-saved = Die Einstellungen wurden als Vorlage { $template } gespeichert
+
+## Answer files
+
+answers-read-failed = Die Antwortdatei { $path } konnte nicht gelesen werden: { $error }
+answers-saved = Die Antworten wurden in { $path } gespeichert
+
+## Privileges
+
+privilege-create-dir = { $path } erstellen und dir als Besitzer zuweisen
+privilege-needed = { $count ->
+    [one] Ein Schritt benötigt Root-Rechte:
+   *[other] { $count } Schritte benötigen Root-Rechte:
+}
+privilege-required = Führe die Befehle oben selbst aus oder wiederhole den Befehl mit --sudo, um nur diese Schritte über sudo auszuführen
+privilege-sudo-failed = sudo konnte nicht ausgeführt werden: { $error }
+privilege-step-failed = Der Schritt `{ $command }` ist fehlgeschlagen
+privilege-append-hosts = { $count } Steam-CDN-Hostname(n) auf den Lancache zeigen lassen
+doctor-privileges = Rechte
+doctor-privileges-root = Läuft als Root, ein normaler Benutzer mit --sudo reicht aus
+doctor-host = Host-Architektur
+doctor-host-emulator = { $arch }, x86-Programme laufen mit { $emulator }
+doctor-host-emulator-unused = { $arch }, { $emulator } ist installiert, wird aber nicht verwendet, setze `emulator = "{ $emulator }"` in der Konfiguration
+host-binfmt = { $name } ist bei binfmt_misc registriert, x86-Programme laufen ohne Wrapper
+host-emulator-missing = kein Emulator für x86-Programme gefunden, installiere FEX-Emu (führt SteamCMD und die Server aus) oder Box86 für SteamCMD und Box64 für die Server
+remote-not-found = Unbekannter Remote-Host { $host }, füge ihn unter [remotes.{ $host }] in der Konfiguration hinzu
+remote-ssh-failed = ssh für { $host } konnte nicht ausgeführt werden: { $error }
+remote-rsync-failed = Die Dateien konnten nicht mit rsync nach { $host } kopiert werden: { $error }
+remote-management-only = Gameserver laufen nur unter Linux, unter { $os } verwaltet steamserv Remote-Hosts: füge einen unter [remotes.<name>] in der Konfiguration hinzu und nutze --remote <name> oder default_remote
+doctor-privileges-user = Der Installationspfad ist ohne Root beschreibbar
+doctor-privileges-sudo = Der Installationspfad benötigt Root, führe `{ $command }` aus oder nutze --sudo
+
+## Permissions
+
+perms-normalized = Die Rechte von { $count } Dateien und Verzeichnissen wurden angepasst
+perms-user-not-found = Der Dienstbenutzer { $user } existiert nicht
+perms-step-modes = Die Schreibrechte für alle in { $path } entfernen
+perms-step-owner = { $path } an { $user } übergeben
+
+## Clean
+
+clean-nothing = Es gibt keine Download-Caches zum Löschen.
+clean-total = Freigebbar: { $size }
+clean-confirm = Diese Download-Caches löschen?
+clean-confirm-help = Nicht löschen, während eine Installation oder ein Update läuft
+clean-cancelled = Abgebrochen, es wurde nichts gelöscht.
+clean-done = { $size } an Download-Caches wurden gelöscht.
+
+## Disk space
+
+disk-quota-warning = Warnung: Die Server in { $root } belegen { $used } des Kontingents von { $quota } ({ $percent } %)
+disk-free-warning = Warnung: Im Dateisystem von { $root } sind nur noch { $free } ({ $percent } %) frei
+disk-quota-invalid = Das Kontingent { $quota } von { $root } ist keine gültige Größe
+daemon-disk-error = Prüfung des Speicherplatzes fehlgeschlagen: { $error }
+daemon-dns-updated = { $hostname } zeigt jetzt auf { $ip }
+daemon-dns-error = Der dynamische DNS-Eintrag konnte nicht aktualisiert werden: { $error }
+daemon-job-started = Job #{ $id } gestartet: { $command }
+daemon-job-error = Die Jobs in der Warteschlange konnten nicht gestartet werden: { $error }
+
+## Aliases
+
+alias-unknown = { $alias } ist weder eine Steam App ID noch ein bekannter Alias, siehe `steamserv aliases`
+
+## Top
+
+top-title = steamserv top – { $time }, { $running } laufen, Aktualisierung alle { $interval }s, Strg+C zum Beenden
+top-header-players = SPIELER
+top-header-cpu = CPU
+top-header-memory = SPEICHER
+
+## Players
+
+players-list-bans = Bannliste
+players-list-whitelist = Whitelist
+players-list-admins = Adminliste
+players-not-supported = Das Spiel von { $target } hat keine { $list }, die steamserv bearbeiten kann
+players-file-unknown = Die { $list }-Datei { $file } von { $target } benötigt einen Weltnamen oder eine Variable, die nicht gesetzt ist
+players-rcon-only = Die { $list } von { $target } kann nur über RCON geändert werden, während der Server läuft
+players-list-unavailable = Die { $list } von { $target } wird nur vom Server geführt und kann nicht aufgelistet werden
+players-added = { $id } wurde zur { $list } von { $target } hinzugefügt.
+players-removed = { $id } wurde aus der { $list } von { $target } entfernt.
+players-unchanged = Die { $list } von { $target } ist für { $id } bereits aktuell.
+players-file-changed = { $file } aktualisiert
+players-reloaded = Über RCON auf den laufenden Server angewendet
+players-failed = Die Liste von { $target } konnte nicht geändert werden: { $error }
+players-synced = { $count } Banns der zentralen Bannliste angewendet.
+players-not-running = { $target } läuft nicht
+players-online-none = Mit { $target } sind keine Spieler verbunden.
+players-header-name = NAME
+players-header-duration = VERBUNDEN
+players-header-ping = PING
+admins-title = Admins von { $target }:
+admins-empty = { $target } hat keine Admins.
+steamid-invalid = { $id } ist weder eine SteamID noch eine Steam-Profil-URL
+steamid-not-found = Kein Steam-Profil verwendet die benutzerdefinierte URL { $name }
+steamid-profile = Profil
+steamid-name = Name
+steamid-custom-url = Eigene URL
+
+## Check updates
+
+check-updates-header-installed = INSTALLIERT
+check-updates-header-latest = NEUESTE
+check-updates-header-published = VERÖFFENTLICHT
+check-updates-header-download = DOWNLOAD
+check-updates-up-to-date = aktuell
+check-updates-unknown = unbekannt
+check-updates-failed = { $server } konnte nicht auf Updates geprüft werden: { $error }
+check-updates-total = { $count } Update(s) ausstehend, geschätzter Download bis zu { $size }.
+
+## Events
+
+event-install-started = { $server } wird installiert (App-ID { $app_id })...
+event-update-started = { $server } wird aktualisiert...
+event-update-applied = Das vorbereitete Update von { $server } wurde angewendet
+event-server-crashed = { $target } hat zu viele Health-Checks in Folge nicht bestanden: { $error }
+event-recovery-finished = Wiederherstellung von { $target }: { $message }
+event-log-alert = Alarm { $alert } bei { $target }: { $line }
+event-phase-started = » { $phase }...
+event-phase-finished = ✔ { $phase } ({ $seconds } s)
+event-webhook-failed = Das Ereignis konnte nicht an den Webhook gesendet werden: { $error }
+event-sink-failed = Ein Ereignis konnte nicht verarbeitet werden: { $error }
+
+## Jobs
+job-submitted = Job #{ $id } eingereiht: { $command }
+job-daemon-hint = Der Daemon (`steamserv-rs daemon run`) startet ihn im Hintergrund, verfolge ihn mit `steamserv-rs jobs attach`.
+job-not-found = Job #{ $id } existiert nicht
+job-not-active = Job #{ $id } kann nicht abgebrochen werden, er ist bereits { $state }
+job-not-running = Job #{ $id } wurde nicht vom Daemon gestartet
+job-cancelled = Job #{ $id } abgebrochen
+job-dependency-failed = Abgebrochen, weil Job #{ $id } nicht erfolgreich war
+downloads-invalid-hours = Ungültige Download-Zeit { $window }, erwartet wird ein Zeitfenster wie `01:00-07:00`
+network-ca-unreadable = CA-Zertifikat { $path } konnte nicht gelesen werden: { $error }
+job-plan-title = Ausführungsplan:
+job-plan-confirm = { $count } Updates in dieser Reihenfolge einreihen?
+job-plan-cancelled = Es wurden keine Jobs eingereiht
+job-plan-empty = Keine Gameserver zum Aktualisieren
+job-plan-cycle = Die Update-Abhängigkeiten von { $servers } bilden einen Zyklus
+job-plan-unknown-dependency = { $server } soll nach { $dependency } aktualisiert werden, der nicht installiert ist
+job-finished = Job #{ $id } { $state }
+job-attach-hint = Drücke Strg+C zum Trennen, der Job läuft weiter.
+job-attach-skipped = Frühere Ausgabe übersprungen, die vollständige Ausgabe steht in { $log }
+job-progress-current = { $phase }: { $percent } % ({ $current } / { $total })
+job-state-queued = eingereiht
+job-state-running = läuft
+job-state-succeeded = erfolgreich
+job-state-failed = fehlgeschlagen
+job-state-cancelled = abgebrochen
+jobs-empty = Keine Jobs
+jobs-header-id = ID
+jobs-header-state = STATUS
+jobs-header-created = ERSTELLT
+jobs-header-duration = DAUER
+jobs-header-command = BEFEHL
+jobs-header-step = SCHRITT
+jobs-header-priority = PRIORITÄT
+jobs-header-after = NACH
+job-status-id = ID
+job-status-command = Befehl
+job-status-state = Status
+job-status-created = Erstellt
+job-status-started = Gestartet
+job-status-finished = Beendet
+job-status-exit-code = Exit-Code
+job-status-reason = Grund
+job-status-priority = Priorität
+job-status-after = Nach
+job-status-log = Log
+job-status-progress = Fortschritt
+
+## Snapshots
+snapshot-unsupported = { $path } liegt auf { $fs }, Snapshots benötigen btrfs oder ZFS
+snapshot-btrfs-no-subvolume = { $path } ist kein btrfs-Subvolume, erstelle die Installation mit `btrfs subvolume create`, um Snapshots zu erstellen
+snapshot-zfs-no-dataset = { $path } ist kein ZFS-Dataset, erstelle eines für die Installation mit `zfs create`, um Snapshots zu erstellen
+snapshot-not-found = Snapshot { $name } existiert nicht
+snapshot-command-failed = `{ $command }` ist fehlgeschlagen: { $error }
+snapshot-reason-create = Snapshot von { $path } erstellen
+snapshot-reason-delete = Snapshot { $name } löschen
+snapshot-reason-rollback = Auf Snapshot { $name } zurücksetzen
+snapshot-created = Snapshot { $name } erstellt
+snapshot-deleted = Snapshot { $name } gelöscht
+snapshots-empty = { $server } hat keine Snapshots
+rollback-confirm = { $server } auf { $name } zurücksetzen?
+rollback-confirm-help = Alle Änderungen seit dem Snapshot gehen verloren, auch Spielstände. Der Server wird dafür gestoppt.
+rollback-cancelled = Zurücksetzen abgebrochen
+rollback-success = { $server } wurde auf { $name } zurückgesetzt
+
+## Import
+lgsm-not-found = { $path } ist keine LinuxGSM-Installation, lgsm/config-lgsm fehlt
+lgsm-games = { $path } sollte die Konfiguration genau eines Spiels enthalten, gefunden: { $count }
+lgsm-no-instances = { $path } enthält keine Instanz-Konfigurationen
+lgsm-no-app-id = In den Konfigurationen in { $path } ist keine appid gesetzt
+import-success = { $server } (App-ID { $app_id }) aus { $path } mit { $count } Instanz(en) importiert:
+import-lgsm-hint = Stoppe die LinuxGSM-Server und entferne ihre Cron-Jobs, bevor du den Server mit steamserv startest. { $path } bleibt unverändert.
+import-path-missing = Das Installationsverzeichnis { $path } existiert auf diesem Host nicht
+
+## Export
+export-written = Export nach { $path } geschrieben
+export-no-ready-line = Warnung: Das Spielprofil von App { $app_id } hat keine ready_line, lege die Starterkennung des Eggs im Panel fest
+export-egg-comment = Von steamserv aus { $server } exportiert
+export-egg-description = Dedizierter Server für { $name } (App-ID { $app_id }), installiert mit SteamCMD
+export-var-description = Wert von { $variable } im Startbefehl
+export-var-name = Servername
+export-var-world = Welt
+export-var-config-file = Konfigurationsdatei
+export-var-gslt = Game Server Login Token
+export-var-gslt-description = Das Token für den Server, erstelle eines auf https://steamcommunity.com/dev/managegameservers
+export-var-app-id = Steam-App-ID
+export-var-app-id-description = Die mit SteamCMD installierte App
+export-var-branch = Beta-Branch
+export-var-branch-description = Der zu installierende Beta-Branch, leer für die öffentliche Version
+export-var-steam-user = Steam-Benutzername
+export-var-steam-pass = Steam-Passwort
+export-var-steam-account-description = Die App kann nicht anonym heruntergeladen werden, das Konto muss sie besitzen
+
+## Lint
+lint-target = { $target } ({ $file }):
+lint-target-no-schema = { $target } (das Spielprofil beschreibt keine Konfigurationsdatei):
+lint-no-findings = Keine Probleme gefunden
+lint-no-config-file = Keine Konfigurationsdatei gesetzt, setze eine mit `steamserv instance`, um sie zu prüfen
+lint-key-missing = { $key } fehlt oder ist leer
+lint-port-mismatch = { $key } ist { $value }, aber steamserv verwendet Port { $port }
+lint-rcon-disabled = { $key } ist leer, RCON ist deaktiviert, obwohl steamserv RCON-Einstellungen hat
+lint-rcon-password-mismatch = { $key } unterscheidet sich vom in steamserv gespeicherten RCON-Passwort
+lint-password-common = Das RCON-Passwort ist ein gängiger Standardwert, jeder kann den Server übernehmen
+lint-password-short = Das RCON-Passwort ist kürzer als { $length } Zeichen
+lint-failed = Die Konfiguration von { $target } hat Fehler
+
+## NAT check
+nat-target = { $target }:
+nat-local-ip = Lokale Adresse
+nat-public-ip = Öffentliche Adresse
+nat-gateway = UPnP-Gateway
+nat-no-gateway = nicht gefunden
+nat-unknown = unbekannt
+nat-reachable = aus dem Internet erreichbar
+nat-forwarded = vom Router an diesen Host weitergeleitet, UDP kann nicht von außen geprüft werden
+nat-direct = dieser Host hat eine öffentliche Adresse, UDP kann nicht von außen geprüft werden
+nat-not-listening = niemand lauscht auf dem Port, starte den Server, um ihn zu prüfen
+nat-not-forwarded = der Router leitet den Port nicht an diesen Host weiter, richte eine Weiterleitung ein oder nutze --upnp
+nat-double-nat = der Router hat keine öffentliche Adresse (doppeltes NAT oder Carrier-Grade-NAT), eine Weiterleitung hilft nicht, frage deinen Anbieter nach einer öffentlichen Adresse
+nat-firewall = Verbindungen werden blockiert, prüfe die Firewall dieses Hosts und deines Anbieters
+nat-unknown-cause = konnte nicht bestimmt werden, es wurde kein UPnP-Gateway zur Prüfung der Weiterleitung gefunden
+nat-upnp-no-gateway = Kein UPnP-Gateway gefunden, aktiviere UPnP am Router oder richte die Weiterleitungen manuell ein
+nat-upnp-invalid-response = Das UPnP-Gateway hat eine ungültige Antwort gesendet
+nat-upnp-failed = Das UPnP-Gateway hat { $action } abgelehnt: { $error }
+nat-upnp-mapped = { $port }/{ $protocol } an { $host } weitergeleitet
+
+## Dynamic DNS
+dns-no-public-ip = Die öffentliche Adresse konnte nicht ermittelt werden
+dns-update-rejected = { $provider } hat die Aktualisierung abgelehnt, prüfe das Token und den Hostnamen
+dns-cloudflare-failed = Cloudflare hat die Aktualisierung abgelehnt: { $error }
+
+## Owners
+owner-set = { $server } gehört jetzt { $owner }.
+owner-unset = { $server } hat keinen Besitzer mehr.
+owner-header-owner = BESITZER
+owner-header-servers = SERVER
+
+## Time zones
+timezone-set = Die Zeitpläne von { $server } laufen jetzt in { $timezone }.
+timezone-unset = Die Zeitpläne von { $server } laufen wieder in der Zeitzone des Systems.
+timezone-invalid = Unbekannte Zeitzone { $timezone } von { $server }, erwartet wird ein IANA-Name wie `America/New_York`
+
+## Environment and arguments
+env-stored = Umgebungsvariablen für { $target } gespeichert, sie werden beim nächsten Start verwendet.
+env-removed = Umgebungsvariablen von { $target } entfernt, die Änderung wird beim nächsten Start verwendet.
+env-not-set = { $key } ist für { $target } nicht gesetzt
+env-none = { $target } hat keine Umgebungsvariablen.
+env-inherited = Geerbt von { $server }:
+args-stored = Zusätzliche Startargumente von { $target }: { $args }
+args-cleared = Die zusätzlichen Startargumente von { $target } wurden entfernt.
+
+## Reports
+report-month-invalid = { $month } ist kein gültiger Monat, verwende die Form JJJJ-MM
+report-uptime-title = Verfügbarkeit im { $month }
+report-header-uptime = LAUFZEIT
+report-header-availability = VERFÜGBARKEIT
+report-header-starts = STARTS
+report-header-crashes = ABSTÜRZE
+
+## Profiles
+profile-test-dir-missing = Das Installationsverzeichnis { $path } existiert nicht
+profile-test-installing = { $name } wird für den Test in { $path } installiert...
+profile-test-kept = Die Testinstallation wurde in { $path } behalten
+profile-test-launching = Starte { $command }
+profile-test-running = Prozess
+profile-test-exited = vorzeitig beendet mit { $status }
+profile-test-still-running = läuft noch
+profile-test-ready-line = Bereit-Zeile
+profile-test-after = nach { $seconds }s
+profile-test-not-seen = "{ $line }" wurde nicht ausgegeben
+profile-test-query = Serverabfrage { $port }
+profile-test-no-answer = keine Antwort
+profile-test-stop = Stopp
+profile-test-killed = wurde nicht innerhalb von { $timeout }s beendet und abgeschossen
+profile-test-log = Die Ausgabe des Servers liegt in { $path }
+profile-test-failed = Das Profil von App { $app_id } hat den Test nicht bestanden
+profile-test-passed = Das Profil von App { $app_id } funktioniert.
+profile-header-name = NAME
+profile-header-source = QUELLE
+profile-source-builtin = eingebaut
+profile-source-override = eingebaut, geändert durch { $path }
+profile-show-source = Quelle: { $source }
+profile-skipped = Übersprungen: { $error }
+profile-file-invalid = { $path } ist keine gültige Profildatei: { $error }
+profile-app-id-missing = Ein Profil in { $path } hat keine app_id
+profile-entry-invalid = Das Profil von App { $app_id } in { $path } ist ungültig: { $error }
+profile-sync-no-repository = Kein Community-Profil-Repository eingerichtet, setze `url` im Abschnitt [profile_repository] der Konfiguration
+profile-sync-pin-missing = Die Repository-URL enthält {"{"}version{"}"}, setze die Version in `pin`
+profile-sync-pin-mismatch = Das Repository ist auf { $pin } festgelegt, der Index hat aber Version { $version }
+profile-sync-pin-unknown = Das Repository hat keinen Tag oder Commit { $pin }
+profile-sync-git-option = Die Repository-URL oder Version { $value } beginnt mit - und würde als Option von git gelesen
+profile-sync-index-invalid = Der Profil-Index { $url } ist ungültig: { $error }
+profile-sync-https = { $url } wird ohne HTTPS nicht heruntergeladen
+profile-sync-checksum = Die Prüfsumme von { $file } stimmt nicht: { $error }
+profile-sync-no-signers = Setze `signers` auf eine Allowed-Signers-Datei, um den Index zu prüfen, oder `verify = false`
+profile-sync-unknown-signer = Der Index ist von keinem erlaubten Unterzeichner signiert
+profile-sync-signature-invalid = Die Signatur des Index ist ungültig: { $error }
+profile-sync-unsigned = Revision { $revision } hat keine gültige Signatur: { $error }
+profile-sync-git-missing = git konnte nicht ausgeführt werden: { $error }
+profile-sync-git-failed = git { $command } ist fehlgeschlagen: { $error }
+profile-sync-file-name = Die Repository-Datei { $file } ist keine Profildatei
+profile-sync-invalid = Die Community-Profile wurden nicht synchronisiert: { $error }
+profile-sync-done = { $count } Profile in { $files } Dateien von { $url } auf Stand { $revision } synchronisiert
+profile-sync-not-verified = Die Signatur wurde nicht geprüft, weil `verify` aus ist
+profile-create-app-id = Steam App ID des Gameservers:
+profile-create-exists = App { $app_id } hat schon ein Profil, das neue ändert es.
+profile-create-name = Name des Spiels:
+profile-create-executable = Server-Programm:
+profile-create-executable-help = Ausführbare Dateien im Installationsverzeichnis
+profile-create-executable-other = Anderer Pfad
+profile-create-executable-path = Pfad des Server-Programms, relativ zum Installationsverzeichnis:
+profile-create-no-executables = Im Installationsverzeichnis wurden keine ausführbaren Dateien gefunden.
+profile-create-extra-ports = Weitere Ports des Servers (kommagetrennt):
+profile-create-extra-ports-help = Ports zum obigen Spielport, z. B. 2457,2458, leer wenn es keine gibt
+profile-create-query-port = Port der Serverabfragen:
+profile-create-query-port-help = Der Port, der auf Steam-Serverabfragen (A2S) antwortet
+profile-create-port-invalid = { $port } ist kein Port ab dem Spielport { $game_port }
+profile-create-args = Startparameter:
+profile-create-args-help = Platzhalter: {"{"}port{"}"}, {"{"}name{"}"}, {"{"}world{"}"}, {"{"}config_file{"}"}, {"{"}install_dir{"}"}, {"{"}var:KEY{"}"}
+profile-create-saves = Spielstände (kommagetrennte Muster):
+profile-create-saves-help = Relativ zum Installationsverzeichnis, {"{"}home{"}"} ist das Home-Verzeichnis, z. B. {"{"}home{"}"}/.config/Game/worlds/*
+profile-create-saves-found = { $pattern } passt derzeit auf { $count } Dateien
+profile-create-stop-signal = Stoppsignal:
+profile-create-stop-signal-help = Das Signal, bei dem der Server speichert und sich beendet
+profile-create-stop-commands = RCON-Befehle vor dem Signal (durch ; getrennt):
+profile-create-stop-commands-help = z. B. save; quit, leer wenn der Server kein RCON hat
+profile-create-stop-timeout = Sekunden, die auf das Ende des Servers gewartet wird:
+profile-create-ready-line = Zeile, die der Server ausgibt, sobald Spieler beitreten können:
+profile-create-ready-line-help = Leer, wenn unbekannt
+profile-create-save = Profil in deinen Benutzerprofilen speichern?
+profile-create-overwrite = { $path } existiert, überschreiben?
+profile-create-saved = Profil nach { $path } gespeichert
+profile-create-next = Prüfe es mit `steamserv profile test { $app_id } --install-dir { $path }`, bevor du es nutzt oder beim Community-Repository einreichst.
+
+## Game config
+game-config-no-schema = Das Profil von { $name } beschreibt seine Konfigurationsdatei nicht
+game-config-value-invalid = { $value } ist kein gültiger Wert für { $key }
+game-config-not-object = { $key } liegt nicht in einem JSON-Objekt
+game-config-not-set = { $key } ist in { $file } nicht gesetzt
+game-config-set = { $key } in { $file } auf { $value } gesetzt
+game-config-restart = { $target } läuft, starte ihn neu, um die Änderung zu übernehmen
+game-config-header-name = NAME
+game-config-header-key = SCHLÜSSEL
+game-config-header-type = TYP
+game-config-header-value = WERT
+
+## Maps
+maps-none = { $target } hat keine Kartenrotation, füge Karten mit `steamserv maps add` hinzu
+maps-no-command = Das Spiel von { $target } hat keinen Befehl zum Kartenwechsel, setze `command` in der Kartenrotation
+maps-not-found = { $map } ist nicht in der Kartenrotation von { $target }
+maps-added = Karten hinzugefügt, die Rotation von { $target } hat { $count } Karten.
+maps-removed = { $map } aus der Kartenrotation von { $target } entfernt.
+maps-rotated = { $target } auf die Karte { $map } gewechselt
+maps-schedule = Wechselt nach dem Zeitplan { $schedule } zur nächsten Karte
+maps-schedule-none = Wechselt nur mit `steamserv maps rotate` zur nächsten Karte
+maps-schedule-set = { $target } wechselt nach dem Zeitplan { $schedule } zur nächsten Karte.
+maps-schedule-cleared = { $target } wechselt die Karte nicht mehr nach Zeitplan.
+maps-header-position = #
+maps-header-map = KARTE
+maps-header-state = STATUS
+maps-state-current = aktuell
+maps-state-next = nächste
+
+## File sync
+file-sync-group-not-found = Keine Gruppe { $group } mit Servern, füge sie unter `groups` in der Konfiguration hinzu
+file-sync-path-invalid = { $path } muss relativ zum Installationsverzeichnis sein
+file-sync-read-failed = { $path } konnte nicht gelesen werden: { $error }
+file-sync-unchanged = Alle Server von { $group } haben dieselben Dateien.
+file-sync-changed = { $server }: { $path } wird geändert
+file-sync-created = { $server }: { $path } wird angelegt
+file-sync-no-diff = (binär oder zu lang zum Vergleichen)
+file-sync-confirm = { $count } Dateien von { $source } kopieren?
+file-sync-cancelled = Es wurden keine Dateien kopiert.
+file-sync-done = { $count } Dateien auf die Server von { $group } kopiert, laufende Server laden sie beim nächsten Neustart.
+
+## Clusters
+cluster-exists = Der Cluster { $name } existiert bereits
+cluster-not-found = Kein Cluster { $name }, lege ihn mit `steamserv cluster create` an
+cluster-created = Cluster { $name } mit dem Verzeichnis { $dir } angelegt
+cluster-member-elsewhere = { $target } ist bereits im Cluster { $cluster }
+cluster-no-args = Das Spiel von { $target } hat keine Cluster-Argumente, setze `cluster_args` in seinem Profil
+cluster-added = { $count } Server zu { $name } hinzugefügt, sie treten dem Cluster beim nächsten Start bei.
+cluster-not-member = { $target } ist nicht im Cluster { $name }
+cluster-removed = Cluster { $name } aktualisiert, die Änderung gilt ab dem nächsten Start.
+cluster-none = Keine Cluster, lege einen mit `steamserv cluster create` an.
+cluster-header-name = NAME
+cluster-header-id = ID
+cluster-header-dir = VERZEICHNIS
+cluster-header-servers = SERVER
+
+## Info
+info-config-file = Konfigurationsdatei
+info-data-dir = Datenverzeichnis
+info-steamcmd = SteamCMD
+info-install-path = Installationspfad
+info-install-dir = Installationsverzeichnis
+info-scratch-dir = Scratch-Verzeichnis
+info-scratch-none = keins, Downloads liegen in steamapps der Installation
+info-staged-dir = Vorbereitete Updates
+info-log-file = Logdatei
+
+## Logs
+logs-size-invalid = { $size } ist keine gültige Größe, nutze z. B. `50M` oder `1G`
+logs-rotated = { $path } rotiert
+logs-would-rotate = Würde { $path } rotieren
+logs-removed = { $path } gelöscht
+logs-would-remove = Würde { $path } löschen
+logs-pruned = { $size } an rotierten Logs freigegeben.
+logs-would-prune = Würde { $size } an rotierten Logs freigeben.
+logs-pattern-invalid = { $pattern } ist kein gültiger regulärer Ausdruck: { $error }
+logs-gzip-failed = { $path } konnte nicht entpackt werden: { $error }
+logs-search-none = Keine Zeile in den Logs von { $target } passt.
+logs-header-file = DATEI
+logs-header-line-number = ZEILE
+logs-header-line = TEXT
+
+## Bundle
+bundle-created = Support-Paket unter { $path } gespeichert
+bundle-review = Die Geheimnisse der Konfiguration wurden entfernt. Prüfe die Dateien, bevor du sie an einen Fehlerbericht anhängst.
+bundle-core-handler = Core-Dumps werden an einen Handler übergeben, liste sie mit `coredumpctl list` auf.
+
+## Phases
+phase-self-update = SteamCMD wird aktualisiert
+phase-login = Anmeldung
+phase-preallocate = Speicherplatz wird reserviert
+phase-download = Download
+phase-validate = Überprüfung
+phase-commit = Dateien werden installiert
+
+## Bench
+bench-network-running = Die Downloadgeschwindigkeit vom Steam-CDN wird gemessen...
+bench-network-failed = Die Downloadgeschwindigkeit konnte nicht gemessen werden: { $error }
+bench-disk-running = Die Schreibgeschwindigkeit von { $dir } wird gemessen...
+bench-disk-failed = Die Testdatei in { $dir } konnte nicht geschrieben werden: { $error }
+bench-header-target = ZIEL
+bench-header-speed = GESCHWINDIGKEIT
+bench-header-verdict = BEWERTUNG
+bench-steam-cdn = Steam-CDN
+bench-verdict-slow = Diese Festplatte bremst große Updates aus, { $size } zu schreiben dauert etwa { $minutes } Minuten.
+bench-verdict-bottleneck = Die Festplatte ist langsamer als der Download und bremst Updates, { $size } dauern etwa { $minutes } Minuten.
+bench-verdict-ok = Die Festplatte ist schnell genug, { $size } dauern etwa { $minutes } Minuten.
+
+## Deps
+deps-none = Das Spielprofil von { $server } gibt keine Abhängigkeiten an.
+deps-header-dependency = ABHÄNGIGKEIT
+deps-header-package = PAKET
+deps-header-status = STATUS
+deps-installed = installiert
+deps-missing = fehlt
+deps-unknown = unbekannt
+deps-no-manager = Es wurde kein unterstützter Paketmanager (apt, dnf oder pacman) gefunden, installiere die Abhängigkeiten selbst.
+deps-no-package = Das Profil nennt kein { $manager }-Paket für { $dependency }, installiere es selbst.
+deps-all-installed = Alle Abhängigkeiten von { $server } sind installiert.
+deps-will-install = Diese Pakete werden installiert: { $packages }
+deps-confirm = Die Pakete installieren?
+deps-cancelled = Installation der Pakete abgebrochen.
+deps-install-reason = Installiert { $count } Pakete, die der Gameserver braucht
+deps-install-failed = Der Paketmanager ist fehlgeschlagen: { $command }
+deps-done = { $count } Pakete installiert.
+
+## SteamCMD errors
+steamcmd-error-state = SteamCMD konnte die App { $app_id } nicht aktualisieren, ihr Status ist { $state }.
+steamcmd-error-install = SteamCMD konnte die App { $app_id } nicht installieren: { $reason }
+steamcmd-error-outage = Steam war nicht erreichbar: { $reason }
+steamcmd-error-exit = SteamCMD ist mit dem Exit-Code { $code } fehlgeschlagen.
+steamcmd-remedy-disk-space = Für den Download ist nicht genug Speicherplatz frei, gib Platz frei oder setze ein scratch_dir auf einer anderen Festplatte und versuche es erneut.
+steamcmd-remedy-content = Die Content-Server haben nicht alle Dateien geliefert, versuche es später erneut oder führe das Update mit Validierung aus.
+steamcmd-remedy-network = Die Verbindung zu Steam wurde unterbrochen oder hat zu lange gedauert, prüfe das Netzwerk und die Proxy-Einstellungen und versuche es erneut.
+steamcmd-remedy-validate = Dateien der Installation fehlen oder sind beschädigt, führe das Update erneut aus, um die Installation zu validieren.
+steamcmd-remedy-state = Die App hat den Status { $flags }, führe das Update erneut aus und prüfe die Ausgabe von SteamCMD oben.
+steamcmd-remedy-subscription = Die App ist für diesen Login nicht verfügbar, verwende ein Steam-Konto, das das Spiel besitzt, statt anonymous.
+steamcmd-remedy-disk-write = Die Dateien konnten nicht geschrieben werden, prüfe den freien Speicherplatz und die Berechtigungen des Installationsverzeichnisses.
+steamcmd-remedy-platform = Die App hat keinen Download für diese Plattform oder existiert nicht, prüfe die App-ID.
+steamcmd-remedy-login = Der Login ist fehlgeschlagen, prüfe Benutzername, Passwort und den Steam-Guard-Code.
+steamcmd-error-login = Steam hat den Login abgelehnt: { $reason }
+steamcmd-remedy-password = Benutzername oder Passwort sind falsch. Es wird nicht automatisch erneut versucht, da wiederholte fehlgeschlagene Logins das Konto sperren.
+steamcmd-remedy-rate-limit = Steam lehnt Logins nach zu vielen Versuchen ab. Warte mindestens 30 Minuten, bevor du es erneut versuchst, jeder Versuch dazwischen verlängert die Sperre.
+steamcmd-remedy-steam-guard = Das Konto ist durch Steam Guard geschützt. Melde dich einmal von Hand mit `steamcmd +login <Benutzername>` an und gib den Code ein, SteamCMD merkt sich dann diesen Computer.
+steamcmd-remedy-outage = Steam hat eine Störung, das ist kein Problem des Logins, versuche es später erneut.
+steamcmd-remedy-maintenance = Steam ist bis etwa { $until } in der wöchentlichen Wartung, versuche es danach erneut.
+steamcmd-remedy-output = Prüfe die Ausgabe von SteamCMD oben auf die Ursache.
+
+## Read-only
+read-only-denied = steamserv ist im Nur-Lesen-Modus, nur lesende Befehle wie list, status, info und logs search dürfen ausgeführt werden
+
+## Confirmation
+stop-confirm = { $target } stoppen?
+restart-confirm = { $target } neu starten?
+confirm-players-online = { $players } Spieler sind verbunden
+confirm-cancelled = Abgebrochen.
+
+## Watch
+watch-title = Alle { $interval } s, { $time }, beenden mit Strg+C
+
+## Migrate
+migrate-confirm = { $server } nach { $host } ({ $path }) verschieben? Der Server ist gestoppt, bis er dort läuft.
+migrate-sync = Kopiere { $server } nach { $host }...
+migrate-register-failed = { $server } konnte auf { $host } nicht registriert werden, die lokale Kopie bleibt erhalten
+migrate-start = Starte { $server } auf { $host }...
+migrate-start-failed = { $server } ist auf { $host } nicht gestartet, die lokale Kopie bleibt erhalten und kann wieder gestartet werden
+migrate-pause-reason = nach { $host } verschoben
+migrate-success = { $server } läuft jetzt auf { $host }. Die lokale Kopie in { $path } ist pausiert, entferne sie mit `uninstall`, wenn sie nicht mehr gebraucht wird.
+migrate-success-removed = { $server } läuft jetzt auf { $host }, die lokale Kopie wurde entfernt.
+
+## Lancache
+lancache-not-configured = Es ist kein Lancache konfiguriert, setze `lancache` unter [network.depot_cache] in der Konfiguration
+lancache-invalid-address = Ungültige Lancache-Adresse { $address }, erwartet wird eine IP-Adresse
+lancache-heartbeat-ok = Der Lancache unter { $address } antwortet
+lancache-heartbeat-foreign = { $address } antwortet, ist aber kein Lancache
+lancache-heartbeat-failed = Der Lancache unter { $address } antwortet nicht: { $error }
+lancache-host-ok = { $host } zeigt auf den Lancache
+lancache-host-elsewhere = { $host } zeigt auf { $resolved }, nicht auf den Lancache
+lancache-ready = SteamCMD lädt über den Lancache herunter. Cache-Treffer erscheinen als HIT im Access-Log des Caches.
+lancache-pinned = { $count } Hostname(n) zeigen jetzt auf den Lancache.
+lancache-apply-hint = Lass die Hostnamen im DNS des LANs auf den Cache zeigen oder trage sie mit `lan-cache --apply --sudo` in /etc/hosts ein.
+
+## Steam Web API
+webapi-offline = Der Netzwerkzugriff ist im Offline-Modus deaktiviert
+webapi-key-store-apps = Für die App-Liste des Stores wird ein Steam-Web-API-Schlüssel benötigt
+webapi-key-workshop = Für die Abfrage von Workshop-Inhalten wird ein Steam-Web-API-Schlüssel benötigt
+webapi-key-tokens = Zum Erstellen von Gameserver-Tokens wird ein Steam-Web-API-Schlüssel benötigt
+webapi-key-vanity = Zum Auflösen benutzerdefinierter Profil-URLs wird ein Steam-Web-API-Schlüssel benötigt
+webapi-key-players = Für die Abfrage von Spielerprofilen wird ein Steam-Web-API-Schlüssel benötigt
@@ -0,0 +1,949 @@
+## General
+
+offline-error = Can not { $action } in offline mode. Run without --offline or set `offline = false` in the config.
+action-install-server = install a game server
+action-nat-check = check the reachability of the ports
+action-lancache-check = check the lancache
+action-dns-update = update the dynamic DNS record
+action-sync-profiles = sync the community profiles
+action-update-server = update a game server
+action-download-steamcmd = download SteamCMD
+action-update-cache = update the server cache
+action-resolve-steamid = resolve a custom profile URL
+action-check-updates = check for updates
+action-install-deps = install packages
+config-dir-not-found = Could not find config directory
+cache-dir-not-found = Could not find cache directory
+command-required = A command is required on an initialized setup, run with --help to list the commands
+
+## Setup
+
+init-welcome = You are using steamserv for the first time. The following steps configure your environment.
+init-steamcmd-installed = Do you have SteamCMD installed?
+init-steamcmd-path = Please enter the path to the SteamCMD executable:
+init-steamcmd-path-help = This is the path to your SteamCMD executable
+init-steamcmd-offline = SteamCMD can not be downloaded in offline mode
+init-steamcmd-required = SteamCMD is required to use steamserv
+init-host-arch = This host is { $arch }, SteamCMD and the game servers are x86 binaries and need an emulator.
+init-emulator = Run SteamCMD and the game servers with { $emulator }?
+init-install-path = Please enter the path to the server install directory:
+init-install-path-help = This is the path to installing the servers.
+init-install-path-placeholder = e.g. /home/user/servers
+init-bench = Measure the write speed of this directory?
+init-bench-help = Writes a 256 MiB test file to find out if the disk is fast enough for large updates.
+init-bench-result = The directory writes { $speed }. { $verdict }
+init-bench-keep = Use this directory anyway?
+init-cache-offline = Offline mode is enabled, skipping the initial server cache.
+init-cache-create = Creating initial server cache...
+init-complete = Setup complete! You can now use steamserv.
+steamcmd-install-path = Please enter the path to the SteamCMD install directory:
+steamcmd-install-path-help = This is the path you want to install SteamCMD.
+steamcmd-install-path-placeholder = e.g. /home/user/steamcmd
+steamcmd-install-confirm = Do you want to install SteamCMD now?
+steamcmd-download-progress = Downloading SteamCMD
+steamcmd-checksum-missing = No SHA-256 checksum is configured, the download could not be verified ({ $checksum }). Run it anyway?
+steamcmd-checksum-missing-help = Set `steamcmd_sha256` in the config to verify the download, or `steamcmd_allow_unverified = true` to skip this question
+steamcmd-unverified = Refusing to run the unverified SteamCMD download. Set `steamcmd_sha256 = "{ $checksum }"` in the config after checking the checksum, or `steamcmd_allow_unverified = true`.
+download-checksum-mismatch = Checksum mismatch: expected { $expected }, got { $actual }
+download-https-required = Refusing to download { $url } without HTTPS
+download-retrying = Download interrupted ({ $error }), retrying { $attempt }/{ $max }...
+download-failed = Download failed after { $retries } retries: { $error }
+download-incomplete = Incomplete download: received { $received } of { $total } bytes
+steamcmd-extract-progress = Extracting SteamCMD
+steamcmd-extract-failed = Could not extract SteamCMD
+steamcmd-init-failed = Could not initialize SteamCMD
+cache-update-progress = Updating server cache
+cache-names-progress = Looking up the English names of localized servers
+configuring = Configuring...
+
+## Server management
+
+prompt-server-name = Please enter the name of the game server:
+prompt-server-name-placeholder = e.g. TestServer
+prompt-server-name-help = It's the name for your game server folder.
+prompt-steam-username = Please enter your steam username:
+prompt-steam-password = Please enter your password for your steam account.
+prompt-app-id = Please enter the Steam App ID of the game server.
+login-select = Please select your login
+login-select-help = Which of these logins do you want to use?
+login-anonymous = anonymous
+login-steam-account = steam account
+install-welcome = Welcome to your installation guide
+install-confirm-name = Would you like to install the server with the name { $name }?
+install-confirm-app = Would you like to install the server for { $app }?
+install-platform-undetected = Could not detect the platform
+install-checking-license = Checking that the account owns the app...
+install-seeding = Copying the files of { $server } from { $path }, SteamCMD only downloads what differs...
+install-seed-failed = Could not copy the files ({ $status }), everything is downloaded
+install-seed-running = { $server } is running and may change its files during the copy, everything is downloaded
+install-seed-saves = { $count } save file(s) of the other server were not carried over
+steamcmd-probing = Checking the features of SteamCMD...
+steamcmd-probe-failed = Could not check the features of SteamCMD, a current version is assumed: { $error }
+install-not-owned = The account { $username } does not own app { $app_id }. Log in with the account that bought the game, games shared through Steam Family Sharing cannot be downloaded with SteamCMD.
+install-success = Server installed successfully.
+install-batch-progress = Installing { $server } ({ $app_id }) ({ $index }/{ $count })
+install-batch-installed = installed
+install-batch-failed = { $failed } of { $count } game servers could not be installed
+install-batch-server-name = --server-name can only be used when installing a single game server, name the servers in a list file with --from-file instead
+install-list-empty = The list contains no game servers to install
+install-list-read-failed = Could not read the list { $path }: { $error }
+install-header-result = RESULT
+install-retry = SteamCMD failed, trying again ({ $attempt }/{ $retries })
+install-outage-retry = Steam could not be reached, trying again in { $seconds }s ({ $attempt }/{ $retries })
+install-executable-missing = The profile { $profile } starts { $executable }, which is not in the install.
+install-executable-no-profile = There is no game profile for this game yet, so steamserv does not know how to start it.
+install-executable-select = Which executable starts the server?
+install-executable-select-help = The likely server binaries are listed first, the choice is saved as a user profile.
+install-executable-skip = None of them, decide later
+install-executable-exists = The profile { $path } already exists, add the executable to it yourself:
+install-executable-next = Add the ports and launch arguments with `steamserv profile create { $path }` or by editing the profile.
+app-not-found = Could not find server
+app-info-unavailable = SteamCMD printed no app info for { $app_id }
+app-info-no-branch = The app info of { $app_id } has no build for the branch { $branch }
+update-select = Please select the game server to update
+update-select-help = Which of these game servers do you want to update?
+update-success = Server updated successfully.
+uninstall-select = Please select the game server to uninstall
+uninstall-select-help = Which of these game servers do you want to uninstall?
+uninstall-confirm = Are you sure you want to uninstall the server { $server }?
+uninstall-success = Server uninstalled successfully.
+
+## List
+
+list-installed-title = Installed Servers:
+list-available-title = Available Servers:
+list-header-app-id = APP ID
+list-header-name = NAME
+list-header-path = PATH
+list-header-owner = OWNER
+list-offline-stale = Warning: Offline mode is enabled and the server cache was last updated on { $date }. The list may be outdated.
+list-new-servers = New since the last update ({ $count }):
+list-new-servers-notification = { $count } new dedicated servers: { $servers }
+
+## Doctor
+
+doctor-steamcmd = SteamCMD
+doctor-steamcmd-build = SteamCMD version
+doctor-steamcmd-version = version { $version }
+doctor-steamcmd-outdated = outdated build without -beta or workshop downloads, update it by running it once without arguments
+doctor-install-path = Install path
+doctor-network = Network
+doctor-proxy = Proxy
+doctor-http-client = HTTP client
+doctor-missing = { $path } does not exist
+doctor-install-path-missing = { $path } does not exist yet and will be created on install
+doctor-offline = offline mode is enabled
+doctor-no-proxy = no proxy configured
+doctor-reachable = reachable ({ $status })
+doctor-unexpected-status = reachable, but returned { $status }
+doctor-failed = { $count } check(s) failed
+
+## Instances
+
+server-not-installed = The server { $server } is not installed
+instance-added = Instance { $instance } added.
+instance-removed = Instance { $instance } removed.
+instance-exists = The instance { $instance } already exists
+instance-not-found = The server { $server } has no instance { $instance }
+instance-port-used = Port { $port } is already used by { $instance }
+instance-target-invalid = { $target } is not an instance, use the form server/instance
+instance-variable-invalid = { $variable } is not a variable, use the form KEY=VALUE
+instance-remove-confirm = Are you sure you want to remove the instance { $instance } of { $server }? The installation is kept.
+update-instance-shared = The instance { $instance } shares the installation of { $server }, updating { $server }.
+update-mods-incompatible = These mods of { $server } are likely incompatible with build { $build } from { $date }:
+update-mods-force = Update the mods first or run the update with --force
+update-mods-check-failed = Could not check the mods against the new build: { $error }
+update-available = Update for { $server }: build { $installed } → { $build } from { $date }
+update-download-size = Estimated download: up to { $size }
+update-download-unknown = The download size could not be estimated.
+update-confirm = Install the update now?
+update-cancelled = Update cancelled.
+update-up-to-date = { $server } is already on the latest build, validating the installation.
+update-check-failed = Could not check for a new build: { $error }
+update-staging-copy = Copying the install of { $server } for the staged update...
+update-staging-copy-failed = Could not copy the install to { $path }: { $status }
+update-staging-invalid = The staged update of { $server } is incomplete or has the wrong build, it was discarded
+update-staged = The update of { $server } is staged and applied on the next start or restart
+update-snapshot-offer = The install is on { $fs }. Take a snapshot before the update?
+update-snapshot-offer-help = Roll back with `steamserv-rs rollback <server> --snapshot`. Set `before_update` in the [snapshots] config to stop asking.
+update-all-confirm = Update all { $count } game servers now?
+update-all-failed = { $count } update(s) failed, see the report above
+update-all-deferred = { $count } update(s) deferred because Steam could not be reached, run the update again later
+update-report = Update of all servers finished in { $time }: { $updated } updated, { $current } up to date, { $failed } failed, { $skipped } skipped, { $deferred } deferred, estimated download { $size }
+update-report-current = { $server }: up to date on build { $build }
+update-report-updated = { $server }: build { $from } → { $to }
+update-report-failed = { $server }: failed, { $reason }
+update-report-skipped = { $server }: skipped, { $players } player(s) online
+update-report-deferred = { $server }: deferred, Steam could not be reached
+list-header-instance = INSTANCE
+list-header-port = PORT
+list-header-world = WORLD
+list-header-config-file = CONFIG FILE
+
+## Game profiles
+
+profile-not-found = No game profile exists for app { $app_id }
+
+## Tokens
+
+token-stored = Login token stored for { $target }.
+token-created = Login token { $token } created for { $target } (game server account { $steamid }).
+token-not-source = Warning: { $server } is not a Source server, the token is not added to its launch command.
+list-header-server = SERVER
+list-header-token = TOKEN
+
+## Workshop mods
+
+action-download-mods = download workshop mods
+mods-installed = Workshop item { $id } installed ({ $links } link(s) created).
+mods-removed = Workshop item { $id } removed.
+mods-not-installed = Workshop item { $id } is not installed.
+mods-none = The server { $server } has no workshop mods.
+mods-unsupported = The game profile of { $server } does not support workshop content
+mods-download-failed = Could not download the workshop items
+mods-steamcmd-unsupported = This SteamCMD cannot download workshop items, update it by running it once without arguments
+mods-link-exists = Could not link workshop content, { $path } already exists and is not a link
+mods-older-than-build = not updated since { $date }, before the new build
+mods-older-version = declares game version { $version }, other mods already { $newest }
+list-header-workshop-id = WORKSHOP ID
+list-header-updated = UPDATED
+list-header-links = LINKS
+
+## Processes
+
+process-started = { $target } started with PID { $pid }, output is written to { $log }.
+process-stopped = { $target } stopped.
+process-not-running = { $target } is not running.
+process-already-running = { $target } is already running with PID { $pid }
+stop-rcon-command = Sending RCON command: { $command }
+stop-rcon-failed = Could not send the stop commands: { $error }
+stop-signal = Sending signal { $signal }
+stop-waiting = Waiting up to { $seconds } seconds for { $target } to shut down...
+stop-fallback = The server did not shut down in time, sending signal { $signal }
+stop-failed = { $target } (PID { $pid }) did not stop
+signal-unknown = Unknown signal { $signal }
+status-header-state = STATE
+status-header-pid = PID
+status-running = running
+status-stopped = stopped
+status-public-address = Public address: { $hostname } ({ $ip }, updated { $age } ago)
+status-public-hostname = Public address: { $hostname }
+status-maintenance = maintenance
+status-placeholder = placeholder
+
+## RCON
+
+rcon-stored = RCON settings stored for { $target }.
+rcon-not-configured = No RCON settings for { $target }, use `steamserv rcon set`
+rcon-address-invalid = Could not resolve the RCON host { $host }
+rcon-auth-failed = The RCON password was rejected
+rcon-packet-invalid = Received an invalid RCON packet
+rcon-broadcast-sent = Message sent to the players of { $target }.
+broadcast-no-command = The game of { $target } has no broadcast command, set `command` in the announcement
+
+## Readiness checks
+
+readiness-executable = Executable
+readiness-not-executable = { $path } is not executable
+readiness-compat-tool = Wine/Proton
+readiness-compat-tool-missing = { $tool } was not found, install Wine or set `compat_tool` in the config
+readiness-port = Port { $port }
+readiness-port-free = free
+readiness-port-used = already in use
+readiness-config-file = Config file
+readiness-failed = { $target } is not ready to start
+readiness-ready = { $target } is ready to start.
+
+## Supervisor
+
+daemon-started = Supervisor started, watching the health of the running servers.
+daemon-check-error = Could not check { $target }: { $error }
+daemon-check-failed = Health check of { $target } failed ({ $failures }/{ $max }): { $error }
+daemon-server-exited = { $target } exited unexpectedly (PID { $pid })
+daemon-restarting = Restarting { $target }...
+daemon-restart-blackout = { $target } is not restarted during the blackout { $blackout }
+daemon-restarted = { $target } restarted with PID { $pid }.
+daemon-notification = steamserv: { $target } is unhealthy: { $error }
+daemon-notified = Notification sent.
+daemon-recovery-failed = Recovery of { $target } failed: { $error }
+daemon-hook-run = Hook { $script } run.
+daemon-hook-failed = Hook { $script } failed with { $status }
+daemon-announced = Announcement sent to { $target }: { $message }
+daemon-announce-failed = Announcement to { $target } failed: { $error }
+daemon-log-rotated = Rotated the log { $path }
+daemon-log-error = Log rotation failed: { $error }
+daemon-maps-rotated = Changed { $target } to the map { $map }
+daemon-maps-failed = Map change of { $target } failed: { $error }
+daemon-alert-invalid = The alert pattern { $pattern } is not a valid regular expression: { $error }
+daemon-alert-error = Could not check the log alerts of { $target }: { $error }
+daemon-alert-failed = Alert action for { $target } failed: { $error }
+daemon-bundle-created = Support bundle of { $target } written to { $path }
+daemon-bundle-failed = Could not write the support bundle of { $target }: { $error }
+daemon-memory-invalid = The memory limit { $limit } is not a valid size
+daemon-memory-exceeded = The memory stayed above { $limit } for { $minutes } minutes
+daemon-memory-deferred = { $target } is above its memory limit, the restart waits up to { $minutes } minutes for { $players } players to leave
+daemon-memory-scheduled = { $target } is above its memory limit and restarts in { $minutes } minutes
+daemon-memory-warning = The server restarts in { $minutes } minutes
+daemon-paused-all = Automation paused for all servers, the supervisor only collects metrics until `steamserv daemon resume`.
+daemon-paused-server = Automation paused for { $server }.
+daemon-resumed-all = Automation resumed.
+daemon-resumed-server = Automation resumed for { $server }.
+daemon-not-paused = The automation is not paused.
+daemon-not-paused-server = The automation of { $server } is not paused.
+daemon-still-paused = The automation of all servers is still paused.
+daemon-server-paused = { $server } stays paused.
+schedule-invalid = Invalid schedule { $schedule } of { $target }, expected five fields like `*/30 * * * *`
+probe-no-port = No port is configured for the probe
+probe-a2s-failed = A2S query on port { $port } failed: { $error }
+probe-tcp-failed = TCP connect to port { $port } failed: { $error }
+probe-http-failed = HTTP check of { $url } failed: { $error }
+probe-log-matched = Log pattern matched: { $line }
+query-address-invalid = Could not resolve the host { $host }
+query-invalid-response = The server sent an invalid query response
+history-empty = No events recorded.
+history-header-time = TIME
+history-header-event = EVENT
+history-header-message = MESSAGE
+
+## Metrics
+
+daemon-sample-error = Could not collect the metrics of { $target }: { $error }
+period-invalid = { $period } is not a valid period, use e.g. 30m, 24h or 7d
+stats-empty = No metrics of { $target } were collected in the last { $period }, is `steamserv daemon run` running?
+stats-header = Metrics of { $target } in the last { $period } ({ $count } samples)
+stats-players = Players
+stats-fps = FPS
+stats-summary = avg { $average }, max { $max }
+stats-summary-limit = avg { $average }, max { $max } of { $limit }
+stats-range = { $start } – { $end }
+
+## Config backups
+
+config-backups-empty = No config backups yet, they are created whenever the config changes
+config-backup-not-found = There is no config backup named { $backup }, see `steamserv config backups`
+config-restore-select = Select the backup to restore
+config-restore-select-help = Backups are listed newest first, the current config is backed up before restoring
+config-restored = Restored the config from { $backup }.
+
+## Secrets
+
+secrets-passphrase = Passphrase for the secrets in the config:
+secrets-passphrase-help = Set STEAMSERV_PASSPHRASE to use the passphrase without a prompt, e.g. for the supervisor
+secrets-encrypt-failed = Could not encrypt a secret of the config
+secrets-decrypt-failed = Could not decrypt a secret of the config, is the key or passphrase correct?
+secrets-value-invalid = The config contains an invalid encrypted value
+secrets-salt-missing = The secrets settings have no salt, run `steamserv secrets enable --passphrase` again
+secrets-keyring-invalid = The key in the OS keyring is invalid
+secrets-keyring-unavailable = The OS keyring is not available ({ $error }), use `steamserv secrets enable --passphrase` instead
+secrets-enabled = Encrypted { $count } secrets in the config.
+secrets-enabled-backups = Older config backups may still contain the secrets as plain text, remove them from the config-backups directory if needed.
+secrets-disabled = The secrets are stored as plain text again.
+secrets-key-keyring = OS keyring
+secrets-key-passphrase = passphrase
+secrets-status-enabled = Secrets are encrypted with a key from the { $key }.
+secrets-status-disabled = Secrets are stored as plain text.
+secrets-status-count = { $encrypted } of { $total } secrets in the config are encrypted.
+secrets-value = Value of the secret { $name }:
+secrets-name-invalid = { $name } is not a valid secret name, use letters, digits, _ and -
+secrets-stored = Secret { $name } stored, reference it as { $reference } in environment variables.
+secrets-stored-plain = The secrets are stored as plain text, run `steamserv secrets enable` to encrypt them.
+secrets-removed = Secret { $name } removed.
+secrets-unknown = The secret { $name } does not exist, store it with `steamserv secrets set { $name }`
+secrets-in-args = The launch argument { $arg } references a secret, other users can read launch arguments. Pass the secret in an environment variable instead, e.g. with `steamserv env set`.
+secrets-none = No named secrets are stored.
+
+## Timeouts
+
+timeout-expired = { $operation } did not finish within { $seconds } seconds and was stopped, see `[timeouts]` in the config
+
+## Maintenance
+
+maintenance-on = { $server } is in maintenance, the supervisor leaves it alone until `steamserv maintenance off { $server }`.
+maintenance-off = The maintenance of { $server } is over.
+maintenance-active = { $server } is in maintenance ({ $reason }), end it with `steamserv maintenance off { $server }`
+maintenance-already-on = { $server } is already in maintenance
+maintenance-not-on = { $server } is not in maintenance
+maintenance-placeholder-unsupported = The game profile of { $server } has no maintenance arguments for a placeholder
+maintenance-placeholder-started = Started the maintenance placeholder with PID { $pid }.
+
+## New server wizard
+
+new-welcome = Let's set up a new game server.
+new-cache-empty = The server cache is empty, run `steamserv list` online first
+new-select-app = Select the game server:
+new-select-app-help = Type to search, use the arrow keys to select
+new-server-exists = A server named { $server } is already installed
+new-install-root = Install directory:
+new-branch = Branch:
+new-branch-help = Leave empty for the public branch
+new-no-profile = There is no game profile for { $app }, so the launch settings and the start are skipped.
+new-port = Game port:
+new-world = World name:
+new-config-file = Config file:
+new-variable = Value of { $variable }:
+new-variable-help = Leave empty to omit the launch argument
+new-mods = Workshop mods:
+new-mods-help = Workshop item IDs separated by commas, leave empty for none
+new-mod-id-invalid = { $id } is not a valid workshop item ID
+new-start = Start the server after the installation?
+new-summary-app = App ID:       { $app_id }
+new-summary-path = Install path: { $path }
+new-summary-branch = Branch:       { $branch }
+new-summary-login = Login:        { $user }
+new-summary-instance = Instance:     { $target } on port { $port }
+new-summary-mods = Mods:         { $mods }
+new-summary-start-yes = Start:        yes
+new-summary-start-no = Start:        no
+new-confirm = Set up the server with these settings?
+new-cancelled = Cancelled, nothing was installed.
+
+## Templates
+
+template-not-found = There is no template named { $template }
+template-no-port = No free port block was found from port { $port }
+template-provisioning = Provisioning { $server } ({ $index }/{ $count })
+template-saved = Saved the settings as template { $template }
+
+## Answer files
+
+answers-read-failed = Could not read the answer file { $path }: { $error }
+answers-saved = Saved the answers to { $path }
+
+## Privileges
+
+privilege-create-dir = Create { $path } and make it owned by you
+privilege-needed = { $count ->
+    [one] One step needs root:
+   *[other] { $count } steps need root:
+}
+privilege-required = Run the commands above yourself or repeat the command with --sudo to run only these steps through sudo
+privilege-sudo-failed = Could not run sudo: { $error }
+privilege-step-failed = The step `{ $command }` failed
+privilege-append-hosts = Point { $count } Steam CDN hostname(s) to the lancache
+doctor-privileges = Privileges
+doctor-privileges-root = Running as root, a normal user with --sudo is enough
+doctor-host = Host architecture
+doctor-host-emulator = { $arch }, x86 binaries run with { $emulator }
+doctor-host-emulator-unused = { $arch }, { $emulator } is installed but not used, set `emulator = "{ $emulator }"` in the config
+host-binfmt = { $name } is registered with binfmt_misc, x86 binaries run without a wrapper
+host-emulator-missing = no emulator for x86 binaries found, install FEX-Emu (runs SteamCMD and the servers) or Box86 for SteamCMD and Box64 for the servers
+remote-not-found = Unknown remote host { $host }, add it under [remotes.{ $host }] in the config
+remote-ssh-failed = Failed to run ssh for { $host }: { $error }
+remote-rsync-failed = Failed to copy the files to { $host } with rsync: { $error }
+remote-management-only = Game servers only run on Linux, on { $os } steamserv manages remote hosts: add one under [remotes.<name>] in the config and use --remote <name> or default_remote
+doctor-privileges-user = The install path is writable without root
+doctor-privileges-sudo = The install path needs root, run `{ $command }` or use --sudo
+
+## Permissions
+
+perms-normalized = Normalized the permissions of { $count } files and directories
+perms-user-not-found = The service user { $user } does not exist
+perms-step-modes = Remove the world-writable bits in { $path }
+perms-step-owner = Hand { $path } over to { $user }
+
+## Clean
+
+clean-nothing = There are no download caches to delete.
+clean-total = Reclaimable: { $size }
+clean-confirm = Delete these download caches?
+clean-confirm-help = Do not delete them while an install or update is running
+clean-cancelled = Cancelled, nothing was deleted.
+clean-done = Deleted { $size } of download caches.
+
+## Disk space
+
+disk-quota-warning = Warning: the servers in { $root } use { $used } of the { $quota } quota ({ $percent }%)
+disk-free-warning = Warning: only { $free } ({ $percent }%) are free on the filesystem of { $root }
+disk-quota-invalid = The quota { $quota } of { $root } is not a valid size
+daemon-disk-error = Disk check failed: { $error }
+daemon-dns-updated = { $hostname } now points to { $ip }
+daemon-dns-error = Could not update the dynamic DNS record: { $error }
+daemon-job-started = Started job #{ $id }: { $command }
+daemon-job-error = Could not start the queued jobs: { $error }
+
+## Aliases
+
+alias-unknown = { $alias } is neither a Steam App ID nor a known alias, see `steamserv aliases`
+
+## Top
+
+top-title = steamserv top – { $time }, { $running } running, refreshed every { $interval }s, Ctrl+C to quit
+top-header-players = PLAYERS
+top-header-cpu = CPU
+top-header-memory = MEMORY
+
+## Players
+
+players-list-bans = ban list
+players-list-whitelist = whitelist
+players-list-admins = admin list
+players-not-supported = The game of { $target } has no { $list } that steamserv can edit
+players-file-unknown = The { $list } file { $file } of { $target } needs a world name or variable that is not set
+players-rcon-only = The { $list } of { $target } can only be changed over RCON while the server is running
+players-list-unavailable = The { $list } of { $target } is only kept by the server and cannot be listed
+players-added = Added { $id } to the { $list } of { $target }.
+players-removed = Removed { $id } from the { $list } of { $target }.
+players-unchanged = The { $list } of { $target } is already up to date for { $id }.
+players-file-changed = Updated { $file }
+players-reloaded = Applied to the running server over RCON
+players-failed = Could not change the list of { $target }: { $error }
+players-synced = Applied { $count } bans of the central ban list.
+players-not-running = { $target } is not running
+players-online-none = No players are connected to { $target }.
+players-header-name = NAME
+players-header-duration = CONNECTED
+players-header-ping = PING
+admins-title = Admins of { $target }:
+admins-empty = { $target } has no admins.
+steamid-invalid = { $id } is neither a SteamID nor a Steam profile URL
+steamid-not-found = No Steam profile uses the custom URL { $name }
+steamid-profile = Profile
+steamid-name = Name
+steamid-custom-url = Custom URL
+
+## Check updates
+
+check-updates-header-installed = INSTALLED
+check-updates-header-latest = LATEST
+check-updates-header-published = PUBLISHED
+check-updates-header-download = DOWNLOAD
+check-updates-up-to-date = up to date
+check-updates-unknown = unknown
+check-updates-failed = Could not check { $server } for updates: { $error }
+check-updates-total = { $count } update(s) pending, estimated download up to { $size }.
+
+## Events
+
+event-install-started = Installing { $server } (App ID { $app_id })...
+event-update-started = Updating { $server }...
+event-update-applied = Applied the staged update of { $server }
+event-server-crashed = { $target } failed too many health checks in a row: { $error }
+event-recovery-finished = Recovery of { $target }: { $message }
+event-log-alert = Alert { $alert } on { $target }: { $line }
+event-phase-started = » { $phase }...
+event-phase-finished = ✔ { $phase } ({ $seconds }s)
+event-webhook-failed = Could not send the event to the webhook: { $error }
+event-sink-failed = Could not handle an event: { $error }
+
+## Jobs
+job-submitted = Job #{ $id } queued: { $command }
+job-daemon-hint = The daemon (`steamserv-rs daemon run`) starts it in the background, follow it with `steamserv-rs jobs attach`.
+job-not-found = Job #{ $id } does not exist
+job-not-active = Job #{ $id } cannot be cancelled, it is already { $state }
+job-not-running = Job #{ $id } was not started by the daemon
+job-cancelled = Job #{ $id } cancelled
+job-dependency-failed = Cancelled because job #{ $id } did not succeed
+downloads-invalid-hours = Invalid download hours { $window }, expected a window like `01:00-07:00`
+network-ca-unreadable = Could not read the CA certificate { $path }: { $error }
+job-plan-title = Execution plan:
+job-plan-confirm = Queue { $count } updates in this order?
+job-plan-cancelled = No jobs were queued
+job-plan-empty = No game servers to update
+job-plan-cycle = The update dependencies of { $servers } form a cycle
+job-plan-unknown-dependency = { $server } should be updated after { $dependency }, which is not installed
+job-finished = Job #{ $id } { $state }
+job-attach-hint = Press Ctrl+C to detach, the job keeps running.
+job-attach-skipped = Earlier output skipped, the full output is in { $log }
+job-progress-current = { $phase }: { $percent }% ({ $current } / { $total })
+job-state-queued = queued
+job-state-running = running
+job-state-succeeded = succeeded
+job-state-failed = failed
+job-state-cancelled = cancelled
+jobs-empty = No jobs
+jobs-header-id = ID
+jobs-header-state = STATE
+jobs-header-created = CREATED
+jobs-header-duration = DURATION
+jobs-header-command = COMMAND
+jobs-header-step = STEP
+jobs-header-priority = PRIORITY
+jobs-header-after = AFTER
+job-status-id = ID
+job-status-command = Command
+job-status-state = State
+job-status-created = Created
+job-status-started = Started
+job-status-finished = Finished
+job-status-exit-code = Exit code
+job-status-reason = Reason
+job-status-priority = Priority
+job-status-after = After
+job-status-log = Log
+job-status-progress = Progress
+
+## Snapshots
+snapshot-unsupported = { $path } is on { $fs }, snapshots need btrfs or ZFS
+snapshot-btrfs-no-subvolume = { $path } is no btrfs subvolume, create the install with `btrfs subvolume create` to take snapshots
+snapshot-zfs-no-dataset = { $path } is no ZFS dataset, create one for the install with `zfs create` to take snapshots
+snapshot-not-found = Snapshot { $name } does not exist
+snapshot-command-failed = `{ $command }` failed: { $error }
+snapshot-reason-create = Take a snapshot of { $path }
+snapshot-reason-delete = Delete the snapshot { $name }
+snapshot-reason-rollback = Roll back to the snapshot { $name }
+snapshot-created = Snapshot { $name } taken
+snapshot-deleted = Snapshot { $name } deleted
+snapshots-empty = { $server } has no snapshots
+rollback-confirm = Roll { $server } back to { $name }?
+rollback-confirm-help = All changes since the snapshot are lost, including saves. The server is stopped for the rollback.
+rollback-cancelled = Rollback cancelled
+rollback-success = { $server } was rolled back to { $name }
+
+## Import
+lgsm-not-found = { $path } is no LinuxGSM installation, lgsm/config-lgsm is missing
+lgsm-games = { $path } should contain the config of exactly one game, found { $count }
+lgsm-no-instances = { $path } contains no instance configs
+lgsm-no-app-id = No appid is set in the configs in { $path }
+import-success = Imported { $server } (App ID { $app_id }) from { $path } with { $count } instance(s):
+import-lgsm-hint = Stop the LinuxGSM servers and remove their cron jobs before starting the server with steamserv, { $path } is left untouched.
+import-path-missing = The install directory { $path } does not exist on this host
+
+## Export
+export-written = Export written to { $path }
+export-no-ready-line = Warning: the game profile of app { $app_id } has no ready_line, set the startup detection of the egg in the panel
+export-egg-comment = Exported by steamserv from { $server }
+export-egg-description = { $name } dedicated server (App ID { $app_id }), installed with SteamCMD
+export-var-description = Value of { $variable } in the startup command
+export-var-name = Server name
+export-var-world = World
+export-var-config-file = Config file
+export-var-gslt = Game server login token
+export-var-gslt-description = The token for the server, create one at https://steamcommunity.com/dev/managegameservers
+export-var-app-id = Steam App ID
+export-var-app-id-description = The app installed with SteamCMD
+export-var-branch = Beta branch
+export-var-branch-description = The beta branch to install, empty for the public release
+export-var-steam-user = Steam username
+export-var-steam-pass = Steam password
+export-var-steam-account-description = The app can not be downloaded anonymously, the account needs to own it
+
+## Lint
+lint-target = { $target } ({ $file }):
+lint-target-no-schema = { $target } (the game profile describes no config file):
+lint-no-findings = No problems found
+lint-no-config-file = No config file is set, set one with `steamserv instance` to check it
+lint-key-missing = { $key } is missing or empty
+lint-port-mismatch = { $key } is { $value }, but steamserv uses port { $port }
+lint-rcon-disabled = { $key } is empty, RCON is disabled but steamserv has RCON settings
+lint-rcon-password-mismatch = { $key } differs from the RCON password stored in steamserv
+lint-password-common = The RCON password is a common default, anyone can take over the server
+lint-password-short = The RCON password is shorter than { $length } characters
+lint-failed = The config of { $target } has errors
+
+## NAT check
+nat-target = { $target }:
+nat-local-ip = Local address
+nat-public-ip = Public address
+nat-gateway = UPnP gateway
+nat-no-gateway = not found
+nat-unknown = unknown
+nat-reachable = reachable from the internet
+nat-forwarded = forwarded to this host by the router, UDP can not be probed from the outside
+nat-direct = this host has a public address, UDP can not be probed from the outside
+nat-not-listening = nothing listens on the port, start the server to check it
+nat-not-forwarded = the router does not forward the port to this host, forward it or use --upnp
+nat-double-nat = the router has no public address (double NAT or carrier-grade NAT), forwarding can not help, ask your provider for a public address
+nat-firewall = connections are blocked, check the firewall of this host and of your provider
+nat-unknown-cause = could not be determined, no UPnP gateway was found to check the forwarding
+nat-upnp-no-gateway = No UPnP gateway was found, enable UPnP on the router or forward the ports manually
+nat-upnp-invalid-response = The UPnP gateway sent an invalid response
+nat-upnp-failed = The UPnP gateway refused { $action }: { $error }
+nat-upnp-mapped = Forwarded { $port }/{ $protocol } to { $host }
+
+## Dynamic DNS
+dns-no-public-ip = The public address could not be found
+dns-update-rejected = { $provider } rejected the update, check the token and the hostname
+dns-cloudflare-failed = Cloudflare rejected the update: { $error }
+
+## Owners
+owner-set = { $server } now belongs to { $owner }.
+owner-unset = { $server } no longer has an owner.
+owner-header-owner = OWNER
+owner-header-servers = SERVERS
+
+## Time zones
+timezone-set = The schedules of { $server } now run in { $timezone }.
+timezone-unset = The schedules of { $server } run in the time zone of the system again.
+timezone-invalid = Unknown time zone { $timezone } of { $server }, expected an IANA name like `America/New_York`
+
+## Environment and arguments
+env-stored = Environment variables stored for { $target }, they are used on the next start.
+env-removed = Environment variables removed from { $target }, the change is used on the next start.
+env-not-set = { $key } is not set for { $target }
+env-none = { $target } has no environment variables.
+env-inherited = Inherited from { $server }:
+args-stored = Extra launch arguments of { $target }: { $args }
+args-cleared = The extra launch arguments of { $target } were removed.
+
+## Reports
+report-month-invalid = { $month } is not a valid month, use the form YYYY-MM
+report-uptime-title = Availability in { $month }
+report-header-uptime = UPTIME
+report-header-availability = AVAILABILITY
+report-header-starts = STARTS
+report-header-crashes = CRASHES
+
+## Profiles
+profile-test-dir-missing = The install directory { $path } does not exist
+profile-test-installing = Installing { $name } into { $path } for the test...
+profile-test-kept = The test install was kept in { $path }
+profile-test-launching = Launching { $command }
+profile-test-running = Process
+profile-test-exited = exited early with { $status }
+profile-test-still-running = still running
+profile-test-ready-line = Ready line
+profile-test-after = after { $seconds }s
+profile-test-not-seen = "{ $line }" was not printed
+profile-test-query = Server query { $port }
+profile-test-no-answer = no answer
+profile-test-stop = Stop
+profile-test-killed = did not exit within { $timeout }s and was killed
+profile-test-log = The output of the server is in { $path }
+profile-test-failed = The profile of app { $app_id } did not pass the test
+profile-test-passed = The profile of app { $app_id } works.
+profile-header-name = NAME
+profile-header-source = SOURCE
+profile-source-builtin = built-in
+profile-source-override = built-in, changed by { $path }
+profile-show-source = Source: { $source }
+profile-skipped = Skipped: { $error }
+profile-file-invalid = { $path } is not a valid profile file: { $error }
+profile-app-id-missing = A profile in { $path } has no app_id
+profile-entry-invalid = The profile of app { $app_id } in { $path } is invalid: { $error }
+profile-sync-no-repository = No community profile repository is configured, set `url` in the [profile_repository] section of the config
+profile-sync-pin-missing = The repository URL contains {"{"}version{"}"}, set the version in `pin`
+profile-sync-pin-mismatch = The repository is pinned to { $pin }, but the index has version { $version }
+profile-sync-pin-unknown = The repository has no tag or commit { $pin }
+profile-sync-git-option = The repository URL or pin { $value } starts with - and would be read as an option of git
+profile-sync-index-invalid = The profile index { $url } is invalid: { $error }
+profile-sync-https = Refusing to download { $url } without HTTPS
+profile-sync-checksum = The checksum of { $file } does not match: { $error }
+profile-sync-no-signers = Set `signers` to an allowed signers file to verify the index, or `verify = false`
+profile-sync-unknown-signer = The index is not signed by an allowed signer
+profile-sync-signature-invalid = The signature of the index is invalid: { $error }
+profile-sync-unsigned = Revision { $revision } has no valid signature: { $error }
+profile-sync-git-missing = git could not be run: { $error }
+profile-sync-git-failed = git { $command } failed: { $error }
+profile-sync-file-name = The repository file { $file } is not a profile file
+profile-sync-invalid = The community profiles were not synced: { $error }
+profile-sync-done = Synced { $count } profiles in { $files } files from { $url } at { $revision }
+profile-sync-not-verified = The signature was not checked because `verify` is off
+profile-create-app-id = Steam App ID of the game server:
+profile-create-exists = App { $app_id } already has a profile, the new one changes it.
+profile-create-name = Name of the game:
+profile-create-executable = Server binary:
+profile-create-executable-help = Executables found in the install directory
+profile-create-executable-other = Other path
+profile-create-executable-path = Path of the server binary, relative to the install directory:
+profile-create-no-executables = No executables were found in the install directory.
+profile-create-extra-ports = Other ports the server uses (comma separated):
+profile-create-extra-ports-help = Ports for the game port as above, e.g. 2457,2458, empty if there are none
+profile-create-query-port = Port of the server queries:
+profile-create-query-port-help = The port that answers Steam server queries (A2S)
+profile-create-port-invalid = Port { $port } is not a port at or above the game port { $game_port }
+profile-create-args = Launch arguments:
+profile-create-args-help = Placeholders: {"{"}port{"}"}, {"{"}name{"}"}, {"{"}world{"}"}, {"{"}config_file{"}"}, {"{"}install_dir{"}"}, {"{"}var:KEY{"}"}
+profile-create-saves = Save files (comma separated patterns):
+profile-create-saves-help = Relative to the install directory, {"{"}home{"}"} is the home directory, e.g. {"{"}home{"}"}/.config/Game/worlds/*
+profile-create-saves-found = { $pattern } matches { $count } files now
+profile-create-stop-signal = Stop signal:
+profile-create-stop-signal-help = The signal that makes the server save and exit
+profile-create-stop-commands = RCON commands before the signal (separated by ;):
+profile-create-stop-commands-help = e.g. save; quit, empty if the server has no RCON
+profile-create-stop-timeout = Seconds to wait for the server to exit:
+profile-create-ready-line = Line the server prints once players can join:
+profile-create-ready-line-help = Empty if unknown
+profile-create-save = Save the profile in your user profiles?
+profile-create-overwrite = { $path } exists, overwrite it?
+profile-create-saved = Saved the profile to { $path }
+profile-create-next = Check it with `steamserv profile test { $app_id } --install-dir { $path }` before you use it or submit it to the community repository.
+
+## Game config
+game-config-no-schema = The profile of { $name } does not describe its config file
+game-config-value-invalid = { $value } is not a valid value for { $key }
+game-config-not-object = { $key } is not inside a JSON object
+game-config-not-set = { $key } is not set in { $file }
+game-config-set = Set { $key } to { $value } in { $file }
+game-config-restart = { $target } is running, restart it to apply the change
+game-config-header-name = NAME
+game-config-header-key = KEY
+game-config-header-type = TYPE
+game-config-header-value = VALUE
+
+## Maps
+maps-none = { $target } has no map rotation, add maps with `steamserv maps add`
+maps-no-command = The game of { $target } has no map command, set `command` in the map rotation
+maps-not-found = { $map } is not in the map rotation of { $target }
+maps-added = Added the maps, the rotation of { $target } has { $count } maps.
+maps-removed = Removed { $map } from the map rotation of { $target }.
+maps-rotated = Changed { $target } to the map { $map }
+maps-schedule = Changes to the next map on the schedule { $schedule }
+maps-schedule-none = Changes to the next map only with `steamserv maps rotate`
+maps-schedule-set = { $target } changes to the next map on the schedule { $schedule }.
+maps-schedule-cleared = { $target } no longer changes the map on a schedule.
+maps-header-position = #
+maps-header-map = MAP
+maps-header-state = STATE
+maps-state-current = current
+maps-state-next = next
+
+## File sync
+file-sync-group-not-found = No group { $group } with servers, add it to `groups` in the config
+file-sync-path-invalid = { $path } must be relative to the install directory
+file-sync-read-failed = Could not read { $path }: { $error }
+file-sync-unchanged = All servers of { $group } have the same files.
+file-sync-changed = { $server }: { $path } changes
+file-sync-created = { $server }: { $path } is created
+file-sync-no-diff = (binary or too long to compare)
+file-sync-confirm = Copy { $count } files from { $source }?
+file-sync-cancelled = No files were copied.
+file-sync-done = Copied { $count } files to the servers of { $group }, running servers load them on the next restart.
+
+## Clusters
+cluster-exists = The cluster { $name } already exists
+cluster-not-found = No cluster { $name }, create it with `steamserv cluster create`
+cluster-created = Created the cluster { $name } with the directory { $dir }
+cluster-member-elsewhere = { $target } is already in the cluster { $cluster }
+cluster-no-args = The game of { $target } has no cluster arguments, set `cluster_args` in its profile
+cluster-added = Added { $count } servers to { $name }, they join the cluster on the next start.
+cluster-not-member = { $target } is not in the cluster { $name }
+cluster-removed = Updated the cluster { $name }, the change applies on the next start.
+cluster-none = No clusters, create one with `steamserv cluster create`.
+cluster-header-name = NAME
+cluster-header-id = ID
+cluster-header-dir = DIRECTORY
+cluster-header-servers = SERVERS
+
+## Info
+info-config-file = Config file
+info-data-dir = Data directory
+info-steamcmd = SteamCMD
+info-install-path = Install path
+info-install-dir = Install directory
+info-scratch-dir = Scratch directory
+info-scratch-none = none, downloads are staged in steamapps of the install
+info-staged-dir = Staged updates
+info-log-file = Log file
+
+## Logs
+logs-size-invalid = { $size } is not a valid size, use e.g. `50M` or `1G`
+logs-rotated = Rotated { $path }
+logs-would-rotate = Would rotate { $path }
+logs-removed = Deleted { $path }
+logs-would-remove = Would delete { $path }
+logs-pruned = Freed { $size } of rotated logs.
+logs-would-prune = Would free { $size } of rotated logs.
+logs-pattern-invalid = { $pattern } is not a valid regular expression: { $error }
+logs-gzip-failed = Could not decompress { $path }: { $error }
+logs-search-none = No lines in the logs of { $target } match.
+logs-header-file = FILE
+logs-header-line-number = LINE
+logs-header-line = TEXT
+
+## Bundle
+bundle-created = Support bundle written to { $path }
+bundle-review = Secrets of the config were removed. Check the files before you attach them to a bug report.
+bundle-core-handler = Core dumps are passed to a handler, list them with `coredumpctl list`.
+
+## Phases
+phase-self-update = Updating SteamCMD
+phase-login = Logging in
+phase-preallocate = Allocating disk space
+phase-download = Downloading
+phase-validate = Validating
+phase-commit = Installing files
+
+## Bench
+bench-network-running = Measuring the download speed from the Steam CDN...
+bench-network-failed = Could not measure the download speed: { $error }
+bench-disk-running = Measuring the write speed of { $dir }...
+bench-disk-failed = Could not write the test file in { $dir }: { $error }
+bench-header-target = TARGET
+bench-header-speed = SPEED
+bench-header-verdict = VERDICT
+bench-steam-cdn = Steam CDN
+bench-verdict-slow = This disk will bottleneck large updates, { $size } take about { $minutes } minutes to write.
+bench-verdict-bottleneck = The disk is slower than the download and limits updates, { $size } take about { $minutes } minutes.
+bench-verdict-ok = The disk is fast enough, { $size } take about { $minutes } minutes.
+
+## Deps
+deps-none = The game profile of { $server } declares no dependencies.
+deps-header-dependency = DEPENDENCY
+deps-header-package = PACKAGE
+deps-header-status = STATUS
+deps-installed = installed
+deps-missing = missing
+deps-unknown = unknown
+deps-no-manager = No supported package manager (apt, dnf or pacman) was found, install the dependencies yourself.
+deps-no-package = The profile names no { $manager } package for { $dependency }, install it yourself.
+deps-all-installed = All dependencies of { $server } are installed.
+deps-will-install = These packages will be installed: { $packages }
+deps-confirm = Install the packages?
+deps-cancelled = Installation of the packages cancelled.
+deps-install-reason = Installs { $count } packages the game server needs
+deps-install-failed = The package manager failed: { $command }
+deps-done = Installed { $count } packages.
+
+## SteamCMD errors
+steamcmd-error-state = SteamCMD could not update app { $app_id }, its state is { $state }.
+steamcmd-error-install = SteamCMD could not install app { $app_id }: { $reason }
+steamcmd-error-outage = Steam could not be reached: { $reason }
+steamcmd-error-exit = SteamCMD failed with exit code { $code }.
+steamcmd-remedy-disk-space = There is not enough free disk space for the download, free some space or set a scratch_dir on another disk and try again.
+steamcmd-remedy-content = The content servers did not deliver all files, try again later or run the update with validation.
+steamcmd-remedy-network = The connection to Steam was lost or timed out, check the network and the proxy settings and try again.
+steamcmd-remedy-validate = Files of the install are missing or corrupt, run the update again to validate the install.
+steamcmd-remedy-state = The app is in the state { $flags }, run the update again and check the SteamCMD output above.
+steamcmd-remedy-subscription = The app is not available for this login, use a Steam account that owns the game instead of anonymous.
+steamcmd-remedy-disk-write = The files could not be written, check the free space and the permissions of the install directory.
+steamcmd-remedy-platform = The app has no download for this platform or does not exist, check the App ID.
+steamcmd-remedy-login = The login failed, check the username, the password and the Steam Guard code.
+steamcmd-error-login = Steam refused the login: { $reason }
+steamcmd-remedy-password = The username or the password is wrong. It is not tried again automatically, as repeated failed logins lock the account.
+steamcmd-remedy-rate-limit = Steam refuses logins after too many attempts. Wait at least 30 minutes before trying again, every attempt in between extends the limit.
+steamcmd-remedy-steam-guard = The account is protected by Steam Guard. Log in once by hand with `steamcmd +login <username>` and enter the code, SteamCMD then remembers this computer.
+steamcmd-remedy-outage = This is an outage of Steam, not a problem of the login, try again later.
+steamcmd-remedy-maintenance = Steam is in its weekly maintenance until about { $until }, try again after it.
+steamcmd-remedy-output = Check the SteamCMD output above for the cause.
+
+## Read-only
+read-only-denied = steamserv is in read-only mode, only commands that read like list, status, info and logs search may run
+
+## Confirmation
+stop-confirm = Stop { $target }?
+restart-confirm = Restart { $target }?
+confirm-players-online = { $players } players are connected
+confirm-cancelled = Cancelled.
+
+## Watch
+watch-title = Every { $interval }s, { $time }, press Ctrl+C to stop
+
+## Migrate
+migrate-confirm = Move { $server } to { $host } ({ $path })? The server is stopped until it runs there.
+migrate-sync = Copying { $server } to { $host }...
+migrate-register-failed = { $server } could not be registered on { $host }, the local copy is kept
+migrate-start = Starting { $server } on { $host }...
+migrate-start-failed = { $server } did not start on { $host }, the local copy is kept and can be started again
+migrate-pause-reason = migrated to { $host }
+migrate-success = { $server } now runs on { $host }. The local copy in { $path } is paused, remove it with `uninstall` when it is no longer needed.
+migrate-success-removed = { $server } now runs on { $host }, the local copy was removed.
+
+## Lancache
+lancache-not-configured = No lancache is configured, set `lancache` under [network.depot_cache] in the config
+lancache-invalid-address = Invalid lancache address { $address }, expected an IP address
+lancache-heartbeat-ok = The lancache at { $address } answers
+lancache-heartbeat-foreign = { $address } answers, but is not a lancache
+lancache-heartbeat-failed = The lancache at { $address } does not answer: { $error }
+lancache-host-ok = { $host } resolves to the lancache
+lancache-host-elsewhere = { $host } resolves to { $resolved }, not to the lancache
+lancache-ready = SteamCMD downloads through the lancache. Cache hits show up as HIT in the access log of the cache.
+lancache-pinned = { $count } hostname(s) now resolve to the lancache.
+lancache-apply-hint = Point the hostnames to the cache in the DNS of the LAN, or pin them in /etc/hosts with `lan-cache --apply --sudo`.
+
+## Steam Web API
+webapi-offline = Network access is disabled in offline mode
+webapi-key-store-apps = A Steam Web API key is required to query the store app list
+webapi-key-workshop = A Steam Web API key is required to query workshop items
+webapi-key-tokens = A Steam Web API key is required to create game server tokens
+webapi-key-vanity = A Steam Web API key is required to resolve custom profile URLs
+webapi-key-players = A Steam Web API key is required to query player profiles
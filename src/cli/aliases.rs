@@ -0,0 +1,26 @@
+use crate::{
+    core::{aliases, GameProfile},
+    utils::Config,
+};
+
+/// Handle the `aliases` command
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the aliases were listed, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the config could not be loaded
+pub fn handle_aliases_command() -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+
+    for (alias, app_id) in aliases::all(&config) {
+        let name = GameProfile::find(app_id)
+            .map(|profile| profile.name)
+            .unwrap_or_default();
+        println!("{:<36} {:>10}  {}", alias, app_id, name);
+    }
+
+    Ok(())
+}
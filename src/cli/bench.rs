@@ -0,0 +1,90 @@
+use std::{collections::BTreeSet, os::unix::fs::MetadataExt};
+
+use serde_json::json;
+
+use crate::{
+    core::bench::{self, Verdict},
+    tr,
+    utils::{
+        disk, fs,
+        output::{Column, OutputFormat, Table},
+        Config,
+    },
+};
+
+/// Handle the `bench` command
+///
+/// # Arguments
+///
+/// * `skip_network` - Only measure the disks
+/// * `output` - The output format
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the install roots were measured
+///
+/// # Errors
+///
+/// Returns an error if the config could not be loaded or the table could not be rendered
+pub async fn handle_bench_command(
+    skip_network: bool,
+    output: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+
+    let network = if skip_network || config.is_offline() {
+        None
+    } else {
+        // The messages go to stderr, so the JSON and CSV output stays clean
+        eprintln!("{}", tr!("bench-network-running"));
+        match bench::download_speed(&config).await {
+            Ok(speed) => Some(speed),
+            Err(e) => {
+                eprintln!("{}", tr!("bench-network-failed", error = e));
+                None
+            }
+        }
+    };
+
+    let mut table = Table::new(vec![
+        Column::new("target", tr!("bench-header-target"), 30),
+        Column::new("speed", tr!("bench-header-speed"), 12),
+        Column::new("verdict", tr!("bench-header-verdict"), 60),
+    ]);
+    if let Some(speed) = network {
+        table.push(vec![
+            json!(tr!("bench-steam-cdn")),
+            json!(format!("{}/s", fs::format_size(speed as u64))),
+            json!(""),
+        ]);
+    }
+
+    // Roots on the same filesystem write at the same speed
+    let mut filesystems = BTreeSet::new();
+    for root in disk::roots(&config) {
+        let device = root
+            .ancestors()
+            .find_map(|path| path.metadata().ok())
+            .map(|meta| meta.dev());
+        if device.is_some_and(|device| !filesystems.insert(device)) {
+            continue;
+        }
+
+        eprintln!("{}", tr!("bench-disk-running", dir = root.display()));
+        let (speed, verdict) = match bench::disk_speed(&root) {
+            Ok(speed) => (
+                format!("{}/s", fs::format_size(speed as u64)),
+                Verdict::judge(speed, network).describe(speed, network),
+            ),
+            Err(e) => ("-".to_string(), e.to_string()),
+        };
+        table.push(vec![
+            json!(root.display().to_string()),
+            json!(speed),
+            json!(verdict),
+        ]);
+    }
+
+    println!("{}", table.render(output)?);
+    Ok(())
+}
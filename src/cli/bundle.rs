@@ -0,0 +1,29 @@
+use crate::{
+    core::bundle,
+    tr,
+    utils::{Config, ServerRef},
+};
+
+/// Handle the `bundle` command
+///
+/// # Arguments
+///
+/// * `target` - The server or instance in the form `server/instance`
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the bundle was created, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist or the bundle could not be written
+pub fn handle_bundle_command(target: String) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let target = ServerRef::parse(&target);
+
+    let path = bundle::create(&config, &target, None)?;
+    println!("{}", tr!("bundle-created", path = path.display()));
+    println!("{}", tr!("bundle-review"));
+
+    Ok(())
+}
@@ -0,0 +1,217 @@
+use std::path::{Path, PathBuf};
+
+use inquire::Confirm;
+
+use crate::{
+    tr,
+    utils::{disk, fs, Config},
+};
+
+/// Directories of SteamCMD and server installs that only hold leftovers of downloads
+const CACHE_DIRS: [&str; 3] = ["steamapps/downloading", "steamapps/temp", "depotcache"];
+
+/// A cache directory that can be deleted
+struct CacheDir {
+    owner: String,
+    path: PathBuf,
+    size: u64,
+    /// Only empty the directory, it is the target of a scratch disk link
+    contents_only: bool,
+}
+
+/// Handle the `clean` command
+///
+/// # Arguments
+///
+/// * `dry_run` - Only report the reclaimable space without deleting anything
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the caches were reported or deleted, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the config could not be loaded or a cache could not be deleted
+pub fn handle_clean_command(dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let caches = find_caches(&config);
+
+    if caches.is_empty() {
+        println!("{}", tr!("clean-nothing"));
+        return Ok(());
+    }
+
+    for cache in &caches {
+        println!(
+            "{:>10}  {:<20} {}",
+            fs::format_size(cache.size),
+            cache.owner,
+            cache.path.display()
+        );
+    }
+    let total: u64 = caches.iter().map(|c| c.size).sum();
+    println!("{}", tr!("clean-total", size = fs::format_size(total)));
+
+    if dry_run {
+        return Ok(());
+    }
+    if !Confirm::new(&tr!("clean-confirm"))
+        .with_default(false)
+        .with_help_message(&tr!("clean-confirm-help"))
+        .prompt()?
+    {
+        println!("{}", tr!("clean-cancelled"));
+        return Ok(());
+    }
+
+    for cache in &caches {
+        remove_cache(cache)?;
+    }
+    println!("{}", tr!("clean-done", size = fs::format_size(total)));
+
+    Ok(())
+}
+
+/// Find the non-empty cache directories of SteamCMD and all installed servers
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+///
+/// # Returns
+///
+/// The cache directories with their sizes
+fn find_caches(config: &Config) -> Vec<CacheDir> {
+    let mut caches = Vec::new();
+
+    if let Some(steamcmd_dir) = config.steamcmd_path.parent() {
+        collect(&mut caches, "steamcmd", steamcmd_dir);
+    }
+    for server in &config.installed_servers {
+        collect(&mut caches, &server.name, &server.install_path);
+        if let Some(scratch) = disk::scratch_dir(&config.disk, &server.install_path) {
+            collect_scratch(&mut caches, &server.name, &scratch);
+        }
+    }
+
+    caches
+}
+
+/// Add the non-empty download directories of an install on the scratch disk
+///
+/// The links of the install point to these directories, so only their contents
+/// are deleted.
+///
+/// # Arguments
+///
+/// * `caches` - The collected cache directories
+/// * `owner` - The name of the server the caches belong to
+/// * `scratch` - The scratch directory of the install
+fn collect_scratch(caches: &mut Vec<CacheDir>, owner: &str, scratch: &Path) {
+    for dir in disk::SCRATCH_DIRS {
+        let path = scratch.join(dir);
+        if !path.is_dir() || caches.iter().any(|c| c.path == path) {
+            continue;
+        }
+        let size = fs::dir_size(&path);
+        if size > 0 {
+            caches.push(CacheDir {
+                owner: owner.to_string(),
+                path,
+                size,
+                contents_only: true,
+            });
+        }
+    }
+}
+
+/// Delete a cache directory, or only its contents if it is a link target
+///
+/// # Arguments
+///
+/// * `cache` - The cache directory
+///
+/// # Errors
+///
+/// Returns an error if an entry could not be deleted
+fn remove_cache(cache: &CacheDir) -> Result<(), Box<dyn std::error::Error>> {
+    if !cache.contents_only {
+        return Ok(std::fs::remove_dir_all(&cache.path)?);
+    }
+    for entry in std::fs::read_dir(&cache.path)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            std::fs::remove_dir_all(entry.path())?;
+        } else {
+            std::fs::remove_file(entry.path())?;
+        }
+    }
+    Ok(())
+}
+
+/// Add the non-empty cache directories below a root
+///
+/// Symlinks into the scratch disk are skipped, their targets are collected by
+/// [`collect_scratch`].
+///
+/// # Arguments
+///
+/// * `caches` - The collected cache directories
+/// * `owner` - SteamCMD or the name of the server the caches belong to
+/// * `root` - The SteamCMD or install directory
+fn collect(caches: &mut Vec<CacheDir>, owner: &str, root: &Path) {
+    for dir in CACHE_DIRS {
+        let path = root.join(dir);
+        if !path.is_dir() || path.is_symlink() || caches.iter().any(|c| c.path == path) {
+            continue;
+        }
+        let size = fs::dir_size(&path);
+        if size > 0 {
+            caches.push(CacheDir {
+                owner: owner.to_string(),
+                path,
+                size,
+                contents_only: false,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{config::LoginType, InstalledServer};
+
+    #[test]
+    fn test_clean_linked_install() {
+        let dir = std::env::temp_dir().join(format!("steamserv-clean-{}", std::process::id()));
+        let install = dir.join("games/valheim");
+        let mut config = Config {
+            steamcmd_path: dir.join("steamcmd/steamcmd.sh"),
+            ..Config::default()
+        };
+        config.disk.scratch_dir = Some(dir.join("scratch"));
+        config.installed_servers.push(InstalledServer::new(
+            896660,
+            "valheim",
+            install.clone(),
+            LoginType::Anonymous,
+        ));
+
+        let scratch = disk::link_scratch(&config.disk, &install).unwrap().unwrap();
+        std::fs::create_dir_all(scratch.join("downloading/896660")).unwrap();
+        std::fs::write(scratch.join("downloading/896660/chunk"), b"data").unwrap();
+
+        let caches = find_caches(&config);
+        assert_eq!(caches.len(), 1);
+        assert_eq!(caches[0].path, scratch.join("downloading"));
+        assert_eq!(caches[0].size, 4);
+
+        remove_cache(&caches[0]).unwrap();
+        assert!(install.join("steamapps/downloading").is_symlink());
+        assert!(scratch.join("downloading").is_dir());
+        assert_eq!(fs::dir_size(&scratch.join("downloading")), 0);
+        assert!(find_caches(&config).is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
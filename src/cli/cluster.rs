@@ -0,0 +1,125 @@
+use serde_json::json;
+
+use crate::{
+    cli::commands::ClusterCommands,
+    core::{Cluster, GameProfile},
+    tr,
+    utils::{
+        output::{Column, Table},
+        Config, ServerRef,
+    },
+};
+
+/// Handle the `cluster` command
+///
+/// # Arguments
+///
+/// * `command` - The cluster subcommand to run
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command was successful, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the cluster or a server does not exist, a server is
+/// already in another cluster, the cluster directory could not be created or
+/// the config could not be saved
+pub fn handle_cluster_command(command: ClusterCommands) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = Config::load()?;
+
+    match command {
+        ClusterCommands::Create { name, id, dir } => {
+            if config.clusters.contains_key(&name) {
+                return Err(tr!("cluster-exists", name = name).into());
+            }
+            let dir = dir.unwrap_or_else(|| config.install_path.join("clusters").join(&name));
+            std::fs::create_dir_all(&dir)?;
+            config.clusters.insert(
+                name.clone(),
+                Cluster {
+                    id: id.unwrap_or_else(|| name.clone()),
+                    dir: dir.clone(),
+                    servers: Vec::new(),
+                },
+            );
+            config.save()?;
+            println!(
+                "{}",
+                tr!("cluster-created", name = name, dir = dir.display())
+            );
+        }
+        ClusterCommands::Add { name, targets } => {
+            for target in &targets {
+                let target = ServerRef::parse(target);
+                let (server, _) = config.resolve(&target)?;
+                let other = config
+                    .clusters
+                    .iter()
+                    .find(|(other, c)| **other != name && c.servers.contains(&target.to_string()));
+                if let Some((other, _)) = other {
+                    return Err(
+                        tr!("cluster-member-elsewhere", target = target, cluster = other).into(),
+                    );
+                }
+                if GameProfile::find(server.app_id).is_some_and(|p| p.cluster_args.is_empty()) {
+                    println!("{}", tr!("cluster-no-args", target = target));
+                }
+
+                let cluster = config
+                    .clusters
+                    .get_mut(&name)
+                    .ok_or_else(|| tr!("cluster-not-found", name = name.as_str()))?;
+                if !cluster.servers.contains(&target.to_string()) {
+                    cluster.servers.push(target.to_string());
+                }
+            }
+            config.save()?;
+            println!(
+                "{}",
+                tr!("cluster-added", name = name, count = targets.len())
+            );
+        }
+        ClusterCommands::Remove { name, targets } => {
+            let cluster = config
+                .clusters
+                .get_mut(&name)
+                .ok_or_else(|| tr!("cluster-not-found", name = name.as_str()))?;
+            for target in &targets {
+                let target = ServerRef::parse(target).to_string();
+                if !cluster.servers.contains(&target) {
+                    println!(
+                        "{}",
+                        tr!("cluster-not-member", target = target, name = name.as_str())
+                    );
+                }
+                cluster.servers.retain(|s| *s != target);
+            }
+            config.save()?;
+            println!("{}", tr!("cluster-removed", name = name));
+        }
+        ClusterCommands::List { output } => {
+            if config.clusters.is_empty() {
+                println!("{}", tr!("cluster-none"));
+                return Ok(());
+            }
+            let mut table = Table::new(vec![
+                Column::new("name", tr!("cluster-header-name"), 16),
+                Column::new("id", tr!("cluster-header-id"), 16),
+                Column::new("dir", tr!("cluster-header-dir"), 36),
+                Column::new("servers", tr!("cluster-header-servers"), 30),
+            ]);
+            for (name, cluster) in &config.clusters {
+                table.push(vec![
+                    json!(name),
+                    json!(cluster.id),
+                    json!(cluster.dir.display().to_string()),
+                    json!(cluster.servers.join(", ")),
+                ]);
+            }
+            println!("{}", table.render(output)?);
+        }
+    }
+
+    Ok(())
+}
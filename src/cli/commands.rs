@@ -1,5 +1,9 @@
+use std::path::PathBuf;
+
 use clap::Subcommand;
 
+use crate::{core::ExportFormat, utils::output::OutputFormat};
+
 #[derive(Subcommand)]
 pub enum Commands {
     /// Update the game server
@@ -7,24 +11,89 @@ pub enum Commands {
     /// # Arguments
     ///
     /// * `server_name` - The name of the game server to use
+    /// * `force` - Update even if workshop mods are likely incompatible with the new build
+    /// * `yes` - Update without asking for confirmation
+    /// * `staged` - Download the update into a copy of the install and swap it in on the next start
+    /// * `all` - Update all game servers and report the result
     Update {
         #[arg(short, long)]
         server_name: Option<String>,
+        /// Update even if workshop mods are likely incompatible with the new build
+        #[arg(long)]
+        force: bool,
+        /// Update without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+        /// Download the update into a copy of the install while the server keeps running,
+        /// the copy is swapped in on the next start
+        #[arg(long)]
+        staged: bool,
+        /// Update all game servers one after another, skip the ones with players online
+        /// and report the result
+        #[arg(long, conflicts_with = "server_name")]
+        all: bool,
     },
     /// Install an game server
     ///
     /// # Arguments
     ///
-    /// * `app_id` - The Steam App ID of the game server
+    /// * `app` - The alias or Steam App ID of the game server, e.g. `valheim`
+    /// * `app_id` - The alias or Steam App ID of the game server, repeat it to install several servers
+    /// * `from_file` - Install the servers of a list with an app and an optional name per line
     /// * `server_name` - The name of the game server
     /// * `username` - The username of the Steam account to use
+    /// * `answers` - Replay the answers of this file instead of asking
+    /// * `save_answers` - Save the answers to this file
     Install {
+        /// The alias or Steam App ID of the game server, e.g. `valheim`
+        #[arg(conflicts_with = "app_id")]
+        app: Option<String>,
+        /// The alias or Steam App ID of the game server, repeat it to install several servers
         #[arg(short, long)]
-        app_id: Option<u32>,
+        app_id: Vec<String>,
+        /// Install the servers of a list with an app and an optional name per line
+        #[arg(long, conflicts_with_all = ["app", "app_id", "server_name", "save_answers"])]
+        from_file: Option<PathBuf>,
         #[arg(short, long)]
         server_name: Option<String>,
         #[arg(short, long)]
         username: Option<String>,
+        /// Replay the answers of this file instead of asking
+        #[arg(long, conflicts_with_all = ["app", "app_id", "from_file", "server_name", "username"])]
+        answers: Option<PathBuf>,
+        /// Save the answers to this file
+        #[arg(long)]
+        save_answers: Option<PathBuf>,
+    },
+    /// Set up a new game server step by step, from the app to the first start
+    ///
+    /// # Arguments
+    ///
+    /// * `save_template` - Save the settings as a template under this name
+    /// * `from_template` - Provision servers from a saved template instead of asking
+    /// * `count` - The number of servers to provision from the template
+    /// * `name_prefix` - The prefix of the server names, defaults to the template name
+    /// * `answers` - Replay the answers of this file instead of asking
+    /// * `save_answers` - Save the answers to this file
+    New {
+        /// Save the settings as a template under this name
+        #[arg(long, conflicts_with = "from_template")]
+        save_template: Option<String>,
+        /// Provision servers from a saved template instead of asking
+        #[arg(long)]
+        from_template: Option<String>,
+        /// The number of servers to provision from the template
+        #[arg(long, default_value_t = 1, requires = "from_template")]
+        count: usize,
+        /// The prefix of the server names, defaults to the template name
+        #[arg(long, requires = "from_template")]
+        name_prefix: Option<String>,
+        /// Replay the answers of this file instead of asking
+        #[arg(long, conflicts_with = "from_template")]
+        answers: Option<PathBuf>,
+        /// Save the answers to this file
+        #[arg(long, conflicts_with = "from_template")]
+        save_answers: Option<PathBuf>,
     },
     /// Uninstall a game server
     ///
@@ -41,6 +110,11 @@ pub enum Commands {
     ///
     /// * `installed` - Show installed game servers
     /// * `filter` - Filter the list of game servers
+    /// * `owner` - Only show installed game servers of this owner
+    /// * `not_updated_for` - Only show installed game servers not updated in this period
+    /// * `refresh` - Update the server cache before listing the available game servers
+    /// * `output` - The output format
+    /// * `watch` - Show the installed game servers again every this many seconds
     List {
         /// Show installed game servers
         #[arg(short, long)]
@@ -48,7 +122,1527 @@ pub enum Commands {
         /// Filter the list of game servers
         #[arg(short, long)]
         filter: Option<String>,
+        /// Only show installed game servers of this owner
+        #[arg(long)]
+        owner: Option<String>,
+        /// Only show installed game servers not updated in this period, e.g. `30d`
+        #[arg(long)]
+        not_updated_for: Option<String>,
+        /// Update the server cache before listing the available game servers
+        #[arg(long, conflicts_with_all = ["installed", "owner", "not_updated_for"])]
+        refresh: bool,
+        /// The output format
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+        /// Show the installed game servers again every this many seconds until Ctrl+C
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            num_args = 0..=1,
+            default_missing_value = "2",
+            requires = "installed",
+            value_parser = clap::value_parser!(u64).range(1..),
+        )]
+        watch: Option<u64>,
+    },
+    /// Manage the instances of an installed game server
+    Instance {
+        #[command(subcommand)]
+        command: InstanceCommands,
+    },
+    /// Manage the game server login tokens (GSLT) of Source servers
+    Token {
+        #[command(subcommand)]
+        command: TokenCommands,
+    },
+    /// Manage the workshop mods of a game server
+    Mods {
+        #[command(subcommand)]
+        command: ModCommands,
+    },
+    /// Show the generated launch command of a game server
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    LaunchCommand {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+    },
+    /// Normalize the ownership and permissions of the install tree of a game server
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The name of the game server
+    FixPerms {
+        /// The name of the game server
+        server: String,
+    },
+    /// Start a game server or instance in the background
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    /// * `check` - Only run the pre-start checks
+    Start {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+        /// Only run the pre-start checks
+        #[arg(long)]
+        check: bool,
+    },
+    /// Stop a game server or instance with the stop sequence of its game profile
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    Stop {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+    },
+    /// Restart a game server or instance
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    Restart {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+    },
+    /// Show which game servers and instances are running
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Only show the game servers of this owner
+    /// * `watch` - Show the status again every this many seconds
+    Status {
+        /// Only show the game servers of this owner
+        #[arg(long)]
+        owner: Option<String>,
+        /// Show the status again every this many seconds until Ctrl+C
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            num_args = 0..=1,
+            default_missing_value = "2",
+            value_parser = clap::value_parser!(u64).range(1..),
+        )]
+        watch: Option<u64>,
+    },
+    /// Put a game server into maintenance, so the supervisor leaves it alone
+    Maintenance {
+        #[command(subcommand)]
+        command: MaintenanceCommands,
+    },
+    /// Configure RCON and send commands to a running game server
+    Rcon {
+        #[command(subcommand)]
+        command: RconCommands,
+    },
+    /// Run the supervisor that watches the game servers
+    Daemon {
+        #[command(subcommand)]
+        command: DaemonCommands,
+    },
+    /// Show the recorded incidents and recovery actions
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Only show the events of this game server or instance
+    /// * `limit` - The maximum number of events to show
+    History {
+        /// Only show the events of this game server or instance
+        target: Option<String>,
+        /// The maximum number of events to show
+        #[arg(short, long, default_value_t = 50)]
+        limit: usize,
+    },
+    /// Show the player counts and frame rates collected by the supervisor
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    /// * `since` - The period to show, e.g. `24h` or `7d`
+    Stats {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+        /// The period to show, e.g. `24h` or `7d`
+        #[arg(short, long, default_value = "24h")]
+        since: String,
+    },
+    /// Encrypt the passwords and tokens stored in the config
+    Secrets {
+        #[command(subcommand)]
+        command: SecretsCommands,
+    },
+    /// Configure the SteamCMD installation and manage the config backups
+    Config {
+        #[command(subcommand)]
+        command: Option<ConfigCommands>,
+    },
+    /// Check the SteamCMD installation and the connectivity to Steam
+    Doctor,
+    /// List the aliases that can be used instead of Steam App IDs
+    Aliases,
+    /// Delete the download caches of SteamCMD and all installed game servers
+    ///
+    /// # Arguments
+    ///
+    /// * `dry_run` - Only report the reclaimable space without deleting anything
+    Clean {
+        /// Only report the reclaimable space without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Show a live view of all game servers with their state, players, CPU and memory
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - The refresh interval in seconds
+    /// * `once` - Print a single frame and exit
+    Top {
+        /// The refresh interval in seconds
+        #[arg(short, long, default_value_t = 3, value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+        /// Print a single frame and exit
+        #[arg(long)]
+        once: bool,
+    },
+    /// List the connected players, ban players and manage whitelists
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - List the connected players of this game server or instance
+    /// * `output` - The output format of the player list
+    #[command(args_conflicts_with_subcommands = true, arg_required_else_help = true)]
+    Players {
+        /// List the connected players of this game server or instance, in the form `server/instance`
+        target: Option<String>,
+        /// The output format of the player list
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+        #[command(subcommand)]
+        command: Option<PlayersCommands>,
+    },
+    /// Convert a SteamID between its forms or resolve a custom profile URL
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The SteamID in any form, a profile URL or a custom URL name
+    Steamid {
+        /// The SteamID in any form, a profile URL or a custom URL name
+        id: String,
+    },
+    /// Manage the admins of the game servers with the admin files of the games
+    Admins {
+        #[command(subcommand)]
+        command: AdminsCommands,
+    },
+    /// Check the installed game servers for updates and estimate the download size
+    ///
+    /// # Arguments
+    ///
+    /// * `server_name` - Only check this game server
+    /// * `output` - The output format
+    CheckUpdates {
+        /// Only check this game server
+        #[arg(short, long)]
+        server_name: Option<String>,
+        /// The output format
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+    /// Run long operations like installs and updates as background jobs of the daemon
+    Jobs {
+        #[command(subcommand)]
+        command: JobsCommands,
+    },
+    /// Manage the btrfs or ZFS snapshots of the installs of the game servers
+    Snapshots {
+        #[command(subcommand)]
+        command: SnapshotsCommands,
+    },
+    /// Roll a game server back to an earlier state
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The name of the game server
+    /// * `snapshot` - The filesystem snapshot to roll back to, the latest if no name is given
+    /// * `yes` - Roll back without asking for confirmation
+    Rollback {
+        /// The name of the game server
+        server: String,
+        /// The filesystem snapshot to roll back to, the latest if no name is given
+        #[arg(
+            long,
+            required = true,
+            num_args = 0..=1,
+            default_missing_value = "latest"
+        )]
+        snapshot: Option<String>,
+        /// Roll back without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Take over a game server that was installed with another tool or on another host
+    ///
+    /// # Arguments
+    ///
+    /// * `from_lgsm` - The directory of a LinuxGSM installation, e.g. /home/vhserver
+    /// * `from_server` - A TOML file with the entry of a server, `-` for stdin, as sent by `migrate`
+    /// * `name` - The name of the imported server, the name of the first LinuxGSM instance if not given
+    Import {
+        /// The directory of a LinuxGSM installation, e.g. /home/vhserver
+        #[arg(
+            long = "from-lgsm",
+            required_unless_present = "from_server",
+            conflicts_with = "from_server"
+        )]
+        from_lgsm: Option<PathBuf>,
+        /// A TOML file with the entry of a server, `-` for stdin, as sent by `migrate`
+        #[arg(long = "from-server")]
+        from_server: Option<PathBuf>,
+        /// The name of the imported server, the name of the first LinuxGSM instance if not given
+        #[arg(short, long)]
+        name: Option<String>,
+    },
+    /// Move a game server to a remote host
+    ///
+    /// # Arguments
+    ///
+    /// * `server_name` - The name of the game server
+    /// * `to` - The remote host from the config
+    /// * `path` - The install directory on the host, the same as here if not given
+    /// * `remove_local` - Remove the local copy once the server runs on the host
+    /// * `yes` - Move the server without asking for confirmation
+    Migrate {
+        /// The name of the game server
+        server_name: String,
+        /// The remote host from the config
+        #[arg(long)]
+        to: String,
+        /// The install directory on the host, the same as here if not given
+        #[arg(long)]
+        path: Option<PathBuf>,
+        /// Remove the local copy once the server runs on the host
+        #[arg(long)]
+        remove_local: bool,
+        /// Move the server without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Export a game server or instance for panel-based hosting
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The server or instance in the form `server/instance`
+    /// * `format` - The format to export to
+    /// * `output` - The file to write the export to, printed if not given
+    Export {
+        /// The server or instance in the form `server/instance`
+        target: String,
+        /// The format to export to
+        #[arg(short, long, value_enum)]
+        format: ExportFormat,
+        /// The file to write the export to, printed if not given
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Check the config files of a game server for common misconfigurations
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The server, or a single instance in the form `server/instance`
+    Lint {
+        /// The server, or a single instance in the form `server/instance`
+        target: String,
+    },
+    /// Check if the ports of a game server are reachable from the internet
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The server, or a single instance in the form `server/instance`
+    /// * `upnp` - Forward the ports that are not forwarded yet on the router with UPnP
+    NatCheck {
+        /// The server, or a single instance in the form `server/instance`
+        target: String,
+        /// Forward the ports that are not forwarded yet on the router with UPnP
+        #[arg(long)]
+        upnp: bool,
+    },
+    /// Check that SteamCMD downloads through the lancache of the config
+    ///
+    /// # Arguments
+    ///
+    /// * `apply` - Pin the CDN hostnames that do not resolve to the cache in /etc/hosts
+    LanCache {
+        /// Pin the CDN hostnames that do not resolve to the cache in /etc/hosts
+        #[arg(long)]
+        apply: bool,
+    },
+    /// Assign game servers to the communities that manage them on a shared host
+    Owner {
+        #[command(subcommand)]
+        command: OwnerCommands,
+    },
+    /// Set the time zone the schedules of a game server run in
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The name of the game server
+    /// * `timezone` - The IANA time zone, e.g. `America/New_York`, none for the one of the system
+    Timezone {
+        /// The name of the game server
+        server: String,
+        /// The IANA time zone, e.g. `America/New_York`, none for the one of the system
+        timezone: Option<String>,
+    },
+    /// Manage the environment variables a game server is started with
+    Env {
+        #[command(subcommand)]
+        command: EnvCommands,
+    },
+    /// Manage the extra launch arguments of a game server
+    Args {
+        #[command(subcommand)]
+        command: ArgsCommands,
+    },
+    /// Summarize the history of the game servers, e.g. their availability
+    Report {
+        #[command(subcommand)]
+        command: ReportCommands,
+    },
+    /// List, show, create, sync and test the game profiles
+    Profile {
+        #[command(subcommand)]
+        command: ProfileCommands,
+    },
+    /// Print a setting of the config file of a game server, or all named settings
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The server, or a single instance in the form `server/instance`
+    /// * `key` - The name of the setting in the game profile, or the key in the file
+    /// * `output` - The output format of the list of settings
+    ConfigGet {
+        /// The server, or a single instance in the form `server/instance`
+        target: String,
+        /// The name of the setting in the game profile, e.g. `max_players`, or the key in the file
+        key: Option<String>,
+        /// The output format of the list of settings
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+    /// Change a setting of the config file of a game server
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The server, or a single instance in the form `server/instance`
+    /// * `key` - The name of the setting in the game profile, or the key in the file
+    /// * `value` - The new value
+    ConfigSet {
+        /// The server, or a single instance in the form `server/instance`
+        target: String,
+        /// The name of the setting in the game profile, e.g. `max_players`, or the key in the file
+        key: String,
+        /// The new value
+        value: String,
+    },
+    /// Manage the map rotation of a game server, changed over RCON on a schedule
+    Maps {
+        #[command(subcommand)]
+        command: MapsCommands,
+    },
+    /// Copy shared files like ban lists from one server to all other servers of a group
+    ///
+    /// # Arguments
+    ///
+    /// * `group` - The group of servers from the `groups` of the config
+    /// * `paths` - The files, relative to the install directory
+    /// * `from` - The server with the canonical files, the first server of the group if omitted
+    /// * `dry_run` - Only show the changes without writing them
+    /// * `yes` - Copy the files without asking for confirmation
+    SyncFiles {
+        /// The group of servers from the `groups` of the config
+        #[arg(short, long)]
+        group: String,
+        /// The files, relative to the install directory, separated by commas
+        #[arg(short, long, value_delimiter = ',', required = true)]
+        paths: Vec<String>,
+        /// The server with the canonical files, the first server of the group if omitted
+        #[arg(short, long)]
+        from: Option<String>,
+        /// Only show the changes without writing them
+        #[arg(long)]
+        dry_run: bool,
+        /// Copy the files without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Manage clusters of servers that share characters and items, like ARK maps
+    Cluster {
+        #[command(subcommand)]
+        command: ClusterCommands,
+    },
+    /// Show where steamserv keeps its files and where SteamCMD stages the downloads
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - Show the directories of this game server
+    Info {
+        /// Show the directories of this game server
+        server: Option<String>,
+    },
+    /// Manage the captured logs of the game servers
+    Logs {
+        #[command(subcommand)]
+        command: LogsCommands,
+    },
+    /// Collect the log, the redacted config and the history of a server for a bug report
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    Bundle {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+    },
+    /// Measure the write speed of the install roots and the download speed from Steam
+    ///
+    /// # Arguments
+    ///
+    /// * `skip_network` - Only measure the disks
+    /// * `output` - The output format
+    Bench {
+        /// Only measure the disks
+        #[arg(long)]
+        skip_network: bool,
+        /// The output format
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+    /// Check and install the packages a game server needs at runtime
+    Deps {
+        #[command(subcommand)]
+        command: DepsCommands,
+    },
+}
+
+impl Commands {
+    /// Check if the command only reads, for the read-only mode
+    ///
+    /// Commands that write the config, the server cache or files of the
+    /// servers, or change a running server, are not read-only.
+    ///
+    /// # Returns
+    ///
+    /// True if the command may run in the read-only mode
+    pub fn is_read_only(&self) -> bool {
+        match self {
+            Self::List { refresh, .. } => !refresh,
+            Self::Status { .. } | Self::Info { .. } | Self::History { .. } | Self::Top { .. } => {
+                true
+            }
+            Self::Instance { command } => matches!(command, InstanceCommands::List { .. }),
+            Self::Mods { command } => matches!(command, ModCommands::List { .. }),
+            Self::Admins { command } => matches!(command, AdminsCommands::List { .. }),
+            Self::Jobs { command } => matches!(command, JobsCommands::List { .. }),
+            Self::Snapshots { command } => matches!(command, SnapshotsCommands::List { .. }),
+            Self::Owner { command } => matches!(command, OwnerCommands::List),
+            Self::Profile { command } => matches!(
+                command,
+                ProfileCommands::List { .. } | ProfileCommands::Show { .. }
+            ),
+            Self::Maps { command } => matches!(command, MapsCommands::List { .. }),
+            Self::Cluster { command } => matches!(command, ClusterCommands::List { .. }),
+            Self::Logs { command } => match command {
+                LogsCommands::Prune { dry_run } => *dry_run,
+                LogsCommands::Search { .. } => true,
+            },
+            // The secrets, tokens and environment, e.g. with passwords, stay hidden
+            _ => false,
+        }
+    }
+
+    /// Check if the command needs the game servers on this host
+    ///
+    /// Only the commands that manage the local settings run on hosts without
+    /// SteamCMD, like macOS, the others run on a remote host.
+    ///
+    /// # Returns
+    ///
+    /// True if the command runs SteamCMD or touches the servers
+    pub fn needs_local_host(&self) -> bool {
+        !matches!(
+            self,
+            Self::Config { .. } | Self::Secrets { .. } | Self::Aliases | Self::Steamid { .. }
+        )
+    }
+}
+
+#[derive(Subcommand)]
+pub enum SecretsCommands {
+    /// Encrypt the secrets with a key from the OS keyring or a passphrase
+    ///
+    /// # Arguments
+    ///
+    /// * `passphrase` - Derive the key from a passphrase instead of the OS keyring
+    Enable {
+        /// Derive the key from a passphrase instead of the OS keyring
+        #[arg(long)]
+        passphrase: bool,
+    },
+    /// Store the secrets as plain text again
+    Disable,
+    /// Show whether the secrets are encrypted
+    Status,
+    /// Store a named secret that environment variables reference as `${secret:name}`
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the secret
+    /// * `value` - The value, asked for without echo if not given
+    Set {
+        /// The name of the secret, e.g. `rcon_password`
+        name: String,
+        /// The value, asked for without echo if not given
+        value: Option<String>,
+    },
+    /// Remove a named secret
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the secret
+    Unset {
+        /// The name of the secret
+        name: String,
+    },
+    /// List the names of the named secrets
+    List,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    /// List the automatic backups of the config file
+    Backups,
+    /// Restore the config file from a backup
+    ///
+    /// # Arguments
+    ///
+    /// * `backup` - The file name of the backup, selected interactively if omitted
+    Restore {
+        /// The file name of the backup, selected interactively if omitted
+        backup: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum InstanceCommands {
+    /// Add an instance to an installed game server
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The instance in the form `server/instance`
+    /// * `port` - The port of the instance
+    /// * `config_file` - The config file of the instance
+    /// * `world_name` - The world name of the instance
+    /// * `vars` - Additional variables in the form `KEY=VALUE`
+    Add {
+        /// The instance in the form `server/instance`
+        target: String,
+        /// The port of the instance
+        #[arg(short, long)]
+        port: Option<u16>,
+        /// The config file of the instance
+        #[arg(short, long)]
+        config_file: Option<PathBuf>,
+        /// The world name of the instance
+        #[arg(short, long)]
+        world_name: Option<String>,
+        /// Additional variables in the form `KEY=VALUE`
+        #[arg(long = "var")]
+        vars: Vec<String>,
+    },
+    /// Remove an instance from an installed game server
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The instance in the form `server/instance`
+    Remove {
+        /// The instance in the form `server/instance`
+        target: String,
     },
-    /// Configure the SteamCMD installation
-    Config,
+    /// List the instances of the installed game servers
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - Only list the instances of this game server
+    List {
+        /// Only list the instances of this game server
+        server: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TokenCommands {
+    /// Store the login token of a game server
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    /// * `token` - The game server login token
+    Set {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+        /// The game server login token
+        token: String,
+    },
+    /// List the stored login tokens
+    List,
+    /// Create a login token with the Steam Web API and store it
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    /// * `memo` - A note to identify the token on Steam
+    Create {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+        /// A note to identify the token on Steam
+        #[arg(short, long)]
+        memo: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ModCommands {
+    /// Download workshop mods and link them into the game server
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The name of the game server
+    /// * `ids` - The workshop item IDs
+    Add {
+        /// The name of the game server
+        server: String,
+        /// The workshop item IDs
+        #[arg(required = true)]
+        ids: Vec<u64>,
+    },
+    /// Remove workshop mods and their links from the game server
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The name of the game server
+    /// * `ids` - The workshop item IDs
+    Remove {
+        /// The name of the game server
+        server: String,
+        /// The workshop item IDs
+        #[arg(required = true)]
+        ids: Vec<u64>,
+    },
+    /// Update all workshop mods of the game server
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The name of the game server
+    Update {
+        /// The name of the game server
+        server: String,
+    },
+    /// List the workshop mods of the game server
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The name of the game server
+    List {
+        /// The name of the game server
+        server: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum MaintenanceCommands {
+    /// Stop a game server and its instances and block restarts until maintenance is turned off
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The name of the game server
+    /// * `reason` - The reason for the maintenance
+    /// * `placeholder` - Start the server with the maintenance arguments of its game profile
+    On {
+        /// The name of the game server
+        server: String,
+        /// The reason for the maintenance
+        #[arg(short, long)]
+        reason: Option<String>,
+        /// Start the server with the maintenance arguments of its game profile
+        #[arg(long)]
+        placeholder: bool,
+    },
+    /// End the maintenance and start the game servers that were stopped for it
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The name of the game server
+    Off {
+        /// The name of the game server
+        server: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum RconCommands {
+    /// Store the RCON settings of a game server or instance
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    /// * `port` - The RCON port
+    /// * `password` - The RCON password
+    /// * `host` - The RCON host, defaults to `127.0.0.1`
+    Set {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+        /// The RCON port
+        #[arg(short, long)]
+        port: u16,
+        /// The RCON password
+        #[arg(short = 'P', long)]
+        password: String,
+        /// The RCON host, defaults to `127.0.0.1`
+        #[arg(long)]
+        host: Option<String>,
+    },
+    /// Send a command to a running game server
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    /// * `command` - The command to send
+    Exec {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+        /// The command to send
+        #[arg(required = true, trailing_var_arg = true)]
+        command: Vec<String>,
+    },
+    /// Send a chat message to all players with the broadcast command of the game
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    /// * `message` - The message to send
+    Broadcast {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+        /// The message to send
+        #[arg(required = true, trailing_var_arg = true)]
+        message: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum PlayersCommands {
+    /// Ban a player from a game server, or from all game servers with `shared_bans`
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    /// * `steamid` - The SteamID, profile URL or custom URL of the player
+    /// * `no_reload` - Only edit the ban file without applying it over RCON
+    Ban {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+        /// The SteamID, profile URL or custom URL of the player
+        steamid: String,
+        /// Only edit the ban file without applying it over RCON
+        #[arg(long)]
+        no_reload: bool,
+    },
+    /// Lift the ban of a player
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    /// * `steamid` - The SteamID, profile URL or custom URL of the player
+    /// * `no_reload` - Only edit the ban file without applying it over RCON
+    Unban {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+        /// The SteamID, profile URL or custom URL of the player
+        steamid: String,
+        /// Only edit the ban file without applying it over RCON
+        #[arg(long)]
+        no_reload: bool,
+    },
+    /// Add a player to the whitelist of a game server
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    /// * `steamid` - The SteamID, profile URL or custom URL of the player
+    /// * `remove` - Remove the player from the whitelist instead
+    /// * `no_reload` - Only edit the whitelist file without applying it over RCON
+    Whitelist {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+        /// The SteamID, profile URL or custom URL of the player
+        steamid: String,
+        /// Remove the player from the whitelist instead
+        #[arg(long)]
+        remove: bool,
+        /// Only edit the whitelist file without applying it over RCON
+        #[arg(long)]
+        no_reload: bool,
+    },
+    /// Apply the central ban list to all game servers
+    ///
+    /// # Arguments
+    ///
+    /// * `no_reload` - Only edit the ban files without applying them over RCON
+    Sync {
+        /// Only edit the ban files without applying them over RCON
+        #[arg(long)]
+        no_reload: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DaemonCommands {
+    /// Run the health checks of the game servers and recover them from failures
+    ///
+    /// # Arguments
+    ///
+    /// * `once` - Run a single pass and exit
+    Run {
+        /// Run a single pass and exit
+        #[arg(long)]
+        once: bool,
+    },
+    /// Pause the health checks, recoveries and announcements of all or one game server
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - Only pause this game server
+    /// * `reason` - Why the automation is paused
+    Pause {
+        /// Only pause this game server
+        server: Option<String>,
+        /// Why the automation is paused
+        #[arg(short, long)]
+        reason: Option<String>,
+    },
+    /// Resume the automation of all or one game server
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - Only resume this game server
+    Resume {
+        /// Only resume this game server
+        server: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AdminsCommands {
+    /// Make a player an admin of a game server
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    /// * `steamid` - The SteamID, profile URL or custom URL of the player
+    /// * `no_reload` - Only edit the admin file without applying it over RCON
+    Add {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+        /// The SteamID, profile URL or custom URL of the player
+        steamid: String,
+        /// Only edit the admin file without applying it over RCON
+        #[arg(long)]
+        no_reload: bool,
+    },
+    /// Take the admin rights of a player away
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    /// * `steamid` - The SteamID, profile URL or custom URL of the player
+    /// * `no_reload` - Only edit the admin file without applying it over RCON
+    Remove {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+        /// The SteamID, profile URL or custom URL of the player
+        steamid: String,
+        /// Only edit the admin file without applying it over RCON
+        #[arg(long)]
+        no_reload: bool,
+    },
+    /// List the admins of a game server
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    List {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum JobsCommands {
+    /// Queue a steamserv command as a background job
+    ///
+    /// # Arguments
+    ///
+    /// * `priority` - Jobs with a higher priority are started first
+    /// * `after` - Only start the job after these jobs have succeeded
+    /// * `args` - The command with its arguments, e.g. `update -s valheim --yes`
+    Run {
+        /// Jobs with a higher priority are started first
+        #[arg(short, long, default_value_t = 0, allow_negative_numbers = true)]
+        priority: i32,
+        /// Only start the job after these jobs have succeeded
+        #[arg(short, long, value_delimiter = ',')]
+        after: Vec<u64>,
+        /// The command with its arguments, e.g. `update -s valheim --yes`
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Queue the updates of several game servers in the order of their priorities and dependencies
+    ///
+    /// # Arguments
+    ///
+    /// * `servers` - The game servers to update, all if none are given
+    /// * `force` - Update even if workshop mods are likely incompatible with the new build
+    /// * `yes` - Queue the updates without asking for confirmation
+    /// * `staged` - Stage the updates, they are applied on the next start of each server
+    Update {
+        /// The game servers to update, all if none are given
+        servers: Vec<String>,
+        /// Update even if workshop mods are likely incompatible with the new build
+        #[arg(long)]
+        force: bool,
+        /// Stage the updates, they are applied on the next start of each server
+        #[arg(long)]
+        staged: bool,
+        /// Queue the updates without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// List the queued, running and finished jobs
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The output format
+    List {
+        /// The output format
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+    /// Show the details of a job
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the job
+    Status {
+        /// The ID of the job
+        id: u64,
+    },
+    /// Cancel a queued or running job
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the job
+    Cancel {
+        /// The ID of the job
+        id: u64,
+    },
+    /// Follow the output of a job until it has finished
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the job
+    Attach {
+        /// The ID of the job
+        id: u64,
+    },
+    /// Run a started job, used by the daemon
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the job
+    #[command(hide = true)]
+    Exec {
+        /// The ID of the job
+        id: u64,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SnapshotsCommands {
+    /// List the snapshots of a game server
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The name of the game server
+    List {
+        /// The name of the game server
+        server: String,
+    },
+    /// Take a snapshot of a game server
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The name of the game server
+    Create {
+        /// The name of the game server
+        server: String,
+    },
+    /// Delete a snapshot of a game server
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The name of the game server
+    /// * `name` - The name of the snapshot
+    Delete {
+        /// The name of the game server
+        server: String,
+        /// The name of the snapshot
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum OwnerCommands {
+    /// Set the owner of a game server
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The name of the game server
+    /// * `owner` - The owner, e.g. the name of the community
+    Set {
+        /// The name of the game server
+        server: String,
+        /// The owner, e.g. the name of the community
+        owner: String,
+    },
+    /// Remove the owner of a game server
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The name of the game server
+    Unset {
+        /// The name of the game server
+        server: String,
+    },
+    /// List the owners and their game servers
+    List,
+}
+
+#[derive(Subcommand)]
+pub enum EnvCommands {
+    /// Set environment variables of a game server or instance
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    /// * `vars` - The variables in the form `KEY=VALUE`
+    Set {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+        /// The variables in the form `KEY=VALUE`
+        #[arg(required = true)]
+        vars: Vec<String>,
+    },
+    /// Remove environment variables of a game server or instance
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    /// * `keys` - The names of the variables
+    Unset {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+        /// The names of the variables
+        #[arg(required = true)]
+        keys: Vec<String>,
+    },
+    /// List the environment variables of a game server or instance
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    List {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ArgsCommands {
+    /// Set the extra launch arguments of a game server or instance
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    /// * `args` - The arguments as one string, quoted like in a shell
+    Set {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+        /// The arguments as one string, quoted like in a shell, e.g. "-crossplay -public 0"
+        #[arg(allow_hyphen_values = true)]
+        args: String,
+    },
+    /// Remove the extra launch arguments of a game server or instance
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    Clear {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ReportCommands {
+    /// Show the availability, starts and crashes of the game servers in a month
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - Only report this game server and its instances
+    /// * `month` - The month as `YYYY-MM`, the current month if not given
+    /// * `output` - The output format
+    Uptime {
+        /// Only report this game server and its instances
+        server: Option<String>,
+        /// The month as `YYYY-MM`, the current month if not given
+        #[arg(short, long)]
+        month: Option<String>,
+        /// The output format
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ProfileCommands {
+    /// List the built-in and user game profiles with their source
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The output format
+    List {
+        /// The output format
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+    /// Show the effective game profile of a game, with the user overrides applied
+    ///
+    /// # Arguments
+    ///
+    /// * `app_id` - The alias or Steam App ID of the game server
+    Show {
+        /// The alias or Steam App ID of the game server
+        app_id: String,
+    },
+    /// Write a profile for a game without one, asking for the server binary,
+    /// the ports, the save files and the stop sequence
+    ///
+    /// # Arguments
+    ///
+    /// * `install_dir` - The install directory of the game server
+    /// * `app_id` - The Steam App ID, read from the app manifest of the install if omitted
+    /// * `file` - Write the profile to this file instead of the user profiles
+    Create {
+        /// The install directory of the game server
+        install_dir: PathBuf,
+        /// The Steam App ID, read from the app manifest of the install if omitted
+        #[arg(short, long)]
+        app_id: Option<u32>,
+        /// Write the profile to this file instead of the user profiles
+        #[arg(short, long)]
+        file: Option<PathBuf>,
+    },
+    /// Pull the pinned revision of the community profile repository into the
+    /// user profiles, after checking its signature
+    Sync,
+    /// Install a game server into a temporary directory, launch it briefly and
+    /// check that its built-in profile works
+    ///
+    /// # Arguments
+    ///
+    /// * `app_id` - The alias or Steam App ID of the game server
+    /// * `install_dir` - Test an existing install instead of installing the game
+    /// * `username` - The username of the Steam account for the install
+    /// * `timeout` - Seconds the server may take to become ready
+    /// * `keep` - Keep the temporary install after the test
+    Test {
+        /// The alias or Steam App ID of the game server
+        app_id: String,
+        /// Test an existing install instead of installing the game
+        #[arg(short, long)]
+        install_dir: Option<PathBuf>,
+        /// The username of the Steam account for the install, anonymous if not given
+        #[arg(short, long)]
+        username: Option<String>,
+        /// Seconds the server may take to become ready
+        #[arg(short, long, default_value_t = 180)]
+        timeout: u64,
+        /// Keep the temporary install after the test
+        #[arg(long)]
+        keep: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum MapsCommands {
+    /// List the maps of the rotation of a game server or instance
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    /// * `output` - The output format
+    List {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+        /// The output format
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+    /// Add maps to the rotation of a game server or instance
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    /// * `maps` - The names of the maps, as the map command of the game expects them
+    /// * `position` - Insert the maps at this position (starting at 1) instead of at the end
+    Add {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+        /// The names of the maps, as the map command of the game expects them
+        #[arg(required = true)]
+        maps: Vec<String>,
+        /// Insert the maps at this position (starting at 1) instead of at the end
+        #[arg(short, long)]
+        position: Option<usize>,
+    },
+    /// Remove a map from the rotation of a game server or instance
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    /// * `map` - The name of the map
+    Remove {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+        /// The name of the map
+        map: String,
+    },
+    /// Change a running game server or instance to the next map of its rotation now
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    Rotate {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+    },
+    /// Set when the supervisor changes to the next map
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    /// * `schedule` - The cron-like schedule, e.g. `0 */2 * * *`, none to only rotate manually
+    /// * `message` - Message broadcast before the map changes, `{map}` is the next map
+    Schedule {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+        /// The cron-like schedule, e.g. `0 */2 * * *`, none to only rotate manually
+        schedule: Option<String>,
+        /// Message broadcast before the map changes, `{map}` is the next map
+        #[arg(short, long)]
+        message: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ClusterCommands {
+    /// Create a cluster with a shared cluster directory
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the cluster
+    /// * `id` - The cluster ID passed to the game, the name if omitted
+    /// * `dir` - The shared cluster directory, `clusters/<name>` in the install path if omitted
+    Create {
+        /// The name of the cluster
+        name: String,
+        /// The cluster ID passed to the game, the name if omitted
+        #[arg(long)]
+        id: Option<String>,
+        /// The shared cluster directory, `clusters/<name>` in the install path if omitted
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+    },
+    /// Add game servers or instances to a cluster
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the cluster
+    /// * `targets` - The game servers or instances in the form `server/instance`
+    Add {
+        /// The name of the cluster
+        name: String,
+        /// The game servers or instances in the form `server/instance`
+        #[arg(required = true)]
+        targets: Vec<String>,
+    },
+    /// Remove game servers or instances from a cluster
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the cluster
+    /// * `targets` - The game servers or instances in the form `server/instance`
+    Remove {
+        /// The name of the cluster
+        name: String,
+        /// The game servers or instances in the form `server/instance`
+        #[arg(required = true)]
+        targets: Vec<String>,
+    },
+    /// List the clusters with their servers
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The output format
+    List {
+        /// The output format
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum LogsCommands {
+    /// Rotate the server logs that are too big and delete old rotated logs now
+    ///
+    /// # Arguments
+    ///
+    /// * `dry_run` - Only show what would be rotated and deleted
+    Prune {
+        /// Only show what would be rotated and deleted
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Search the log of a server and its rotated logs
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The game server or instance in the form `server/instance`
+    /// * `pattern` - The regular expression to search for
+    /// * `since` - Only search the logs written to in the period, e.g. `2d`
+    /// * `output` - The output format
+    Search {
+        /// The game server or instance in the form `server/instance`
+        target: String,
+        /// The regular expression to search for
+        pattern: String,
+        /// Only search the logs written to in the period, e.g. `2d`
+        #[arg(short, long)]
+        since: Option<String>,
+        /// The output format
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DepsCommands {
+    /// Check if the packages of the game profile of a server are installed
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The game server
+    /// * `output` - The output format
+    Check {
+        /// The game server
+        server: String,
+        /// The output format
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+    /// Install the missing packages of a server with the package manager
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The game server
+    /// * `yes` - Install without asking for confirmation
+    Install {
+        /// The game server
+        server: String,
+        /// Install without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use crate::cli::Cli;
+
+    #[test]
+    fn test_read_only_commands() {
+        let read_only = |args: &[&str]| {
+            let args = std::iter::once("steamserv-rs").chain(args.iter().copied());
+            Cli::try_parse_from(args)
+                .unwrap()
+                .command
+                .unwrap()
+                .is_read_only()
+        };
+        assert!(read_only(&["list", "--installed"]));
+        assert!(!read_only(&["list", "--refresh"]));
+        assert!(read_only(&["status"]));
+        assert!(read_only(&["logs", "search", "valheim", "error"]));
+        assert!(read_only(&[
+            "logs", "search", "valheim", "error", "--since", "2d", "--output", "json"
+        ]));
+        assert!(read_only(&["logs", "prune", "--dry-run"]));
+        assert!(!read_only(&["logs", "prune"]));
+        assert!(read_only(&["history"]));
+        assert!(read_only(&["instance", "list"]));
+        assert!(!read_only(&[
+            "players",
+            "ban",
+            "valheim",
+            "76561197960287930"
+        ]));
+        assert!(!read_only(&["uninstall"]));
+        assert!(!read_only(&["stop", "valheim"]));
+        assert!(!read_only(&["config"]));
+        assert!(!read_only(&["report", "uptime"]));
+        // Commands that show secrets, tokens or passwords
+        assert!(!read_only(&["launch-command", "cs2"]));
+        assert!(!read_only(&["config-get", "cs2"]));
+        assert!(!read_only(&["secrets", "list"]));
+        assert!(!read_only(&["token", "list"]));
+    }
+
+    #[test]
+    fn test_bare_command_line() {
+        // The first run sets up steamserv without a command
+        assert!(Cli::try_parse_from(["steamserv-rs"])
+            .unwrap()
+            .command
+            .is_none());
+        assert!(Cli::try_parse_from(["steamserv-rs", "--offline"])
+            .unwrap()
+            .command
+            .is_none());
+    }
 }
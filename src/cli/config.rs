@@ -0,0 +1,63 @@
+use inquire::Select;
+
+use crate::{cli::commands::ConfigCommands, tr, utils::Config};
+
+/// Handle the `config` command
+///
+/// # Arguments
+///
+/// * `command` - The config subcommand to run
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command was successful, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the backups could not be read or the backup could not be restored
+pub fn handle_config_command(
+    command: Option<ConfigCommands>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let store = Config::settings_store()?;
+
+    match command {
+        None => println!("{}", tr!("configuring")),
+        Some(ConfigCommands::Backups) => {
+            let backups = store.backups()?;
+            if backups.is_empty() {
+                println!("{}", tr!("config-backups-empty"));
+            }
+            for backup in backups {
+                if let Some(name) = backup.file_name() {
+                    println!("{}", name.to_string_lossy());
+                }
+            }
+        }
+        Some(ConfigCommands::Restore { backup }) => {
+            let backups = store.backups()?;
+            let names: Vec<String> = backups
+                .iter()
+                .filter_map(|b| b.file_name().map(|n| n.to_string_lossy().into_owned()))
+                .collect();
+            if names.is_empty() {
+                return Err(tr!("config-backups-empty").into());
+            }
+
+            let name = match backup {
+                Some(name) => name,
+                None => Select::new(&tr!("config-restore-select"), names.clone())
+                    .with_help_message(&tr!("config-restore-select-help"))
+                    .prompt()?,
+            };
+            let index = names
+                .iter()
+                .position(|n| *n == name)
+                .ok_or_else(|| tr!("config-backup-not-found", backup = name.as_str()))?;
+
+            store.restore(&backups[index])?;
+            println!("{}", tr!("config-restored", backup = name));
+        }
+    }
+
+    Ok(())
+}
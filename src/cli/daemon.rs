@@ -0,0 +1,138 @@
+use chrono::Utc;
+
+use crate::{
+    cli::commands::DaemonCommands,
+    core::Supervisor,
+    tr,
+    utils::{history, Config, HistoryEvent, Pause},
+};
+
+/// Handle the `daemon` command
+///
+/// # Arguments
+///
+/// * `command` - The daemon subcommand to run
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command was successful, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the config could not be loaded or saved, or the server does not exist
+pub async fn handle_daemon_command(
+    command: DaemonCommands,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut supervisor = Supervisor::new();
+
+    match command {
+        DaemonCommands::Run { once } => {
+            if once {
+                supervisor.tick().await?;
+            } else {
+                supervisor.run().await?;
+            }
+        }
+        DaemonCommands::Pause { server, reason } => pause(server.as_deref(), reason)?,
+        DaemonCommands::Resume { server } => resume(server.as_deref())?,
+    }
+
+    Ok(())
+}
+
+/// Pause the automation of all servers or of one server
+///
+/// # Arguments
+///
+/// * `name` - The name of the server, or `None` for all servers
+/// * `reason` - Why the automation is paused
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the automation is paused
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist or the config could not be saved
+fn pause(name: Option<&str>, reason: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = Config::load()?;
+    let pause = Pause {
+        since: Utc::now(),
+        reason: reason.clone(),
+    };
+
+    match name {
+        Some(name) => {
+            let server = config
+                .find_server_mut(name)
+                .ok_or_else(|| tr!("server-not-installed", server = name))?;
+            server.paused = Some(pause);
+            config.save()?;
+            history::record(&HistoryEvent::new(
+                name,
+                "daemon-paused",
+                reason.as_deref().unwrap_or_default(),
+            ))?;
+            println!("{}", tr!("daemon-paused-server", server = name));
+        }
+        None => {
+            config.paused = Some(pause);
+            config.save()?;
+            println!("{}", tr!("daemon-paused-all"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Resume the automation of all servers or of one server
+///
+/// # Arguments
+///
+/// * `name` - The name of the server, or `None` to lift the global pause
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the automation is resumed
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist or the config could not be saved
+fn resume(name: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = Config::load()?;
+
+    match name {
+        Some(name) => {
+            let server = config
+                .find_server_mut(name)
+                .ok_or_else(|| tr!("server-not-installed", server = name))?;
+            if server.paused.take().is_none() {
+                println!("{}", tr!("daemon-not-paused-server", server = name));
+                return Ok(());
+            }
+            config.save()?;
+            history::record(&HistoryEvent::new(name, "daemon-resumed", ""))?;
+            println!("{}", tr!("daemon-resumed-server", server = name));
+            if config.paused.is_some() {
+                println!("{}", tr!("daemon-still-paused"));
+            }
+        }
+        None => {
+            if config.paused.take().is_none() {
+                println!("{}", tr!("daemon-not-paused"));
+                return Ok(());
+            }
+            config.save()?;
+            println!("{}", tr!("daemon-resumed-all"));
+            for server in config
+                .installed_servers
+                .iter()
+                .filter(|s| s.paused.is_some())
+            {
+                println!("  {}", tr!("daemon-server-paused", server = server.name));
+            }
+        }
+    }
+
+    Ok(())
+}
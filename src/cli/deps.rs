@@ -0,0 +1,131 @@
+use inquire::Confirm;
+use serde_json::json;
+
+use crate::{
+    cli::commands::DepsCommands,
+    core::{deps, DependencyCheck, GameProfile, PackageManager},
+    tr,
+    utils::{
+        output::{Column, OutputFormat, Table},
+        Config, ServerRef,
+    },
+};
+
+/// Handle the `deps` command
+///
+/// # Arguments
+///
+/// * `command` - The deps subcommand to run
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the dependencies were checked or installed
+///
+/// # Errors
+///
+/// Returns an error if the server or its game profile does not exist, no
+/// package manager was found, or the packages could not be installed
+pub fn handle_deps_command(command: DepsCommands) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let manager = PackageManager::detect();
+
+    match command {
+        DepsCommands::Check { server, output } => {
+            let profile = profile_of(&config, &server)?;
+            let checks = deps::check(&profile, manager);
+            if checks.is_empty() && output == OutputFormat::Table {
+                println!("{}", tr!("deps-none", server = server));
+                return Ok(());
+            }
+
+            let mut table = Table::new(vec![
+                Column::new("dependency", tr!("deps-header-dependency"), 30),
+                Column::new("package", tr!("deps-header-package"), 24),
+                Column::new("status", tr!("deps-header-status"), 12),
+            ]);
+            for check in &checks {
+                let status = match check.installed {
+                    Some(true) => tr!("deps-installed"),
+                    Some(false) => tr!("deps-missing"),
+                    None => tr!("deps-unknown"),
+                };
+                table.push(vec![
+                    json!(check.dependency.name),
+                    json!(check.package.as_deref().unwrap_or("-")),
+                    json!(status),
+                ]);
+            }
+            println!("{}", table.render(output)?);
+            if manager.is_none() {
+                println!("{}", tr!("deps-no-manager"));
+            }
+        }
+        DepsCommands::Install { server, yes } => {
+            config.ensure_online(&tr!("action-install-deps"))?;
+            let profile = profile_of(&config, &server)?;
+            let manager = manager.ok_or_else(|| tr!("deps-no-manager"))?;
+            let missing: Vec<DependencyCheck> = deps::check(&profile, Some(manager))
+                .into_iter()
+                .filter(|check| check.installed != Some(true))
+                .collect();
+
+            let mut packages: Vec<String> = Vec::new();
+            for check in &missing {
+                match &check.package {
+                    Some(package) if !packages.contains(package) => packages.push(package.clone()),
+                    Some(_) => {}
+                    None => println!(
+                        "{}",
+                        tr!(
+                            "deps-no-package",
+                            dependency = check.dependency.name.as_str(),
+                            manager = manager.key()
+                        )
+                    ),
+                }
+            }
+            if packages.is_empty() {
+                println!("{}", tr!("deps-all-installed", server = server));
+                return Ok(());
+            }
+
+            println!(
+                "{}",
+                tr!("deps-will-install", packages = packages.join(" "))
+            );
+            if !yes
+                && !Confirm::new(&tr!("deps-confirm"))
+                    .with_default(true)
+                    .prompt()?
+            {
+                println!("{}", tr!("deps-cancelled"));
+                return Ok(());
+            }
+
+            deps::install(manager, &packages)?;
+            println!("{}", tr!("deps-done", count = packages.len()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Get the game profile of a server
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+/// * `server` - The game server, instances share the profile of their server
+///
+/// # Returns
+///
+/// The game profile
+///
+/// # Errors
+///
+/// Returns an error if the server or its game profile does not exist
+fn profile_of(config: &Config, server: &str) -> Result<GameProfile, Box<dyn std::error::Error>> {
+    let (server, _) = config.resolve(&ServerRef::parse(server))?;
+    GameProfile::find(server.app_id)
+        .ok_or_else(|| tr!("profile-not-found", app_id = server.app_id).into())
+}
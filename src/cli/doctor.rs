@@ -0,0 +1,281 @@
+use crate::{
+    core::SteamCMD,
+    tr,
+    utils::{
+        host,
+        privilege::{self, Escalation},
+        steamcmd::BetaSyntax,
+        Config, TimeoutConfig,
+    },
+};
+
+/// Hosts that need to be reachable for steamserv to work
+const CONNECTIVITY_TARGETS: [(&str, &str); 2] = [
+    (
+        "Steam Web API",
+        "https://api.steampowered.com/ISteamWebAPIUtil/GetServerInfo/v1/",
+    ),
+    (
+        "Steam CDN",
+        "https://steamcdn-a.akamaihd.net/client/installer/steamcmd_linux.tar.gz",
+    ),
+];
+
+enum CheckStatus {
+    Ok,
+    Warning,
+    Failed,
+    Skipped,
+}
+
+struct Check {
+    name: String,
+    status: CheckStatus,
+    detail: String,
+}
+
+impl Check {
+    fn new(name: &str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Handle the `doctor` command
+///
+/// # Returns
+///
+/// Returns `Ok(())` if all checks passed, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the config could not be loaded or a check failed
+pub async fn handle_doctor_command() -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+
+    let mut checks = check_installation(&config);
+    checks.push(check_host(&config));
+    checks.extend(check_connectivity(&config).await);
+
+    display_checks(&checks);
+
+    let failed = checks
+        .iter()
+        .filter(|c| matches!(c.status, CheckStatus::Failed))
+        .count();
+    if failed > 0 {
+        return Err(tr!("doctor-failed", count = failed).into());
+    }
+
+    Ok(())
+}
+
+/// Check the local SteamCMD and server installation paths
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+///
+/// # Returns
+///
+/// The results of the checks
+fn check_installation(config: &Config) -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    if config.steamcmd_path.is_file() {
+        checks.push(Check::new(
+            &tr!("doctor-steamcmd"),
+            CheckStatus::Ok,
+            config.steamcmd_path.display().to_string(),
+        ));
+        let capabilities = SteamCMD::capabilities(config);
+        let (status, detail) = match capabilities.beta {
+            BetaSyntax::Flag if capabilities.workshop_download => (
+                CheckStatus::Ok,
+                tr!(
+                    "doctor-steamcmd-version",
+                    version = capabilities.version.as_deref().unwrap_or("?")
+                ),
+            ),
+            _ => (CheckStatus::Warning, tr!("doctor-steamcmd-outdated")),
+        };
+        checks.push(Check::new(&tr!("doctor-steamcmd-build"), status, detail));
+    } else {
+        checks.push(Check::new(
+            &tr!("doctor-steamcmd"),
+            CheckStatus::Failed,
+            tr!("doctor-missing", path = config.steamcmd_path.display()),
+        ));
+    }
+
+    if config.install_path.is_dir() {
+        checks.push(Check::new(
+            &tr!("doctor-install-path"),
+            CheckStatus::Ok,
+            config.install_path.display().to_string(),
+        ));
+    } else {
+        checks.push(Check::new(
+            &tr!("doctor-install-path"),
+            CheckStatus::Warning,
+            tr!(
+                "doctor-install-path-missing",
+                path = config.install_path.display()
+            ),
+        ));
+    }
+
+    if privilege::is_root() {
+        checks.push(Check::new(
+            &tr!("doctor-privileges"),
+            CheckStatus::Warning,
+            tr!("doctor-privileges-root"),
+        ));
+    } else if privilege::is_writable(&config.install_path) {
+        checks.push(Check::new(
+            &tr!("doctor-privileges"),
+            CheckStatus::Ok,
+            tr!("doctor-privileges-user"),
+        ));
+    } else {
+        checks.push(Check::new(
+            &tr!("doctor-privileges"),
+            CheckStatus::Warning,
+            tr!(
+                "doctor-privileges-sudo",
+                command = Escalation::create_dir(&config.install_path).to_string()
+            ),
+        ));
+    }
+
+    checks
+}
+
+/// Check that the host can run SteamCMD and the game servers, which are x86 binaries
+///
+/// # Arguments
+///
+/// * `config` - The configuration with the emulator
+///
+/// # Returns
+///
+/// The result of the check
+fn check_host(config: &Config) -> Check {
+    let name = tr!("doctor-host");
+    if host::is_native() {
+        return Check::new(&name, CheckStatus::Ok, host::arch());
+    }
+
+    if let Some(emulator) = &config.emulator {
+        return Check::new(
+            &name,
+            CheckStatus::Ok,
+            tr!(
+                "doctor-host-emulator",
+                arch = host::arch(),
+                emulator = emulator.display().to_string()
+            ),
+        );
+    }
+    if let Some(binfmt) = host::binfmt_emulator() {
+        return Check::new(
+            &name,
+            CheckStatus::Ok,
+            format!("{}, {}", host::arch(), tr!("host-binfmt", name = binfmt)),
+        );
+    }
+    let detail = match host::find_emulator() {
+        Some(emulator) => tr!(
+            "doctor-host-emulator-unused",
+            arch = host::arch(),
+            emulator = emulator.display().to_string()
+        ),
+        None => format!("{}, {}", host::arch(), tr!("host-emulator-missing")),
+    };
+    Check::new(&name, CheckStatus::Warning, detail)
+}
+
+/// Check the connectivity to Steam, through the proxy if one is configured
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+///
+/// # Returns
+///
+/// The results of the checks
+async fn check_connectivity(config: &Config) -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    if config.is_offline() {
+        checks.push(Check::new(
+            &tr!("doctor-network"),
+            CheckStatus::Skipped,
+            tr!("doctor-offline"),
+        ));
+        return checks;
+    }
+
+    match config.network.effective_proxy() {
+        Some(proxy) => checks.push(Check::new(&tr!("doctor-proxy"), CheckStatus::Ok, proxy)),
+        None => checks.push(Check::new(
+            &tr!("doctor-proxy"),
+            CheckStatus::Skipped,
+            tr!("doctor-no-proxy"),
+        )),
+    }
+
+    let client = match config
+        .network
+        .http_client(TimeoutConfig::limit(config.timeouts.network))
+    {
+        Ok(client) => client,
+        Err(e) => {
+            checks.push(Check::new(
+                &tr!("doctor-http-client"),
+                CheckStatus::Failed,
+                e.to_string(),
+            ));
+            return checks;
+        }
+    };
+
+    for (name, url) in CONNECTIVITY_TARGETS {
+        let check = match client.head(url).send().await {
+            Ok(response) if response.status().is_success() => Check::new(
+                name,
+                CheckStatus::Ok,
+                tr!("doctor-reachable", status = response.status()),
+            ),
+            Ok(response) => Check::new(
+                name,
+                CheckStatus::Warning,
+                tr!("doctor-unexpected-status", status = response.status()),
+            ),
+            Err(e) => Check::new(name, CheckStatus::Failed, e.to_string()),
+        };
+        checks.push(check);
+    }
+
+    checks
+}
+
+/// Display the results of the checks
+///
+/// # Arguments
+///
+/// * `checks` - The results of the checks
+fn display_checks(checks: &[Check]) {
+    for check in checks {
+        let status = match check.status {
+            CheckStatus::Ok => "OK",
+            CheckStatus::Warning => "WARN",
+            CheckStatus::Failed => "FAIL",
+            CheckStatus::Skipped => "SKIP",
+        };
+        println!("[{:<4}] {:<20} {}", status, check.name, check.detail);
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    cli::{
+        commands::{ArgsCommands, EnvCommands},
+        instance::parse_variables,
+    },
+    core::launch::{quote, split_args},
+    tr,
+    utils::{Config, ServerRef},
+};
+
+/// The environment variables and extra launch arguments of a server or instance
+type LaunchSettings<'a> = (&'a mut BTreeMap<String, String>, &'a mut Vec<String>);
+
+/// Handle the `env` command
+///
+/// # Arguments
+///
+/// * `command` - The env subcommand to run
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command was successful, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the server or instance does not exist, a variable is not
+/// in the form `KEY=VALUE` or the config could not be saved
+pub fn handle_env_command(command: EnvCommands) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = Config::load()?;
+
+    match command {
+        EnvCommands::Set { target, vars } => {
+            let target = ServerRef::parse(&target);
+            let vars = parse_variables(&vars)?;
+            let (env, _) = launch_settings(&mut config, &target)?;
+            env.extend(vars);
+            config.save()?;
+            println!("{}", tr!("env-stored", target = target));
+        }
+        EnvCommands::Unset { target, keys } => {
+            let target = ServerRef::parse(&target);
+            let (env, _) = launch_settings(&mut config, &target)?;
+            for key in &keys {
+                if env.remove(key).is_none() {
+                    println!("{}", tr!("env-not-set", key = key, target = target));
+                }
+            }
+            config.save()?;
+            println!("{}", tr!("env-removed", target = target));
+        }
+        EnvCommands::List { target } => {
+            let target = ServerRef::parse(&target);
+            let (server, instance) = config.resolve(&target)?;
+            let env = instance.map_or(&server.env, |i| &i.env);
+            if env.is_empty() {
+                println!("{}", tr!("env-none", target = target));
+            }
+            for (key, value) in env {
+                println!("{}={}", key, quote(value));
+            }
+            if instance.is_some() && !server.env.is_empty() {
+                println!();
+                println!("{}", tr!("env-inherited", server = server.name));
+                for (key, value) in &server.env {
+                    println!("{}={}", key, quote(value));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle the `args` command
+///
+/// # Arguments
+///
+/// * `command` - The args subcommand to run
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command was successful, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the server or instance does not exist or the config could not be saved
+pub fn handle_args_command(command: ArgsCommands) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = Config::load()?;
+
+    match command {
+        ArgsCommands::Set { target, args } => {
+            let target = ServerRef::parse(&target);
+            let (_, extra_args) = launch_settings(&mut config, &target)?;
+            *extra_args = split_args(&args);
+            let shown: Vec<String> = extra_args.iter().map(|arg| quote(arg)).collect();
+            config.save()?;
+            println!(
+                "{}",
+                tr!("args-stored", target = target, args = shown.join(" "))
+            );
+        }
+        ArgsCommands::Clear { target } => {
+            let target = ServerRef::parse(&target);
+            let (_, extra_args) = launch_settings(&mut config, &target)?;
+            extra_args.clear();
+            config.save()?;
+            println!("{}", tr!("args-cleared", target = target));
+        }
+    }
+
+    Ok(())
+}
+
+/// Get the launch settings of a server or instance for modification
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+/// * `target` - The server or instance
+///
+/// # Returns
+///
+/// The environment variables and the extra launch arguments
+///
+/// # Errors
+///
+/// Returns an error if the server or instance does not exist
+fn launch_settings<'a>(
+    config: &'a mut Config,
+    target: &ServerRef,
+) -> Result<LaunchSettings<'a>, Box<dyn std::error::Error>> {
+    config.resolve(target)?;
+    let server = config
+        .find_server_mut(&target.server)
+        .ok_or_else(|| tr!("server-not-installed", server = target.server))?;
+
+    match &target.instance {
+        Some(name) => {
+            let instance = server
+                .instances
+                .iter_mut()
+                .find(|i| &i.name == name)
+                .ok_or_else(|| {
+                    tr!(
+                        "instance-not-found",
+                        instance = name,
+                        server = target.server
+                    )
+                })?;
+            Ok((&mut instance.env, &mut instance.extra_args))
+        }
+        None => Ok((&mut server.env, &mut server.extra_args)),
+    }
+}
@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+
+use crate::{
+    core::{export, ExportFormat, GameProfile},
+    tr,
+    utils::{fs, Config, ServerRef},
+};
+
+/// Handle the `export` command
+///
+/// # Arguments
+///
+/// * `target` - The server or instance in the form `server/instance`
+/// * `format` - The format to export to
+/// * `output` - The file to write the export to, printed if not given
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command was successful, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist, has no game profile or the
+/// file could not be written
+pub fn handle_export_command(
+    target: String,
+    format: ExportFormat,
+    output: Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let (server, instance) = config.resolve(&ServerRef::parse(&target))?;
+
+    let exported = export::export(format, server, instance)?;
+    if GameProfile::find(server.app_id).is_some_and(|p| p.ready_line.is_none()) {
+        eprintln!("{}", tr!("export-no-ready-line", app_id = server.app_id));
+    }
+
+    match output {
+        Some(path) => {
+            fs::write_atomic(&path, exported.as_bytes())?;
+            println!("{}", tr!("export-written", path = path.display()));
+        }
+        None => println!("{}", exported),
+    }
+
+    Ok(())
+}
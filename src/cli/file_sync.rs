@@ -0,0 +1,95 @@
+use inquire::Confirm;
+
+use crate::{
+    core::FileSync,
+    tr,
+    utils::{history, Config, HistoryEvent},
+};
+
+/// Handle the `sync-files` command
+///
+/// The changes are shown as a diff of the changed lines before they are written.
+///
+/// # Arguments
+///
+/// * `group` - The group of servers
+/// * `paths` - The files, relative to the install directory
+/// * `from` - The server with the canonical files, the first server of the group if `None`
+/// * `dry_run` - Only show the changes without writing them
+/// * `yes` - Copy the files without asking for confirmation
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the files were copied, are the same on all servers or the
+/// copy was declined
+///
+/// # Errors
+///
+/// Returns an error if the group or a server does not exist, a file of the
+/// source server could not be read or a file could not be written
+pub fn handle_sync_files_command(
+    group: String,
+    paths: Vec<String>,
+    from: Option<String>,
+    dry_run: bool,
+    yes: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let sync = FileSync::plan(&config, &group, from.as_deref(), &paths)?;
+
+    if sync.changes.is_empty() {
+        println!("{}", tr!("file-sync-unchanged", group = group));
+        return Ok(());
+    }
+
+    for change in &sync.changes {
+        let key = if change.old.is_some() {
+            "file-sync-changed"
+        } else {
+            "file-sync-created"
+        };
+        println!(
+            "{}",
+            tr!(key, server = change.server, path = change.path.display())
+        );
+        match change.diff() {
+            Some(lines) => {
+                for line in lines {
+                    println!("  {}", line);
+                }
+            }
+            None => println!("  {}", tr!("file-sync-no-diff")),
+        }
+    }
+
+    if dry_run {
+        return Ok(());
+    }
+    if !yes
+        && !Confirm::new(&tr!(
+            "file-sync-confirm",
+            count = sync.changes.len(),
+            source = sync.source
+        ))
+        .with_default(false)
+        .prompt()?
+    {
+        println!("{}", tr!("file-sync-cancelled"));
+        return Ok(());
+    }
+
+    sync.apply()?;
+    for change in &sync.changes {
+        history::record(&HistoryEvent::new(
+            &change.server,
+            "files-synced",
+            &format!("{} ({})", change.path.display(), sync.source),
+        ))?;
+    }
+    println!(
+        "{}",
+        tr!("file-sync-done", count = sync.changes.len(), group = group)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use serde_json::json;
+
+use crate::{
+    core::{GameConfig, ServerProcess},
+    tr,
+    utils::{
+        output::{Column, OutputFormat, Table},
+        Config, ServerRef,
+    },
+};
+
+/// Handle the `config-get` command
+///
+/// Without a key the named settings of the game profile are listed with their
+/// values, with a key only the value is printed, so scripts can read it.
+///
+/// # Arguments
+///
+/// * `target` - The server, or a single instance in the form `server/instance`
+/// * `key` - The name of the setting or the key in the file
+/// * `output` - The output format of the list of settings
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the setting was printed
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist, its config file could not be
+/// read or the file does not have the setting
+pub fn handle_config_get_command(
+    target: String,
+    key: Option<String>,
+    output: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let (server, instance) = config.resolve(&ServerRef::parse(&target))?;
+    let file = GameConfig::load(server, instance)?;
+
+    let Some(key) = key else {
+        let mut table = Table::new(vec![
+            Column::new("name", tr!("game-config-header-name"), 20),
+            Column::new("key", tr!("game-config-header-key"), 28),
+            Column::new("type", tr!("game-config-header-type"), 10),
+            Column::new("value", tr!("game-config-header-value"), 30),
+        ]);
+        for (name, setting) in &file.settings {
+            table.push(vec![
+                json!(name),
+                json!(setting.key),
+                serde_json::to_value(setting.kind)?,
+                json!(file.get(name)),
+            ]);
+        }
+        println!("{}", table.render(output)?);
+        return Ok(());
+    };
+
+    let value = file.get(&key).ok_or_else(|| {
+        tr!(
+            "game-config-not-set",
+            key = key.as_str(),
+            file = file.path.display()
+        )
+    })?;
+    println!("{}", value);
+    Ok(())
+}
+
+/// Handle the `config-set` command
+///
+/// # Arguments
+///
+/// * `target` - The server, or a single instance in the form `server/instance`
+/// * `key` - The name of the setting or the key in the file
+/// * `value` - The new value
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the setting was changed
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist, its config file could not be
+/// read or written or the value does not fit the type of the setting
+pub fn handle_config_set_command(
+    target: String,
+    key: String,
+    value: String,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let target = ServerRef::parse(&target);
+    let (server, instance) = config.resolve(&target)?;
+    let mut file = GameConfig::load(server, instance)?;
+
+    let written = file.set(&key, &value)?;
+    file.save()?;
+    println!(
+        "{}",
+        tr!(
+            "game-config-set",
+            key = key.as_str(),
+            value = written.as_str(),
+            file = file.path.display()
+        )
+    );
+    if ServerProcess::new(&target)?.is_running() {
+        println!("{}", tr!("game-config-restart", target = target));
+    }
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use crate::{tr, utils::history};
+
+/// Handle the `history` command
+///
+/// # Arguments
+///
+/// * `target` - Only show the events of this server or instance
+/// * `limit` - The maximum number of events to show
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the history was displayed, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the history could not be read
+pub fn handle_history_command(
+    target: Option<String>,
+    limit: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let events = history::load(target.as_deref())?;
+    if events.is_empty() {
+        println!("{}", tr!("history-empty"));
+        return Ok(());
+    }
+
+    println!(
+        "{:<20} {:<30} {:<22} {}",
+        tr!("history-header-time"),
+        tr!("list-header-server"),
+        tr!("history-header-event"),
+        tr!("history-header-message")
+    );
+    println!("{:-<100}", "");
+
+    let skip = events.len().saturating_sub(limit);
+    for event in events.iter().skip(skip) {
+        println!(
+            "{:<20} {:<30} {:<22} {}",
+            event
+                .timestamp
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S"),
+            event.target,
+            event.kind,
+            event.message
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    core::{LaunchCommand, LgsmInstall},
+    tr,
+    utils::{history, Config, HistoryEvent, InstalledServer},
+};
+
+/// Handle the `import` command
+///
+/// The LinuxGSM installation is only read, its server files stay where they are
+/// and are managed by steamserv from then on. A server entry is sent by
+/// `migrate` after the files were copied to this host.
+///
+/// # Arguments
+///
+/// * `from_lgsm` - The directory of the LinuxGSM installation
+/// * `from_server` - The TOML file with the entry of a server, `-` for stdin
+/// * `name` - The name of the imported server, the name of the first LinuxGSM instance if not given
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the server was imported, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the LinuxGSM configs or the server entry could not be
+/// read or a server with the name is already installed
+pub fn handle_import_command(
+    from_lgsm: Option<PathBuf>,
+    from_server: Option<PathBuf>,
+    name: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = Config::load()?;
+    let Some(from_lgsm) = from_lgsm else {
+        return import_server(&mut config, &from_server.unwrap_or_default(), name);
+    };
+    let lgsm = LgsmInstall::read(&from_lgsm)?;
+    let name = name
+        .or_else(|| lgsm.instances.keys().next().cloned())
+        .unwrap_or_default();
+    if config.find_server(&name).is_some() {
+        return Err(tr!("new-server-exists", server = name).into());
+    }
+
+    let server = lgsm.to_server(&name);
+    config.installed_servers.push(server.clone());
+    config.save()?;
+    history::record(&HistoryEvent::new(
+        &name,
+        "imported",
+        &lgsm.root.display().to_string(),
+    ))?;
+
+    println!(
+        "{}",
+        tr!(
+            "import-success",
+            server = name,
+            app_id = server.app_id,
+            path = server.install_path.display(),
+            count = server.instances.len()
+        )
+    );
+    for instance in &server.instances {
+        match LaunchCommand::build(&config, &server, Some(instance)) {
+            Ok(command) => println!("  {}/{}: {}", name, instance.name, command),
+            Err(e) => println!("  {}/{}: {}", name, instance.name, e),
+        }
+    }
+    println!("{}", tr!("import-lgsm-hint", path = lgsm.root.display()));
+
+    Ok(())
+}
+
+/// Import the entry of a server whose files are already on this host
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+/// * `path` - The TOML file with the entry of the server, `-` for stdin
+/// * `name` - The name of the imported server, the name in the entry if not given
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the server was imported
+///
+/// # Errors
+///
+/// Returns an error if the entry could not be read, its install directory does
+/// not exist or a server with the name is already installed
+fn import_server(
+    config: &mut Config,
+    path: &Path,
+    name: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let content = if path == Path::new("-") {
+        std::io::read_to_string(std::io::stdin())?
+    } else {
+        std::fs::read_to_string(path)?
+    };
+    let mut server: InstalledServer = toml::from_str(&content)?;
+    if let Some(name) = name {
+        server.name = name;
+    }
+    if config.find_server(&server.name).is_some() {
+        return Err(tr!("new-server-exists", server = server.name).into());
+    }
+    if !server.install_path.is_dir() {
+        return Err(tr!(
+            "import-path-missing",
+            path = server.install_path.display().to_string()
+        )
+        .into());
+    }
+
+    config.installed_servers.push(server.clone());
+    config.save()?;
+    history::record(&HistoryEvent::new(
+        &server.name,
+        "imported",
+        &server.install_path.display().to_string(),
+    ))?;
+    println!(
+        "{}",
+        tr!(
+            "import-success",
+            server = server.name.as_str(),
+            app_id = server.app_id,
+            path = server.install_path.display(),
+            count = server.instances.len()
+        )
+    );
+    for instance in &server.instances {
+        println!("  {}/{}", server.name, instance.name);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use std::path::Path;
+
+use crate::{
+    core::{staging, ServerProcess},
+    tr,
+    utils::{
+        disk::{self, SCRATCH_DIRS},
+        Config, ServerRef,
+    },
+};
+
+/// Handle the `info` command
+///
+/// Prints where steamserv keeps its files. For a server the install directory
+/// and where SteamCMD stages its downloads are shown.
+///
+/// # Arguments
+///
+/// * `server` - The name of the game server, or `None` for the global directories
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the directories were printed
+///
+/// # Errors
+///
+/// Returns an error if the steamserv directory could not be found or the server
+/// does not exist
+pub fn handle_info_command(server: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+
+    let Some(name) = server else {
+        let data_dir = Config::data_dir()?;
+        row("info-config-file", &data_dir.join("config.toml"));
+        row("info-data-dir", &data_dir);
+        row("info-steamcmd", &config.steamcmd_path);
+        row("info-install-path", &config.install_path);
+        match &config.disk.scratch_dir {
+            Some(scratch) => row("info-scratch-dir", scratch),
+            None => println!(
+                "{:<24} {}",
+                tr!("info-scratch-dir"),
+                tr!("info-scratch-none")
+            ),
+        }
+        return Ok(());
+    };
+
+    let server = config
+        .find_server(&name)
+        .ok_or_else(|| tr!("server-not-installed", server = name))?;
+    row("info-install-dir", &server.install_path);
+    for dir in SCRATCH_DIRS {
+        let path = server.install_path.join("steamapps").join(dir);
+        let label = format!("steamapps/{}", dir);
+        match std::fs::read_link(&path) {
+            Ok(target) => println!("{:<24} {} -> {}", label, path.display(), target.display()),
+            Err(_) => println!("{:<24} {}", label, path.display()),
+        }
+    }
+    if let Some(scratch) = disk::scratch_dir(&config.disk, &server.install_path) {
+        row("info-scratch-dir", &scratch);
+    }
+    row("info-staged-dir", &staging::staged_dir(server));
+    row(
+        "info-log-file",
+        &ServerProcess::new(&ServerRef::parse(&server.name))?.log_file,
+    );
+
+    Ok(())
+}
+
+/// Print a labeled path
+///
+/// # Arguments
+///
+/// * `label` - The message id of the label
+/// * `path` - The path
+fn row(label: &str, path: &Path) {
+    println!("{:<24} {}", tr!(label), path.display());
+}
@@ -0,0 +1,152 @@
+use std::{collections::BTreeSet, path::PathBuf};
+
+use serde_json::json;
+
+use crate::{
+    core::{AppEntry, NewServer, SteamCMD},
+    tr,
+    utils::{
+        output::{Column, OutputFormat, Table},
+        Config, ServerCache,
+    },
+};
+
+/// Handle the `install` command
+///
+/// A single app is installed interactively. Several apps, given with repeated
+/// `--app-id` or a list file, are installed one after another without prompts,
+/// because SteamCMD can only run one install at a time.
+///
+/// # Arguments
+///
+/// * `app` - The alias or Steam App ID of the game server
+/// * `app_ids` - The aliases or Steam App IDs given with `--app-id`
+/// * `from_file` - The list of apps to install
+/// * `server_name` - The name of the game server
+/// * `username` - The username of the Steam account to use
+/// * `answers` - Replay the answers of this file instead of asking
+/// * `save_answers` - Save the answers to this file
+///
+/// # Returns
+///
+/// Returns `Ok(())` if all game servers were installed, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if an app is unknown, the list could not be read or a game
+/// server could not be installed
+pub async fn handle_install_command(
+    app: Option<String>,
+    mut app_ids: Vec<String>,
+    from_file: Option<PathBuf>,
+    server_name: Option<String>,
+    username: Option<String>,
+    answers: Option<PathBuf>,
+    save_answers: Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let entries = match from_file {
+        Some(path) => AppEntry::load_list(&path)?,
+        None if app_ids.len() > 1 => {
+            if server_name.is_some() {
+                return Err(tr!("install-batch-server-name").into());
+            }
+            app_ids
+                .into_iter()
+                .map(|app| AppEntry { app, name: None })
+                .collect()
+        }
+        None => {
+            return SteamCMD::install(
+                app.or(app_ids.pop()),
+                server_name,
+                username,
+                answers.as_deref(),
+                save_answers.as_deref(),
+            );
+        }
+    };
+
+    install_many(&entries, username).await
+}
+
+/// Install the game servers of a list one after another
+///
+/// A failed server does not stop the others, the summary shows the result of each.
+///
+/// # Arguments
+///
+/// * `entries` - The apps to install
+/// * `username` - The username of the Steam account, anonymous if not given
+///
+/// # Returns
+///
+/// Returns `Ok(())` if all game servers were installed
+///
+/// # Errors
+///
+/// Returns an error if the list is empty or a game server could not be installed
+async fn install_many(
+    entries: &[AppEntry],
+    username: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if entries.is_empty() {
+        return Err(tr!("install-list-empty").into());
+    }
+    let config = Config::load()?;
+    config.ensure_online(&tr!("action-install-server"))?;
+    let cache = ServerCache::load()?;
+    let login = SteamCMD::get_login(Some(username.unwrap_or_else(|| "anonymous".to_string())))?;
+    let mut names: BTreeSet<String> = config
+        .installed_servers
+        .iter()
+        .map(|s| s.name.clone())
+        .collect();
+
+    let mut table = Table::new(vec![
+        Column::new("server", tr!("list-header-server"), 30),
+        Column::new("app_id", tr!("list-header-app-id"), 10),
+        Column::new("result", tr!("install-header-result"), 50),
+    ]);
+    let mut failed = 0;
+    for (index, entry) in entries.iter().enumerate() {
+        let (name, app_id, result) =
+            match NewServer::for_app(&config, &cache, entry, &login, &mut names) {
+                Ok(server) => {
+                    println!(
+                        "{}",
+                        tr!(
+                            "install-batch-progress",
+                            server = server.name.as_str(),
+                            app_id = server.app_id,
+                            index = index + 1,
+                            count = entries.len()
+                        )
+                    );
+                    let result = server.provision().await;
+                    (server.name, json!(server.app_id), result)
+                }
+                Err(e) => (entry.app.clone(), json!(null), Err(e)),
+            };
+        let message = match result {
+            Ok(()) => tr!("install-batch-installed"),
+            Err(e) => {
+                failed += 1;
+                e.to_string()
+            }
+        };
+        table.push(vec![json!(name), app_id, json!(message)]);
+    }
+
+    println!();
+    println!("{}", table.render(OutputFormat::Table)?);
+
+    if failed > 0 {
+        return Err(tr!(
+            "install-batch-failed",
+            failed = failed,
+            count = entries.len()
+        )
+        .into());
+    }
+    Ok(())
+}
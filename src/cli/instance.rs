@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    cli::commands::InstanceCommands,
+    tr,
+    utils::{Config, ServerInstance, ServerRef},
+};
+
+/// Handle the `instance` command
+///
+/// # Arguments
+///
+/// * `command` - The instance subcommand to run
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command was successful, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the command fails
+pub fn handle_instance_command(
+    command: InstanceCommands,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = Config::load()?;
+
+    match command {
+        InstanceCommands::Add {
+            target,
+            port,
+            config_file,
+            world_name,
+            vars,
+        } => {
+            let (server, instance) = parse_instance_target(&target)?;
+            let instance = ServerInstance {
+                name: instance,
+                port,
+                config_file,
+                world_name,
+                variables: parse_variables(&vars)?,
+                gslt: None,
+                rcon: None,
+                health: None,
+                announcements: Vec::new(),
+                extra_args: Vec::new(),
+                maps: None,
+                env: BTreeMap::new(),
+            };
+            config.add_instance(&server, instance)?;
+            config.save()?;
+            println!("{}", tr!("instance-added", instance = target));
+        }
+        InstanceCommands::Remove { target } => {
+            let (server, instance) = parse_instance_target(&target)?;
+            config.remove_instance(&server, &instance)?;
+            config.save()?;
+            println!("{}", tr!("instance-removed", instance = target));
+        }
+        InstanceCommands::List { server } => list_instances(&config, server.as_deref()),
+    }
+
+    Ok(())
+}
+
+/// Parse an instance target in the form `server/instance`
+///
+/// # Arguments
+///
+/// * `target` - The instance target
+///
+/// # Returns
+///
+/// The server name and the instance name
+///
+/// # Errors
+///
+/// Returns an error if the target does not name an instance
+fn parse_instance_target(target: &str) -> Result<(String, String), Box<dyn std::error::Error>> {
+    match ServerRef::parse(target) {
+        ServerRef {
+            server,
+            instance: Some(instance),
+        } => Ok((server, instance)),
+        _ => Err(tr!("instance-target-invalid", target = target).into()),
+    }
+}
+
+/// Parse instance variables in the form `KEY=VALUE`
+///
+/// # Arguments
+///
+/// * `vars` - The variables to parse
+///
+/// # Returns
+///
+/// The parsed variables
+///
+/// # Errors
+///
+/// Returns an error if a variable is not in the form `KEY=VALUE`
+pub fn parse_variables(
+    vars: &[String],
+) -> Result<BTreeMap<String, String>, Box<dyn std::error::Error>> {
+    vars.iter()
+        .map(|var| match var.split_once('=') {
+            Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+            _ => Err(tr!("instance-variable-invalid", variable = var).into()),
+        })
+        .collect()
+}
+
+/// List the instances of the installed servers
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+/// * `server` - Only list the instances of this server
+fn list_instances(config: &Config, server: Option<&str>) {
+    println!(
+        "{:<40} {:<8} {:<20} {:<40}",
+        tr!("list-header-instance"),
+        tr!("list-header-port"),
+        tr!("list-header-world"),
+        tr!("list-header-config-file")
+    );
+    println!("{:-<110}", "");
+
+    for installed in config
+        .installed_servers
+        .iter()
+        .filter(|s| server.is_none_or(|name| s.name == name))
+    {
+        for instance in &installed.instances {
+            println!(
+                "{:<40} {:<8} {:<20} {:<40}",
+                format!("{}/{}", installed.name, instance.name),
+                instance.port.map(|p| p.to_string()).unwrap_or_default(),
+                instance.world_name.clone().unwrap_or_default(),
+                instance
+                    .config_file
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_default()
+            );
+            for (key, value) in &instance.variables {
+                println!("    {}={}", key, value);
+            }
+        }
+    }
+}
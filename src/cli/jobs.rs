@@ -0,0 +1,365 @@
+use std::{
+    collections::HashMap,
+    io::{Read, Seek, SeekFrom, Write},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use inquire::Confirm;
+use serde_json::json;
+
+use crate::{
+    cli::commands::JobsCommands,
+    core::{jobs, Job, JobProgress, JobQueue, JobState, PlanStep},
+    tr,
+    utils::{
+        duration::format_age,
+        output::{Column, OutputFormat, Table},
+        Config, InstalledServer,
+    },
+};
+
+/// Interval to check the log and the state of an attached job
+const ATTACH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Handle the `jobs` command
+///
+/// # Arguments
+///
+/// * `command` - The jobs subcommand to run
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command was successful, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the job does not exist or the job queue could not be read or saved
+pub async fn handle_jobs_command(command: JobsCommands) -> Result<(), Box<dyn std::error::Error>> {
+    match command {
+        JobsCommands::Run {
+            priority,
+            after,
+            args,
+        } => {
+            let job = JobQueue::submit(args, priority, after)?;
+            println!(
+                "{}",
+                tr!("job-submitted", id = job.id, command = job.command())
+            );
+            println!("{}", tr!("job-daemon-hint"));
+        }
+        JobsCommands::Update {
+            servers,
+            force,
+            yes,
+            staged,
+        } => update(servers, force, yes, staged)?,
+        JobsCommands::List { output } => list(output)?,
+        JobsCommands::Status { id } => status(JobQueue::load()?.get(id)?)?,
+        JobsCommands::Cancel { id } => {
+            JobQueue::cancel(id)?;
+            println!("{}", tr!("job-cancelled", id = id));
+        }
+        JobsCommands::Attach { id } => attach(id).await?,
+        JobsCommands::Exec { id } => {
+            let state = JobQueue::execute(id)?;
+            if state != JobState::Succeeded {
+                return Err(tr!("job-finished", id = id, state = state.label()).into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Plan the updates of several servers and queue them as jobs
+///
+/// # Arguments
+///
+/// * `names` - The servers to update, all if empty
+/// * `force` - Update even if workshop mods are likely incompatible
+/// * `yes` - Queue the updates without asking for confirmation
+/// * `staged` - Stage the updates instead of updating in place
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the updates were queued or the plan was declined
+///
+/// # Errors
+///
+/// Returns an error if a server or dependency does not exist, the dependencies
+/// form a cycle or the jobs could not be queued
+fn update(
+    names: Vec<String>,
+    force: bool,
+    yes: bool,
+    staged: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let servers: Vec<&InstalledServer> = if names.is_empty() {
+        config.installed_servers.iter().collect()
+    } else {
+        names
+            .iter()
+            .map(|name| {
+                config
+                    .find_server(name)
+                    .ok_or_else(|| tr!("server-not-installed", server = name))
+            })
+            .collect::<Result<_, _>>()?
+    };
+    for server in &servers {
+        if let Some(dependency) = server
+            .update_after
+            .iter()
+            .find(|name| config.find_server(name).is_none())
+        {
+            return Err(tr!(
+                "job-plan-unknown-dependency",
+                server = server.name,
+                dependency = dependency
+            )
+            .into());
+        }
+    }
+
+    let steps = jobs::plan(servers.into_iter().map(PlanStep::update).collect())?;
+    if steps.is_empty() {
+        println!("{}", tr!("job-plan-empty"));
+        return Ok(());
+    }
+
+    let mut table = Table::new(vec![
+        Column::new("step", tr!("jobs-header-step"), 6),
+        Column::new("server", tr!("list-header-server"), 30),
+        Column::new("priority", tr!("jobs-header-priority"), 10),
+        Column::new("after", tr!("jobs-header-after"), 30),
+    ]);
+    for (index, step) in steps.iter().enumerate() {
+        table.push(vec![
+            json!(index + 1),
+            json!(step.name),
+            json!(step.priority),
+            json!(step.after.join(", ")),
+        ]);
+    }
+    println!("{}", tr!("job-plan-title"));
+    println!("{}", table.render(OutputFormat::Table)?);
+
+    if !yes
+        && !Confirm::new(&tr!("job-plan-confirm", count = steps.len()))
+            .with_default(true)
+            .prompt()?
+    {
+        println!("{}", tr!("job-plan-cancelled"));
+        return Ok(());
+    }
+
+    let mut ids: HashMap<&str, u64> = HashMap::new();
+    for step in &steps {
+        let mut args = vec![
+            "update".to_string(),
+            "--server-name".to_string(),
+            step.name.clone(),
+            "--yes".to_string(),
+        ];
+        if force {
+            args.push("--force".to_string());
+        }
+        if staged {
+            args.push("--staged".to_string());
+        }
+        let after = step.after.iter().map(|name| ids[name.as_str()]).collect();
+        let job = JobQueue::submit(args, step.priority, after)?;
+        println!(
+            "{}",
+            tr!("job-submitted", id = job.id, command = job.command())
+        );
+        ids.insert(&step.name, job.id);
+    }
+    println!("{}", tr!("job-daemon-hint"));
+
+    Ok(())
+}
+
+/// List the jobs
+///
+/// # Arguments
+///
+/// * `format` - The output format
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the jobs were listed
+///
+/// # Errors
+///
+/// Returns an error if the job queue could not be read
+fn list(format: OutputFormat) -> Result<(), Box<dyn std::error::Error>> {
+    let queue = JobQueue::load()?;
+    if queue.jobs().is_empty() && format == OutputFormat::Table {
+        println!("{}", tr!("jobs-empty"));
+        return Ok(());
+    }
+
+    let mut table = Table::new(vec![
+        Column::new("id", tr!("jobs-header-id"), 6),
+        Column::new("state", tr!("jobs-header-state"), 14),
+        Column::new("created", tr!("jobs-header-created"), 18),
+        Column::new("duration", tr!("jobs-header-duration"), 10),
+        Column::new("command", tr!("jobs-header-command"), 40),
+    ]);
+    for job in queue.jobs() {
+        let duration = job
+            .started
+            .map(|started| format_age(job.finished.unwrap_or_else(Utc::now) - started))
+            .unwrap_or_else(|| "-".to_string());
+        table.push(vec![
+            json!(job.id),
+            json!(job.state.label()),
+            json!(format_time(job.created)),
+            json!(duration),
+            json!(job.command()),
+        ]);
+    }
+
+    println!("{}", table.render(format)?);
+    Ok(())
+}
+
+/// Show the details of a job
+///
+/// # Arguments
+///
+/// * `job` - The job
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the details were shown
+///
+/// # Errors
+///
+/// Returns an error if the steamserv directory could not be found
+fn status(job: &Job) -> Result<(), Box<dyn std::error::Error>> {
+    let time = |time: Option<DateTime<Utc>>| time.map(format_time).unwrap_or("-".to_string());
+
+    println!("{:<12} {}", tr!("job-status-id"), job.id);
+    println!("{:<12} {}", tr!("job-status-command"), job.command());
+    println!("{:<12} {}", tr!("job-status-state"), job.state.label());
+    println!(
+        "{:<12} {}",
+        tr!("job-status-created"),
+        format_time(job.created)
+    );
+    println!("{:<12} {}", tr!("job-status-started"), time(job.started));
+    println!("{:<12} {}", tr!("job-status-finished"), time(job.finished));
+    if let Some(code) = job.exit_code {
+        println!("{:<12} {}", tr!("job-status-exit-code"), code);
+    }
+    if let Some(reason) = &job.reason {
+        println!("{:<12} {}", tr!("job-status-reason"), reason);
+    }
+    println!("{:<12} {}", tr!("job-status-priority"), job.priority);
+    if !job.after.is_empty() {
+        let after: Vec<String> = job.after.iter().map(|id| format!("#{}", id)).collect();
+        println!("{:<12} {}", tr!("job-status-after"), after.join(", "));
+    }
+    if let Some(progress) = JobProgress::load(job)
+        .filter(|_| job.state == JobState::Running)
+        .and_then(|progress| progress.describe())
+    {
+        println!("{:<12} {}", tr!("job-status-progress"), progress);
+    }
+    println!(
+        "{:<12} {}",
+        tr!("job-status-log"),
+        job.log_file()?.display()
+    );
+
+    Ok(())
+}
+
+/// Print the output of a job as it is written until the job has finished
+///
+/// If the job saved a checkpoint, its finished phases and the current progress
+/// are shown and the log is followed from the start of the last phase.
+///
+/// # Arguments
+///
+/// * `id` - The ID of the job
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the job has finished
+///
+/// # Errors
+///
+/// Returns an error if the job does not exist or its log could not be read
+async fn attach(id: u64) -> Result<(), Box<dyn std::error::Error>> {
+    let job = JobQueue::load()?.get(id)?.clone();
+    let log_file = job.log_file()?;
+    println!("{}", tr!("job-attach-hint"));
+
+    let mut offset = 0;
+    if let Some(progress) = JobProgress::load(&job).filter(|_| !job.state.is_finished()) {
+        if let Some(operation) = &progress.operation {
+            println!("{}", operation);
+        }
+        for (phase, seconds) in &progress.finished {
+            println!(
+                "{}",
+                tr!(
+                    "event-phase-finished",
+                    phase = phase.name(),
+                    seconds = format!("{:.1}", seconds)
+                )
+            );
+        }
+        if let Some(current) = progress.describe() {
+            println!("{}", current);
+        }
+        if progress.offset > 0 {
+            println!(
+                "{}",
+                tr!("job-attach-skipped", log = log_file.display().to_string())
+            );
+            offset = progress.offset;
+        }
+    }
+
+    loop {
+        // The state is read before the log, so the last output is not missed
+        let state = JobQueue::load()?.get(id)?.state;
+
+        if let Ok(mut file) = std::fs::File::open(&log_file) {
+            file.seek(SeekFrom::Start(offset))?;
+            let mut content = Vec::new();
+            file.read_to_end(&mut content)?;
+            offset += content.len() as u64;
+            std::io::stdout().write_all(&content)?;
+            std::io::stdout().flush()?;
+        }
+
+        if state.is_finished() {
+            println!("{}", tr!("job-finished", id = id, state = state.label()));
+            return Ok(());
+        }
+        tokio::time::sleep(ATTACH_INTERVAL).await;
+    }
+}
+
+/// Format a time of a job in the local timezone
+///
+/// # Arguments
+///
+/// * `time` - The time
+///
+/// # Returns
+///
+/// The formatted time
+fn format_time(time: DateTime<Utc>) -> String {
+    time.with_timezone(&chrono::Local)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
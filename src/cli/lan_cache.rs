@@ -0,0 +1,109 @@
+use std::{
+    net::{IpAddr, ToSocketAddrs},
+    time::Duration,
+};
+
+use crate::{
+    tr,
+    utils::{
+        privilege::{self, Escalation},
+        Config,
+    },
+};
+
+/// Header a lancache adds to the responses it served
+const LANCACHE_HEADER: &str = "x-lancache-processed-by";
+
+/// Handle the `lan-cache` command
+///
+/// The cache has to answer its heartbeat, and the CDN hostnames have to
+/// resolve to it, as SteamCMD only downloads through a lancache it finds by DNS.
+///
+/// # Arguments
+///
+/// * `apply` - Pin the CDN hostnames that do not resolve to the cache in /etc/hosts
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the cache was checked and, with `apply`, the hostnames pinned
+///
+/// # Errors
+///
+/// Returns an error if no lancache is configured, its address is invalid,
+/// network access is disabled, or `apply` is set and /etc/hosts could not be changed
+pub async fn handle_lan_cache_command(apply: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    config.ensure_online(&tr!("action-lancache-check"))?;
+    let cache = &config.network.depot_cache;
+    let address = cache
+        .lancache
+        .as_deref()
+        .ok_or_else(|| tr!("lancache-not-configured"))?;
+    let ip: IpAddr = address
+        .parse()
+        .map_err(|_| tr!("lancache-invalid-address", address = address))?;
+
+    // The cache is on the LAN, so the proxy of the config is not used
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+    match client
+        .get(format!("http://{}/lancache-heartbeat", address))
+        .send()
+        .await
+    {
+        Ok(response) if response.headers().contains_key(LANCACHE_HEADER) => {
+            println!("{}", tr!("lancache-heartbeat-ok", address = address));
+        }
+        Ok(_) => println!("{}", tr!("lancache-heartbeat-foreign", address = address)),
+        Err(e) => println!(
+            "{}",
+            tr!(
+                "lancache-heartbeat-failed",
+                address = address,
+                error = e.to_string()
+            )
+        ),
+    }
+
+    let mut missing = Vec::new();
+    for host in &cache.cdn_hosts {
+        let resolved: Vec<IpAddr> = (host.as_str(), 80)
+            .to_socket_addrs()
+            .map(|addrs| addrs.map(|a| a.ip()).collect())
+            .unwrap_or_default();
+        if resolved.contains(&ip) {
+            println!("{}", tr!("lancache-host-ok", host = host.as_str()));
+        } else {
+            let resolved = match resolved.is_empty() {
+                true => "-".to_string(),
+                false => resolved
+                    .iter()
+                    .map(IpAddr::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            };
+            println!(
+                "{}",
+                tr!(
+                    "lancache-host-elsewhere",
+                    host = host.as_str(),
+                    resolved = resolved
+                )
+            );
+            missing.push(host.as_str());
+        }
+    }
+
+    if missing.is_empty() {
+        println!("{}", tr!("lancache-ready"));
+    } else if apply {
+        privilege::escalate(&[Escalation::append_hosts(address, &missing)])?;
+        println!("{}", tr!("lancache-pinned", count = missing.len()));
+    } else {
+        println!("{}", tr!("lancache-apply-hint"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use crate::{
+    core::LaunchCommand,
+    utils::{Config, ServerRef},
+};
+
+/// Handle the `launch-command` command
+///
+/// # Arguments
+///
+/// * `target` - The server or instance in the form `server/instance`
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command was successful, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the server has no game profile or does not exist
+pub fn handle_launch_command(target: String) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let (server, instance) = config.resolve(&ServerRef::parse(&target))?;
+
+    let command = LaunchCommand::build(&config, server, instance)?;
+    println!("{}", command);
+
+    Ok(())
+}
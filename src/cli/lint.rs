@@ -0,0 +1,57 @@
+use crate::{
+    core::LintReport,
+    tr,
+    utils::{Config, ServerRef},
+};
+
+/// Handle the `lint` command
+///
+/// A server with instances is checked per instance, every instance has its own
+/// config file.
+///
+/// # Arguments
+///
+/// * `target` - The server, or a single instance in the form `server/instance`
+///
+/// # Returns
+///
+/// Returns `Ok(())` if no config file has errors
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist, has no game profile or a
+/// config file has errors
+pub fn handle_lint_command(target: String) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let (server, instance) = config.resolve(&ServerRef::parse(&target))?;
+
+    let targets: Vec<_> = match instance {
+        Some(instance) => vec![Some(instance)],
+        None if server.instances.is_empty() => vec![None],
+        None => server.instances.iter().map(Some).collect(),
+    };
+
+    let mut failed = false;
+    for (i, instance) in targets.into_iter().enumerate() {
+        let report = LintReport::check(server, instance)?;
+        let name = match instance {
+            Some(instance) => format!("{}/{}", server.name, instance.name),
+            None => server.name.clone(),
+        };
+
+        if i > 0 {
+            println!();
+        }
+        match &report.file {
+            Some(file) => println!("{}", tr!("lint-target", target = name, file = file)),
+            None => println!("{}", tr!("lint-target-no-schema", target = name)),
+        }
+        println!("{}", report);
+        failed |= report.has_errors();
+    }
+
+    if failed {
+        return Err(tr!("lint-failed", target = target).into());
+    }
+    Ok(())
+}
@@ -1,11 +1,23 @@
 use std::path::PathBuf;
 
-use crate::utils::{Config, InstalledServer, ServerCache, ServerInfo};
+use chrono::Utc;
+use serde_json::json;
+
+use crate::{
+    tr,
+    utils::{
+        disk, duration,
+        notify::notify,
+        output::{Column, OutputFormat, Table},
+        terminal, Config, InstalledServer, ServerCache, ServerInfo,
+    },
+};
 
 trait ServerDisplay {
     fn get_app_id(&self) -> u32;
     fn get_name(&self) -> &str;
     fn get_path(&self) -> Option<&PathBuf>;
+    fn get_owner(&self) -> Option<&str>;
 }
 
 impl ServerDisplay for ServerInfo {
@@ -18,6 +30,9 @@ impl ServerDisplay for ServerInfo {
     fn get_path(&self) -> Option<&PathBuf> {
         None
     }
+    fn get_owner(&self) -> Option<&str> {
+        None
+    }
 }
 
 impl ServerDisplay for InstalledServer {
@@ -30,6 +45,9 @@ impl ServerDisplay for InstalledServer {
     fn get_path(&self) -> Option<&PathBuf> {
         Some(&self.install_path)
     }
+    fn get_owner(&self) -> Option<&str> {
+        self.owner.as_deref()
+    }
 }
 
 enum ServerType {
@@ -43,6 +61,11 @@ enum ServerType {
 ///
 /// * `installed` - Show installed servers
 /// * `filter` - Filter the list of servers
+/// * `owner` - Only show installed servers of this owner
+/// * `not_updated_for` - Only show installed servers not updated in this period, e.g. `30d`
+/// * `refresh` - Update the server cache first, it is also updated when it is outdated
+/// * `format` - The output format
+/// * `watch` - Show the installed servers again every this many seconds until Ctrl+C
 ///
 /// # Returns
 ///
@@ -54,22 +77,115 @@ enum ServerType {
 pub async fn handle_list_command(
     installed: bool,
     filter: Option<String>,
+    owner: Option<String>,
+    not_updated_for: Option<String>,
+    refresh: bool,
+    format: OutputFormat,
+    watch: Option<u64>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    if installed {
+    if let Some(period) = not_updated_for {
+        let period = duration::parse_duration(&period)
+            .ok_or_else(|| tr!("period-invalid", period = period))?;
+        let servers = Config::store()?.servers_updated_before(Utc::now() - period)?;
+        list_installed_servers(&servers, filter, owner.as_deref(), format)
+    } else if let (true, Some(interval)) = (installed, watch) {
+        terminal::watch(interval, || {
+            let config = Config::load()?;
+            list_installed_servers(
+                &config.installed_servers,
+                filter.clone(),
+                owner.as_deref(),
+                format,
+            )
+        })
+    } else if installed {
         let config = Config::load()?;
-        list_installed_servers(&config.installed_servers, filter)
+        list_installed_servers(&config.installed_servers, filter, owner.as_deref(), format)?;
+        if format == OutputFormat::Table {
+            disk::warn(&config)?;
+        }
+        Ok(())
     } else {
-        let cache = ServerCache::load()?;
-        list_available_servers(&cache.servers, filter)
+        let config = Config::load()?;
+        let mut cache = ServerCache::load()?;
+        if refresh || (cache.is_stale() && !config.is_offline()) {
+            config.ensure_online(&tr!("action-update-cache"))?;
+            let new = cache.update_cache().await?;
+            list_available_servers(&cache.servers, filter, format)?;
+            return report_new_servers(&config, &new, format).await;
+        }
+        if cache.is_stale() && format == OutputFormat::Table {
+            println!(
+                "{}",
+                tr!(
+                    "list-offline-stale",
+                    date = cache.last_checked().format("%Y-%m-%d")
+                )
+            );
+        }
+        list_available_servers(&cache.servers, filter, format)
     }
 }
 
+/// Show the servers that are new since the last cache update and notify about them
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+/// * `servers` - The new servers
+/// * `format` - The output format, the servers are only shown in the terminal table
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the servers were reported
+///
+/// # Errors
+///
+/// Returns an error if the notification could not be sent
+async fn report_new_servers(
+    config: &Config,
+    servers: &[ServerInfo],
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if servers.is_empty() {
+        return Ok(());
+    }
+
+    if format == OutputFormat::Table {
+        println!();
+        println!("{}", tr!("list-new-servers", count = servers.len()));
+        for server in servers {
+            println!("  {:<10} {}", server.app_id, server.name);
+        }
+    }
+
+    if config.notifications.new_servers {
+        let names = servers
+            .iter()
+            .map(|s| format!("{} ({})", s.name, s.app_id))
+            .collect::<Vec<_>>()
+            .join(", ");
+        notify(
+            config,
+            &tr!(
+                "list-new-servers-notification",
+                count = servers.len(),
+                servers = names
+            ),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
 /// List the available servers
 ///
 /// # Arguments
 ///
 /// * `servers` - The list of available servers
 /// * `filter` - Filter the list of servers
+/// * `format` - The output format
 ///
 /// # Returns
 ///
@@ -81,17 +197,23 @@ pub async fn handle_list_command(
 fn list_available_servers(
     servers: &[ServerInfo],
     filter: Option<String>,
+    format: OutputFormat,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let filtered: Vec<_> = if let Some(filter) = filter {
+        let filter = filter.to_lowercase();
         servers
             .iter()
-            .filter(|s| s.name.to_lowercase().contains(&filter.to_lowercase()))
+            .filter(|s| {
+                std::iter::once(&s.name)
+                    .chain(&s.aliases)
+                    .any(|name| name.to_lowercase().contains(&filter))
+            })
             .collect()
     } else {
         servers.iter().collect()
     };
 
-    display_output(ServerType::Available, &filtered)?;
+    display_output(ServerType::Available, &filtered, format)?;
 
     Ok(())
 }
@@ -102,6 +224,8 @@ fn list_available_servers(
 ///
 /// * `servers` - The list of installed servers
 /// * `filter` - Filter the list of servers
+/// * `owner` - Only show the servers of this owner
+/// * `format` - The output format
 ///
 /// # Returns
 ///
@@ -113,17 +237,19 @@ fn list_available_servers(
 fn list_installed_servers(
     servers: &[InstalledServer],
     filter: Option<String>,
+    owner: Option<&str>,
+    format: OutputFormat,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    let servers = servers.iter().filter(|s| s.is_owned_by(owner));
     let filtered: Vec<_> = if let Some(filter) = filter {
         servers
-            .iter()
             .filter(|s| s.name.to_lowercase().contains(&filter.to_lowercase()))
             .collect()
     } else {
-        servers.iter().collect()
+        servers.collect()
     };
 
-    display_output(ServerType::Installed, &filtered)?;
+    display_output(ServerType::Installed, &filtered, format)?;
 
     Ok(())
 }
@@ -134,6 +260,7 @@ fn list_installed_servers(
 ///
 /// * `server_type` - The type of server to display
 /// * `servers` - The list of servers to display
+/// * `format` - The output format
 ///
 /// # Returns
 ///
@@ -145,34 +272,38 @@ fn list_installed_servers(
 fn display_output<T: ServerDisplay>(
     server_type: ServerType,
     servers: &[&T],
+    format: OutputFormat,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    match server_type {
+    let mut columns = vec![
+        Column::new("app_id", tr!("list-header-app-id"), 10),
+        Column::new("name", tr!("list-header-name"), 50),
+    ];
+    let title = match server_type {
         ServerType::Installed => {
-            println!("Installed Servers:");
-            println!("{:<10} {:<50} {:<80}", "APP ID", "NAME", "PATH");
-            println!("{:-<140}", "");
-
-            for server in servers {
-                if let Some(path) = server.get_path() {
-                    println!(
-                        "{:<10} {:<50} {:<80}",
-                        server.get_app_id(),
-                        server.get_name(),
-                        path.display()
-                    );
-                }
-            }
+            columns.push(Column::new("path", tr!("list-header-path"), 80));
+            columns.push(Column::new("owner", tr!("list-header-owner"), 20));
+            tr!("list-installed-title")
         }
-        ServerType::Available => {
-            println!("Available Servers:");
-            println!("{:<10} {:<50}", "APP ID", "NAME");
-            println!("{:-60}", "");
-
-            for server in servers {
-                println!("{:<10} {:<50}", server.get_app_id(), server.get_name());
-            }
+        ServerType::Available => tr!("list-available-title"),
+    };
+
+    let mut table = Table::new(columns);
+    for server in servers {
+        let mut row = vec![json!(server.get_app_id()), json!(server.get_name())];
+        if let ServerType::Installed = server_type {
+            let Some(path) = server.get_path() else {
+                continue;
+            };
+            row.push(json!(path.display().to_string()));
+            row.push(json!(server.get_owner()));
         }
+        table.push(row);
+    }
+
+    if format == OutputFormat::Table {
+        println!("{}", title);
     }
+    println!("{}", table.render(format)?);
 
     Ok(())
 }
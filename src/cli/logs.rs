@@ -0,0 +1,137 @@
+use std::time::SystemTime;
+
+use regex::Regex;
+use serde_json::json;
+
+use crate::{
+    cli::commands::LogsCommands,
+    core::{logs, LogPrune},
+    tr,
+    utils::{
+        duration, fs,
+        output::{Column, OutputFormat, Table},
+        runtime, Config, ServerRef,
+    },
+};
+
+/// Start and end of a highlighted match in the terminal
+const HIGHLIGHT: (&str, &str) = ("\x1b[1;31m", "\x1b[0m");
+
+/// Handle the `logs` command
+///
+/// # Arguments
+///
+/// * `command` - The logs subcommand to run
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command was successful, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if a size in the log settings is invalid, a log could not
+/// be rotated, deleted or read, or the server, the pattern or the period is invalid
+pub fn handle_logs_command(command: LogsCommands) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+
+    match command {
+        LogsCommands::Prune { dry_run } => {
+            let prune = LogPrune::run(&config.logs, dry_run)?;
+            let (rotated, removed, freed) = if dry_run {
+                ("logs-would-rotate", "logs-would-remove", "logs-would-prune")
+            } else {
+                ("logs-rotated", "logs-removed", "logs-pruned")
+            };
+            for path in &prune.rotated {
+                println!("{}", tr!(rotated, path = path.display()));
+            }
+            for path in &prune.removed {
+                println!("{}", tr!(removed, path = path.display()));
+            }
+            println!("{}", tr!(freed, size = fs::format_size(prune.freed)));
+        }
+        LogsCommands::Search {
+            target,
+            pattern,
+            since,
+            output,
+        } => {
+            let target = ServerRef::parse(&target);
+            config.resolve(&target)?;
+            let regex = Regex::new(&pattern).map_err(|e| {
+                tr!(
+                    "logs-pattern-invalid",
+                    pattern = pattern.as_str(),
+                    error = e
+                )
+            })?;
+            let since = match since {
+                Some(since) => {
+                    let period = duration::parse_duration(&since)
+                        .ok_or_else(|| tr!("period-invalid", period = since))?;
+                    Some(SystemTime::now() - period.to_std()?)
+                }
+                None => None,
+            };
+
+            let matches = logs::search(&target, &regex, since)?;
+            if output != OutputFormat::Table {
+                let mut table = Table::new(vec![
+                    Column::new("file", tr!("logs-header-file"), 36),
+                    Column::new("line_number", tr!("logs-header-line-number"), 8),
+                    Column::new("line", tr!("logs-header-line"), 80),
+                ]);
+                for found in &matches {
+                    table.push(vec![
+                        json!(found.file.display().to_string()),
+                        json!(found.line_number),
+                        json!(found.line),
+                    ]);
+                }
+                println!("{}", table.render(output)?);
+                return Ok(());
+            }
+
+            if matches.is_empty() {
+                println!("{}", tr!("logs-search-none", target = target));
+                return Ok(());
+            }
+            for found in &matches {
+                let file = found
+                    .file
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                println!(
+                    "{}:{}: {}",
+                    file,
+                    found.line_number,
+                    highlight(&regex, &found.line)
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Highlight the matches of an expression in a line
+///
+/// # Arguments
+///
+/// * `regex` - The expression
+/// * `line` - The line
+///
+/// # Returns
+///
+/// The line with the matches in bold red, or unchanged in the plain output mode
+fn highlight(regex: &Regex, line: &str) -> String {
+    if runtime::is_plain() {
+        return line.to_string();
+    }
+    regex
+        .replace_all(line, |caps: &regex::Captures| {
+            format!("{}{}{}", HIGHLIGHT.0, &caps[0], HIGHLIGHT.1)
+        })
+        .to_string()
+}
@@ -0,0 +1,150 @@
+use chrono::Utc;
+
+use crate::{
+    cli::commands::MaintenanceCommands,
+    core::{GameProfile, ServerProcess},
+    tr,
+    utils::{history, Config, HistoryEvent, Maintenance, ServerRef},
+};
+
+/// Handle the `maintenance` command
+///
+/// # Arguments
+///
+/// * `command` - The maintenance subcommand to run
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command was successful, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist or could not be stopped or started
+pub fn handle_maintenance_command(
+    command: MaintenanceCommands,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match command {
+        MaintenanceCommands::On {
+            server,
+            reason,
+            placeholder,
+        } => maintenance_on(&server, reason, placeholder),
+        MaintenanceCommands::Off { server } => maintenance_off(&server),
+    }
+}
+
+/// Stop a server and its instances and put it into maintenance
+///
+/// # Arguments
+///
+/// * `name` - The name of the server
+/// * `reason` - The reason for the maintenance
+/// * `placeholder` - Start the server with the maintenance arguments of its game profile
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the server is in maintenance
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist, is already in maintenance or
+/// has no maintenance arguments for a placeholder
+fn maintenance_on(
+    name: &str,
+    reason: Option<String>,
+    placeholder: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = Config::load()?;
+    let server = config
+        .find_server(name)
+        .ok_or_else(|| tr!("server-not-installed", server = name))?;
+
+    if server.maintenance.is_some() {
+        return Err(tr!("maintenance-already-on", server = name).into());
+    }
+    if placeholder
+        && GameProfile::find(server.app_id)
+            .is_none_or(|profile| profile.maintenance_args.is_empty())
+    {
+        return Err(tr!("maintenance-placeholder-unsupported", server = name).into());
+    }
+
+    let mut stopped = Vec::new();
+    for instance in std::iter::once(None).chain(server.instances.iter().map(Some)) {
+        let target = match instance {
+            Some(i) => ServerRef::parse(&format!("{}/{}", server.name, i.name)),
+            None => ServerRef::parse(&server.name),
+        };
+        if ServerProcess::new(&target)?.stop(server, instance)? {
+            stopped.push(target.to_string());
+        }
+    }
+
+    let server = config.find_server_mut(name).unwrap();
+    server.maintenance = Some(Maintenance {
+        since: Utc::now(),
+        reason: reason.clone(),
+        placeholder,
+        stopped,
+    });
+    config.save()?;
+
+    history::record(&HistoryEvent::new(
+        name,
+        "maintenance-on",
+        reason.as_deref().unwrap_or_default(),
+    ))?;
+    println!("{}", tr!("maintenance-on", server = name));
+
+    if placeholder {
+        let server = config.find_server(name).unwrap();
+        let process = ServerProcess::new(&ServerRef::parse(name))?;
+        let pid = process.start(&config, server, None)?;
+        println!("{}", tr!("maintenance-placeholder-started", pid = pid));
+    }
+
+    Ok(())
+}
+
+/// End the maintenance of a server and start the servers that were stopped for it
+///
+/// # Arguments
+///
+/// * `name` - The name of the server
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the maintenance was ended
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist, is not in maintenance or
+/// could not be started again
+fn maintenance_off(name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = Config::load()?;
+    let server = config
+        .find_server(name)
+        .ok_or_else(|| tr!("server-not-installed", server = name))?;
+    let maintenance = server
+        .maintenance
+        .clone()
+        .ok_or_else(|| tr!("maintenance-not-on", server = name))?;
+
+    if maintenance.placeholder {
+        ServerProcess::new(&ServerRef::parse(name))?.stop(server, None)?;
+    }
+
+    config.find_server_mut(name).unwrap().maintenance = None;
+    config.save()?;
+
+    history::record(&HistoryEvent::new(name, "maintenance-off", ""))?;
+    println!("{}", tr!("maintenance-off", server = name));
+
+    for target in &maintenance.stopped {
+        let target = ServerRef::parse(target);
+        let (server, instance) = config.resolve(&target)?;
+        ServerProcess::new(&target)?.start(&config, server, instance)?;
+    }
+
+    Ok(())
+}
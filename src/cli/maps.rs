@@ -0,0 +1,149 @@
+use serde_json::json;
+
+use crate::{
+    cli::commands::MapsCommands,
+    core::{maps, ServerProcess},
+    tr,
+    utils::{
+        output::{Column, OutputFormat, Table},
+        schedule::Schedule,
+        Config, ServerRef,
+    },
+};
+
+/// Handle the `maps` command
+///
+/// # Arguments
+///
+/// * `command` - The maps subcommand to run
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command was successful, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the server or instance does not exist, the map or the
+/// schedule is invalid, the map could not be changed or the config could not be saved
+pub fn handle_maps_command(command: MapsCommands) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = Config::load()?;
+
+    match command {
+        MapsCommands::List { target, output } => {
+            let target = ServerRef::parse(&target);
+            let (server, instance) = config.resolve(&target)?;
+            let Some(rotation) = instance
+                .map_or(&server.maps, |i| &i.maps)
+                .as_ref()
+                .filter(|rotation| !rotation.maps.is_empty())
+            else {
+                println!("{}", tr!("maps-none", target = target));
+                return Ok(());
+            };
+
+            let next = maps::next_map(rotation).map(|(index, _)| index);
+            let mut table = Table::new(vec![
+                Column::new("position", tr!("maps-header-position"), 4),
+                Column::new("map", tr!("maps-header-map"), 30),
+                Column::new("state", tr!("maps-header-state"), 10),
+            ]);
+            for (index, map) in rotation.maps.iter().enumerate() {
+                let state = if index == rotation.current {
+                    tr!("maps-state-current")
+                } else if Some(index) == next {
+                    tr!("maps-state-next")
+                } else {
+                    String::new()
+                };
+                table.push(vec![json!(index + 1), json!(map), json!(state)]);
+            }
+            println!("{}", table.render(output)?);
+
+            if output == OutputFormat::Table {
+                match &rotation.schedule {
+                    Some(schedule) => {
+                        println!("{}", tr!("maps-schedule", schedule = schedule.as_str()))
+                    }
+                    None => println!("{}", tr!("maps-schedule-none")),
+                }
+            }
+        }
+        MapsCommands::Add {
+            target,
+            maps,
+            position,
+        } => {
+            let target = ServerRef::parse(&target);
+            let rotation =
+                maps::rotation_mut(&mut config, &target)?.get_or_insert_with(Default::default);
+            maps::add_maps(rotation, maps, position);
+            let count = rotation.maps.len();
+            config.save()?;
+            println!("{}", tr!("maps-added", target = target, count = count));
+        }
+        MapsCommands::Remove { target, map } => {
+            let target = ServerRef::parse(&target);
+            let rotation = maps::rotation_mut(&mut config, &target)?;
+            if !rotation
+                .as_mut()
+                .is_some_and(|rotation| maps::remove_map(rotation, &map))
+            {
+                return Err(tr!("maps-not-found", map = map.as_str(), target = target).into());
+            }
+            config.save()?;
+            println!(
+                "{}",
+                tr!("maps-removed", map = map.as_str(), target = target)
+            );
+        }
+        MapsCommands::Rotate { target } => {
+            let target = ServerRef::parse(&target);
+            config.resolve(&target)?;
+            if !ServerProcess::new(&target)?.is_running() {
+                return Err(tr!("process-not-running", target = target).into());
+            }
+            let map = maps::rotate(&mut config, &target)?;
+            println!(
+                "{}",
+                tr!("maps-rotated", target = target, map = map.as_str())
+            );
+        }
+        MapsCommands::Schedule {
+            target,
+            schedule,
+            message,
+        } => {
+            let target = ServerRef::parse(&target);
+            if let Some(schedule) = &schedule {
+                if Schedule::parse(schedule).is_none() {
+                    return Err(tr!(
+                        "schedule-invalid",
+                        target = target,
+                        schedule = schedule.as_str()
+                    )
+                    .into());
+                }
+            }
+            let rotation =
+                maps::rotation_mut(&mut config, &target)?.get_or_insert_with(Default::default);
+            rotation.schedule = schedule.clone();
+            if message.is_some() {
+                rotation.message = message;
+            }
+            config.save()?;
+            match schedule {
+                Some(schedule) => println!(
+                    "{}",
+                    tr!(
+                        "maps-schedule-set",
+                        target = target,
+                        schedule = schedule.as_str()
+                    )
+                ),
+                None => println!("{}", tr!("maps-schedule-cleared", target = target)),
+            }
+        }
+    }
+
+    Ok(())
+}
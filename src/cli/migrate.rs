@@ -0,0 +1,168 @@
+use std::path::PathBuf;
+
+use chrono::Utc;
+use inquire::Confirm;
+
+use crate::{
+    core::{ServerProcess, StagedUpdate},
+    tr,
+    utils::{history, Config, HistoryEvent, Pause, ServerRef},
+};
+
+/// Handle the `migrate` command
+///
+/// The server and its instances are stopped, the install directory is copied
+/// to the host with rsync and the server is registered and started there. The
+/// local copy is paused, or removed with `remove_local`, only after the server
+/// started on the host.
+///
+/// # Arguments
+///
+/// * `server_name` - The name of the game server
+/// * `to` - The remote host from the config
+/// * `path` - The install directory on the host, the same as here if not given
+/// * `remove_local` - Remove the local copy once the server runs on the host
+/// * `yes` - Move the server without asking for confirmation
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the server runs on the host, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the server or the host does not exist, or the copy,
+/// the registration or the start on the host failed
+pub fn handle_migrate_command(
+    server_name: String,
+    to: String,
+    path: Option<PathBuf>,
+    remove_local: bool,
+    yes: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = Config::load()?;
+    let host = config
+        .remotes
+        .get(&to)
+        .cloned()
+        .ok_or_else(|| tr!("remote-not-found", host = to.as_str()))?;
+    let server = config
+        .find_server(&server_name)
+        .cloned()
+        .ok_or_else(|| tr!("server-not-installed", server = server_name.as_str()))?;
+    let target_path = path.unwrap_or_else(|| server.install_path.clone());
+
+    if !yes
+        && !Confirm::new(&tr!(
+            "migrate-confirm",
+            server = server_name.as_str(),
+            host = to.as_str(),
+            path = target_path.display().to_string()
+        ))
+        .with_default(false)
+        .prompt()?
+    {
+        println!("{}", tr!("confirm-cancelled"));
+        return Ok(());
+    }
+
+    for instance in std::iter::once(None).chain(server.instances.iter().map(Some)) {
+        let target = match instance {
+            Some(i) => ServerRef::parse(&format!("{}/{}", server.name, i.name)),
+            None => ServerRef::parse(&server.name),
+        };
+        ServerProcess::new(&target)?.stop(&server, instance)?;
+    }
+
+    println!(
+        "{}",
+        tr!(
+            "migrate-sync",
+            server = server_name.as_str(),
+            host = to.as_str()
+        )
+    );
+    host.sync(&server.install_path, &target_path)?;
+
+    let mut remote = server.clone();
+    remote.install_path = target_path;
+    remote.paused = None;
+    let entry = toml::to_string(&remote)?;
+    let status = host.run_with_input(
+        &[
+            "import".to_string(),
+            "--from-server".to_string(),
+            "-".to_string(),
+        ],
+        &entry,
+    )?;
+    if !status.success() {
+        return Err(tr!(
+            "migrate-register-failed",
+            server = server_name.as_str(),
+            host = to.as_str()
+        )
+        .into());
+    }
+
+    println!(
+        "{}",
+        tr!(
+            "migrate-start",
+            server = server_name.as_str(),
+            host = to.as_str()
+        )
+    );
+    if !host
+        .run(&["start".to_string(), server_name.clone()])?
+        .success()
+    {
+        return Err(tr!(
+            "migrate-start-failed",
+            server = server_name.as_str(),
+            host = to.as_str()
+        )
+        .into());
+    }
+
+    let local = config
+        .installed_servers
+        .iter_mut()
+        .find(|s| s.name == server_name)
+        .ok_or_else(|| tr!("server-not-installed", server = server_name.as_str()))?;
+    if remove_local {
+        StagedUpdate::discard(local)?;
+        std::fs::remove_dir_all(&local.install_path)?;
+        config.installed_servers.retain(|s| s.name != server_name);
+    } else {
+        // The supervisor must not start the old copy again
+        local.paused = Some(Pause {
+            since: Utc::now(),
+            reason: Some(tr!("migrate-pause-reason", host = to.as_str())),
+        });
+    }
+    config.save()?;
+    history::record(&HistoryEvent::new(&server_name, "migrated", &to))?;
+
+    if remove_local {
+        println!(
+            "{}",
+            tr!(
+                "migrate-success-removed",
+                server = server_name.as_str(),
+                host = to.as_str()
+            )
+        );
+    } else {
+        println!(
+            "{}",
+            tr!(
+                "migrate-success",
+                server = server_name.as_str(),
+                host = to.as_str(),
+                path = server.install_path.display().to_string()
+            )
+        );
+    }
+
+    Ok(())
+}
@@ -1,13 +1,121 @@
+pub mod aliases;
+pub mod bench;
+pub mod bundle;
+pub mod clean;
+pub mod cluster;
 pub mod commands;
+pub mod config;
+pub mod daemon;
+pub mod deps;
+pub mod doctor;
+pub mod env;
+pub mod export;
+pub mod file_sync;
+pub mod game_config;
+pub mod history;
+pub mod import;
+pub mod info;
+pub mod install;
+pub mod instance;
+pub mod jobs;
+pub mod lan_cache;
+pub mod launch;
+pub mod lint;
 pub mod list;
+pub mod logs;
+pub mod maintenance;
+pub mod maps;
+pub mod migrate;
+pub mod mods;
+pub mod nat;
+pub mod new;
+pub mod owner;
+pub mod perms;
+pub mod players;
+pub mod profile;
+pub mod rcon;
+pub mod report;
+pub mod secrets;
+pub mod server;
+pub mod snapshots;
+pub mod stats;
+pub mod steamid;
+pub mod timezone;
+pub mod token;
+pub mod top;
+pub mod updates;
 
+pub use aliases::handle_aliases_command;
+pub use bench::handle_bench_command;
+pub use bundle::handle_bundle_command;
 use clap::Parser;
+pub use clean::handle_clean_command;
+pub use cluster::handle_cluster_command;
 use commands::Commands;
+
+use crate::utils::ProgressFormat;
+pub use config::handle_config_command;
+pub use daemon::handle_daemon_command;
+pub use deps::handle_deps_command;
+pub use doctor::handle_doctor_command;
+pub use env::{handle_args_command, handle_env_command};
+pub use export::handle_export_command;
+pub use file_sync::handle_sync_files_command;
+pub use game_config::{handle_config_get_command, handle_config_set_command};
+pub use history::handle_history_command;
+pub use import::handle_import_command;
+pub use info::handle_info_command;
+pub use install::handle_install_command;
+pub use instance::handle_instance_command;
+pub use jobs::handle_jobs_command;
+pub use lan_cache::handle_lan_cache_command;
+pub use launch::handle_launch_command;
+pub use lint::handle_lint_command;
 pub use list::handle_list_command;
+pub use logs::handle_logs_command;
+pub use maintenance::handle_maintenance_command;
+pub use maps::handle_maps_command;
+pub use migrate::handle_migrate_command;
+pub use mods::handle_mods_command;
+pub use nat::handle_nat_check_command;
+pub use new::handle_new_command;
+pub use owner::handle_owner_command;
+pub use perms::handle_fix_perms_command;
+pub use players::{handle_admins_command, handle_players_command};
+pub use profile::handle_profile_command;
+pub use rcon::handle_rcon_command;
+pub use report::handle_report_command;
+pub use secrets::handle_secrets_command;
+pub use server::{
+    handle_restart_command, handle_start_command, handle_status_command, handle_stop_command,
+};
+pub use snapshots::{handle_rollback_command, handle_snapshots_command};
+pub use stats::handle_stats_command;
+pub use steamid::handle_steamid_command;
+pub use timezone::handle_timezone_command;
+pub use token::handle_token_command;
+pub use top::handle_top_command;
+pub use updates::handle_check_updates_command;
 
 /// SteamCMD server management tool to install, update, and uninstall game servers.
 #[derive(Parser)]
 pub struct Cli {
+    /// Forbid all network calls and only use cached data
+    #[arg(long, global = true)]
+    pub offline: bool,
+    /// Print progress as plain text lines without spinners or redraws
+    #[arg(long, global = true)]
+    pub plain: bool,
+    /// Print the progress and the events as JSON lines for scripts
+    #[arg(long, global = true, value_enum, default_value_t = ProgressFormat::Text)]
+    pub progress: ProgressFormat,
+    /// Run the single steps that need root, like creating directories in /opt, through sudo
+    #[arg(long, global = true)]
+    pub sudo: bool,
+    /// Run the command on a remote host from the config over SSH
+    #[arg(long, global = true, value_name = "HOST")]
+    pub remote: Option<String>,
+    // Optional, so the first run sets up steamserv without a command
     #[command(subcommand)]
-    pub command: Commands,
+    pub command: Option<Commands>,
 }
@@ -0,0 +1,64 @@
+use crate::{cli::commands::ModCommands, core::ModManager, tr, utils::Config};
+
+/// Handle the `mods` command
+///
+/// # Arguments
+///
+/// * `command` - The mods subcommand to run
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command was successful, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the command fails
+pub async fn handle_mods_command(command: ModCommands) -> Result<(), Box<dyn std::error::Error>> {
+    match command {
+        ModCommands::Add { server, ids } => ModManager::add(&server, &ids).await,
+        ModCommands::Remove { server, ids } => ModManager::remove(&server, &ids),
+        ModCommands::Update { server } => ModManager::update(&server).await,
+        ModCommands::List { server } => list_mods(&server),
+    }
+}
+
+/// List the workshop mods of a server
+///
+/// # Arguments
+///
+/// * `server_name` - The name of the game server
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command was successful, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist
+fn list_mods(server_name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let server = config
+        .find_server(server_name)
+        .ok_or_else(|| tr!("server-not-installed", server = server_name))?;
+
+    println!(
+        "{:<14} {:<50} {:<20} {:<6}",
+        tr!("list-header-workshop-id"),
+        tr!("list-header-name"),
+        tr!("list-header-updated"),
+        tr!("list-header-links")
+    );
+    println!("{:-<92}", "");
+
+    for workshop_mod in &server.mods {
+        println!(
+            "{:<14} {:<50} {:<20} {:<6}",
+            workshop_mod.id,
+            workshop_mod.title.clone().unwrap_or_default(),
+            workshop_mod.updated_at.format("%Y-%m-%d %H:%M"),
+            workshop_mod.links.len()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use crate::{
+    core::{
+        nat::{self, Diagnosis},
+        Gateway, NatReport,
+    },
+    tr,
+    utils::{Config, ServerRef},
+};
+
+/// Handle the `nat-check` command
+///
+/// # Arguments
+///
+/// * `target` - The server, or a single instance in the form `server/instance`
+/// * `upnp` - Forward the ports that are not forwarded yet on the router with UPnP
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the ports were checked and, with `upnp`, forwarded
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist, network access is disabled,
+/// or `upnp` is set and no UPnP gateway was found or a mapping was refused
+pub async fn handle_nat_check_command(
+    target: String,
+    upnp: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let (server, instance) = config.resolve(&ServerRef::parse(&target))?;
+    let targets: Vec<_> = match instance {
+        Some(instance) => vec![Some(instance)],
+        None if server.instances.is_empty() => vec![None],
+        None => server.instances.iter().map(Some).collect(),
+    };
+
+    let gateway = Gateway::discover(&config).await;
+    if upnp && gateway.is_none() {
+        return Err(tr!("nat-upnp-no-gateway").into());
+    }
+
+    for (i, instance) in targets.into_iter().enumerate() {
+        let name = match instance {
+            Some(instance) => format!("{}/{}", server.name, instance.name),
+            None => server.name.clone(),
+        };
+        let report = NatReport::check(&config, server, instance, gateway.as_ref()).await?;
+
+        if i > 0 {
+            println!();
+        }
+        println!("{}", tr!("nat-target", target = name));
+        println!("{}", report);
+
+        let (Some(gateway), Some(host), true) = (&gateway, nat::local_ip(), upnp) else {
+            continue;
+        };
+        for check in &report.ports {
+            if !matches!(check.diagnosis, Diagnosis::Nat | Diagnosis::NotListening)
+                || check.mapped_to == Some(host)
+            {
+                continue;
+            }
+            gateway
+                .add_mapping(
+                    check.port,
+                    check.protocol,
+                    host,
+                    &format!("steamserv {}", name),
+                )
+                .await?;
+            println!(
+                "{}",
+                tr!(
+                    "nat-upnp-mapped",
+                    port = check.port,
+                    protocol = check.protocol,
+                    host = host
+                )
+            );
+        }
+    }
+
+    Ok(())
+}
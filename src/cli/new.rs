@@ -0,0 +1,78 @@
+use std::path::PathBuf;
+
+use inquire::Confirm;
+
+use crate::{
+    core::{Answers, NewServer, ServerTemplate},
+    tr,
+    utils::Config,
+};
+
+/// Handle the `new` command
+///
+/// # Arguments
+///
+/// * `save_template` - Save the settings as a template under this name
+/// * `from_template` - Provision servers from a saved template instead of asking
+/// * `count` - The number of servers to provision from the template
+/// * `name_prefix` - The prefix of the server names, defaults to the template name
+/// * `answers` - Replay the answers of this file instead of asking
+/// * `save_answers` - Save the answers to this file
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the servers were set up or the wizard was cancelled, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if a prompt failed, the template or answer file could not be
+/// read or a server could not be installed or started
+pub async fn handle_new_command(
+    save_template: Option<String>,
+    from_template: Option<String>,
+    count: usize,
+    name_prefix: Option<String>,
+    answers: Option<PathBuf>,
+    save_answers: Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    config.ensure_online(&tr!("action-install-server"))?;
+
+    if let Some(name) = from_template {
+        let template = ServerTemplate::load(&name)?;
+        let prefix = name_prefix.unwrap_or(name);
+        return template.provision_many(&prefix, count).await;
+    }
+
+    let server = match answers {
+        Some(path) => {
+            let server = Answers::load(&path)?.into_server()?;
+            println!("{}", server);
+            server
+        }
+        None => {
+            println!("{}", tr!("new-welcome"));
+            let server = NewServer::prompt(&config)?;
+
+            println!("{}", server);
+            if !Confirm::new(&tr!("new-confirm"))
+                .with_default(true)
+                .prompt()?
+            {
+                println!("{}", tr!("new-cancelled"));
+                return Ok(());
+            }
+            server
+        }
+    };
+
+    if let Some(path) = save_answers {
+        Answers::from_server(&server).save(&path)?;
+    }
+    if let Some(name) = save_template {
+        ServerTemplate::from_server(&server).save(&name)?;
+        println!("{}", tr!("template-saved", template = name));
+    }
+
+    server.provision().await
+}
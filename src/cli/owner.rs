@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    cli::commands::OwnerCommands,
+    tr,
+    utils::{history, Config, HistoryEvent},
+};
+
+/// Handle the `owner` command
+///
+/// # Arguments
+///
+/// * `command` - The owner subcommand to run
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command was successful, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist or the config could not be saved
+pub fn handle_owner_command(command: OwnerCommands) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = Config::load()?;
+
+    match command {
+        OwnerCommands::Set { server, owner } => {
+            set_owner(&mut config, &server, Some(owner.clone()))?;
+            println!("{}", tr!("owner-set", server = server, owner = owner));
+        }
+        OwnerCommands::Unset { server } => {
+            set_owner(&mut config, &server, None)?;
+            println!("{}", tr!("owner-unset", server = server));
+        }
+        OwnerCommands::List => list_owners(&config),
+    }
+
+    Ok(())
+}
+
+/// Change the owner of a server
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+/// * `name` - The name of the server
+/// * `owner` - The new owner, or `None` to remove the owner
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the owner was saved
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist or the config could not be saved
+fn set_owner(
+    config: &mut Config,
+    name: &str,
+    owner: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let server = config
+        .find_server_mut(name)
+        .ok_or_else(|| tr!("server-not-installed", server = name))?;
+    let kind = if owner.is_some() {
+        "owner-set"
+    } else {
+        "owner-unset"
+    };
+    let message = owner.clone().unwrap_or_default();
+    server.owner = owner;
+    config.save()?;
+    history::record(&HistoryEvent::new(name, kind, &message))?;
+
+    Ok(())
+}
+
+/// List the owners with their servers
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+fn list_owners(config: &Config) {
+    let mut owners: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for server in &config.installed_servers {
+        let owner = server.owner.as_deref().unwrap_or("-");
+        owners.entry(owner).or_default().push(&server.name);
+    }
+
+    println!(
+        "{:<20} {}",
+        tr!("owner-header-owner"),
+        tr!("owner-header-servers")
+    );
+    println!("{:-<62}", "");
+    for (owner, servers) in owners {
+        println!("{:<20} {}", owner, servers.join(", "));
+    }
+}
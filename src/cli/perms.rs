@@ -0,0 +1,30 @@
+use crate::{
+    tr,
+    utils::{permissions, Config},
+};
+
+/// Handle the `fix-perms` command
+///
+/// # Arguments
+///
+/// * `name` - The name of the game server
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the install tree was normalized, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist, the service user is unknown or
+/// a step needs root without `--sudo`
+pub fn handle_fix_perms_command(name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let server = config
+        .find_server(name)
+        .ok_or_else(|| tr!("server-not-installed", server = name))?;
+
+    let changed = permissions::normalize(&config.permissions, &server.install_path, server.app_id)?;
+    println!("{}", tr!("perms-normalized", count = changed));
+
+    Ok(())
+}
@@ -0,0 +1,402 @@
+use serde_json::json;
+
+use crate::{
+    cli::commands::{AdminsCommands, PlayersCommands},
+    core::{
+        players::{self, ListChange, ListKind},
+        GameProfile,
+    },
+    tr,
+    utils::{
+        output::{Column, OutputFormat, Table},
+        steamid::SteamId,
+        Config, InstalledServer, ServerInstance, ServerRef,
+    },
+};
+
+/// Handle the `players` command
+///
+/// # Arguments
+///
+/// * `target` - List the connected players of this server or instance
+/// * `output` - The output format of the player list
+/// * `command` - The players subcommand to run instead
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command was successful, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist, the players could not be
+/// queried, the SteamID is invalid or the list could not be changed
+pub async fn handle_players_command(
+    target: Option<String>,
+    output: OutputFormat,
+    command: Option<PlayersCommands>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = Config::load()?;
+
+    let Some(command) = command else {
+        return list_online(&config, &target.unwrap_or_default(), output);
+    };
+    match command {
+        PlayersCommands::Ban {
+            target,
+            steamid,
+            no_reload,
+        } => {
+            change(
+                &mut config,
+                &target,
+                &steamid,
+                ListKind::Bans,
+                true,
+                !no_reload,
+            )
+            .await?
+        }
+        PlayersCommands::Unban {
+            target,
+            steamid,
+            no_reload,
+        } => {
+            change(
+                &mut config,
+                &target,
+                &steamid,
+                ListKind::Bans,
+                false,
+                !no_reload,
+            )
+            .await?
+        }
+        PlayersCommands::Whitelist {
+            target,
+            steamid,
+            remove,
+            no_reload,
+        } => {
+            change(
+                &mut config,
+                &target,
+                &steamid,
+                ListKind::Whitelist,
+                !remove,
+                !no_reload,
+            )
+            .await?
+        }
+        PlayersCommands::Sync { no_reload } => {
+            for id in config
+                .players
+                .bans
+                .iter()
+                .filter_map(|id| SteamId::parse(&id.to_string()))
+            {
+                for (server, instance, target) in targets(&config, ListKind::Bans) {
+                    apply(
+                        server,
+                        instance,
+                        &target,
+                        ListKind::Bans,
+                        id,
+                        true,
+                        !no_reload,
+                    );
+                }
+            }
+            println!(
+                "{}",
+                tr!("players-synced", count = config.players.bans.len())
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle the `admins` command
+///
+/// # Arguments
+///
+/// * `command` - The admins subcommand to run
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command was successful, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist, the SteamID is invalid or the admin list could not be read or changed
+pub async fn handle_admins_command(
+    command: AdminsCommands,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = Config::load()?;
+
+    match command {
+        AdminsCommands::Add {
+            target,
+            steamid,
+            no_reload,
+        } => {
+            change(
+                &mut config,
+                &target,
+                &steamid,
+                ListKind::Admins,
+                true,
+                !no_reload,
+            )
+            .await?
+        }
+        AdminsCommands::Remove {
+            target,
+            steamid,
+            no_reload,
+        } => {
+            change(
+                &mut config,
+                &target,
+                &steamid,
+                ListKind::Admins,
+                false,
+                !no_reload,
+            )
+            .await?
+        }
+        AdminsCommands::List { target } => {
+            let target = ServerRef::parse(&target);
+            let (server, instance) = config.resolve(&target)?;
+            let ids = players::entries(server, instance, ListKind::Admins)?;
+            if ids.is_empty() {
+                println!("{}", tr!("admins-empty", target = target));
+                return Ok(());
+            }
+
+            println!("{}", tr!("admins-title", target = target));
+            for id in ids {
+                println!("  {:<20} {}", id.steam64(), id.steam2());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// List the players connected to a server or instance
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+/// * `target` - The game server or instance in the form `server/instance`
+/// * `output` - The output format
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the players were listed
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist, is not running or did not answer
+fn list_online(
+    config: &Config,
+    target: &str,
+    output: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let target = ServerRef::parse(target);
+    let (server, instance) = config.resolve(&target)?;
+    let online = players::online(server, instance)?;
+
+    if online.is_empty() && output == OutputFormat::Table {
+        println!("{}", tr!("players-online-none", target = target));
+        return Ok(());
+    }
+
+    let mut table = Table::new(vec![
+        Column::new("name", tr!("players-header-name"), 32),
+        Column::new("duration", tr!("players-header-duration"), 10),
+        Column::new("ping", tr!("players-header-ping"), 6),
+    ]);
+    for player in online {
+        // Scripts get the seconds, people the connection time as h:mm:ss
+        let duration = match output {
+            OutputFormat::Table | OutputFormat::Markdown => {
+                json!(player.duration.map(|seconds| format!(
+                    "{}:{:02}:{:02}",
+                    seconds / 3600,
+                    seconds / 60 % 60,
+                    seconds % 60
+                )))
+            }
+            OutputFormat::Json | OutputFormat::Csv => json!(player.duration),
+        };
+        table.push(vec![json!(player.name), duration, json!(player.ping)]);
+    }
+    println!("{}", table.render(output)?);
+
+    Ok(())
+}
+
+/// Change a list of a server, or of all servers for a shared ban list
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+/// * `target` - The game server or instance in the form `server/instance`
+/// * `steamid` - The SteamID, profile URL or custom URL of the player
+/// * `kind` - The list to change
+/// * `add` - True to add the player, false to remove them
+/// * `reload` - Send the change to running servers over RCON
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the list was changed
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist, the SteamID is invalid or the list could not be changed
+async fn change(
+    config: &mut Config,
+    target: &str,
+    steamid: &str,
+    kind: ListKind,
+    add: bool,
+    reload: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let id = players::resolve_id(config, steamid).await?;
+    let target = ServerRef::parse(target);
+    let (server, instance) = config.resolve(&target)?;
+
+    if kind != ListKind::Bans || !config.players.shared_bans {
+        let change = players::change(server, instance, kind, id, add, reload)?;
+        report(&target, kind, id, add, &change);
+        return Ok(());
+    }
+
+    for (server, instance, target) in targets(config, kind) {
+        apply(server, instance, &target, kind, id, add, reload);
+    }
+    if add {
+        config.players.bans.insert(id.steam64());
+    } else {
+        config.players.bans.remove(&id.steam64());
+    }
+    config.save()?;
+
+    Ok(())
+}
+
+/// Change a list of a server and print the result or the error
+///
+/// # Arguments
+///
+/// * `server` - The installed server
+/// * `instance` - The instance, or `None` for the server itself
+/// * `target` - The reference to the server or instance
+/// * `kind` - The list to change
+/// * `id` - The player
+/// * `add` - True to add the player, false to remove them
+/// * `reload` - Send the change to the running server over RCON
+fn apply(
+    server: &InstalledServer,
+    instance: Option<&ServerInstance>,
+    target: &ServerRef,
+    kind: ListKind,
+    id: SteamId,
+    add: bool,
+    reload: bool,
+) {
+    match players::change(server, instance, kind, id, add, reload) {
+        Ok(change) => report(target, kind, id, add, &change),
+        Err(e) => println!(
+            "{}",
+            tr!("players-failed", target = target, error = e.to_string())
+        ),
+    }
+}
+
+/// Get all servers and instances whose game supports a list
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+/// * `kind` - The list
+///
+/// # Returns
+///
+/// The server, the instance or `None` for the server itself, and the reference to it
+fn targets(
+    config: &Config,
+    kind: ListKind,
+) -> Vec<(&InstalledServer, Option<&ServerInstance>, ServerRef)> {
+    let supported = |server: &InstalledServer| {
+        GameProfile::find(server.app_id).is_some_and(|profile| match kind {
+            ListKind::Bans => profile.bans.is_some(),
+            ListKind::Whitelist => profile.whitelist.is_some(),
+            ListKind::Admins => profile.admins.is_some(),
+        })
+    };
+
+    config
+        .installed_servers
+        .iter()
+        .filter(|server| supported(server))
+        .flat_map(|server| {
+            std::iter::once((server, None, ServerRef::parse(&server.name))).chain(
+                server.instances.iter().map(move |i| {
+                    (
+                        server,
+                        Some(i),
+                        ServerRef::parse(&format!("{}/{}", server.name, i.name)),
+                    )
+                }),
+            )
+        })
+        .collect()
+}
+
+/// Print the result of a list change
+///
+/// # Arguments
+///
+/// * `target` - The server or instance
+/// * `kind` - The changed list
+/// * `id` - The player
+/// * `add` - True if the player was added
+/// * `change` - What was changed
+fn report(target: &ServerRef, kind: ListKind, id: SteamId, add: bool, change: &ListChange) {
+    if change.file.is_none() && !change.reloaded {
+        println!(
+            "{}",
+            tr!(
+                "players-unchanged",
+                target = target,
+                id = id.to_string(),
+                list = kind.to_string()
+            )
+        );
+        return;
+    }
+
+    let key = if add {
+        "players-added"
+    } else {
+        "players-removed"
+    };
+    println!(
+        "{}",
+        tr!(
+            key,
+            target = target,
+            id = id.to_string(),
+            list = kind.to_string()
+        )
+    );
+    if let Some(file) = &change.file {
+        println!("  {}", tr!("players-file-changed", file = file.display()));
+    }
+    if change.reloaded {
+        println!("  {}", tr!("players-reloaded"));
+    }
+}
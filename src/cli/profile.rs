@@ -0,0 +1,179 @@
+use std::time::Duration;
+
+use inquire::Confirm;
+use serde_json::json;
+
+use crate::{
+    cli::commands::ProfileCommands,
+    core::{aliases, ProfileRegistry, ProfileSync, ProfileTest, ProfileWizard, SteamCMD},
+    tr,
+    utils::{
+        output::{Column, Table},
+        Config,
+    },
+};
+
+/// Handle the `profile` command
+///
+/// # Arguments
+///
+/// * `command` - The profile subcommand to run
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command was successful, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the game is unknown or has no profile, the community
+/// profiles could not be synced, or the tested profile could not be installed
+/// or failed a check
+pub async fn handle_profile_command(
+    command: ProfileCommands,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let registry = ProfileRegistry::global();
+
+    match command {
+        ProfileCommands::List { output } => {
+            let mut table = Table::new(vec![
+                Column::new("app_id", tr!("list-header-app-id"), 10),
+                Column::new("name", tr!("profile-header-name"), 36),
+                Column::new("source", tr!("profile-header-source"), 40),
+            ]);
+            for registered in registry.profiles() {
+                table.push(vec![
+                    json!(registered.profile.app_id),
+                    json!(registered.profile.name),
+                    json!(registered.source.to_string()),
+                ]);
+            }
+            println!("{}", table.render(output)?);
+            print_errors(registry);
+        }
+        ProfileCommands::Show { app_id } => {
+            let app_id = aliases::resolve(&config, &app_id)?;
+            let registered = registry
+                .find(app_id)
+                .ok_or_else(|| tr!("profile-not-found", app_id = app_id))?;
+            println!(
+                "# {}",
+                tr!(
+                    "profile-show-source",
+                    source = registered.source.to_string()
+                )
+            );
+            println!("{}", registered.profile.to_toml()?);
+            print_errors(registry);
+        }
+        ProfileCommands::Create {
+            install_dir,
+            app_id,
+            file,
+        } => {
+            let profile = ProfileWizard {
+                install_dir: install_dir.clone(),
+                app_id,
+            }
+            .prompt()?;
+            let content = profile.to_toml()?;
+            println!("\n{}", content);
+
+            let path = match file {
+                Some(file) => Some(file),
+                None => Confirm::new(&tr!("profile-create-save"))
+                    .with_default(true)
+                    .prompt()?
+                    .then(|| {
+                        ProfileRegistry::dir()
+                            .map(|dir| dir.join(format!("{}.toml", aliases::slug(&profile.name))))
+                    })
+                    .transpose()?,
+            };
+            if let Some(path) = path {
+                if path.exists()
+                    && !Confirm::new(&tr!("profile-create-overwrite", path = path.display()))
+                        .with_default(false)
+                        .prompt()?
+                {
+                    return Ok(());
+                }
+                if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+                    std::fs::create_dir_all(dir)?;
+                }
+                std::fs::write(&path, content)?;
+                println!("{}", tr!("profile-create-saved", path = path.display()));
+            }
+            println!(
+                "{}",
+                tr!(
+                    "profile-create-next",
+                    app_id = profile.app_id,
+                    path = install_dir.display()
+                )
+            );
+        }
+        ProfileCommands::Sync => {
+            let sync = ProfileSync::run(&config).await?;
+            println!(
+                "{}",
+                tr!(
+                    "profile-sync-done",
+                    count = sync.profiles,
+                    files = sync.files.len(),
+                    url = sync.url.as_str(),
+                    revision = sync.revision.as_str()
+                )
+            );
+            if !sync.verified {
+                println!("{}", tr!("profile-sync-not-verified"));
+            }
+        }
+        ProfileCommands::Test {
+            app_id,
+            install_dir,
+            username,
+            timeout,
+            keep,
+        } => {
+            let app_id = aliases::resolve(&config, &app_id)?;
+            let login = match install_dir {
+                Some(_) => ("anonymous".to_string(), String::new()),
+                None => {
+                    SteamCMD::get_login(Some(username.unwrap_or_else(|| "anonymous".to_string())))?
+                }
+            };
+            let report = ProfileTest {
+                app_id,
+                install_dir,
+                login,
+                timeout: Duration::from_secs(timeout),
+                keep,
+            }
+            .run(&config)?;
+
+            println!("{}", report);
+            if !report.is_ready() {
+                return Err(tr!("profile-test-failed", app_id = app_id).into());
+            }
+            println!("{}", tr!("profile-test-passed", app_id = app_id));
+        }
+    }
+
+    Ok(())
+}
+
+/// Print the user profiles that were left out because they are invalid
+///
+/// # Arguments
+///
+/// * `registry` - The profile registry
+fn print_errors(registry: &ProfileRegistry) {
+    if registry.errors.is_empty() {
+        return;
+    }
+    println!();
+    for error in &registry.errors {
+        println!("{}", tr!("profile-skipped", error = error.as_str()));
+    }
+}
@@ -0,0 +1,77 @@
+use crate::{
+    cli::commands::RconCommands,
+    core::{rcon, RconClient},
+    tr,
+    utils::{Config, RconConfig, ServerRef},
+};
+
+/// Handle the `rcon` command
+///
+/// # Arguments
+///
+/// * `command` - The rcon subcommand to run
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command was successful, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist or the RCON connection failed
+pub fn handle_rcon_command(command: RconCommands) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = Config::load()?;
+
+    match command {
+        RconCommands::Set {
+            target,
+            port,
+            password,
+            host,
+        } => {
+            let target = ServerRef::parse(&target);
+            config.resolve(&target)?;
+            let rcon = RconConfig {
+                host,
+                port,
+                password,
+            };
+
+            let server = config
+                .find_server_mut(&target.server)
+                .ok_or_else(|| tr!("server-not-installed", server = target.server))?;
+            match &target.instance {
+                Some(name) => {
+                    if let Some(instance) = server.instances.iter_mut().find(|i| &i.name == name) {
+                        instance.rcon = Some(rcon);
+                    }
+                }
+                None => server.rcon = Some(rcon),
+            }
+
+            config.save()?;
+            println!("{}", tr!("rcon-stored", target = target));
+        }
+        RconCommands::Exec { target, command } => {
+            let target = ServerRef::parse(&target);
+            let (server, instance) = config.resolve(&target)?;
+            let rcon = server
+                .rcon_for(instance)
+                .ok_or_else(|| tr!("rcon-not-configured", target = target))?;
+
+            let mut client = RconClient::connect(rcon)?;
+            let response = client.execute(&command.join(" "))?;
+            if !response.is_empty() {
+                println!("{}", response.trim_end());
+            }
+        }
+        RconCommands::Broadcast { target, message } => {
+            let target = ServerRef::parse(&target);
+            let (server, instance) = config.resolve(&target)?;
+
+            rcon::broadcast(server, instance, &message.join(" "), None)?;
+            println!("{}", tr!("rcon-broadcast-sent", target = target));
+        }
+    }
+
+    Ok(())
+}
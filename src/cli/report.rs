@@ -0,0 +1,125 @@
+use chrono::Utc;
+use serde_json::json;
+
+use crate::{
+    cli::commands::ReportCommands,
+    core::{uptime, UptimeReport},
+    tr,
+    utils::{
+        history,
+        output::{Column, OutputFormat, Table},
+        Config,
+    },
+};
+
+/// Handle the `report` command
+///
+/// # Arguments
+///
+/// * `command` - The report subcommand to run
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the report was printed, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the month is invalid, the server does not exist or the
+/// history could not be read
+pub fn handle_report_command(command: ReportCommands) -> Result<(), Box<dyn std::error::Error>> {
+    match command {
+        ReportCommands::Uptime {
+            server,
+            month,
+            output,
+        } => report_uptime(server.as_deref(), month.as_deref(), output),
+    }
+}
+
+/// Print the availability of the servers and instances in a month
+///
+/// # Arguments
+///
+/// * `server` - Only report this server and its instances
+/// * `month` - The month as `YYYY-MM`, or `None` for the current month
+/// * `output` - The output format
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the report was printed
+///
+/// # Errors
+///
+/// Returns an error if the month is invalid, the server does not exist or the
+/// history could not be read
+fn report_uptime(
+    server: Option<&str>,
+    month: Option<&str>,
+    output: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let (from, to) = uptime::month_period(month)?;
+    if let Some(name) = server {
+        config
+            .find_server(name)
+            .ok_or_else(|| tr!("server-not-installed", server = name))?;
+    }
+
+    let events = history::load(server)?;
+    let now = Utc::now();
+    let reports: Vec<UptimeReport> = config
+        .installed_servers
+        .iter()
+        .filter(|s| server.is_none_or(|name| s.name == name))
+        .flat_map(|s| {
+            std::iter::once(s.name.clone())
+                .chain(s.instances.iter().map(|i| format!("{}/{}", s.name, i.name)))
+        })
+        .map(|target| UptimeReport::build(&target, &events, from, to, now))
+        .collect();
+
+    let mut table = Table::new(vec![
+        Column::new("server", tr!("list-header-server"), 30),
+        Column::new("uptime", tr!("report-header-uptime"), 12),
+        Column::new("availability", tr!("report-header-availability"), 14),
+        Column::new("starts", tr!("report-header-starts"), 8),
+        Column::new("crashes", tr!("report-header-crashes"), 8),
+    ]);
+    for report in reports {
+        // Scripts get seconds and percentages as numbers
+        let (uptime, availability) = match output {
+            OutputFormat::Table | OutputFormat::Markdown => (
+                json!(format!(
+                    "{}h {:02}m",
+                    report.uptime / 3600,
+                    report.uptime / 60 % 60
+                )),
+                json!(report.availability.map(|a| format!("{:.2}%", a))),
+            ),
+            OutputFormat::Json | OutputFormat::Csv => (
+                json!(report.uptime),
+                json!(report.availability.map(|a| (a * 100.0).round() / 100.0)),
+            ),
+        };
+        table.push(vec![
+            json!(report.target),
+            uptime,
+            availability,
+            json!(report.starts),
+            json!(report.crashes),
+        ]);
+    }
+
+    if output == OutputFormat::Table {
+        println!(
+            "{}",
+            tr!(
+                "report-uptime-title",
+                month = from.format("%Y-%m").to_string()
+            )
+        );
+    }
+    println!("{}", table.render(output)?);
+
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use inquire::Password;
+
+use crate::{
+    cli::commands::SecretsCommands,
+    tr,
+    utils::{
+        secrets::{self, PASSPHRASE_ENV},
+        Config, KeySource, SecretsConfig,
+    },
+};
+
+/// Handle the `secrets` command
+///
+/// # Arguments
+///
+/// * `command` - The secrets subcommand to run
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command was successful, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the key is not available or the config could not be saved
+pub fn handle_secrets_command(command: SecretsCommands) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = Config::load()?;
+
+    match command {
+        SecretsCommands::Enable { passphrase } => {
+            config.secrets = if passphrase {
+                let passphrase = match std::env::var(PASSPHRASE_ENV) {
+                    Ok(passphrase) => passphrase,
+                    Err(_) => Password::new(&tr!("secrets-passphrase"))
+                        .with_help_message(&tr!("secrets-passphrase-help"))
+                        .prompt()?,
+                };
+                let salt = secrets::new_salt();
+                secrets::unlock(&passphrase, &salt)?;
+                SecretsConfig {
+                    enabled: true,
+                    key: KeySource::Passphrase,
+                    salt: Some(salt),
+                    values: config.secrets.values.clone(),
+                }
+            } else {
+                secrets::forget_key();
+                SecretsConfig {
+                    enabled: true,
+                    key: KeySource::Keyring,
+                    salt: None,
+                    values: config.secrets.values.clone(),
+                }
+            };
+
+            let count = config.secret_fields_mut().len();
+            config.save()?;
+            println!("{}", tr!("secrets-enabled", count = count));
+            println!("{}", tr!("secrets-enabled-backups"));
+        }
+        SecretsCommands::Disable => {
+            config.secrets.enabled = false;
+            config.save()?;
+            println!("{}", tr!("secrets-disabled"));
+        }
+        SecretsCommands::Status => {
+            let mut stored = Config::store()?.load()?;
+            let fields = stored.secret_fields_mut();
+            let encrypted = fields.iter().filter(|v| secrets::is_encrypted(v)).count();
+
+            let key = match config.secrets.key {
+                KeySource::Keyring => tr!("secrets-key-keyring"),
+                KeySource::Passphrase => tr!("secrets-key-passphrase"),
+            };
+            if config.secrets.enabled {
+                println!("{}", tr!("secrets-status-enabled", key = key));
+            } else {
+                println!("{}", tr!("secrets-status-disabled"));
+            }
+            println!(
+                "{}",
+                tr!(
+                    "secrets-status-count",
+                    encrypted = encrypted,
+                    total = fields.len()
+                )
+            );
+        }
+        SecretsCommands::Set { name, value } => {
+            if !secrets::is_valid_name(&name) {
+                return Err(tr!("secrets-name-invalid", name = name).into());
+            }
+            let value = match value {
+                Some(value) => value,
+                None => Password::new(&tr!("secrets-value", name = name.as_str())).prompt()?,
+            };
+            config.secrets.values.insert(name.clone(), value);
+            config.save()?;
+            println!(
+                "{}",
+                tr!(
+                    "secrets-stored",
+                    name = name.as_str(),
+                    reference = secrets::reference(&name)
+                )
+            );
+            if !config.secrets.enabled {
+                println!("{}", tr!("secrets-stored-plain"));
+            }
+        }
+        SecretsCommands::Unset { name } => {
+            if config.secrets.values.remove(&name).is_none() {
+                return Err(tr!("secrets-unknown", name = name).into());
+            }
+            config.save()?;
+            println!("{}", tr!("secrets-removed", name = name));
+        }
+        SecretsCommands::List => {
+            if config.secrets.values.is_empty() {
+                println!("{}", tr!("secrets-none"));
+            }
+            for name in config.secrets.values.keys() {
+                println!("{}", secrets::reference(name));
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,230 @@
+use crate::{
+    core::{DnsState, ReadinessReport, ServerProcess},
+    tr,
+    utils::{duration, terminal, Config, InstalledServer, ServerRef},
+};
+
+/// Handle the `start` command
+///
+/// # Arguments
+///
+/// * `target` - The server or instance in the form `server/instance`
+/// * `check` - Only run the pre-start checks
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the server was started, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist, is already running or could not be started
+pub fn handle_start_command(target: String, check: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let target = ServerRef::parse(&target);
+    let (server, instance) = config.resolve(&target)?;
+
+    if check {
+        let report = ReadinessReport::check(&config, server, instance)?;
+        println!("{}", report);
+        if !report.is_ready() {
+            return Err(tr!("readiness-failed", target = target).into());
+        }
+        println!("{}", tr!("readiness-ready", target = target));
+        return Ok(());
+    }
+
+    ensure_not_in_maintenance(server)?;
+    let process = ServerProcess::new(&target)?;
+    process.start(&config, server, instance)?;
+
+    Ok(())
+}
+
+/// Handle the `stop` command
+///
+/// # Arguments
+///
+/// * `target` - The server or instance in the form `server/instance`
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the server was stopped or was not running, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist or did not stop
+pub fn handle_stop_command(target: String) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let target = ServerRef::parse(&target);
+    let (server, instance) = config.resolve(&target)?;
+    ensure_not_in_maintenance(server)?;
+
+    let process = ServerProcess::new(&target)?;
+    if !config.confirm.stop.ask(
+        &tr!("stop-confirm", target = target.to_string()),
+        true,
+        || process.players(server, instance),
+    )? {
+        println!("{}", tr!("confirm-cancelled"));
+        return Ok(());
+    }
+    if !process.stop(server, instance)? {
+        println!("{}", tr!("process-not-running", target = target));
+    }
+
+    Ok(())
+}
+
+/// Handle the `restart` command
+///
+/// # Arguments
+///
+/// * `target` - The server or instance in the form `server/instance`
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the server was restarted, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist or could not be stopped or started
+pub fn handle_restart_command(target: String) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let target = ServerRef::parse(&target);
+    let (server, instance) = config.resolve(&target)?;
+
+    let process = ServerProcess::new(&target)?;
+    if !config.confirm.restart.ask(
+        &tr!("restart-confirm", target = target.to_string()),
+        true,
+        || process.players(server, instance),
+    )? {
+        println!("{}", tr!("confirm-cancelled"));
+        return Ok(());
+    }
+    process.stop(server, instance)?;
+    process.start(&config, server, instance)?;
+
+    Ok(())
+}
+
+/// Handle the `status` command
+///
+/// # Arguments
+///
+/// * `owner` - Only show the servers of this owner
+/// * `watch` - Show the status again every this many seconds until Ctrl+C
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the status was displayed, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the config could not be loaded
+pub fn handle_status_command(
+    owner: Option<String>,
+    watch: Option<u64>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match watch {
+        Some(interval) => terminal::watch(interval, || print_status(owner.as_deref())),
+        None => print_status(owner.as_deref()),
+    }
+}
+
+/// Print which servers and instances are running
+///
+/// # Arguments
+///
+/// * `owner` - Only show the servers of this owner
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the status was printed
+///
+/// # Errors
+///
+/// Returns an error if the config could not be loaded
+fn print_status(owner: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+
+    println!(
+        "{:<40} {:<10} {:<10}",
+        tr!("list-header-server"),
+        tr!("status-header-state"),
+        tr!("status-header-pid")
+    );
+    println!("{:-<62}", "");
+
+    for server in config
+        .installed_servers
+        .iter()
+        .filter(|s| s.is_owned_by(owner))
+    {
+        let targets = std::iter::once(ServerRef::parse(&server.name)).chain(
+            server
+                .instances
+                .iter()
+                .map(|i| ServerRef::parse(&format!("{}/{}", server.name, i.name))),
+        );
+
+        for target in targets {
+            let pid = ServerProcess::new(&target)?.pid();
+            let state = match (pid, &server.maintenance) {
+                (Some(_), Some(_)) => tr!("status-placeholder"),
+                (Some(_), None) => tr!("status-running"),
+                (None, Some(_)) => tr!("status-maintenance"),
+                (None, None) => tr!("status-stopped"),
+            };
+            println!(
+                "{:<40} {:<10} {:<10}",
+                target.to_string(),
+                state,
+                pid.map(|p| p.to_string()).unwrap_or_default()
+            );
+        }
+    }
+
+    if let Some(hostname) = &config.dns.hostname {
+        println!();
+        match DnsState::load() {
+            Some(state) => println!(
+                "{}",
+                tr!(
+                    "status-public-address",
+                    hostname = hostname,
+                    ip = state.ip,
+                    age = duration::format_age(chrono::Utc::now() - state.updated)
+                )
+            ),
+            None => println!("{}", tr!("status-public-hostname", hostname = hostname)),
+        }
+    }
+
+    Ok(())
+}
+
+/// Refuse to start a server that is in maintenance
+///
+/// # Arguments
+///
+/// * `server` - The installed server
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the server is not in maintenance
+///
+/// # Errors
+///
+/// Returns an error with the reason of the maintenance
+fn ensure_not_in_maintenance(server: &InstalledServer) -> Result<(), Box<dyn std::error::Error>> {
+    match &server.maintenance {
+        Some(maintenance) => Err(tr!(
+            "maintenance-active",
+            server = server.name.as_str(),
+            reason = maintenance.reason.clone().unwrap_or_default()
+        )
+        .into()),
+        None => Ok(()),
+    }
+}
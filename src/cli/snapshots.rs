@@ -0,0 +1,130 @@
+use inquire::Confirm;
+
+use crate::{
+    cli::commands::SnapshotsCommands,
+    core::{Snapshots, StagedUpdate, SteamCMD},
+    tr,
+    utils::{history, Config, HistoryEvent},
+};
+
+/// Handle the `snapshots` command
+///
+/// # Arguments
+///
+/// * `command` - The snapshots subcommand to run
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command was successful, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist, its filesystem does not
+/// support snapshots or the snapshot tool failed
+pub fn handle_snapshots_command(
+    command: SnapshotsCommands,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+
+    match command {
+        SnapshotsCommands::List { server } => {
+            let server = config
+                .find_server(&server)
+                .ok_or_else(|| tr!("server-not-installed", server = server))?;
+            let snapshots = Snapshots::for_server(server)?.list()?;
+            if snapshots.is_empty() {
+                println!("{}", tr!("snapshots-empty", server = server.name));
+                return Ok(());
+            }
+            for snapshot in snapshots.iter().rev() {
+                println!(
+                    "  {:<28} {}",
+                    snapshot.name,
+                    snapshot
+                        .created
+                        .with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M:%S")
+                );
+            }
+        }
+        SnapshotsCommands::Create { server } => {
+            let server = config
+                .find_server(&server)
+                .ok_or_else(|| tr!("server-not-installed", server = server))?;
+            let snapshot = Snapshots::for_server(server)?.create()?;
+            println!("{}", tr!("snapshot-created", name = snapshot.name));
+        }
+        SnapshotsCommands::Delete { server, name } => {
+            let server = config
+                .find_server(&server)
+                .ok_or_else(|| tr!("server-not-installed", server = server))?;
+            let snapshots = Snapshots::for_server(server)?;
+            let snapshot = snapshots.find(&name)?;
+            snapshots.delete(&snapshot)?;
+            println!("{}", tr!("snapshot-deleted", name = snapshot.name));
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle the `rollback` command
+///
+/// The server and its instances are stopped for the rollback and started again
+/// afterwards. A staged update is discarded, it was based on the replaced install.
+///
+/// # Arguments
+///
+/// * `name` - The name of the game server
+/// * `snapshot` - The name of the snapshot, or `latest`
+/// * `yes` - Roll back without asking for confirmation
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the server was rolled back or the rollback was declined
+///
+/// # Errors
+///
+/// Returns an error if the server or snapshot does not exist or the rollback failed
+pub fn handle_rollback_command(
+    name: String,
+    snapshot: Option<String>,
+    yes: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let server = config
+        .find_server(&name)
+        .ok_or_else(|| tr!("server-not-installed", server = name))?;
+    let snapshots = Snapshots::for_server(server)?;
+    let snapshot = snapshots.find(snapshot.as_deref().unwrap_or("latest"))?;
+
+    if !yes
+        && !Confirm::new(&tr!(
+            "rollback-confirm",
+            server = server.name,
+            name = snapshot.name
+        ))
+        .with_help_message(&tr!("rollback-confirm-help"))
+        .with_default(false)
+        .prompt()?
+    {
+        println!("{}", tr!("rollback-cancelled"));
+        return Ok(());
+    }
+
+    let stopped = SteamCMD::stop_running(server)?;
+    snapshots.rollback(&snapshot)?;
+    StagedUpdate::discard(server)?;
+    history::record(&HistoryEvent::new(&server.name, "rollback", &snapshot.name))?;
+    println!(
+        "{}",
+        tr!(
+            "rollback-success",
+            server = server.name,
+            name = snapshot.name
+        )
+    );
+    SteamCMD::start_stopped(&config, server, &stopped)?;
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use chrono::Utc;
+
+use crate::{
+    core::{metrics::Sample, MetricsStore},
+    tr,
+    utils::{duration, sparkline, Config, ServerRef},
+};
+
+/// Width of the sparklines in characters
+const SPARKLINE_WIDTH: usize = 60;
+
+/// Handle the `stats` command
+///
+/// # Arguments
+///
+/// * `target` - The server or instance in the form `server/instance`
+/// * `since` - The period to show, e.g. `24h` or `7d`
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the statistics were displayed, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist, the period is invalid or the metrics could not be read
+pub fn handle_stats_command(
+    target: String,
+    since: String,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let target = ServerRef::parse(&target);
+    config.resolve(&target)?;
+
+    let period =
+        duration::parse_duration(&since).ok_or_else(|| tr!("period-invalid", period = since))?;
+    let samples = MetricsStore::open(&target)?.since(Utc::now() - period)?;
+
+    if samples.is_empty() {
+        println!("{}", tr!("stats-empty", target = target, period = since));
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        tr!(
+            "stats-header",
+            target = target,
+            period = since,
+            count = samples.len()
+        )
+    );
+
+    let players: Vec<f64> = samples.iter().map(|s| f64::from(s.players)).collect();
+    let max_players = samples.iter().map(|s| s.max_players).max().unwrap_or(0);
+    print_series(&tr!("stats-players"), &players, Some(max_players));
+
+    let fps: Vec<f64> = samples.iter().filter_map(|s| s.fps).collect();
+    if !fps.is_empty() {
+        print_series(&tr!("stats-fps"), &fps, None);
+    }
+
+    print_period(&samples);
+
+    Ok(())
+}
+
+/// Print a series of values as a sparkline with its average and maximum
+///
+/// # Arguments
+///
+/// * `label` - The name of the series
+/// * `values` - The values, oldest first
+/// * `limit` - The upper limit of the values, e.g. the player slots
+fn print_series(label: &str, values: &[f64], limit: Option<u32>) {
+    let average = values.iter().sum::<f64>() / values.len() as f64;
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    let summary = match limit {
+        Some(limit) => tr!(
+            "stats-summary-limit",
+            average = format!("{:.1}", average),
+            max = format!("{:.0}", max),
+            limit = limit
+        ),
+        None => tr!(
+            "stats-summary",
+            average = format!("{:.1}", average),
+            max = format!("{:.1}", max)
+        ),
+    };
+
+    println!(
+        "{:<10} {}  {}",
+        label,
+        sparkline::sparkline(values, SPARKLINE_WIDTH),
+        summary
+    );
+}
+
+/// Print the time range covered by the samples
+///
+/// # Arguments
+///
+/// * `samples` - The samples, oldest first
+fn print_period(samples: &[Sample]) {
+    let format = |sample: &Sample| {
+        sample
+            .timestamp
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M")
+            .to_string()
+    };
+    if let (Some(first), Some(last)) = (samples.first(), samples.last()) {
+        println!(
+            "{}",
+            tr!("stats-range", start = format(first), end = format(last))
+        );
+    }
+}
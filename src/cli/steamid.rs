@@ -0,0 +1,45 @@
+use crate::{
+    core::{players, SteamWebApi},
+    tr,
+    utils::Config,
+};
+
+/// Handle the `steamid` command
+///
+/// # Arguments
+///
+/// * `id` - The SteamID in any form, a profile URL or a custom URL name
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the SteamID was resolved and displayed, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the value is invalid or the custom URL could not be resolved
+pub async fn handle_steamid_command(id: String) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let id = players::resolve_id(&config, &id).await?;
+
+    println!("{:<12} {}", "SteamID64", id.steam64());
+    println!("{:<12} {}", "SteamID2", id.steam2());
+    println!("{:<12} {}", "SteamID3", id.steam3());
+    println!("{:<12} {}", tr!("steamid-profile"), id.profile_url());
+
+    // The name is only a bonus, so the command also works without an API key
+    if config.api_key.is_none() || config.is_offline() {
+        return Ok(());
+    }
+    let summaries = SteamWebApi::new(&config)?
+        .get_player_summaries(&[id.steam64()])
+        .await
+        .unwrap_or_default();
+    if let Some(summary) = summaries.first() {
+        println!("{:<12} {}", tr!("steamid-name"), summary.personaname);
+        if summary.profileurl.trim_end_matches('/') != id.profile_url() {
+            println!("{:<12} {}", tr!("steamid-custom-url"), summary.profileurl);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use crate::{
+    tr,
+    utils::{history, Config, HistoryEvent},
+};
+
+/// Handle the `timezone` command
+///
+/// # Arguments
+///
+/// * `server` - The name of the server
+/// * `timezone` - The IANA time zone, or `None` for the one of the system
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the time zone was saved
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist, the time zone is unknown
+/// or the config could not be saved
+pub fn handle_timezone_command(
+    server: String,
+    timezone: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = Config::load()?;
+    let installed = config
+        .find_server_mut(&server)
+        .ok_or_else(|| tr!("server-not-installed", server = server.as_str()))?;
+    installed.timezone = timezone.clone();
+    // Checked before saving, so the daemon never sees an unknown time zone
+    installed.time_zone()?;
+    config.save()?;
+
+    let kind = if timezone.is_some() {
+        "timezone-set"
+    } else {
+        "timezone-unset"
+    };
+    let message = timezone.clone().unwrap_or_default();
+    history::record(&HistoryEvent::new(&server, kind, &message))?;
+
+    match timezone {
+        Some(timezone) => println!(
+            "{}",
+            tr!("timezone-set", server = server, timezone = timezone)
+        ),
+        None => println!("{}", tr!("timezone-unset", server = server)),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use crate::{
+    cli::commands::TokenCommands,
+    core::{GameProfile, SteamWebApi},
+    tr,
+    utils::{Config, ServerRef},
+};
+
+/// Handle the `token` command
+///
+/// # Arguments
+///
+/// * `command` - The token subcommand to run
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command was successful, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the command fails
+pub async fn handle_token_command(
+    command: TokenCommands,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = Config::load()?;
+
+    match command {
+        TokenCommands::Set { target, token } => {
+            let target = ServerRef::parse(&target);
+            store_token(&mut config, &target, token)?;
+            config.save()?;
+            println!("{}", tr!("token-stored", target = target));
+        }
+        TokenCommands::List => list_tokens(&config),
+        TokenCommands::Create { target, memo } => {
+            let target = ServerRef::parse(&target);
+            let (server, _) = config.resolve(&target)?;
+            let app_id = GameProfile::find(server.app_id)
+                .and_then(|p| p.token_app_id)
+                .unwrap_or(server.app_id);
+            let memo = memo.unwrap_or_else(|| format!("steamserv {}", target));
+
+            let api = SteamWebApi::new(&config)?;
+            let account = api.create_game_server_account(app_id, &memo).await?;
+
+            store_token(&mut config, &target, account.login_token.clone())?;
+            config.save()?;
+            println!(
+                "{}",
+                tr!(
+                    "token-created",
+                    target = target,
+                    token = mask_token(&account.login_token),
+                    steamid = account.steamid
+                )
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Store a login token on a server or instance
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+/// * `target` - The server or instance
+/// * `token` - The login token
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the token was stored
+///
+/// # Errors
+///
+/// Returns an error if the server or instance does not exist
+fn store_token(
+    config: &mut Config,
+    target: &ServerRef,
+    token: String,
+) -> Result<(), Box<dyn std::error::Error>> {
+    config.resolve(target)?;
+    let server = config
+        .find_server_mut(&target.server)
+        .ok_or_else(|| tr!("server-not-installed", server = target.server))?;
+
+    if !GameProfile::find(server.app_id).is_some_and(|p| p.is_source_engine()) {
+        println!("{}", tr!("token-not-source", server = server.name));
+    }
+
+    match &target.instance {
+        Some(name) => {
+            if let Some(instance) = server.instances.iter_mut().find(|i| &i.name == name) {
+                instance.gslt = Some(token);
+            }
+        }
+        None => server.gslt = Some(token),
+    }
+
+    Ok(())
+}
+
+/// List the stored login tokens
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+fn list_tokens(config: &Config) {
+    println!(
+        "{:<40} {:<10} {:<40}",
+        tr!("list-header-server"),
+        tr!("list-header-app-id"),
+        tr!("list-header-token")
+    );
+    println!("{:-<90}", "");
+
+    for server in &config.installed_servers {
+        if let Some(token) = &server.gslt {
+            println!(
+                "{:<40} {:<10} {:<40}",
+                server.name,
+                server.app_id,
+                mask_token(token)
+            );
+        }
+        for instance in &server.instances {
+            if let Some(token) = &instance.gslt {
+                println!(
+                    "{:<40} {:<10} {:<40}",
+                    format!("{}/{}", server.name, instance.name),
+                    server.app_id,
+                    mask_token(token)
+                );
+            }
+        }
+    }
+}
+
+/// Mask a login token for display
+///
+/// # Arguments
+///
+/// * `token` - The login token
+///
+/// # Returns
+///
+/// The token with all but the first and last four characters hidden
+fn mask_token(token: &str) -> String {
+    let chars: Vec<char> = token.chars().collect();
+    if chars.len() <= 8 {
+        return "*".repeat(chars.len());
+    }
+    let start: String = chars[..4].iter().collect();
+    let end: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}…{}", start, end)
+}
@@ -0,0 +1,270 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use serde_json::{json, Value};
+
+use crate::{
+    core::{query, GameProfile, LaunchCommand, MetricsStore, ProcessUsage, ServerProcess},
+    tr,
+    utils::{
+        duration, fs,
+        output::{Column, OutputFormat, Table},
+        runtime,
+        terminal::CLEAR_SCREEN,
+        Config, InstalledServer, ServerInstance, ServerRef,
+    },
+};
+
+/// Time between the first two CPU measurements, so the first frame already shows the CPU load
+const CPU_WARMUP: Duration = Duration::from_secs(1);
+
+/// CPU time of the servers at the last refresh, to calculate the CPU load in between
+type CpuTimes = HashMap<String, (Instant, Duration)>;
+
+/// Handle the `top` command
+///
+/// # Arguments
+///
+/// * `interval` - The refresh interval in seconds
+/// * `once` - Print a single frame and exit
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the view was shown, it runs until Ctrl+C unless `once` is set
+///
+/// # Errors
+///
+/// Returns an error if the config could not be loaded
+pub async fn handle_top_command(
+    interval: u64,
+    once: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut cpu_times = CpuTimes::new();
+
+    let config = Config::load()?;
+    measure_cpu(&config, &mut cpu_times)?;
+    if !cpu_times.is_empty() {
+        tokio::time::sleep(CPU_WARMUP).await;
+    }
+
+    loop {
+        let config = Config::load()?;
+        let frame = render(&config, interval, &mut cpu_times)?;
+
+        // In plain mode the frames are printed below each other, e.g. for a log file
+        if runtime::is_plain() {
+            println!("{}\n", frame);
+        } else {
+            println!("{}{}", CLEAR_SCREEN, frame);
+        }
+
+        if once {
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+    }
+}
+
+/// Render one frame of the fleet view
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+/// * `interval` - The refresh interval in seconds, shown in the title
+/// * `cpu_times` - The CPU times of the last refresh, updated with the current ones
+///
+/// # Returns
+///
+/// The title and the table of all servers and instances
+///
+/// # Errors
+///
+/// Returns an error if the steamserv directory could not be found
+fn render(
+    config: &Config,
+    interval: u64,
+    cpu_times: &mut CpuTimes,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut table = Table::new(vec![
+        Column::new("server", tr!("list-header-server"), 30),
+        Column::new("state", tr!("status-header-state"), 12),
+        Column::new("pid", tr!("status-header-pid"), 8),
+        Column::new("players", tr!("top-header-players"), 9),
+        Column::new("cpu", tr!("top-header-cpu"), 7),
+        Column::new("memory", tr!("top-header-memory"), 10),
+        Column::new("updated", tr!("list-header-updated"), 8),
+    ]);
+
+    let mut running = 0;
+    for (server, instance, target) in targets(config) {
+        let process = ServerProcess::new(&target)?;
+        let pid = process.pid();
+        let state = match (pid, &server.maintenance) {
+            (Some(_), Some(_)) => tr!("status-placeholder"),
+            (Some(_), None) => tr!("status-running"),
+            (None, Some(_)) => tr!("status-maintenance"),
+            (None, None) => tr!("status-stopped"),
+        };
+
+        let mut row = vec![json!(target.to_string()), json!(state)];
+        match pid {
+            Some(pid) => {
+                running += 1;
+                let usage = process.usage();
+                let players = players(config, server, instance, &target)
+                    .map(|(players, max)| json!(format!("{}/{}", players, max)));
+                row.extend([
+                    json!(pid),
+                    players.unwrap_or(Value::Null),
+                    usage
+                        .and_then(|u| cpu_load(cpu_times, &target, u))
+                        .map(|load| json!(format!("{:.0}%", load)))
+                        .unwrap_or(Value::Null),
+                    usage
+                        .map(|u| json!(fs::format_size(u.memory)))
+                        .unwrap_or(Value::Null),
+                ]);
+            }
+            None => {
+                cpu_times.remove(&target.to_string());
+                row.extend([Value::Null, Value::Null, Value::Null, Value::Null]);
+            }
+        }
+        row.push(json!(duration::format_age(
+            Utc::now() - server.last_updated
+        )));
+        table.push(row);
+    }
+
+    let title = tr!(
+        "top-title",
+        time = chrono::Local::now().format("%H:%M:%S").to_string(),
+        running = running,
+        interval = interval
+    );
+    Ok(format!(
+        "{}\n\n{}",
+        title,
+        table.render(OutputFormat::Table)?
+    ))
+}
+
+/// Get all servers and their instances
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+///
+/// # Returns
+///
+/// The server, the instance or `None` for the server itself, and the reference to it
+fn targets(config: &Config) -> Vec<(&InstalledServer, Option<&ServerInstance>, ServerRef)> {
+    config
+        .installed_servers
+        .iter()
+        .flat_map(|server| {
+            std::iter::once((server, None, ServerRef::parse(&server.name))).chain(
+                server.instances.iter().map(move |i| {
+                    (
+                        server,
+                        Some(i),
+                        ServerRef::parse(&format!("{}/{}", server.name, i.name)),
+                    )
+                }),
+            )
+        })
+        .collect()
+}
+
+/// Store the current CPU times of all running servers
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+/// * `cpu_times` - The CPU times
+///
+/// # Returns
+///
+/// Ok if the CPU times were stored
+///
+/// # Errors
+///
+/// Returns an error if the steamserv directory could not be found
+fn measure_cpu(
+    config: &Config,
+    cpu_times: &mut CpuTimes,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for (_, _, target) in targets(config) {
+        if let Some(usage) = ServerProcess::new(&target)?.usage() {
+            cpu_times.insert(target.to_string(), (Instant::now(), usage.cpu_time));
+        }
+    }
+    Ok(())
+}
+
+/// Calculate the CPU load since the last refresh and remember the current CPU time
+///
+/// # Arguments
+///
+/// * `cpu_times` - The CPU times of the last refresh
+/// * `target` - The server or instance
+/// * `usage` - The current usage of the process
+///
+/// # Returns
+///
+/// The CPU load in percent of one core, or `None` if the server was not measured before
+fn cpu_load(cpu_times: &mut CpuTimes, target: &ServerRef, usage: ProcessUsage) -> Option<f64> {
+    let now = Instant::now();
+    let previous = cpu_times.insert(target.to_string(), (now, usage.cpu_time));
+    let (then, cpu_time) = previous?;
+    let elapsed = now.duration_since(then).as_secs_f64();
+    if elapsed <= 0.0 {
+        return None;
+    }
+    Some(usage.cpu_time.saturating_sub(cpu_time).as_secs_f64() / elapsed * 100.0)
+}
+
+/// Get the player count of a running server
+///
+/// A fresh sample of the supervisor is used if the metrics are collected,
+/// otherwise the server is queried directly.
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+/// * `server` - The installed server
+/// * `instance` - The instance, or `None` for the server itself
+/// * `target` - The reference to the server or instance
+///
+/// # Returns
+///
+/// The players without bots and the player slots, or `None` if the server did not answer
+fn players(
+    config: &Config,
+    server: &InstalledServer,
+    instance: Option<&ServerInstance>,
+    target: &ServerRef,
+) -> Option<(u32, u32)> {
+    if config.metrics.enabled {
+        let max_age = chrono::Duration::seconds(config.metrics.interval as i64 * 2);
+        let sample = MetricsStore::open(target)
+            .and_then(|store| store.latest())
+            .ok()
+            .flatten()
+            .filter(|sample| Utc::now() - sample.timestamp <= max_age);
+        if let Some(sample) = sample {
+            return Some((sample.players, sample.max_players));
+        }
+    }
+
+    let profile = GameProfile::find(server.app_id)?;
+    let port = LaunchCommand::port(&profile, server, instance)?;
+    let info = query::query_info("127.0.0.1", profile.query_port(port)).ok()?;
+    Some((
+        u32::from(info.players.saturating_sub(info.bots)),
+        u32::from(info.max_players),
+    ))
+}
@@ -0,0 +1,101 @@
+use serde_json::json;
+
+use crate::{
+    core::SteamCMD,
+    tr,
+    utils::{
+        fs,
+        output::{Column, OutputFormat, Table},
+        Config, InstalledServer, ServerRef,
+    },
+};
+
+/// Handle the `check-updates` command
+///
+/// # Arguments
+///
+/// * `server_name` - Only check this game server
+/// * `format` - The output format
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the updates were checked, otherwise an error
+///
+/// # Errors
+///
+/// Returns an error if the server does not exist or the tool is offline
+pub fn handle_check_updates_command(
+    server_name: Option<String>,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    config.ensure_online(&tr!("action-check-updates"))?;
+
+    let servers: Vec<&InstalledServer> = match server_name.as_deref().map(ServerRef::parse) {
+        Some(target) => vec![config
+            .find_server(&target.server)
+            .ok_or_else(|| tr!("server-not-installed", server = target.server))?],
+        None => config.installed_servers.iter().collect(),
+    };
+
+    let mut table = Table::new(vec![
+        Column::new("name", tr!("list-header-name"), 30),
+        Column::new("installed", tr!("check-updates-header-installed"), 12),
+        Column::new("latest", tr!("check-updates-header-latest"), 12),
+        Column::new("published", tr!("check-updates-header-published"), 18),
+        Column::new("download", tr!("check-updates-header-download"), 14),
+    ]);
+    let mut pending = 0;
+    let mut total = 0;
+
+    for server in servers {
+        let check = match SteamCMD::check_update(&config, server) {
+            Ok(check) => check,
+            Err(e) => {
+                eprintln!(
+                    "{}",
+                    tr!(
+                        "check-updates-failed",
+                        server = server.name,
+                        error = e.to_string()
+                    )
+                );
+                continue;
+            }
+        };
+
+        let download = match (check.is_pending(), check.download) {
+            (false, _) => tr!("check-updates-up-to-date"),
+            (true, Some(size)) => {
+                pending += 1;
+                total += size;
+                fs::format_size(size)
+            }
+            (true, None) => {
+                pending += 1;
+                tr!("check-updates-unknown")
+            }
+        };
+        table.push(vec![
+            json!(server.name),
+            json!(check.installed.unwrap_or_else(|| "-".to_string())),
+            json!(check.latest.id),
+            json!(check.latest.time.format("%Y-%m-%d %H:%M").to_string()),
+            json!(download),
+        ]);
+    }
+
+    println!("{}", table.render(format)?);
+    if format == OutputFormat::Table && pending > 0 {
+        println!(
+            "{}",
+            tr!(
+                "check-updates-total",
+                count = pending,
+                size = fs::format_size(total)
+            )
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use std::collections::BTreeMap;
+
+use crate::{core::ProfileRegistry, tr, utils::Config};
+
+/// Get the aliases of the built-in and user game profiles
+///
+/// Every profile is available under its name in lowercase with dashes and
+/// under the `aliases` of the profile.
+///
+/// # Returns
+///
+/// The aliases with their Steam App IDs
+pub fn builtin() -> BTreeMap<String, u32> {
+    ProfileRegistry::global()
+        .profiles()
+        .iter()
+        .map(|registered| &registered.profile)
+        .flat_map(|profile| {
+            std::iter::once(slug(&profile.name))
+                .chain(profile.aliases.iter().map(|a| a.to_lowercase()))
+                .filter(|alias| !alias.is_empty())
+                .map(|alias| (alias, profile.app_id))
+        })
+        .collect()
+}
+
+/// Get all aliases, the aliases of the config override the built-in ones
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+///
+/// # Returns
+///
+/// The aliases with their Steam App IDs
+pub fn all(config: &Config) -> BTreeMap<String, u32> {
+    let mut aliases = builtin();
+    aliases.extend(
+        config
+            .aliases
+            .iter()
+            .map(|(alias, app_id)| (alias.to_lowercase(), *app_id)),
+    );
+    aliases
+}
+
+/// Resolve an alias or a Steam App ID
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+/// * `value` - The alias or the numeric app ID
+///
+/// # Returns
+///
+/// The Steam App ID
+///
+/// # Errors
+///
+/// If the value is neither an app ID nor a known alias
+pub fn resolve(config: &Config, value: &str) -> Result<u32, Box<dyn std::error::Error>> {
+    let value = value.trim();
+    if let Ok(app_id) = value.parse() {
+        return Ok(app_id);
+    }
+
+    all(config)
+        .get(&value.to_lowercase())
+        .copied()
+        .ok_or_else(|| tr!("alias-unknown", alias = value).into())
+}
+
+/// Turn a game name into an alias
+///
+/// # Arguments
+///
+/// * `name` - The name of the game
+///
+/// # Returns
+///
+/// The lowercase name with dashes, without a `Dedicated Server` suffix, e.g.
+/// `valheim` for `Valheim Dedicated Server`
+pub fn slug(name: &str) -> String {
+    name.to_lowercase()
+        .replace("dedicated server", "")
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_aliases() {
+        let aliases = builtin();
+        assert_eq!(aliases.get("valheim"), Some(&896660));
+        assert_eq!(aliases.get("palworld"), Some(&2394010));
+        assert_eq!(aliases.get("cs2"), Some(&730));
+        assert_eq!(aliases.get("counter-strike-2"), Some(&730));
+    }
+}
@@ -0,0 +1,170 @@
+use std::{
+    fs::File,
+    io::Write,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    tr,
+    utils::{config::DEFAULT_STEAMCMD_URL, fs, Config, TimeoutConfig},
+};
+
+/// Size of the test file of the disk benchmark
+const DISK_TEST_SIZE: usize = 256 * 1024 * 1024;
+/// Size of a single write of the disk benchmark
+const DISK_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+/// Write speed below which a disk slows down every large update, in bytes per second
+const SLOW_DISK: f64 = 50.0 * 1024.0 * 1024.0;
+/// Size of a large update to estimate the duration for, about an update of ARK
+const LARGE_UPDATE: u64 = 20 * 1024 * 1024 * 1024;
+
+/// Measure the sequential write speed of a directory
+///
+/// A test file is written in chunks of random data and synced to the disk,
+/// then it is deleted again. For a directory that does not exist yet, the
+/// nearest existing parent is measured.
+///
+/// # Arguments
+///
+/// * `dir` - The directory, e.g. an install root
+///
+/// # Returns
+///
+/// The write speed in bytes per second
+///
+/// # Errors
+///
+/// If the test file could not be written
+pub fn disk_speed(dir: &Path) -> Result<f64, Box<dyn std::error::Error>> {
+    // Random data, so compressing filesystems do not skip the writes
+    let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+    let chunk: Vec<u8> = (0..DISK_CHUNK_SIZE)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect();
+
+    let existing = dir.ancestors().find(|p| p.is_dir()).unwrap_or(dir);
+    let path = existing.join(format!(".steamserv-bench-{}", std::process::id()));
+    let result = (|| {
+        let started = Instant::now();
+        let mut file = File::create(&path)?;
+        for _ in 0..DISK_TEST_SIZE / DISK_CHUNK_SIZE {
+            file.write_all(&chunk)?;
+        }
+        file.sync_all()?;
+        Ok::<Duration, std::io::Error>(started.elapsed())
+    })();
+    let _ = std::fs::remove_file(&path);
+
+    let elapsed = result.map_err(|e| tr!("bench-disk-failed", dir = dir.display(), error = e))?;
+    Ok(DISK_TEST_SIZE as f64 / elapsed.as_secs_f64())
+}
+
+/// Measure the download speed from the Steam CDN
+///
+/// Downloads the SteamCMD installer from the configured URL.
+///
+/// # Arguments
+///
+/// * `config` - The configuration with the network settings
+///
+/// # Returns
+///
+/// The download speed in bytes per second
+///
+/// # Errors
+///
+/// If the download failed
+pub async fn download_speed(config: &Config) -> Result<f64, Box<dyn std::error::Error>> {
+    let url = config
+        .steamcmd_url
+        .as_deref()
+        .unwrap_or(DEFAULT_STEAMCMD_URL);
+    let client = config
+        .network
+        .http_client(TimeoutConfig::limit(config.timeouts.network))?;
+
+    let started = Instant::now();
+    let mut response = client.get(url).send().await?.error_for_status()?;
+    let mut size = 0;
+    while let Some(chunk) = response.chunk().await? {
+        size += chunk.len();
+    }
+    Ok(size as f64 / started.elapsed().as_secs_f64())
+}
+
+/// Verdict on the write speed of a disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// The disk slows down every large update
+    Slow,
+    /// The disk is slower than the download and limits the updates
+    Bottleneck,
+    Ok,
+}
+
+impl Verdict {
+    /// Judge the write speed of a disk
+    ///
+    /// # Arguments
+    ///
+    /// * `disk` - The write speed of the disk in bytes per second
+    /// * `network` - The download speed in bytes per second, if it was measured
+    ///
+    /// # Returns
+    ///
+    /// The verdict
+    pub fn judge(disk: f64, network: Option<f64>) -> Self {
+        if disk < SLOW_DISK {
+            Self::Slow
+        } else if network.is_some_and(|network| disk < network) {
+            Self::Bottleneck
+        } else {
+            Self::Ok
+        }
+    }
+
+    /// Describe the verdict with the estimated duration of a large update
+    ///
+    /// # Arguments
+    ///
+    /// * `disk` - The write speed of the disk in bytes per second
+    /// * `network` - The download speed in bytes per second, if it was measured
+    ///
+    /// # Returns
+    ///
+    /// The translated verdict
+    pub fn describe(&self, disk: f64, network: Option<f64>) -> String {
+        let limit = network.map_or(disk, |network| network.min(disk));
+        let minutes = (LARGE_UPDATE as f64 / limit / 60.0).ceil();
+        let size = fs::format_size(LARGE_UPDATE);
+        let key = match self {
+            Self::Slow => "bench-verdict-slow",
+            Self::Bottleneck => "bench-verdict-bottleneck",
+            Self::Ok => "bench-verdict-ok",
+        };
+        tr!(key, size = size, minutes = minutes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verdict() {
+        let mib = 1024.0 * 1024.0;
+        assert_eq!(Verdict::judge(20.0 * mib, None), Verdict::Slow);
+        assert_eq!(
+            Verdict::judge(80.0 * mib, Some(100.0 * mib)),
+            Verdict::Bottleneck
+        );
+        assert_eq!(Verdict::judge(500.0 * mib, Some(100.0 * mib)), Verdict::Ok);
+        assert_eq!(Verdict::judge(80.0 * mib, None), Verdict::Ok);
+    }
+}
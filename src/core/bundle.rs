@@ -0,0 +1,303 @@
+use std::{
+    fmt::Write as _,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use chrono::Local;
+
+use crate::{
+    core::{GameProfile, ServerProcess},
+    tr,
+    utils::{fs, history, Config, ServerRef},
+};
+
+/// Number of lines of the server log in a bundle
+const LOG_TAIL_LINES: usize = 500;
+/// Number of history events of the server in a bundle
+const HISTORY_EVENTS: usize = 100;
+/// Number of bundles kept per server, the oldest are deleted
+const KEEP_BUNDLES: usize = 5;
+/// Placeholder for the secrets removed from a bundle
+const REDACTED: &str = "<redacted>";
+
+/// Create a support bundle of a server or instance for a bug report
+///
+/// The bundle contains a summary of the system, the redacted config of the
+/// server, the tail of its log, its history and where core dumps are written.
+/// The secrets of the config are removed from all files. It is packed to
+/// `bundles/<server>-<time>.tar.gz` in the steamserv directory, or kept as a
+/// directory if `tar` is missing.
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+/// * `target` - The server or instance
+/// * `reason` - The crash that triggered the bundle, if any
+///
+/// # Returns
+///
+/// The path of the bundle
+///
+/// # Errors
+///
+/// If the server does not exist or the bundle could not be written
+pub fn create(
+    config: &Config,
+    target: &ServerRef,
+    reason: Option<&str>,
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let (server, _) = config.resolve(target)?;
+    let process = ServerProcess::new(target)?;
+    let dir = bundles_dir()?;
+    let prefix = target.to_string().replace('/', "@");
+    let name = format!("{}-{}", prefix, Local::now().format("%Y%m%d-%H%M%S"));
+    let bundle = dir.join(&name);
+    std::fs::create_dir_all(&bundle)?;
+
+    // The secrets are removed from the config and from everything else, e.g.
+    // an RCON password in a logged command line
+    let mut redacted = config.clone();
+    let mut secrets: Vec<String> = Vec::new();
+    for field in redacted.secret_fields_mut() {
+        if !field.is_empty() {
+            secrets.push(std::mem::replace(field, REDACTED.to_string()));
+        }
+    }
+    let redact = |text: &str| {
+        secrets.iter().fold(text.to_string(), |text, secret| {
+            text.replace(secret, REDACTED)
+        })
+    };
+
+    let mut summary = String::new();
+    writeln!(summary, "steamserv-rs {}", env!("CARGO_PKG_VERSION"))?;
+    writeln!(summary, "created: {}", Local::now().to_rfc3339())?;
+    writeln!(summary, "target: {}", target)?;
+    writeln!(summary, "app_id: {}", server.app_id)?;
+    if let Some(profile) = GameProfile::find(server.app_id) {
+        writeln!(summary, "profile: {}", profile.name)?;
+    }
+    writeln!(
+        summary,
+        "branch: {}",
+        server.branch.as_deref().unwrap_or("public")
+    )?;
+    writeln!(
+        summary,
+        "last_updated: {}",
+        server.last_updated.to_rfc3339()
+    )?;
+    match process.pid() {
+        Some(pid) => writeln!(summary, "running: pid {}", pid)?,
+        None => writeln!(summary, "running: no")?,
+    }
+    if let Some(reason) = reason {
+        writeln!(summary, "crash: {}", reason)?;
+    }
+    writeln!(
+        summary,
+        "system: {} {} {}",
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        os_release().unwrap_or_default()
+    )?;
+    writeln!(summary, "config_dir: {}", Config::data_dir()?.display())?;
+    writeln!(summary, "install_path: {}", server.install_path.display())?;
+    std::fs::write(bundle.join("summary.txt"), redact(&summary))?;
+
+    // The entry of the server includes its instances
+    let (server_config, _) = redacted.resolve(target)?;
+    std::fs::write(
+        bundle.join("server.toml"),
+        redact(&toml::to_string_pretty(server_config)?),
+    )?;
+
+    let log = std::fs::read(&process.log_file).unwrap_or_default();
+    let log = String::from_utf8_lossy(&log);
+    let lines: Vec<&str> = log.lines().collect();
+    let mut tail = lines[lines.len().saturating_sub(LOG_TAIL_LINES)..].join("\n");
+    tail.push('\n');
+    std::fs::write(bundle.join("log-tail.txt"), redact(&tail))?;
+
+    let key = target.to_string();
+    let events = history::load(Some(&key))?;
+    let mut content = String::new();
+    for event in &events[events.len().saturating_sub(HISTORY_EVENTS)..] {
+        writeln!(content, "{}", serde_json::to_string(event)?)?;
+    }
+    std::fs::write(bundle.join("history.jsonl"), redact(&content))?;
+
+    std::fs::write(
+        bundle.join("core-dumps.txt"),
+        redact(&core_dumps(&server.install_path)),
+    )?;
+
+    let path = pack(&dir, &name).unwrap_or(bundle);
+    prune(&dir, &prefix)?;
+    Ok(path)
+}
+
+/// Get the directory of the support bundles
+///
+/// # Returns
+///
+/// The directory `bundles` in the steamserv directory
+///
+/// # Errors
+///
+/// If the steamserv directory could not be found
+pub fn bundles_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(Config::data_dir()?.join("bundles"))
+}
+
+/// Describe where the core dumps of a server are written
+///
+/// # Arguments
+///
+/// * `install_path` - The install directory of the server
+///
+/// # Returns
+///
+/// The core pattern of the kernel and the core files in the install directory
+fn core_dumps(install_path: &Path) -> String {
+    let mut content = String::new();
+    let pattern = std::fs::read_to_string("/proc/sys/kernel/core_pattern").unwrap_or_default();
+    let pattern = pattern.trim();
+    let _ = writeln!(content, "core_pattern: {}", pattern);
+    if pattern.starts_with('|') {
+        // Core dumps piped to a handler, e.g. systemd-coredump, are not files
+        let _ = writeln!(content, "{}", tr!("bundle-core-handler"));
+    }
+
+    let cores = std::fs::read_dir(install_path)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("core"))
+        .filter_map(|entry| Some((entry.path(), entry.metadata().ok()?)))
+        .filter(|(_, meta)| meta.is_file());
+    for (path, meta) in cores {
+        let _ = writeln!(
+            content,
+            "{} ({})",
+            path.display(),
+            fs::format_size(meta.len())
+        );
+    }
+    content
+}
+
+/// Get the name of the Linux distribution
+///
+/// # Returns
+///
+/// The pretty name from `/etc/os-release`, or `None` if it could not be read
+fn os_release() -> Option<String> {
+    let content = std::fs::read_to_string("/etc/os-release").ok()?;
+    content.lines().find_map(|line| {
+        line.strip_prefix("PRETTY_NAME=")
+            .map(|name| name.trim_matches('"').to_string())
+    })
+}
+
+/// Pack a bundle directory with `tar` and remove the directory
+///
+/// # Arguments
+///
+/// * `dir` - The directory of the bundles
+/// * `name` - The name of the bundle directory
+///
+/// # Returns
+///
+/// The path of the archive, or `None` if `tar` failed and the directory was kept
+fn pack(dir: &Path, name: &str) -> Option<PathBuf> {
+    let archive = dir.join(format!("{}.tar.gz", name));
+    let status = Command::new("tar")
+        .arg("-czf")
+        .arg(&archive)
+        .arg("-C")
+        .arg(dir)
+        .arg(name)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .ok()?;
+    if !status.success() {
+        let _ = std::fs::remove_file(&archive);
+        return None;
+    }
+    let _ = std::fs::remove_dir_all(dir.join(name));
+    Some(archive)
+}
+
+/// Delete the oldest bundles of a server beyond [`KEEP_BUNDLES`]
+///
+/// # Arguments
+///
+/// * `dir` - The directory of the bundles
+/// * `prefix` - The file name of the server or instance
+///
+/// # Returns
+///
+/// Ok if the old bundles were deleted
+///
+/// # Errors
+///
+/// If a bundle could not be deleted
+fn prune(dir: &Path, prefix: &str) -> Result<(), Box<dyn std::error::Error>> {
+    // The time in the name sorts the bundles, it is checked so the bundles of
+    // `cs2` do not include those of a server `cs2-eu`
+    let mut bundles: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix(prefix))
+                .and_then(|rest| rest.strip_prefix('-'))
+                .is_some_and(is_bundle_time)
+        })
+        .collect();
+    bundles.sort();
+
+    for old in &bundles[..bundles.len().saturating_sub(KEEP_BUNDLES)] {
+        if old.is_dir() {
+            std::fs::remove_dir_all(old)?;
+        } else {
+            std::fs::remove_file(old)?;
+        }
+    }
+    Ok(())
+}
+
+/// Check if a file name continues with the time of a bundle, e.g. `20240101-120000.tar.gz`
+///
+/// # Arguments
+///
+/// * `name` - The rest of the file name after the server
+///
+/// # Returns
+///
+/// True if the name starts with the time
+fn is_bundle_time(name: &str) -> bool {
+    let time = name.split('.').next().unwrap_or_default();
+    time.len() == 15
+        && time
+            .chars()
+            .enumerate()
+            .all(|(i, c)| if i == 8 { c == '-' } else { c.is_ascii_digit() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_bundle_time() {
+        assert!(is_bundle_time("20240101-120000.tar.gz"));
+        assert!(is_bundle_time("20240101-120000"));
+        assert!(!is_bundle_time("eu-20240101-120000.tar.gz"));
+        assert!(!is_bundle_time("1-20240101-120000"));
+    }
+}
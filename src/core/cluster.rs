@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{Config, InstalledServer, ServerInstance};
+
+/// Servers that share a cluster directory, e.g. the maps of an ARK cluster
+///
+/// Players move their characters and items between the servers of a cluster.
+/// The `cluster_args` of the game profile pass the ID and the directory to the
+/// servers when they are launched.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Cluster {
+    /// The cluster ID passed to the game, the same on all servers of the cluster
+    pub id: String,
+    /// The directory the servers exchange the transferred data in
+    pub dir: PathBuf,
+    /// The servers and instances of the cluster, as `server` or `server/instance`
+    #[serde(default)]
+    pub servers: Vec<String>,
+}
+
+impl Cluster {
+    /// Find the cluster of a server or instance
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration with the clusters
+    /// * `server` - The installed server
+    /// * `instance` - The instance, or `None` for the server itself
+    ///
+    /// # Returns
+    ///
+    /// The name and the cluster, or `None` if the server is in no cluster
+    pub fn find<'a>(
+        config: &'a Config,
+        server: &InstalledServer,
+        instance: Option<&ServerInstance>,
+    ) -> Option<(&'a String, &'a Cluster)> {
+        let target = match instance {
+            Some(instance) => format!("{}/{}", server.name, instance.name),
+            None => server.name.clone(),
+        };
+        config
+            .clusters
+            .iter()
+            .find(|(_, cluster)| cluster.servers.contains(&target))
+    }
+
+    /// Replace the cluster placeholders of a launch argument template
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - The template with `{cluster_id}` and `{cluster_dir}`
+    ///
+    /// # Returns
+    ///
+    /// The template with the values of the cluster
+    pub fn render(&self, template: &str) -> String {
+        template
+            .replace("{cluster_id}", &self.id)
+            .replace("{cluster_dir}", &self.dir.display().to_string())
+    }
+}
@@ -0,0 +1,285 @@
+use std::{
+    collections::BTreeMap,
+    process::{Command, Stdio},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::GameProfile,
+    tr,
+    utils::privilege::{self, Escalation},
+};
+
+/// A package a game server needs at runtime, declared in its game profile
+///
+/// The dependency is installed if its library or command is found, without
+/// either the package manager is asked for the package.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Dependency {
+    pub name: String,
+    /// A shared library the loader has to find, e.g. `libSDL2-2.0.so.0`
+    pub library: Option<String>,
+    /// Look for the 32-bit build of the library
+    #[serde(default)]
+    pub i386: bool,
+    /// A command that has to be in the `PATH`, e.g. `java`
+    pub command: Option<String>,
+    /// The package per package manager, e.g. `apt = "lib32gcc-s1"`
+    #[serde(default)]
+    pub packages: BTreeMap<String, String>,
+}
+
+/// A package manager of a Linux distribution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageManager {
+    /// Debian and Ubuntu
+    Apt,
+    /// Fedora, RHEL and their derivatives
+    Dnf,
+    /// Arch Linux
+    Pacman,
+}
+
+impl PackageManager {
+    /// Find the package manager of the system
+    ///
+    /// # Returns
+    ///
+    /// The first package manager in the `PATH`, or `None` if there is none
+    pub fn detect() -> Option<Self> {
+        [
+            (Self::Apt, "apt-get"),
+            (Self::Dnf, "dnf"),
+            (Self::Pacman, "pacman"),
+        ]
+        .into_iter()
+        .find(|(_, command)| in_path(command))
+        .map(|(manager, _)| manager)
+    }
+
+    /// Get the key of the package manager in the `packages` of a dependency
+    ///
+    /// # Returns
+    ///
+    /// The key, e.g. `apt`
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Apt => "apt",
+            Self::Dnf => "dnf",
+            Self::Pacman => "pacman",
+        }
+    }
+
+    /// Check if a package is installed
+    ///
+    /// # Arguments
+    ///
+    /// * `package` - The name of the package
+    ///
+    /// # Returns
+    ///
+    /// True if the package manager knows the package as installed
+    pub fn is_installed(self, package: &str) -> bool {
+        let mut command = match self {
+            Self::Apt => {
+                let mut command = Command::new("dpkg-query");
+                command.args(["-W", "-f=${Status}", package]);
+                command
+            }
+            Self::Dnf => {
+                let mut command = Command::new("rpm");
+                command.args(["-q", package]);
+                command
+            }
+            Self::Pacman => {
+                let mut command = Command::new("pacman");
+                command.args(["-Q", package]);
+                command
+            }
+        };
+        let Ok(output) = command.stderr(Stdio::null()).output() else {
+            return false;
+        };
+        // dpkg also knows removed packages, only their status differs
+        output.status.success()
+            && (self != Self::Apt || String::from_utf8_lossy(&output.stdout).contains(" installed"))
+    }
+
+    /// Get the step that installs packages
+    ///
+    /// # Arguments
+    ///
+    /// * `packages` - The names of the packages
+    ///
+    /// # Returns
+    ///
+    /// The step with the install command of the package manager
+    pub fn install_step(self, packages: &[String]) -> Escalation {
+        let command: &[&str] = match self {
+            Self::Apt => &["apt-get", "install", "-y"],
+            Self::Dnf => &["dnf", "install", "-y"],
+            Self::Pacman => &["pacman", "-S", "--needed", "--noconfirm"],
+        };
+        Escalation {
+            reason: tr!("deps-install-reason", count = packages.len()),
+            command: command
+                .iter()
+                .map(|arg| arg.to_string())
+                .chain(packages.iter().cloned())
+                .collect(),
+        }
+    }
+}
+
+/// Result of the check of a dependency
+#[derive(Debug, Clone)]
+pub struct DependencyCheck {
+    pub dependency: Dependency,
+    /// The package of the package manager of the system
+    pub package: Option<String>,
+    /// Whether the dependency is installed, `None` if it could not be checked
+    pub installed: Option<bool>,
+}
+
+/// Check the dependencies of a game profile
+///
+/// # Arguments
+///
+/// * `profile` - The game profile
+/// * `manager` - The package manager of the system
+///
+/// # Returns
+///
+/// The result of every dependency of the profile
+pub fn check(profile: &GameProfile, manager: Option<PackageManager>) -> Vec<DependencyCheck> {
+    let cache = if profile.dependencies.iter().any(|d| d.library.is_some()) {
+        library_cache()
+    } else {
+        String::new()
+    };
+
+    profile
+        .dependencies
+        .iter()
+        .map(|dependency| {
+            let package = manager.and_then(|m| dependency.packages.get(m.key()).cloned());
+            let installed = if let Some(library) = &dependency.library {
+                Some(has_library(&cache, library, dependency.i386))
+            } else if let Some(command) = &dependency.command {
+                Some(in_path(command))
+            } else {
+                manager
+                    .zip(package.as_deref())
+                    .map(|(manager, package)| manager.is_installed(package))
+            };
+            DependencyCheck {
+                dependency: dependency.clone(),
+                package,
+                installed,
+            }
+        })
+        .collect()
+}
+
+/// Install packages with the package manager
+///
+/// As root the package manager runs directly, otherwise through `sudo` with
+/// `--sudo` like the other steps that need root.
+///
+/// # Arguments
+///
+/// * `manager` - The package manager of the system
+/// * `packages` - The names of the packages
+///
+/// # Returns
+///
+/// Ok if the packages were installed
+///
+/// # Errors
+///
+/// If steamserv does not run as root and `--sudo` was not given, or the
+/// package manager failed
+pub fn install(
+    manager: PackageManager,
+    packages: &[String],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let step = manager.install_step(packages);
+    if !privilege::is_root() {
+        return privilege::escalate(&[step]);
+    }
+
+    let status = Command::new(&step.command[0])
+        .args(&step.command[1..])
+        .status()?;
+    if !status.success() {
+        return Err(tr!("deps-install-failed", command = step.command.join(" ")).into());
+    }
+    Ok(())
+}
+
+/// Check if a command is in the `PATH`
+///
+/// # Arguments
+///
+/// * `command` - The name of the command
+///
+/// # Returns
+///
+/// True if an executable file of that name is in one of the directories
+fn in_path(command: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|paths| std::env::split_paths(&paths).any(|dir| dir.join(command).is_file()))
+}
+
+/// Get the libraries known to the loader
+///
+/// # Returns
+///
+/// The output of `ldconfig -p`, empty if it could not be run
+fn library_cache() -> String {
+    ["ldconfig", "/sbin/ldconfig", "/usr/sbin/ldconfig"]
+        .into_iter()
+        .find_map(|ldconfig| Command::new(ldconfig).arg("-p").output().ok())
+        .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+        .unwrap_or_default()
+}
+
+/// Check if the loader knows a library
+///
+/// # Arguments
+///
+/// * `cache` - The output of `ldconfig -p`
+/// * `library` - The file name of the library
+/// * `i386` - Look for the 32-bit build of the library
+///
+/// # Returns
+///
+/// True if the library is in the cache
+fn has_library(cache: &str, library: &str, i386: bool) -> bool {
+    cache.lines().any(|line| {
+        // e.g. `libgcc_s.so.1 (libc6,x86-64) => /lib/x86_64-linux-gnu/libgcc_s.so.1`
+        let Some((name, rest)) = line.trim().split_once(' ') else {
+            return false;
+        };
+        let flags = rest.split(')').next().unwrap_or_default();
+        name == library && !(i386 && flags.contains("64"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_library() {
+        let cache = "1234 libs found in cache `/etc/ld.so.cache'\n\
+            \tlibgcc_s.so.1 (libc6,x86-64) => /lib/x86_64-linux-gnu/libgcc_s.so.1\n\
+            \tlibstdc++.so.6 (libc6,x86-64) => /lib/x86_64-linux-gnu/libstdc++.so.6\n\
+            \tlibstdc++.so.6 (libc6) => /lib32/libstdc++.so.6\n";
+        assert!(has_library(cache, "libgcc_s.so.1", false));
+        assert!(!has_library(cache, "libgcc_s.so.1", true));
+        assert!(has_library(cache, "libstdc++.so.6", true));
+        assert!(!has_library(cache, "libSDL2-2.0.so.0", false));
+    }
+}
@@ -0,0 +1,315 @@
+use std::{future::Future, net::IpAddr, pin::Pin};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    core::nat,
+    tr,
+    utils::{fs, Config, TimeoutConfig},
+};
+
+/// Base URL of the Cloudflare API
+const CLOUDFLARE_API_URL: &str = "https://api.cloudflare.com/client/v4";
+
+/// Update URL of DuckDNS
+const DUCKDNS_UPDATE_URL: &str = "https://www.duckdns.org/update";
+
+/// Name of the file with the last published address
+const STATE_FILE: &str = "dns.json";
+
+/// Future returned by the DNS providers
+pub type UpdateFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<(), Box<dyn std::error::Error>>> + 'a>>;
+
+/// Settings of the dynamic DNS updates done by the daemon
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct DnsConfig {
+    /// The public hostname of the servers, e.g. `games.example.com`
+    pub hostname: Option<String>,
+    /// Seconds between two checks of the public address
+    pub interval: u64,
+    /// The DNS provider that gets the updates, `None` only shows the hostname
+    pub provider: Option<ProviderConfig>,
+}
+
+impl Default for DnsConfig {
+    /// Check the public address every five minutes without provider
+    fn default() -> Self {
+        Self {
+            hostname: None,
+            interval: 300,
+            provider: None,
+        }
+    }
+}
+
+/// A DNS provider with its credentials
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ProviderConfig {
+    /// A record in a Cloudflare zone, the token needs the `DNS:Edit` permission
+    Cloudflare { api_token: String, zone_id: String },
+    /// A subdomain of `duckdns.org`
+    DuckDns { token: String },
+}
+
+impl ProviderConfig {
+    /// Get the provider for the settings
+    ///
+    /// # Returns
+    ///
+    /// The provider that updates the records
+    pub fn provider(&self) -> Box<dyn DnsProvider + '_> {
+        match self {
+            Self::Cloudflare { api_token, zone_id } => Box::new(Cloudflare { api_token, zone_id }),
+            Self::DuckDns { token } => Box::new(DuckDns { token }),
+        }
+    }
+
+    /// Get the credentials of the provider
+    ///
+    /// # Returns
+    ///
+    /// Mutable references to the tokens, to encrypt them with the other secrets
+    pub fn secrets_mut(&mut self) -> Vec<&mut String> {
+        match self {
+            Self::Cloudflare { api_token, .. } => vec![api_token],
+            Self::DuckDns { token } => vec![token],
+        }
+    }
+}
+
+/// A service that points a hostname to an address
+pub trait DnsProvider {
+    /// Point a hostname to an address
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The HTTP client
+    /// * `hostname` - The hostname to update
+    /// * `ip` - The new address
+    ///
+    /// # Returns
+    ///
+    /// A future that resolves when the record was updated
+    ///
+    /// # Errors
+    ///
+    /// The future fails if the provider rejected the update
+    fn update<'a>(
+        &'a self,
+        client: &'a reqwest::Client,
+        hostname: &'a str,
+        ip: IpAddr,
+    ) -> UpdateFuture<'a>;
+}
+
+/// Cloudflare DNS, updates the A or AAAA record of the hostname or creates it
+pub struct Cloudflare<'a> {
+    api_token: &'a str,
+    zone_id: &'a str,
+}
+
+impl DnsProvider for Cloudflare<'_> {
+    fn update<'a>(
+        &'a self,
+        client: &'a reqwest::Client,
+        hostname: &'a str,
+        ip: IpAddr,
+    ) -> UpdateFuture<'a> {
+        Box::pin(async move {
+            let record_type = if ip.is_ipv4() { "A" } else { "AAAA" };
+            let records_url = format!("{}/zones/{}/dns_records", CLOUDFLARE_API_URL, self.zone_id);
+            let found: Value = client
+                .get(&records_url)
+                .bearer_auth(self.api_token)
+                .query(&[("type", record_type), ("name", hostname)])
+                .send()
+                .await?
+                .json()
+                .await?;
+            check_cloudflare(&found)?;
+
+            let record = json!({
+                "type": record_type,
+                "name": hostname,
+                "content": ip.to_string(),
+                "ttl": 1,
+                "proxied": false,
+            });
+            let request = match found["result"][0]["id"].as_str() {
+                Some(id) => client.put(format!("{}/{}", records_url, id)),
+                None => client.post(&records_url),
+            };
+            let response: Value = request
+                .bearer_auth(self.api_token)
+                .json(&record)
+                .send()
+                .await?
+                .json()
+                .await?;
+            check_cloudflare(&response)
+        })
+    }
+}
+
+/// DuckDNS, updates the subdomain of the hostname
+pub struct DuckDns<'a> {
+    token: &'a str,
+}
+
+impl DnsProvider for DuckDns<'_> {
+    fn update<'a>(
+        &'a self,
+        client: &'a reqwest::Client,
+        hostname: &'a str,
+        ip: IpAddr,
+    ) -> UpdateFuture<'a> {
+        Box::pin(async move {
+            let domain = hostname.trim_end_matches(".duckdns.org");
+            let address = if ip.is_ipv4() { "ip" } else { "ipv6" };
+            let response = client
+                .get(DUCKDNS_UPDATE_URL)
+                .query(&[
+                    ("domains", domain),
+                    ("token", self.token),
+                    (address, &ip.to_string()),
+                ])
+                .send()
+                .await?
+                .text()
+                .await?;
+            // DuckDNS answers KO for every error without details
+            if response.trim() != "OK" {
+                return Err(tr!("dns-update-rejected", provider = "DuckDNS").into());
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Check the response of the Cloudflare API for errors
+///
+/// # Arguments
+///
+/// * `response` - The JSON response
+///
+/// # Returns
+///
+/// Ok if the request succeeded
+///
+/// # Errors
+///
+/// The messages of the errors Cloudflare reported
+fn check_cloudflare(response: &Value) -> Result<(), Box<dyn std::error::Error>> {
+    if response["success"].as_bool() == Some(true) {
+        return Ok(());
+    }
+    let errors: Vec<&str> = response["errors"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e["message"].as_str())
+        .collect();
+    if errors.is_empty() {
+        return Err(tr!("dns-update-rejected", provider = "Cloudflare").into());
+    }
+    Err(tr!("dns-cloudflare-failed", error = errors.join(", ")).into())
+}
+
+/// The address that was last published, shown by `steamserv status`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DnsState {
+    pub ip: IpAddr,
+    pub updated: DateTime<Utc>,
+}
+
+impl DnsState {
+    /// Load the last published address
+    ///
+    /// # Returns
+    ///
+    /// The state, or `None` if no address was published yet
+    pub fn load() -> Option<Self> {
+        let path = Config::data_dir().ok()?.join(STATE_FILE);
+        serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+    }
+
+    /// Save the published address
+    ///
+    /// # Returns
+    ///
+    /// Ok if the state was saved
+    ///
+    /// # Errors
+    ///
+    /// If the file could not be written
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Config::data_dir()?.join(STATE_FILE);
+        fs::write_atomic(&path, serde_json::to_string_pretty(self)?.as_bytes())
+    }
+}
+
+/// Point the hostname to the current public address if it changed
+///
+/// # Arguments
+///
+/// * `config` - The configuration with the DNS and network settings
+///
+/// # Returns
+///
+/// The new address if the record was updated, `None` if nothing changed or no
+/// hostname or provider is configured
+///
+/// # Errors
+///
+/// If the public address could not be found or the provider rejected the update
+pub async fn update(config: &Config) -> Result<Option<IpAddr>, Box<dyn std::error::Error>> {
+    let (Some(hostname), Some(provider)) = (&config.dns.hostname, &config.dns.provider) else {
+        return Ok(None);
+    };
+    config.ensure_online(&tr!("action-dns-update"))?;
+
+    let client = config
+        .network
+        .http_client(TimeoutConfig::limit(config.timeouts.network))?;
+    let ip = nat::public_ip(&client, nat::probe_url(config))
+        .await
+        .ok_or_else(|| tr!("dns-no-public-ip"))?;
+    if DnsState::load().is_some_and(|state| state.ip == ip) {
+        return Ok(None);
+    }
+
+    provider.provider().update(&client, hostname, ip).await?;
+    DnsState {
+        ip,
+        updated: Utc::now(),
+    }
+    .save()?;
+    Ok(Some(ip))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_config() {
+        let config: DnsConfig = toml::from_str(
+            "hostname = \"games.duckdns.org\"\nprovider = { type = \"duckdns\", token = \"abc\" }",
+        )
+        .unwrap();
+        assert_eq!(config.interval, 300);
+        assert!(matches!(
+            config.provider,
+            Some(ProviderConfig::DuckDns { ref token }) if token == "abc"
+        ));
+
+        let failed = json!({ "success": false, "errors": [{ "message": "Invalid zone" }] });
+        assert!(check_cloudflare(&failed).is_err());
+        assert!(check_cloudflare(&json!({ "success": true })).is_ok());
+    }
+}
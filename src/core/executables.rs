@@ -0,0 +1,261 @@
+use std::{
+    fmt,
+    io::Read,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+};
+
+use crate::core::ProfileRegistry;
+
+/// Depth up to which the install directory is searched for executables
+const MAX_DEPTH: usize = 4;
+/// Directories of an install that never contain the server binary
+const SKIPPED_DIRS: &[&str] = &["steamapps", ".git"];
+/// Parts of the names of server binaries and launch scripts
+const SERVER_NAMES: &[&str] = &["server", "dedicated", "srcds", "hlds"];
+/// Parts of the names of tools that are shipped next to the server
+const HELPER_NAMES: &[&str] = &["crash", "unins", "errorreporter", "steamcmd", "update"];
+
+/// Kind of an executable file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutableKind {
+    /// A Linux binary
+    Elf,
+    /// A Windows binary, launched through Wine or Proton
+    Pe,
+    /// A script with a `#!` line
+    Script,
+}
+
+/// An executable file of an install
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Executable {
+    /// The path relative to the install directory
+    pub path: PathBuf,
+    pub kind: ExecutableKind,
+}
+
+impl fmt::Display for Executable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            ExecutableKind::Elf => "Linux",
+            ExecutableKind::Pe => "Windows",
+            ExecutableKind::Script => "script",
+        };
+        write!(f, "{} ({})", self.path.display(), kind)
+    }
+}
+
+/// Find the executables of an install
+///
+/// Linux binaries and scripts need the executable bit, shared libraries are
+/// left out. Windows binaries are found by their `.exe` extension.
+///
+/// # Arguments
+///
+/// * `dir` - The install directory
+///
+/// # Returns
+///
+/// The executables, the ones closer to the install directory first
+pub fn scan(dir: &Path) -> Vec<Executable> {
+    let mut executables = Vec::new();
+    scan_dir(dir, Path::new(""), 0, &mut executables);
+    executables.sort_by(|a, b| {
+        let depth = |e: &Executable| e.path.components().count();
+        depth(a).cmp(&depth(b)).then_with(|| a.path.cmp(&b.path))
+    });
+    executables
+}
+
+/// Find the executables of an install, the likely server binary first
+///
+/// The executables of the known game profiles are taken as examples of server
+/// binaries.
+///
+/// # Arguments
+///
+/// * `dir` - The install directory
+///
+/// # Returns
+///
+/// The ranked executables
+pub fn suggest(dir: &Path) -> Vec<Executable> {
+    let known: Vec<String> = ProfileRegistry::global()
+        .profiles()
+        .iter()
+        .map(|p| p.profile.executable.clone())
+        .collect();
+    rank(scan(dir), &known)
+}
+
+/// Rank executables by how likely they start the server
+///
+/// A path of a profile ranks first, then the file name of a profile, names
+/// like `server` or `dedicated`, other executables and last tools like crash
+/// reporters. Within a rank Linux binaries and scripts come before Windows
+/// binaries, otherwise the order of [`scan`] is kept.
+///
+/// # Arguments
+///
+/// * `executables` - The executables of an install
+/// * `known` - The executables of the game profiles, relative to their install
+///
+/// # Returns
+///
+/// The ranked executables
+fn rank(mut executables: Vec<Executable>, known: &[String]) -> Vec<Executable> {
+    let file_name = |path: &Path| {
+        path.file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default()
+    };
+    let known_names: Vec<String> = known.iter().map(|k| file_name(Path::new(k))).collect();
+
+    executables.sort_by_key(|e| {
+        let name = file_name(&e.path);
+        let rank = if known.iter().any(|k| Path::new(k) == e.path) {
+            0
+        } else if known_names.contains(&name) {
+            1
+        } else if HELPER_NAMES.iter().any(|h| name.contains(h)) {
+            4
+        } else if SERVER_NAMES.iter().any(|s| name.contains(s)) {
+            2
+        } else {
+            3
+        };
+        (rank, e.kind == ExecutableKind::Pe)
+    });
+    executables
+}
+
+/// Search a directory of an install for executables
+///
+/// # Arguments
+///
+/// * `root` - The install directory
+/// * `relative` - The directory to search, relative to the install directory
+/// * `depth` - The depth of the directory
+/// * `executables` - The found executables
+fn scan_dir(root: &Path, relative: &Path, depth: usize, executables: &mut Vec<Executable>) {
+    let Ok(entries) = std::fs::read_dir(root.join(relative)) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let path = relative.join(&name);
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            let skipped = name.to_str().is_some_and(|n| SKIPPED_DIRS.contains(&n));
+            if depth + 1 < MAX_DEPTH && !skipped {
+                scan_dir(root, &path, depth + 1, executables);
+            }
+        } else if file_type.is_file() {
+            if let Some(kind) = kind(&root.join(&path)) {
+                executables.push(Executable { path, kind });
+            }
+        }
+    }
+}
+
+/// Detect the kind of an executable file
+///
+/// # Arguments
+///
+/// * `path` - The file
+///
+/// # Returns
+///
+/// The kind, or `None` if the file is no executable
+pub fn kind(path: &Path) -> Option<ExecutableKind> {
+    let name = path.file_name()?.to_str()?;
+    let mut magic = [0u8; 4];
+    let read = std::fs::File::open(path).ok()?.read(&mut magic).ok()?;
+    let magic = &magic[..read];
+
+    if name.to_lowercase().ends_with(".exe") {
+        return magic.starts_with(b"MZ").then_some(ExecutableKind::Pe);
+    }
+    let executable = std::fs::metadata(path).ok()?.permissions().mode() & 0o111 != 0;
+    if !executable || name.contains(".so") {
+        return None;
+    }
+    if magic.starts_with(b"\x7fELF") {
+        Some(ExecutableKind::Elf)
+    } else if magic.starts_with(b"#!") {
+        Some(ExecutableKind::Script)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_executables() {
+        let dir = std::env::temp_dir().join(format!("steamserv-exe-{}", std::process::id()));
+        let write = |path: &str, content: &[u8], mode: u32| {
+            let path = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, content).unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).unwrap();
+        };
+        write("bin/server_x64", b"\x7fELF\x02", 0o755);
+        write("start.sh", b"#!/bin/sh\n", 0o755);
+        write("Server.exe", b"MZ\x90\x00", 0o644);
+        write("linux64/steamclient.so", b"\x7fELF\x02", 0o755);
+        write("readme.txt", b"hello", 0o755);
+        write("steamapps/tool", b"\x7fELF\x02", 0o755);
+
+        let found: Vec<(String, ExecutableKind)> = scan(&dir)
+            .into_iter()
+            .map(|e| (e.path.display().to_string(), e.kind))
+            .collect();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            found,
+            vec![
+                ("Server.exe".to_string(), ExecutableKind::Pe),
+                ("start.sh".to_string(), ExecutableKind::Script),
+                ("bin/server_x64".to_string(), ExecutableKind::Elf),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rank_executables() {
+        let exe = |path: &str, kind| Executable {
+            path: PathBuf::from(path),
+            kind,
+        };
+        let executables = vec![
+            exe("UnityCrashHandler64", ExecutableKind::Elf),
+            exe("Server.exe", ExecutableKind::Pe),
+            exe("start.sh", ExecutableKind::Script),
+            exe("srcds_run", ExecutableKind::Script),
+            exe("bin/game", ExecutableKind::Elf),
+            exe("bin/DedicatedServer", ExecutableKind::Elf),
+        ];
+        let ranked: Vec<String> = rank(executables, &["srcds_run".to_string()])
+            .into_iter()
+            .map(|e| e.path.display().to_string())
+            .collect();
+        assert_eq!(
+            ranked,
+            vec![
+                "srcds_run",
+                "bin/DedicatedServer",
+                "Server.exe",
+                "start.sh",
+                "bin/game",
+                "UnityCrashHandler64"
+            ]
+        );
+    }
+}
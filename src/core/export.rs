@@ -0,0 +1,390 @@
+use clap::ValueEnum;
+use serde_json::{json, Value};
+
+use crate::{
+    core::{launch::quote, profiles::Runtime, GameProfile},
+    tr,
+    utils::{config::LoginType, InstalledServer, ServerInstance},
+};
+
+/// Docker image for native Linux servers in Pterodactyl
+const PTERODACTYL_IMAGE: &str = "ghcr.io/parkervcp/steamcmd:debian";
+
+/// Docker image for Windows servers run through Proton in Pterodactyl
+const PTERODACTYL_PROTON_IMAGE: &str = "ghcr.io/parkervcp/steamcmd:proton";
+
+/// Container that runs the install script in Pterodactyl
+const PTERODACTYL_INSTALLER: &str = "ghcr.io/parkervcp/installers:debian";
+
+/// Format of an exported server definition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// An egg to import into the Pterodactyl panel
+    PterodactylEgg,
+}
+
+/// A variable the panel asks for and substitutes into the startup command
+struct EggVariable {
+    env: String,
+    name: String,
+    description: String,
+    default: String,
+    editable: bool,
+    rules: &'static str,
+}
+
+impl EggVariable {
+    /// Convert the variable into its JSON form
+    ///
+    /// # Returns
+    ///
+    /// The variable as it appears in the `variables` array of an egg
+    fn to_json(&self) -> Value {
+        json!({
+            "name": self.name,
+            "description": self.description,
+            "env_variable": self.env,
+            "default_value": self.default,
+            "user_viewable": true,
+            "user_editable": self.editable,
+            "rules": self.rules,
+            "field_type": "text",
+        })
+    }
+}
+
+/// Export a server or instance for another hosting tool
+///
+/// # Arguments
+///
+/// * `format` - The format to export to
+/// * `server` - The installed server
+/// * `instance` - The instance to export, or `None` to export the server itself
+///
+/// # Returns
+///
+/// The exported definition
+///
+/// # Errors
+///
+/// If no game profile exists for the server
+pub fn export(
+    format: ExportFormat,
+    server: &InstalledServer,
+    instance: Option<&ServerInstance>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let profile = GameProfile::find(server.app_id)
+        .ok_or_else(|| tr!("profile-not-found", app_id = server.app_id))?;
+
+    match format {
+        ExportFormat::PterodactylEgg => Ok(serde_json::to_string_pretty(&pterodactyl_egg(
+            &profile, server, instance,
+        ))?),
+    }
+}
+
+/// Build a Pterodactyl egg from the game profile and the stored settings
+///
+/// The launch arguments of the profile become the startup command. Their
+/// placeholders become egg variables with the stored values as defaults, and
+/// arguments whose placeholder has no value are left out like in the launch
+/// command. The install script installs the app with SteamCMD.
+///
+/// # Arguments
+///
+/// * `profile` - The game profile
+/// * `server` - The installed server
+/// * `instance` - The instance to export
+///
+/// # Returns
+///
+/// The egg in the `PTDL_v2` format
+fn pterodactyl_egg(
+    profile: &GameProfile,
+    server: &InstalledServer,
+    instance: Option<&ServerInstance>,
+) -> Value {
+    let mut variables = Vec::new();
+    let mut startup = match profile.runtime {
+        Runtime::Native => vec![format!("./{}", profile.executable)],
+        Runtime::Windows => vec![
+            "proton".to_string(),
+            "run".to_string(),
+            format!("./{}", profile.executable),
+        ],
+    };
+
+    for template in &profile.args {
+        let tokens: Option<Vec<String>> = template
+            .split_whitespace()
+            .map(|token| egg_token(token, server, instance, &mut variables))
+            .collect();
+        startup.extend(tokens.unwrap_or_default());
+    }
+    startup.extend(server.extra_args.iter().map(|arg| quote(arg)));
+    if let Some(instance) = instance {
+        startup.extend(instance.extra_args.iter().map(|arg| quote(arg)));
+    }
+    if profile.is_source_engine()
+        && instance
+            .and_then(|i| i.gslt.as_ref())
+            .or(server.gslt.as_ref())
+            .is_some()
+    {
+        startup.push("+sv_setsteamaccount".to_string());
+        startup.push("{{STEAM_ACC}}".to_string());
+        variables.push(EggVariable {
+            env: "STEAM_ACC".to_string(),
+            name: tr!("export-var-gslt"),
+            description: tr!("export-var-gslt-description"),
+            default: String::new(),
+            editable: true,
+            rules: "required|string|max:32",
+        });
+    }
+
+    variables.push(EggVariable {
+        env: "SRCDS_APPID".to_string(),
+        name: tr!("export-var-app-id"),
+        description: tr!("export-var-app-id-description"),
+        default: server.app_id.to_string(),
+        editable: false,
+        rules: "required|string",
+    });
+    variables.push(EggVariable {
+        env: "SRCDS_BETAID".to_string(),
+        name: tr!("export-var-branch"),
+        description: tr!("export-var-branch-description"),
+        default: server.branch.clone().unwrap_or_default(),
+        editable: true,
+        rules: "nullable|string",
+    });
+    if matches!(server.login_type, LoginType::SteamAccount) {
+        for (env, name) in [
+            ("STEAM_USER", "export-var-steam-user"),
+            ("STEAM_PASS", "export-var-steam-pass"),
+        ] {
+            variables.push(EggVariable {
+                env: env.to_string(),
+                name: tr!(name),
+                description: tr!("export-var-steam-account-description"),
+                default: String::new(),
+                editable: true,
+                rules: "required|string",
+            });
+        }
+    }
+
+    let image = match profile.runtime {
+        Runtime::Native => PTERODACTYL_IMAGE,
+        Runtime::Windows => PTERODACTYL_PROTON_IMAGE,
+    };
+    let stop = profile
+        .stop
+        .commands
+        .first()
+        .cloned()
+        .unwrap_or_else(|| "^C".to_string());
+    let done = profile
+        .ready_line
+        .clone()
+        .unwrap_or_else(|| profile.executable.clone());
+
+    json!({
+        "_comment": tr!("export-egg-comment", server = server.name),
+        "meta": { "version": "PTDL_v2", "update_url": null },
+        "exported_at": chrono::Utc::now().to_rfc3339(),
+        "name": profile.name,
+        "author": "steamserv@localhost",
+        "description": tr!("export-egg-description", name = profile.name, app_id = server.app_id),
+        "features": null,
+        "docker_images": { image: image },
+        "file_denylist": [],
+        "startup": startup.join(" "),
+        "config": {
+            "files": "{}",
+            "startup": json!({ "done": done }).to_string(),
+            "logs": "{}",
+            "stop": stop,
+        },
+        "scripts": {
+            "installation": {
+                "script": install_script(profile, server),
+                "container": PTERODACTYL_INSTALLER,
+                "entrypoint": "bash",
+            }
+        },
+        "variables": variables.iter().map(EggVariable::to_json).collect::<Vec<_>>(),
+    })
+}
+
+/// Translate a launch argument token into the startup syntax of Pterodactyl
+///
+/// # Arguments
+///
+/// * `token` - The template token
+/// * `server` - The installed server
+/// * `instance` - The instance to export
+/// * `variables` - The egg variables so far, a new variable is added once
+///
+/// # Returns
+///
+/// The token with `{{VARIABLE}}` references, or `None` if a placeholder has no value
+fn egg_token(
+    token: &str,
+    server: &InstalledServer,
+    instance: Option<&ServerInstance>,
+    variables: &mut Vec<EggVariable>,
+) -> Option<String> {
+    let mut rendered = String::new();
+    let mut quoted = false;
+    let mut rest = token;
+
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..].find('}')? + start;
+        rendered.push_str(&rest[..start]);
+
+        let placeholder = &rest[start + 1..end];
+        let (env, name, default) = match placeholder {
+            "port" => {
+                rendered.push_str("{{SERVER_PORT}}");
+                rest = &rest[end + 1..];
+                continue;
+            }
+            "install_dir" | "home" => {
+                rendered.push_str("/home/container");
+                rest = &rest[end + 1..];
+                continue;
+            }
+            "name" => (
+                "SERVER_NAME".to_string(),
+                tr!("export-var-name"),
+                instance
+                    .map(|i| i.name.clone())
+                    .unwrap_or_else(|| server.name.clone()),
+            ),
+            "world" => (
+                "WORLD".to_string(),
+                tr!("export-var-world"),
+                instance.and_then(|i| i.world_name.clone())?,
+            ),
+            "config_file" => (
+                "CONFIG_FILE".to_string(),
+                tr!("export-var-config-file"),
+                instance
+                    .and_then(|i| i.config_file.as_ref())
+                    .map(|path| path.display().to_string())?,
+            ),
+            _ => {
+                let key = placeholder.strip_prefix("var:")?;
+                (
+                    key.to_string(),
+                    key.to_string(),
+                    instance.and_then(|i| i.variables.get(key).cloned())?,
+                )
+            }
+        };
+
+        rendered.push_str(&format!("{{{{{}}}}}", env));
+        quoted = true;
+        if !variables.iter().any(|v| v.env == env) {
+            variables.push(EggVariable {
+                description: tr!("export-var-description", variable = env),
+                env,
+                name,
+                default,
+                editable: true,
+                rules: "required|string",
+            });
+        }
+        rest = &rest[end + 1..];
+    }
+    rendered.push_str(rest);
+
+    // Values from the panel may contain spaces, the startup runs through a shell
+    Some(if quoted {
+        format!("\"{}\"", rendered)
+    } else {
+        rendered
+    })
+}
+
+/// Build the install script of an egg
+///
+/// # Arguments
+///
+/// * `profile` - The game profile
+/// * `server` - The installed server
+///
+/// # Returns
+///
+/// A bash script that installs SteamCMD and the app into `/mnt/server`
+fn install_script(profile: &GameProfile, server: &InstalledServer) -> String {
+    let login = match server.login_type {
+        LoginType::Anonymous => "anonymous",
+        LoginType::SteamAccount => "${STEAM_USER} ${STEAM_PASS}",
+    };
+    let platform = match profile.runtime {
+        Runtime::Native => "",
+        Runtime::Windows => "+@sSteamCmdForcePlatformType windows ",
+    };
+
+    format!(
+        "#!/bin/bash
+# {name} install script, exported by steamserv
+mkdir -p /mnt/server/steamcmd /mnt/server/.steam/sdk64
+cd /tmp
+curl -sSL -o steamcmd.tar.gz https://steamcdn-a.akamaihd.net/client/installer/steamcmd_linux.tar.gz
+tar -xzf steamcmd.tar.gz -C /mnt/server/steamcmd
+cd /mnt/server/steamcmd
+export HOME=/mnt/server
+./steamcmd.sh {platform}+force_install_dir /mnt/server +login {login} +app_update ${{SRCDS_APPID}} ${{SRCDS_BETAID:+-beta $SRCDS_BETAID}} validate +quit
+cp -v linux64/steamclient.so /mnt/server/.steam/sdk64/steamclient.so
+",
+        name = profile.name,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    #[test]
+    fn test_pterodactyl_egg() {
+        let mut instance = ServerInstance::new("main");
+        instance.port = Some(2456);
+        instance.world_name = Some("Dedicated".to_string());
+        instance.extra_args = vec!["-crossplay".to_string()];
+        let mut server = InstalledServer::new(
+            896660,
+            "valheim",
+            "/srv/valheim".into(),
+            LoginType::Anonymous,
+        );
+        server.instances = vec![instance.clone()];
+        let profile = GameProfile::find(896660).unwrap();
+
+        let egg = pterodactyl_egg(&profile, &server, Some(&instance));
+        assert_eq!(
+            egg["startup"],
+            "./valheim_server.x86_64 -nographics -batchmode -name \"{{SERVER_NAME}}\" \
+             -port {{SERVER_PORT}} -world \"{{WORLD}}\" -public 1 -crossplay"
+        );
+        let variables: BTreeMap<&str, &str> = egg["variables"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| {
+                (
+                    v["env_variable"].as_str().unwrap(),
+                    v["default_value"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(variables["WORLD"], "Dedicated");
+        assert_eq!(variables["SRCDS_APPID"], "896660");
+        assert!(!variables.contains_key("PASSWORD"));
+    }
+}
@@ -0,0 +1,232 @@
+use std::path::{Component, Path, PathBuf};
+
+use crate::{
+    tr,
+    utils::{fs, Config},
+};
+
+/// Maximum number of lines of a file that are compared for the diff preview
+const MAX_DIFF_LINES: usize = 5000;
+
+/// Copy of shared files from a source server to the other servers of a group,
+/// run by `steamserv sync-files`
+///
+/// The plan is made first, so the changes can be shown before they are written.
+pub struct FileSync {
+    /// The server the canonical files are taken from
+    pub source: String,
+    /// The files that differ on the other servers
+    pub changes: Vec<FileChange>,
+}
+
+/// A file that is created or replaced on a server of the group
+pub struct FileChange {
+    /// The server the file is written to
+    pub server: String,
+    /// The path relative to the install directory
+    pub path: PathBuf,
+    /// The absolute path of the file on the server
+    pub target: PathBuf,
+    /// The current content, `None` if the server does not have the file
+    pub old: Option<Vec<u8>>,
+    /// The content of the source server
+    pub new: Vec<u8>,
+}
+
+impl FileSync {
+    /// Compare the files of the source server with the other servers of a group
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration with the groups
+    /// * `group` - The name of the group
+    /// * `source` - The server to copy from, defaults to the first server of the group
+    /// * `paths` - The files, relative to the install directory
+    ///
+    /// # Returns
+    ///
+    /// The planned changes, empty if all servers have the same files
+    ///
+    /// # Errors
+    ///
+    /// If the group does not exist, a server is not installed, a path leaves the
+    /// install directory or a file of the source server could not be read
+    pub fn plan(
+        config: &Config,
+        group: &str,
+        source: Option<&str>,
+        paths: &[String],
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let members = config
+            .groups
+            .get(group)
+            .filter(|members| !members.is_empty())
+            .ok_or_else(|| tr!("file-sync-group-not-found", group = group))?;
+        let source = source
+            .or(members.first().map(String::as_str))
+            .unwrap_or_default();
+        let source_server = config
+            .find_server(source)
+            .ok_or_else(|| tr!("server-not-installed", server = source))?;
+
+        let mut changes = Vec::new();
+        for path in paths {
+            let path = relative_path(path)?;
+            let source_file = source_server.install_path.join(&path);
+            let new = std::fs::read(&source_file).map_err(|e| {
+                tr!(
+                    "file-sync-read-failed",
+                    path = source_file.display(),
+                    error = e
+                )
+            })?;
+
+            for member in members.iter().filter(|m| m.as_str() != source) {
+                let server = config
+                    .find_server(member)
+                    .ok_or_else(|| tr!("server-not-installed", server = member))?;
+                let target = server.install_path.join(&path);
+                let old = std::fs::read(&target).ok();
+                if old.as_ref() != Some(&new) {
+                    changes.push(FileChange {
+                        server: server.name.clone(),
+                        path: path.clone(),
+                        target,
+                        old,
+                        new: new.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(Self {
+            source: source_server.name.clone(),
+            changes,
+        })
+    }
+
+    /// Write the changed files to the servers of the group
+    ///
+    /// # Returns
+    ///
+    /// Ok if all files were written
+    ///
+    /// # Errors
+    ///
+    /// If a file could not be written
+    pub fn apply(&self) -> Result<(), Box<dyn std::error::Error>> {
+        for change in &self.changes {
+            if let Some(parent) = change.target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            fs::write_atomic(&change.target, &change.new)?;
+        }
+        Ok(())
+    }
+}
+
+impl FileChange {
+    /// Get the changed lines of a text file
+    ///
+    /// # Returns
+    ///
+    /// The removed lines with `-` and the added lines with `+` in file order, or
+    /// `None` if a file is not text or too long to compare
+    pub fn diff(&self) -> Option<Vec<String>> {
+        let old = std::str::from_utf8(self.old.as_deref().unwrap_or_default()).ok()?;
+        let new = std::str::from_utf8(&self.new).ok()?;
+        diff_lines(old, new)
+    }
+}
+
+/// Check that a path stays inside the install directory
+///
+/// # Arguments
+///
+/// * `path` - The path given by the user
+///
+/// # Returns
+///
+/// The path
+///
+/// # Errors
+///
+/// If the path is absolute or contains `..`
+fn relative_path(path: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let path = Path::new(path.trim());
+    let inside = path
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+    if !inside || path.as_os_str().is_empty() {
+        return Err(tr!("file-sync-path-invalid", path = path.display()).into());
+    }
+    Ok(path.to_path_buf())
+}
+
+/// Compare two texts line by line
+///
+/// # Arguments
+///
+/// * `old` - The current text
+/// * `new` - The new text
+///
+/// # Returns
+///
+/// The removed lines with `-` and the added lines with `+` in file order, or
+/// `None` if a text has more than [`MAX_DIFF_LINES`] lines
+fn diff_lines(old: &str, new: &str) -> Option<Vec<String>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    if old.len() > MAX_DIFF_LINES || new.len() > MAX_DIFF_LINES {
+        return None;
+    }
+
+    // Length of the longest common subsequence of the remaining lines
+    let mut common = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            lines.push(format!("-{}", old[i]));
+            i += 1;
+        } else {
+            lines.push(format!("+{}", new[j]));
+            j += 1;
+        }
+    }
+    Some(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_lines() {
+        let old = "banid 0 STEAM_0:1:1\nbanid 0 STEAM_0:1:2\nbanid 0 STEAM_0:1:3\n";
+        let new = "banid 0 STEAM_0:1:1\nbanid 0 STEAM_0:1:3\nbanid 0 STEAM_0:1:4\n";
+        assert_eq!(
+            diff_lines(old, new).unwrap(),
+            vec!["-banid 0 STEAM_0:1:2", "+banid 0 STEAM_0:1:4"]
+        );
+        assert_eq!(diff_lines("a\nb\n", "a\nc\n").unwrap(), vec!["-b", "+c"]);
+        assert_eq!(diff_lines("", "a\n").unwrap(), vec!["+a"]);
+
+        assert!(relative_path("cfg/banned.cfg").is_ok());
+        assert!(relative_path("../other/cfg").is_err());
+        assert!(relative_path("/etc/passwd").is_err());
+    }
+}
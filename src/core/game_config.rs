@@ -0,0 +1,576 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use serde_json::Value;
+
+use crate::{
+    core::{
+        profiles::{ConfigFormat, ConfigSetting, SettingKind},
+        GameProfile, LaunchCommand,
+    },
+    tr,
+    utils::{fs, InstalledServer, ServerInstance},
+};
+
+/// The main config file of a server or instance, read and changed through the
+/// schema of its game profile
+///
+/// Keys are the names of the settings in the schema, like `max_players`, or the
+/// keys of the file itself. Changes keep the rest of the file as it is.
+pub struct GameConfig {
+    pub path: PathBuf,
+    pub format: ConfigFormat,
+    /// The named settings of the schema
+    pub settings: BTreeMap<String, ConfigSetting>,
+    text: String,
+}
+
+impl GameConfig {
+    /// Read the config file of a server or instance
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The installed server
+    /// * `instance` - The instance, or `None` for the server itself
+    ///
+    /// # Returns
+    ///
+    /// The config file
+    ///
+    /// # Errors
+    ///
+    /// If the game has no profile or no config schema, no config file is set or
+    /// the file could not be read
+    pub fn load(
+        server: &InstalledServer,
+        instance: Option<&ServerInstance>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let profile = GameProfile::find(server.app_id)
+            .ok_or_else(|| tr!("profile-not-found", app_id = server.app_id))?;
+        let schema = profile
+            .config_schema
+            .as_ref()
+            .ok_or_else(|| tr!("game-config-no-schema", name = profile.name.as_str()))?;
+        let path = LaunchCommand::render_path(&schema.file, &profile, server, instance)
+            .ok_or_else(|| tr!("lint-no-config-file"))?;
+        if !path.is_file() {
+            return Err(tr!("doctor-missing", path = path.display()).into());
+        }
+
+        Ok(Self {
+            text: std::fs::read_to_string(&path)?,
+            path,
+            format: schema.format,
+            settings: schema.settings.clone(),
+        })
+    }
+
+    /// Get the value of a setting
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The name of the setting or the key in the file
+    ///
+    /// # Returns
+    ///
+    /// The value, or `None` if the file does not have the setting
+    pub fn get(&self, key: &str) -> Option<String> {
+        let setting = self.setting(key);
+        parse(self.format, &self.text).remove(&setting.key)
+    }
+
+    /// Change the value of a setting, or add it if the file does not have it
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The name of the setting or the key in the file
+    /// * `value` - The new value
+    ///
+    /// # Returns
+    ///
+    /// The value as written to the file
+    ///
+    /// # Errors
+    ///
+    /// If the value does not fit the type of the setting or the JSON file is invalid
+    pub fn set(&mut self, key: &str, value: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let setting = self.setting(key);
+        let current = self.get(key);
+        let value = typed_value(&setting, value, current.as_deref())
+            .ok_or_else(|| tr!("game-config-value-invalid", key = key, value = value))?;
+
+        self.text = match self.format {
+            ConfigFormat::Source => set_source(&self.text, &setting, &value),
+            ConfigFormat::Ini => set_ini(&self.text, &setting, &value),
+            ConfigFormat::Xml => set_xml(&self.text, &setting, &value),
+            ConfigFormat::Json => set_json(&self.text, &setting, &value)?,
+        };
+        Ok(value)
+    }
+
+    /// Write the changed file
+    ///
+    /// # Returns
+    ///
+    /// Ok if the file was written
+    ///
+    /// # Errors
+    ///
+    /// If the file could not be written
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        fs::write_atomic(&self.path, self.text.as_bytes())
+    }
+
+    /// Get the setting of a key
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The name of the setting or the key in the file
+    ///
+    /// # Returns
+    ///
+    /// The named setting, or an untyped setting for a key of the file
+    fn setting(&self, key: &str) -> ConfigSetting {
+        self.settings.get(key).cloned().unwrap_or(ConfigSetting {
+            key: key.to_string(),
+            ..ConfigSetting::default()
+        })
+    }
+}
+
+/// Check a value against the type of a setting and write it like the file does
+///
+/// Booleans keep the style of the current value, e.g. `1`/`0` or `True`/`False`.
+///
+/// # Arguments
+///
+/// * `setting` - The setting
+/// * `value` - The new value
+/// * `current` - The current value in the file
+///
+/// # Returns
+///
+/// The value to write, or `None` if it does not fit the type
+fn typed_value(setting: &ConfigSetting, value: &str, current: Option<&str>) -> Option<String> {
+    let value = value.trim();
+    match setting.kind {
+        SettingKind::String => Some(value.to_string()),
+        SettingKind::Integer => value.parse::<i64>().ok().map(|v| v.to_string()),
+        SettingKind::Float => value
+            .parse::<f64>()
+            .ok()
+            .filter(|v| v.is_finite())
+            .map(|_| value.to_string()),
+        SettingKind::Bool => {
+            let enabled = match value.to_lowercase().as_str() {
+                "true" | "1" | "yes" | "on" => true,
+                "false" | "0" | "no" | "off" => false,
+                _ => return None,
+            };
+            Some(match current {
+                Some("0" | "1") => u8::from(enabled).to_string(),
+                Some(current) if current.starts_with(['T', 'F']) => {
+                    if enabled { "True" } else { "False" }.to_string()
+                }
+                _ => enabled.to_string(),
+            })
+        }
+    }
+}
+
+/// Set a console command in a Source config file
+///
+/// # Arguments
+///
+/// * `text` - The content of the file
+/// * `setting` - The setting
+/// * `value` - The new value
+///
+/// # Returns
+///
+/// The changed content, the last line with the key is replaced and a comment
+/// after it is kept
+fn set_source(text: &str, setting: &ConfigSetting, value: &str) -> String {
+    let line = match setting.kind {
+        SettingKind::String => format!("{} \"{}\"", setting.key, value.replace('"', "'")),
+        _ => format!("{} {}", setting.key, value),
+    };
+    let position = last_line(text, |l| {
+        l.split("//")
+            .next()
+            .unwrap_or_default()
+            .split_whitespace()
+            .next()
+            == Some(&setting.key)
+    });
+    replace_line(text, position, |old| {
+        match old.and_then(|old| old.find("//")).zip(old) {
+            Some((comment, old)) => format!("{} {}", line, &old[comment..]),
+            None => line.clone(),
+        }
+    })
+}
+
+/// Set a `key=value` line in an INI file
+///
+/// # Arguments
+///
+/// * `text` - The content of the file
+/// * `setting` - The setting
+/// * `value` - The new value
+///
+/// # Returns
+///
+/// The changed content, a missing key is added to the section of the setting
+/// or at the end
+fn set_ini(text: &str, setting: &ConfigSetting, value: &str) -> String {
+    let line = format!("{}={}", setting.key, value);
+    let position = last_line(text, |l| {
+        let l = l.trim();
+        !l.starts_with([';', '#', '['])
+            && l.split_once('=').map(|(key, _)| key.trim()) == Some(setting.key.as_str())
+    });
+    if position.is_some() {
+        return replace_line(text, position, |_| line.clone());
+    }
+
+    let Some(section) = &setting.section else {
+        return replace_line(text, None, |_| line.clone());
+    };
+    let header = format!("[{}]", section);
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+    match lines.iter().position(|l| l.trim() == header) {
+        Some(start) => {
+            let end = lines[start + 1..]
+                .iter()
+                .position(|l| l.trim().starts_with('['))
+                .map_or(lines.len(), |i| start + 1 + i);
+            let end = lines[..end]
+                .iter()
+                .rposition(|l| !l.trim().is_empty())
+                .map_or(end, |i| i + 1);
+            lines.insert(end, line);
+        }
+        None => {
+            if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+                lines.push(String::new());
+            }
+            lines.extend([header, line]);
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Set the value of a `<property>` element in an XML file
+///
+/// # Arguments
+///
+/// * `text` - The content of the file
+/// * `setting` - The setting
+/// * `value` - The new value
+///
+/// # Returns
+///
+/// The changed content, a missing property is added before the closing tag of
+/// the root element
+fn set_xml(text: &str, setting: &ConfigSetting, value: &str) -> String {
+    let value = value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;");
+    let position = last_line(text, |l| {
+        attribute(l, "name").as_deref() == Some(setting.key.as_str())
+    });
+    if position.is_some() {
+        return replace_line(text, position, |old| {
+            let old = old.unwrap_or_default();
+            match old.find(" value=\"") {
+                Some(start) => {
+                    let start = start + " value=\"".len();
+                    let end = old[start..].find('"').map_or(old.len(), |end| start + end);
+                    format!("{}{}{}", &old[..start], value, &old[end..])
+                }
+                None => old.to_string(),
+            }
+        });
+    }
+
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+    let closing = lines
+        .iter()
+        .rposition(|l| l.trim().starts_with("</"))
+        .unwrap_or(lines.len());
+    let indent = lines
+        .iter()
+        .find(|l| l.trim().starts_with("<property"))
+        .map(|l| l[..l.len() - l.trim_start().len()].to_string())
+        .unwrap_or_else(|| "\t".to_string());
+    lines.insert(
+        closing,
+        format!(
+            "{}<property name=\"{}\" value=\"{}\"/>",
+            indent, setting.key, value
+        ),
+    );
+    lines.join("\n") + "\n"
+}
+
+/// Set a value in a JSON file
+///
+/// # Arguments
+///
+/// * `text` - The content of the file
+/// * `setting` - The setting, nested keys are joined with dots
+/// * `value` - The new value
+///
+/// # Returns
+///
+/// The changed content, pretty printed
+///
+/// # Errors
+///
+/// If the file is not a JSON object
+fn set_json(
+    text: &str,
+    setting: &ConfigSetting,
+    value: &str,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut root: Value = serde_json::from_str(text)?;
+    let mut node = &mut root;
+    let mut keys = setting.key.split('.').peekable();
+    while let Some(key) = keys.next() {
+        let object = node
+            .as_object_mut()
+            .ok_or_else(|| tr!("game-config-not-object", key = setting.key.as_str()))?;
+        if keys.peek().is_none() {
+            let current = object.get(key);
+            let typed = match (setting.kind, current) {
+                (SettingKind::Integer | SettingKind::Float, _) | (_, Some(Value::Number(_))) => {
+                    serde_json::from_str::<serde_json::Number>(value)
+                        .map(Value::Number)
+                        .unwrap_or_else(|_| Value::String(value.to_string()))
+                }
+                (SettingKind::Bool, _) | (_, Some(Value::Bool(_))) => match value {
+                    "true" | "1" | "True" => Value::Bool(true),
+                    "false" | "0" | "False" => Value::Bool(false),
+                    _ => Value::String(value.to_string()),
+                },
+                _ => Value::String(value.to_string()),
+            };
+            object.insert(key.to_string(), typed);
+            break;
+        }
+        node = object
+            .entry(key)
+            .or_insert_with(|| Value::Object(Default::default()));
+    }
+    Ok(serde_json::to_string_pretty(&root)? + "\n")
+}
+
+/// Find the last line of a file that matches
+///
+/// # Arguments
+///
+/// * `text` - The content of the file
+/// * `matches` - Checks a line
+///
+/// # Returns
+///
+/// The index of the line, or `None` if no line matches
+fn last_line(text: &str, matches: impl Fn(&str) -> bool) -> Option<usize> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| matches(line))
+        .map(|(i, _)| i)
+        .last()
+}
+
+/// Replace a line of a file or append one
+///
+/// # Arguments
+///
+/// * `text` - The content of the file
+/// * `position` - The index of the line to replace, or `None` to append
+/// * `line` - Creates the new line from the old one
+///
+/// # Returns
+///
+/// The changed content
+fn replace_line(
+    text: &str,
+    position: Option<usize>,
+    line: impl Fn(Option<&str>) -> String,
+) -> String {
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+    match position {
+        Some(i) => lines[i] = line(Some(&lines[i])),
+        None => lines.push(line(None)),
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Parse the settings of a config file
+///
+/// # Arguments
+///
+/// * `format` - The format of the file
+/// * `text` - The content of the file
+///
+/// # Returns
+///
+/// The settings by key, later ones override earlier ones
+pub fn parse(format: ConfigFormat, text: &str) -> BTreeMap<String, String> {
+    let unquote = |value: &str| value.trim().trim_matches('"').to_string();
+    let mut settings = BTreeMap::new();
+
+    if format == ConfigFormat::Json {
+        if let Ok(root) = serde_json::from_str::<Value>(text) {
+            flatten(&root, "", &mut settings);
+        }
+        return settings;
+    }
+
+    for line in text.lines() {
+        let line = line.trim();
+        let setting = match format {
+            ConfigFormat::Source => {
+                let line = line.split("//").next().unwrap_or_default().trim();
+                line.split_once(char::is_whitespace)
+                    .map(|(key, value)| (key.to_string(), unquote(value)))
+                    .or_else(|| (!line.is_empty()).then(|| (line.to_string(), String::new())))
+            }
+            ConfigFormat::Ini if line.starts_with([';', '#', '[']) => None,
+            ConfigFormat::Ini => line
+                .split_once('=')
+                .map(|(key, value)| (key.trim().to_string(), unquote(value))),
+            ConfigFormat::Xml => attribute(line, "name").zip(attribute(line, "value")),
+            ConfigFormat::Json => None,
+        };
+        if let Some((key, value)) = setting {
+            settings.insert(key, value);
+        }
+    }
+
+    settings
+}
+
+/// Collect the values of a JSON object with the keys joined by dots
+///
+/// # Arguments
+///
+/// * `value` - The JSON value
+/// * `prefix` - The key of the value
+/// * `settings` - The collected settings
+fn flatten(value: &Value, prefix: &str, settings: &mut BTreeMap<String, String>) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object {
+                let key = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten(value, &key, settings);
+            }
+        }
+        Value::String(text) => {
+            settings.insert(prefix.to_string(), text.clone());
+        }
+        other => {
+            settings.insert(prefix.to_string(), other.to_string());
+        }
+    }
+}
+
+/// Get the value of an XML attribute on a line
+///
+/// # Arguments
+///
+/// * `line` - The line with the element
+/// * `name` - The name of the attribute
+///
+/// # Returns
+///
+/// The value of the attribute, if the line has it
+fn attribute(line: &str, name: &str) -> Option<String> {
+    let start = line.find(&format!(" {}=\"", name))? + name.len() + 3;
+    let end = line[start..].find('"')? + start;
+    Some(line[start..end].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(format: ConfigFormat, text: &str) -> GameConfig {
+        let settings = BTreeMap::from([
+            (
+                "max_players".to_string(),
+                ConfigSetting {
+                    key: "MaxPlayers".to_string(),
+                    kind: SettingKind::Integer,
+                    section: Some("Server".to_string()),
+                },
+            ),
+            (
+                "public".to_string(),
+                ConfigSetting {
+                    key: "Public".to_string(),
+                    kind: SettingKind::Bool,
+                    section: None,
+                },
+            ),
+        ]);
+        GameConfig {
+            path: PathBuf::new(),
+            format,
+            settings,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_set_settings() {
+        let mut ini = config(
+            ConfigFormat::Ini,
+            "[Server]\nPublic=false\n\n[Other]\nA=1\n",
+        );
+        assert_eq!(ini.set("max_players", "16").unwrap(), "16");
+        assert_eq!(ini.set("public", "yes").unwrap(), "true");
+        assert!(ini.set("max_players", "many").is_err());
+        assert_eq!(
+            ini.text,
+            "[Server]\nPublic=true\nMaxPlayers=16\n\n[Other]\nA=1\n"
+        );
+        assert_eq!(ini.get("max_players").as_deref(), Some("16"));
+
+        let mut source = config(
+            ConfigFormat::Source,
+            "hostname \"Old\" // name\nsv_cheats 0\n",
+        );
+        source.set("hostname", "New Server").unwrap();
+        source.set("sv_password", "secret").unwrap();
+        assert_eq!(
+            source.text,
+            "hostname \"New Server\" // name\nsv_cheats 0\nsv_password \"secret\"\n"
+        );
+
+        let mut xml = config(
+            ConfigFormat::Xml,
+            "<ServerSettings>\n\t<property name=\"Public\" value=\"True\"/>\n</ServerSettings>\n",
+        );
+        xml.set("public", "off").unwrap();
+        xml.set("max_players", "8").unwrap();
+        assert_eq!(xml.get("public").as_deref(), Some("False"));
+        assert!(xml
+            .text
+            .contains("\t<property name=\"MaxPlayers\" value=\"8\"/>\n</ServerSettings>"));
+
+        let mut json = config(ConfigFormat::Json, r#"{"server": {"MaxPlayers": 4}}"#);
+        json.settings.get_mut("max_players").unwrap().key = "server.MaxPlayers".to_string();
+        json.set("max_players", "10").unwrap();
+        json.set("server.name", "Test").unwrap();
+        assert_eq!(json.get("max_players").as_deref(), Some("10"));
+        assert_eq!(json.get("server.name").as_deref(), Some("Test"));
+    }
+}
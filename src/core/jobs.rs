@@ -0,0 +1,912 @@
+use std::{
+    cmp::Reverse,
+    fs::{File, OpenOptions},
+    os::{fd::AsRawFd, unix::process::CommandExt},
+    path::PathBuf,
+    process::{Command, Stdio},
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::process,
+    tr,
+    utils::{
+        events::{Event, EventSink},
+        fs, host,
+        phases::Phase,
+        steamcmd, Config, InstalledServer, ServerRef,
+    },
+};
+
+/// Number of finished jobs that are kept with their logs
+const KEPT_FINISHED_JOBS: usize = 50;
+/// Variable with the ID of the job in the environment of a running job
+pub const JOB_ENV: &str = "STEAMSERV_JOB";
+/// Minimum time between two checkpoints of the download progress
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// State of a background job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+impl JobState {
+    /// Check if the job has ended
+    ///
+    /// # Returns
+    ///
+    /// True if the job succeeded, failed or was cancelled
+    pub fn is_finished(self) -> bool {
+        !matches!(self, Self::Queued | Self::Running)
+    }
+
+    /// Get the translated name of the state
+    ///
+    /// # Returns
+    ///
+    /// The name
+    pub fn label(self) -> String {
+        match self {
+            Self::Queued => tr!("job-state-queued"),
+            Self::Running => tr!("job-state-running"),
+            Self::Succeeded => tr!("job-state-succeeded"),
+            Self::Failed => tr!("job-state-failed"),
+            Self::Cancelled => tr!("job-state-cancelled"),
+        }
+    }
+}
+
+/// A steamserv command that runs in the background
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: u64,
+    /// The arguments of the command, e.g. `["install", "-a", "valheim"]`
+    pub args: Vec<String>,
+    pub state: JobState,
+    pub created: DateTime<Utc>,
+    pub started: Option<DateTime<Utc>>,
+    pub finished: Option<DateTime<Utc>>,
+    /// The PID of the job runner while the job is running
+    pub pid: Option<u32>,
+    pub exit_code: Option<i32>,
+    /// Jobs with a higher priority are started first
+    #[serde(default)]
+    pub priority: i32,
+    /// The jobs that have to succeed before this job is started
+    #[serde(default)]
+    pub after: Vec<u64>,
+    /// Why the job was cancelled without being run
+    pub reason: Option<String>,
+}
+
+impl Job {
+    /// Get the command line of the job
+    ///
+    /// # Returns
+    ///
+    /// The arguments joined by spaces
+    pub fn command(&self) -> String {
+        self.args.join(" ")
+    }
+
+    /// Get the servers the job updates or restarts
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration with the installed servers
+    ///
+    /// # Returns
+    ///
+    /// The servers named in the arguments, all servers for `update --all`
+    pub fn servers<'a>(&self, config: &'a Config) -> Vec<&'a InstalledServer> {
+        let Some((command, args)) = self.args.split_first() else {
+            return Vec::new();
+        };
+        if command != "update" && command != "restart" {
+            return Vec::new();
+        }
+        let all = args.iter().any(|arg| arg == "--all");
+
+        config
+            .installed_servers
+            .iter()
+            .filter(|s| {
+                all || args
+                    .iter()
+                    .any(|arg| ServerRef::parse(arg).server == s.name)
+            })
+            .collect()
+    }
+
+    /// Check if the job runs SteamCMD, which needs Steam to be reachable
+    ///
+    /// # Returns
+    ///
+    /// True for installs, updates and workshop mods
+    pub fn uses_steamcmd(&self) -> bool {
+        self.args
+            .first()
+            .is_some_and(|command| ["install", "new", "update", "mods"].contains(&command.as_str()))
+    }
+
+    /// Get the name of the download slot of the job
+    ///
+    /// # Returns
+    ///
+    /// The host name and the ID, unique across the hosts sharing the slots
+    pub fn slot(&self) -> String {
+        format!("{}-{}", host::hostname(), self.id)
+    }
+
+    /// Get the log file with the output of the job
+    ///
+    /// # Returns
+    ///
+    /// The path in `logs/jobs` of the steamserv directory
+    ///
+    /// # Errors
+    ///
+    /// If the steamserv directory could not be found
+    pub fn log_file(&self) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        job_file(self.id, "log")
+    }
+
+    /// Get the file with the progress checkpoints of the job
+    ///
+    /// # Returns
+    ///
+    /// The path in `logs/jobs` of the steamserv directory
+    ///
+    /// # Errors
+    ///
+    /// If the steamserv directory could not be found
+    pub fn progress_file(&self) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        job_file(self.id, "progress.json")
+    }
+}
+
+/// Progress of a running job, saved as checkpoints next to its log
+///
+/// `jobs attach` reads the checkpoint after a reconnect to show where the job
+/// is, instead of replaying the whole log.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JobProgress {
+    /// The operation the job is running, e.g. `Updating valheim...`
+    pub operation: Option<String>,
+    /// The phases of the operation that have finished, with the seconds they took
+    pub finished: Vec<(Phase, f64)>,
+    pub phase: Option<Phase>,
+    /// The position in the log where the last phase started
+    pub offset: u64,
+    /// The state of the last progress line, e.g. `downloading`
+    pub state: Option<String>,
+    pub percent: f64,
+    pub current: u64,
+    pub total: u64,
+    pub updated: Option<DateTime<Utc>>,
+}
+
+impl JobProgress {
+    /// Load the last checkpoint of a job
+    ///
+    /// # Arguments
+    ///
+    /// * `job` - The job
+    ///
+    /// # Returns
+    ///
+    /// The progress, or `None` if the job has not saved a checkpoint
+    pub fn load(job: &Job) -> Option<Self> {
+        let content = std::fs::read_to_string(job.progress_file().ok()?).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Describe the progress of the current phase
+    ///
+    /// # Returns
+    ///
+    /// The translated progress, or `None` if no phase is running
+    pub fn describe(&self) -> Option<String> {
+        let phase = self.phase?;
+        if self.total == 0 {
+            return Some(phase.name());
+        }
+        Some(tr!(
+            "job-progress-current",
+            phase = phase.name(),
+            percent = format!("{:.1}", self.percent),
+            current = fs::format_size(self.current),
+            total = fs::format_size(self.total)
+        ))
+    }
+}
+
+/// Sink that saves the progress of the running job as checkpoints
+///
+/// Progress updates are saved at most once per `CHECKPOINT_INTERVAL`, the
+/// start and end of operations and phases right away.
+pub struct ProgressSink {
+    path: PathBuf,
+    log: PathBuf,
+    progress: JobProgress,
+    saved: Option<Instant>,
+}
+
+impl ProgressSink {
+    /// Create the sink for a job
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the job
+    ///
+    /// # Returns
+    ///
+    /// The sink
+    ///
+    /// # Errors
+    ///
+    /// If the steamserv directory could not be found
+    pub fn new(id: u64) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self {
+            path: job_file(id, "progress.json")?,
+            log: job_file(id, "log")?,
+            progress: JobProgress::default(),
+            saved: None,
+        })
+    }
+}
+
+impl EventSink for ProgressSink {
+    fn handle(&mut self, event: &Event) -> Result<(), Box<dyn std::error::Error>> {
+        match event {
+            Event::InstallStarted { .. } | Event::UpdateStarted { .. } => {
+                self.progress = JobProgress {
+                    operation: event.message(),
+                    offset: self.progress.offset,
+                    ..JobProgress::default()
+                };
+            }
+            Event::PhaseStarted { phase } => {
+                self.progress.phase = Some(*phase);
+                // The job writes its output to the log, the line of the phase follows
+                self.progress.offset = std::fs::metadata(&self.log).map_or(0, |m| m.len());
+                self.progress.state = None;
+                self.progress.percent = 0.0;
+                self.progress.current = 0;
+                self.progress.total = 0;
+            }
+            Event::PhaseFinished { phase, seconds } => {
+                self.progress.phase = None;
+                self.progress.finished.push((*phase, *seconds));
+            }
+            Event::ProgressUpdated {
+                state,
+                percent,
+                current,
+                total,
+            } => {
+                self.progress.state = Some(state.clone());
+                self.progress.percent = *percent;
+                self.progress.current = *current;
+                self.progress.total = *total;
+                if self
+                    .saved
+                    .is_some_and(|saved| saved.elapsed() < CHECKPOINT_INTERVAL)
+                {
+                    return Ok(());
+                }
+            }
+            _ => return Ok(()),
+        }
+
+        self.progress.updated = Some(Utc::now());
+        self.saved = Some(Instant::now());
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        fs::write_atomic(
+            &self.path,
+            serde_json::to_string(&self.progress)?.as_bytes(),
+        )
+    }
+}
+
+/// Get the ID of the job this process runs as
+///
+/// # Returns
+///
+/// The ID, or `None` if the process is no background job
+pub fn current_job() -> Option<u64> {
+    std::env::var(JOB_ENV).ok()?.parse().ok()
+}
+
+/// Get a file of a job in `logs/jobs` of the steamserv directory
+///
+/// # Arguments
+///
+/// * `id` - The ID of the job
+/// * `extension` - The extension of the file, e.g. `log`
+///
+/// # Returns
+///
+/// The path of the file
+///
+/// # Errors
+///
+/// If the steamserv directory could not be found
+fn job_file(id: u64, extension: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(Config::data_dir()?
+        .join("logs")
+        .join("jobs")
+        .join(format!("{}.{}", id, extension)))
+}
+
+/// Queue of the background jobs, stored in `jobs.json` in the steamserv directory
+///
+/// Jobs are submitted from the command line and started one after another by
+/// the daemon. Every job runs in a runner process (`jobs exec`) that starts the
+/// command and records its result, so the daemon does not have to wait for it.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct JobQueue {
+    next_id: u64,
+    jobs: Vec<Job>,
+}
+
+impl JobQueue {
+    /// Load the queue
+    ///
+    /// # Returns
+    ///
+    /// The queue, empty if no job was submitted yet
+    ///
+    /// # Errors
+    ///
+    /// If the queue file could not be read or parsed
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// Get all jobs
+    ///
+    /// # Returns
+    ///
+    /// The jobs, oldest first
+    pub fn jobs(&self) -> &[Job] {
+        &self.jobs
+    }
+
+    /// Get a job by its ID
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the job
+    ///
+    /// # Returns
+    ///
+    /// The job
+    ///
+    /// # Errors
+    ///
+    /// If the job does not exist
+    pub fn get(&self, id: u64) -> Result<&Job, Box<dyn std::error::Error>> {
+        self.jobs
+            .iter()
+            .find(|job| job.id == id)
+            .ok_or_else(|| tr!("job-not-found", id = id).into())
+    }
+
+    /// Add a job to the queue
+    ///
+    /// Finished jobs beyond the newest ones are removed with their logs.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - The arguments of the steamserv command
+    /// * `priority` - Jobs with a higher priority are started first
+    /// * `after` - The jobs that have to succeed before this job is started
+    ///
+    /// # Returns
+    ///
+    /// The queued job
+    ///
+    /// # Errors
+    ///
+    /// If a job in `after` does not exist or the queue could not be saved
+    pub fn submit(
+        args: Vec<String>,
+        priority: i32,
+        after: Vec<u64>,
+    ) -> Result<Job, Box<dyn std::error::Error>> {
+        Self::edit(|queue| {
+            for id in &after {
+                queue.get(*id)?;
+            }
+            queue.next_id = queue.next_id.max(1);
+            let job = Job {
+                id: queue.next_id,
+                args,
+                state: JobState::Queued,
+                created: Utc::now(),
+                started: None,
+                finished: None,
+                pid: None,
+                exit_code: None,
+                priority,
+                after,
+                reason: None,
+            };
+            queue.next_id += 1;
+            queue.jobs.push(job.clone());
+
+            let finished = queue.jobs.iter().filter(|j| j.state.is_finished()).count();
+            let mut excess = finished.saturating_sub(KEPT_FINISHED_JOBS);
+            let mut removed = Vec::new();
+            queue.jobs.retain(|j| {
+                if excess > 0 && j.state.is_finished() {
+                    excess -= 1;
+                    removed.push(j.clone());
+                    return false;
+                }
+                true
+            });
+            for job in removed {
+                let _ = std::fs::remove_file(job.log_file()?);
+                let _ = std::fs::remove_file(job.progress_file()?);
+            }
+
+            Ok(job)
+        })
+    }
+
+    /// Cancel a job
+    ///
+    /// Queued jobs are never started, running jobs are terminated with their
+    /// whole process group.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the job
+    ///
+    /// # Returns
+    ///
+    /// The state of the job before it was cancelled
+    ///
+    /// # Errors
+    ///
+    /// If the job does not exist, has already finished or could not be terminated
+    pub fn cancel(id: u64) -> Result<JobState, Box<dyn std::error::Error>> {
+        Self::edit(|queue| {
+            let job = queue
+                .jobs
+                .iter_mut()
+                .find(|job| job.id == id)
+                .ok_or_else(|| tr!("job-not-found", id = id))?;
+            let state = job.state;
+            match (state, job.pid) {
+                (JobState::Queued, _) => {}
+                (JobState::Running, Some(pid)) if process::is_alive(pid) => {
+                    process::send_signal(pid, "TERM")?;
+                }
+                (JobState::Running, _) => {}
+                _ => {
+                    return Err(tr!("job-not-active", id = id, state = state.label()).into());
+                }
+            }
+
+            if let Ok(config) = Config::load() {
+                config.network.downloads.release(&job.slot());
+            }
+            job.state = JobState::Cancelled;
+            job.finished = Some(Utc::now());
+            job.pid = None;
+            Ok(state)
+        })
+    }
+
+    /// Start the next queued job if no job is running
+    ///
+    /// Running jobs whose runner is gone, e.g. after a reboot, are marked as failed.
+    /// Of the jobs whose dependencies have succeeded, the one with the highest
+    /// priority is started, then the oldest. Jobs whose dependencies failed or
+    /// were cancelled are cancelled as well. Jobs that update or restart a
+    /// server in a blackout window wait until the window has ended, jobs that
+    /// run SteamCMD wait for the end of the weekly Steam maintenance, for the
+    /// download hours and for a free download slot.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration with the blackout windows and the download policy
+    ///
+    /// # Returns
+    ///
+    /// The started job, or `None` if a job is still running or the queue is empty
+    ///
+    /// # Errors
+    ///
+    /// If the queue could not be saved or the runner could not be started
+    pub fn run_next(config: &Config) -> Result<Option<Job>, Box<dyn std::error::Error>> {
+        Self::edit(|queue| {
+            let downloads = &config.network.downloads;
+            let mut running = false;
+            for job in queue
+                .jobs
+                .iter_mut()
+                .filter(|j| j.state == JobState::Running)
+            {
+                if job.pid.is_some_and(process::is_alive) {
+                    running = true;
+                    if job.uses_steamcmd() {
+                        downloads.refresh(&job.slot());
+                    }
+                } else {
+                    downloads.release(&job.slot());
+                    job.state = JobState::Failed;
+                    job.finished = Some(Utc::now());
+                    job.pid = None;
+                }
+            }
+            if running {
+                return Ok(None);
+            }
+
+            queue.cancel_orphans();
+            let maintenance = steamcmd::maintenance_until(Utc::now()).is_some();
+            let download_hours = downloads.allows(Local::now().time())?;
+            let mut ready: Vec<&Job> = queue
+                .jobs
+                .iter()
+                .filter(|j| j.state == JobState::Queued)
+                .filter(|j| {
+                    j.after
+                        .iter()
+                        .all(|id| queue.state_of(*id) == Some(JobState::Succeeded))
+                })
+                .filter(|j| !((maintenance || !download_hours) && j.uses_steamcmd()))
+                .filter(|j| {
+                    j.servers(config)
+                        .iter()
+                        .all(|s| s.blackout(config, Utc::now()).is_none())
+                })
+                .collect();
+            ready.sort_by_key(|j| Reverse((j.priority, Reverse(j.id))));
+            // Downloads wait while the other hosts use all slots, other jobs go ahead
+            let mut next = None;
+            for job in ready {
+                if !job.uses_steamcmd() || downloads.acquire(&job.slot())? {
+                    next = Some(job.id);
+                    break;
+                }
+            }
+            let Some(job) = next.and_then(|id| queue.jobs.iter_mut().find(|j| j.id == id)) else {
+                return Ok(None);
+            };
+
+            // The runner gets its own process group, so cancelling the job
+            // terminates the command and everything it started
+            let runner = Command::new(std::env::current_exe()?)
+                .args(["--plain", "jobs", "exec", &job.id.to_string()])
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .process_group(0)
+                .spawn()?;
+
+            job.state = JobState::Running;
+            job.started = Some(Utc::now());
+            job.pid = Some(runner.id());
+            Ok(Some(job.clone()))
+        })
+    }
+
+    /// Run a started job and record its result, called in the runner process
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the job
+    ///
+    /// # Returns
+    ///
+    /// The final state of the job
+    ///
+    /// # Errors
+    ///
+    /// If the job is not running or the command could not be started
+    pub fn execute(id: u64) -> Result<JobState, Box<dyn std::error::Error>> {
+        let job = Self::load()?.get(id)?.clone();
+        if job.state != JobState::Running {
+            return Err(tr!("job-not-running", id = id).into());
+        }
+
+        let log_file = job.log_file()?;
+        if let Some(parent) = log_file.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_file)?;
+
+        // Prompts cannot be answered, without a terminal they use their defaults
+        let status = Command::new(std::env::current_exe()?)
+            .arg("--plain")
+            .args(&job.args)
+            .env(JOB_ENV, id.to_string())
+            .stdin(Stdio::null())
+            .stdout(log.try_clone()?)
+            .stderr(log)
+            .status()?;
+        if job.uses_steamcmd() {
+            Config::load()?.network.downloads.release(&job.slot());
+        }
+
+        Self::edit(|queue| {
+            let job = queue
+                .jobs
+                .iter_mut()
+                .find(|job| job.id == id)
+                .ok_or_else(|| tr!("job-not-found", id = id))?;
+            if job.state == JobState::Running {
+                job.state = if status.success() {
+                    JobState::Succeeded
+                } else {
+                    JobState::Failed
+                };
+                job.finished = Some(Utc::now());
+                job.pid = None;
+            }
+            job.exit_code = status.code();
+            Ok(job.state)
+        })
+    }
+
+    /// Get the state of a job
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the job
+    ///
+    /// # Returns
+    ///
+    /// The state, or `None` if the job was removed
+    fn state_of(&self, id: u64) -> Option<JobState> {
+        self.jobs.iter().find(|j| j.id == id).map(|j| j.state)
+    }
+
+    /// Cancel the queued jobs that depend on a job that did not succeed
+    ///
+    /// Cancelling a job can orphan the jobs that depend on it, so this repeats
+    /// until no job is cancelled anymore.
+    fn cancel_orphans(&mut self) {
+        loop {
+            let orphans: Vec<(u64, u64)> = self
+                .jobs
+                .iter()
+                .filter(|j| j.state == JobState::Queued)
+                .filter_map(|j| {
+                    j.after
+                        .iter()
+                        .find(|id| {
+                            self.state_of(**id)
+                                .is_none_or(|s| s.is_finished() && s != JobState::Succeeded)
+                        })
+                        .map(|dependency| (j.id, *dependency))
+                })
+                .collect();
+            if orphans.is_empty() {
+                return;
+            }
+
+            for (id, dependency) in orphans {
+                if let Some(job) = self.jobs.iter_mut().find(|j| j.id == id) {
+                    job.state = JobState::Cancelled;
+                    job.finished = Some(Utc::now());
+                    job.reason = Some(tr!("job-dependency-failed", id = dependency));
+                }
+            }
+        }
+    }
+
+    /// Load, change and save the queue while holding a lock on it
+    ///
+    /// # Arguments
+    ///
+    /// * `change` - The change, its error leaves the queue unchanged
+    ///
+    /// # Returns
+    ///
+    /// The result of the change
+    ///
+    /// # Errors
+    ///
+    /// If the queue could not be locked, loaded or saved, or the change failed
+    fn edit<T>(
+        change: impl FnOnce(&mut Self) -> Result<T, Box<dyn std::error::Error>>,
+    ) -> Result<T, Box<dyn std::error::Error>> {
+        let path = Self::path()?;
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        let lock = File::create(path.with_extension("lock"))?;
+        // SAFETY: flock only operates on the open file descriptor, the lock is
+        // released when the file is closed
+        if unsafe { libc::flock(lock.as_raw_fd(), libc::LOCK_EX) } != 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+
+        let mut queue = Self::load()?;
+        let result = change(&mut queue)?;
+        fs::write_atomic(&path, serde_json::to_string_pretty(&queue)?.as_bytes())?;
+        Ok(result)
+    }
+
+    /// Get the path to the queue file
+    ///
+    /// # Returns
+    ///
+    /// The path to `jobs.json`
+    ///
+    /// # Errors
+    ///
+    /// If the steamserv directory could not be found
+    fn path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        Ok(Config::data_dir()?.join("jobs.json"))
+    }
+}
+
+/// A step of a batch of jobs, e.g. the update of one server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanStep {
+    pub name: String,
+    pub priority: i32,
+    /// The names of the steps that have to run before this step
+    pub after: Vec<String>,
+}
+
+impl PlanStep {
+    /// Create the update step of a server with its priority and dependencies
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The server
+    ///
+    /// # Returns
+    ///
+    /// The step
+    pub fn update(server: &InstalledServer) -> Self {
+        Self {
+            name: server.name.clone(),
+            priority: server.priority,
+            after: server.update_after.clone(),
+        }
+    }
+}
+
+/// Order the steps of a batch so that every step runs after its dependencies
+///
+/// Of the steps whose dependencies are planned, the one with the highest priority
+/// comes first, ties keep the given order. Dependencies on steps outside the
+/// batch are dropped, they do not have to wait for anything.
+///
+/// # Arguments
+///
+/// * `steps` - The steps of the batch
+///
+/// # Returns
+///
+/// The steps in the order they run
+///
+/// # Errors
+///
+/// If the dependencies form a cycle
+pub fn plan(mut steps: Vec<PlanStep>) -> Result<Vec<PlanStep>, Box<dyn std::error::Error>> {
+    let names: Vec<String> = steps.iter().map(|s| s.name.clone()).collect();
+    for step in &mut steps {
+        step.after.retain(|name| names.contains(name));
+    }
+
+    let mut planned: Vec<PlanStep> = Vec::new();
+    while !steps.is_empty() {
+        let next = steps
+            .iter()
+            .enumerate()
+            .filter(|(_, step)| {
+                step.after
+                    .iter()
+                    .all(|name| planned.iter().any(|p| &p.name == name))
+            })
+            .max_by_key(|(index, step)| (step.priority, Reverse(*index)))
+            .map(|(index, _)| index);
+        let Some(index) = next else {
+            let names: Vec<&str> = steps.iter().map(|s| s.name.as_str()).collect();
+            return Err(tr!("job-plan-cycle", servers = names.join(", ")).into());
+        };
+        planned.push(steps.remove(index));
+    }
+
+    Ok(planned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(name: &str, priority: i32, after: &[&str]) -> PlanStep {
+        PlanStep {
+            name: name.to_string(),
+            priority,
+            after: after.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_plan() {
+        let steps = plan(vec![
+            step("lobby", 10, &["game-1", "game-2"]),
+            step("game-1", 0, &[]),
+            step("game-2", 5, &["removed"]),
+            step("stats", 0, &[]),
+        ])
+        .unwrap();
+        let names: Vec<&str> = steps.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["game-2", "game-1", "lobby", "stats"]);
+        assert!(steps[0].after.is_empty());
+
+        assert!(plan(vec![step("a", 0, &["b"]), step("b", 0, &["a"])]).is_err());
+    }
+
+    #[test]
+    fn test_progress_checkpoints() {
+        let dir = std::env::temp_dir().join(format!("steamserv-jobs-{}", std::process::id()));
+        let mut sink = ProgressSink {
+            path: dir.join("1.progress.json"),
+            log: dir.join("1.log"),
+            progress: JobProgress::default(),
+            saved: None,
+        };
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(&sink.log, "Connecting anonymously to Steam Public...OK\n").unwrap();
+
+        let events = [
+            Event::PhaseStarted {
+                phase: Phase::Login,
+            },
+            Event::PhaseFinished {
+                phase: Phase::Login,
+                seconds: 1.5,
+            },
+            Event::PhaseStarted {
+                phase: Phase::Download,
+            },
+            Event::ProgressUpdated {
+                state: "downloading".to_string(),
+                percent: 45.0,
+                current: 450,
+                total: 1000,
+            },
+        ];
+        for event in &events {
+            sink.handle(event).unwrap();
+        }
+
+        let saved: JobProgress =
+            serde_json::from_str(&std::fs::read_to_string(&sink.path).unwrap()).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(saved.finished, [(Phase::Login, 1.5)]);
+        assert_eq!(saved.phase, Some(Phase::Download));
+        assert_eq!(saved.offset, 44);
+        // The progress right after the start of the phase is throttled
+        assert_eq!(saved.current, 0);
+        assert_eq!(sink.progress.current, 450);
+    }
+}
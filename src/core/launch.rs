@@ -0,0 +1,634 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    core::{
+        profiles::{GameProfile, Runtime},
+        Cluster,
+    },
+    tr,
+    utils::{secrets, Config, InstalledServer, ServerInstance},
+};
+
+/// Compatibility tool used for Windows servers if none is configured
+const DEFAULT_COMPAT_TOOL: &str = "wine";
+/// Argument of Source servers that is followed by the login token
+const GSLT_ARG: &str = "+sv_setsteamaccount";
+/// Shown instead of the login token when the command is printed
+const GSLT_PLACEHOLDER: &str = "<gslt>";
+
+/// Command to launch a game server or one of its instances
+#[derive(Debug, Clone)]
+pub struct LaunchCommand {
+    pub program: PathBuf,
+    pub args: Vec<String>,
+    pub env: BTreeMap<String, String>,
+    pub working_dir: PathBuf,
+    /// The named secrets that were resolved, shown as references when the command is printed
+    pub secrets: BTreeMap<String, String>,
+}
+
+impl LaunchCommand {
+    /// Generate the launch command of a server from its game profile
+    ///
+    /// Servers in a cluster get the `cluster_args` of the profile. Windows
+    /// servers are launched through Wine or Proton. The extra arguments
+    /// and environment variables of the server come first, those of the
+    /// instance are added after them and override them. References to named
+    /// secrets in the environment are resolved last.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    /// * `server` - The installed server
+    /// * `instance` - The instance to launch, or `None` to launch the server itself
+    ///
+    /// # Returns
+    ///
+    /// The launch command
+    ///
+    /// # Errors
+    ///
+    /// If no game profile exists for the server, a referenced secret does not exist
+    /// or an argument references a secret
+    pub fn build(
+        config: &Config,
+        server: &InstalledServer,
+        instance: Option<&ServerInstance>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let profile = GameProfile::find(server.app_id)
+            .ok_or_else(|| tr!("profile-not-found", app_id = server.app_id))?;
+
+        let mut args = Vec::new();
+        for template in &profile.args {
+            if let Some(tokens) = Self::render_template(template, &profile, server, instance) {
+                args.extend(tokens);
+            }
+        }
+
+        if let Some((_, cluster)) = Cluster::find(config, server, instance) {
+            for template in &profile.cluster_args {
+                let template = cluster.render(template);
+                if let Some(tokens) = Self::render_template(&template, &profile, server, instance) {
+                    args.extend(tokens);
+                }
+            }
+        }
+
+        args.extend(server.extra_args.iter().cloned());
+        if let Some(instance) = instance {
+            args.extend(instance.extra_args.iter().cloned());
+        }
+
+        if server.maintenance.as_ref().is_some_and(|m| m.placeholder) {
+            for template in &profile.maintenance_args {
+                if let Some(tokens) = Self::render_template(template, &profile, server, instance) {
+                    args.extend(tokens);
+                }
+            }
+        }
+
+        if profile.is_source_engine() {
+            let token = instance
+                .and_then(|i| i.gslt.as_ref())
+                .or(server.gslt.as_ref());
+            if let Some(token) = token {
+                args.push(GSLT_ARG.to_string());
+                args.push(token.clone());
+            }
+        }
+
+        let executable = server.install_path.join(&profile.executable);
+        let mut command = Self {
+            program: executable.clone(),
+            args,
+            env: BTreeMap::new(),
+            working_dir: server.install_path.clone(),
+            secrets: BTreeMap::new(),
+        };
+
+        if profile.runtime == Runtime::Windows {
+            let tool = Self::compat_tool(config);
+            let mut args = Vec::new();
+            if Self::is_proton(&tool) {
+                args.push("run".to_string());
+                command.env.insert(
+                    "STEAM_COMPAT_DATA_PATH".to_string(),
+                    server.install_path.join("compatdata").display().to_string(),
+                );
+                if let Some(home) = dirs::home_dir() {
+                    command.env.insert(
+                        "STEAM_COMPAT_CLIENT_INSTALL_PATH".to_string(),
+                        home.join(".steam/steam").display().to_string(),
+                    );
+                }
+            }
+            args.push(executable.display().to_string());
+            args.append(&mut command.args);
+            command.program = tool;
+            command.args = args;
+        }
+
+        if let Some(emulator) = &config.emulator {
+            command
+                .args
+                .insert(0, command.program.display().to_string());
+            command.program = emulator.clone();
+        }
+
+        command.env.extend(server.env.clone());
+        if let Some(instance) = instance {
+            command.env.extend(instance.env.clone());
+        }
+        command.resolve_secrets(&config.secrets.values)?;
+
+        Ok(command)
+    }
+
+    /// Replace the references to named secrets in the environment
+    ///
+    /// Secrets are only passed through the environment of the server. The
+    /// arguments of a process can be read by every user in `ps`, so a reference
+    /// in an argument is an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The decrypted named secrets
+    ///
+    /// # Returns
+    ///
+    /// Ok if all references were resolved
+    ///
+    /// # Errors
+    ///
+    /// If a referenced secret does not exist or an argument references a secret
+    fn resolve_secrets(
+        &mut self,
+        values: &BTreeMap<String, String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(arg) = self.args.iter().find(|arg| secrets::has_reference(arg)) {
+            return Err(tr!("secrets-in-args", arg = arg.as_str()).into());
+        }
+        let mut used = Vec::new();
+        for value in self.env.values_mut() {
+            *value = secrets::resolve_references(values, value, &mut used)?;
+        }
+        self.secrets = used
+            .into_iter()
+            .filter_map(|name| {
+                let value = values.get(&name)?.clone();
+                (!value.is_empty()).then_some((name, value))
+            })
+            .collect();
+
+        Ok(())
+    }
+
+    /// Hide the values of the resolved secrets in a value
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - An argument or environment value
+    ///
+    /// # Returns
+    ///
+    /// The value with the secrets replaced by their references
+    fn redact(&self, value: &str) -> String {
+        self.secrets
+            .iter()
+            .fold(value.to_string(), |value, (name, secret)| {
+                value.replace(secret.as_str(), &secrets::reference(name))
+            })
+    }
+
+    /// Get the compatibility tool to run Windows servers with
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    ///
+    /// # Returns
+    ///
+    /// The configured Wine or Proton executable, or `wine` from the `PATH`
+    pub fn compat_tool(config: &Config) -> PathBuf {
+        config
+            .compat_tool
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_COMPAT_TOOL))
+    }
+
+    /// Check if a compatibility tool is Proton
+    ///
+    /// # Arguments
+    ///
+    /// * `tool` - The path to the compatibility tool
+    ///
+    /// # Returns
+    ///
+    /// True if the file name of the tool is `proton`
+    fn is_proton(tool: &Path) -> bool {
+        tool.file_name().is_some_and(|name| name == "proton")
+    }
+
+    /// Render a file path template of a game profile
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - The path template, relative to the install directory
+    /// * `profile` - The game profile
+    /// * `server` - The installed server
+    /// * `instance` - The instance to launch
+    ///
+    /// # Returns
+    ///
+    /// The rendered path, or `None` if a placeholder has no value
+    pub fn render_path(
+        template: &str,
+        profile: &GameProfile,
+        server: &InstalledServer,
+        instance: Option<&ServerInstance>,
+    ) -> Option<PathBuf> {
+        let path = Self::render_token(template, profile, server, instance)?;
+        Some(server.install_path.join(path))
+    }
+
+    /// Get the port the server or instance is launched with
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - The game profile
+    /// * `server` - The installed server
+    /// * `instance` - The instance to launch
+    ///
+    /// # Returns
+    ///
+    /// The port of the instance, the server or the default port of the profile
+    pub fn port(
+        profile: &GameProfile,
+        server: &InstalledServer,
+        instance: Option<&ServerInstance>,
+    ) -> Option<u16> {
+        instance
+            .and_then(|i| i.port)
+            .or(server.port)
+            .or(profile.default_port)
+    }
+
+    /// Render a launch argument template
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - The argument template
+    /// * `profile` - The game profile
+    /// * `server` - The installed server
+    /// * `instance` - The instance to launch
+    ///
+    /// # Returns
+    ///
+    /// The rendered arguments, or `None` if a placeholder has no value
+    pub fn render_template(
+        template: &str,
+        profile: &GameProfile,
+        server: &InstalledServer,
+        instance: Option<&ServerInstance>,
+    ) -> Option<Vec<String>> {
+        template
+            .split_whitespace()
+            .map(|token| Self::render_token(token, profile, server, instance))
+            .collect()
+    }
+
+    /// Replace the placeholders of a single template token
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The template token
+    /// * `profile` - The game profile
+    /// * `server` - The installed server
+    /// * `instance` - The instance to launch
+    ///
+    /// # Returns
+    ///
+    /// The rendered token, or `None` if a placeholder has no value
+    fn render_token(
+        token: &str,
+        profile: &GameProfile,
+        server: &InstalledServer,
+        instance: Option<&ServerInstance>,
+    ) -> Option<String> {
+        let mut rendered = String::new();
+        let mut rest = token;
+
+        while let Some(start) = rest.find('{') {
+            let end = rest[start..].find('}')? + start;
+            rendered.push_str(&rest[..start]);
+            rendered.push_str(&Self::resolve_placeholder(
+                &rest[start + 1..end],
+                profile,
+                server,
+                instance,
+            )?);
+            rest = &rest[end + 1..];
+        }
+        rendered.push_str(rest);
+
+        Some(rendered)
+    }
+
+    /// Resolve the value of a placeholder
+    ///
+    /// # Arguments
+    ///
+    /// * `placeholder` - The name of the placeholder without braces
+    /// * `profile` - The game profile
+    /// * `server` - The installed server
+    /// * `instance` - The instance to launch
+    ///
+    /// # Returns
+    ///
+    /// The value of the placeholder, or `None` if it has no value
+    fn resolve_placeholder(
+        placeholder: &str,
+        profile: &GameProfile,
+        server: &InstalledServer,
+        instance: Option<&ServerInstance>,
+    ) -> Option<String> {
+        match placeholder {
+            "port" => Self::port(profile, server, instance).map(|port| port.to_string()),
+            "name" => Some(
+                instance
+                    .map(|i| i.name.clone())
+                    .unwrap_or_else(|| server.name.clone()),
+            ),
+            "world" => instance.and_then(|i| i.world_name.clone()),
+            "config_file" => instance
+                .and_then(|i| i.config_file.as_ref())
+                .map(|path| path.display().to_string()),
+            "install_dir" => Some(server.install_path.display().to_string()),
+            "home" => dirs::home_dir().map(|home| home.display().to_string()),
+            _ => {
+                let key = placeholder.strip_prefix("var:")?;
+                instance.and_then(|i| i.variables.get(key).cloned())
+            }
+        }
+    }
+}
+
+impl fmt::Display for LaunchCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (key, value) in &self.env {
+            write!(f, "{}={} ", key, quote(&self.redact(value)))?;
+        }
+        write!(f, "{}", quote(&self.program.display().to_string()))?;
+        let mut previous = None;
+        for arg in &self.args {
+            match previous {
+                Some(GSLT_ARG) => write!(f, " {}", quote(GSLT_PLACEHOLDER))?,
+                _ => write!(f, " {}", quote(&self.redact(arg)))?,
+            }
+            previous = Some(arg.as_str());
+        }
+        Ok(())
+    }
+}
+
+/// Quote a shell argument if needed
+///
+/// # Arguments
+///
+/// * `arg` - The argument to quote
+///
+/// # Returns
+///
+/// The argument, wrapped in single quotes if it contains special characters
+pub fn quote(arg: &str) -> String {
+    if !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=+:,@%".contains(c))
+    {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+/// Split start parameters into arguments like a shell
+///
+/// # Arguments
+///
+/// * `parameters` - The parameters, e.g. `-name "My Server" -port 2456`
+///
+/// # Returns
+///
+/// The arguments without quotes
+pub fn split_args(parameters: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut quote = None;
+
+    for c in parameters.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.get_or_insert_with(String::new).push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                current.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => args.extend(current.take()),
+            (None, c) => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    args.extend(current);
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::utils::config::LoginType;
+
+    fn server(app_id: u32) -> InstalledServer {
+        let mut server = InstalledServer::new(
+            app_id,
+            "test",
+            PathBuf::from("/srv/test"),
+            LoginType::Anonymous,
+        );
+        server.gslt = Some("TOKEN".to_string());
+        server
+    }
+
+    #[test]
+    fn test_build_source_launch_command() {
+        let server = server(730);
+        let mut instance = ServerInstance::new("competitive");
+        instance.port = Some(27016);
+        instance.variables = BTreeMap::from([("MAP".to_string(), "de_inferno".to_string())]);
+
+        let command = LaunchCommand::build(&Config::default(), &server, Some(&instance)).unwrap();
+        assert_eq!(
+            command.args,
+            [
+                "-dedicated",
+                "-port",
+                "27016",
+                "+map",
+                "de_inferno",
+                "+sv_setsteamaccount",
+                "TOKEN"
+            ]
+        );
+        let printed = command.to_string();
+        assert!(!printed.contains("TOKEN"));
+        assert!(printed.ends_with("+sv_setsteamaccount '<gslt>'"));
+    }
+
+    #[test]
+    fn test_install_commands() {
+        let server = server(90);
+        let profile = GameProfile::find(90).unwrap();
+        let mut instance = ServerInstance::new("main");
+        instance
+            .variables
+            .insert("MOD".to_string(), "cstrike".to_string());
+
+        assert_eq!(
+            profile.install_commands(&server, Some(&instance)),
+            ["+app_set_config 90 mod cstrike"]
+        );
+        assert!(profile.install_commands(&server, None).is_empty());
+        assert!(profile.variables().contains(&"MOD".to_string()));
+    }
+
+    #[test]
+    fn test_build_skips_unset_placeholders() {
+        let server = server(896660);
+        let command = LaunchCommand::build(&Config::default(), &server, None).unwrap();
+        assert!(command.args.contains(&"2456".to_string()));
+        assert!(!command.args.contains(&"-password".to_string()));
+        assert!(!command.args.contains(&"+sv_setsteamaccount".to_string()));
+    }
+
+    #[test]
+    fn test_build_maintenance_placeholder() {
+        let mut server = server(730);
+        server.maintenance = Some(crate::utils::Maintenance {
+            since: chrono::Utc::now(),
+            reason: None,
+            placeholder: true,
+            stopped: Vec::new(),
+        });
+
+        let command = LaunchCommand::build(&Config::default(), &server, None).unwrap();
+        assert!(command.args.contains(&"Maintenance".to_string()));
+        assert!(!command.args.contains(&"+sv_password".to_string()));
+    }
+
+    #[test]
+    fn test_build_with_emulator() {
+        let server = server(896660);
+        let config = Config {
+            emulator: Some(PathBuf::from("/usr/bin/FEXInterpreter")),
+            ..Default::default()
+        };
+
+        let command = LaunchCommand::build(&config, &server, None).unwrap();
+        assert_eq!(command.program, PathBuf::from("/usr/bin/FEXInterpreter"));
+        assert_eq!(command.args[0], "/srv/test/valheim_server.x86_64");
+    }
+
+    #[test]
+    fn test_build_windows_server_with_proton() {
+        let server = server(2278520);
+        let config = Config {
+            compat_tool: Some(PathBuf::from("/opt/proton/proton")),
+            ..Default::default()
+        };
+
+        let command = LaunchCommand::build(&config, &server, None).unwrap();
+        assert_eq!(command.program, PathBuf::from("/opt/proton/proton"));
+        assert_eq!(
+            command.args[..2],
+            ["run", "/srv/test/enshrouded_server.exe"]
+        );
+        assert!(command.env.contains_key("STEAM_COMPAT_DATA_PATH"));
+    }
+
+    #[test]
+    fn test_build_with_env_and_extra_args() {
+        let mut server = server(896660);
+        server.env = BTreeMap::from([
+            ("SteamAppId".to_string(), "892970".to_string()),
+            ("LD_LIBRARY_PATH".to_string(), "./linux64".to_string()),
+        ]);
+        server.extra_args = split_args("-crossplay -saveinterval 600");
+        let mut instance = ServerInstance::new("pvp");
+        instance.env = BTreeMap::from([("SteamAppId".to_string(), "896660".to_string())]);
+        instance.extra_args = vec!["-public".to_string(), "0".to_string()];
+
+        let command = LaunchCommand::build(&Config::default(), &server, Some(&instance)).unwrap();
+        assert_eq!(
+            command.args[command.args.len() - 5..],
+            ["-crossplay", "-saveinterval", "600", "-public", "0"]
+        );
+        assert_eq!(command.env["SteamAppId"], "896660");
+        assert_eq!(command.env["LD_LIBRARY_PATH"], "./linux64");
+    }
+
+    #[test]
+    fn test_build_resolves_and_redacts_secrets() {
+        let mut config = Config::default();
+        config
+            .secrets
+            .values
+            .insert("admin".to_string(), "hunter2".to_string());
+        let mut server = server(896660);
+        server.env =
+            BTreeMap::from([("ADMIN_PASSWORD".to_string(), "${secret:admin}".to_string())]);
+
+        let command = LaunchCommand::build(&config, &server, None).unwrap();
+        assert_eq!(command.env["ADMIN_PASSWORD"], "hunter2");
+        assert!(!command.to_string().contains("hunter2"));
+
+        // Secrets are never passed as arguments, other users can read them
+        server.extra_args = vec!["-password=${secret:admin}".to_string()];
+        assert!(LaunchCommand::build(&config, &server, None).is_err());
+
+        server.extra_args.clear();
+        server
+            .env
+            .insert("RCON".to_string(), "${secret:missing}".to_string());
+        assert!(LaunchCommand::build(&config, &server, None).is_err());
+    }
+
+    #[test]
+    fn test_build_with_cluster_args() {
+        let server = server(376030);
+        let mut instance = ServerInstance::new("island");
+        instance.world_name = Some("TheIsland".to_string());
+        let mut config = Config::default();
+        config.clusters.insert(
+            "ark".to_string(),
+            Cluster {
+                id: "ark-cluster".to_string(),
+                dir: PathBuf::from("/srv/clusters/ark"),
+                servers: vec!["test/island".to_string()],
+            },
+        );
+
+        let command = LaunchCommand::build(&config, &server, Some(&instance)).unwrap();
+        assert!(command.args.contains(&"-clusterid=ark-cluster".to_string()));
+        assert!(command
+            .args
+            .contains(&"-ClusterDirOverride=/srv/clusters/ark".to_string()));
+
+        let command = LaunchCommand::build(&config, &server, None).unwrap();
+        assert!(!command.args.iter().any(|arg| arg.starts_with("-clusterid")));
+    }
+}
@@ -0,0 +1,356 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    core::{launch::split_args, GameProfile},
+    tr,
+    utils::{config::LoginType, InstalledServer, RconConfig, ServerInstance},
+};
+
+/// LinuxGSM settings that map to instance variables of the game profiles
+const VARIABLES: &[(&str, &str)] = &[
+    ("PASSWORD", "serverpassword"),
+    ("MAP", "defaultmap"),
+    ("MAXPLAYERS", "maxplayers"),
+];
+
+/// A LinuxGSM installation, e.g. `/home/vhserver`
+///
+/// LinuxGSM keeps the settings of every instance in
+/// `lgsm/config-lgsm/<game>/`, layered as `_default.cfg`, `common.cfg` and
+/// `<instance>.cfg`. All instances share the server files, so the installation
+/// becomes one server and its instances become steamserv instances.
+#[derive(Debug)]
+pub struct LgsmInstall {
+    pub root: PathBuf,
+    pub app_id: u32,
+    pub serverfiles: PathBuf,
+    /// The settings of the instances by name, e.g. `vhserver` and `vhserver-2`
+    pub instances: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl LgsmInstall {
+    /// Read the configs of a LinuxGSM installation
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The directory with the `lgsm` directory and the server files
+    ///
+    /// # Returns
+    ///
+    /// The installation with the settings of its instances
+    ///
+    /// # Errors
+    ///
+    /// If no or several game configs exist, or the App ID is missing
+    pub fn read(root: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let config_dir = root.join("lgsm").join("config-lgsm");
+        let games: Vec<PathBuf> = std::fs::read_dir(&config_dir)
+            .map_err(|_| tr!("lgsm-not-found", path = root.display()))?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .collect();
+        let [game_dir] = games.as_slice() else {
+            return Err(tr!(
+                "lgsm-games",
+                path = config_dir.display(),
+                count = games.len()
+            )
+            .into());
+        };
+
+        let mut instances = BTreeMap::new();
+        for entry in std::fs::read_dir(game_dir)? {
+            let path = entry?.path();
+            let Some(name) = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_suffix(".cfg"))
+            else {
+                continue;
+            };
+            if name.starts_with('_') || name.starts_with("secrets-") || name == "common" {
+                continue;
+            }
+
+            let mut settings = BTreeMap::from([
+                ("rootdir".to_string(), root.display().to_string()),
+                ("selfname".to_string(), name.to_string()),
+                (
+                    "serverfiles".to_string(),
+                    root.join("serverfiles").display().to_string(),
+                ),
+            ]);
+            // Later files override earlier ones, the secrets files hold passwords and tokens
+            for file in [
+                "_default".to_string(),
+                "common".to_string(),
+                "secrets-common".to_string(),
+                name.to_string(),
+                format!("secrets-{}", name),
+            ] {
+                if let Ok(text) = std::fs::read_to_string(game_dir.join(format!("{}.cfg", file))) {
+                    parse_config(&text, &mut settings);
+                }
+            }
+            instances.insert(name.to_string(), settings);
+        }
+
+        let first = instances
+            .values()
+            .next()
+            .ok_or_else(|| tr!("lgsm-no-instances", path = game_dir.display()))?;
+        let app_id = first
+            .get("appid")
+            .and_then(|id| id.parse().ok())
+            .ok_or_else(|| tr!("lgsm-no-app-id", path = game_dir.display()))?;
+        let serverfiles = PathBuf::from(&first["serverfiles"]);
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            app_id,
+            serverfiles,
+            instances,
+        })
+    }
+
+    /// Translate the installation into a steamserv server
+    ///
+    /// Ports, tokens, RCON, worlds, config files and the variables of the game
+    /// profile are taken over. Start parameters that the profile does not set
+    /// itself are kept as extra launch arguments of the instances.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the server in steamserv
+    ///
+    /// # Returns
+    ///
+    /// The server with one instance per LinuxGSM instance
+    pub fn to_server(&self, name: &str) -> InstalledServer {
+        let profile = GameProfile::find(self.app_id);
+        let first = self.instances.values().next();
+        let setting = |key: &str| {
+            first
+                .and_then(|s| s.get(key))
+                .filter(|v| !v.is_empty())
+                .cloned()
+        };
+        let login_type = match setting("steamuser").as_deref() {
+            None | Some("anonymous") | Some("username") => LoginType::Anonymous,
+            Some(_) => LoginType::SteamAccount,
+        };
+
+        let instances: Vec<ServerInstance> = self
+            .instances
+            .iter()
+            .map(|(name, settings)| to_instance(name, settings, profile.as_ref()))
+            .collect();
+
+        let mut server =
+            InstalledServer::new(self.app_id, name, self.serverfiles.clone(), login_type);
+        server.port = instances.first().and_then(|i| i.port);
+        server.instances = instances;
+        server.branch = setting("branch");
+        server
+    }
+}
+
+/// Translate the settings of a LinuxGSM instance
+///
+/// # Arguments
+///
+/// * `name` - The name of the instance
+/// * `settings` - The merged settings of the instance
+/// * `profile` - The game profile, if one exists for the game
+///
+/// # Returns
+///
+/// The steamserv instance
+fn to_instance(
+    name: &str,
+    settings: &BTreeMap<String, String>,
+    profile: Option<&GameProfile>,
+) -> ServerInstance {
+    let setting = |key: &str| settings.get(key).filter(|v| !v.is_empty()).cloned();
+    let mut instance = ServerInstance::new(name);
+
+    instance.port = setting("port").and_then(|p| p.parse().ok());
+    instance.gslt = setting("gslt");
+    instance.world_name = setting("worldname");
+    instance.config_file = setting("servercfg").map(PathBuf::from);
+    if let Some(password) = setting("rconpassword") {
+        instance.rcon = Some(RconConfig {
+            host: None,
+            port: setting("rconport")
+                .and_then(|p| p.parse().ok())
+                .or(instance.port)
+                .unwrap_or_default(),
+            password,
+        });
+    }
+
+    let used = profile.map(|p| p.variables()).unwrap_or_default();
+    for (variable, key) in VARIABLES {
+        if let Some(value) = setting(key) {
+            if profile.is_none() || used.iter().any(|v| v == variable) {
+                instance.variables.insert(variable.to_string(), value);
+            }
+        }
+    }
+
+    if let Some(parameters) = setting("startparameters") {
+        instance.extra_args = extra_args(&split_args(&parameters), profile);
+    }
+    instance
+}
+
+/// Parse the variable assignments of a LinuxGSM config
+///
+/// # Arguments
+///
+/// * `text` - The config, a bash file with lines like `port="2456"`
+/// * `settings` - The settings so far, used to expand `${name}` and updated
+fn parse_config(text: &str, settings: &mut BTreeMap<String, String>) {
+    for line in text.lines() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            continue;
+        }
+
+        let value = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..]
+                .rfind(quote)
+                .map(|end| &value[1..end + 1])
+                .unwrap_or(&value[1..]),
+            _ => value.split('#').next().unwrap_or_default().trim(),
+        };
+        let value = expand(value, settings);
+        settings.insert(key.to_string(), value);
+    }
+}
+
+/// Expand the `${name}` and `$name` references in a value
+///
+/// # Arguments
+///
+/// * `value` - The value
+/// * `settings` - The known settings, unknown references become empty
+///
+/// # Returns
+///
+/// The expanded value
+fn expand(value: &str, settings: &BTreeMap<String, String>) -> String {
+    let mut expanded = String::new();
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            expanded.push(c);
+            continue;
+        }
+        let mut name = String::new();
+        if chars.peek() == Some(&'{') {
+            chars.next();
+            for c in chars.by_ref() {
+                if c == '}' {
+                    break;
+                }
+                name.push(c);
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                if !(c.is_ascii_alphanumeric() || c == '_') {
+                    break;
+                }
+                name.push(c);
+                chars.next();
+            }
+        }
+        if name.is_empty() {
+            expanded.push('$');
+        } else if let Some(value) = settings.get(&name) {
+            expanded.push_str(value);
+        }
+    }
+
+    expanded
+}
+
+/// Get the start parameters that the game profile does not set itself
+///
+/// # Arguments
+///
+/// * `args` - The start parameters
+/// * `profile` - The game profile
+///
+/// # Returns
+///
+/// The options with their values whose flag is not used by the profile, all
+/// parameters if there is no profile
+fn extra_args(args: &[String], profile: Option<&GameProfile>) -> Vec<String> {
+    let Some(profile) = profile else {
+        return args.to_vec();
+    };
+    let flags: Vec<&str> = profile
+        .args
+        .iter()
+        .filter_map(|template| template.split_whitespace().next())
+        .collect();
+
+    let mut extra = Vec::new();
+    let mut keep = true;
+    for arg in args {
+        if arg.starts_with('-') || arg.starts_with('+') {
+            keep = !flags.contains(&arg.as_str());
+        }
+        if keep {
+            extra.push(arg.clone());
+        }
+    }
+    extra
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let mut settings = BTreeMap::from([("rootdir".to_string(), "/home/vh".to_string())]);
+        parse_config(
+            r#"
+            # Valheim
+            port="2456"
+            servername='My Server'
+            serverfiles="${rootdir}/serverfiles"
+            worldname=Dedicated # comment
+            startparameters="-name '${servername}' -port ${port} -crossplay -saveinterval 600"
+            "#,
+            &mut settings,
+        );
+        assert_eq!(settings["serverfiles"], "/home/vh/serverfiles");
+        assert_eq!(settings["worldname"], "Dedicated");
+
+        let args = split_args(&settings["startparameters"]);
+        assert_eq!(args[..2], ["-name", "My Server"]);
+
+        let valheim = GameProfile::find(896660).unwrap();
+        assert_eq!(
+            extra_args(&args, Some(&valheim)),
+            ["-crossplay", "-saveinterval", "600"]
+        );
+        let instance = to_instance("vhserver", &settings, Some(&valheim));
+        assert_eq!(instance.port, Some(2456));
+        assert_eq!(instance.world_name.as_deref(), Some("Dedicated"));
+    }
+}
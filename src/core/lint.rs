@@ -0,0 +1,271 @@
+use std::{collections::BTreeMap, fmt};
+
+use crate::{
+    core::{game_config::parse, profiles::ConfigSchema, GameProfile, LaunchCommand},
+    tr,
+    utils::{InstalledServer, ServerInstance},
+};
+
+/// Minimum length of an RCON password
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+/// Passwords that are tried first when RCON ports are scanned
+const COMMON_PASSWORDS: &[&str] = &[
+    "admin", "changeme", "default", "letmein", "password", "qwerty", "rcon", "secret", "12345678",
+];
+
+/// Severity of a lint finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The server starts but likely misbehaves, e.g. a weak password
+    Warning,
+    /// The server does not work as steamserv expects, e.g. a port mismatch
+    Error,
+}
+
+/// A problem found in the config file of a game
+pub struct LintFinding {
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// Result of checking the config file of a server or instance against the
+/// schema of its game profile
+pub struct LintReport {
+    /// The checked config file, or `None` if the profile has no schema
+    pub file: Option<String>,
+    pub findings: Vec<LintFinding>,
+}
+
+impl LintReport {
+    /// Check the config file of a server or instance
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The installed server
+    /// * `instance` - The instance to check, or `None` for the server itself
+    ///
+    /// # Returns
+    ///
+    /// The findings, empty if the config file is fine
+    ///
+    /// # Errors
+    ///
+    /// If no game profile exists for the server or the file could not be read
+    pub fn check(
+        server: &InstalledServer,
+        instance: Option<&ServerInstance>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let profile = GameProfile::find(server.app_id)
+            .ok_or_else(|| tr!("profile-not-found", app_id = server.app_id))?;
+        let mut report = Self {
+            file: None,
+            findings: Vec::new(),
+        };
+
+        if let Some(rcon) = server.rcon_for(instance) {
+            report.check_password(&rcon.password);
+        }
+
+        let Some(schema) = &profile.config_schema else {
+            return Ok(report);
+        };
+        let Some(path) = LaunchCommand::render_path(&schema.file, &profile, server, instance)
+        else {
+            report.push(Severity::Warning, tr!("lint-no-config-file"));
+            return Ok(report);
+        };
+        report.file = Some(path.display().to_string());
+
+        if !path.is_file() {
+            report.push(
+                Severity::Error,
+                tr!("doctor-missing", path = path.display()),
+            );
+            return Ok(report);
+        }
+        let settings = parse(schema.format, &std::fs::read_to_string(&path)?);
+        report.check_settings(schema, &settings, &profile, server, instance);
+
+        Ok(report)
+    }
+
+    /// Check if the config file has errors
+    ///
+    /// # Returns
+    ///
+    /// True if any finding is an error
+    pub fn has_errors(&self) -> bool {
+        self.findings.iter().any(|f| f.severity == Severity::Error)
+    }
+
+    /// Add a finding
+    ///
+    /// # Arguments
+    ///
+    /// * `severity` - The severity of the finding
+    /// * `message` - The description of the problem
+    fn push(&mut self, severity: Severity, message: String) {
+        self.findings.push(LintFinding { severity, message });
+    }
+
+    /// Check the settings of the config file against the schema
+    ///
+    /// # Arguments
+    ///
+    /// * `schema` - The schema of the config file
+    /// * `settings` - The settings in the config file
+    /// * `profile` - The game profile
+    /// * `server` - The installed server
+    /// * `instance` - The checked instance
+    fn check_settings(
+        &mut self,
+        schema: &ConfigSchema,
+        settings: &BTreeMap<String, String>,
+        profile: &GameProfile,
+        server: &InstalledServer,
+        instance: Option<&ServerInstance>,
+    ) {
+        for key in &schema.required {
+            if settings.get(key).is_none_or(|v| v.is_empty()) {
+                self.push(Severity::Error, tr!("lint-key-missing", key = key));
+            }
+        }
+
+        let port = LaunchCommand::port(profile, server, instance);
+        self.check_port(schema.port_key.as_deref(), settings, port);
+
+        let rcon = server.rcon_for(instance);
+        self.check_port(
+            schema.rcon_port_key.as_deref(),
+            settings,
+            rcon.map(|r| r.port),
+        );
+
+        let Some(key) = &schema.rcon_password_key else {
+            return;
+        };
+        let password = settings.get(key).map(String::as_str).unwrap_or_default();
+        match rcon {
+            Some(_) if password.is_empty() => {
+                self.push(Severity::Error, tr!("lint-rcon-disabled", key = key))
+            }
+            Some(rcon) if rcon.password != password => self.push(
+                Severity::Error,
+                tr!("lint-rcon-password-mismatch", key = key),
+            ),
+            // The stored password was already checked
+            Some(_) => {}
+            None if !password.is_empty() => self.check_password(password),
+            None => {}
+        }
+    }
+
+    /// Check that a port in the config file matches the port steamserv uses
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the port in the config file, if the schema has one
+    /// * `settings` - The settings in the config file
+    /// * `expected` - The port steamserv uses, if it knows one
+    fn check_port(
+        &mut self,
+        key: Option<&str>,
+        settings: &BTreeMap<String, String>,
+        expected: Option<u16>,
+    ) {
+        let (Some(key), Some(expected)) = (key, expected) else {
+            return;
+        };
+        let Some(value) = settings.get(key) else {
+            return;
+        };
+        if value.parse::<u16>().ok() != Some(expected) {
+            self.push(
+                Severity::Error,
+                tr!(
+                    "lint-port-mismatch",
+                    key = key,
+                    value = value,
+                    port = expected
+                ),
+            );
+        }
+    }
+
+    /// Check the strength of an RCON password
+    ///
+    /// # Arguments
+    ///
+    /// * `password` - The password
+    fn check_password(&mut self, password: &str) {
+        if COMMON_PASSWORDS
+            .iter()
+            .any(|common| password.eq_ignore_ascii_case(common))
+        {
+            self.push(Severity::Error, tr!("lint-password-common"));
+        } else if password.chars().count() < MIN_PASSWORD_LENGTH {
+            self.push(
+                Severity::Warning,
+                tr!("lint-password-short", length = MIN_PASSWORD_LENGTH),
+            );
+        }
+    }
+}
+
+impl fmt::Display for LintReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.findings.is_empty() {
+            return write!(f, "[OK  ] {}", tr!("lint-no-findings"));
+        }
+        for (i, finding) in self.findings.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            let status = match finding.severity {
+                Severity::Warning => "WARN",
+                Severity::Error => "FAIL",
+            };
+            write!(f, "[{:<4}] {}", status, finding.message)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::profiles::ConfigFormat;
+
+    #[test]
+    fn test_parse_formats() {
+        let source = parse(
+            ConfigFormat::Source,
+            "// server.cfg\nhostname \"My Server\"\nrcon_password \"changeme\" // TODO\nsv_lan 0\n",
+        );
+        assert_eq!(source["hostname"], "My Server");
+        assert_eq!(source["rcon_password"], "changeme");
+
+        let ini = parse(
+            ConfigFormat::Ini,
+            "[Server]\nDefaultPort=16261\n;RCONPort=1\n",
+        );
+        assert_eq!(ini["DefaultPort"], "16261");
+        assert!(!ini.contains_key(";RCONPort"));
+
+        let xml = parse(
+            ConfigFormat::Xml,
+            r#"<property name="ServerPort" value="26900"/> <!-- Port -->"#,
+        );
+        assert_eq!(xml["ServerPort"], "26900");
+
+        let mut report = LintReport {
+            file: None,
+            findings: Vec::new(),
+        };
+        report.check_port(Some("DefaultPort"), &ini, Some(16262));
+        report.check_password("changeme");
+        assert!(report.has_errors());
+        assert_eq!(report.findings.len(), 2);
+    }
+}
@@ -0,0 +1,410 @@
+use std::{
+    collections::HashMap,
+    fs::OpenOptions,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::{Duration, SystemTime},
+};
+
+use chrono::Local;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::ServerProcess,
+    tr,
+    utils::{fs, Config, ServerRef},
+};
+
+/// Rotation of the captured server logs in `logs` of the steamserv directory
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct LogConfig {
+    /// Rotate a server log once it is bigger, e.g. `50M`
+    pub max_size: String,
+    /// Number of rotated files kept per server
+    pub keep: usize,
+    /// Days after which rotated files are deleted, `0` keeps them
+    pub max_age_days: u64,
+    /// Cap of all rotated files together, the oldest are deleted first, e.g. `1G`
+    pub total_size: Option<String>,
+    /// Compress rotated files with gzip
+    pub compress: bool,
+    /// Interval of the checks of the supervisor in seconds
+    pub check_interval: u64,
+}
+
+impl Default for LogConfig {
+    /// Rotate at 50 MB, keep five compressed files per server for 30 days and 1 GB in total
+    fn default() -> Self {
+        Self {
+            max_size: "50M".to_string(),
+            keep: 5,
+            max_age_days: 30,
+            total_size: Some("1G".to_string()),
+            compress: true,
+            check_interval: 5 * 60,
+        }
+    }
+}
+
+/// A rotated log file
+#[derive(Debug, Clone)]
+struct RotatedLog {
+    path: PathBuf,
+    /// The file name of the log it was rotated from
+    log: String,
+    modified: SystemTime,
+    size: u64,
+}
+
+/// Result of a rotation of the server logs
+#[derive(Debug, Default)]
+pub struct LogPrune {
+    /// The logs that were rotated
+    pub rotated: Vec<PathBuf>,
+    /// The rotated files that were deleted
+    pub removed: Vec<PathBuf>,
+    /// The space of the deleted files in bytes
+    pub freed: u64,
+}
+
+impl LogPrune {
+    /// Rotate the server logs that are too big and delete old rotated files
+    ///
+    /// A log is copied and then truncated, so running servers keep writing to
+    /// it. Rotated files beyond the number to keep, older than the maximum age
+    /// or above the total cap are deleted, the oldest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `settings` - The log settings
+    /// * `dry_run` - Only report what would be rotated and deleted
+    ///
+    /// # Returns
+    ///
+    /// The rotated and deleted files
+    ///
+    /// # Errors
+    ///
+    /// If a size is invalid or a log could not be rotated or deleted
+    pub fn run(settings: &LogConfig, dry_run: bool) -> Result<Self, Box<dyn std::error::Error>> {
+        let max_size = parse_size(&settings.max_size)?;
+        let total_size = settings.total_size.as_deref().map(parse_size).transpose()?;
+        let dir = logs_dir()?;
+        let mut prune = Self::default();
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            return Ok(prune);
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_log = path.extension().is_some_and(|e| e == "log");
+            let size = entry.metadata().map_or(0, |meta| meta.len());
+            if is_log && path.is_file() && size >= max_size {
+                if !dry_run {
+                    rotate(&path, settings.compress)?;
+                }
+                prune.rotated.push(path);
+            }
+        }
+
+        // The files rotated above count towards the files to keep
+        let mut rotated = Vec::new();
+        for entry in std::fs::read_dir(&dir)?.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Some((log, _)) = name.split_once(".log.") else {
+                continue;
+            };
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            if meta.is_file() {
+                rotated.push(RotatedLog {
+                    path: entry.path(),
+                    log: format!("{}.log", log),
+                    modified: meta.modified()?,
+                    size: meta.len(),
+                });
+            }
+        }
+
+        let max_age = Duration::from_secs(settings.max_age_days * 24 * 60 * 60);
+        for log in expired(
+            rotated,
+            settings.keep,
+            max_age,
+            total_size,
+            SystemTime::now(),
+        ) {
+            if !dry_run {
+                std::fs::remove_file(&log.path)?;
+            }
+            prune.freed += log.size;
+            prune.removed.push(log.path);
+        }
+
+        Ok(prune)
+    }
+}
+
+/// A line of a server log that matched a search
+#[derive(Debug, Serialize)]
+pub struct LogMatch {
+    /// The log or rotated file the line is in
+    pub file: PathBuf,
+    /// The number of the line in the file, starting at 1
+    pub line_number: usize,
+    pub line: String,
+}
+
+/// Search the log of a server or instance and its rotated files
+///
+/// The rotated files are searched oldest first and the current log last.
+/// Compressed files are read with `gzip`.
+///
+/// # Arguments
+///
+/// * `target` - The server or instance
+/// * `regex` - The expression, matched anywhere in a line
+/// * `since` - Only search the files written to after this time
+///
+/// # Returns
+///
+/// The matching lines in the order they were written
+///
+/// # Errors
+///
+/// If the steamserv directory could not be found or a file could not be read
+pub fn search(
+    target: &ServerRef,
+    regex: &Regex,
+    since: Option<SystemTime>,
+) -> Result<Vec<LogMatch>, Box<dyn std::error::Error>> {
+    let log_file = ServerProcess::new(target)?.log_file;
+    let name = log_file
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let Ok(entries) = std::fs::read_dir(logs_dir()?) else {
+        return Ok(Vec::new());
+    };
+
+    let mut files = Vec::new();
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let rotated = file_name.starts_with(&format!("{}.", name));
+        if file_name != name && !rotated {
+            continue;
+        }
+        let Ok(modified) = entry.metadata().and_then(|meta| meta.modified()) else {
+            continue;
+        };
+        if since.is_none_or(|since| modified >= since) {
+            files.push((modified, entry.path()));
+        }
+    }
+    files.sort();
+
+    let mut matches = Vec::new();
+    for (_, path) in files {
+        let content = read_log(&path)?;
+        for (index, line) in String::from_utf8_lossy(&content).lines().enumerate() {
+            if regex.is_match(line) {
+                matches.push(LogMatch {
+                    file: path.clone(),
+                    line_number: index + 1,
+                    line: line.to_string(),
+                });
+            }
+        }
+    }
+    Ok(matches)
+}
+
+/// Read a log or rotated file
+///
+/// # Arguments
+///
+/// * `path` - The file, ending with `.gz` if it is compressed
+///
+/// # Returns
+///
+/// The content of the file
+///
+/// # Errors
+///
+/// If the file could not be read or `gzip` failed
+fn read_log(path: &Path) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    if path.extension().is_none_or(|e| e != "gz") {
+        return Ok(std::fs::read(path)?);
+    }
+
+    let output = Command::new("gzip")
+        .arg("-dc")
+        .arg(path)
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|e| tr!("logs-gzip-failed", path = path.display(), error = e))?;
+    if !output.status.success() {
+        return Err(tr!(
+            "logs-gzip-failed",
+            path = path.display(),
+            error = output.status
+        )
+        .into());
+    }
+    Ok(output.stdout)
+}
+
+/// Get the directory of the server logs
+///
+/// # Returns
+///
+/// The directory `logs` in the steamserv directory
+///
+/// # Errors
+///
+/// If the steamserv directory could not be found
+pub fn logs_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(Config::data_dir()?.join("logs"))
+}
+
+/// Parse a size of the log settings
+///
+/// # Arguments
+///
+/// * `size` - The size, e.g. `50M`
+///
+/// # Returns
+///
+/// The size in bytes
+///
+/// # Errors
+///
+/// If the size is invalid
+fn parse_size(size: &str) -> Result<u64, Box<dyn std::error::Error>> {
+    fs::parse_size(size).ok_or_else(|| tr!("logs-size-invalid", size = size).into())
+}
+
+/// Copy a log to `<name>.log.<time>` and truncate it
+///
+/// The log is opened for appending by the server, so after the truncation the
+/// server writes to its start again. Lines written during the copy are lost.
+///
+/// # Arguments
+///
+/// * `path` - The log file
+/// * `compress` - Compress the copy with gzip
+///
+/// # Returns
+///
+/// Ok if the log was rotated
+///
+/// # Errors
+///
+/// If the log could not be copied or truncated
+fn rotate(path: &Path, compress: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut target = path.as_os_str().to_owned();
+    target.push(format!(".{}", Local::now().format("%Y%m%d-%H%M%S")));
+    let target = PathBuf::from(target);
+
+    std::fs::copy(path, &target)?;
+    OpenOptions::new().write(true).open(path)?.set_len(0)?;
+
+    if compress {
+        // Without gzip the rotated file is kept uncompressed
+        let _ = Command::new("gzip")
+            .arg("-f")
+            .arg(&target)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+    Ok(())
+}
+
+/// Select the rotated files to delete
+///
+/// # Arguments
+///
+/// * `logs` - The rotated files
+/// * `keep` - The number of files to keep per log
+/// * `max_age` - The maximum age of a file, zero keeps files of any age
+/// * `total_size` - The cap of all files together
+/// * `now` - The current time
+///
+/// # Returns
+///
+/// The files to delete
+fn expired(
+    mut logs: Vec<RotatedLog>,
+    keep: usize,
+    max_age: Duration,
+    total_size: Option<u64>,
+    now: SystemTime,
+) -> Vec<RotatedLog> {
+    logs.sort_by_key(|log| std::cmp::Reverse(log.modified));
+
+    let mut per_log: HashMap<String, usize> = HashMap::new();
+    let mut total = 0u64;
+    let mut over_cap = false;
+    let mut expired = Vec::new();
+    for log in logs {
+        let count = per_log.entry(log.log.clone()).or_default();
+        *count += 1;
+        let age = now.duration_since(log.modified).unwrap_or_default();
+        let too_old = !max_age.is_zero() && age > max_age;
+        // Once the cap is reached, all older files are deleted
+        over_cap |= total_size.is_some_and(|cap| total + log.size > cap);
+
+        if *count > keep || too_old || over_cap {
+            expired.push(log);
+        } else {
+            total += log.size;
+        }
+    }
+    expired
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expired_logs() {
+        let now = SystemTime::now();
+        let day = Duration::from_secs(24 * 60 * 60);
+        let log = |name: &str, days: u32, size: u64| RotatedLog {
+            path: PathBuf::from(format!("{}.{}", name, days)),
+            log: name.to_string(),
+            modified: now - day * days,
+            size,
+        };
+        let logs = vec![
+            log("cs2.log", 1, 10),
+            log("cs2.log", 2, 10),
+            log("cs2.log", 3, 10),
+            log("valheim.log", 1, 30),
+            log("valheim.log", 40, 1),
+        ];
+
+        let names = |logs: Vec<RotatedLog>| {
+            let mut names: Vec<String> = logs
+                .into_iter()
+                .map(|l| l.path.display().to_string())
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(
+            names(expired(logs.clone(), 2, day * 30, None, now)),
+            vec!["cs2.log.3", "valheim.log.40"]
+        );
+        // The cap keeps the newest files
+        assert_eq!(
+            names(expired(logs, 5, Duration::ZERO, Some(45), now)),
+            vec!["cs2.log.2", "cs2.log.3", "valheim.log.40"]
+        );
+    }
+}
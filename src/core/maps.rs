@@ -0,0 +1,197 @@
+use crate::{
+    core::{rcon, GameProfile, RconClient},
+    tr,
+    utils::{Config, MapRotation, ServerRef},
+};
+
+/// Get the next map of a rotation
+///
+/// # Arguments
+///
+/// * `rotation` - The map rotation
+///
+/// # Returns
+///
+/// The index and name of the map after the current one, wrapping around at the
+/// end, or `None` if the rotation has no maps
+pub fn next_map(rotation: &MapRotation) -> Option<(usize, &str)> {
+    if rotation.maps.is_empty() {
+        return None;
+    }
+    let next = (rotation.current + 1) % rotation.maps.len();
+    Some((next, rotation.maps[next].as_str()))
+}
+
+/// Add maps to a rotation, keeping the position of the current map
+///
+/// # Arguments
+///
+/// * `rotation` - The map rotation
+/// * `maps` - The names of the maps
+/// * `position` - The position to insert the maps at, starting at 1, `None` for the end
+pub fn add_maps(rotation: &mut MapRotation, maps: Vec<String>, position: Option<usize>) {
+    let index = position
+        .map_or(rotation.maps.len(), |p| p.saturating_sub(1))
+        .min(rotation.maps.len());
+    if index <= rotation.current && !rotation.maps.is_empty() {
+        rotation.current += maps.len();
+    }
+    rotation.maps.splice(index..index, maps);
+}
+
+/// Remove a map from a rotation, the map after it stays the next one
+///
+/// # Arguments
+///
+/// * `rotation` - The map rotation
+/// * `map` - The name of the map
+///
+/// # Returns
+///
+/// True if the rotation had the map
+pub fn remove_map(rotation: &mut MapRotation, map: &str) -> bool {
+    let Some(index) = rotation.maps.iter().position(|m| m == map) else {
+        return false;
+    };
+    rotation.maps.remove(index);
+    let len = rotation.maps.len();
+    if index < rotation.current {
+        rotation.current -= 1;
+    } else if index == rotation.current && len > 0 {
+        rotation.current = (index + len - 1) % len;
+    }
+    true
+}
+
+/// Get the map rotation of a server or instance for modification
+///
+/// Instances have their own rotation, they do not share the one of the server.
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+/// * `target` - The server or instance
+///
+/// # Returns
+///
+/// The map rotation, `None` if it was never set
+///
+/// # Errors
+///
+/// If the server or instance does not exist
+pub fn rotation_mut<'a>(
+    config: &'a mut Config,
+    target: &ServerRef,
+) -> Result<&'a mut Option<MapRotation>, Box<dyn std::error::Error>> {
+    config.resolve(target)?;
+    let server = config
+        .find_server_mut(&target.server)
+        .ok_or_else(|| tr!("server-not-installed", server = target.server))?;
+
+    match &target.instance {
+        Some(name) => {
+            let instance = server
+                .instances
+                .iter_mut()
+                .find(|i| &i.name == name)
+                .ok_or_else(|| {
+                    tr!(
+                        "instance-not-found",
+                        instance = name,
+                        server = target.server
+                    )
+                })?;
+            Ok(&mut instance.maps)
+        }
+        None => Ok(&mut server.maps),
+    }
+}
+
+/// Change a running server to the next map of its rotation over RCON
+///
+/// The message of the rotation is broadcast first. The new position is saved,
+/// so the rotation continues there after a restart of steamserv.
+///
+/// # Arguments
+///
+/// * `config` - The configuration, saved with the new position
+/// * `target` - The server or instance
+///
+/// # Returns
+///
+/// The map that was changed to
+///
+/// # Errors
+///
+/// If the server has no maps, RCON is not configured, the game has no map
+/// command, a command could not be sent or the config could not be saved
+pub fn rotate(
+    config: &mut Config,
+    target: &ServerRef,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let (server, instance) = config.resolve(target)?;
+    let rotation = instance
+        .map_or(&server.maps, |i| &i.maps)
+        .as_ref()
+        .ok_or_else(|| tr!("maps-none", target = target))?;
+    let (next, map) = next_map(rotation).ok_or_else(|| tr!("maps-none", target = target))?;
+    let map = map.to_string();
+
+    let rcon = server
+        .rcon_for(instance)
+        .ok_or_else(|| tr!("rcon-not-configured", target = target))?;
+    let template = rotation
+        .command
+        .clone()
+        .or_else(|| GameProfile::find(server.app_id).and_then(|p| p.map_command))
+        .ok_or_else(|| tr!("maps-no-command", target = target))?;
+
+    if let Some(message) = &rotation.message {
+        rcon::broadcast(server, instance, &message.replace("{map}", &map), None)?;
+    }
+    RconClient::connect(rcon)?.execute(&template.replace("{map}", &map))?;
+
+    if let Some(rotation) = rotation_mut(config, target)? {
+        rotation.current = next;
+    }
+    config.save()?;
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_map() {
+        let mut rotation = MapRotation {
+            maps: vec!["de_dust2".to_string(), "de_inferno".to_string()],
+            ..MapRotation::default()
+        };
+        assert_eq!(next_map(&rotation), Some((1, "de_inferno")));
+        rotation.current = 1;
+        assert_eq!(next_map(&rotation), Some((0, "de_dust2")));
+        // A position behind a removed map wraps around
+        rotation.current = 5;
+        assert_eq!(next_map(&rotation), Some((0, "de_dust2")));
+        rotation.maps.clear();
+        assert_eq!(next_map(&rotation), None);
+    }
+
+    #[test]
+    fn test_edit_rotation() {
+        let mut rotation = MapRotation::default();
+        add_maps(&mut rotation, vec!["a".to_string(), "c".to_string()], None);
+        rotation.current = 1;
+        add_maps(&mut rotation, vec!["b".to_string()], Some(2));
+        assert_eq!(rotation.maps, vec!["a", "b", "c"]);
+        assert_eq!(rotation.current, 2);
+
+        // Removing the current map keeps the map after it as the next one
+        assert!(remove_map(&mut rotation, "c"));
+        assert_eq!(next_map(&rotation), Some((0, "a")));
+        assert!(remove_map(&mut rotation, "a"));
+        assert_eq!(next_map(&rotation), Some((0, "b")));
+        assert!(!remove_map(&mut rotation, "x"));
+    }
+}
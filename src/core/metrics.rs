@@ -0,0 +1,287 @@
+use std::path::PathBuf;
+
+use chrono::{DateTime, TimeZone, Utc};
+use rusqlite::{params, Connection};
+
+use crate::{
+    core::{query, GameProfile, LaunchCommand, RconClient},
+    tr,
+    utils::{Config, InstalledServer, ServerInstance, ServerRef},
+};
+
+/// A single sample of the server metrics
+#[derive(Debug, Clone)]
+pub struct Sample {
+    pub timestamp: DateTime<Utc>,
+    pub players: u32,
+    pub max_players: u32,
+    pub bots: u32,
+    pub fps: Option<f64>,
+}
+
+/// Time series of the metrics of a server or instance, stored in its own SQLite database
+pub struct MetricsStore {
+    connection: Connection,
+}
+
+impl MetricsStore {
+    /// Open the metrics database of a server or instance
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The server or instance
+    ///
+    /// # Returns
+    ///
+    /// The metrics store
+    ///
+    /// # Errors
+    ///
+    /// If the database could not be opened or created
+    pub fn open(target: &ServerRef) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = Self::path(target)?;
+        std::fs::create_dir_all(path.parent().unwrap())?;
+
+        let connection = Connection::open(path)?;
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS samples (
+                timestamp INTEGER NOT NULL,
+                players INTEGER NOT NULL,
+                max_players INTEGER NOT NULL,
+                bots INTEGER NOT NULL,
+                fps REAL
+            );
+            CREATE INDEX IF NOT EXISTS samples_timestamp ON samples (timestamp);",
+        )?;
+
+        Ok(Self { connection })
+    }
+
+    /// Get the path to the metrics database of a server or instance
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The server or instance
+    ///
+    /// # Returns
+    ///
+    /// The path to the database
+    ///
+    /// # Errors
+    ///
+    /// If the steamserv directory could not be found
+    fn path(target: &ServerRef) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let file_name = target.to_string().replace('/', "@");
+        Ok(Config::data_dir()?
+            .join("metrics")
+            .join(format!("{}.sqlite", file_name)))
+    }
+
+    /// Store a sample
+    ///
+    /// # Arguments
+    ///
+    /// * `sample` - The sample to store
+    ///
+    /// # Returns
+    ///
+    /// Ok if the sample was stored
+    ///
+    /// # Errors
+    ///
+    /// If the sample could not be written
+    pub fn insert(&self, sample: &Sample) -> Result<(), Box<dyn std::error::Error>> {
+        self.connection.execute(
+            "INSERT INTO samples (timestamp, players, max_players, bots, fps)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                sample.timestamp.timestamp(),
+                sample.players,
+                sample.max_players,
+                sample.bots,
+                sample.fps
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Load the samples since a point in time
+    ///
+    /// # Arguments
+    ///
+    /// * `since` - The oldest sample to load
+    ///
+    /// # Returns
+    ///
+    /// The samples, oldest first
+    ///
+    /// # Errors
+    ///
+    /// If the samples could not be read
+    pub fn since(&self, since: DateTime<Utc>) -> Result<Vec<Sample>, Box<dyn std::error::Error>> {
+        let mut statement = self.connection.prepare(
+            "SELECT timestamp, players, max_players, bots, fps FROM samples
+             WHERE timestamp >= ?1 ORDER BY timestamp",
+        )?;
+
+        let samples = statement
+            .query_map(params![since.timestamp()], |row| {
+                Ok(Sample {
+                    timestamp: Utc
+                        .timestamp_opt(row.get(0)?, 0)
+                        .single()
+                        .unwrap_or_default(),
+                    players: row.get(1)?,
+                    max_players: row.get(2)?,
+                    bots: row.get(3)?,
+                    fps: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(samples)
+    }
+
+    /// Load the newest sample
+    ///
+    /// # Returns
+    ///
+    /// The newest sample, or `None` if no sample was stored yet
+    ///
+    /// # Errors
+    ///
+    /// If the samples could not be read
+    pub fn latest(&self) -> Result<Option<Sample>, Box<dyn std::error::Error>> {
+        let mut statement = self.connection.prepare(
+            "SELECT timestamp, players, max_players, bots, fps FROM samples
+             ORDER BY timestamp DESC LIMIT 1",
+        )?;
+
+        let mut samples = statement.query_map([], |row| {
+            Ok(Sample {
+                timestamp: Utc
+                    .timestamp_opt(row.get(0)?, 0)
+                    .single()
+                    .unwrap_or_default(),
+                players: row.get(1)?,
+                max_players: row.get(2)?,
+                bots: row.get(3)?,
+                fps: row.get(4)?,
+            })
+        })?;
+
+        Ok(samples.next().transpose()?)
+    }
+
+    /// Delete the samples older than a point in time
+    ///
+    /// # Arguments
+    ///
+    /// * `before` - The oldest sample to keep
+    ///
+    /// # Returns
+    ///
+    /// The number of deleted samples
+    ///
+    /// # Errors
+    ///
+    /// If the samples could not be deleted
+    pub fn prune(&self, before: DateTime<Utc>) -> Result<usize, Box<dyn std::error::Error>> {
+        Ok(self.connection.execute(
+            "DELETE FROM samples WHERE timestamp < ?1",
+            params![before.timestamp()],
+        )?)
+    }
+}
+
+/// Take a sample of a running server with an A2S query and, if configured, RCON
+///
+/// # Arguments
+///
+/// * `server` - The installed server
+/// * `instance` - The instance, or `None` for the server itself
+///
+/// # Returns
+///
+/// The sample
+///
+/// # Errors
+///
+/// If the server has no game profile or did not answer the query
+pub fn collect_sample(
+    server: &InstalledServer,
+    instance: Option<&ServerInstance>,
+) -> Result<Sample, Box<dyn std::error::Error>> {
+    let profile = GameProfile::find(server.app_id)
+        .ok_or_else(|| tr!("profile-not-found", app_id = server.app_id))?;
+    let port =
+        LaunchCommand::port(&profile, server, instance).ok_or_else(|| tr!("probe-no-port"))?;
+
+    let info = query::query_info("127.0.0.1", profile.query_port(port))?;
+
+    let fps = match (&profile.fps_command, server.rcon_for(instance)) {
+        (Some(command), Some(rcon)) => RconClient::connect(rcon)
+            .and_then(|mut client| client.execute(command))
+            .ok()
+            .and_then(|response| parse_fps(&response)),
+        _ => None,
+    };
+
+    Ok(Sample {
+        timestamp: Utc::now(),
+        players: u32::from(info.players.saturating_sub(info.bots)),
+        max_players: u32::from(info.max_players),
+        bots: u32::from(info.bots),
+        fps,
+    })
+}
+
+/// Parse the server frame rate from an RCON response
+///
+/// Supports tables with an `FPS` column (e.g. the `stats` command of Source
+/// servers) and values followed by `fps` (e.g. the `fps` command of Rust).
+///
+/// # Arguments
+///
+/// * `response` - The RCON response
+///
+/// # Returns
+///
+/// The frame rate if the response contains one
+fn parse_fps(response: &str) -> Option<f64> {
+    let lines: Vec<Vec<&str>> = response
+        .lines()
+        .map(|line| line.split_whitespace().collect())
+        .collect();
+    let from_table = lines.windows(2).find_map(|pair| {
+        let column = pair[0].iter().position(|c| c.eq_ignore_ascii_case("fps"))?;
+        pair[1].get(column)?.parse().ok()
+    });
+    if from_table.is_some() {
+        return from_table;
+    }
+
+    let words: Vec<&str> = response.split_whitespace().collect();
+    words.windows(2).find_map(|pair| {
+        if pair[1].eq_ignore_ascii_case("fps") {
+            pair[0].parse().ok()
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fps() {
+        let source =
+            "CPU   In_(KB/s)  Out_(KB/s)  Uptime  Map_changes  FPS      Players  Connects\n\
+                      10.00 0.00       0.00        12      1            64.02    3        4";
+        assert_eq!(parse_fps(source), Some(64.02));
+        assert_eq!(parse_fps("256 FPS"), Some(256.0));
+        assert_eq!(parse_fps("Unknown command"), None);
+    }
+}
@@ -1,3 +1,67 @@
+pub mod aliases;
+pub mod bench;
+pub mod bundle;
+pub mod cluster;
+pub mod deps;
+pub mod dyndns;
+pub mod executables;
+pub mod export;
+pub mod file_sync;
+pub mod game_config;
+pub mod jobs;
+pub mod launch;
+pub mod lgsm;
+pub mod lint;
+pub mod logs;
+pub mod maps;
+pub mod metrics;
+pub mod mods;
+pub mod nat;
+pub mod players;
+pub mod process;
+pub mod profile_sync;
+pub mod profile_test;
+pub mod profile_wizard;
+pub mod profiles;
+pub mod provision;
+pub mod query;
+pub mod rcon;
+pub mod readiness;
+pub mod snapshots;
+pub mod staging;
 pub mod steam;
+pub mod supervisor;
+pub mod update_report;
+pub mod uptime;
+pub mod webapi;
 
-pub use steam::SteamCMD;
+pub use cluster::Cluster;
+pub use deps::{Dependency, DependencyCheck, PackageManager};
+pub use dyndns::{DnsConfig, DnsState};
+pub use export::ExportFormat;
+pub use file_sync::FileSync;
+pub use game_config::GameConfig;
+pub use jobs::{Job, JobProgress, JobQueue, JobState, PlanStep};
+pub use launch::LaunchCommand;
+pub use lgsm::LgsmInstall;
+pub use lint::LintReport;
+pub use logs::{LogConfig, LogMatch, LogPrune};
+pub use metrics::MetricsStore;
+pub use mods::{ModManager, ModWarning};
+pub use nat::{Gateway, NatReport};
+pub use players::PlayersConfig;
+pub use process::{ProcessUsage, ServerProcess};
+pub use profile_sync::{ProfileRepository, ProfileSync};
+pub use profile_test::ProfileTest;
+pub use profile_wizard::ProfileWizard;
+pub use profiles::{GameProfile, ProfileRegistry};
+pub use provision::{Answers, AppEntry, NewServer, ServerTemplate};
+pub use rcon::RconClient;
+pub use readiness::ReadinessReport;
+pub use snapshots::{Snapshot, SnapshotConfig, Snapshots};
+pub use staging::StagedUpdate;
+pub use steam::{AppBuild, SteamCMD, UpdateCheck};
+pub use supervisor::Supervisor;
+pub use update_report::{ServerUpdate, UpdateOutcome, UpdateReport};
+pub use uptime::UptimeReport;
+pub use webapi::{CacheValidators, SteamWebApi};
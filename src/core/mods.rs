@@ -0,0 +1,540 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    core::{AppBuild, GameProfile, SteamCMD, SteamWebApi},
+    tr,
+    utils::{
+        pattern::matches_wildcard, run_with_output, steamcmd, Config, InstalledServer,
+        TimeoutConfig, Watchdog, WorkshopMod,
+    },
+};
+
+/// Manages the workshop mods of installed game servers
+pub struct ModManager;
+
+/// A workshop mod that is likely incompatible with a game build
+#[derive(Debug, Clone)]
+pub struct ModWarning {
+    /// The workshop item ID
+    pub id: u64,
+    /// The title of the workshop item
+    pub title: String,
+    /// Why the mod is likely incompatible
+    pub reason: String,
+}
+
+impl ModManager {
+    /// Add workshop mods to a game server
+    ///
+    /// # Arguments
+    ///
+    /// * `server_name` - The name of the game server
+    /// * `ids` - The workshop item IDs
+    ///
+    /// # Returns
+    ///
+    /// Ok if the mods were downloaded and linked successfully
+    ///
+    /// # Errors
+    ///
+    /// If the server does not exist, has no workshop support or the download failed
+    pub async fn add(server_name: &str, ids: &[u64]) -> Result<(), Box<dyn std::error::Error>> {
+        let mut config = Config::load()?;
+        config.ensure_online(&tr!("action-download-mods"))?;
+
+        let server = config
+            .find_server(server_name)
+            .ok_or_else(|| tr!("server-not-installed", server = server_name))?
+            .clone();
+        let profile = Self::workshop_profile(&server)?;
+
+        Self::download(&config, &server, &profile, ids)?;
+        let titles = Self::fetch_titles(&config, ids).await;
+
+        let server = config
+            .find_server_mut(server_name)
+            .ok_or_else(|| tr!("server-not-installed", server = server_name))?;
+        for id in ids {
+            let mut workshop_mod = match server.mods.iter().position(|m| m.id == *id) {
+                Some(index) => server.mods.remove(index),
+                None => WorkshopMod::new(*id),
+            };
+            if let Some((_, title)) = titles.iter().find(|(item, _)| item == id) {
+                workshop_mod.title = Some(title.clone());
+            }
+            workshop_mod.updated_at = Utc::now();
+
+            Self::remove_links(&workshop_mod.links)?;
+            workshop_mod.links = Self::apply_link_rules(server, &profile, *id)?;
+            println!(
+                "{}",
+                tr!("mods-installed", id = id, links = workshop_mod.links.len())
+            );
+            server.mods.push(workshop_mod);
+        }
+
+        config.save()?;
+
+        Ok(())
+    }
+
+    /// Remove workshop mods from a game server
+    ///
+    /// # Arguments
+    ///
+    /// * `server_name` - The name of the game server
+    /// * `ids` - The workshop item IDs
+    ///
+    /// # Returns
+    ///
+    /// Ok if the mods were removed successfully
+    ///
+    /// # Errors
+    ///
+    /// If the server does not exist or the content could not be removed
+    pub fn remove(server_name: &str, ids: &[u64]) -> Result<(), Box<dyn std::error::Error>> {
+        let mut config = Config::load()?;
+        let server = config
+            .find_server_mut(server_name)
+            .ok_or_else(|| tr!("server-not-installed", server = server_name))?;
+        let profile = Self::workshop_profile(server)?;
+
+        for id in ids {
+            let Some(index) = server.mods.iter().position(|m| m.id == *id) else {
+                println!("{}", tr!("mods-not-installed", id = id));
+                continue;
+            };
+            let workshop_mod = server.mods.remove(index);
+            Self::remove_links(&workshop_mod.links)?;
+
+            let content_dir = Self::content_dir(server, &profile, *id);
+            if content_dir.exists() {
+                std::fs::remove_dir_all(content_dir)?;
+            }
+            println!("{}", tr!("mods-removed", id = id));
+        }
+
+        config.save()?;
+
+        Ok(())
+    }
+
+    /// Update all workshop mods of a game server and relink their content
+    ///
+    /// # Arguments
+    ///
+    /// * `server_name` - The name of the game server
+    ///
+    /// # Returns
+    ///
+    /// Ok if the mods were updated successfully
+    ///
+    /// # Errors
+    ///
+    /// If the server does not exist or the download failed
+    pub async fn update(server_name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let config = Config::load()?;
+        let server = config
+            .find_server(server_name)
+            .ok_or_else(|| tr!("server-not-installed", server = server_name))?;
+        let ids: Vec<u64> = server.mods.iter().map(|m| m.id).collect();
+
+        if ids.is_empty() {
+            println!("{}", tr!("mods-none", server = server_name));
+            return Ok(());
+        }
+
+        Self::add(server_name, &ids).await
+    }
+
+    /// Check the workshop mods of a server against a game build
+    ///
+    /// A mod is likely incompatible if it was last updated before the build was
+    /// published, or if it declares game versions in its tags (e.g. `1.4`) and
+    /// another mod of the server declares a newer one.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration with the API key
+    /// * `server` - The installed server
+    /// * `build` - The game build to check against
+    ///
+    /// # Returns
+    ///
+    /// The mods that are likely incompatible
+    ///
+    /// # Errors
+    ///
+    /// If no API key is configured or the workshop details could not be fetched
+    pub async fn check_compatibility(
+        config: &Config,
+        server: &InstalledServer,
+        build: &AppBuild,
+    ) -> Result<Vec<ModWarning>, Box<dyn std::error::Error>> {
+        let ids: Vec<u64> = server.mods.iter().map(|m| m.id).collect();
+        let details = SteamWebApi::new(config)?
+            .get_published_file_details(&ids)
+            .await?;
+
+        let versions: Vec<Option<Vec<u32>>> = details
+            .iter()
+            .map(|d| d.tags.iter().filter_map(|t| parse_version(&t.tag)).max())
+            .collect();
+        let newest = versions.iter().flatten().max();
+
+        let mut warnings = Vec::new();
+        for (details, version) in details.iter().zip(&versions) {
+            let Ok(id) = details.publishedfileid.parse() else {
+                continue;
+            };
+            let title = server
+                .mods
+                .iter()
+                .find(|m| m.id == id)
+                .and_then(|m| m.title.clone())
+                .unwrap_or_else(|| details.title.clone());
+
+            let reason = match (version, newest) {
+                (Some(version), Some(newest)) if version < newest => Some(tr!(
+                    "mods-older-version",
+                    version = format_version(version),
+                    newest = format_version(newest)
+                )),
+                _ => DateTime::from_timestamp(details.time_updated, 0)
+                    .filter(|updated| *updated < build.time)
+                    .map(|updated| {
+                        tr!(
+                            "mods-older-than-build",
+                            date = updated.format("%Y-%m-%d").to_string()
+                        )
+                    }),
+            };
+            if let Some(reason) = reason {
+                warnings.push(ModWarning { id, title, reason });
+            }
+        }
+
+        Ok(warnings)
+    }
+
+    /// Get the directory a workshop item is downloaded to
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The installed server
+    /// * `profile` - The game profile of the server
+    /// * `id` - The workshop item ID
+    ///
+    /// # Returns
+    ///
+    /// The content directory of the workshop item
+    pub fn content_dir(server: &InstalledServer, profile: &GameProfile, id: u64) -> PathBuf {
+        server
+            .install_path
+            .join("steamapps/workshop/content")
+            .join(profile.workshop_app_id.unwrap_or(server.app_id).to_string())
+            .join(id.to_string())
+    }
+
+    /// Apply the link rules of a game profile to a workshop item
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The installed server
+    /// * `profile` - The game profile with the link rules
+    /// * `id` - The workshop item ID
+    ///
+    /// # Returns
+    ///
+    /// The created links
+    ///
+    /// # Errors
+    ///
+    /// If a link could not be created
+    pub fn apply_link_rules(
+        server: &InstalledServer,
+        profile: &GameProfile,
+        id: u64,
+    ) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
+        let content_dir = Self::content_dir(server, profile, id);
+        let mut links = Vec::new();
+
+        for rule in &profile.links {
+            let target = server
+                .install_path
+                .join(rule.target.replace("{id}", &id.to_string()));
+            match &rule.pattern {
+                Some(pattern) => {
+                    for file in Self::find_files(&content_dir, pattern)? {
+                        let file_name = file.file_name().ok_or("Invalid file name")?;
+                        let link = target.join(file_name);
+                        Self::create_link(&file, &link)?;
+                        links.push(link);
+                    }
+                }
+                None => {
+                    Self::create_link(&content_dir, &target)?;
+                    links.push(target);
+                }
+            }
+        }
+
+        Ok(links)
+    }
+
+    /// Get the game profile of a server and make sure it supports workshop content
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The installed server
+    ///
+    /// # Returns
+    ///
+    /// The game profile of the server
+    ///
+    /// # Errors
+    ///
+    /// If the server has no game profile or the game has no workshop support
+    fn workshop_profile(
+        server: &InstalledServer,
+    ) -> Result<GameProfile, Box<dyn std::error::Error>> {
+        GameProfile::find(server.app_id)
+            .filter(|p| p.workshop_app_id.is_some())
+            .ok_or_else(|| tr!("mods-unsupported", server = server.name).into())
+    }
+
+    /// Download workshop items with SteamCMD
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    /// * `server` - The installed server
+    /// * `profile` - The game profile of the server
+    /// * `ids` - The workshop item IDs
+    ///
+    /// # Returns
+    ///
+    /// Ok if the items were downloaded successfully
+    ///
+    /// # Errors
+    ///
+    /// If SteamCMD cannot download workshop items, could not be run or failed
+    fn download(
+        config: &Config,
+        server: &InstalledServer,
+        profile: &GameProfile,
+        ids: &[u64],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if !SteamCMD::capabilities(config).workshop_download {
+            return Err(tr!("mods-steamcmd-unsupported").into());
+        }
+        let (username, password) = SteamCMD::login_for(server)?;
+        let workshop_app_id = profile.workshop_app_id.unwrap_or(server.app_id);
+
+        let (before_login, after_login) =
+            steamcmd::extra_commands(config.steamcmd_args.iter().chain(&server.steamcmd_args));
+
+        let mut command = SteamCMD::command(config);
+        command
+            .arg(format!(
+                "+force_install_dir {}",
+                server.install_path.display()
+            ))
+            .args(before_login)
+            .arg(format!("+login {} {}", username, password))
+            .args(after_login);
+        for id in ids {
+            command.arg(format!(
+                "+workshop_download_item {} {} validate",
+                workshop_app_id, id
+            ));
+        }
+        let mut child = command
+            .arg("+quit")
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()?;
+
+        let watchdog = Watchdog::start(&child, TimeoutConfig::limit(config.timeouts.install));
+        let error = run_with_output(&mut child)?;
+
+        let status = child.wait()?;
+        watchdog.finish("steamcmd +workshop_download_item")?;
+        if let Some(error) = error {
+            return Err(error.into());
+        }
+        if !status.success() {
+            return Err(tr!("mods-download-failed").into());
+        }
+
+        Ok(())
+    }
+
+    /// Fetch the titles of workshop items, if an API key is configured
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    /// * `ids` - The workshop item IDs
+    ///
+    /// # Returns
+    ///
+    /// The titles of the items that could be fetched
+    async fn fetch_titles(config: &Config, ids: &[u64]) -> Vec<(u64, String)> {
+        if config.api_key.is_none() {
+            return Vec::new();
+        }
+
+        let Ok(api) = SteamWebApi::new(config) else {
+            return Vec::new();
+        };
+
+        api.get_published_file_details(ids)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|details| {
+                let id = details.publishedfileid.parse().ok()?;
+                Some((id, details.title))
+            })
+            .collect()
+    }
+
+    /// Find all files of a directory matching a pattern
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory to search recursively
+    /// * `pattern` - The wildcard pattern for the file names
+    ///
+    /// # Returns
+    ///
+    /// The matching files
+    ///
+    /// # Errors
+    ///
+    /// If the directory could not be read
+    fn find_files(dir: &Path, pattern: &str) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
+        let mut files = Vec::new();
+        if !dir.is_dir() {
+            return Ok(files);
+        }
+
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                files.extend(Self::find_files(&path, pattern)?);
+            } else if path
+                .file_name()
+                .is_some_and(|name| matches_wildcard(pattern, &name.to_string_lossy()))
+            {
+                files.push(path);
+            }
+        }
+
+        Ok(files)
+    }
+
+    /// Create a symbolic link, replacing an existing link
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The file or directory to link to
+    /// * `link` - The path of the link
+    ///
+    /// # Returns
+    ///
+    /// Ok if the link was created
+    ///
+    /// # Errors
+    ///
+    /// If a regular file exists at the link path or the link could not be created
+    fn create_link(source: &Path, link: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if let Ok(metadata) = std::fs::symlink_metadata(link) {
+            if !metadata.file_type().is_symlink() {
+                return Err(tr!("mods-link-exists", path = link.display()).into());
+            }
+            std::fs::remove_file(link)?;
+        }
+
+        if let Some(parent) = link.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::os::unix::fs::symlink(source, link)?;
+
+        Ok(())
+    }
+
+    /// Remove links created for a workshop item
+    ///
+    /// # Arguments
+    ///
+    /// * `links` - The links to remove
+    ///
+    /// # Returns
+    ///
+    /// Ok if the links were removed
+    ///
+    /// # Errors
+    ///
+    /// If a link could not be removed
+    fn remove_links(links: &[PathBuf]) -> Result<(), Box<dyn std::error::Error>> {
+        for link in links {
+            if std::fs::symlink_metadata(link).is_ok_and(|m| m.file_type().is_symlink()) {
+                std::fs::remove_file(link)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse a workshop tag that declares a game version
+///
+/// # Arguments
+///
+/// * `tag` - The tag, e.g. `1.4` or `v1.0.2`
+///
+/// # Returns
+///
+/// The parts of the version, or `None` if the tag is no version
+fn parse_version(tag: &str) -> Option<Vec<u32>> {
+    let tag = tag.trim().trim_start_matches(['v', 'V']);
+    if !tag.contains('.') {
+        return None;
+    }
+    tag.split('.').map(|part| part.parse().ok()).collect()
+}
+
+/// Format the parts of a version
+///
+/// # Arguments
+///
+/// * `version` - The parts of the version
+///
+/// # Returns
+///
+/// The version, e.g. `1.4`
+fn format_version(version: &[u32]) -> String {
+    version
+        .iter()
+        .map(|part| part.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("1.4"), Some(vec![1, 4]));
+        assert_eq!(parse_version("v1.0.2"), Some(vec![1, 0, 2]));
+        assert_eq!(parse_version("Mod"), None);
+        assert_eq!(parse_version("2024"), None);
+        assert!(parse_version("1.10") > parse_version("1.9"));
+        assert_eq!(format_version(&[1, 4]), "1.4");
+    }
+}
@@ -0,0 +1,675 @@
+use std::{
+    fmt,
+    net::{IpAddr, Ipv4Addr, TcpListener, UdpSocket},
+    time::Duration,
+};
+
+use serde::Deserialize;
+
+use crate::{
+    core::{GameProfile, LaunchCommand},
+    tr,
+    utils::{Config, InstalledServer, ServerInstance, TimeoutConfig},
+};
+
+/// Default echoip service that reports the public address and probes TCP ports
+pub const DEFAULT_PROBE_URL: &str = "https://ifconfig.co";
+
+/// Multicast address of SSDP, used to discover UPnP gateways
+const SSDP_ADDRESS: &str = "239.255.255.250:1900";
+
+/// Time to wait for a UPnP gateway to answer the discovery
+const SSDP_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// UPnP services that manage port mappings, in order of preference
+const WAN_SERVICES: &[&str] = &[
+    "urn:schemas-upnp-org:service:WANIPConnection:2",
+    "urn:schemas-upnp-org:service:WANIPConnection:1",
+    "urn:schemas-upnp-org:service:WANPPPConnection:1",
+];
+
+/// Transport protocol of a port
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Udp,
+    Tcp,
+}
+
+impl fmt::Display for Protocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Protocol::Udp => write!(f, "UDP"),
+            Protocol::Tcp => write!(f, "TCP"),
+        }
+    }
+}
+
+/// Why a port is or is not reachable from the outside
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Diagnosis {
+    /// The probe service could connect to the port
+    Reachable,
+    /// The router forwards the port, UDP can not be probed from the outside
+    Forwarded,
+    /// The host has a public address, UDP can not be probed from the outside
+    Direct,
+    /// Nothing listens on the port, the server is not running
+    NotListening,
+    /// The router does not forward the port to this host
+    Nat,
+    /// The router has no public address, forwarding on it can not help
+    DoubleNat,
+    /// The port is forwarded or there is no NAT, but connections are blocked
+    Firewall,
+    /// Not enough information, e.g. no UPnP gateway was found
+    Unknown,
+}
+
+/// Result of the check of a single port
+pub struct PortCheck {
+    pub port: u16,
+    pub protocol: Protocol,
+    pub listening: bool,
+    /// Result of the probe from the outside, `None` if it was not probed
+    pub reachable: Option<bool>,
+    /// The host the gateway forwards the port to, `None` without mapping or gateway
+    pub mapped_to: Option<Ipv4Addr>,
+    pub diagnosis: Diagnosis,
+}
+
+/// A router that supports UPnP port mappings (Internet Gateway Device)
+pub struct Gateway {
+    client: reqwest::Client,
+    control_url: String,
+    service: String,
+}
+
+/// Response of the port probe of an echoip service
+#[derive(Debug, Deserialize)]
+struct ProbeResponse {
+    reachable: bool,
+}
+
+/// Reachability of the ports of a server or instance from the internet
+pub struct NatReport {
+    pub local_ip: Option<Ipv4Addr>,
+    pub public_ip: Option<IpAddr>,
+    /// The public address of the UPnP gateway, if one was found
+    pub gateway_ip: Option<Ipv4Addr>,
+    pub ports: Vec<PortCheck>,
+}
+
+impl NatReport {
+    /// Check the game, extra and query ports of a server or instance
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration with the network settings
+    /// * `server` - The installed server
+    /// * `instance` - The instance to check, or `None` for the server itself
+    /// * `gateway` - The UPnP gateway, if one was found
+    ///
+    /// # Returns
+    ///
+    /// The result for every port and protocol
+    ///
+    /// # Errors
+    ///
+    /// If no game profile exists for the server or network access is disabled
+    pub async fn check(
+        config: &Config,
+        server: &InstalledServer,
+        instance: Option<&ServerInstance>,
+        gateway: Option<&Gateway>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        config.ensure_online(&tr!("action-nat-check"))?;
+        let profile = GameProfile::find(server.app_id)
+            .ok_or_else(|| tr!("profile-not-found", app_id = server.app_id))?;
+        let client = config
+            .network
+            .http_client(TimeoutConfig::limit(config.timeouts.network))?;
+        let probe_url = probe_url(config);
+
+        let public_ip = public_ip(&client, probe_url).await;
+        let gateway_ip = match gateway {
+            Some(gateway) => gateway.external_ip().await.ok(),
+            None => None,
+        };
+        let mut report = Self {
+            local_ip: local_ip(),
+            public_ip,
+            gateway_ip,
+            ports: Vec::new(),
+        };
+
+        for port in ports(&profile, server, instance) {
+            let listening: Vec<Protocol> = [Protocol::Udp, Protocol::Tcp]
+                .into_iter()
+                .filter(|protocol| is_listening(port, *protocol))
+                .collect();
+            let protocols = if listening.is_empty() {
+                vec![Protocol::Udp, Protocol::Tcp]
+            } else {
+                listening.clone()
+            };
+
+            for protocol in protocols {
+                let reachable = if protocol == Protocol::Tcp && !listening.is_empty() {
+                    probe(&client, probe_url, port).await
+                } else {
+                    None
+                };
+                let mapped_to = match gateway {
+                    Some(gateway) => gateway.mapping(port, protocol).await,
+                    None => None,
+                };
+                let mut check = PortCheck {
+                    port,
+                    protocol,
+                    listening: listening.contains(&protocol),
+                    reachable,
+                    mapped_to,
+                    diagnosis: Diagnosis::Unknown,
+                };
+                check.diagnosis = report.diagnose(&check, gateway.is_some());
+                report.ports.push(check);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Find out why a port is or is not reachable
+    ///
+    /// # Arguments
+    ///
+    /// * `check` - The results of the checks of the port
+    /// * `has_gateway` - Whether a UPnP gateway was found
+    ///
+    /// # Returns
+    ///
+    /// The most likely cause
+    fn diagnose(&self, check: &PortCheck, has_gateway: bool) -> Diagnosis {
+        let behind_nat = match (self.local_ip, self.public_ip) {
+            (Some(local), Some(public)) => IpAddr::V4(local) != public,
+            _ => true,
+        };
+        let double_nat = self.gateway_ip.is_some_and(|gateway| {
+            is_private(gateway)
+                || self
+                    .public_ip
+                    .is_some_and(|public| public != IpAddr::V4(gateway))
+        });
+        let mapped_here = check.mapped_to.is_some() && check.mapped_to == self.local_ip;
+
+        if !check.listening {
+            Diagnosis::NotListening
+        } else if check.reachable == Some(true) {
+            Diagnosis::Reachable
+        } else if behind_nat && double_nat {
+            Diagnosis::DoubleNat
+        } else if check.reachable == Some(false) && (!behind_nat || mapped_here) {
+            Diagnosis::Firewall
+        } else if !behind_nat {
+            Diagnosis::Direct
+        } else if mapped_here {
+            Diagnosis::Forwarded
+        } else if has_gateway {
+            Diagnosis::Nat
+        } else {
+            Diagnosis::Unknown
+        }
+    }
+}
+
+impl fmt::Display for NatReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unknown = || tr!("nat-unknown");
+        writeln!(
+            f,
+            "{:<20} {}",
+            tr!("nat-local-ip"),
+            self.local_ip
+                .map(|ip| ip.to_string())
+                .unwrap_or_else(unknown)
+        )?;
+        writeln!(
+            f,
+            "{:<20} {}",
+            tr!("nat-public-ip"),
+            self.public_ip
+                .map(|ip| ip.to_string())
+                .unwrap_or_else(unknown)
+        )?;
+        write!(
+            f,
+            "{:<20} {}",
+            tr!("nat-gateway"),
+            self.gateway_ip
+                .map(|ip| ip.to_string())
+                .unwrap_or_else(|| tr!("nat-no-gateway"))
+        )?;
+
+        for check in &self.ports {
+            let (status, detail) = match check.diagnosis {
+                Diagnosis::Reachable => ("OK", tr!("nat-reachable")),
+                Diagnosis::Forwarded => ("OK", tr!("nat-forwarded")),
+                Diagnosis::Direct => ("OK", tr!("nat-direct")),
+                Diagnosis::NotListening => ("WARN", tr!("nat-not-listening")),
+                Diagnosis::Nat => ("FAIL", tr!("nat-not-forwarded")),
+                Diagnosis::DoubleNat => ("FAIL", tr!("nat-double-nat")),
+                Diagnosis::Firewall => ("FAIL", tr!("nat-firewall")),
+                Diagnosis::Unknown => ("WARN", tr!("nat-unknown-cause")),
+            };
+            write!(
+                f,
+                "\n[{:<4}] {:<20} {}",
+                status,
+                format!("{}/{}", check.port, check.protocol),
+                detail
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Gateway {
+    /// Find a UPnP gateway in the local network
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration with the network settings
+    ///
+    /// # Returns
+    ///
+    /// The gateway, or `None` if no router answered or it has no WAN service
+    pub async fn discover(config: &Config) -> Option<Self> {
+        let socket = tokio::net::UdpSocket::bind("0.0.0.0:0").await.ok()?;
+        let request = format!(
+            "M-SEARCH * HTTP/1.1\r\nHOST: {}\r\nST: urn:schemas-upnp-org:device:InternetGatewayDevice:1\r\nMAN: \"ssdp:discover\"\r\nMX: 2\r\n\r\n",
+            SSDP_ADDRESS
+        );
+        socket
+            .send_to(request.as_bytes(), SSDP_ADDRESS)
+            .await
+            .ok()?;
+
+        let mut buffer = [0u8; 2048];
+        let (length, _) = tokio::time::timeout(SSDP_TIMEOUT, socket.recv_from(&mut buffer))
+            .await
+            .ok()?
+            .ok()?;
+        let answer = String::from_utf8_lossy(&buffer[..length]);
+        let location = answer.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.eq_ignore_ascii_case("location")
+                .then(|| value.trim().to_string())
+        })?;
+
+        // The router is in the local network, the proxy must not be used for it
+        let client = reqwest::Client::builder()
+            .no_proxy()
+            .timeout(TimeoutConfig::limit(config.timeouts.network).unwrap_or(SSDP_TIMEOUT))
+            .build()
+            .ok()?;
+        let description = client.get(&location).send().await.ok()?.text().await.ok()?;
+        let (service, control_url) = control_url(&description)?;
+        let control_url = reqwest::Url::parse(&location)
+            .ok()?
+            .join(&control_url)
+            .ok()?
+            .to_string();
+
+        Some(Self {
+            client,
+            control_url,
+            service,
+        })
+    }
+
+    /// Get the public address of the gateway
+    ///
+    /// # Returns
+    ///
+    /// The address of the WAN side of the router
+    ///
+    /// # Errors
+    ///
+    /// If the gateway did not answer or the address is invalid
+    pub async fn external_ip(&self) -> Result<Ipv4Addr, Box<dyn std::error::Error>> {
+        let response = self.call("GetExternalIPAddress", &[]).await?;
+        let ip = xml_value(&response, "NewExternalIPAddress")
+            .ok_or_else(|| tr!("nat-upnp-invalid-response"))?;
+        Ok(ip.parse()?)
+    }
+
+    /// Get the host a port is forwarded to
+    ///
+    /// # Arguments
+    ///
+    /// * `port` - The external port
+    /// * `protocol` - The protocol of the port
+    ///
+    /// # Returns
+    ///
+    /// The internal address of the mapping, or `None` if the port is not mapped
+    pub async fn mapping(&self, port: u16, protocol: Protocol) -> Option<Ipv4Addr> {
+        let response = self
+            .call(
+                "GetSpecificPortMappingEntry",
+                &[
+                    ("NewRemoteHost", String::new()),
+                    ("NewExternalPort", port.to_string()),
+                    ("NewProtocol", protocol.to_string()),
+                ],
+            )
+            .await
+            .ok()?;
+        xml_value(&response, "NewInternalClient")?.parse().ok()
+    }
+
+    /// Forward a port to a host without expiry
+    ///
+    /// # Arguments
+    ///
+    /// * `port` - The port, used outside and inside
+    /// * `protocol` - The protocol of the port
+    /// * `host` - The host to forward the port to
+    /// * `description` - The description shown in the router
+    ///
+    /// # Returns
+    ///
+    /// Ok if the router added the mapping
+    ///
+    /// # Errors
+    ///
+    /// If the router refused the mapping, e.g. because another host has it
+    pub async fn add_mapping(
+        &self,
+        port: u16,
+        protocol: Protocol,
+        host: Ipv4Addr,
+        description: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.call(
+            "AddPortMapping",
+            &[
+                ("NewRemoteHost", String::new()),
+                ("NewExternalPort", port.to_string()),
+                ("NewProtocol", protocol.to_string()),
+                ("NewInternalPort", port.to_string()),
+                ("NewInternalClient", host.to_string()),
+                ("NewEnabled", "1".to_string()),
+                ("NewPortMappingDescription", description.to_string()),
+                ("NewLeaseDuration", "0".to_string()),
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Call an action of the WAN service of the gateway
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The name of the action
+    /// * `arguments` - The arguments of the action in order
+    ///
+    /// # Returns
+    ///
+    /// The SOAP response
+    ///
+    /// # Errors
+    ///
+    /// If the request failed or the gateway answered with a fault
+    async fn call(
+        &self,
+        action: &str,
+        arguments: &[(&str, String)],
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let arguments: String = arguments
+            .iter()
+            .map(|(name, value)| format!("<{0}>{1}</{0}>", name, value))
+            .collect();
+        let body = format!(
+            "<?xml version=\"1.0\"?>\
+             <s:Envelope xmlns:s=\"http://schemas.xmlsoap.org/soap/envelope/\" \
+             s:encodingStyle=\"http://schemas.xmlsoap.org/soap/encoding/\">\
+             <s:Body><u:{action} xmlns:u=\"{service}\">{arguments}</u:{action}></s:Body>\
+             </s:Envelope>",
+            action = action,
+            service = self.service,
+            arguments = arguments
+        );
+
+        let response = self
+            .client
+            .post(&self.control_url)
+            .header("Content-Type", "text/xml; charset=\"utf-8\"")
+            .header("SOAPAction", format!("\"{}#{}\"", self.service, action))
+            .body(body)
+            .send()
+            .await?;
+        let status = response.status();
+        let text = response.text().await?;
+        if !status.is_success() {
+            let error = xml_value(&text, "errorDescription").unwrap_or_else(|| status.to_string());
+            return Err(tr!("nat-upnp-failed", action = action, error = error).into());
+        }
+        Ok(text)
+    }
+}
+
+/// Get the base URL of the echoip service
+///
+/// # Arguments
+///
+/// * `config` - The configuration with the network settings
+///
+/// # Returns
+///
+/// The configured URL without trailing slash, or the default service
+pub fn probe_url(config: &Config) -> &str {
+    config
+        .network
+        .probe_url
+        .as_deref()
+        .unwrap_or(DEFAULT_PROBE_URL)
+        .trim_end_matches('/')
+}
+
+/// Get the ports a server or instance uses
+///
+/// # Arguments
+///
+/// * `profile` - The game profile
+/// * `server` - The installed server
+/// * `instance` - The instance
+///
+/// # Returns
+///
+/// The game port, the extra ports and the query port, without duplicates
+pub fn ports(
+    profile: &GameProfile,
+    server: &InstalledServer,
+    instance: Option<&ServerInstance>,
+) -> Vec<u16> {
+    let Some(port) = LaunchCommand::port(profile, server, instance) else {
+        return Vec::new();
+    };
+    let mut ports = vec![port];
+    for offset in &profile.extra_port_offsets {
+        ports.extend(port.checked_add(*offset));
+    }
+    ports.push(profile.query_port(port));
+    ports.sort();
+    ports.dedup();
+    ports
+}
+
+/// Get the address of this host in the local network
+///
+/// # Returns
+///
+/// The address of the interface with the default route
+pub fn local_ip() -> Option<Ipv4Addr> {
+    // Connecting a UDP socket only selects the route, no packet is sent
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("192.0.2.1:9").ok()?;
+    match socket.local_addr().ok()?.ip() {
+        IpAddr::V4(ip) => Some(ip),
+        IpAddr::V6(_) => None,
+    }
+}
+
+/// Check if a process listens on a port
+///
+/// # Arguments
+///
+/// * `port` - The port
+/// * `protocol` - The protocol
+///
+/// # Returns
+///
+/// True if the port can not be bound
+fn is_listening(port: u16, protocol: Protocol) -> bool {
+    match protocol {
+        Protocol::Udp => UdpSocket::bind(("0.0.0.0", port)).is_err(),
+        Protocol::Tcp => TcpListener::bind(("0.0.0.0", port)).is_err(),
+    }
+}
+
+/// Get the public address of this host from an echoip service
+///
+/// # Arguments
+///
+/// * `client` - The HTTP client
+/// * `probe_url` - The base URL of the echoip service
+///
+/// # Returns
+///
+/// The address the service sees requests from, `None` if the request failed
+pub async fn public_ip(client: &reqwest::Client, probe_url: &str) -> Option<IpAddr> {
+    let response = client.get(format!("{}/ip", probe_url)).send().await.ok()?;
+    response.text().await.ok()?.trim().parse().ok()
+}
+
+/// Let the probe service connect to a TCP port of the public address
+///
+/// # Arguments
+///
+/// * `client` - The HTTP client
+/// * `probe_url` - The base URL of the echoip service
+/// * `port` - The port
+///
+/// # Returns
+///
+/// Whether the port is reachable, `None` if the probe failed
+async fn probe(client: &reqwest::Client, probe_url: &str, port: u16) -> Option<bool> {
+    let response = client
+        .get(format!("{}/port/{}", probe_url, port))
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .ok()?;
+    Some(response.json::<ProbeResponse>().await.ok()?.reachable)
+}
+
+/// Check if an address is private or in the carrier-grade NAT range
+///
+/// # Arguments
+///
+/// * `ip` - The address
+///
+/// # Returns
+///
+/// True if the address is not routed on the internet
+fn is_private(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    ip.is_private() || ip.is_loopback() || (a == 100 && (64..128).contains(&b))
+}
+
+/// Find the WAN service in the device description of a gateway
+///
+/// # Arguments
+///
+/// * `description` - The XML device description
+///
+/// # Returns
+///
+/// The service type and its control URL, which may be relative
+fn control_url(description: &str) -> Option<(String, String)> {
+    WAN_SERVICES.iter().find_map(|service| {
+        let start = description.find(&format!(">{}<", service))?;
+        let control = xml_value(&description[start..], "controlURL")?;
+        Some((service.to_string(), control))
+    })
+}
+
+/// Get the text of the first element with a name
+///
+/// # Arguments
+///
+/// * `xml` - The XML document
+/// * `tag` - The name of the element without namespace prefix
+///
+/// # Returns
+///
+/// The trimmed text of the element
+fn xml_value(xml: &str, tag: &str) -> Option<String> {
+    let start = xml.find(&format!("<{}>", tag))? + tag.len() + 2;
+    let end = xml[start..].find(&format!("</{}>", tag))? + start;
+    Some(xml[start..end].trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_control_url() {
+        let description =
+            "<service><serviceType>urn:schemas-upnp-org:service:Layer3Forwarding:1</serviceType>\
+            <controlURL>/l3f</controlURL></service>\
+            <service><serviceType>urn:schemas-upnp-org:service:WANIPConnection:1</serviceType>\
+            <controlURL>/upnp/control/wanip</controlURL></service>";
+        assert_eq!(
+            control_url(description),
+            Some((
+                WAN_SERVICES[1].to_string(),
+                "/upnp/control/wanip".to_string()
+            ))
+        );
+        assert!(is_private("100.72.1.1".parse().unwrap()));
+        assert!(!is_private("203.0.113.7".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_diagnose() {
+        let report = NatReport {
+            local_ip: Some(Ipv4Addr::new(192, 168, 1, 10)),
+            public_ip: Some("203.0.113.7".parse().unwrap()),
+            gateway_ip: Some("203.0.113.7".parse().unwrap()),
+            ports: Vec::new(),
+        };
+        let mut check = PortCheck {
+            port: 2456,
+            protocol: Protocol::Udp,
+            listening: true,
+            reachable: None,
+            mapped_to: None,
+            diagnosis: Diagnosis::Unknown,
+        };
+        assert_eq!(report.diagnose(&check, true), Diagnosis::Nat);
+        check.mapped_to = report.local_ip;
+        assert_eq!(report.diagnose(&check, true), Diagnosis::Forwarded);
+        check.reachable = Some(false);
+        assert_eq!(report.diagnose(&check, true), Diagnosis::Firewall);
+
+        let report = NatReport {
+            gateway_ip: Some("100.72.1.1".parse().unwrap()),
+            ..report
+        };
+        assert_eq!(report.diagnose(&check, true), Diagnosis::DoubleNat);
+    }
+}
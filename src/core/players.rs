@@ -0,0 +1,676 @@
+use std::{collections::BTreeSet, fmt, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::{
+        profiles::PlayerList, query, GameProfile, LaunchCommand, RconClient, ServerProcess,
+        SteamWebApi,
+    },
+    tr,
+    utils::{
+        fs,
+        steamid::{self, SteamId},
+        Config, InstalledServer, ServerInstance, ServerRef,
+    },
+};
+
+/// Settings of the player lists shared by all servers
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct PlayersConfig {
+    /// Apply every ban to all servers that support bans
+    pub shared_bans: bool,
+    /// The central ban list, applied to new servers with `steamserv players sync`
+    pub bans: BTreeSet<u64>,
+}
+
+/// The player lists of a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListKind {
+    Bans,
+    Whitelist,
+    Admins,
+}
+
+impl ListKind {
+    /// Get the list of a game profile
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - The game profile
+    ///
+    /// # Returns
+    ///
+    /// The list, or `None` if the game does not support it
+    fn of(self, profile: &GameProfile) -> Option<&PlayerList> {
+        match self {
+            Self::Bans => profile.bans.as_ref(),
+            Self::Whitelist => profile.whitelist.as_ref(),
+            Self::Admins => profile.admins.as_ref(),
+        }
+    }
+}
+
+impl fmt::Display for ListKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bans => write!(f, "{}", tr!("players-list-bans")),
+            Self::Whitelist => write!(f, "{}", tr!("players-list-whitelist")),
+            Self::Admins => write!(f, "{}", tr!("players-list-admins")),
+        }
+    }
+}
+
+/// What a change of a player list did
+#[derive(Debug, Default)]
+pub struct ListChange {
+    /// The file that was changed
+    pub file: Option<PathBuf>,
+    /// True if the change was sent to the running server over RCON
+    pub reloaded: bool,
+}
+
+/// A player connected to a running server
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OnlinePlayer {
+    pub name: String,
+    /// Seconds since the player connected
+    pub duration: Option<u64>,
+    /// Round trip time in milliseconds, only known from RCON
+    pub ping: Option<u32>,
+}
+
+/// Resolve a player given as SteamID, profile URL or custom URL name
+///
+/// SteamIDs and profile URLs with a SteamID64 are converted locally, custom URLs
+/// are resolved with the Steam Web API.
+///
+/// # Arguments
+///
+/// * `config` - The configuration with the API key
+/// * `value` - The SteamID in any form, a profile URL or a custom URL name
+///
+/// # Returns
+///
+/// The SteamID of the player
+///
+/// # Errors
+///
+/// If the value is invalid, no profile uses the custom URL or the API request failed
+pub async fn resolve_id(
+    config: &Config,
+    value: &str,
+) -> Result<SteamId, Box<dyn std::error::Error>> {
+    if let Some(id) = SteamId::parse(value) {
+        return Ok(id);
+    }
+    let name = steamid::vanity_name(value).ok_or_else(|| tr!("steamid-invalid", id = value))?;
+
+    config.ensure_online(&tr!("action-resolve-steamid"))?;
+    SteamWebApi::new(config)?
+        .resolve_vanity_url(name)
+        .await?
+        .and_then(|id| SteamId::parse(&id.to_string()))
+        .ok_or_else(|| tr!("steamid-not-found", name = name).into())
+}
+
+/// Get the players on a list of a server or instance
+///
+/// # Arguments
+///
+/// * `server` - The installed server
+/// * `instance` - The instance, or `None` for the server itself
+/// * `kind` - The list to read
+///
+/// # Returns
+///
+/// The players in the order of the list file
+///
+/// # Errors
+///
+/// If the game has no such list, the list is only kept by the running server or
+/// the file could not be read
+pub fn entries(
+    server: &InstalledServer,
+    instance: Option<&ServerInstance>,
+    kind: ListKind,
+) -> Result<Vec<SteamId>, Box<dyn std::error::Error>> {
+    let target = match instance {
+        Some(instance) => ServerRef::parse(&format!("{}/{}", server.name, instance.name)),
+        None => ServerRef::parse(&server.name),
+    };
+    let (profile, list) = list_of(server, &target, kind)?;
+    let template = list.file.as_ref().ok_or_else(|| {
+        tr!(
+            "players-list-unavailable",
+            target = target,
+            list = kind.to_string()
+        )
+    })?;
+    let path =
+        LaunchCommand::render_path(template, &profile, server, instance).ok_or_else(|| {
+            tr!(
+                "players-file-unknown",
+                target = target,
+                list = kind.to_string(),
+                file = template
+            )
+        })?;
+
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let prefix = line_prefix(&list.line);
+    let mut ids = Vec::new();
+    for line in content.lines() {
+        if !line.trim_start().starts_with(prefix) {
+            continue;
+        }
+        if let Some(id) = words(line).find_map(SteamId::parse) {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+    }
+
+    Ok(ids)
+}
+
+/// Add a player to or remove a player from a list of a server or instance
+///
+/// The list file of the game profile is edited and, if the server is running
+/// with RCON, the change is applied to the running server.
+///
+/// # Arguments
+///
+/// * `server` - The installed server
+/// * `instance` - The instance, or `None` for the server itself
+/// * `kind` - The list to change
+/// * `id` - The player
+/// * `add` - True to add the player, false to remove them
+/// * `reload` - Send the change to the running server over RCON
+///
+/// # Returns
+///
+/// What was changed
+///
+/// # Errors
+///
+/// If the game has no such list, the file could not be written, the RCON commands
+/// failed or the list can only be changed over RCON and the server is not running
+pub fn change(
+    server: &InstalledServer,
+    instance: Option<&ServerInstance>,
+    kind: ListKind,
+    id: SteamId,
+    add: bool,
+    reload: bool,
+) -> Result<ListChange, Box<dyn std::error::Error>> {
+    let target = match instance {
+        Some(instance) => ServerRef::parse(&format!("{}/{}", server.name, instance.name)),
+        None => ServerRef::parse(&server.name),
+    };
+    let (profile, list) = list_of(server, &target, kind)?;
+
+    let mut change = ListChange::default();
+    if let Some(template) = &list.file {
+        let path =
+            LaunchCommand::render_path(template, &profile, server, instance).ok_or_else(|| {
+                tr!(
+                    "players-file-unknown",
+                    target = target,
+                    list = kind.to_string(),
+                    file = template
+                )
+            })?;
+        if edit_file(&path, &list.line, id, add)? {
+            change.file = Some(path);
+        }
+    }
+
+    let commands = if add { &list.add } else { &list.remove };
+    let rcon = server.rcon_for(instance);
+    let running = ServerProcess::new(&target)?.is_running();
+    if reload && running && !commands.is_empty() {
+        if let Some(rcon) = rcon {
+            let mut client = RconClient::connect(rcon)?;
+            for command in commands {
+                client.execute(&id.render(command))?;
+            }
+            change.reloaded = true;
+        }
+    }
+
+    if list.file.is_none() && !change.reloaded {
+        return Err(tr!(
+            "players-rcon-only",
+            target = target,
+            list = kind.to_string()
+        )
+        .into());
+    }
+
+    Ok(change)
+}
+
+/// Get the players connected to a running server or instance
+///
+/// The server is asked with an A2S_PLAYER query first. If the query fails or the
+/// game leaves the names empty, the `players_command` of the game profile is sent
+/// over RCON instead.
+///
+/// # Arguments
+///
+/// * `server` - The installed server
+/// * `instance` - The instance, or `None` for the server itself
+///
+/// # Returns
+///
+/// The connected players
+///
+/// # Errors
+///
+/// If the server is not running, has no game profile or port, or neither the
+/// query nor RCON answered
+pub fn online(
+    server: &InstalledServer,
+    instance: Option<&ServerInstance>,
+) -> Result<Vec<OnlinePlayer>, Box<dyn std::error::Error>> {
+    let target = match instance {
+        Some(instance) => ServerRef::parse(&format!("{}/{}", server.name, instance.name)),
+        None => ServerRef::parse(&server.name),
+    };
+    if !ServerProcess::new(&target)?.is_running() {
+        return Err(tr!("players-not-running", target = target).into());
+    }
+    let profile = GameProfile::find(server.app_id)
+        .ok_or_else(|| tr!("profile-not-found", app_id = server.app_id))?;
+    let port =
+        LaunchCommand::port(&profile, server, instance).ok_or_else(|| tr!("probe-no-port"))?;
+
+    let queried = query::query_players("127.0.0.1", profile.query_port(port));
+    let rcon = profile
+        .players_command
+        .as_deref()
+        .zip(server.rcon_for(instance));
+    let players = match (queried, rcon) {
+        (Ok(players), Some(_)) if players.iter().any(|p| p.name.is_empty()) => None,
+        (Ok(players), _) => Some(players),
+        (Err(_), Some(_)) => None,
+        (Err(e), None) => return Err(e),
+    };
+
+    match (players, rcon) {
+        (Some(players), _) => Ok(players
+            .into_iter()
+            .map(|player| OnlinePlayer {
+                name: player.name,
+                duration: Some(player.duration.max(0.0) as u64),
+                ping: None,
+            })
+            .collect()),
+        (None, Some((command, rcon))) => {
+            let response = RconClient::connect(rcon)?.execute(command)?;
+            Ok(parse_player_list(&response))
+        }
+        (None, None) => Ok(Vec::new()),
+    }
+}
+
+/// Parse the players from the response of an RCON player list command
+///
+/// Supports the `status` tables of Source games and Rust with quoted names,
+/// numbered lists like `0. Name, 76561198000000000` (ARK, 7 Days to Die), the
+/// `name,playeruid,steamid` CSV of Palworld and `-Name` lines (Project Zomboid).
+///
+/// # Arguments
+///
+/// * `response` - The RCON response
+///
+/// # Returns
+///
+/// The players in the order of the response
+fn parse_player_list(response: &str) -> Vec<OnlinePlayer> {
+    let mut lines = response.lines().map(str::trim).filter(|l| !l.is_empty());
+    let csv = response
+        .trim_start()
+        .to_ascii_lowercase()
+        .starts_with("name,playeruid");
+    if csv {
+        lines.next();
+    }
+
+    lines
+        .filter_map(|line| {
+            if csv {
+                let mut fields = line.rsplitn(3, ',');
+                let name = fields.nth(2)?;
+                return Some(OnlinePlayer {
+                    name: name.to_string(),
+                    duration: None,
+                    ping: None,
+                });
+            }
+            if let Some(player) = parse_status_line(line) {
+                return Some(player);
+            }
+            if let Some((number, rest)) = line.split_once(". ") {
+                if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) {
+                    return parse_numbered_line(rest);
+                }
+            }
+            let name = line.strip_prefix('-')?.trim();
+            (!name.is_empty()).then(|| OnlinePlayer {
+                name: name.to_string(),
+                duration: None,
+                ping: None,
+            })
+        })
+        .collect()
+}
+
+/// Parse a line of a `status` table with a quoted player name
+///
+/// The ping is the number right after the connection time (Source) or right
+/// before it (Rust). Bots and lines without a connection time, like a quoted
+/// host name, are left out.
+///
+/// # Arguments
+///
+/// * `line` - The line of the table
+///
+/// # Returns
+///
+/// The player, or `None` if the line is no player of the table
+fn parse_status_line(line: &str) -> Option<OnlinePlayer> {
+    let quote = line.find(['"', '\''])?;
+    let mark = &line[quote..=quote];
+    let length = line[quote + 1..].find(mark)?;
+    let name = &line[quote + 1..quote + 1 + length];
+    let tokens: Vec<&str> = line[..quote]
+        .split_whitespace()
+        .chain(line[quote + length + 2..].split_whitespace())
+        .collect();
+    if name.is_empty() || tokens.contains(&"BOT") {
+        return None;
+    }
+
+    let (index, duration) = tokens
+        .iter()
+        .enumerate()
+        .find_map(|(index, token)| Some((index, parse_connected(token)?)))?;
+    let ping = tokens
+        .get(index + 1)
+        .and_then(|t| t.parse().ok())
+        .or_else(|| tokens.get(index.checked_sub(1)?)?.parse().ok());
+    Some(OnlinePlayer {
+        name: name.to_string(),
+        duration: Some(duration),
+        ping,
+    })
+}
+
+/// Parse a numbered player line without the number
+///
+/// # Arguments
+///
+/// * `line` - The fields of the player separated by commas
+///
+/// # Returns
+///
+/// The player, or `None` if the line has no name
+fn parse_numbered_line(line: &str) -> Option<OnlinePlayer> {
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    let name = fields
+        .iter()
+        .find(|f| !f.is_empty() && !f.contains('=') && !f.chars().all(|c| c.is_ascii_digit()))?;
+    Some(OnlinePlayer {
+        name: name.to_string(),
+        duration: None,
+        ping: fields
+            .iter()
+            .find_map(|f| f.strip_prefix("ping=")?.parse().ok()),
+    })
+}
+
+/// Parse the connection time of a player
+///
+/// # Arguments
+///
+/// * `value` - The time as `mm:ss`, `h:mm:ss` or seconds like `1234.5s`
+///
+/// # Returns
+///
+/// The seconds since the player connected, or `None` if the value is no time
+fn parse_connected(value: &str) -> Option<u64> {
+    if let Some(seconds) = value.strip_suffix('s') {
+        return seconds.parse::<f64>().ok().map(|s| s.max(0.0) as u64);
+    }
+    let parts: Vec<u64> = value
+        .split(':')
+        .map(|part| part.parse().ok())
+        .collect::<Option<_>>()?;
+    (2..=3)
+        .contains(&parts.len())
+        .then(|| parts.iter().fold(0, |total, part| total * 60 + part))
+}
+
+/// Get the game profile of a server and one of its lists
+///
+/// # Arguments
+///
+/// * `server` - The installed server
+/// * `target` - The reference to the server or instance, for the error messages
+/// * `kind` - The list
+///
+/// # Returns
+///
+/// The game profile and the list
+///
+/// # Errors
+///
+/// If the game has no profile or does not support the list
+fn list_of(
+    server: &InstalledServer,
+    target: &ServerRef,
+    kind: ListKind,
+) -> Result<(GameProfile, PlayerList), Box<dyn std::error::Error>> {
+    let profile = GameProfile::find(server.app_id)
+        .ok_or_else(|| tr!("profile-not-found", app_id = server.app_id))?;
+    let list = kind.of(&profile).cloned().ok_or_else(|| {
+        tr!(
+            "players-not-supported",
+            target = target,
+            list = kind.to_string()
+        )
+    })?;
+    Ok((profile, list))
+}
+
+/// Add or remove the line of a player in a list file
+///
+/// # Arguments
+///
+/// * `path` - The list file, it is created if it does not exist
+/// * `line` - The line template of the list
+/// * `id` - The player, lines like `line` mentioning them in any form belong to them
+/// * `add` - True to add the line, false to remove all lines of the player
+///
+/// # Returns
+///
+/// True if the file was changed
+///
+/// # Errors
+///
+/// If the file could not be read or written
+fn edit_file(
+    path: &std::path::Path,
+    line: &str,
+    id: SteamId,
+    add: bool,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+
+    let Some(lines) = edit_lines(&content, line, id, add) else {
+        return Ok(false);
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    fs::write_atomic(path, lines.as_bytes())?;
+    Ok(true)
+}
+
+/// Add or remove the line of a player
+///
+/// # Arguments
+///
+/// * `content` - The content of the list file
+/// * `line` - The line template of the list
+/// * `id` - The player
+/// * `add` - True to add the line, false to remove all lines of the player
+///
+/// # Returns
+///
+/// The new content, or `None` if nothing changed
+fn edit_lines(content: &str, line: &str, id: SteamId, add: bool) -> Option<String> {
+    // Files like the users.cfg of Rust keep several kinds of entries, only the
+    // lines that start like the line of the player belong to the list
+    let prefix = line_prefix(line);
+    let line = id.render(line);
+    let mentions =
+        |l: &str| l.trim_start().starts_with(prefix) && words(l).any(|word| id.matches(word));
+    let present = content.lines().any(mentions);
+
+    let lines: Vec<&str> = match (add, present) {
+        (true, false) => content
+            .lines()
+            .chain(std::iter::once(line.as_str()))
+            .collect(),
+        (false, true) => content.lines().filter(|l| !mentions(l)).collect(),
+        _ => return None,
+    };
+
+    let mut content = lines.join("\n");
+    if !content.is_empty() {
+        content.push('\n');
+    }
+    Some(content)
+}
+
+/// Get the fixed start of a line template
+///
+/// # Arguments
+///
+/// * `line` - The line template
+///
+/// # Returns
+///
+/// The text before the first SteamID, without surrounding whitespace
+fn line_prefix(line: &str) -> &str {
+    line.split('{').next().unwrap_or_default().trim()
+}
+
+/// Split a line of a list file into the words that can be a SteamID
+///
+/// # Arguments
+///
+/// * `line` - The line
+///
+/// # Returns
+///
+/// The words, split at quotes, spaces and other separators
+fn words(line: &str) -> impl Iterator<Item = &str> {
+    line.split(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | ':' | '[' | ']')))
+        .filter(|word| !word.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_lines() {
+        let id = SteamId::parse("76561197960287930").unwrap();
+        let content = "banid 0 STEAM_0:1:1\n";
+
+        let added = edit_lines(content, "banid 0 {steamid2}", id, true).unwrap();
+        assert_eq!(added, "banid 0 STEAM_0:1:1\nbanid 0 STEAM_0:0:11101\n");
+        assert_eq!(edit_lines(&added, "banid 0 {steamid2}", id, true), None);
+
+        let removed = edit_lines(&added, "banid 0 {steamid2}", id, false).unwrap();
+        assert_eq!(removed, content);
+        assert_eq!(edit_lines(content, "banid 0 {steamid2}", id, false), None);
+
+        // Only the lines of the list count, the owner stays an owner
+        let users = "ownerid 76561197960287930 \"\" \"\"\n";
+        let line = "moderatorid {steamid64} \"\" \"\"";
+        let added = edit_lines(users, line, id, true).unwrap();
+        assert_eq!(
+            added,
+            "ownerid 76561197960287930 \"\" \"\"\nmoderatorid 76561197960287930 \"\" \"\"\n"
+        );
+        assert_eq!(edit_lines(&added, line, id, false).unwrap(), users);
+        assert_eq!(
+            edit_lines(
+                "\"STEAM_0:0:11101\" \"99:z\"\n",
+                "\"{steamid2}\" \"99:z\"",
+                id,
+                false
+            ),
+            Some(String::new())
+        );
+    }
+
+    #[test]
+    fn test_parse_player_list() {
+        let player = |name: &str, duration, ping| OnlinePlayer {
+            name: name.to_string(),
+            duration,
+            ping,
+        };
+
+        let source = "hostname: \"My Server\"\n\
+            # userid name uniqueid connected ping loss state adr\n\
+            #      2 \"Alice Smith\" [U:1:1234] 1:02:03 45 0 active 10.0.0.2:27005\n\
+            #      3 \"Bot\" BOT active\n";
+        assert_eq!(
+            parse_player_list(source),
+            [player("Alice Smith", Some(3723), Some(45))]
+        );
+
+        let cs2 = "  id     time ping loss      state   rate adr name\n\
+            \x20 2    12:34   38    0     active 786432 10.0.0.2:27005 'Bob'";
+        assert_eq!(parse_player_list(cs2), [player("Bob", Some(754), Some(38))]);
+
+        let rust = "id name ping connected addr owner violation kicks\n\
+            76561198000000000 \"Carol\" 61 95.5s 10.0.0.3:5678 0.0 0";
+        assert_eq!(
+            parse_player_list(rust),
+            [player("Carol", Some(95), Some(61))]
+        );
+
+        let ark = "0. Dave, 76561198000000001\n1. Eve, 76561198000000002";
+        assert_eq!(
+            parse_player_list(ark),
+            [player("Dave", None, None), player("Eve", None, None)]
+        );
+
+        let days = "0. id=171, Frank, pos=(1.0, 2.0, 3.0), steamid=76561198000000003, ping=30\n\
+            Total of 1 in the game";
+        assert_eq!(parse_player_list(days), [player("Frank", None, Some(30))]);
+
+        let palworld = "name,playeruid,steamid\nGrace,123456,76561198000000004";
+        assert_eq!(parse_player_list(palworld), [player("Grace", None, None)]);
+
+        let zomboid = "Players connected (1): \n-Heidi";
+        assert_eq!(parse_player_list(zomboid), [player("Heidi", None, None)]);
+    }
+}
@@ -0,0 +1,590 @@
+use std::{
+    fs::OpenOptions,
+    os::unix::process::CommandExt,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    core::{
+        profiles::StopSequence, rcon::RconClient, supervisor, GameProfile, LaunchCommand,
+        ReadinessReport, StagedUpdate,
+    },
+    tr,
+    utils::{
+        events::{self, Event},
+        history, Config, HistoryEvent, InstalledServer, ServerInstance, ServerRef,
+    },
+};
+
+/// Interval to check if a process has exited
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Time to wait after the fallback signal before the process is killed
+const FALLBACK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A game server or instance running in the background
+///
+/// The PID of the process is stored in `run/<name>.pid` and its output is
+/// appended to `logs/<name>.log` in the steamserv directory. A process that
+/// exited without being stopped leaves its PID in `run/<name>.exited` for the
+/// supervisor.
+pub struct ServerProcess {
+    pub target: ServerRef,
+    pub pid_file: PathBuf,
+    pub exit_file: PathBuf,
+    pub log_file: PathBuf,
+}
+
+/// CPU time and memory of a running process, read from `/proc`
+#[derive(Debug, Clone, Copy)]
+pub struct ProcessUsage {
+    /// The CPU time used in user and kernel mode since the start
+    pub cpu_time: Duration,
+    /// The resident memory in bytes
+    pub memory: u64,
+}
+
+impl ServerProcess {
+    /// Create the process handle of a server or instance
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The server or instance
+    ///
+    /// # Returns
+    ///
+    /// The process handle
+    ///
+    /// # Errors
+    ///
+    /// If the steamserv directory could not be found
+    pub fn new(target: &ServerRef) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self::in_dir(target, &Config::data_dir()?))
+    }
+
+    /// Create the process handle of a server or instance in a steamserv directory
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The server or instance
+    /// * `dir` - The steamserv directory
+    ///
+    /// # Returns
+    ///
+    /// The process handle
+    fn in_dir(target: &ServerRef, dir: &Path) -> Self {
+        let file_name = target.to_string().replace('/', "@");
+
+        Self {
+            target: target.clone(),
+            pid_file: dir.join("run").join(format!("{}.pid", file_name)),
+            exit_file: dir.join("run").join(format!("{}.exited", file_name)),
+            log_file: dir.join("logs").join(format!("{}.log", file_name)),
+        }
+    }
+
+    /// Get the PID of the running process
+    ///
+    /// A PID file of a process that is gone means the server exited without
+    /// being stopped, the exit is recorded in the history.
+    ///
+    /// # Returns
+    ///
+    /// The PID, or `None` if the server is not running
+    pub fn pid(&self) -> Option<u32> {
+        let pid = std::fs::read_to_string(&self.pid_file)
+            .ok()?
+            .trim()
+            .parse()
+            .ok()?;
+        if is_alive(pid) {
+            Some(pid)
+        } else {
+            if self.mark_exited(pid) {
+                let target = self.target.to_string();
+                let _ = history::record(&HistoryEvent::new(
+                    &target,
+                    "server-exited",
+                    &pid.to_string(),
+                ));
+            }
+            None
+        }
+    }
+
+    /// Remove the PID file of a process that is gone and remember its exit
+    ///
+    /// # Arguments
+    ///
+    /// * `pid` - The PID of the process
+    ///
+    /// # Returns
+    ///
+    /// True if the exit was noticed by this call
+    fn mark_exited(&self, pid: u32) -> bool {
+        if std::fs::remove_file(&self.pid_file).is_err() {
+            return false;
+        }
+        let _ = std::fs::write(&self.exit_file, pid.to_string());
+        true
+    }
+
+    /// Take the exit of a process that ended without being stopped
+    ///
+    /// A server stopped with `stop` removes its PID file and has no exit. The
+    /// exit is only returned once.
+    ///
+    /// # Returns
+    ///
+    /// The PID of the exited process, or `None` if the server is running, was
+    /// stopped or its exit was already taken
+    pub fn take_exit(&self) -> Option<u32> {
+        if self.pid().is_some() {
+            return None;
+        }
+        let pid = std::fs::read_to_string(&self.exit_file)
+            .ok()?
+            .trim()
+            .parse()
+            .ok();
+        let _ = std::fs::remove_file(&self.exit_file);
+        pid
+    }
+
+    /// Get the CPU time and memory of the running process
+    ///
+    /// # Returns
+    ///
+    /// The usage, or `None` if the server is not running or `/proc` could not be read
+    pub fn usage(&self) -> Option<ProcessUsage> {
+        let pid = self.pid()?;
+        let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+        let statm = std::fs::read_to_string(format!("/proc/{}/statm", pid)).ok()?;
+        let pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+
+        // SAFETY: sysconf has no memory safety requirements
+        let (ticks, page_size) = unsafe {
+            (
+                libc::sysconf(libc::_SC_CLK_TCK),
+                libc::sysconf(libc::_SC_PAGESIZE),
+            )
+        };
+        let ticks = u64::try_from(ticks).ok().filter(|t| *t > 0)?;
+
+        Some(ProcessUsage {
+            cpu_time: Duration::from_secs_f64(cpu_ticks(&stat)? as f64 / ticks as f64),
+            memory: pages * u64::try_from(page_size).ok()?,
+        })
+    }
+
+    /// Check if the server is running
+    ///
+    /// # Returns
+    ///
+    /// True if the process is running
+    pub fn is_running(&self) -> bool {
+        self.pid().is_some()
+    }
+
+    /// Count the players connected to the server
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The installed server
+    /// * `instance` - The instance, or `None` for the server itself
+    ///
+    /// # Returns
+    ///
+    /// The number of players without bots, or `None` if the server is not
+    /// running or could not be queried
+    pub fn players(
+        &self,
+        server: &InstalledServer,
+        instance: Option<&ServerInstance>,
+    ) -> Option<u32> {
+        if !self.is_running() {
+            return None;
+        }
+        supervisor::player_count(server, instance)
+    }
+
+    /// Start the server in the background after the pre-start checks passed
+    ///
+    /// A staged update is applied first if no other process of the server runs.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    /// * `server` - The installed server
+    /// * `instance` - The instance to start, or `None` to start the server itself
+    ///
+    /// # Returns
+    ///
+    /// The PID of the started process
+    ///
+    /// # Errors
+    ///
+    /// If the server is already running, is not ready or could not be started
+    pub fn start(
+        &self,
+        config: &Config,
+        server: &InstalledServer,
+        instance: Option<&ServerInstance>,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        if let Some(pid) = self.pid() {
+            return Err(tr!("process-already-running", target = self.target, pid = pid).into());
+        }
+
+        StagedUpdate::apply(server)?;
+
+        let report = ReadinessReport::check(config, server, instance)?;
+        if !report.is_ready() {
+            return Err(format!(
+                "{}\n{}",
+                tr!("readiness-failed", target = self.target),
+                report
+            )
+            .into());
+        }
+
+        let command = LaunchCommand::build(config, server, instance)?;
+
+        std::fs::create_dir_all(self.pid_file.parent().unwrap())?;
+        std::fs::create_dir_all(self.log_file.parent().unwrap())?;
+        let log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.log_file)?;
+
+        // The server gets its own process group, so a Ctrl+C in the terminal does
+        // not reach it and signals can be sent to all of its child processes.
+        let child = Command::new(&command.program)
+            .args(&command.args)
+            .envs(&command.env)
+            .current_dir(&command.working_dir)
+            .stdin(Stdio::null())
+            .stdout(log.try_clone()?)
+            .stderr(log)
+            .process_group(0)
+            .spawn()?;
+
+        std::fs::write(&self.pid_file, child.id().to_string())?;
+        let _ = std::fs::remove_file(&self.exit_file);
+        history::record(&HistoryEvent::new(
+            &self.target.to_string(),
+            "server-started",
+            &child.id().to_string(),
+        ))?;
+        events::publish(Event::ServerStarted {
+            target: self.target.to_string(),
+            pid: child.id(),
+            log: self.log_file.clone(),
+        });
+
+        Ok(child.id())
+    }
+
+    /// Stop the server with the stop sequence of its game profile
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The installed server
+    /// * `instance` - The instance to stop, or `None` to stop the server itself
+    ///
+    /// # Returns
+    ///
+    /// True if the server was running and has been stopped
+    ///
+    /// # Errors
+    ///
+    /// If a signal could not be sent or the process did not exit
+    pub fn stop(
+        &self,
+        server: &InstalledServer,
+        instance: Option<&ServerInstance>,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        let Some(pid) = self.pid() else {
+            return Ok(false);
+        };
+
+        let sequence = GameProfile::find(server.app_id)
+            .map(|profile| profile.stop)
+            .unwrap_or_default();
+
+        self.run_stop_sequence(pid, &sequence, server, instance)?;
+        let _ = std::fs::remove_file(&self.pid_file);
+        history::record(&HistoryEvent::new(
+            &self.target.to_string(),
+            "server-stopped",
+            &pid.to_string(),
+        ))?;
+        events::publish(Event::ServerStopped {
+            target: self.target.to_string(),
+        });
+
+        Ok(true)
+    }
+
+    /// Run a stop sequence until the process has exited
+    ///
+    /// # Arguments
+    ///
+    /// * `pid` - The PID of the process
+    /// * `sequence` - The stop sequence
+    /// * `server` - The installed server
+    /// * `instance` - The instance to stop
+    ///
+    /// # Returns
+    ///
+    /// Ok if the process has exited
+    ///
+    /// # Errors
+    ///
+    /// If a signal could not be sent or the process did not exit
+    fn run_stop_sequence(
+        &self,
+        pid: u32,
+        sequence: &StopSequence,
+        server: &InstalledServer,
+        instance: Option<&ServerInstance>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut signal = sequence.signal.clone();
+
+        if !sequence.commands.is_empty() {
+            match self.send_commands(&sequence.commands, server, instance) {
+                Ok(()) => {}
+                Err(e) => {
+                    println!("{}", tr!("stop-rcon-failed", error = e.to_string()));
+                    signal.get_or_insert_with(|| "TERM".to_string());
+                }
+            }
+        }
+
+        if let Some(signal) = &signal {
+            println!("{}", tr!("stop-signal", signal = signal));
+            send_signal(pid, signal)?;
+        }
+
+        println!(
+            "{}",
+            tr!(
+                "stop-waiting",
+                target = self.target,
+                seconds = sequence.timeout
+            )
+        );
+        if wait_for_exit(pid, Duration::from_secs(sequence.timeout)) {
+            return Ok(());
+        }
+
+        println!(
+            "{}",
+            tr!("stop-fallback", signal = sequence.fallback_signal)
+        );
+        send_signal(pid, &sequence.fallback_signal)?;
+        if wait_for_exit(pid, FALLBACK_TIMEOUT) {
+            return Ok(());
+        }
+
+        send_signal(pid, "KILL")?;
+        if wait_for_exit(pid, FALLBACK_TIMEOUT) {
+            return Ok(());
+        }
+
+        Err(tr!("stop-failed", target = self.target, pid = pid).into())
+    }
+
+    /// Send the RCON commands of a stop sequence
+    ///
+    /// # Arguments
+    ///
+    /// * `commands` - The RCON commands
+    /// * `server` - The installed server
+    /// * `instance` - The instance to stop
+    ///
+    /// # Returns
+    ///
+    /// Ok if all commands were sent
+    ///
+    /// # Errors
+    ///
+    /// If RCON is not configured or a command could not be sent
+    fn send_commands(
+        &self,
+        commands: &[String],
+        server: &InstalledServer,
+        instance: Option<&ServerInstance>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let rcon = server
+            .rcon_for(instance)
+            .ok_or_else(|| tr!("rcon-not-configured", target = self.target))?;
+
+        let mut client = RconClient::connect(rcon)?;
+        for command in commands {
+            println!("{}", tr!("stop-rcon-command", command = command));
+            client.execute(command)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Check if a process is alive
+///
+/// # Arguments
+///
+/// * `pid` - The PID of the process
+///
+/// # Returns
+///
+/// True if the process exists and is not a zombie
+pub fn is_alive(pid: u32) -> bool {
+    // SAFETY: signal 0 only checks if the process exists
+    if unsafe { libc::kill(pid as libc::pid_t, 0) } != 0 {
+        return false;
+    }
+
+    match std::fs::read_to_string(format!("/proc/{}/stat", pid)) {
+        Ok(stat) => stat
+            .rsplit_once(')')
+            .and_then(|(_, rest)| rest.trim_start().chars().next())
+            .is_some_and(|state| state != 'Z'),
+        Err(_) => true,
+    }
+}
+
+/// Get the CPU time of a process from its `/proc/<pid>/stat` line
+///
+/// # Arguments
+///
+/// * `stat` - The content of the stat file
+///
+/// # Returns
+///
+/// The user and kernel time in clock ticks, or `None` if the line could not be parsed
+fn cpu_ticks(stat: &str) -> Option<u64> {
+    // The command name may contain spaces, so the fields are counted after its closing parenthesis
+    let mut fields = stat.rsplit_once(')')?.1.split_whitespace().skip(11);
+    let user: u64 = fields.next()?.parse().ok()?;
+    let kernel: u64 = fields.next()?.parse().ok()?;
+    Some(user + kernel)
+}
+
+/// Wait until a process has exited
+///
+/// # Arguments
+///
+/// * `pid` - The PID of the process
+/// * `timeout` - The maximum time to wait
+///
+/// # Returns
+///
+/// True if the process has exited before the timeout
+fn wait_for_exit(pid: u32, timeout: Duration) -> bool {
+    let start = Instant::now();
+    while is_alive(pid) {
+        if start.elapsed() >= timeout {
+            return false;
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+    true
+}
+
+/// Send a signal to a process and its process group
+///
+/// # Arguments
+///
+/// * `pid` - The PID of the process
+/// * `signal` - The name of the signal, e.g. `TERM` or `SIGINT`
+///
+/// # Returns
+///
+/// Ok if the signal was sent
+///
+/// # Errors
+///
+/// If the signal is unknown or could not be sent
+pub fn send_signal(pid: u32, signal: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let number = signal_number(signal).ok_or_else(|| tr!("signal-unknown", signal = signal))?;
+    let pid = pid as libc::pid_t;
+
+    // SAFETY: kill has no memory safety requirements
+    unsafe {
+        if libc::kill(-pid, number) != 0 && libc::kill(pid, number) != 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Get the number of a signal by its name
+///
+/// # Arguments
+///
+/// * `name` - The name of the signal with or without the `SIG` prefix
+///
+/// # Returns
+///
+/// The signal number if the signal is supported
+fn signal_number(name: &str) -> Option<libc::c_int> {
+    let name = name.to_uppercase();
+    match name.strip_prefix("SIG").unwrap_or(&name) {
+        "INT" => Some(libc::SIGINT),
+        "TERM" => Some(libc::SIGTERM),
+        "KILL" => Some(libc::SIGKILL),
+        "HUP" => Some(libc::SIGHUP),
+        "QUIT" => Some(libc::SIGQUIT),
+        "USR1" => Some(libc::SIGUSR1),
+        "USR2" => Some(libc::SIGUSR2),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signal_number() {
+        assert_eq!(signal_number("INT"), Some(libc::SIGINT));
+        assert_eq!(signal_number("sigterm"), Some(libc::SIGTERM));
+        assert_eq!(signal_number("STOP"), None);
+    }
+
+    #[test]
+    fn test_take_exit_of_dead_process() {
+        let dir = std::env::temp_dir().join(format!("steamserv-exit-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("run")).unwrap();
+        let process = ServerProcess::in_dir(&ServerRef::parse("valheim/pvp"), &dir);
+
+        let mut child = Command::new("true").spawn().unwrap();
+        child.wait().unwrap();
+        let pid = child.id();
+        assert!(!is_alive(pid));
+
+        // The PID file of the dead process is left behind by a crash
+        std::fs::write(&process.pid_file, pid.to_string()).unwrap();
+        assert!(process.mark_exited(pid));
+        assert!(!process.pid_file.exists());
+        assert_eq!(process.take_exit(), Some(pid));
+        assert_eq!(process.take_exit(), None);
+
+        // A server stopped by the user removes its PID file without an exit
+        std::fs::write(&process.pid_file, pid.to_string()).unwrap();
+        std::fs::remove_file(&process.pid_file).unwrap();
+        assert_eq!(process.take_exit(), None);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_cpu_ticks() {
+        let stat =
+            "4242 (Valheim Server) S 1 4242 4242 0 -1 4194560 1 0 0 0 1500 250 0 0 20 0 40 0";
+        assert_eq!(cpu_ticks(stat), Some(1750));
+        assert_eq!(cpu_ticks("4242 (broken"), None);
+    }
+}
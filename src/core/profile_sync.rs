@@ -0,0 +1,579 @@
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    os::unix::fs::OpenOptionsExt,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::{profiles::ProfileSource, ProfileRegistry},
+    tr,
+    utils::{download, fs, Config, TimeoutConfig},
+};
+
+/// Name of the file with the last sync in the community profile directory
+const SYNC_FILE: &str = "sync.json";
+/// Directory of the clone of a git repository in the steamserv directory
+const CLONE_DIR: &str = "profile-repository";
+/// Namespace the SSH signature of a profile index is made for
+const SIGNATURE_NAMESPACE: &str = "steamserv-profiles";
+
+/// A fetched revision with the names and contents of its profile files
+type Revision = (String, Vec<(String, String)>);
+
+/// Settings of the community profile repository
+///
+/// The repository is either a git repository with the profile files in its top
+/// directory, or an `index.toml` on an HTTPS server that lists the profile files
+/// with their SHA-256 checksums.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ProfileRepository {
+    /// The git URL or the HTTPS URL of the `index.toml`, `{version}` is replaced with the pin
+    pub url: Option<String>,
+    /// The tag or commit of a git repository, or the version of the index
+    pub pin: Option<String>,
+    /// The allowed signers file of `ssh-keygen` for SSH signatures, git falls
+    /// back to its own settings without it
+    pub signers: Option<PathBuf>,
+    /// Refuse commits, tags and indexes without a valid signature
+    pub verify: bool,
+}
+
+impl Default for ProfileRepository {
+    /// No repository, signatures are required once one is set
+    fn default() -> Self {
+        Self {
+            url: None,
+            pin: None,
+            signers: None,
+            verify: true,
+        }
+    }
+}
+
+/// The index of a profile repository on an HTTPS server
+#[derive(Debug, Deserialize)]
+struct ProfileIndex {
+    version: String,
+    #[serde(default)]
+    file: Vec<IndexFile>,
+}
+
+/// A profile file listed in the index
+#[derive(Debug, Deserialize)]
+struct IndexFile {
+    /// The file name, relative to the URL of the index
+    path: String,
+    sha256: String,
+}
+
+/// The result of a sync of the community profiles
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProfileSync {
+    pub url: String,
+    /// The commit of the git repository or the version of the index
+    pub revision: String,
+    /// True if the signature of the revision was checked
+    pub verified: bool,
+    /// The names of the synced profile files
+    pub files: Vec<String>,
+    /// Number of profiles the files add or change
+    pub profiles: usize,
+    pub synced_at: DateTime<Utc>,
+}
+
+impl ProfileSync {
+    /// Fetch the pinned revision of the community repository and replace the
+    /// synced profiles with its files
+    ///
+    /// The files are only installed if the signature could be verified and all
+    /// profiles in them are valid, otherwise the synced profiles are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration with the repository and network settings
+    ///
+    /// # Returns
+    ///
+    /// The result of the sync
+    ///
+    /// # Errors
+    ///
+    /// If no repository is configured, the repository could not be fetched, a
+    /// signature or checksum is invalid or a profile file is invalid
+    pub async fn run(config: &Config) -> Result<Self, Box<dyn std::error::Error>> {
+        let repository = &config.profile_repository;
+        let url = repository
+            .url
+            .as_deref()
+            .ok_or_else(|| tr!("profile-sync-no-repository"))?;
+        config.ensure_online(&tr!("action-sync-profiles"))?;
+
+        let (url, revision, files) = if is_index(url) {
+            let url = match &repository.pin {
+                Some(pin) => url.replace("{version}", pin),
+                None if url.contains("{version}") => {
+                    return Err(tr!("profile-sync-pin-missing").into())
+                }
+                None => url.to_string(),
+            };
+            let (version, files) = fetch_index(config, &url).await?;
+            (url, version, files)
+        } else {
+            let (commit, files) = fetch_git(config, url)?;
+            (url.to_string(), commit, files)
+        };
+
+        let dir = ProfileRegistry::community_dir()?;
+        let sync = Self {
+            url,
+            revision,
+            verified: repository.verify,
+            files: files.iter().map(|(name, _)| name.clone()).collect(),
+            profiles: install(&dir, files)?,
+            synced_at: Utc::now(),
+        };
+        fs::write_atomic(
+            &dir.join(SYNC_FILE),
+            serde_json::to_string_pretty(&sync)?.as_bytes(),
+        )?;
+        Ok(sync)
+    }
+
+    /// Load the last sync
+    ///
+    /// # Returns
+    ///
+    /// The last sync, or `None` if the community profiles were never synced
+    pub fn load() -> Option<Self> {
+        let path = ProfileRegistry::community_dir().ok()?.join(SYNC_FILE);
+        serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+    }
+}
+
+/// Check if a repository URL points to an index instead of a git repository
+///
+/// # Arguments
+///
+/// * `url` - The URL of the repository
+///
+/// # Returns
+///
+/// True if the URL is an HTTPS URL of a `.toml` file
+fn is_index(url: &str) -> bool {
+    url.starts_with("https://") && url.ends_with(".toml")
+}
+
+/// Fetch the profile files listed in an index
+///
+/// # Arguments
+///
+/// * `config` - The configuration with the repository and network settings
+/// * `url` - The URL of the index
+///
+/// # Returns
+///
+/// The version of the index and the names and contents of the profile files
+///
+/// # Errors
+///
+/// If a file could not be downloaded, the signature of the index is invalid,
+/// the version differs from the pin or a checksum does not match
+async fn fetch_index(config: &Config, url: &str) -> Result<Revision, Box<dyn std::error::Error>> {
+    let repository = &config.profile_repository;
+    let client = config
+        .network
+        .https_client(TimeoutConfig::limit(config.timeouts.network))?;
+    let base = reqwest::Url::parse(url)?;
+
+    let content = fetch(&client, base.clone()).await?;
+    if repository.verify {
+        let signature = fetch(&client, reqwest::Url::parse(&format!("{}.sig", url))?).await?;
+        verify_signature(repository.signers.as_deref(), &content, &signature)?;
+    }
+    let index: ProfileIndex = toml::from_str(&String::from_utf8(content)?).map_err(|e| {
+        tr!(
+            "profile-sync-index-invalid",
+            url = url,
+            error = e.to_string()
+        )
+    })?;
+    if let Some(pin) = repository.pin.as_ref().filter(|pin| **pin != index.version) {
+        return Err(tr!(
+            "profile-sync-pin-mismatch",
+            pin = pin.as_str(),
+            version = index.version.as_str()
+        )
+        .into());
+    }
+
+    let mut files = Vec::new();
+    for file in index.file {
+        check_file_name(&file.path)?;
+        let content = fetch(&client, base.join(&file.path)?).await?;
+        download::verify_sha256(&content, &file.sha256).map_err(|e| {
+            tr!(
+                "profile-sync-checksum",
+                file = file.path.as_str(),
+                error = e
+            )
+        })?;
+        files.push((file.path, String::from_utf8(content)?));
+    }
+    Ok((index.version, files))
+}
+
+/// Download a file of a profile index
+///
+/// # Arguments
+///
+/// * `client` - The HTTP client
+/// * `url` - The URL of the file, must use HTTPS
+///
+/// # Returns
+///
+/// The content of the file
+///
+/// # Errors
+///
+/// If the URL does not use HTTPS or the download failed
+async fn fetch(
+    client: &reqwest::Client,
+    url: reqwest::Url,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    if url.scheme() != "https" {
+        return Err(tr!("profile-sync-https", url = url.as_str()).into());
+    }
+    let response = client.get(url).send().await?.error_for_status()?;
+    Ok(response.bytes().await?.to_vec())
+}
+
+/// Verify the SSH signature of a profile index with `ssh-keygen`
+///
+/// # Arguments
+///
+/// * `signers` - The allowed signers file
+/// * `content` - The signed content
+/// * `signature` - The signature
+///
+/// # Returns
+///
+/// Ok if the content was signed by one of the allowed signers
+///
+/// # Errors
+///
+/// If no allowed signers file is configured or the signature is invalid
+fn verify_signature(
+    signers: Option<&Path>,
+    content: &[u8],
+    signature: &[u8],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let signers = signers.ok_or_else(|| tr!("profile-sync-no-signers"))?;
+    // Written to the private steamserv directory, no other user can swap it
+    let dir = Config::data_dir()?;
+    std::fs::create_dir_all(&dir)?;
+    let signature_file = dir.join(format!("profiles-{}.sig", std::process::id()));
+    let _ = std::fs::remove_file(&signature_file);
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&signature_file)?
+        .write_all(signature)?;
+
+    let result = (|| {
+        let principals = Command::new("ssh-keygen")
+            .args(["-Y", "find-principals", "-f"])
+            .arg(signers)
+            .arg("-s")
+            .arg(&signature_file)
+            .output()?;
+        let principal = String::from_utf8_lossy(&principals.stdout)
+            .lines()
+            .next()
+            .map(str::to_string)
+            .filter(|_| principals.status.success())
+            .ok_or_else(|| tr!("profile-sync-unknown-signer"))?;
+
+        let mut child = Command::new("ssh-keygen")
+            .args(["-Y", "verify", "-f"])
+            .arg(signers)
+            .args(["-I", &principal, "-n", SIGNATURE_NAMESPACE, "-s"])
+            .arg(&signature_file)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(content)?;
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(tr!(
+                "profile-sync-signature-invalid",
+                error = String::from_utf8_lossy(&output.stderr).trim()
+            )
+            .into());
+        }
+        Ok::<(), Box<dyn std::error::Error>>(())
+    })();
+
+    let _ = std::fs::remove_file(&signature_file);
+    result
+}
+
+/// Fetch the pinned revision of a git repository
+///
+/// The repository is cloned once into the steamserv directory and fetched on
+/// later syncs. Without a pin the default branch of the repository is used.
+///
+/// # Arguments
+///
+/// * `config` - The configuration with the repository and network settings
+/// * `url` - The URL of the repository
+///
+/// # Returns
+///
+/// The commit and the names and contents of the profile files
+///
+/// # Errors
+///
+/// If git failed, the pin does not exist or the signature is invalid
+fn fetch_git(config: &Config, url: &str) -> Result<Revision, Box<dyn std::error::Error>> {
+    let repository = &config.profile_repository;
+    let dir = Config::data_dir()?.join(CLONE_DIR);
+    let proxy_env = config.network.proxy_env();
+    let run = |args: &[&str]| git(&dir, args, &proxy_env);
+    check_git_value(url)?;
+    if let Some(pin) = &repository.pin {
+        check_git_value(pin)?;
+    }
+
+    if !dir.join(".git").is_dir() {
+        std::fs::create_dir_all(&dir)?;
+        run(&["clone", "--quiet", "--no-checkout", "--", url, "."])?;
+    } else {
+        run(&["remote", "set-url", "--", "origin", url])?;
+        run(&["fetch", "--quiet", "--tags", "--force", "--prune", "origin"])?;
+        run(&["remote", "set-head", "origin", "--auto"])?;
+    }
+    let target = repository.pin.as_deref().unwrap_or("origin/HEAD");
+    run(&["checkout", "--quiet", "--force", "--detach", target, "--"])
+        .map_err(|_| tr!("profile-sync-pin-unknown", pin = target))?;
+    let commit = run(&["rev-parse", "HEAD"])?.trim().to_string();
+
+    if repository.verify {
+        let signers = repository
+            .signers
+            .as_ref()
+            .map(|path| format!("gpg.ssh.allowedSignersFile={}", path.display()));
+        let tag = repository
+            .pin
+            .as_ref()
+            .map(|pin| format!("refs/tags/{}", pin))
+            .filter(|tag| run(&["rev-parse", "--verify", "--quiet", tag]).is_ok());
+        let mut args: Vec<&str> = Vec::new();
+        if let Some(signers) = &signers {
+            args.extend(["-c", signers]);
+        }
+        match &tag {
+            Some(tag) => args.extend(["verify-tag", tag]),
+            None => args.extend(["verify-commit", "HEAD"]),
+        }
+        run(&args).map_err(|e| {
+            tr!(
+                "profile-sync-unsigned",
+                revision = commit.as_str(),
+                error = e
+            )
+        })?;
+    }
+
+    let mut names: Vec<String> = std::fs::read_dir(&dir)?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| name.ends_with(".toml"))
+        .collect();
+    names.sort();
+    let files = names
+        .into_iter()
+        .map(|name| {
+            let content = std::fs::read_to_string(dir.join(&name))?;
+            Ok((name, content))
+        })
+        .collect::<Result<_, std::io::Error>>()?;
+    Ok((commit, files))
+}
+
+/// Run git in a directory
+///
+/// # Arguments
+///
+/// * `dir` - The working directory
+/// * `args` - The arguments of git
+/// * `env` - Additional environment variables, e.g. the proxy
+///
+/// # Returns
+///
+/// The output of git
+///
+/// # Errors
+///
+/// If git could not be run or failed
+fn git(
+    dir: &Path,
+    args: &[&str],
+    env: &[(String, String)],
+) -> Result<String, Box<dyn std::error::Error>> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .envs(env.iter().map(|(k, v)| (k, v)))
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| tr!("profile-sync-git-missing", error = e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(tr!(
+            "profile-sync-git-failed",
+            command = args.join(" "),
+            error = if stderr.is_empty() {
+                output.status.to_string()
+            } else {
+                stderr
+            }
+        )
+        .into());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Check that a repository URL or pin can not be taken for an option of git
+///
+/// # Arguments
+///
+/// * `value` - The URL or pin from the config
+///
+/// # Returns
+///
+/// Ok if the value can be passed to git
+///
+/// # Errors
+///
+/// If the value starts with `-`
+fn check_git_value(value: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if value.starts_with('-') {
+        return Err(tr!("profile-sync-git-option", value = value).into());
+    }
+    Ok(())
+}
+
+/// Check that a profile file of the repository is a plain `.toml` file name
+///
+/// # Arguments
+///
+/// * `name` - The name of the file
+///
+/// # Returns
+///
+/// Ok if the file can be written into the community profile directory
+///
+/// # Errors
+///
+/// If the name contains a directory or is not a `.toml` file
+fn check_file_name(name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let plain = Path::new(name).file_name().and_then(|n| n.to_str()) == Some(name);
+    if !plain || !name.ends_with(".toml") || name.starts_with('.') {
+        return Err(tr!("profile-sync-file-name", file = name).into());
+    }
+    Ok(())
+}
+
+/// Replace the synced profile files after checking them
+///
+/// # Arguments
+///
+/// * `dir` - The community profile directory
+/// * `files` - The names and contents of the new profile files
+///
+/// # Returns
+///
+/// The number of profiles the files add or change
+///
+/// # Errors
+///
+/// If a profile is invalid or the files could not be written
+fn install(dir: &Path, files: Vec<(String, String)>) -> Result<usize, Box<dyn std::error::Error>> {
+    let files: Vec<(PathBuf, String)> = files
+        .into_iter()
+        .map(|(name, content)| (dir.join(name), content))
+        .collect();
+    let registry = ProfileRegistry::merge(files.clone());
+    if let Some(error) = registry.errors.first() {
+        return Err(tr!("profile-sync-invalid", error = error.as_str()).into());
+    }
+    let profiles = registry
+        .profiles()
+        .iter()
+        .filter(|p| {
+            matches!(
+                &p.source,
+                ProfileSource::User(_) | ProfileSource::Override(_)
+            )
+        })
+        .count();
+
+    std::fs::create_dir_all(dir)?;
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "toml") {
+            std::fs::remove_file(path)?;
+        }
+    }
+    for (path, content) in &files {
+        fs::write_atomic(path, content.as_bytes())?;
+    }
+    Ok(profiles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_files_and_index() {
+        assert!(is_index(
+            "https://example.org/profiles/{version}/index.toml"
+        ));
+        assert!(!is_index("https://github.com/example/profiles.git"));
+        assert!(!is_index("http://example.org/index.toml"));
+
+        assert!(check_file_name("valheim.toml").is_ok());
+        assert!(check_file_name("../valheim.toml").is_err());
+        assert!(check_file_name("games/valheim.toml").is_err());
+        assert!(check_file_name("valheim.sh").is_err());
+
+        assert!(check_git_value("https://github.com/example/profiles.git").is_ok());
+        assert!(check_git_value("v2026.10").is_ok());
+        assert!(check_git_value("--upload-pack=touch /tmp/pwned").is_err());
+        assert!(check_git_value("-b").is_err());
+
+        let index: ProfileIndex = toml::from_str(
+            r#"
+            version = "2026.10"
+
+            [[file]]
+            path = "valheim.toml"
+            sha256 = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(index.version, "2026.10");
+        assert_eq!(index.file[0].path, "valheim.toml");
+    }
+}
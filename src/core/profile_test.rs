@@ -0,0 +1,338 @@
+use std::{
+    fs::OpenOptions,
+    os::unix::process::CommandExt,
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    core::{
+        process::send_signal,
+        query,
+        readiness::{ReadinessCheck, ReadinessReport},
+        GameProfile, LaunchCommand, SteamCMD,
+    },
+    tr,
+    utils::{config::LoginType, Config, InstalledServer},
+};
+
+/// Interval to check the log and the query port of the launched server
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What happened while a launched server was watched
+#[derive(Default)]
+struct Watched {
+    /// When the ready line was printed
+    ready: Option<Duration>,
+    /// When the server answered a query
+    answered: Option<Duration>,
+    /// The exit status if the server exited
+    exited: Option<String>,
+}
+
+/// Test of a game profile against a real install of the game
+///
+/// The game is installed into a temporary directory, or an existing install is
+/// used. The server is checked like before a start, launched with the profile
+/// and watched for the ready line and an answer to a server query. Then it is
+/// stopped with the stop signal of the profile.
+pub struct ProfileTest {
+    pub app_id: u32,
+    /// An existing install to test instead of a fresh one
+    pub install_dir: Option<PathBuf>,
+    /// The login for the fresh install
+    pub login: (String, String),
+    /// How long the server may take to become ready
+    pub timeout: Duration,
+    /// Keep the fresh install after the test
+    pub keep: bool,
+}
+
+impl ProfileTest {
+    /// Run the test
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    ///
+    /// # Returns
+    ///
+    /// The results of all checks
+    ///
+    /// # Errors
+    ///
+    /// If the game has no profile, the install directory does not exist or the
+    /// game could not be installed
+    pub fn run(&self, config: &Config) -> Result<ReadinessReport, Box<dyn std::error::Error>> {
+        let profile = GameProfile::find(self.app_id)
+            .ok_or_else(|| tr!("profile-not-found", app_id = self.app_id))?;
+
+        let install_path = match &self.install_dir {
+            Some(dir) if dir.is_dir() => dir.clone(),
+            Some(dir) => return Err(tr!("profile-test-dir-missing", path = dir.display()).into()),
+            None => {
+                config.ensure_online(&tr!("action-install-server"))?;
+                let dir = std::env::temp_dir().join(format!(
+                    "steamserv-profile-{}-{}",
+                    self.app_id,
+                    std::process::id()
+                ));
+                println!(
+                    "{}",
+                    tr!(
+                        "profile-test-installing",
+                        name = profile.name.as_str(),
+                        path = dir.display()
+                    )
+                );
+                std::fs::create_dir_all(&dir)?;
+                SteamCMD::download(
+                    SteamCMD {
+                        login: self.login.clone(),
+                        force_install_dir: dir.display().to_string(),
+                        app_update: self.app_id,
+                        branch: None,
+                        extra_args: Vec::new(),
+                    },
+                    config,
+                )?;
+                dir
+            }
+        };
+
+        let server = test_server(self.app_id, install_path.clone());
+        let result = self.launch(config, &profile, &server);
+
+        if self.install_dir.is_none() {
+            if self.keep {
+                println!(
+                    "{}",
+                    tr!("profile-test-kept", path = install_path.display())
+                );
+            } else {
+                std::fs::remove_dir_all(&install_path)?;
+            }
+        }
+        result
+    }
+
+    /// Check, launch, watch and stop the server
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    /// * `profile` - The game profile
+    /// * `server` - The temporary server of the install
+    ///
+    /// # Returns
+    ///
+    /// The results of all checks
+    ///
+    /// # Errors
+    ///
+    /// If the log file could not be created or the server could not be stopped
+    fn launch(
+        &self,
+        config: &Config,
+        profile: &GameProfile,
+        server: &InstalledServer,
+    ) -> Result<ReadinessReport, Box<dyn std::error::Error>> {
+        let mut report = ReadinessReport::check(config, server, None)?;
+        if !report.is_ready() {
+            return Ok(report);
+        }
+
+        let command = LaunchCommand::build(config, server, None)?;
+        let log_file = std::env::temp_dir().join(format!(
+            "steamserv-profile-{}-{}.log",
+            self.app_id,
+            std::process::id()
+        ));
+        let log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_file)?;
+        println!("{}", tr!("profile-test-launching", command = &command));
+        let mut child = Command::new(&command.program)
+            .args(&command.args)
+            .envs(&command.env)
+            .current_dir(&command.working_dir)
+            .stdin(Stdio::null())
+            .stdout(log.try_clone()?)
+            .stderr(log)
+            .process_group(0)
+            .spawn()?;
+
+        let query_port = LaunchCommand::port(profile, server, None).map(|p| profile.query_port(p));
+        let Watched {
+            ready,
+            answered,
+            exited,
+        } = watch(
+            &mut child,
+            &log_file,
+            profile.ready_line.as_deref(),
+            query_port,
+            self.timeout,
+        )?;
+
+        let mut push = |name: String, passed: bool, detail: String| {
+            report.checks.push(ReadinessCheck {
+                name,
+                passed,
+                detail,
+            })
+        };
+        push(
+            tr!("profile-test-running"),
+            exited.is_none(),
+            match &exited {
+                Some(status) => tr!("profile-test-exited", status = status.as_str()),
+                None => tr!("profile-test-still-running"),
+            },
+        );
+        if let Some(line) = &profile.ready_line {
+            push(
+                tr!("profile-test-ready-line"),
+                ready.is_some(),
+                match ready {
+                    Some(after) => tr!("profile-test-after", seconds = after.as_secs()),
+                    None => tr!("profile-test-not-seen", line = line.as_str()),
+                },
+            );
+        }
+        if let Some(port) = query_port {
+            push(
+                tr!("profile-test-query", port = port),
+                answered.is_some(),
+                match answered {
+                    Some(after) => tr!("profile-test-after", seconds = after.as_secs()),
+                    None => tr!("profile-test-no-answer"),
+                },
+            );
+        }
+
+        if exited.is_none() {
+            let stopped = stop(&mut child, profile)?;
+            push(
+                tr!("profile-test-stop"),
+                stopped,
+                if stopped {
+                    profile.stop.signal.clone().unwrap_or_default()
+                } else {
+                    tr!("profile-test-killed", timeout = profile.stop.timeout)
+                },
+            );
+        }
+        if report.is_ready() {
+            let _ = std::fs::remove_file(&log_file);
+        } else {
+            println!("{}", tr!("profile-test-log", path = log_file.display()));
+        }
+
+        Ok(report)
+    }
+}
+
+/// Create a temporary server for an install
+///
+/// # Arguments
+///
+/// * `app_id` - The Steam App ID of the game
+/// * `install_path` - The install directory
+///
+/// # Returns
+///
+/// The server with the defaults of the game profile
+fn test_server(app_id: u32, install_path: PathBuf) -> InstalledServer {
+    InstalledServer::new(app_id, "profile-test", install_path, LoginType::Anonymous)
+}
+
+/// Watch a launched server until it is ready, has exited or the timeout elapsed
+///
+/// # Arguments
+///
+/// * `child` - The server process
+/// * `log_file` - The file with the output of the server
+/// * `ready_line` - The line the server prints once it accepts players
+/// * `query_port` - The port to send server queries to
+/// * `timeout` - How long to wait
+///
+/// # Returns
+///
+/// What happened until the server was ready
+///
+/// # Errors
+///
+/// If the state of the process could not be read
+fn watch(
+    child: &mut Child,
+    log_file: &Path,
+    ready_line: Option<&str>,
+    query_port: Option<u16>,
+    timeout: Duration,
+) -> Result<Watched, Box<dyn std::error::Error>> {
+    let started = Instant::now();
+    let mut watched = Watched::default();
+
+    while started.elapsed() < timeout {
+        if let Some(status) = child.try_wait()? {
+            watched.exited = Some(status.to_string());
+            break;
+        }
+        if watched.ready.is_none() {
+            let log = std::fs::read_to_string(log_file).unwrap_or_default();
+            if ready_line.is_some_and(|line| log.contains(line)) {
+                watched.ready = Some(started.elapsed());
+            }
+        }
+        if watched.answered.is_none() {
+            if let Some(port) = query_port {
+                if query::query_info("127.0.0.1", port).is_ok() {
+                    watched.answered = Some(started.elapsed());
+                }
+            }
+        }
+        let done_ready = watched.ready.is_some() || ready_line.is_none();
+        let done_query = watched.answered.is_some() || query_port.is_none();
+        if done_ready && done_query {
+            break;
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+
+    Ok(watched)
+}
+
+/// Stop a launched server with the stop signal of its profile
+///
+/// # Arguments
+///
+/// * `child` - The server process
+/// * `profile` - The game profile
+///
+/// # Returns
+///
+/// True if the server exited in time, false if it had to be killed
+///
+/// # Errors
+///
+/// If a signal could not be sent or the process could not be waited for
+fn stop(child: &mut Child, profile: &GameProfile) -> Result<bool, Box<dyn std::error::Error>> {
+    let signal = profile.stop.signal.as_deref().unwrap_or("TERM");
+    send_signal(child.id(), signal)?;
+
+    let deadline = Instant::now() + Duration::from_secs(profile.stop.timeout);
+    while Instant::now() < deadline {
+        if child.try_wait()?.is_some() {
+            return Ok(true);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+
+    send_signal(child.id(), "KILL")?;
+    child.wait()?;
+    Ok(false)
+}
@@ -0,0 +1,354 @@
+use std::path::{Path, PathBuf};
+
+use inquire::{CustomType, Select, Text};
+
+use crate::{
+    core::{
+        executables::{self, ExecutableKind},
+        GameProfile,
+    },
+    tr,
+    utils::{pattern::matches_wildcard, vdf::Vdf},
+};
+
+/// Signals a server can be stopped with, the first one is the default
+const STOP_SIGNALS: [&str; 4] = ["TERM", "INT", "QUIT", "HUP"];
+
+/// Wizard that writes a game profile for an install, run by `steamserv profile create`
+///
+/// The executables of the install are offered as the server binary, then the
+/// ports, the launch arguments, the save files and the stop sequence are asked.
+pub struct ProfileWizard {
+    pub install_dir: PathBuf,
+    /// The Steam App ID, taken from the app manifest of the install if not given
+    pub app_id: Option<u32>,
+}
+
+impl ProfileWizard {
+    /// Ask for the settings of the profile
+    ///
+    /// # Returns
+    ///
+    /// The new profile
+    ///
+    /// # Errors
+    ///
+    /// If the install directory does not exist, a prompt was cancelled or an
+    /// answer is invalid
+    pub fn prompt(&self) -> Result<GameProfile, Box<dyn std::error::Error>> {
+        if !self.install_dir.is_dir() {
+            return Err(tr!(
+                "profile-test-dir-missing",
+                path = self.install_dir.display()
+            )
+            .into());
+        }
+        let manifest = app_manifest(&self.install_dir);
+
+        let app_id = match self.app_id {
+            Some(app_id) => app_id,
+            None => {
+                let label = tr!("profile-create-app-id");
+                let mut prompt = CustomType::<u32>::new(&label);
+                if let Some((app_id, _)) = &manifest {
+                    prompt = prompt.with_default(*app_id);
+                }
+                prompt.prompt()?
+            }
+        };
+        if GameProfile::find(app_id).is_some() {
+            println!("{}", tr!("profile-create-exists", app_id = app_id));
+        }
+        let name = Text::new(&tr!("profile-create-name"))
+            .with_default(manifest.map(|(_, name)| name).as_deref().unwrap_or(""))
+            .prompt()?;
+
+        let (executable, runtime) = self.prompt_executable()?;
+
+        let port = CustomType::<u16>::new(&tr!("new-port")).prompt()?;
+        let extra_ports = Text::new(&tr!("profile-create-extra-ports"))
+            .with_help_message(&tr!("profile-create-extra-ports-help"))
+            .prompt()?;
+        let extra_port_offsets = split_list(&extra_ports, ',')
+            .iter()
+            .map(|p| port_offset(port, p))
+            .collect::<Result<Vec<_>, _>>()?;
+        let query_port = CustomType::<u16>::new(&tr!("profile-create-query-port"))
+            .with_default(port)
+            .with_help_message(&tr!("profile-create-query-port-help"))
+            .prompt()?;
+        let query_port_offset = port_offset(port, &query_port.to_string())?;
+
+        let args = Text::new(&tr!("profile-create-args"))
+            .with_help_message(&tr!("profile-create-args-help"))
+            .prompt()?;
+
+        let saves = Text::new(&tr!("profile-create-saves"))
+            .with_help_message(&tr!("profile-create-saves-help"))
+            .prompt()?;
+        let saves = split_list(&saves, ',');
+        for pattern in &saves {
+            let count = count_matches(&self.install_dir, pattern);
+            println!(
+                "{}",
+                tr!(
+                    "profile-create-saves-found",
+                    pattern = pattern.as_str(),
+                    count = count
+                )
+            );
+        }
+
+        let signal = Select::new(&tr!("profile-create-stop-signal"), STOP_SIGNALS.to_vec())
+            .with_help_message(&tr!("profile-create-stop-signal-help"))
+            .prompt()?;
+        let commands = Text::new(&tr!("profile-create-stop-commands"))
+            .with_help_message(&tr!("profile-create-stop-commands-help"))
+            .prompt()?;
+        let timeout = CustomType::<u64>::new(&tr!("profile-create-stop-timeout"))
+            .with_default(30)
+            .prompt()?;
+        let ready_line = Text::new(&tr!("profile-create-ready-line"))
+            .with_help_message(&tr!("profile-create-ready-line-help"))
+            .prompt()?;
+
+        let mut stop = toml::Table::new();
+        stop.insert("signal".to_string(), signal.into());
+        stop.insert("timeout".to_string(), (timeout as i64).into());
+        stop.insert("commands".to_string(), split_list(&commands, ';').into());
+        let mut profile = toml::Table::new();
+        profile.insert("app_id".to_string(), i64::from(app_id).into());
+        profile.insert("name".to_string(), name.trim().into());
+        profile.insert("executable".to_string(), executable.into());
+        profile.insert("runtime".to_string(), runtime.into());
+        profile.insert("default_port".to_string(), i64::from(port).into());
+        profile.insert(
+            "extra_port_offsets".to_string(),
+            extra_port_offsets
+                .into_iter()
+                .map(i64::from)
+                .collect::<Vec<_>>()
+                .into(),
+        );
+        profile.insert(
+            "query_port_offset".to_string(),
+            i64::from(query_port_offset).into(),
+        );
+        profile.insert("args".to_string(), split_args(&args).into());
+        profile.insert("saves".to_string(), saves.into());
+        profile.insert("stop".to_string(), stop.into());
+        if !ready_line.trim().is_empty() {
+            profile.insert("ready_line".to_string(), ready_line.trim().into());
+        }
+
+        Ok(toml::Value::Table(profile).try_into()?)
+    }
+
+    /// Ask for the server binary among the executables of the install
+    ///
+    /// # Returns
+    ///
+    /// The path of the binary relative to the install directory and the runtime
+    /// it needs
+    ///
+    /// # Errors
+    ///
+    /// If a prompt was cancelled
+    fn prompt_executable(&self) -> Result<(String, &'static str), Box<dyn std::error::Error>> {
+        let found = executables::scan(&self.install_dir);
+        let executable = if found.is_empty() {
+            println!("{}", tr!("profile-create-no-executables"));
+            None
+        } else {
+            let mut choices: Vec<String> = found.iter().map(ToString::to_string).collect();
+            choices.push(tr!("profile-create-executable-other"));
+            let choice = Select::new(&tr!("profile-create-executable"), choices)
+                .with_help_message(&tr!("profile-create-executable-help"))
+                .raw_prompt()?;
+            found.get(choice.index).cloned()
+        };
+
+        Ok(match executable {
+            Some(executable) => (
+                executable.path.display().to_string(),
+                match executable.kind {
+                    ExecutableKind::Pe => "windows",
+                    _ => "native",
+                },
+            ),
+            None => {
+                let path = Text::new(&tr!("profile-create-executable-path")).prompt()?;
+                let runtime = match executables::kind(&self.install_dir.join(&path)) {
+                    Some(ExecutableKind::Pe) => "windows",
+                    _ => "native",
+                };
+                (path.trim().to_string(), runtime)
+            }
+        })
+    }
+}
+
+/// Read the app ID and name from the app manifest SteamCMD keeps in an install
+///
+/// # Arguments
+///
+/// * `install_dir` - The install directory
+///
+/// # Returns
+///
+/// The app ID and name, or `None` if the install has no readable manifest
+fn app_manifest(install_dir: &Path) -> Option<(u32, String)> {
+    let entry = std::fs::read_dir(install_dir.join("steamapps"))
+        .ok()?
+        .flatten()
+        .find(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.starts_with("appmanifest_") && name.ends_with(".acf")
+        })?;
+    let manifest = Vdf::parse(&std::fs::read_to_string(entry.path()).ok()?)?;
+    let app_id = manifest.value("AppState/appid")?.parse().ok()?;
+    let name = manifest.value("AppState/name").unwrap_or_default();
+    Some((app_id, name.to_string()))
+}
+
+/// Split a list answer into its trimmed, non-empty entries
+///
+/// # Arguments
+///
+/// * `answer` - The answer
+/// * `separator` - The separator of the entries
+///
+/// # Returns
+///
+/// The entries
+fn split_list(answer: &str, separator: char) -> Vec<String> {
+    answer
+        .split(separator)
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Split launch arguments into profile entries
+///
+/// A new entry starts at every option (`-name`, `+map`), so an option and its
+/// value are left out together if a placeholder in the value has no value.
+///
+/// # Arguments
+///
+/// * `args` - The launch arguments
+///
+/// # Returns
+///
+/// The entries of the `args` list
+fn split_args(args: &str) -> Vec<String> {
+    let mut entries: Vec<String> = Vec::new();
+    for token in args.split_whitespace() {
+        match entries.last_mut() {
+            Some(entry) if !token.starts_with(['-', '+']) => {
+                entry.push(' ');
+                entry.push_str(token);
+            }
+            _ => entries.push(token.to_string()),
+        }
+    }
+    entries
+}
+
+/// Get the offset of a port from the game port
+///
+/// # Arguments
+///
+/// * `port` - The game port
+/// * `other` - The other port
+///
+/// # Returns
+///
+/// The offset
+///
+/// # Errors
+///
+/// If the other port is not a port or lower than the game port
+fn port_offset(port: u16, other: &str) -> Result<u16, Box<dyn std::error::Error>> {
+    other
+        .parse::<u16>()
+        .ok()
+        .and_then(|other| other.checked_sub(port))
+        .ok_or_else(|| {
+            tr!(
+                "profile-create-port-invalid",
+                port = other,
+                game_port = port
+            )
+            .into()
+        })
+}
+
+/// Count the files of an install that match a save pattern
+///
+/// # Arguments
+///
+/// * `install_dir` - The install directory
+/// * `pattern` - The wildcard pattern relative to the install directory
+///
+/// # Returns
+///
+/// The number of matching files
+fn count_matches(install_dir: &Path, pattern: &str) -> usize {
+    let home = dirs::home_dir().unwrap_or_default();
+    let pattern = pattern.replace("{home}", &home.display().to_string());
+    let (root, pattern) = if pattern.starts_with('/') {
+        (
+            PathBuf::from("/"),
+            pattern.trim_start_matches('/').to_string(),
+        )
+    } else {
+        (install_dir.to_path_buf(), pattern)
+    };
+    // Only the directories before the first wildcard have to be searched
+    let fixed = pattern[..pattern.find(['*', '?']).unwrap_or(pattern.len())]
+        .rsplit_once('/')
+        .map(|(dir, _)| dir)
+        .unwrap_or("");
+
+    let mut count = 0;
+    let mut dirs = vec![root.join(fixed)];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if let Ok(relative) = path.strip_prefix(&root) {
+                count += usize::from(matches_wildcard(&pattern, &relative.display().to_string()));
+            }
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_answers() {
+        assert_eq!(
+            split_args("-nographics -batchmode -name {name} +map de_dust2 -public 1"),
+            vec![
+                "-nographics",
+                "-batchmode",
+                "-name {name}",
+                "+map de_dust2",
+                "-public 1"
+            ]
+        );
+        assert_eq!(split_list(" 2457, ,2458 ", ','), vec!["2457", "2458"]);
+        assert_eq!(port_offset(2456, "2458").unwrap(), 2);
+        assert!(port_offset(2456, "2455").is_err());
+        assert!(port_offset(2456, "port").is_err());
+    }
+}
@@ -0,0 +1,724 @@
+use std::{collections::BTreeMap, fmt, path::PathBuf, sync::OnceLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::{Dependency, LaunchCommand},
+    tr,
+    utils::{Config, InstalledServer, ServerInstance},
+};
+
+/// The built-in game profiles
+const BUILTIN_PROFILES: &str = include_str!("../../profiles/builtin.toml");
+/// Directory of the user profiles in the steamserv directory
+const PROFILES_DIR: &str = "profiles";
+/// Directory of the synced community profiles in the user profile directory
+const COMMUNITY_DIR: &str = "community";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Engine {
+    GoldSrc,
+    Source,
+    Source2,
+    Unity,
+    Unreal,
+    #[default]
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Runtime {
+    #[default]
+    Native,
+    Windows,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GameProfile {
+    pub app_id: u32,
+    pub name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub engine: Engine,
+    pub executable: String,
+    #[serde(default)]
+    pub runtime: Runtime,
+    #[serde(default)]
+    pub args: Vec<String>,
+    pub default_port: Option<u16>,
+    #[serde(default)]
+    pub extra_port_offsets: Vec<u16>,
+    #[serde(default)]
+    pub query_port_offset: u16,
+    pub fps_command: Option<String>,
+    pub broadcast_command: Option<String>,
+    pub map_command: Option<String>,
+    pub players_command: Option<String>,
+    #[serde(default)]
+    pub required_files: Vec<String>,
+    pub token_app_id: Option<u32>,
+    pub workshop_app_id: Option<u32>,
+    #[serde(default)]
+    pub links: Vec<LinkRule>,
+    #[serde(default)]
+    pub stop: StopSequence,
+    #[serde(default)]
+    pub maintenance_args: Vec<String>,
+    #[serde(default)]
+    pub cluster_args: Vec<String>,
+    pub bans: Option<PlayerList>,
+    pub whitelist: Option<PlayerList>,
+    pub admins: Option<PlayerList>,
+    pub ready_line: Option<String>,
+    pub config_schema: Option<ConfigSchema>,
+    #[serde(default)]
+    pub saves: Vec<String>,
+    #[serde(default)]
+    pub dependencies: Vec<Dependency>,
+    #[serde(default)]
+    pub steamcmd_args: Vec<String>,
+    #[serde(default)]
+    pub install_retries: u32,
+}
+
+/// Rule to link downloaded workshop content into the server directory
+///
+/// With a pattern every matching file of the workshop item is linked into the
+/// target directory, without a pattern the whole item directory is linked to
+/// the target. `{id}` in the target is replaced with the workshop item ID.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LinkRule {
+    pub pattern: Option<String>,
+    pub target: String,
+}
+
+/// Sequence to shut down a game server gracefully
+///
+/// The RCON commands are sent in order, then the signal is sent to the process.
+/// If the server is still running after the timeout, the fallback signal is sent
+/// and the process is killed if that does not help either.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StopSequence {
+    #[serde(default)]
+    pub commands: Vec<String>,
+    pub signal: Option<String>,
+    #[serde(default = "default_stop_timeout")]
+    pub timeout: u64,
+    #[serde(default = "default_fallback_signal")]
+    pub fallback_signal: String,
+}
+
+/// Ban list, whitelist or admin list of a game
+///
+/// The file is a path template relative to the install directory with one line
+/// per player, rendered from the `line` template. Only the lines that start like
+/// the template belong to the list. The RCON commands apply a change
+/// to a running server. Both templates support `{steamid64}`, `{steamid2}` and
+/// `{steamid3}`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PlayerList {
+    pub file: Option<String>,
+    #[serde(default = "default_player_line")]
+    pub line: String,
+    #[serde(default)]
+    pub add: Vec<String>,
+    #[serde(default)]
+    pub remove: Vec<String>,
+}
+
+/// Schema of the main config file of a game, checked by `steamserv lint`
+///
+/// The file is a path template relative to the install directory. The port and
+/// RCON keys name the settings that have to match what steamserv launches and
+/// connects to the server with.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConfigSchema {
+    pub file: String,
+    #[serde(default)]
+    pub format: ConfigFormat,
+    #[serde(default)]
+    pub required: Vec<String>,
+    pub port_key: Option<String>,
+    pub rcon_port_key: Option<String>,
+    pub rcon_password_key: Option<String>,
+    /// Settings known by name to `steamserv config-get` and `config-set`
+    #[serde(default)]
+    pub settings: BTreeMap<String, ConfigSetting>,
+}
+
+/// A setting of the config file of a game, e.g. `max_players`
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ConfigSetting {
+    /// The key in the config file, keys of nested JSON objects are joined with dots
+    pub key: String,
+    #[serde(default, rename = "type")]
+    pub kind: SettingKind,
+    /// The INI section a missing key is added to
+    pub section: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SettingKind {
+    #[default]
+    String,
+    Integer,
+    Float,
+    Bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigFormat {
+    /// Console commands like `hostname "My Server"`, used by Source games
+    #[default]
+    Source,
+    /// `key=value` lines, sections are ignored
+    Ini,
+    /// `<property name="key" value="value"/>` elements
+    Xml,
+    /// A JSON object, nested keys are joined with dots
+    Json,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProfileFile {
+    #[serde(default)]
+    profile: Vec<GameProfile>,
+}
+
+/// Where a game profile comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProfileSource {
+    Builtin,
+    /// A profile of a game without a built-in profile
+    User(PathBuf),
+    /// A built-in profile with settings overridden by a user profile
+    Override(PathBuf),
+}
+
+/// A game profile with its source
+#[derive(Debug, Clone)]
+pub struct RegisteredProfile {
+    pub profile: GameProfile,
+    pub source: ProfileSource,
+}
+
+/// The built-in game profiles together with the user profiles
+///
+/// Every TOML file in the `profiles` directory of the steamserv directory holds
+/// `[[profile]]` entries like the built-in profiles. An entry with the app ID of
+/// a known game only needs the settings it changes, tables like `stop` are
+/// merged key by key. The files are read in alphabetical order, so a later file
+/// wins over an earlier one and every user profile wins over the built-in one.
+/// The community profiles synced into the `community` subdirectory are read
+/// before the files of the user, so the user can still change them.
+/// Entries that are invalid are left out and reported in `errors`.
+#[derive(Debug, Default)]
+pub struct ProfileRegistry {
+    profiles: Vec<RegisteredProfile>,
+    pub errors: Vec<String>,
+}
+
+static PROFILES: OnceLock<Vec<GameProfile>> = OnceLock::new();
+static REGISTRY: OnceLock<ProfileRegistry> = OnceLock::new();
+
+impl Default for StopSequence {
+    /// Create the stop sequence for games without a profile
+    fn default() -> Self {
+        Self {
+            commands: Vec::new(),
+            signal: Some("TERM".to_string()),
+            timeout: default_stop_timeout(),
+            fallback_signal: default_fallback_signal(),
+        }
+    }
+}
+
+/// Default time in seconds to wait for a server to stop
+fn default_stop_timeout() -> u64 {
+    30
+}
+
+/// Default signal to send if a server did not stop in time
+fn default_fallback_signal() -> String {
+    "KILL".to_string()
+}
+
+/// Default line of a player in a ban list or whitelist
+fn default_player_line() -> String {
+    "{steamid64}".to_string()
+}
+
+impl GameProfile {
+    /// Get all built-in game profiles
+    ///
+    /// # Returns
+    ///
+    /// The built-in game profiles
+    pub fn builtin() -> &'static [GameProfile] {
+        PROFILES.get_or_init(|| {
+            toml::from_str::<ProfileFile>(BUILTIN_PROFILES)
+                .map(|file| file.profile)
+                .unwrap_or_default()
+        })
+    }
+
+    /// Write the profile as a profile file
+    ///
+    /// Empty lists are left out, so the file only holds the settings of the game.
+    ///
+    /// # Returns
+    ///
+    /// The TOML of a profile file with this profile
+    ///
+    /// # Errors
+    ///
+    /// If the profile could not be serialized
+    pub fn to_toml(&self) -> Result<String, Box<dyn std::error::Error>> {
+        let mut profile = toml::Table::try_from(self)?;
+        remove_empty_lists(&mut profile);
+        let mut file = toml::Table::new();
+        file.insert(
+            "profile".to_string(),
+            toml::Value::Array(vec![toml::Value::Table(profile)]),
+        );
+        Ok(toml::to_string_pretty(&file)?)
+    }
+
+    /// Find the game profile of an app
+    ///
+    /// # Arguments
+    ///
+    /// * `app_id` - The Steam App ID of the game server
+    ///
+    /// # Returns
+    ///
+    /// The game profile if one exists for the app
+    pub fn find(app_id: u32) -> Option<GameProfile> {
+        ProfileRegistry::global()
+            .find(app_id)
+            .map(|p| p.profile.clone())
+    }
+
+    /// Check if the game uses a Source engine and supports game server login tokens
+    ///
+    /// # Returns
+    ///
+    /// True if the game is based on Source or Source 2
+    pub fn is_source_engine(&self) -> bool {
+        matches!(self.engine, Engine::Source | Engine::Source2)
+    }
+
+    /// Get the port that answers Steam server queries
+    ///
+    /// # Arguments
+    ///
+    /// * `port` - The game port of the server
+    ///
+    /// # Returns
+    ///
+    /// The query port
+    pub fn query_port(&self, port: u16) -> u16 {
+        port.saturating_add(self.query_port_offset)
+    }
+
+    /// Get the number of consecutive ports a server uses, starting at the game port
+    ///
+    /// # Returns
+    ///
+    /// The game port plus the extra ports and the query port
+    pub fn port_span(&self) -> u16 {
+        let max_offset = self
+            .extra_port_offsets
+            .iter()
+            .copied()
+            .chain(std::iter::once(self.query_port_offset))
+            .max()
+            .unwrap_or_default();
+        max_offset + 1
+    }
+
+    /// Check if the launch arguments use a placeholder
+    ///
+    /// # Arguments
+    ///
+    /// * `placeholder` - The name of the placeholder without braces, e.g. `world`
+    ///
+    /// # Returns
+    ///
+    /// True if any launch argument contains the placeholder
+    pub fn uses_placeholder(&self, placeholder: &str) -> bool {
+        let placeholder = format!("{{{}}}", placeholder);
+        self.args.iter().any(|arg| arg.contains(&placeholder))
+    }
+
+    /// Get the instance variables used by the launch arguments and the SteamCMD commands
+    ///
+    /// # Returns
+    ///
+    /// The names of the `{var:KEY}` placeholders in order of appearance
+    pub fn variables(&self) -> Vec<String> {
+        let mut variables: Vec<String> = Vec::new();
+        for arg in self.args.iter().chain(&self.steamcmd_args) {
+            let mut rest = arg.as_str();
+            while let Some(start) = rest.find("{var:") {
+                let Some(end) = rest[start..].find('}') else {
+                    break;
+                };
+                let name = rest[start + 5..start + end].to_string();
+                if !variables.contains(&name) {
+                    variables.push(name);
+                }
+                rest = &rest[start + end + 1..];
+            }
+        }
+        variables
+    }
+
+    /// Render the SteamCMD commands the game needs for an install
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The installed server
+    /// * `instance` - The instance with the variables, e.g. the mod to install
+    ///
+    /// # Returns
+    ///
+    /// The commands, without the ones with a placeholder that has no value
+    pub fn install_commands(
+        &self,
+        server: &InstalledServer,
+        instance: Option<&ServerInstance>,
+    ) -> Vec<String> {
+        self.steamcmd_args
+            .iter()
+            .filter_map(|template| LaunchCommand::render_template(template, self, server, instance))
+            .map(|tokens| tokens.join(" "))
+            .collect()
+    }
+}
+
+impl ProfileRegistry {
+    /// Get the registry of the built-in and user profiles
+    ///
+    /// The user profiles are read once per process.
+    ///
+    /// # Returns
+    ///
+    /// The registry
+    pub fn global() -> &'static Self {
+        REGISTRY.get_or_init(|| {
+            let files = Self::dir()
+                .map(|dir| {
+                    let mut files = read_profile_files(&dir.join(COMMUNITY_DIR));
+                    files.extend(read_profile_files(&dir));
+                    files
+                })
+                .unwrap_or_default();
+            Self::merge(files)
+        })
+    }
+
+    /// Get the directory of the user profiles
+    ///
+    /// # Returns
+    ///
+    /// The `profiles` directory in the steamserv directory
+    ///
+    /// # Errors
+    ///
+    /// If the steamserv directory could not be found
+    pub fn dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        Ok(Config::data_dir()?.join(PROFILES_DIR))
+    }
+
+    /// Get the directory of the synced community profiles
+    ///
+    /// # Returns
+    ///
+    /// The `community` directory in the user profile directory
+    ///
+    /// # Errors
+    ///
+    /// If the steamserv directory could not be found
+    pub fn community_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        Ok(Self::dir()?.join(COMMUNITY_DIR))
+    }
+
+    /// Build the registry from the built-in profiles and user profile files
+    ///
+    /// # Arguments
+    ///
+    /// * `files` - The paths and contents of the user profile files, in the order they apply
+    ///
+    /// # Returns
+    ///
+    /// The registry with the profiles sorted by app ID
+    pub fn merge(files: Vec<(PathBuf, String)>) -> Self {
+        let mut registry = Self::default();
+        let mut entries: Vec<(toml::Table, ProfileSource)> = profile_tables(BUILTIN_PROFILES)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|table| (table, ProfileSource::Builtin))
+            .collect();
+
+        for (path, content) in files {
+            let tables = match profile_tables(&content) {
+                Ok(tables) => tables,
+                Err(e) => {
+                    registry.errors.push(tr!(
+                        "profile-file-invalid",
+                        path = path.display(),
+                        error = e.to_string()
+                    ));
+                    continue;
+                }
+            };
+            for table in tables {
+                let Some(app_id) = table.get("app_id").and_then(toml::Value::as_integer) else {
+                    registry
+                        .errors
+                        .push(tr!("profile-app-id-missing", path = path.display()));
+                    continue;
+                };
+                let existing = entries.iter().position(|(t, _)| {
+                    t.get("app_id").and_then(toml::Value::as_integer) == Some(app_id)
+                });
+
+                let mut merged = existing.map(|i| entries[i].0.clone()).unwrap_or_default();
+                merge_table(&mut merged, table);
+                if let Err(e) = toml::Value::Table(merged.clone()).try_into::<GameProfile>() {
+                    registry.errors.push(tr!(
+                        "profile-entry-invalid",
+                        path = path.display(),
+                        app_id = app_id,
+                        error = e.to_string()
+                    ));
+                    continue;
+                }
+
+                match existing {
+                    Some(i) => {
+                        let source = match entries[i].1 {
+                            ProfileSource::User(_) => ProfileSource::User(path.clone()),
+                            _ => ProfileSource::Override(path.clone()),
+                        };
+                        entries[i] = (merged, source);
+                    }
+                    None => entries.push((merged, ProfileSource::User(path.clone()))),
+                }
+            }
+        }
+
+        registry.profiles = entries
+            .into_iter()
+            .filter_map(|(table, source)| {
+                let profile = toml::Value::Table(table).try_into().ok()?;
+                Some(RegisteredProfile { profile, source })
+            })
+            .collect();
+        registry.profiles.sort_by_key(|p| p.profile.app_id);
+        registry
+    }
+
+    /// Get all profiles
+    ///
+    /// # Returns
+    ///
+    /// The profiles sorted by app ID
+    pub fn profiles(&self) -> &[RegisteredProfile] {
+        &self.profiles
+    }
+
+    /// Find the profile of an app
+    ///
+    /// # Arguments
+    ///
+    /// * `app_id` - The Steam App ID of the game server
+    ///
+    /// # Returns
+    ///
+    /// The profile with its source if one exists for the app
+    pub fn find(&self, app_id: u32) -> Option<&RegisteredProfile> {
+        self.profiles.iter().find(|p| p.profile.app_id == app_id)
+    }
+}
+
+impl fmt::Display for ProfileSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Builtin => write!(f, "{}", tr!("profile-source-builtin")),
+            Self::User(path) => write!(f, "{}", path.display()),
+            Self::Override(path) => write!(
+                f,
+                "{}",
+                tr!("profile-source-override", path = path.display())
+            ),
+        }
+    }
+}
+
+/// Read the user profile files of a directory
+///
+/// # Arguments
+///
+/// * `dir` - The directory of the user profiles
+///
+/// # Returns
+///
+/// The paths and contents of the `.toml` files in alphabetical order, files that
+/// cannot be read are left out
+fn read_profile_files(dir: &std::path::Path) -> Vec<(PathBuf, String)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let content = std::fs::read_to_string(&path).ok()?;
+            Some((path, content))
+        })
+        .collect()
+}
+
+/// Remove the empty lists of a profile table and its tables
+///
+/// # Arguments
+///
+/// * `table` - The table
+fn remove_empty_lists(table: &mut toml::Table) {
+    table.retain(|_, value| !matches!(value, toml::Value::Array(a) if a.is_empty()));
+    for (_, value) in table.iter_mut() {
+        if let toml::Value::Table(inner) = value {
+            remove_empty_lists(inner);
+        }
+    }
+}
+
+/// Get the `[[profile]]` entries of a profile file
+///
+/// # Arguments
+///
+/// * `content` - The content of the file
+///
+/// # Returns
+///
+/// The entries as TOML tables
+///
+/// # Errors
+///
+/// If the file is not valid TOML
+fn profile_tables(content: &str) -> Result<Vec<toml::Table>, toml::de::Error> {
+    let mut file: toml::Table = toml::from_str(content)?;
+    let Some(toml::Value::Array(profiles)) = file.remove("profile") else {
+        return Ok(Vec::new());
+    };
+    Ok(profiles
+        .into_iter()
+        .filter_map(|value| match value {
+            toml::Value::Table(table) => Some(table),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Merge the keys of a user profile into a profile
+///
+/// Tables are merged key by key, all other values replace the old value.
+///
+/// # Arguments
+///
+/// * `base` - The profile to change
+/// * `overrides` - The keys of the user profile
+fn merge_table(base: &mut toml::Table, overrides: toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(old)), toml::Value::Table(new)) => merge_table(old, new),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_profiles_parse() {
+        let profiles = GameProfile::builtin();
+        assert!(!profiles.is_empty());
+        assert!(GameProfile::find(896660).is_some());
+        assert!(GameProfile::find(730).unwrap().is_source_engine());
+        assert_eq!(GameProfile::find(730).unwrap().stop.commands, ["quit"]);
+    }
+
+    #[test]
+    fn test_profile_variables() {
+        let valheim = GameProfile::find(896660).unwrap();
+        assert_eq!(valheim.variables(), ["PASSWORD"]);
+        assert!(valheim.uses_placeholder("world"));
+        assert!(!valheim.uses_placeholder("config_file"));
+        assert_eq!(valheim.port_span(), 2);
+    }
+
+    #[test]
+    fn test_user_profiles_override_builtin() {
+        let overrides = r#"
+            [[profile]]
+            app_id = 896660
+            default_port = 2500
+            stop = { timeout = 120 }
+
+            [[profile]]
+            app_id = 1000
+            name = "Custom Game"
+            executable = "server.sh"
+
+            [[profile]]
+            app_id = 1001
+            name = "Broken"
+        "#;
+        let registry = ProfileRegistry::merge(vec![
+            (PathBuf::from("a.toml"), overrides.to_string()),
+            (PathBuf::from("b.toml"), "not toml [".to_string()),
+        ]);
+
+        let valheim = registry.find(896660).unwrap();
+        assert_eq!(
+            valheim.source,
+            ProfileSource::Override(PathBuf::from("a.toml"))
+        );
+        assert_eq!(valheim.profile.default_port, Some(2500));
+        assert_eq!(valheim.profile.stop.timeout, 120);
+        assert_eq!(valheim.profile.stop.signal.as_deref(), Some("INT"));
+        assert_eq!(valheim.profile.executable, "valheim_server.x86_64");
+
+        let custom = registry.find(1000).unwrap();
+        assert_eq!(custom.source, ProfileSource::User(PathBuf::from("a.toml")));
+        assert!(registry.find(1001).is_none());
+        assert_eq!(registry.errors.len(), 2);
+        assert_eq!(registry.find(730).unwrap().source, ProfileSource::Builtin);
+    }
+
+    #[test]
+    fn test_profile_to_toml() {
+        let valheim = GameProfile::find(896660).unwrap();
+        let content = valheim.to_toml().unwrap();
+        assert!(!content.contains("= []"));
+
+        let file: ProfileFile = toml::from_str(&content).unwrap();
+        assert_eq!(file.profile[0].args, valheim.args);
+        assert_eq!(file.profile[0].stop.signal.as_deref(), Some("INT"));
+    }
+}
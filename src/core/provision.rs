@@ -0,0 +1,882 @@
+use std::{
+    collections::BTreeSet,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use inquire::{Confirm, CustomType, Select, Text};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::{aliases, GameProfile, ModManager, ServerProcess, SteamCMD},
+    tr,
+    utils::{
+        config::LoginType,
+        disk,
+        events::{self, Event},
+        fs, privilege, Config, InstalledServer, ServerCache, ServerInstance, ServerRef,
+    },
+};
+
+/// Name of the instance that holds the launch settings chosen in the wizard
+const DEFAULT_INSTANCE: &str = "main";
+
+/// Everything needed to bring a new game server online, collected by `steamserv new`
+#[derive(Debug, Clone)]
+pub struct NewServer {
+    pub app_id: u32,
+    pub name: String,
+    pub install_root: PathBuf,
+    pub branch: Option<String>,
+    pub login: (String, String),
+    pub instance: Option<ServerInstance>,
+    pub mods: Vec<u64>,
+    pub start: bool,
+}
+
+/// Saved settings of `steamserv new` to provision identical servers
+///
+/// Templates are stored in the `templates` directory of steamserv. They keep
+/// the Steam user name but never the password.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerTemplate {
+    pub app_id: u32,
+    pub install_root: PathBuf,
+    pub branch: Option<String>,
+    pub username: String,
+    pub instance: Option<ServerInstance>,
+    #[serde(default)]
+    pub mods: Vec<u64>,
+    #[serde(default)]
+    pub start: bool,
+}
+
+/// Answers of an interactive `install` or `new` run
+///
+/// Saved with `--save-answers` and replayed with `--answers` to repeat a run
+/// without prompts, e.g. on another host. The Steam password is never saved and
+/// is asked again on replay unless the login is anonymous.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Answers {
+    pub app_id: u32,
+    pub name: String,
+    pub install_root: PathBuf,
+    pub branch: Option<String>,
+    pub username: String,
+    pub instance: Option<ServerInstance>,
+    #[serde(default)]
+    pub mods: Vec<u64>,
+    #[serde(default)]
+    pub start: bool,
+}
+
+/// A server to install with `steamserv install` for several apps at once
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppEntry {
+    /// The alias or Steam App ID
+    pub app: String,
+    /// The name of the server, derived from the name of the app if not given
+    pub name: Option<String>,
+}
+
+impl AppEntry {
+    /// Parse a list of apps
+    ///
+    /// Every line has an alias or app ID, optionally followed by the name of the
+    /// server. Empty lines and lines starting with `#` are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The content of the list
+    ///
+    /// # Returns
+    ///
+    /// The entries of the list
+    pub fn parse_list(text: &str) -> Vec<Self> {
+        text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let mut parts = line.split_whitespace();
+                Some(Self {
+                    app: parts.next()?.to_string(),
+                    name: parts.next().map(String::from),
+                })
+            })
+            .collect()
+    }
+
+    /// Load a list of apps from a file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the list
+    ///
+    /// # Returns
+    ///
+    /// The entries of the list
+    ///
+    /// # Errors
+    ///
+    /// If the file could not be read
+    pub fn load_list(path: &Path) -> Result<Vec<Self>, Box<dyn std::error::Error>> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| tr!("install-list-read-failed", path = path.display(), error = e))?;
+        Ok(Self::parse_list(&text))
+    }
+}
+
+/// Entry of the app selection, shown as `name (app_id)` followed by the localized names
+struct AppChoice {
+    app_id: u32,
+    name: String,
+    aliases: Vec<String>,
+}
+
+impl fmt::Display for AppChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.app_id)?;
+        if !self.aliases.is_empty() {
+            write!(f, " {}", self.aliases.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl NewServer {
+    /// Ask for all settings of a new server
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    ///
+    /// # Returns
+    ///
+    /// The settings of the new server
+    ///
+    /// # Errors
+    ///
+    /// If the server cache is empty or a prompt was cancelled
+    pub fn prompt(config: &Config) -> Result<Self, Box<dyn std::error::Error>> {
+        let cache = ServerCache::load()?;
+        if cache.servers.is_empty() {
+            return Err(tr!("new-cache-empty").into());
+        }
+
+        let choices: Vec<AppChoice> = cache
+            .servers
+            .iter()
+            .map(|s| AppChoice {
+                app_id: s.app_id,
+                name: s.name.clone(),
+                aliases: s.aliases.clone(),
+            })
+            .collect();
+        let app = Select::new(&tr!("new-select-app"), choices)
+            .with_help_message(&tr!("new-select-app-help"))
+            .prompt()?;
+        let profile = GameProfile::find(app.app_id);
+
+        let name = Text::new(&tr!("prompt-server-name"))
+            .with_default(&default_name(&app.name))
+            .with_help_message(&tr!("prompt-server-name-help"))
+            .prompt()?;
+        if config.find_server(&name).is_some() {
+            return Err(tr!("new-server-exists", server = name).into());
+        }
+
+        let install_root = Text::new(&tr!("new-install-root"))
+            .with_default(&config.install_path.display().to_string())
+            .prompt()?;
+
+        let branch = Text::new(&tr!("new-branch"))
+            .with_help_message(&tr!("new-branch-help"))
+            .prompt()?;
+
+        let login = SteamCMD::get_login(None)?;
+
+        let instance = match &profile {
+            Some(profile) => Some(Self::prompt_instance(profile)?),
+            None => {
+                println!("{}", tr!("new-no-profile", app = app.name.as_str()));
+                None
+            }
+        };
+
+        let mods = match &profile {
+            Some(profile) if profile.workshop_app_id.is_some() => {
+                let ids = Text::new(&tr!("new-mods"))
+                    .with_help_message(&tr!("new-mods-help"))
+                    .prompt()?;
+                parse_ids(&ids)?
+            }
+            _ => Vec::new(),
+        };
+
+        let start = profile.is_some()
+            && Confirm::new(&tr!("new-start"))
+                .with_default(true)
+                .prompt()?;
+
+        Ok(Self {
+            app_id: app.app_id,
+            name,
+            install_root: PathBuf::from(install_root),
+            branch: Some(branch.trim().to_string()).filter(|b| !b.is_empty()),
+            login,
+            instance,
+            mods,
+            start,
+        })
+    }
+
+    /// Plan a server for an app of a list without asking
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration with the install path
+    /// * `cache` - The server cache to take the app name from
+    /// * `entry` - The app and the optional server name
+    /// * `login` - The Steam login shared by all servers of the list
+    /// * `names` - The names that are already taken, the new name is added
+    ///
+    /// # Returns
+    ///
+    /// The server, installed into the install path without instance or mods
+    ///
+    /// # Errors
+    ///
+    /// If the app is unknown or the given name is already taken
+    pub fn for_app(
+        config: &Config,
+        cache: &ServerCache,
+        entry: &AppEntry,
+        login: &(String, String),
+        names: &mut BTreeSet<String>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let app_id = aliases::resolve(config, &entry.app)?;
+        let name = match &entry.name {
+            Some(name) if names.contains(name) => {
+                return Err(tr!("new-server-exists", server = name.as_str()).into());
+            }
+            Some(name) => name.clone(),
+            None => {
+                let name = cache
+                    .servers
+                    .iter()
+                    .find(|s| s.app_id == app_id)
+                    .map(|s| default_name(&s.name))
+                    .unwrap_or_else(|| app_id.to_string());
+                if names.contains(&name) {
+                    next_name(&name, names)
+                } else {
+                    name
+                }
+            }
+        };
+        names.insert(name.clone());
+
+        Ok(Self {
+            app_id,
+            name,
+            install_root: config.install_path.clone(),
+            branch: None,
+            login: login.clone(),
+            instance: None,
+            mods: Vec::new(),
+            start: false,
+        })
+    }
+
+    /// Ask for the launch settings used by the game profile
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - The game profile
+    ///
+    /// # Returns
+    ///
+    /// The instance with the launch settings
+    ///
+    /// # Errors
+    ///
+    /// If a prompt was cancelled
+    fn prompt_instance(
+        profile: &GameProfile,
+    ) -> Result<ServerInstance, Box<dyn std::error::Error>> {
+        let mut instance = ServerInstance::new(DEFAULT_INSTANCE);
+
+        let label = tr!("new-port");
+        let mut port = CustomType::<u16>::new(&label);
+        if let Some(default_port) = &profile.default_port {
+            port = port.with_default(*default_port);
+        }
+        instance.port = Some(port.prompt()?);
+
+        if profile.uses_placeholder("world") {
+            let world = Text::new(&tr!("new-world")).prompt()?;
+            instance.world_name = Some(world).filter(|w| !w.is_empty());
+        }
+        if profile.uses_placeholder("config_file") {
+            let config_file = Text::new(&tr!("new-config-file")).prompt()?;
+            instance.config_file =
+                Some(PathBuf::from(config_file)).filter(|p| !p.as_os_str().is_empty());
+        }
+        for variable in profile.variables() {
+            let value = Text::new(&tr!("new-variable", variable = variable.as_str()))
+                .with_help_message(&tr!("new-variable-help"))
+                .prompt()?;
+            if !value.is_empty() {
+                instance.variables.insert(variable, value);
+            }
+        }
+
+        Ok(instance)
+    }
+
+    /// Install the server, add its instance and mods and optionally start it
+    ///
+    /// # Returns
+    ///
+    /// Ok if the server was provisioned
+    ///
+    /// # Errors
+    ///
+    /// If the server could not be installed, the mods could not be downloaded or
+    /// the server could not be started
+    pub async fn provision(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let mut config = Config::load()?;
+        config.ensure_online(&tr!("action-install-server"))?;
+        if config.find_server(&self.name).is_some() {
+            return Err(tr!("new-server-exists", server = self.name.as_str()).into());
+        }
+
+        let install_path = self.install_root.join(&self.name);
+        privilege::ensure_dirs(&[&install_path])?;
+        disk::warn(&config)?;
+        let mut server = InstalledServer::new(
+            self.app_id,
+            &self.name,
+            install_path.clone(),
+            match self.login.0.as_str() {
+                "anonymous" => LoginType::Anonymous,
+                _ => LoginType::SteamAccount,
+            },
+        );
+        server.branch = self.branch.clone();
+        // Kept with the server, so the updates use the same commands
+        if let Some(profile) = GameProfile::find(self.app_id) {
+            server.steamcmd_args = profile.install_commands(&server, self.instance.as_ref());
+        }
+
+        events::publish(Event::InstallStarted {
+            server: self.name.clone(),
+            app_id: self.app_id,
+        });
+        SteamCMD::download(
+            SteamCMD {
+                login: self.login.clone(),
+                force_install_dir: install_path.display().to_string(),
+                app_update: self.app_id,
+                branch: self.branch.clone(),
+                extra_args: server.steamcmd_args.clone(),
+            },
+            &config,
+        )?;
+
+        let now = chrono::Local::now().to_utc();
+        server.install_date = now;
+        server.last_updated = now;
+        config.installed_servers.push(server);
+        if let Some(instance) = &self.instance {
+            config.add_instance(&self.name, instance.clone())?;
+        }
+        config.save()?;
+        events::publish(Event::InstallFinished {
+            server: self.name.clone(),
+        });
+
+        if !self.mods.is_empty() {
+            ModManager::add(&self.name, &self.mods).await?;
+        }
+
+        if self.start {
+            let config = Config::load()?;
+            let target = match &self.instance {
+                Some(instance) => ServerRef::parse(&format!("{}/{}", self.name, instance.name)),
+                None => ServerRef::parse(&self.name),
+            };
+            let (server, instance) = config.resolve(&target)?;
+            ServerProcess::new(&target)?.start(&config, server, instance)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Answers {
+    /// Take the answers of a new server
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The settings of the new server
+    ///
+    /// # Returns
+    ///
+    /// The answers without the password
+    pub fn from_server(server: &NewServer) -> Self {
+        Self {
+            app_id: server.app_id,
+            name: server.name.clone(),
+            install_root: server.install_root.clone(),
+            branch: server.branch.clone(),
+            username: server.login.0.clone(),
+            instance: server.instance.clone(),
+            mods: server.mods.clone(),
+            start: server.start,
+        }
+    }
+
+    /// Turn the answers into the settings of a new server, asking only for the password
+    ///
+    /// # Returns
+    ///
+    /// The settings of the new server
+    ///
+    /// # Errors
+    ///
+    /// If the password prompt was cancelled
+    pub fn into_server(self) -> Result<NewServer, Box<dyn std::error::Error>> {
+        let login = SteamCMD::get_login(Some(self.username))?;
+        Ok(NewServer {
+            app_id: self.app_id,
+            name: self.name,
+            install_root: self.install_root,
+            branch: self.branch,
+            login,
+            instance: self.instance,
+            mods: self.mods,
+            start: self.start,
+        })
+    }
+
+    /// Load an answer file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the answer file
+    ///
+    /// # Returns
+    ///
+    /// The answers
+    ///
+    /// # Errors
+    ///
+    /// If the file could not be read or parsed
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            tr!(
+                "answers-read-failed",
+                path = path.display(),
+                error = e.to_string()
+            )
+        })?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    /// Save the answers to a file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the answer file
+    ///
+    /// # Returns
+    ///
+    /// Ok if the answers were saved
+    ///
+    /// # Errors
+    ///
+    /// If the file could not be written
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        fs::write_atomic(path, toml::to_string(self)?.as_bytes())?;
+        println!("{}", tr!("answers-saved", path = path.display()));
+        Ok(())
+    }
+}
+
+impl ServerTemplate {
+    /// Create a template from the settings of a new server
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The settings of the new server
+    ///
+    /// # Returns
+    ///
+    /// The template without the name and the password
+    pub fn from_server(server: &NewServer) -> Self {
+        Self {
+            app_id: server.app_id,
+            install_root: server.install_root.clone(),
+            branch: server.branch.clone(),
+            username: server.login.0.clone(),
+            instance: server.instance.clone(),
+            mods: server.mods.clone(),
+            start: server.start,
+        }
+    }
+
+    /// Get the path to a template
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the template
+    ///
+    /// # Returns
+    ///
+    /// The path to the template file
+    ///
+    /// # Errors
+    ///
+    /// If the steamserv directory could not be found
+    fn path(name: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        Ok(Config::data_dir()?
+            .join("templates")
+            .join(format!("{}.toml", name)))
+    }
+
+    /// Load a saved template
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the template
+    ///
+    /// # Returns
+    ///
+    /// The template
+    ///
+    /// # Errors
+    ///
+    /// If the template does not exist or could not be parsed
+    pub fn load(name: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = std::fs::read_to_string(Self::path(name)?)
+            .map_err(|_| tr!("template-not-found", template = name))?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    /// Save the template
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the template
+    ///
+    /// # Returns
+    ///
+    /// Ok if the template was saved
+    ///
+    /// # Errors
+    ///
+    /// If the template could not be written
+    pub fn save(&self, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        fs::write_atomic(&Self::path(name)?, toml::to_string(self)?.as_bytes())
+    }
+
+    /// Provision several servers from the template
+    ///
+    /// The servers are named `<prefix>-<n>` with the next free numbers, and every
+    /// server gets the next free block of ports at or after the port of the template.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The prefix of the server names
+    /// * `count` - The number of servers
+    ///
+    /// # Returns
+    ///
+    /// Ok if all servers were provisioned
+    ///
+    /// # Errors
+    ///
+    /// If no free port is left or a server could not be provisioned
+    pub async fn provision_many(
+        &self,
+        prefix: &str,
+        count: usize,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let config = Config::load()?;
+        let profile = GameProfile::find(self.app_id);
+        let span = profile.as_ref().map(|p| p.port_span()).unwrap_or(1);
+        let base_port = self
+            .instance
+            .as_ref()
+            .and_then(|i| i.port)
+            .or(profile.as_ref().and_then(|p| p.default_port));
+
+        let mut names: BTreeSet<String> = config
+            .installed_servers
+            .iter()
+            .map(|s| s.name.clone())
+            .collect();
+        let mut ports: BTreeSet<u16> = config
+            .installed_servers
+            .iter()
+            .flat_map(|s| {
+                s.instances
+                    .iter()
+                    .filter_map(|i| i.port)
+                    .chain(s.port)
+                    .collect::<Vec<_>>()
+            })
+            .flat_map(|port| port..port.saturating_add(span))
+            .collect();
+
+        privilege::ensure_dirs(&[&self.install_root])?;
+        let login = SteamCMD::get_login(Some(self.username.clone()))?;
+
+        let mut servers = Vec::new();
+        for _ in 0..count {
+            let name = next_name(prefix, &names);
+            names.insert(name.clone());
+
+            let instance = match (&self.instance, base_port) {
+                (Some(instance), Some(base_port)) => {
+                    let port = next_port(base_port, span, &ports)
+                        .ok_or_else(|| tr!("template-no-port", port = base_port))?;
+                    ports.extend(port..port.saturating_add(span));
+                    Some(ServerInstance {
+                        port: Some(port),
+                        ..instance.clone()
+                    })
+                }
+                (instance, _) => instance.clone(),
+            };
+
+            servers.push(NewServer {
+                app_id: self.app_id,
+                name,
+                install_root: self.install_root.clone(),
+                branch: self.branch.clone(),
+                login: login.clone(),
+                instance,
+                mods: self.mods.clone(),
+                start: self.start,
+            });
+        }
+
+        for (index, server) in servers.iter().enumerate() {
+            println!(
+                "{}",
+                tr!(
+                    "template-provisioning",
+                    server = server.name.as_str(),
+                    index = index + 1,
+                    count = count
+                )
+            );
+            server.provision().await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for NewServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", tr!("new-summary-app", app_id = self.app_id))?;
+        writeln!(
+            f,
+            "{}",
+            tr!(
+                "new-summary-path",
+                path = self.install_root.join(&self.name).display()
+            )
+        )?;
+        writeln!(
+            f,
+            "{}",
+            tr!(
+                "new-summary-branch",
+                branch = self.branch.as_deref().unwrap_or("public")
+            )
+        )?;
+        writeln!(
+            f,
+            "{}",
+            tr!("new-summary-login", user = self.login.0.as_str())
+        )?;
+        if let Some(instance) = &self.instance {
+            writeln!(
+                f,
+                "{}",
+                tr!(
+                    "new-summary-instance",
+                    target = format!("{}/{}", self.name, instance.name),
+                    port = instance.port.map(|p| p.to_string()).unwrap_or_default()
+                )
+            )?;
+        }
+        if !self.mods.is_empty() {
+            let mods: Vec<String> = self.mods.iter().map(|id| id.to_string()).collect();
+            writeln!(f, "{}", tr!("new-summary-mods", mods = mods.join(", ")))?;
+        }
+        write!(
+            f,
+            "{}",
+            if self.start {
+                tr!("new-summary-start-yes")
+            } else {
+                tr!("new-summary-start-no")
+            }
+        )
+    }
+}
+
+/// Derive a server name from the name of the game
+///
+/// # Arguments
+///
+/// * `app_name` - The name of the dedicated server app
+///
+/// # Returns
+///
+/// The lowercase name with dashes, e.g. `valheim` for `Valheim Dedicated Server`
+fn default_name(app_name: &str) -> String {
+    let name = aliases::slug(app_name);
+    if name.is_empty() {
+        "server".to_string()
+    } else {
+        name
+    }
+}
+
+/// Parse a list of workshop item IDs separated by commas or spaces
+///
+/// # Arguments
+///
+/// * `ids` - The list of IDs
+///
+/// # Returns
+///
+/// The parsed IDs
+///
+/// # Errors
+///
+/// If an ID is not a number
+fn parse_ids(ids: &str) -> Result<Vec<u64>, Box<dyn std::error::Error>> {
+    ids.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|id| !id.is_empty())
+        .map(|id| {
+            id.parse()
+                .map_err(|_| tr!("new-mod-id-invalid", id = id).into())
+        })
+        .collect()
+}
+
+/// Get the first free server name with a prefix
+///
+/// # Arguments
+///
+/// * `prefix` - The prefix of the name
+/// * `names` - The names that are already taken
+///
+/// # Returns
+///
+/// The name `<prefix>-<n>` with the lowest free number, starting at 1
+fn next_name(prefix: &str, names: &BTreeSet<String>) -> String {
+    (1..)
+        .map(|n| format!("{}-{}", prefix, n))
+        .find(|name| !names.contains(name))
+        .unwrap()
+}
+
+/// Get the first free block of ports at or after a base port
+///
+/// # Arguments
+///
+/// * `base` - The first port to try
+/// * `span` - The number of consecutive ports a server uses
+/// * `used` - The ports that are already taken
+///
+/// # Returns
+///
+/// The first port of the free block, or `None` if no block is left
+fn next_port(base: u16, span: u16, used: &BTreeSet<u16>) -> Option<u16> {
+    let mut port = base;
+    loop {
+        let end = port.checked_add(span - 1)?;
+        if !(port..=end).any(|p| used.contains(&p)) {
+            return Some(port);
+        }
+        port = port.checked_add(span)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answers_round_trip() {
+        let mut instance = ServerInstance::new(DEFAULT_INSTANCE);
+        instance.port = Some(2456);
+        let answers = Answers {
+            app_id: 896660,
+            name: "valheim".to_string(),
+            install_root: PathBuf::from("/srv/games"),
+            branch: None,
+            username: "anonymous".to_string(),
+            instance: Some(instance),
+            mods: vec![1, 2],
+            start: true,
+        };
+
+        let content = toml::to_string(&answers).unwrap();
+        assert!(!content.contains("password"));
+
+        let loaded: Answers = toml::from_str(&content).unwrap();
+        assert_eq!(loaded.name, "valheim");
+        assert_eq!(loaded.instance.unwrap().port, Some(2456));
+        assert_eq!(loaded.mods, vec![1, 2]);
+        assert!(loaded.start);
+    }
+
+    #[test]
+    fn test_default_name() {
+        assert_eq!(default_name("Valheim Dedicated Server"), "valheim");
+        assert_eq!(
+            default_name("Don't Starve Together Dedicated Server"),
+            "don-t-starve-together"
+        );
+        assert_eq!(parse_ids("123, 456 789").unwrap(), [123, 456, 789]);
+        assert!(parse_ids("abc").is_err());
+    }
+
+    #[test]
+    fn test_parse_app_list() {
+        let entries = AppEntry::parse_list("# host 1\nvalheim\n\n  2394010 palworld-eu  \n");
+        assert_eq!(
+            entries,
+            [
+                AppEntry {
+                    app: "valheim".to_string(),
+                    name: None
+                },
+                AppEntry {
+                    app: "2394010".to_string(),
+                    name: Some("palworld-eu".to_string())
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_next_name_and_port() {
+        let names = BTreeSet::from(["arena-1".to_string(), "arena-3".to_string()]);
+        assert_eq!(next_name("arena", &names), "arena-2");
+
+        let used = BTreeSet::from([2456, 2457, 2459]);
+        assert_eq!(next_port(2456, 2, &used), Some(2460));
+        assert_eq!(next_port(2456, 1, &BTreeSet::new()), Some(2456));
+        assert_eq!(next_port(65535, 2, &BTreeSet::new()), None);
+    }
+}
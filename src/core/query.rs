@@ -0,0 +1,265 @@
+use std::{
+    net::{ToSocketAddrs, UdpSocket},
+    time::Duration,
+};
+
+use crate::tr;
+
+/// Header of connectionless Source packets
+const PACKET_HEADER: [u8; 4] = [0xFF; 4];
+const A2S_INFO: u8 = 0x54;
+const S2A_INFO: u8 = 0x49;
+const S2C_CHALLENGE: u8 = 0x41;
+const A2S_PLAYER: u8 = 0x55;
+const S2A_PLAYER: u8 = 0x44;
+
+/// Timeout for a query
+const QUERY_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Server information returned by an A2S_INFO query
+#[derive(Debug, Clone)]
+pub struct A2sInfo {
+    pub name: String,
+    pub map: String,
+    pub folder: String,
+    pub game: String,
+    pub players: u8,
+    pub max_players: u8,
+    pub bots: u8,
+}
+
+/// A connected player returned by an A2S_PLAYER query
+#[derive(Debug, Clone)]
+pub struct A2sPlayer {
+    pub name: String,
+    pub score: i32,
+    pub duration: f32,
+}
+
+/// Query the server information with the Steam server query protocol
+///
+/// # Arguments
+///
+/// * `host` - The host of the server
+/// * `port` - The query port of the server
+///
+/// # Returns
+///
+/// The server information
+///
+/// # Errors
+///
+/// If the server did not answer or the answer could not be parsed
+pub fn query_info(host: &str, port: u16) -> Result<A2sInfo, Box<dyn std::error::Error>> {
+    let address = (host, port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| tr!("query-address-invalid", host = host))?;
+
+    let socket = UdpSocket::bind(("0.0.0.0", 0))?;
+    socket.set_read_timeout(Some(QUERY_TIMEOUT))?;
+    socket.connect(address)?;
+
+    let mut request = PACKET_HEADER.to_vec();
+    request.push(A2S_INFO);
+    request.extend_from_slice(b"Source Engine Query\0");
+
+    let mut buffer = [0u8; 1400];
+    socket.send(&request)?;
+    let mut size = socket.recv(&mut buffer)?;
+
+    // Newer servers answer with a challenge that has to be sent back
+    if size >= 9 && buffer[4] == S2C_CHALLENGE {
+        request.extend_from_slice(&buffer[5..9]);
+        socket.send(&request)?;
+        size = socket.recv(&mut buffer)?;
+    }
+
+    parse_info(&buffer[..size])
+}
+
+/// Query the connected players with the Steam server query protocol
+///
+/// # Arguments
+///
+/// * `host` - The host of the server
+/// * `port` - The query port of the server
+///
+/// # Returns
+///
+/// The connected players
+///
+/// # Errors
+///
+/// If the server did not answer or the answer could not be parsed
+pub fn query_players(host: &str, port: u16) -> Result<Vec<A2sPlayer>, Box<dyn std::error::Error>> {
+    let address = (host, port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| tr!("query-address-invalid", host = host))?;
+
+    let socket = UdpSocket::bind(("0.0.0.0", 0))?;
+    socket.set_read_timeout(Some(QUERY_TIMEOUT))?;
+    socket.connect(address)?;
+
+    // The first request asks for a challenge with the challenge number -1
+    let mut request = PACKET_HEADER.to_vec();
+    request.push(A2S_PLAYER);
+    request.extend_from_slice(&PACKET_HEADER);
+
+    let mut buffer = [0u8; 1400];
+    socket.send(&request)?;
+    let mut size = socket.recv(&mut buffer)?;
+
+    if size >= 9 && buffer[4] == S2C_CHALLENGE {
+        request.truncate(5);
+        request.extend_from_slice(&buffer[5..9]);
+        socket.send(&request)?;
+        size = socket.recv(&mut buffer)?;
+    }
+
+    parse_players(&buffer[..size])
+}
+
+/// Parse an S2A_INFO response
+///
+/// # Arguments
+///
+/// * `packet` - The response packet
+///
+/// # Returns
+///
+/// The server information
+///
+/// # Errors
+///
+/// If the packet is not a valid S2A_INFO response
+fn parse_info(packet: &[u8]) -> Result<A2sInfo, Box<dyn std::error::Error>> {
+    if packet.len() < 6 || packet[..4] != PACKET_HEADER || packet[4] != S2A_INFO {
+        return Err(tr!("query-invalid-response").into());
+    }
+
+    // Skip the header, the response type and the protocol version
+    let mut rest = &packet[6..];
+    let name = read_string(&mut rest)?;
+    let map = read_string(&mut rest)?;
+    let folder = read_string(&mut rest)?;
+    let game = read_string(&mut rest)?;
+
+    // The app ID (2 bytes) is followed by players, max players and bots
+    if rest.len() < 5 {
+        return Err(tr!("query-invalid-response").into());
+    }
+
+    Ok(A2sInfo {
+        name,
+        map,
+        folder,
+        game,
+        players: rest[2],
+        max_players: rest[3],
+        bots: rest[4],
+    })
+}
+
+/// Parse an S2A_PLAYER response
+///
+/// # Arguments
+///
+/// * `packet` - The response packet
+///
+/// # Returns
+///
+/// The connected players
+///
+/// # Errors
+///
+/// If the packet is not a valid S2A_PLAYER response
+fn parse_players(packet: &[u8]) -> Result<Vec<A2sPlayer>, Box<dyn std::error::Error>> {
+    if packet.len() < 6 || packet[..4] != PACKET_HEADER || packet[4] != S2A_PLAYER {
+        return Err(tr!("query-invalid-response").into());
+    }
+
+    // Every player has an index, a name, the score (4 bytes) and the duration (4 bytes)
+    let mut rest = &packet[6..];
+    let mut players = Vec::with_capacity(usize::from(packet[5]));
+    for _ in 0..packet[5] {
+        if rest.is_empty() {
+            break;
+        }
+        rest = &rest[1..];
+        let name = read_string(&mut rest)?;
+        if rest.len() < 8 {
+            return Err(tr!("query-invalid-response").into());
+        }
+        players.push(A2sPlayer {
+            name,
+            score: i32::from_le_bytes([rest[0], rest[1], rest[2], rest[3]]),
+            duration: f32::from_le_bytes([rest[4], rest[5], rest[6], rest[7]]),
+        });
+        rest = &rest[8..];
+    }
+
+    Ok(players)
+}
+
+/// Read a null-terminated string and advance the buffer
+///
+/// # Arguments
+///
+/// * `buffer` - The buffer to read from
+///
+/// # Returns
+///
+/// The string
+///
+/// # Errors
+///
+/// If the buffer contains no null terminator
+fn read_string(buffer: &mut &[u8]) -> Result<String, Box<dyn std::error::Error>> {
+    let end = buffer
+        .iter()
+        .position(|b| *b == 0)
+        .ok_or_else(|| tr!("query-invalid-response"))?;
+    let value = String::from_utf8_lossy(&buffer[..end]).to_string();
+    *buffer = &buffer[end + 1..];
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_info() {
+        let mut packet = vec![0xFF, 0xFF, 0xFF, 0xFF, S2A_INFO, 17];
+        packet.extend_from_slice(b"My Server\0de_dust2\0csgo\0Counter-Strike\0");
+        packet.extend_from_slice(&[0xDA, 0x02, 12, 24, 2]);
+
+        let info = parse_info(&packet).unwrap();
+        assert_eq!(info.name, "My Server");
+        assert_eq!(info.map, "de_dust2");
+        assert_eq!(info.players, 12);
+        assert_eq!(info.max_players, 24);
+        assert_eq!(info.bots, 2);
+    }
+
+    #[test]
+    fn test_parse_players() {
+        let mut packet = vec![0xFF, 0xFF, 0xFF, 0xFF, S2A_PLAYER, 2, 0];
+        packet.extend_from_slice(b"Alice\0");
+        packet.extend_from_slice(&12i32.to_le_bytes());
+        packet.extend_from_slice(&754.5f32.to_le_bytes());
+        packet.push(1);
+        packet.extend_from_slice(b"Bob\0");
+        packet.extend_from_slice(&0i32.to_le_bytes());
+        packet.extend_from_slice(&30.0f32.to_le_bytes());
+
+        let players = parse_players(&packet).unwrap();
+        assert_eq!(players.len(), 2);
+        assert_eq!(players[0].name, "Alice");
+        assert_eq!(players[0].score, 12);
+        assert_eq!(players[0].duration, 754.5);
+        assert_eq!(players[1].name, "Bob");
+    }
+}
@@ -0,0 +1,191 @@
+use std::{
+    io::{Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    time::Duration,
+};
+
+use crate::{
+    core::GameProfile,
+    tr,
+    utils::{InstalledServer, RconConfig, ServerInstance},
+};
+
+const PACKET_AUTH: i32 = 3;
+const PACKET_AUTH_RESPONSE: i32 = 2;
+const PACKET_EXEC: i32 = 2;
+const PACKET_RESPONSE: i32 = 0;
+
+/// Timeout for connecting and reading from the RCON server
+const RCON_TIMEOUT: Duration = Duration::from_secs(5);
+/// Maximum size of a single RCON packet
+const MAX_PACKET_SIZE: i32 = 4096 + 10;
+
+/// Send a chat message to all players of a server or instance
+///
+/// # Arguments
+///
+/// * `server` - The installed server
+/// * `instance` - The instance, or `None` for the server itself
+/// * `message` - The message
+/// * `command` - The command template with a `{message}` placeholder, defaults to the
+///   `broadcast_command` of the game profile
+///
+/// # Returns
+///
+/// Ok if the message was sent
+///
+/// # Errors
+///
+/// If RCON is not configured, the game has no broadcast command or the command could not be sent
+pub fn broadcast(
+    server: &InstalledServer,
+    instance: Option<&ServerInstance>,
+    message: &str,
+    command: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let name = match instance {
+        Some(instance) => format!("{}/{}", server.name, instance.name),
+        None => server.name.clone(),
+    };
+    let rcon = server
+        .rcon_for(instance)
+        .ok_or_else(|| tr!("rcon-not-configured", target = name.as_str()))?;
+    let template = command
+        .map(str::to_string)
+        .or_else(|| GameProfile::find(server.app_id).and_then(|p| p.broadcast_command))
+        .ok_or_else(|| tr!("broadcast-no-command", target = name.as_str()))?;
+
+    RconClient::connect(rcon)?.execute(&template.replace("{message}", message))?;
+    Ok(())
+}
+
+/// Client for the Source RCON protocol
+pub struct RconClient {
+    stream: TcpStream,
+    next_id: i32,
+}
+
+impl RconClient {
+    /// Connect and authenticate to an RCON server
+    ///
+    /// # Arguments
+    ///
+    /// * `settings` - The RCON settings of the server
+    ///
+    /// # Returns
+    ///
+    /// The authenticated client
+    ///
+    /// # Errors
+    ///
+    /// If the connection failed or the password was rejected
+    pub fn connect(settings: &RconConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        let host = settings.host.as_deref().unwrap_or("127.0.0.1");
+        let address = (host, settings.port)
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| tr!("rcon-address-invalid", host = host))?;
+
+        let stream = TcpStream::connect_timeout(&address, RCON_TIMEOUT)?;
+        stream.set_read_timeout(Some(RCON_TIMEOUT))?;
+        stream.set_write_timeout(Some(RCON_TIMEOUT))?;
+
+        let mut client = Self { stream, next_id: 1 };
+        let id = client.send(PACKET_AUTH, &settings.password)?;
+
+        loop {
+            let (response_id, packet_type, _) = client.receive()?;
+            if packet_type == PACKET_AUTH_RESPONSE {
+                if response_id == -1 || response_id != id {
+                    return Err(tr!("rcon-auth-failed").into());
+                }
+                return Ok(client);
+            }
+        }
+    }
+
+    /// Execute a command on the server
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The command to execute
+    ///
+    /// # Returns
+    ///
+    /// The response of the server
+    ///
+    /// # Errors
+    ///
+    /// If the command could not be sent or the response could not be read
+    pub fn execute(&mut self, command: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let id = self.send(PACKET_EXEC, command)?;
+
+        loop {
+            let (response_id, packet_type, body) = match self.receive() {
+                Ok(packet) => packet,
+                // Some servers close the connection on commands like `quit`
+                Err(_) => return Ok(String::new()),
+            };
+            if response_id == id && packet_type == PACKET_RESPONSE {
+                return Ok(body);
+            }
+        }
+    }
+
+    /// Send a packet
+    ///
+    /// # Arguments
+    ///
+    /// * `packet_type` - The type of the packet
+    /// * `body` - The body of the packet
+    ///
+    /// # Returns
+    ///
+    /// The ID of the sent packet
+    ///
+    /// # Errors
+    ///
+    /// If the packet could not be written
+    fn send(&mut self, packet_type: i32, body: &str) -> Result<i32, Box<dyn std::error::Error>> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let size = 4 + 4 + body.len() as i32 + 2;
+        let mut packet = Vec::with_capacity(size as usize + 4);
+        packet.extend_from_slice(&size.to_le_bytes());
+        packet.extend_from_slice(&id.to_le_bytes());
+        packet.extend_from_slice(&packet_type.to_le_bytes());
+        packet.extend_from_slice(body.as_bytes());
+        packet.extend_from_slice(&[0, 0]);
+
+        self.stream.write_all(&packet)?;
+        Ok(id)
+    }
+
+    /// Receive a packet
+    ///
+    /// # Returns
+    ///
+    /// The ID, type and body of the received packet
+    ///
+    /// # Errors
+    ///
+    /// If the packet could not be read or is malformed
+    fn receive(&mut self) -> Result<(i32, i32, String), Box<dyn std::error::Error>> {
+        let mut size = [0u8; 4];
+        self.stream.read_exact(&mut size)?;
+        let size = i32::from_le_bytes(size);
+        if !(10..=MAX_PACKET_SIZE).contains(&size) {
+            return Err(tr!("rcon-packet-invalid").into());
+        }
+
+        let mut packet = vec![0u8; size as usize];
+        self.stream.read_exact(&mut packet)?;
+
+        let id = i32::from_le_bytes([packet[0], packet[1], packet[2], packet[3]]);
+        let packet_type = i32::from_le_bytes([packet[4], packet[5], packet[6], packet[7]]);
+        let body = String::from_utf8_lossy(&packet[8..packet.len() - 2]).to_string();
+
+        Ok((id, packet_type, body))
+    }
+}
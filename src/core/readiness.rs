@@ -0,0 +1,208 @@
+use std::{
+    fmt,
+    net::{TcpListener, UdpSocket},
+    os::unix::fs::PermissionsExt,
+    path::Path,
+};
+
+use crate::{
+    core::{profiles::Runtime, GameProfile, LaunchCommand},
+    tr,
+    utils::{Config, InstalledServer, ServerInstance},
+};
+
+/// Result of a single pre-start check
+pub struct ReadinessCheck {
+    pub name: String,
+    pub passed: bool,
+    pub detail: String,
+}
+
+/// Aggregated result of the checks that run before a server is started
+pub struct ReadinessReport {
+    pub checks: Vec<ReadinessCheck>,
+}
+
+impl ReadinessReport {
+    /// Check if a server or instance is ready to be started
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    /// * `server` - The installed server
+    /// * `instance` - The instance to start, or `None` for the server itself
+    ///
+    /// # Returns
+    ///
+    /// The results of all checks
+    ///
+    /// # Errors
+    ///
+    /// If no game profile exists for the server
+    pub fn check(
+        config: &Config,
+        server: &InstalledServer,
+        instance: Option<&ServerInstance>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let profile = GameProfile::find(server.app_id)
+            .ok_or_else(|| tr!("profile-not-found", app_id = server.app_id))?;
+        let mut report = Self { checks: Vec::new() };
+
+        let executable = server.install_path.join(&profile.executable);
+        if profile.runtime == Runtime::Windows {
+            report.check_file(&tr!("readiness-executable"), &executable);
+            report.check_compat_tool(config);
+        } else {
+            report.check_executable(&executable);
+        }
+
+        if let Some(port) = LaunchCommand::port(&profile, server, instance) {
+            report.check_port(port);
+            for offset in &profile.extra_port_offsets {
+                if let Some(port) = port.checked_add(*offset) {
+                    report.check_port(port);
+                }
+            }
+        }
+
+        for template in &profile.required_files {
+            if let Some(path) = LaunchCommand::render_path(template, &profile, server, instance) {
+                report.check_file(&tr!("readiness-config-file"), &path);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Check if all checks passed
+    ///
+    /// # Returns
+    ///
+    /// True if the server is ready to be started
+    pub fn is_ready(&self) -> bool {
+        self.checks.iter().all(|c| c.passed)
+    }
+
+    /// Add the result of a check
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the check
+    /// * `passed` - Whether the check passed
+    /// * `detail` - Details about the result
+    fn push(&mut self, name: &str, passed: bool, detail: String) {
+        self.checks.push(ReadinessCheck {
+            name: name.to_string(),
+            passed,
+            detail,
+        });
+    }
+
+    /// Check that the launch binary exists and is executable
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the launch binary
+    fn check_executable(&mut self, path: &Path) {
+        let name = tr!("readiness-executable");
+        match std::fs::metadata(path) {
+            Ok(meta) if meta.is_file() && meta.permissions().mode() & 0o111 != 0 => {
+                self.push(&name, true, path.display().to_string())
+            }
+            Ok(_) => self.push(
+                &name,
+                false,
+                tr!("readiness-not-executable", path = path.display()),
+            ),
+            Err(_) => self.push(&name, false, tr!("doctor-missing", path = path.display())),
+        }
+    }
+
+    /// Check that a file exists
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the check
+    /// * `path` - The path to the file
+    fn check_file(&mut self, name: &str, path: &Path) {
+        if path.is_file() {
+            self.push(name, true, path.display().to_string());
+        } else {
+            self.push(name, false, tr!("doctor-missing", path = path.display()));
+        }
+    }
+
+    /// Check that Wine or Proton is available for Windows servers
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    fn check_compat_tool(&mut self, config: &Config) {
+        let name = tr!("readiness-compat-tool");
+        let tool = LaunchCommand::compat_tool(config);
+
+        let found = if tool.components().count() > 1 {
+            tool.is_file().then(|| tool.clone())
+        } else {
+            std::env::var_os("PATH").and_then(|paths| {
+                std::env::split_paths(&paths)
+                    .map(|dir| dir.join(&tool))
+                    .find(|path| path.is_file())
+            })
+        };
+
+        match found {
+            Some(path) => self.push(&name, true, path.display().to_string()),
+            None => self.push(
+                &name,
+                false,
+                tr!("readiness-compat-tool-missing", tool = tool.display()),
+            ),
+        }
+    }
+
+    /// Check that a port is free for TCP and UDP
+    ///
+    /// # Arguments
+    ///
+    /// * `port` - The port to check
+    fn check_port(&mut self, port: u16) {
+        let name = tr!("readiness-port", port = port);
+        let free = UdpSocket::bind(("0.0.0.0", port)).is_ok()
+            && TcpListener::bind(("0.0.0.0", port)).is_ok();
+
+        if free {
+            self.push(&name, true, tr!("readiness-port-free"));
+        } else {
+            self.push(&name, false, tr!("readiness-port-used"));
+        }
+    }
+}
+
+impl fmt::Display for ReadinessReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, check) in self.checks.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            let status = if check.passed { "OK" } else { "FAIL" };
+            write!(f, "[{:<4}] {:<20} {}", status, check.name, check.detail)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_port_in_use_fails() {
+        let socket = UdpSocket::bind("0.0.0.0:0").unwrap();
+        let port = socket.local_addr().unwrap().port();
+
+        let mut report = ReadinessReport { checks: Vec::new() };
+        report.check_port(port);
+        assert!(!report.is_ready());
+    }
+}
@@ -0,0 +1,488 @@
+use std::{
+    os::unix::fs::MetadataExt,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    tr,
+    utils::{
+        privilege::{self, Escalation},
+        InstalledServer,
+    },
+};
+
+/// Prefix of the snapshots taken by steamserv
+const SNAPSHOT_PREFIX: &str = "steamserv-";
+/// Format of the time in the snapshot names
+const TIME_FORMAT: &str = "%Y%m%dT%H%M%S";
+/// Inode number of the root directory of a btrfs subvolume
+const BTRFS_SUBVOLUME_INODE: u64 = 256;
+
+/// Settings of the filesystem snapshots of the installs
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct SnapshotConfig {
+    /// Take a snapshot before every update, `None` asks in the terminal
+    pub before_update: Option<bool>,
+    /// Number of snapshots that are kept per server, older ones are deleted
+    pub keep: usize,
+}
+
+impl Default for SnapshotConfig {
+    /// Ask before updates and keep the last three snapshots
+    fn default() -> Self {
+        Self {
+            before_update: None,
+            keep: 3,
+        }
+    }
+}
+
+/// A snapshot of an install
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub name: String,
+    pub created: DateTime<Utc>,
+}
+
+impl Snapshot {
+    /// Create a snapshot for the current time
+    ///
+    /// # Returns
+    ///
+    /// The snapshot with a name like `steamserv-20250101T120000`
+    fn now() -> Self {
+        let created = Utc::now();
+        Self {
+            name: format!("{}{}", SNAPSHOT_PREFIX, created.format(TIME_FORMAT)),
+            created,
+        }
+    }
+
+    /// Parse the name of a snapshot taken by steamserv
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the snapshot
+    ///
+    /// # Returns
+    ///
+    /// The snapshot, or `None` if it was not taken by steamserv
+    fn parse(name: &str) -> Option<Self> {
+        let time = name.strip_prefix(SNAPSHOT_PREFIX)?;
+        let created = NaiveDateTime::parse_from_str(time, TIME_FORMAT)
+            .ok()?
+            .and_utc();
+        Some(Self {
+            name: name.to_string(),
+            created,
+        })
+    }
+}
+
+/// The filesystem that takes the snapshots of an install
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Backend {
+    /// The install is a btrfs subvolume, the snapshots are kept in `<install>.snapshots`
+    Btrfs,
+    /// The install is the mountpoint of a ZFS dataset
+    Zfs { dataset: String },
+}
+
+/// Filesystem snapshots of the install of a server on btrfs or ZFS
+///
+/// Snapshots are taken before updates and let a server be rolled back in
+/// seconds, without copying the whole install like a backup.
+pub struct Snapshots {
+    backend: Backend,
+    install_path: PathBuf,
+}
+
+impl Snapshots {
+    /// Get the snapshots of a server
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The installed server
+    ///
+    /// # Returns
+    ///
+    /// The snapshots of the install
+    ///
+    /// # Errors
+    ///
+    /// If the install is neither a btrfs subvolume nor a ZFS dataset
+    pub fn for_server(server: &InstalledServer) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = &server.install_path;
+        let mountinfo = std::fs::read_to_string("/proc/self/mountinfo")?;
+        let mount = find_mount(&mountinfo, path)
+            .ok_or_else(|| tr!("snapshot-unsupported", path = path.display(), fs = "?"))?;
+
+        let backend = match mount.fs_type.as_str() {
+            "btrfs" if std::fs::metadata(path)?.ino() == BTRFS_SUBVOLUME_INODE => Backend::Btrfs,
+            "btrfs" => {
+                return Err(tr!("snapshot-btrfs-no-subvolume", path = path.display()).into());
+            }
+            "zfs" if mount.point == *path => Backend::Zfs {
+                dataset: mount.source,
+            },
+            "zfs" => return Err(tr!("snapshot-zfs-no-dataset", path = path.display()).into()),
+            fs => {
+                return Err(tr!("snapshot-unsupported", path = path.display(), fs = fs).into());
+            }
+        };
+
+        Ok(Self {
+            backend,
+            install_path: path.clone(),
+        })
+    }
+
+    /// Get the name of the filesystem
+    ///
+    /// # Returns
+    ///
+    /// `btrfs` or `zfs`
+    pub fn filesystem(&self) -> &'static str {
+        match self.backend {
+            Backend::Btrfs => "btrfs",
+            Backend::Zfs { .. } => "zfs",
+        }
+    }
+
+    /// List the snapshots taken by steamserv
+    ///
+    /// # Returns
+    ///
+    /// The snapshots, oldest first
+    ///
+    /// # Errors
+    ///
+    /// If the snapshots could not be listed
+    pub fn list(&self) -> Result<Vec<Snapshot>, Box<dyn std::error::Error>> {
+        let mut snapshots: Vec<Snapshot> = match &self.backend {
+            Backend::Btrfs => match std::fs::read_dir(self.btrfs_dir()) {
+                Ok(entries) => entries
+                    .filter_map(|e| e.ok())
+                    .filter_map(|e| Snapshot::parse(&e.file_name().to_string_lossy()))
+                    .collect(),
+                Err(_) => Vec::new(),
+            },
+            Backend::Zfs { dataset } => {
+                let output = Command::new("zfs")
+                    .args(["list", "-H", "-t", "snapshot", "-o", "name", "-d", "1"])
+                    .arg(dataset)
+                    .output()?;
+                String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .filter_map(|line| line.split_once('@'))
+                    .filter_map(|(_, name)| Snapshot::parse(name))
+                    .collect()
+            }
+        };
+        snapshots.sort_by_key(|s| s.created);
+        Ok(snapshots)
+    }
+
+    /// Find a snapshot by its name
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the snapshot, or `latest` for the newest one
+    ///
+    /// # Returns
+    ///
+    /// The snapshot
+    ///
+    /// # Errors
+    ///
+    /// If the snapshot does not exist
+    pub fn find(&self, name: &str) -> Result<Snapshot, Box<dyn std::error::Error>> {
+        let snapshots = self.list()?;
+        let snapshot = match name {
+            "latest" => snapshots.last(),
+            name => snapshots.iter().find(|s| s.name == name),
+        };
+        snapshot
+            .cloned()
+            .ok_or_else(|| tr!("snapshot-not-found", name = name).into())
+    }
+
+    /// Take a read-only snapshot of the install
+    ///
+    /// # Returns
+    ///
+    /// The new snapshot
+    ///
+    /// # Errors
+    ///
+    /// If the snapshot could not be taken
+    pub fn create(&self) -> Result<Snapshot, Box<dyn std::error::Error>> {
+        let snapshot = Snapshot::now();
+        match &self.backend {
+            Backend::Btrfs => {
+                let dir = self.btrfs_dir();
+                privilege::ensure_dirs(&[&dir])?;
+                run(
+                    tr!("snapshot-reason-create", path = self.install_path.display()),
+                    vec![
+                        "btrfs".to_string(),
+                        "subvolume".to_string(),
+                        "snapshot".to_string(),
+                        "-r".to_string(),
+                        self.install_path.display().to_string(),
+                        dir.join(&snapshot.name).display().to_string(),
+                    ],
+                )?;
+            }
+            Backend::Zfs { dataset } => run(
+                tr!("snapshot-reason-create", path = self.install_path.display()),
+                vec![
+                    "zfs".to_string(),
+                    "snapshot".to_string(),
+                    format!("{}@{}", dataset, snapshot.name),
+                ],
+            )?,
+        }
+        Ok(snapshot)
+    }
+
+    /// Delete a snapshot
+    ///
+    /// # Arguments
+    ///
+    /// * `snapshot` - The snapshot
+    ///
+    /// # Returns
+    ///
+    /// Ok if the snapshot was deleted
+    ///
+    /// # Errors
+    ///
+    /// If the snapshot could not be deleted
+    pub fn delete(&self, snapshot: &Snapshot) -> Result<(), Box<dyn std::error::Error>> {
+        let reason = tr!("snapshot-reason-delete", name = snapshot.name.as_str());
+        match &self.backend {
+            Backend::Btrfs => run(
+                reason,
+                vec![
+                    "btrfs".to_string(),
+                    "subvolume".to_string(),
+                    "delete".to_string(),
+                    self.btrfs_dir().join(&snapshot.name).display().to_string(),
+                ],
+            ),
+            Backend::Zfs { dataset } => run(
+                reason,
+                vec![
+                    "zfs".to_string(),
+                    "destroy".to_string(),
+                    format!("{}@{}", dataset, snapshot.name),
+                ],
+            ),
+        }
+    }
+
+    /// Delete the oldest snapshots beyond a number
+    ///
+    /// # Arguments
+    ///
+    /// * `keep` - The number of snapshots to keep
+    ///
+    /// # Returns
+    ///
+    /// The deleted snapshots
+    ///
+    /// # Errors
+    ///
+    /// If a snapshot could not be deleted
+    pub fn prune(&self, keep: usize) -> Result<Vec<Snapshot>, Box<dyn std::error::Error>> {
+        let snapshots = self.list()?;
+        let excess = snapshots.len().saturating_sub(keep);
+        let deleted: Vec<Snapshot> = snapshots.into_iter().take(excess).collect();
+        for snapshot in &deleted {
+            self.delete(snapshot)?;
+        }
+        Ok(deleted)
+    }
+
+    /// Roll the install back to a snapshot, the server has to be stopped
+    ///
+    /// On ZFS the snapshots newer than the rolled back one are destroyed, on
+    /// btrfs they are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `snapshot` - The snapshot
+    ///
+    /// # Returns
+    ///
+    /// Ok if the install was rolled back
+    ///
+    /// # Errors
+    ///
+    /// If the install could not be rolled back
+    pub fn rollback(&self, snapshot: &Snapshot) -> Result<(), Box<dyn std::error::Error>> {
+        let reason = tr!("snapshot-reason-rollback", name = snapshot.name.as_str());
+        match &self.backend {
+            Backend::Btrfs => {
+                let install = self.install_path.display().to_string();
+                let restored = format!("{}.rollback", install);
+                let replaced = format!("{}.replaced", install);
+                let source = self.btrfs_dir().join(&snapshot.name).display().to_string();
+
+                // A writable snapshot of the snapshot takes the place of the install
+                run(
+                    reason.clone(),
+                    vec![
+                        "btrfs".to_string(),
+                        "subvolume".to_string(),
+                        "snapshot".to_string(),
+                        source,
+                        restored.clone(),
+                    ],
+                )?;
+                run(
+                    reason.clone(),
+                    vec!["mv".to_string(), install.clone(), replaced.clone()],
+                )?;
+                run(reason.clone(), vec!["mv".to_string(), restored, install])?;
+                run(
+                    reason,
+                    vec![
+                        "btrfs".to_string(),
+                        "subvolume".to_string(),
+                        "delete".to_string(),
+                        replaced,
+                    ],
+                )
+            }
+            Backend::Zfs { dataset } => run(
+                reason,
+                vec![
+                    "zfs".to_string(),
+                    "rollback".to_string(),
+                    "-r".to_string(),
+                    format!("{}@{}", dataset, snapshot.name),
+                ],
+            ),
+        }
+    }
+
+    /// Get the directory of the btrfs snapshots
+    ///
+    /// # Returns
+    ///
+    /// The directory `<install>.snapshots` next to the install
+    fn btrfs_dir(&self) -> PathBuf {
+        let mut name = self
+            .install_path
+            .file_name()
+            .unwrap_or_default()
+            .to_os_string();
+        name.push(".snapshots");
+        self.install_path.with_file_name(name)
+    }
+}
+
+/// A mounted filesystem from `/proc/self/mountinfo`
+#[derive(Debug, PartialEq, Eq)]
+struct Mount {
+    point: PathBuf,
+    fs_type: String,
+    source: String,
+}
+
+/// Find the mount that holds a path
+///
+/// # Arguments
+///
+/// * `mountinfo` - The content of `/proc/self/mountinfo`
+/// * `path` - The path
+///
+/// # Returns
+///
+/// The mount with the longest mountpoint that contains the path
+fn find_mount(mountinfo: &str, path: &Path) -> Option<Mount> {
+    mountinfo
+        .lines()
+        .filter_map(|line| {
+            let (left, right) = line.split_once(" - ")?;
+            let point = left.split(' ').nth(4)?.replace("\\040", " ");
+            let mut right = right.split(' ');
+            Some(Mount {
+                point: PathBuf::from(point),
+                fs_type: right.next()?.to_string(),
+                source: right.next()?.to_string(),
+            })
+        })
+        .filter(|mount| path.starts_with(&mount.point))
+        .max_by_key(|mount| mount.point.components().count())
+}
+
+/// Run a snapshot tool, through `sudo` if it needs root
+///
+/// The command is tried with the rights of the user first, e.g. for delegated
+/// ZFS permissions or btrfs subvolumes owned by the user.
+///
+/// # Arguments
+///
+/// * `reason` - Why the command is run
+/// * `command` - The command with its arguments
+///
+/// # Returns
+///
+/// Ok if the command succeeded
+///
+/// # Errors
+///
+/// If the command failed, or needs root and `--sudo` was not given
+fn run(reason: String, command: Vec<String>) -> Result<(), Box<dyn std::error::Error>> {
+    let output = Command::new(&command[0]).args(&command[1..]).output()?;
+    if output.status.success() {
+        return Ok(());
+    }
+    if privilege::is_root() {
+        return Err(tr!(
+            "snapshot-command-failed",
+            command = command.join(" "),
+            error = String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+
+    privilege::escalate(&[Escalation { reason, command }])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_mount() {
+        let mountinfo = "\
+22 1 0:21 / / rw,relatime shared:1 - ext4 /dev/sda1 rw
+40 22 0:35 / /srv/games rw,relatime shared:20 - btrfs /dev/sdb1 rw,subvol=/games
+41 22 0:36 / /srv/zfs\\040pool/cs2 rw shared:21 - zfs tank/cs2 rw,xattr";
+
+        let mount = find_mount(mountinfo, Path::new("/srv/games/valheim")).unwrap();
+        assert_eq!(mount.fs_type, "btrfs");
+        assert_eq!(mount.point, PathBuf::from("/srv/games"));
+        let mount = find_mount(mountinfo, Path::new("/srv/zfs pool/cs2")).unwrap();
+        assert_eq!(mount.source, "tank/cs2");
+        assert_eq!(
+            find_mount(mountinfo, Path::new("/opt/rust"))
+                .unwrap()
+                .fs_type,
+            "ext4"
+        );
+
+        let snapshot = Snapshot::parse("steamserv-20250101T120000").unwrap();
+        assert_eq!(snapshot.created.to_rfc3339(), "2025-01-01T12:00:00+00:00");
+        assert!(Snapshot::parse("daily-2025").is_none());
+    }
+}
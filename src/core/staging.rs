@@ -0,0 +1,329 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+    time::SystemTime,
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::ServerProcess,
+    tr,
+    utils::{
+        events::{self, Event},
+        fs,
+        vdf::Vdf,
+        InstalledServer, ServerRef,
+    },
+};
+
+/// Name of the file that marks a validated staged update
+const MARKER_FILE: &str = ".steamserv-staged.json";
+
+/// An update that was downloaded next to the install directory and is applied
+/// on the next start of the server
+///
+/// The install directory is copied (as reflink where the filesystem supports it)
+/// to `<install>.staged`, SteamCMD updates the copy while the server keeps running,
+/// and the directories are swapped when the server and all its instances are stopped.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StagedUpdate {
+    /// The build ID of the staged update, if it was known
+    pub build: Option<String>,
+    /// When the install directory was copied
+    pub copied: DateTime<Utc>,
+}
+
+impl StagedUpdate {
+    /// Get the staged update of a server
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The installed server
+    ///
+    /// # Returns
+    ///
+    /// The staged update, or `None` if no validated update is staged
+    pub fn find(server: &InstalledServer) -> Option<Self> {
+        let marker = staged_dir(server).join(MARKER_FILE);
+        serde_json::from_str(&std::fs::read_to_string(marker).ok()?).ok()
+    }
+
+    /// Copy the install directory of a server to the staging directory
+    ///
+    /// An unfinished staged update from before is replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The installed server
+    ///
+    /// # Returns
+    ///
+    /// The staging directory and the time of the copy
+    ///
+    /// # Errors
+    ///
+    /// If the directory could not be copied
+    pub fn prepare(
+        server: &InstalledServer,
+    ) -> Result<(PathBuf, DateTime<Utc>), Box<dyn std::error::Error>> {
+        let staged = staged_dir(server);
+        if staged.exists() {
+            std::fs::remove_dir_all(&staged)?;
+        }
+
+        let copied = Utc::now();
+        let status = Command::new("cp")
+            .arg("-a")
+            .arg("--reflink=auto")
+            .arg(&server.install_path)
+            .arg(&staged)
+            .status()?;
+        if !status.success() {
+            let _ = std::fs::remove_dir_all(&staged);
+            return Err(tr!(
+                "update-staging-copy-failed",
+                path = staged.display(),
+                status = status.to_string()
+            )
+            .into());
+        }
+
+        Ok((staged, copied))
+    }
+
+    /// Validate the update in the staging directory and mark it as ready
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The installed server
+    /// * `build` - The build ID the update should have, if it is known
+    /// * `copied` - When the install directory was copied
+    ///
+    /// # Returns
+    ///
+    /// The staged update
+    ///
+    /// # Errors
+    ///
+    /// If the update is not fully installed or has another build, the staging
+    /// directory is removed then
+    pub fn validate(
+        server: &InstalledServer,
+        build: Option<String>,
+        copied: DateTime<Utc>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let staged = staged_dir(server);
+        let manifest = std::fs::read_to_string(
+            staged
+                .join("steamapps")
+                .join(format!("appmanifest_{}.acf", server.app_id)),
+        )
+        .ok()
+        .and_then(|m| Vdf::parse(&m));
+        // StateFlags 4 means fully installed
+        let installed = manifest
+            .as_ref()
+            .and_then(|m| m.value("AppState/StateFlags"))
+            == Some("4");
+        let staged_build = manifest
+            .as_ref()
+            .and_then(|m| m.value("AppState/buildid"))
+            .map(str::to_string);
+
+        if !installed || (build.is_some() && staged_build != build) {
+            std::fs::remove_dir_all(&staged)?;
+            return Err(tr!("update-staging-invalid", server = server.name).into());
+        }
+
+        let update = Self {
+            build: staged_build,
+            copied,
+        };
+        fs::write_atomic(
+            &staged.join(MARKER_FILE),
+            serde_json::to_string_pretty(&update)?.as_bytes(),
+        )?;
+        Ok(update)
+    }
+
+    /// Swap the install directory with the staged update if the server is stopped
+    ///
+    /// Files the server changed after the copy, like saves and logs, are carried
+    /// over unless the update changed them too.
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The installed server
+    ///
+    /// # Returns
+    ///
+    /// True if a staged update was applied, false if none is staged or a process
+    /// of the server is still running
+    ///
+    /// # Errors
+    ///
+    /// If the directories could not be swapped
+    pub fn apply(server: &InstalledServer) -> Result<bool, Box<dyn std::error::Error>> {
+        let Some(update) = Self::find(server) else {
+            return Ok(false);
+        };
+
+        let targets = std::iter::once(server.name.clone()).chain(
+            server
+                .instances
+                .iter()
+                .map(|i| format!("{}/{}", server.name, i.name)),
+        );
+        for target in targets {
+            if ServerProcess::new(&ServerRef::parse(&target))?.is_running() {
+                return Ok(false);
+            }
+        }
+
+        let staged = staged_dir(server);
+        let copied = SystemTime::from(update.copied);
+        carry_over(&server.install_path, &staged, copied)?;
+        std::fs::remove_file(staged.join(MARKER_FILE))?;
+
+        let previous = sibling(&server.install_path, "previous");
+        if previous.exists() {
+            std::fs::remove_dir_all(&previous)?;
+        }
+        std::fs::rename(&server.install_path, &previous)?;
+        if let Err(e) = std::fs::rename(&staged, &server.install_path) {
+            std::fs::rename(&previous, &server.install_path)?;
+            return Err(e.into());
+        }
+        std::fs::remove_dir_all(&previous)?;
+
+        events::publish(Event::UpdateApplied {
+            server: server.name.clone(),
+        });
+        Ok(true)
+    }
+
+    /// Remove the staged update of a server
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The installed server
+    ///
+    /// # Returns
+    ///
+    /// Ok if no staging directory is left
+    ///
+    /// # Errors
+    ///
+    /// If the staging directory could not be removed
+    pub fn discard(server: &InstalledServer) -> Result<(), Box<dyn std::error::Error>> {
+        let staged = staged_dir(server);
+        if staged.exists() {
+            std::fs::remove_dir_all(staged)?;
+        }
+        Ok(())
+    }
+}
+
+/// Get the staging directory of a server
+///
+/// # Arguments
+///
+/// * `server` - The installed server
+///
+/// # Returns
+///
+/// The directory `<install>.staged` next to the install directory
+pub fn staged_dir(server: &InstalledServer) -> PathBuf {
+    sibling(&server.install_path, "staged")
+}
+
+/// Get a directory next to another one with a suffix
+///
+/// # Arguments
+///
+/// * `path` - The directory
+/// * `suffix` - The suffix, appended with a dot
+///
+/// # Returns
+///
+/// The path of the sibling
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}", suffix));
+    path.with_file_name(name)
+}
+
+/// Copy the files that changed in the live directory since a time into the staged one
+///
+/// # Arguments
+///
+/// * `live` - The directory the server ran from
+/// * `staged` - The staged copy
+/// * `since` - When the copy was made
+///
+/// # Returns
+///
+/// Ok if the files were copied
+///
+/// # Errors
+///
+/// If a directory could not be read or a file could not be copied
+fn carry_over(
+    live: &Path,
+    staged: &Path,
+    since: SystemTime,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for entry in std::fs::read_dir(live)? {
+        let entry = entry?;
+        let target = staged.join(entry.file_name());
+        let file_type = entry.file_type()?;
+
+        if file_type.is_dir() {
+            std::fs::create_dir_all(&target)?;
+            carry_over(&entry.path(), &target, since)?;
+        } else if file_type.is_file() {
+            let changed = entry.metadata()?.modified()? > since;
+            let updated = target
+                .metadata()
+                .and_then(|m| m.modified())
+                .is_ok_and(|modified| modified > since);
+            if changed && !updated {
+                std::fs::copy(entry.path(), &target)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_carry_over() {
+        let dir = std::env::temp_dir().join(format!("steamserv-staging-{}", std::process::id()));
+        let (live, staged) = (dir.join("live"), dir.join("live.staged"));
+        std::fs::create_dir_all(live.join("saves")).unwrap();
+        std::fs::create_dir_all(&staged).unwrap();
+        std::fs::write(live.join("server.bin"), "old").unwrap();
+        let since = SystemTime::now() - std::time::Duration::from_secs(60);
+        std::fs::write(live.join("saves/world.db"), "played").unwrap();
+        std::fs::write(staged.join("server.bin"), "new").unwrap();
+
+        carry_over(&live, &staged, since).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(staged.join("saves/world.db")).unwrap(),
+            "played"
+        );
+        assert_eq!(
+            std::fs::read_to_string(staged.join("server.bin")).unwrap(),
+            "new"
+        );
+        assert_eq!(sibling(&live, "staged"), staged);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
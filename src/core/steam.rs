@@ -1,16 +1,93 @@
-use std::{path::PathBuf, process::Output};
+use std::{
+    io::IsTerminal,
+    path::{Path, PathBuf},
+    process::Output,
+    thread,
+    time::{Duration, Instant},
+};
 
+use chrono::{DateTime, Utc};
 use inquire::{Confirm, Password, Select, Text};
+use serde::{Deserialize, Serialize};
 
-use crate::utils::{
-    config::{LoginType, Platform},
-    run_with_output, Config, InstalledServer, ServerCache,
+use crate::{
+    core::{
+        aliases,
+        executables::{self, Executable, ExecutableKind},
+        jobs::{self, JOB_ENV},
+        update_report::{ServerUpdate, UpdateOutcome, UpdateReport},
+        Answers, GameProfile, ModManager, PlanStep, ProfileRegistry, ServerProcess, Snapshots,
+        StagedUpdate,
+    },
+    tr,
+    utils::{
+        config::{LoginType, Platform},
+        disk,
+        events::{self, Event},
+        fs, permissions, privilege, run_with_output,
+        steamcmd::{self, Capabilities, OutputLine, SteamCmdError},
+        vdf::Vdf,
+        Config, InstalledServer, ServerCache, ServerInstance, ServerRef, TimeoutConfig, Watchdog,
+    },
 };
 
+/// Number of executables offered as the server binary after an install
+const MAX_SUGGESTED_EXECUTABLES: usize = 10;
+/// Number of retries when Steam could not be reached
+const OUTAGE_RETRIES: u32 = 3;
+/// Delay before the first retry when Steam could not be reached, doubled for every further one
+const OUTAGE_DELAY: Duration = Duration::from_secs(30);
+/// File in the steamserv directory with the probed capabilities of SteamCMD
+const CAPABILITIES_FILE: &str = "cache/steamcmd_capabilities.json";
+/// Age after which the capabilities are probed again, as SteamCMD updates itself
+const CAPABILITIES_MAX_AGE: chrono::Duration = chrono::Duration::days(7);
+
 pub struct SteamCMD {
     pub login: (String, String),
     pub force_install_dir: String,
     pub app_update: u32,
+    pub branch: Option<String>,
+    /// Extra commands of the server, run with the ones of the config
+    pub extra_args: Vec<String>,
+}
+
+/// A build of an app on a branch
+#[derive(Debug, Clone)]
+pub struct AppBuild {
+    /// The build ID
+    pub id: String,
+    /// When the build was published on the branch
+    pub time: DateTime<Utc>,
+}
+
+/// The capabilities of SteamCMD with the binary they were probed for
+#[derive(Debug, Serialize, Deserialize)]
+struct CapabilityCache {
+    steamcmd_path: PathBuf,
+    probed: DateTime<Utc>,
+    capabilities: Capabilities,
+}
+
+/// The installed build of a server compared with the latest build of its branch
+#[derive(Debug, Clone)]
+pub struct UpdateCheck {
+    /// The installed build ID, `None` if the app manifest could not be read
+    pub installed: Option<String>,
+    /// The latest build of the branch
+    pub latest: AppBuild,
+    /// The estimated download size in bytes, `None` if the installed depots are unknown
+    pub download: Option<u64>,
+}
+
+impl UpdateCheck {
+    /// Check if the latest build is not installed yet
+    ///
+    /// # Returns
+    ///
+    /// True if an update is available
+    pub fn is_pending(&self) -> bool {
+        self.installed.as_deref() != Some(self.latest.id.as_str())
+    }
 }
 
 impl SteamCMD {
@@ -18,235 +95,1519 @@ impl SteamCMD {
     ///
     /// # Arguments
     ///
-    /// * `app_id` - The Steam App ID of the game server
-    /// * `server_name` - The name of the game server
-    /// * `username` - The username of the Steam account to use
+    /// * `app` - The alias or Steam App ID of the game server
+    /// * `server_name` - The name of the game server
+    /// * `username` - The username of the Steam account to use
+    /// * `answers` - Replay the answers of this file instead of asking
+    /// * `save_answers` - Save the answers to this file
+    ///
+    /// # Returns
+    ///
+    /// Ok if the game server was installed successfully
+    ///
+    /// # Errors
+    ///
+    /// If the alias is unknown, the answer file could not be read or written, or the game
+    /// server could not be installed
+    pub fn install(
+        app: Option<String>,
+        server_name: Option<String>,
+        username: Option<String>,
+        answers: Option<&Path>,
+        save_answers: Option<&Path>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut config = Config::load()?;
+        config.ensure_online(&tr!("action-install-server"))?;
+        let app_id = app.map(|app| aliases::resolve(&config, &app)).transpose()?;
+
+        let (force_install_dir, login, app_update, instance) = match answers {
+            Some(path) => {
+                let answers = Answers::load(path)?;
+                let force_install_dir = answers
+                    .install_root
+                    .join(&answers.name)
+                    .display()
+                    .to_string();
+                let login = Self::get_login(Some(answers.username))?;
+                (force_install_dir, login, answers.app_id, answers.instance)
+            }
+            None => {
+                println!("{}", tr!("install-welcome"));
+
+                let server_ref = server_name.as_deref().map(ServerRef::parse);
+                let force_install_dir = Self::get_force_install_dir(
+                    config.clone(),
+                    server_ref.as_ref().map(|r| r.server.clone()),
+                )?;
+
+                let login = match username {
+                    Some(username) => Self::get_login(Some(username))?,
+                    None => Self::get_login(None)?,
+                };
+
+                let app_update = match app_id {
+                    Some(app_id) => Self::get_app_update(Some(app_id))?,
+                    None => Self::get_app_update(None)?,
+                };
+
+                let instance = server_ref
+                    .and_then(|r| r.instance)
+                    .map(|instance| ServerInstance::new(&instance));
+                (force_install_dir, login, app_update, instance)
+            }
+        };
+
+        let server_name = force_install_dir
+            .split('/')
+            .next_back()
+            .unwrap()
+            .to_string();
+
+        // Instances share the install of their server, so an installed server
+        // only gets the new instance
+        if let Some(instance) = &instance {
+            if Self::add_to_installed(&mut config, &server_name, instance.clone())? {
+                config.save()?;
+                println!(
+                    "{}",
+                    tr!("instance-added", instance = instance.name.as_str())
+                );
+                return Ok(());
+            }
+        }
+
+        privilege::ensure_dirs(&[Path::new(&force_install_dir)])?;
+        disk::warn(&config)?;
+
+        if let Some(path) = save_answers {
+            Answers {
+                app_id: app_update,
+                name: server_name.clone(),
+                install_root: config.install_path.clone(),
+                branch: None,
+                username: login.0.clone(),
+                instance: instance.clone(),
+                mods: Vec::new(),
+                start: false,
+            }
+            .save(path)?;
+        }
+
+        let login_type = match login.0.as_str() {
+            "anonymous" => LoginType::Anonymous,
+            _ => LoginType::SteamAccount,
+        };
+
+        let install_path = PathBuf::from(&force_install_dir);
+        let interactive = answers.is_none() && std::io::stdin().is_terminal();
+
+        let mut server = InstalledServer::new(app_update, &server_name, install_path, login_type);
+        server.instances = instance.into_iter().collect();
+        // Kept with the server, so the updates use the same commands
+        if let Some(profile) = GameProfile::find(app_update) {
+            server.steamcmd_args = profile.install_commands(&server, server.instances.first());
+        }
+
+        let steamcmd = SteamCMD {
+            login,
+            force_install_dir,
+            app_update,
+            branch: None,
+            extra_args: server.steamcmd_args.clone(),
+        };
+
+        events::publish(Event::InstallStarted {
+            server: server_name.clone(),
+            app_id: app_update,
+        });
+        Self::download(steamcmd, &config)?;
+        server.install_date = chrono::Local::now().to_utc();
+        server.last_updated = server.install_date;
+
+        if interactive {
+            Self::suggest_executable(&server)?;
+        }
+
+        config.installed_servers.push(server);
+
+        config.save()?;
+
+        events::publish(Event::InstallFinished {
+            server: server_name,
+        });
+
+        Ok(())
+    }
+
+    /// Add an instance to a server that is already installed
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    /// * `server_name` - The name of the server
+    /// * `instance` - The instance to add
+    ///
+    /// # Returns
+    ///
+    /// True if the server is installed and got the instance, false if the
+    /// server has to be installed first
+    ///
+    /// # Errors
+    ///
+    /// If the instance already exists or its port is used
+    fn add_to_installed(
+        config: &mut Config,
+        server_name: &str,
+        instance: ServerInstance,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        if config.find_server(server_name).is_none() {
+            return Ok(false);
+        }
+        config.add_instance(server_name, instance)?;
+        Ok(true)
+    }
+
+    /// Update a game server
+    ///
+    /// # Arguments
+    ///
+    /// * `server_name` - The name of the game server, instances share the install of their server
+    /// * `force` - Update even if workshop mods are likely incompatible with the new build
+    /// * `yes` - Update without asking for confirmation
+    /// * `staged` - Download the update into a copy of the install while the server
+    ///   keeps running, the copy is swapped in on the next start
+    ///
+    /// # Returns
+    ///
+    /// Ok if the game server was updated successfully
+    ///
+    /// # Errors
+    ///
+    /// If the game server could not be updated or mods are likely incompatible without `force`
+    pub async fn update(
+        server_name: Option<String>,
+        force: bool,
+        yes: bool,
+        staged: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut config = Config::load()?;
+        config.ensure_online(&tr!("action-update-server"))?;
+        let servers: Vec<InstalledServer> = config.installed_servers.clone();
+
+        let server_names = servers
+            .iter()
+            .map(|s| s.name.clone())
+            .collect::<Vec<String>>();
+
+        let server_ref = server_name.as_deref().map(ServerRef::parse);
+        if let Some(ServerRef {
+            server,
+            instance: Some(instance),
+        }) = &server_ref
+        {
+            println!(
+                "{}",
+                tr!(
+                    "update-instance-shared",
+                    instance = instance,
+                    server = server
+                )
+            );
+        }
+
+        let server_name = match server_ref.map(|r| r.server) {
+            Some(server_name) => {
+                if server_names.contains(&server_name) {
+                    server_name
+                } else {
+                    Text::new(&tr!("prompt-server-name"))
+                        .with_placeholder(&tr!("prompt-server-name-placeholder"))
+                        .with_help_message(&tr!("prompt-server-name-help"))
+                        .prompt()?
+                }
+            }
+            None => {
+                let server_name = Select::new(&tr!("update-select"), server_names)
+                    .with_help_message(&tr!("update-select-help"))
+                    .prompt()?;
+                server_name
+            }
+        };
+
+        let server = servers.iter().find(|s| s.name == server_name).unwrap();
+
+        let login = Self::login_for(server)?;
+
+        let latest = match Self::check_update(&config, server) {
+            Ok(check) if check.is_pending() => {
+                if !server.mods.is_empty() {
+                    Self::check_mods(&config, server, &check.latest, force).await?;
+                }
+                if !yes && !Self::confirm_update(&config, server, &check)? {
+                    println!("{}", tr!("update-cancelled"));
+                    return Ok(());
+                }
+                Some(check.latest.id)
+            }
+            Ok(check) => {
+                println!("{}", tr!("update-up-to-date", server = server.name));
+                Some(check.latest.id)
+            }
+            Err(e) => {
+                println!("{}", tr!("update-check-failed", error = e.to_string()));
+                None
+            }
+        };
+
+        Self::apply_update(&mut config, server, login, latest, staged, yes)
+    }
+
+    /// Update all installed servers and report what happened to each of them
+    ///
+    /// The servers are updated one after another in the order of their priority
+    /// and `update_after` dependencies. Servers on the latest build are not
+    /// validated, servers with connected players are skipped. The report is
+    /// saved and published, so the webhook can send it.
+    ///
+    /// # Arguments
+    ///
+    /// * `force` - Update even if workshop mods are likely incompatible with the new build
+    /// * `yes` - Update without asking for confirmation
+    /// * `staged` - Stage the updates instead of updating in place
+    ///
+    /// # Returns
+    ///
+    /// Ok if no update failed
+    ///
+    /// # Errors
+    ///
+    /// If the dependencies of the servers form a cycle, the report could not be
+    /// saved, or the update of a server failed
+    pub async fn update_all(
+        force: bool,
+        yes: bool,
+        staged: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut config = Config::load()?;
+        config.ensure_online(&tr!("action-update-server"))?;
+        let servers: Vec<InstalledServer> = config.installed_servers.clone();
+        let order = jobs::plan(servers.iter().map(PlanStep::update).collect())?;
+
+        if !yes
+            && std::io::stdin().is_terminal()
+            && !config.confirm.update.ask(
+                &tr!("update-all-confirm", count = order.len()),
+                true,
+                || None,
+            )?
+        {
+            println!("{}", tr!("update-cancelled"));
+            return Ok(());
+        }
+
+        let started = Utc::now();
+        let timer = Instant::now();
+        let mut updates = Vec::new();
+        // Once Steam could not be reached, the other servers are not tried
+        // either, so an outage is reported once instead of for every server
+        let mut outage: Option<String> = None;
+        for step in order {
+            let server = servers.iter().find(|s| s.name == step.name).unwrap();
+            let outcome = match &outage {
+                Some(reason) => UpdateOutcome::Deferred {
+                    reason: reason.clone(),
+                },
+                None => match Self::update_one(&mut config, server, force, staged).await {
+                    Ok(outcome) => outcome,
+                    Err(e) if is_outage(e.as_ref()) => {
+                        outage = Some(e.to_string());
+                        UpdateOutcome::Deferred {
+                            reason: e.to_string(),
+                        }
+                    }
+                    Err(e) => UpdateOutcome::Failed {
+                        reason: e.to_string(),
+                    },
+                },
+            };
+            updates.push(ServerUpdate {
+                server: server.name.clone(),
+                outcome,
+            });
+        }
+
+        let report = UpdateReport {
+            started,
+            seconds: timer.elapsed().as_secs_f64(),
+            servers: updates,
+        };
+        report.save()?;
+        let failed = report.count(|o| matches!(o, UpdateOutcome::Failed { .. }));
+        let deferred = report.count(|o| matches!(o, UpdateOutcome::Deferred { .. }));
+        events::publish(Event::UpdatesFinished { report });
+
+        if failed > 0 {
+            return Err(tr!("update-all-failed", count = failed).into());
+        }
+        if deferred > 0 {
+            return Err(tr!("update-all-deferred", count = deferred).into());
+        }
+        Ok(())
+    }
+
+    /// Update a server as part of an update of all servers
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    /// * `server` - The installed server
+    /// * `force` - Update even if workshop mods are likely incompatible with the new build
+    /// * `staged` - Stage the update instead of updating in place
+    ///
+    /// # Returns
+    ///
+    /// What was done with the server
+    ///
+    /// # Errors
+    ///
+    /// If the update could not be checked or failed
+    async fn update_one(
+        config: &mut Config,
+        server: &InstalledServer,
+        force: bool,
+        staged: bool,
+    ) -> Result<UpdateOutcome, Box<dyn std::error::Error>> {
+        let check = Self::check_update(config, server)?;
+        if !check.is_pending() {
+            return Ok(UpdateOutcome::Current {
+                build: check.latest.id,
+            });
+        }
+        if let Some(players) = Self::players_online(server).filter(|players| *players > 0) {
+            return Ok(UpdateOutcome::Skipped { players });
+        }
+        if !server.mods.is_empty() {
+            Self::check_mods(config, server, &check.latest, force).await?;
+        }
+
+        let login = Self::login_for(server)?;
+        Self::apply_update(
+            config,
+            server,
+            login,
+            Some(check.latest.id.clone()),
+            staged,
+            true,
+        )?;
+        Ok(UpdateOutcome::Updated {
+            from: check.installed,
+            to: check.latest.id,
+            download: check.download,
+        })
+    }
+
+    /// Download an update in place or staged, with the servers stopped and
+    /// started again around an in-place update
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    /// * `server` - The installed server
+    /// * `login` - The login for SteamCMD
+    /// * `latest` - The build ID the update should have, if it is known
+    /// * `staged` - Stage the update instead of updating in place
+    /// * `yes` - The update runs without questions
+    ///
+    /// # Returns
+    ///
+    /// Ok if the server was updated
+    ///
+    /// # Errors
+    ///
+    /// If the snapshot, the download or the restart of the servers failed
+    fn apply_update(
+        config: &mut Config,
+        server: &InstalledServer,
+        login: (String, String),
+        latest: Option<String>,
+        staged: bool,
+        yes: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if staged {
+            return Self::update_staged(config, server, login, latest);
+        }
+
+        let steamcmd = SteamCMD {
+            login,
+            force_install_dir: server.install_path.display().to_string(),
+            app_update: server.app_id,
+            branch: server.branch.clone(),
+            extra_args: server.steamcmd_args.clone(),
+        };
+
+        let snapshots = Self::wants_snapshot(config, server, yes)?;
+        let stopped = Self::stop_running(server)?;
+        let result = Self::update_in_place(config, server, steamcmd, snapshots.as_ref());
+        // The stopped servers are started again whether the update worked or not
+        let started = Self::start_stopped(config, server, &stopped);
+        result.and(started)
+    }
+
+    /// Take the snapshot and download the update of a stopped server
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    /// * `server` - The installed server
+    /// * `steamcmd` - The SteamCMD configuration of the update
+    /// * `snapshots` - The snapshots of the install, if a snapshot is taken
+    ///
+    /// # Returns
+    ///
+    /// Ok if the server was updated
+    ///
+    /// # Errors
+    ///
+    /// If the snapshot or the download failed
+    fn update_in_place(
+        config: &mut Config,
+        server: &InstalledServer,
+        steamcmd: SteamCMD,
+        snapshots: Option<&Snapshots>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(snapshots) = snapshots {
+            Self::take_snapshot(config, snapshots)?;
+        }
+
+        events::publish(Event::UpdateStarted {
+            server: server.name.clone(),
+        });
+        Self::execute_install_command(steamcmd, config)?;
+
+        if let Some(installed) = config.find_server_mut(&server.name) {
+            installed.update_timestamp();
+            config.save()?;
+        }
+
+        events::publish(Event::UpdateFinished {
+            server: server.name.clone(),
+        });
+
+        Ok(())
+    }
+
+    /// Decide if a filesystem snapshot is taken before an update
+    ///
+    /// Without a setting in the config, the user is asked in the terminal.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    /// * `server` - The installed server
+    /// * `yes` - The update runs without questions
+    ///
+    /// # Returns
+    ///
+    /// The snapshots of the install, or `None` if no snapshot is taken or the
+    /// filesystem does not support them
+    ///
+    /// # Errors
+    ///
+    /// If the question could not be asked
+    fn wants_snapshot(
+        config: &Config,
+        server: &InstalledServer,
+        yes: bool,
+    ) -> Result<Option<Snapshots>, Box<dyn std::error::Error>> {
+        let Ok(snapshots) = Snapshots::for_server(server) else {
+            return Ok(None);
+        };
+
+        let take = match config.snapshots.before_update {
+            Some(take) => take,
+            None if !yes && std::io::stdin().is_terminal() => {
+                Confirm::new(&tr!("update-snapshot-offer", fs = snapshots.filesystem()))
+                    .with_help_message(&tr!("update-snapshot-offer-help"))
+                    .with_default(true)
+                    .prompt()?
+            }
+            None => false,
+        };
+        Ok(take.then_some(snapshots))
+    }
+
+    /// Take a snapshot of an install and delete the ones beyond the configured number
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    /// * `snapshots` - The snapshots of the install
+    ///
+    /// # Returns
+    ///
+    /// Ok if the snapshot was taken
+    ///
+    /// # Errors
+    ///
+    /// If the snapshot could not be taken or an old one could not be deleted
+    fn take_snapshot(
+        config: &Config,
+        snapshots: &Snapshots,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let snapshot = snapshots.create()?;
+        println!("{}", tr!("snapshot-created", name = snapshot.name));
+        for snapshot in snapshots.prune(config.snapshots.keep.max(1))? {
+            println!("{}", tr!("snapshot-deleted", name = snapshot.name));
+        }
+        Ok(())
+    }
+
+    /// Download an update into a copy of the install directory
+    ///
+    /// The running server is not touched. The validated update is swapped in
+    /// right away if the server is stopped, otherwise on its next start.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    /// * `server` - The installed server
+    /// * `login` - The login for SteamCMD
+    /// * `latest` - The build ID the update should have, if it is known
+    ///
+    /// # Returns
+    ///
+    /// Ok if the update was staged
+    ///
+    /// # Errors
+    ///
+    /// If the install could not be copied, or the update failed or is invalid
+    fn update_staged(
+        config: &mut Config,
+        server: &InstalledServer,
+        login: (String, String),
+        latest: Option<String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        println!("{}", tr!("update-staging-copy", server = server.name));
+        let (staged_dir, copied) = StagedUpdate::prepare(server)?;
+
+        let steamcmd = SteamCMD {
+            login,
+            force_install_dir: staged_dir.display().to_string(),
+            app_update: server.app_id,
+            branch: server.branch.clone(),
+            extra_args: server.steamcmd_args.clone(),
+        };
+        events::publish(Event::UpdateStarted {
+            server: server.name.clone(),
+        });
+        if let Err(e) = Self::execute_install_command(steamcmd, config) {
+            StagedUpdate::discard(server)?;
+            return Err(e);
+        }
+        StagedUpdate::validate(server, latest, copied)?;
+
+        if let Some(installed) = config.find_server_mut(&server.name) {
+            installed.update_timestamp();
+            config.save()?;
+        }
+        events::publish(Event::UpdateFinished {
+            server: server.name.clone(),
+        });
+
+        if !StagedUpdate::apply(server)? {
+            println!("{}", tr!("update-staged", server = server.name));
+        }
+
+        Ok(())
+    }
+
+    /// Uninstall a game server
+    ///
+    /// # Arguments
+    ///
+    /// * `server_name` - The name of the game server, or `server/instance` to only remove an instance
+    ///
+    /// # Returns
+    ///
+    /// Ok if the game server was uninstalled successfully
+    ///
+    /// # Errors
+    ///
+    /// If the game server could not be uninstalled
+    pub fn uninstall(server_name: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+        let mut config = Config::load()?;
+        let servers: Vec<InstalledServer> = config.installed_servers.clone();
+
+        let server_names = servers
+            .iter()
+            .map(|s| s.name.clone())
+            .collect::<Vec<String>>();
+
+        let server_ref = server_name.as_deref().map(ServerRef::parse);
+        if let Some(ServerRef {
+            server,
+            instance: Some(instance),
+        }) = &server_ref
+        {
+            let target = ServerRef::parse(&format!("{}/{}", server, instance));
+            let confirm = config.confirm.uninstall.ask(
+                &tr!(
+                    "instance-remove-confirm",
+                    instance = instance,
+                    server = server
+                ),
+                false,
+                || {
+                    let (installed, found) = config.resolve(&target).ok()?;
+                    ServerProcess::new(&target).ok()?.players(installed, found)
+                },
+            )?;
+            if confirm {
+                config.remove_instance(server, instance)?;
+                config.save()?;
+                println!("{}", tr!("instance-removed", instance = instance));
+            }
+            return Ok(());
+        }
+
+        let server_name = match server_ref.map(|r| r.server) {
+            Some(server_name) => {
+                if server_names.contains(&server_name) {
+                    server_name
+                } else {
+                    Text::new(&tr!("prompt-server-name"))
+                        .with_placeholder(&tr!("prompt-server-name-placeholder"))
+                        .with_help_message(&tr!("prompt-server-name-help"))
+                        .prompt()?
+                }
+            }
+            None => {
+                let server_name = Select::new(&tr!("uninstall-select"), server_names)
+                    .with_help_message(&tr!("uninstall-select-help"))
+                    .prompt()?;
+                server_name
+            }
+        };
+
+        let server = servers.iter().find(|s| s.name == server_name).unwrap();
+
+        let force_install_dir = server.install_path.clone();
+
+        let confirm = config.confirm.uninstall.ask(
+            &tr!("uninstall-confirm", server = server_name),
+            false,
+            || Self::players_online(server),
+        )?;
+
+        if confirm {
+            StagedUpdate::discard(server)?;
+            std::fs::remove_dir_all(force_install_dir)?;
+            config.installed_servers.retain(|s| s.name != server_name);
+            config.save()?;
+            println!("{}", tr!("uninstall-success"));
+        }
+
+        Ok(())
+    }
+
+    /// Get the login information of an installed server
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The installed server
+    ///
+    /// # Returns
+    ///
+    /// The login information, prompting for the credentials of Steam accounts
+    ///
+    /// # Errors
+    ///
+    /// If the credentials could not be read
+    pub fn login_for(
+        server: &InstalledServer,
+    ) -> Result<(String, String), Box<dyn std::error::Error>> {
+        match server.login_type {
+            LoginType::Anonymous => Ok(("anonymous".to_string(), "".to_string())),
+            LoginType::SteamAccount => {
+                let username = Text::new(&tr!("prompt-steam-username")).prompt()?;
+                let password = Password::new(&tr!("prompt-steam-password"))
+                    .without_confirmation()
+                    .prompt()?;
+                Ok((username, password))
+            }
+        }
+    }
+
+    /// Check the workshop mods of a server against the build an update would install
+    ///
+    /// If the check itself fails, the update goes ahead with a warning.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    /// * `server` - The installed server with mods
+    /// * `build` - The build the update installs
+    /// * `force` - Only warn about likely incompatible mods
+    ///
+    /// # Returns
+    ///
+    /// Ok if the update can go ahead
+    ///
+    /// # Errors
+    ///
+    /// If mods are likely incompatible and `force` is not set
+    async fn check_mods(
+        config: &Config,
+        server: &InstalledServer,
+        build: &AppBuild,
+        force: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let warnings = match ModManager::check_compatibility(config, server, build).await {
+            Ok(warnings) => warnings,
+            Err(e) => {
+                println!("{}", tr!("update-mods-check-failed", error = e.to_string()));
+                return Ok(());
+            }
+        };
+        if warnings.is_empty() {
+            return Ok(());
+        }
+
+        println!(
+            "{}",
+            tr!(
+                "update-mods-incompatible",
+                server = server.name,
+                build = build.id,
+                date = build.time.format("%Y-%m-%d %H:%M").to_string()
+            )
+        );
+        for warning in &warnings {
+            println!(
+                "  {:<14} {:<40} {}",
+                warning.id, warning.title, warning.reason
+            );
+        }
+
+        if !force {
+            return Err(tr!("update-mods-force").into());
+        }
+        Ok(())
+    }
+
+    /// Offer the executables of a new install as the server binary
+    ///
+    /// Nothing is asked if the game profile of the app starts a binary of the
+    /// install. Otherwise the chosen executable is saved as a user profile, which
+    /// only overrides the executable of a built-in profile.
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The installed server
+    ///
+    /// # Returns
+    ///
+    /// Ok if no executable was needed, chosen or found
+    ///
+    /// # Errors
+    ///
+    /// If the prompt failed or the profile could not be saved
+    fn suggest_executable(server: &InstalledServer) -> Result<(), Box<dyn std::error::Error>> {
+        let profile = GameProfile::find(server.app_id);
+        match &profile {
+            Some(profile) if server.install_path.join(&profile.executable).is_file() => {
+                return Ok(());
+            }
+            Some(profile) => println!(
+                "{}",
+                tr!(
+                    "install-executable-missing",
+                    profile = profile.name.as_str(),
+                    executable = profile.executable.as_str()
+                )
+            ),
+            None => println!("{}", tr!("install-executable-no-profile")),
+        }
+
+        let found: Vec<Executable> = executables::suggest(&server.install_path)
+            .into_iter()
+            .take(MAX_SUGGESTED_EXECUTABLES)
+            .collect();
+        if found.is_empty() {
+            println!("{}", tr!("profile-create-no-executables"));
+            return Ok(());
+        }
+        let mut choices: Vec<String> = found.iter().map(ToString::to_string).collect();
+        choices.push(tr!("install-executable-skip"));
+        let choice = Select::new(&tr!("install-executable-select"), choices)
+            .with_help_message(&tr!("install-executable-select-help"))
+            .raw_prompt()?;
+        let Some(executable) = found.get(choice.index) else {
+            return Ok(());
+        };
+
+        let mut entry = toml::Table::new();
+        entry.insert("app_id".to_string(), i64::from(server.app_id).into());
+        if profile.is_none() {
+            entry.insert("name".to_string(), server.name.as_str().into());
+        }
+        entry.insert(
+            "executable".to_string(),
+            executable.path.display().to_string().into(),
+        );
+        let runtime = match executable.kind {
+            ExecutableKind::Pe => "windows",
+            _ => "native",
+        };
+        entry.insert("runtime".to_string(), runtime.into());
+        let mut file = toml::Table::new();
+        file.insert(
+            "profile".to_string(),
+            toml::Value::Array(vec![toml::Value::Table(entry)]),
+        );
+        let content = toml::to_string_pretty(&file)?;
+
+        let name = profile.map_or_else(|| server.name.clone(), |p| p.name);
+        let path = ProfileRegistry::dir()?.join(format!("{}.toml", aliases::slug(&name)));
+        if path.exists() {
+            println!(
+                "{}",
+                tr!("install-executable-exists", path = path.display())
+            );
+            println!("\n{}", content);
+            return Ok(());
+        }
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, content)?;
+        println!("{}", tr!("profile-create-saved", path = path.display()));
+        println!(
+            "{}",
+            tr!(
+                "install-executable-next",
+                path = server.install_path.display()
+            )
+        );
+        Ok(())
+    }
+
+    /// Show the pending update of a server and ask whether to install it
+    ///
+    /// Without a terminal the update is installed without asking.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration with the confirmation policy
+    /// * `server` - The installed server
+    /// * `check` - The pending update
+    ///
+    /// # Returns
+    ///
+    /// True if the update should be installed
+    ///
+    /// # Errors
+    ///
+    /// If the prompt failed
+    fn confirm_update(
+        config: &Config,
+        server: &InstalledServer,
+        check: &UpdateCheck,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        println!(
+            "{}",
+            tr!(
+                "update-available",
+                server = server.name,
+                installed = check.installed.clone().unwrap_or_else(|| "-".to_string()),
+                build = check.latest.id,
+                date = check.latest.time.format("%Y-%m-%d %H:%M").to_string()
+            )
+        );
+        match check.download {
+            Some(size) => println!(
+                "{}",
+                tr!("update-download-size", size = fs::format_size(size))
+            ),
+            None => println!("{}", tr!("update-download-unknown")),
+        }
+
+        if !std::io::stdin().is_terminal() {
+            return Ok(true);
+        }
+        config.confirm.update.ask(&tr!("update-confirm"), true, || {
+            Self::players_online(server)
+        })
+    }
+
+    /// Count the players on a server and its instances
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The installed server
+    ///
+    /// # Returns
+    ///
+    /// The number of players, or `None` if nothing runs or could be queried
+    fn players_online(server: &InstalledServer) -> Option<u32> {
+        let targets = std::iter::once((server.name.clone(), None)).chain(
+            server
+                .instances
+                .iter()
+                .map(|i| (format!("{}/{}", server.name, i.name), Some(i))),
+        );
+        targets
+            .filter_map(|(target, instance)| {
+                ServerProcess::new(&ServerRef::parse(&target))
+                    .ok()?
+                    .players(server, instance)
+            })
+            .reduce(|a, b| a + b)
+    }
+
+    /// Get the app info of an app from SteamCMD
+    ///
+    /// The app info is public, so SteamCMD logs in anonymously.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    /// * `app_id` - The Steam App ID
+    ///
+    /// # Returns
+    ///
+    /// The app info with the sections `common`, `config` and `depots`
+    ///
+    /// # Errors
+    ///
+    /// If SteamCMD could not be run or printed no app info
+    pub fn app_info(config: &Config, app_id: u32) -> Result<Vdf, Box<dyn std::error::Error>> {
+        let child = Self::command(config)
+            .arg("+login anonymous")
+            .arg("+app_info_update 1")
+            .arg(format!("+app_info_print {}", app_id))
+            .arg("+quit")
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()?;
+
+        let watchdog =
+            Watchdog::start(&child, TimeoutConfig::limit(config.timeouts.platform_check));
+        let output = child.wait_with_output()?;
+        watchdog.finish("steamcmd +app_info_print")?;
+
+        Vdf::find(
+            &String::from_utf8_lossy(&output.stdout),
+            &app_id.to_string(),
+        )
+        .ok_or_else(|| tr!("app-info-unavailable", app_id = app_id).into())
+    }
+
+    /// Compare the installed build of a server with the latest build of its branch
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    /// * `server` - The installed server
+    ///
+    /// # Returns
+    ///
+    /// The installed and the latest build with the estimated download size
+    ///
+    /// # Errors
+    ///
+    /// If the app info could not be read or has no build for the branch
+    pub fn check_update(
+        config: &Config,
+        server: &InstalledServer,
+    ) -> Result<UpdateCheck, Box<dyn std::error::Error>> {
+        let info = Self::app_info(config, server.app_id)?;
+        let branch = server.branch.as_deref().unwrap_or("public");
+        let no_branch = || {
+            tr!(
+                "app-info-no-branch",
+                app_id = server.app_id,
+                branch = branch
+            )
+        };
+        let latest = info
+            .get(&format!("depots/branches/{}", branch))
+            .ok_or_else(no_branch)?;
+
+        let id = latest.value("buildid").unwrap_or_default().to_string();
+        let time = latest
+            .value("timeupdated")
+            .and_then(|time| time.parse().ok())
+            .and_then(|time| DateTime::from_timestamp(time, 0))
+            .ok_or_else(no_branch)?;
+
+        let manifest = Self::app_manifest(server);
+        let installed = manifest
+            .as_ref()
+            .and_then(|m| m.value("AppState/buildid"))
+            .map(str::to_string);
+        let download = manifest
+            .as_ref()
+            .and_then(|m| m.get("AppState/InstalledDepots"))
+            .map(|depots| download_size(&info, depots, branch));
+
+        Ok(UpdateCheck {
+            installed,
+            latest: AppBuild { id, time },
+            download,
+        })
+    }
+
+    /// Read the app manifest SteamCMD keeps in the install directory of a server
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The installed server
+    ///
+    /// # Returns
+    ///
+    /// The manifest with the installed build and depots, or `None` if it could not be read
+    fn app_manifest(server: &InstalledServer) -> Option<Vdf> {
+        let manifest = server
+            .install_path
+            .join("steamapps")
+            .join(format!("appmanifest_{}.acf", server.app_id));
+        Vdf::parse(&std::fs::read_to_string(manifest).ok()?)
+    }
+
+    /// Stop the running processes of a server and its instances, e.g. before an update
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The installed server
     ///
     /// # Returns
     ///
-    /// Ok if the game server was installed successfully
+    /// The servers and instances that were stopped
     ///
     /// # Errors
     ///
-    /// If the game server could not be installed
-    pub fn install(
-        app_id: Option<u32>,
-        server_name: Option<String>,
-        username: Option<String>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        let mut config = Config::load()?;
+    /// If a process could not be stopped
+    pub fn stop_running(
+        server: &InstalledServer,
+    ) -> Result<Vec<ServerRef>, Box<dyn std::error::Error>> {
+        let mut stopped = Vec::new();
 
-        println!("Welcome to your installation guide");
+        let targets = std::iter::once((ServerRef::parse(&server.name), None)).chain(
+            server.instances.iter().map(|i| {
+                (
+                    ServerRef::parse(&format!("{}/{}", server.name, i.name)),
+                    Some(i),
+                )
+            }),
+        );
 
-        let force_install_dir = Self::get_force_install_dir(config.clone(), server_name)?;
+        for (target, instance) in targets {
+            if ServerProcess::new(&target)?.stop(server, instance)? {
+                stopped.push(target);
+            }
+        }
 
-        let login = match username {
-            Some(username) => Self::get_login(Some(username))?,
-            None => Self::get_login(None)?,
-        };
+        Ok(stopped)
+    }
 
-        let app_update = match app_id {
-            Some(app_id) => Self::get_app_update(Some(app_id))?,
-            None => Self::get_app_update(None)?,
-        };
+    /// Start the servers and instances again that were stopped, e.g. for an update
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    /// * `server` - The installed server
+    /// * `stopped` - The servers and instances that were stopped
+    ///
+    /// # Returns
+    ///
+    /// Ok if all processes were started
+    ///
+    /// # Errors
+    ///
+    /// If a process could not be started
+    pub fn start_stopped(
+        config: &Config,
+        server: &InstalledServer,
+        stopped: &[ServerRef],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        for target in stopped {
+            let instance = target
+                .instance
+                .as_deref()
+                .and_then(|name| server.find_instance(name));
+            ServerProcess::new(target)?.start(config, server, instance)?;
+        }
 
-        let server_name = force_install_dir.split('/').last().unwrap().to_string();
+        Ok(())
+    }
 
-        let login_type = match login.clone() {
-            (username, _) => match username.as_str() {
-                "anonymous" => LoginType::Anonymous,
-                _ => LoginType::SteamAccount,
-            },
+    /// Create a SteamCMD command with the network settings applied
+    ///
+    /// SteamCMD runs with a fixed locale, so its output can be matched. In a
+    /// background job of the daemon, it runs with the lower priority of the
+    /// disk settings. On hosts that are not x86-64, it runs in the configured
+    /// emulator.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    ///
+    /// # Returns
+    ///
+    /// The command to run SteamCMD
+    pub fn command(config: &Config) -> std::process::Command {
+        let mut command = match &config.emulator {
+            Some(emulator) => {
+                let mut command = std::process::Command::new(emulator);
+                command.arg(&config.steamcmd_path);
+                command
+            }
+            None => std::process::Command::new(&config.steamcmd_path),
         };
+        command.envs(config.network.proxy_env());
+        steamcmd::normalize_env(&mut command);
+        if std::env::var_os(JOB_ENV).is_some() {
+            config.disk.background.apply(&mut command);
+        }
+        command
+    }
 
-        let install_path = PathBuf::from(&force_install_dir);
+    /// Get the capabilities of SteamCMD
+    ///
+    /// They are probed once and cached for a week, or until another SteamCMD is
+    /// configured. If the probe fails or steamserv is offline, a current build
+    /// is assumed.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    ///
+    /// # Returns
+    ///
+    /// The capabilities of SteamCMD
+    pub fn capabilities(config: &Config) -> Capabilities {
+        let path = Config::data_dir().map(|dir| dir.join(CAPABILITIES_FILE));
+        let cached = path
+            .as_ref()
+            .ok()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str::<CapabilityCache>(&content).ok())
+            .filter(|cache| {
+                cache.steamcmd_path == config.steamcmd_path
+                    && Utc::now() - cache.probed < CAPABILITIES_MAX_AGE
+            });
+        if let Some(cache) = cached {
+            return cache.capabilities;
+        }
+        if config.is_offline() {
+            return Capabilities::default();
+        }
 
-        match Self::ceck_platform(config.clone(), app_update, Some(login.clone())) {
-            Ok(platforms) => {
-                if platforms.is_empty() {
-                    return Err("Could not detect the platform".into());
+        match Self::probe_capabilities(config) {
+            Ok(Some(capabilities)) => {
+                let cache = CapabilityCache {
+                    steamcmd_path: config.steamcmd_path.clone(),
+                    probed: Utc::now(),
+                    capabilities: capabilities.clone(),
+                };
+                // A cache that cannot be written only means the next run probes again
+                if let (Ok(path), Ok(content)) = (path, serde_json::to_string_pretty(&cache)) {
+                    let _ = fs::write_atomic(&path, content.as_bytes());
                 }
+                capabilities
             }
+            Ok(None) => Capabilities::default(),
             Err(e) => {
-                return Err(e);
+                println!("{}", tr!("steamcmd-probe-failed", error = e.to_string()));
+                Capabilities::default()
             }
         }
+    }
 
-        let steamcmd = SteamCMD {
-            login,
-            force_install_dir,
-            app_update,
-        };
-
-        Self::execute_install_command(steamcmd, config.steamcmd_path.clone())?;
-
-        let server = InstalledServer {
-            app_id: app_update,
-            name: server_name,
-            install_path,
-            install_date: chrono::Local::now().to_utc(),
-            last_updated: chrono::Local::now().to_utc(),
-            port: None,
-            login_type,
-        };
-
-        config.installed_servers.push(server);
+    /// Ask SteamCMD for the commands it knows
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    ///
+    /// # Returns
+    ///
+    /// The capabilities, or `None` if the output did not list the commands
+    ///
+    /// # Errors
+    ///
+    /// If SteamCMD could not be run or timed out
+    fn probe_capabilities(
+        config: &Config,
+    ) -> Result<Option<Capabilities>, Box<dyn std::error::Error>> {
+        println!("{}", tr!("steamcmd-probing"));
+        let child = Self::command(config)
+            .args(["+find app_", "+find workshop_", "+quit"])
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::null())
+            .spawn()?;
 
-        config.save()?;
+        let watchdog =
+            Watchdog::start(&child, TimeoutConfig::limit(config.timeouts.platform_check));
+        let output = child.wait_with_output()?;
+        watchdog.finish("steamcmd +find")?;
+        Ok(Capabilities::parse(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
 
-        println!("Server installation successfully.");
+    /// Check that the account owns the app and the app supports a platform, then download it
+    ///
+    /// # Arguments
+    ///
+    /// * `steamcmd` - The SteamCMD configuration
+    /// * `config` - The configuration
+    ///
+    /// # Returns
+    ///
+    /// Ok if the app was downloaded
+    ///
+    /// # Errors
+    ///
+    /// If the account does not own the app, no platform was detected or the download failed
+    pub fn download(steamcmd: SteamCMD, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+        // Old builds without license requests report a missing license on download
+        if steamcmd.login.0 != "anonymous" && Self::capabilities(config).license_request {
+            Self::check_license(&steamcmd, config)?;
+        }
+        let platforms = Self::ceck_platform(
+            config.clone(),
+            steamcmd.app_update,
+            Some(steamcmd.login.clone()),
+        )?;
+        if platforms.is_empty() {
+            return Err(tr!("install-platform-undetected").into());
+        }
 
-        Ok(())
+        Self::seed(&steamcmd, config)?;
+        Self::execute_install_command(steamcmd, config)
     }
 
-    /// Update a game server
+    /// Copy the files of an installed server of the same app into a new install
+    ///
+    /// SteamCMD then only validates the copy and downloads what differs, which
+    /// makes provisioning many servers of the same game fast. The copy is a
+    /// reflink where the filesystem supports it. Saves of the game profile,
+    /// unfinished downloads and the state of steamserv are not carried over. A
+    /// running server may write its files during the copy, so only stopped
+    /// servers are copied. If the copy fails, everything is downloaded.
     ///
     /// # Arguments
     ///
-    /// * `server_name` - The name of the game server
+    /// * `steamcmd` - The SteamCMD configuration of the new install
+    /// * `config` - The configuration
     ///
     /// # Returns
     ///
-    /// Ok if the game server was updated successfully
+    /// Ok if the files were copied or there was nothing to copy
     ///
     /// # Errors
     ///
-    /// If the game server could not be updated
-    pub fn update(server_name: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
-        let mut config = Config::load()?;
-        let servers: Vec<InstalledServer> = config.installed_servers.clone();
-
-        let server_names = servers
+    /// If `cp` could not be started or a save could not be removed from the copy
+    fn seed(steamcmd: &SteamCMD, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+        let target = Path::new(&steamcmd.force_install_dir);
+        if !config.network.depot_cache.seed_from_local
+            || std::fs::read_dir(target).is_ok_and(|mut entries| entries.next().is_some())
+        {
+            return Ok(());
+        }
+        let candidates: Vec<&InstalledServer> = config
+            .installed_servers
             .iter()
-            .map(|s| s.name.clone())
-            .collect::<Vec<String>>();
-
-        let server_name = match server_name {
-            Some(server_name) => {
-                if server_names.contains(&server_name) {
-                    server_name
-                } else {
-                    Text::new("Please enter the name of the game server:")
-                        .with_placeholder("e.g. TestServer")
-                        .with_help_message("It's the name for your game server folder.")
-                        .prompt()?
-                }
-            }
-            None => {
-                let server_name =
-                    Select::new("Please select the game server to update", server_names)
-                        .with_help_message("Which of this game servers you will update?")
-                        .prompt()?;
-                server_name
-            }
-        };
-
-        let server = servers.iter().find(|s| s.name == server_name).unwrap();
-
-        let login = match server.login_type {
-            LoginType::Anonymous => ("anonymous".to_string(), "".to_string()),
-            LoginType::SteamAccount => {
-                let username = Text::new("Please enter your steam username:").prompt()?;
-                let password = Password::new("Please enter your password for your steam account.")
-                    .without_confirmation()
-                    .prompt()?;
-                (username, password)
+            .filter(|s| {
+                s.app_id == steamcmd.app_update
+                    && s.branch == steamcmd.branch
+                    && s.install_path != target
+                    && s.install_path.is_dir()
+            })
+            .collect();
+        let Some(source) = candidates.iter().find(|s| !Self::is_running(s)) else {
+            if let Some(running) = candidates.first() {
+                println!(
+                    "{}",
+                    tr!("install-seed-running", server = running.name.as_str())
+                );
             }
+            return Ok(());
         };
 
-        let force_install_dir = server.install_path.clone();
-        let app_update = server.app_id;
-
-        let steamcmd = SteamCMD {
-            login,
-            force_install_dir: force_install_dir.display().to_string(),
-            app_update,
-        };
-
-        Self::execute_install_command(steamcmd, config.steamcmd_path.clone())?;
-
-        if let Some(server) = config
-            .installed_servers
-            .iter_mut()
-            .find(|s| s.name == server_name)
-        {
-            server.update_timestamp();
-            config.save()?;
+        println!(
+            "{}",
+            tr!(
+                "install-seeding",
+                server = source.name.as_str(),
+                path = source.install_path.display().to_string()
+            )
+        );
+        if let Some(status) = Self::copy_seed(&source.install_path, target)? {
+            println!(
+                "{}",
+                tr!("install-seed-failed", status = status.to_string())
+            );
+            return Ok(());
         }
 
-        println!("Server update successfully.");
-
+        let saves = GameProfile::find(steamcmd.app_update).map_or(Vec::new(), |p| p.saves);
+        let removed = fs::remove_matching(target, &saves)?;
+        if removed > 0 {
+            println!("{}", tr!("install-seed-saves", count = removed));
+        }
         Ok(())
     }
 
-    /// Uninstall a game server
+    /// Copy an install directory without its unfinished downloads and steamserv state
+    ///
+    /// `steamapps/downloading`, `steamapps/temp` and the `.steamserv*` files of
+    /// a staged update are left out.
     ///
     /// # Arguments
     ///
-    /// * `server_name` - The name of the game server
+    /// * `source` - The install directory to copy
+    /// * `target` - The new install directory
     ///
     /// # Returns
     ///
-    /// Ok if the game server was uninstalled successfully
+    /// `None` if the files were copied, otherwise the exit status of `cp`
     ///
     /// # Errors
     ///
-    /// If the game server could not be uninstalled
-    pub fn uninstall(server_name: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
-        let mut config = Config::load()?;
-        let servers: Vec<InstalledServer> = config.installed_servers.clone();
-
-        let server_names = servers
-            .iter()
-            .map(|s| s.name.clone())
-            .collect::<Vec<String>>();
+    /// If a directory could not be read or created or `cp` could not be started
+    fn copy_seed(
+        source: &Path,
+        target: &Path,
+    ) -> Result<Option<std::process::ExitStatus>, Box<dyn std::error::Error>> {
+        let entries = |dir: &Path, skip: &dyn Fn(&str) -> bool| {
+            std::fs::read_dir(dir).map(|entries| {
+                entries
+                    .flatten()
+                    .filter(|e| !skip(&e.file_name().to_string_lossy()))
+                    .map(|e| e.path())
+                    .collect::<Vec<PathBuf>>()
+            })
+        };
+        let steamapps = source.join("steamapps");
+        let mut copies = vec![(
+            target.to_path_buf(),
+            entries(source, &|name| {
+                name.starts_with(".steamserv") || name == "steamapps"
+            })?,
+        )];
+        if steamapps.is_dir() {
+            copies.push((
+                target.join("steamapps"),
+                entries(&steamapps, &|name| disk::SCRATCH_DIRS.contains(&name))?,
+            ));
+        }
 
-        let server_name = match server_name {
-            Some(server_name) => {
-                if server_names.contains(&server_name) {
-                    server_name
-                } else {
-                    Text::new("Please enter the name of the game server:")
-                        .with_placeholder("e.g. TestServer")
-                        .with_help_message("It's the name for your game server folder.")
-                        .prompt()?
-                }
+        for (dir, paths) in copies {
+            std::fs::create_dir_all(&dir)?;
+            if paths.is_empty() {
+                continue;
             }
-            None => {
-                let server_name =
-                    Select::new("Please select the game server to uninstall", server_names)
-                        .with_help_message("Which of this game servers you will uninstall?")
-                        .prompt()?;
-                server_name
+            let status = std::process::Command::new("cp")
+                .arg("-a")
+                .arg("--reflink=auto")
+                .args(&paths)
+                .arg(&dir)
+                .status()?;
+            if !status.success() {
+                return Ok(Some(status));
             }
-        };
+        }
+        Ok(None)
+    }
 
-        let server = servers.iter().find(|s| s.name == server_name).unwrap();
+    /// Check if a server or one of its instances is running
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The installed server
+    ///
+    /// # Returns
+    ///
+    /// True if any process of the server runs
+    fn is_running(server: &InstalledServer) -> bool {
+        std::iter::once(server.name.clone())
+            .chain(
+                server
+                    .instances
+                    .iter()
+                    .map(|i| format!("{}/{}", server.name, i.name)),
+            )
+            .any(|target| {
+                ServerProcess::new(&ServerRef::parse(&target)).is_ok_and(|p| p.is_running())
+            })
+    }
 
-        let force_install_dir = server.install_path.clone();
+    /// Check that the Steam account owns the app before the download starts
+    ///
+    /// A license of a free app is requested first, so only apps that have to be
+    /// bought fail. If SteamCMD prints no licenses at all, the download goes
+    /// ahead and reports any problem itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `steamcmd` - The SteamCMD configuration with the login
+    /// * `config` - The configuration
+    ///
+    /// # Returns
+    ///
+    /// Ok if the account owns the app or the licenses could not be read
+    ///
+    /// # Errors
+    ///
+    /// If the login failed or the account does not own the app
+    fn check_license(
+        steamcmd: &SteamCMD,
+        config: &Config,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        println!("{}", tr!("install-checking-license"));
+        let child = Self::command(config)
+            .arg(format!("+login {} {}", steamcmd.login.0, steamcmd.login.1))
+            .arg(format!("+app_license_request {}", steamcmd.app_update))
+            .arg("+licenses_print")
+            .arg("+quit")
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()?;
 
-        let confirm = Confirm::new(&format!(
-            "Are you sure you want to uninstall the server {}?",
-            server_name
-        ))
-        .prompt()?;
+        let watchdog =
+            Watchdog::start(&child, TimeoutConfig::limit(config.timeouts.platform_check));
+        let output = child.wait_with_output()?;
+        watchdog.finish("steamcmd +licenses_print")?;
 
-        if confirm {
-            std::fs::remove_dir_all(force_install_dir)?;
-            config.installed_servers.retain(|s| s.name != server_name);
-            config.save()?;
-            println!("Server uninstalled successfully.");
+        let output = String::from_utf8_lossy(&output.stdout);
+        if let Some(error) = output.lines().find_map(SteamCmdError::parse) {
+            return Err(error.into());
+        }
+        match steamcmd::licensed_apps(&output) {
+            Some(apps) if !apps.contains(&steamcmd.app_update) => Err(tr!(
+                "install-not-owned",
+                username = steamcmd.login.0.as_str(),
+                app_id = steamcmd.app_update
+            )
+            .into()),
+            _ => Ok(()),
         }
-
-        Ok(())
     }
 
     /// Execute the install command
     ///
+    /// If SteamCMD reports an error other than a failed login, the download is
+    /// retried as often as the game profile allows. When Steam could not be
+    /// reached, it is retried with a growing delay, but not during the weekly
+    /// Steam maintenance.
+    ///
     /// # Arguments
     ///
     /// * `steamcmd` - The SteamCMD configuration
-    /// * `steamcmd_path` - The path to the SteamCMD installation
+    /// * `config` - The configuration
     ///
     /// # Returns
     ///
@@ -257,29 +1618,120 @@ impl SteamCMD {
     /// If the install command could not be executed
     fn execute_install_command(
         steamcmd: SteamCMD,
-        steamcmd_path: PathBuf,
+        config: &Config,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        disk::link_scratch(&config.disk, Path::new(&steamcmd.force_install_dir))?;
+
+        let retries = GameProfile::find(steamcmd.app_update).map_or(0, |p| p.install_retries);
+        let capabilities = Self::capabilities(config);
+        let mut attempt = 0;
+        let mut outages = 0;
+        loop {
+            match Self::run_app_update(&steamcmd, config, &capabilities) {
+                Ok(()) => break,
+                Err(e) if is_outage(e.as_ref()) => {
+                    if outages == OUTAGE_RETRIES
+                        || steamcmd::maintenance_until(Utc::now()).is_some()
+                    {
+                        return Err(e);
+                    }
+                    outages += 1;
+                    let delay = OUTAGE_DELAY * 2u32.pow(outages - 1);
+                    println!(
+                        "{}",
+                        tr!(
+                            "install-outage-retry",
+                            seconds = delay.as_secs(),
+                            attempt = outages,
+                            retries = OUTAGE_RETRIES
+                        )
+                    );
+                    thread::sleep(delay);
+                }
+                // Failed logins are not retried, so the account is not locked
+                Err(e)
+                    if attempt < retries
+                        && e.downcast_ref::<SteamCmdError>()
+                            .is_some_and(|e| !e.is_login_failure()) =>
+                {
+                    attempt += 1;
+                    println!(
+                        "{}",
+                        tr!("install-retry", attempt = attempt, retries = retries)
+                    );
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        if config.permissions.normalize {
+            let changed = permissions::normalize(
+                &config.permissions,
+                Path::new(&steamcmd.force_install_dir),
+                steamcmd.app_update,
+            )?;
+            println!("{}", tr!("perms-normalized", count = changed));
+        }
+        Ok(())
+    }
+
+    /// Run SteamCMD once to download the app
+    ///
+    /// # Arguments
+    ///
+    /// * `steamcmd` - The SteamCMD configuration
+    /// * `config` - The configuration
+    /// * `capabilities` - The capabilities of SteamCMD, for the branch syntax
+    ///
+    /// # Returns
+    ///
+    /// Ok if SteamCMD downloaded the app
+    ///
+    /// # Errors
+    ///
+    /// If SteamCMD could not be run, timed out or reported an error
+    fn run_app_update(
+        steamcmd: &SteamCMD,
+        config: &Config,
+        capabilities: &Capabilities,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        let mut install_child = std::process::Command::new(steamcmd_path)
+        let (before_login, after_login) =
+            steamcmd::extra_commands(config.steamcmd_args.iter().chain(&steamcmd.extra_args));
+        let mut install_child = Self::command(config)
             .arg(format!(
                 "+force_install_dir {}",
                 steamcmd.force_install_dir.clone()
             ))
+            .args(before_login)
             .arg(format!(
                 "+login {} {}",
                 steamcmd.login.clone().0,
                 steamcmd.login.1,
             ))
-            .arg(format!("+app_update {} validate", steamcmd.app_update))
+            .args(after_login)
+            .args(capabilities.app_update(steamcmd.app_update, steamcmd.branch.as_deref()))
             .arg("+quit")
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
             .spawn()?;
 
-        run_with_output(&mut install_child)?;
+        let watchdog = Watchdog::start(
+            &install_child,
+            TimeoutConfig::limit(config.timeouts.install),
+        );
+        let error = run_with_output(&mut install_child)?;
 
         let install_status = install_child.wait()?;
+        watchdog.finish("steamcmd +app_update")?;
+        // SteamCMD can exit successfully although the update job failed
+        if let Some(error) = error {
+            return Err(error.into());
+        }
         if !install_status.success() {
-            return Err("Could not install game server".into());
+            return Err(SteamCmdError::Exit {
+                code: install_status.code(),
+            }
+            .into());
         }
         Ok(())
     }
@@ -302,7 +1754,7 @@ impl SteamCMD {
         let server = if let Some(server) = servers.servers.iter().find(|s| s.app_id == app_id) {
             server
         } else {
-            return Err("Could not find server".into());
+            return Err(tr!("app-not-found").into());
         };
         Ok(server.name.clone())
     }
@@ -327,25 +1779,22 @@ impl SteamCMD {
     ) -> Result<String, Box<dyn std::error::Error>> {
         let force_install_dir = match server_name {
             Some(server_name) => {
-                let confirm = Confirm::new(&format!(
-                    "Would you like to install the server with this name {}?",
-                    server_name
-                ))
-                .prompt()?;
+                let confirm =
+                    Confirm::new(&tr!("install-confirm-name", name = server_name)).prompt()?;
                 if confirm {
                     format!("{}/{}", config.install_path.display(), server_name)
                 } else {
-                    Text::new("Please enter the name of the game server:")
-                        .with_placeholder("e.g. TestServer")
-                        .with_help_message("It's the name for your game server folder.")
+                    Text::new(&tr!("prompt-server-name"))
+                        .with_placeholder(&tr!("prompt-server-name-placeholder"))
+                        .with_help_message(&tr!("prompt-server-name-help"))
                         .prompt()?;
                     format!("{}/{}", config.install_path.display(), server_name)
                 }
             }
             None => {
-                let name = Text::new("Please enter the name of the game server:")
-                    .with_placeholder("e.g. TestServer")
-                    .with_help_message("It's the name for your game server folder.")
+                let name = Text::new(&tr!("prompt-server-name"))
+                    .with_placeholder(&tr!("prompt-server-name-placeholder"))
+                    .with_help_message(&tr!("prompt-server-name-help"))
                     .prompt()?;
                 format!("{}/{}", config.install_path.display(), name)
             }
@@ -366,31 +1815,33 @@ impl SteamCMD {
     /// # Errors
     ///
     /// If the login information could not be found
-    fn get_login(username: Option<String>) -> Result<(String, String), Box<dyn std::error::Error>> {
+    pub fn get_login(
+        username: Option<String>,
+    ) -> Result<(String, String), Box<dyn std::error::Error>> {
         let login = match username {
             Some(username) => match username.as_str() {
                 "anonymous" => Some(("anonymous".to_string(), "".to_string())),
                 _ => {
-                    let password =
-                        Password::new("Please enter your password for your steam account.")
-                            .without_confirmation()
-                            .prompt()?;
+                    let password = Password::new(&tr!("prompt-steam-password"))
+                        .without_confirmation()
+                        .prompt()?;
                     Some((username, password))
                 }
             },
             None => {
-                let login_type = vec!["anonymous", "steam account"];
-                let select_login = Select::new("Please select your login", login_type)
-                    .with_help_message("Which of this logins you will use?")
+                let anonymous = tr!("login-anonymous");
+                let steam_account = tr!("login-steam-account");
+                let login_type = vec![anonymous.clone(), steam_account.clone()];
+                let select_login = Select::new(&tr!("login-select"), login_type)
+                    .with_help_message(&tr!("login-select-help"))
                     .prompt()?;
                 match select_login {
-                    "anonymous" => Some(("anonymous".to_string(), "".to_string())),
-                    "steam account" => {
-                        let username = Text::new("Please enter your steam username:").prompt()?;
-                        let password =
-                            Password::new("Please enter your password for your steam account.")
-                                .without_confirmation()
-                                .prompt()?;
+                    login if login == anonymous => Some(("anonymous".to_string(), "".to_string())),
+                    login if login == steam_account => {
+                        let username = Text::new(&tr!("prompt-steam-username")).prompt()?;
+                        let password = Password::new(&tr!("prompt-steam-password"))
+                            .without_confirmation()
+                            .prompt()?;
                         Some((username, password))
                     }
                     _ => None,
@@ -417,33 +1868,22 @@ impl SteamCMD {
         let app_update = match app_id {
             Some(app_id) => {
                 let app_name = Self::check_app_id(app_id)?;
-                let confirm = Confirm::new(&format!(
-                    "Would you like to install the server for {}?",
-                    app_name
-                ))
-                .prompt()?;
+                let confirm = Confirm::new(&tr!("install-confirm-app", app = app_name)).prompt()?;
                 if confirm {
                     Some(app_id)
                 } else {
-                    let app_id =
-                        Text::new("Please enter the Steam App ID of the game server.").prompt()?;
+                    let app_id = Text::new(&tr!("prompt-app-id")).prompt()?;
                     app_id.parse::<u32>().ok()
                 }
             }
             None => {
-                let app_id =
-                    Text::new("Please enter the Steam App ID of the game server.").prompt()?;
+                let app_id = Text::new(&tr!("prompt-app-id")).prompt()?;
                 let app_name = Self::check_app_id(app_id.parse::<u32>()?)?;
-                let confirm = Confirm::new(&format!(
-                    "Would you like to install the server for {}?",
-                    app_name
-                ))
-                .prompt()?;
+                let confirm = Confirm::new(&tr!("install-confirm-app", app = app_name)).prompt()?;
                 if confirm {
                     Some(app_id.parse::<u32>()?)
                 } else {
-                    let app_id =
-                        Text::new("Please enter the Steam App ID of the game server.").prompt()?;
+                    let app_id = Text::new(&tr!("prompt-app-id")).prompt()?;
                     Some(app_id.parse::<u32>()?)
                 }
             }
@@ -480,11 +1920,11 @@ impl SteamCMD {
         let linux_output = String::from_utf8_lossy(&linux.stdout);
         let windows_output = String::from_utf8_lossy(&windows.stdout);
 
-        if !linux_output.contains("Invalid Platform") && !linux_output.contains("unknown") {
+        if !OutputLine::InvalidPlatform.is_in(&linux_output) {
             platforms.push(Platform::Linux);
         }
 
-        if !windows_output.contains("Invalid Platform") && !windows_output.contains("unknown") {
+        if !OutputLine::InvalidPlatform.is_in(&windows_output) {
             platforms.push(Platform::Windows);
         }
 
@@ -497,7 +1937,7 @@ impl SteamCMD {
         platform: &str,
         config: Config,
     ) -> Result<Output, Box<dyn std::error::Error>> {
-        let command = std::process::Command::new(&config.steamcmd_path)
+        let child = Self::command(&config)
             .arg("+sSteamCmdForcePlatformType")
             .arg(platform)
             .arg("+login")
@@ -506,8 +1946,150 @@ impl SteamCMD {
             .arg("+app_status")
             .arg(app_id.to_string())
             .arg("+quit")
-            .output();
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()?;
+
+        let watchdog =
+            Watchdog::start(&child, TimeoutConfig::limit(config.timeouts.platform_check));
+        let output = child.wait_with_output()?;
+        watchdog.finish("steamcmd +app_status")?;
+
+        Ok(output)
+    }
+}
+
+/// Estimate the download size of an update from the depots of the app info
+///
+/// SteamCMD only downloads the changed chunks of a depot, but the app info only has
+/// the size of complete manifests. Every installed depot with a new manifest is
+/// counted with its full download size, so the estimate is an upper bound.
+///
+/// # Arguments
+///
+/// - `info` - The app info
+/// - `installed` - The installed depots of the app manifest with their manifest IDs
+/// - `branch` - The branch the server is installed from
+///
+/// # Returns
+///
+/// The estimated download size in bytes
+pub fn download_size(info: &Vdf, installed: &Vdf, branch: &str) -> u64 {
+    installed
+        .entries()
+        .iter()
+        .filter_map(|(depot_id, depot)| {
+            let latest = info.get(&format!("depots/{}", depot_id))?;
+            // Newer app info has an object per branch, older app info only the manifest ID
+            let manifest = latest
+                .get(&format!("manifests/{}", branch))
+                .or_else(|| latest.get("manifests/public"))?;
+            let gid = manifest.value("gid").or(match manifest {
+                Vdf::Value(gid) => Some(gid.as_str()),
+                Vdf::Object(_) => None,
+            })?;
+            if Some(gid) == depot.value("manifest") {
+                return None;
+            }
+
+            manifest
+                .value("download")
+                .or_else(|| manifest.value("size"))
+                .or_else(|| latest.value("maxsize"))
+                .and_then(|size| size.parse::<u64>().ok())
+        })
+        .sum()
+}
+
+/// Check if an error means that Steam could not be reached
+///
+/// # Arguments
+///
+/// * `error` - The error
+///
+/// # Returns
+///
+/// True if SteamCMD reported an outage of Steam
+fn is_outage(error: &(dyn std::error::Error + 'static)) -> bool {
+    error
+        .downcast_ref::<SteamCmdError>()
+        .is_some_and(SteamCmdError::is_outage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_seed_skips_downloads_and_state() {
+        let dir = std::env::temp_dir().join(format!("steamserv-copy-seed-{}", std::process::id()));
+        let source = dir.join("source");
+        let target = dir.join("target");
+        for path in ["steamapps/downloading/896660", "steamapps/temp", "data"] {
+            std::fs::create_dir_all(source.join(path)).unwrap();
+        }
+        for file in [
+            "valheim_server.x86_64",
+            "data/world.db",
+            "steamapps/appmanifest_896660.acf",
+            "steamapps/downloading/896660/chunk",
+            ".steamserv-staged.json",
+        ] {
+            std::fs::write(source.join(file), file).unwrap();
+        }
+
+        assert!(SteamCMD::copy_seed(&source, &target).unwrap().is_none());
+        assert!(target.join("valheim_server.x86_64").is_file());
+        assert!(target.join("data/world.db").is_file());
+        assert!(target.join("steamapps/appmanifest_896660.acf").is_file());
+        assert!(!target.join("steamapps/downloading").exists());
+        assert!(!target.join("steamapps/temp").exists());
+        assert!(!target.join(".steamserv-staged.json").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_install_instance_of_installed_server() {
+        let mut config = Config {
+            installed_servers: vec![InstalledServer::new(
+                730,
+                "cs2",
+                PathBuf::from("/srv/cs2"),
+                LoginType::Anonymous,
+            )],
+            ..Default::default()
+        };
+
+        let added =
+            SteamCMD::add_to_installed(&mut config, "cs2", ServerInstance::new("casual")).unwrap();
+        assert!(added);
+        assert_eq!(config.installed_servers.len(), 1);
+        assert_eq!(config.installed_servers[0].instances[0].name, "casual");
+        assert!(
+            SteamCMD::add_to_installed(&mut config, "cs2", ServerInstance::new("casual")).is_err()
+        );
+        assert!(
+            !SteamCMD::add_to_installed(&mut config, "valheim", ServerInstance::new("main"))
+                .unwrap()
+        );
+    }
 
-        Ok(command?)
+    #[test]
+    fn test_download_size() {
+        let info = Vdf::parse(
+            r#""depots" {
+                "1" { "manifests" { "public" { "gid" "10" "size" "900" "download" "300" } } }
+                "2" { "manifests" { "public" { "gid" "20" "size" "500" "download" "200" } } }
+                "3" { "maxsize" "700" "manifests" { "public" "30" } }
+            }"#,
+        )
+        .unwrap();
+        let installed = Vdf::parse(
+            r#""1" { "manifest" "9" "size" "800" }
+               "2" { "manifest" "20" "size" "500" }
+               "3" { "manifest" "29" "size" "600" }"#,
+        )
+        .unwrap();
+        assert_eq!(download_size(&info, &installed, "public"), 300 + 700);
     }
 }
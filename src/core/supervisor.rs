@@ -0,0 +1,1179 @@
+use std::{
+    collections::HashMap,
+    io::{Read, Seek, SeekFrom},
+    net::{TcpStream, ToSocketAddrs},
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use regex::Regex;
+
+use crate::{
+    core::{
+        bundle, dyndns,
+        logs::LogPrune,
+        maps,
+        metrics::{self, MetricsStore},
+        query, rcon, GameProfile, JobQueue, LaunchCommand, ServerProcess,
+    },
+    tr,
+    utils::{
+        disk::{self, DiskWarning},
+        events::{self, Event},
+        fs, history,
+        notify::notify,
+        pattern::matches_wildcard,
+        schedule::Schedule,
+        Config, HealthConfig, HealthProbe, HistoryEvent, InstalledServer, LogAlert, MemoryLimit,
+        RecoveryAction, ServerInstance, ServerRef,
+    },
+};
+
+/// Interval of the supervisor loop
+const TICK_INTERVAL: Duration = Duration::from_secs(5);
+/// Timeout of TCP and HTTP probes
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// State of the health checks of a single server or instance
+#[derive(Default)]
+struct WatchState {
+    failures: u32,
+    next_check: Option<Instant>,
+    next_sample: Option<Instant>,
+    log_offset: Option<u64>,
+    /// Read position of the log file for the alerts
+    alert_offset: Option<u64>,
+    /// When the alerts fired last, by pattern
+    alerts_fired: HashMap<String, Instant>,
+    /// Since when the memory is above the limit
+    memory_since: Option<Instant>,
+    /// When the memory restart is due, set once the players are warned
+    memory_restart: Option<Instant>,
+    /// True if the deferral of the memory restart was reported
+    memory_deferred: bool,
+    /// The warnings of the memory restart that were sent, in minutes
+    memory_warned: Vec<u64>,
+}
+
+/// Supervisor that watches the running servers, recovers them from failures,
+/// collects their metrics and starts the queued jobs
+#[derive(Default)]
+pub struct Supervisor {
+    states: HashMap<String, WatchState>,
+    next_disk_check: Option<Instant>,
+    next_dns_update: Option<Instant>,
+    next_log_rotation: Option<Instant>,
+    disk_warnings: Vec<DiskWarning>,
+    announced_minute: Option<i64>,
+    rotated_minute: Option<i64>,
+}
+
+impl Supervisor {
+    /// Create a new supervisor
+    ///
+    /// # Returns
+    ///
+    /// The supervisor
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run the supervisor until the process is terminated
+    ///
+    /// # Returns
+    ///
+    /// Never returns on success
+    ///
+    /// # Errors
+    ///
+    /// If the config could not be loaded
+    pub async fn run(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        println!("{}", tr!("daemon-started"));
+        loop {
+            self.tick().await?;
+            tokio::time::sleep(TICK_INTERVAL).await;
+        }
+    }
+
+    /// Run one pass over all servers and instances with health checks
+    ///
+    /// # Returns
+    ///
+    /// Ok if the pass was completed
+    ///
+    /// # Errors
+    ///
+    /// If the config could not be loaded
+    pub async fn tick(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        reap_children();
+
+        // The config is loaded on every pass so changes apply without a restart
+        let config = Config::load()?;
+
+        for server in &config.installed_servers {
+            let targets = std::iter::once(None).chain(server.instances.iter().map(Some));
+            for instance in targets {
+                let target = match instance {
+                    Some(i) => ServerRef::parse(&format!("{}/{}", server.name, i.name)),
+                    None => ServerRef::parse(&server.name),
+                };
+                // Servers in maintenance or with paused automation are neither
+                // checked nor recovered
+                let health = server
+                    .health_for(instance)
+                    .filter(|_| server.is_automated(&config));
+                if let Some(health) = health {
+                    if let Err(e) = self
+                        .check_target(&config, server, instance, health, &target)
+                        .await
+                    {
+                        println!(
+                            "{}",
+                            tr!("daemon-check-error", target = target, error = e.to_string())
+                        );
+                    }
+                }
+                if !server.alerts.is_empty() && server.is_automated(&config) {
+                    if let Err(e) = self.check_alerts(&config, server, instance, &target).await {
+                        println!(
+                            "{}",
+                            tr!("daemon-alert-error", target = target, error = e.to_string())
+                        );
+                    }
+                }
+                if config.metrics.enabled {
+                    if let Err(e) = self.sample_target(&config, server, instance, &target) {
+                        println!(
+                            "{}",
+                            tr!(
+                                "daemon-sample-error",
+                                target = target,
+                                error = e.to_string()
+                            )
+                        );
+                    }
+                }
+            }
+        }
+
+        self.announce(&config)?;
+        self.rotate_maps(&config)?;
+
+        if let Err(e) = self.check_disk(&config).await {
+            println!("{}", tr!("daemon-disk-error", error = e.to_string()));
+        }
+
+        self.update_dns(&config).await;
+
+        self.rotate_logs(&config);
+
+        match JobQueue::run_next(&config) {
+            Ok(Some(job)) => println!(
+                "{}",
+                tr!("daemon-job-started", id = job.id, command = job.command())
+            ),
+            Ok(None) => {}
+            Err(e) => println!("{}", tr!("daemon-job-error", error = e.to_string())),
+        }
+
+        Ok(())
+    }
+
+    /// Broadcast the announcements that are due in the current minute
+    ///
+    /// Every minute is only handled once, although the loop runs more often.
+    /// The schedules run in the time zone of the server. Servers that are
+    /// stopped, in maintenance or paused are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    ///
+    /// # Returns
+    ///
+    /// Ok if the announcements were handled, failed broadcasts are only printed
+    ///
+    /// # Errors
+    ///
+    /// If the steamserv directory could not be found
+    fn announce(&mut self, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+        let now = chrono::Utc::now();
+        let minute = now.timestamp() / 60;
+        if self.announced_minute == Some(minute) {
+            return Ok(());
+        }
+        self.announced_minute = Some(minute);
+
+        for server in config
+            .installed_servers
+            .iter()
+            .filter(|s| s.is_automated(config))
+        {
+            let zone = match server.time_zone() {
+                Ok(zone) => zone,
+                Err(e) => {
+                    println!("{e}");
+                    continue;
+                }
+            };
+            let targets = std::iter::once(None).chain(server.instances.iter().map(Some));
+            for instance in targets {
+                let target = match instance {
+                    Some(i) => ServerRef::parse(&format!("{}/{}", server.name, i.name)),
+                    None => ServerRef::parse(&server.name),
+                };
+                let due: Vec<_> = server
+                    .announcements_for(instance)
+                    .iter()
+                    .filter(
+                        |announcement| match Schedule::parse(&announcement.schedule) {
+                            Some(schedule) => schedule.is_due(now, zone),
+                            None => {
+                                println!(
+                                    "{}",
+                                    tr!(
+                                        "schedule-invalid",
+                                        target = target,
+                                        schedule = announcement.schedule.as_str()
+                                    )
+                                );
+                                false
+                            }
+                        },
+                    )
+                    .collect();
+                if due.is_empty() || !ServerProcess::new(&target)?.is_running() {
+                    continue;
+                }
+
+                for announcement in due {
+                    match rcon::broadcast(
+                        server,
+                        instance,
+                        &announcement.message,
+                        announcement.command.as_deref(),
+                    ) {
+                        Ok(()) => println!(
+                            "{}",
+                            tr!(
+                                "daemon-announced",
+                                target = target,
+                                message = announcement.message.as_str()
+                            )
+                        ),
+                        Err(e) => println!(
+                            "{}",
+                            tr!(
+                                "daemon-announce-failed",
+                                target = target,
+                                error = e.to_string()
+                            )
+                        ),
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Change the servers whose map rotation is due in the current minute to their next map
+    ///
+    /// Like the announcements, every minute is only handled once and servers
+    /// that are stopped, in maintenance or paused are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    ///
+    /// # Returns
+    ///
+    /// Ok if the rotations were handled, failed map changes are only printed
+    ///
+    /// # Errors
+    ///
+    /// If the steamserv directory could not be found
+    fn rotate_maps(&mut self, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+        let now = chrono::Utc::now();
+        let minute = now.timestamp() / 60;
+        if self.rotated_minute == Some(minute) {
+            return Ok(());
+        }
+        self.rotated_minute = Some(minute);
+
+        for server in config
+            .installed_servers
+            .iter()
+            .filter(|s| s.is_automated(config))
+        {
+            let zone = match server.time_zone() {
+                Ok(zone) => zone,
+                Err(e) => {
+                    println!("{e}");
+                    continue;
+                }
+            };
+            let targets = std::iter::once(None).chain(server.instances.iter().map(Some));
+            for instance in targets {
+                let target = match instance {
+                    Some(i) => ServerRef::parse(&format!("{}/{}", server.name, i.name)),
+                    None => ServerRef::parse(&server.name),
+                };
+                let Some(schedule) = instance
+                    .map_or(&server.maps, |i| &i.maps)
+                    .as_ref()
+                    .and_then(|rotation| rotation.schedule.as_deref())
+                else {
+                    continue;
+                };
+                let due = match Schedule::parse(schedule) {
+                    Some(parsed) => parsed.is_due(now, zone),
+                    None => {
+                        println!(
+                            "{}",
+                            tr!("schedule-invalid", target = target, schedule = schedule)
+                        );
+                        false
+                    }
+                };
+                if !due || !ServerProcess::new(&target)?.is_running() {
+                    continue;
+                }
+
+                // The rotation saves its position, so it works on a fresh config
+                match Config::load().and_then(|mut config| maps::rotate(&mut config, &target)) {
+                    Ok(map) => println!(
+                        "{}",
+                        tr!("daemon-maps-rotated", target = target, map = map.as_str())
+                    ),
+                    Err(e) => println!(
+                        "{}",
+                        tr!("daemon-maps-failed", target = target, error = e.to_string())
+                    ),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rotate the server logs if the check interval has elapsed
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    fn rotate_logs(&mut self, config: &Config) {
+        let now = Instant::now();
+        if self.next_log_rotation.is_some_and(|next| now < next) {
+            return;
+        }
+        self.next_log_rotation =
+            Some(now + Duration::from_secs(config.logs.check_interval.max(60)));
+
+        match LogPrune::run(&config.logs, false) {
+            Ok(prune) => {
+                for path in &prune.rotated {
+                    println!("{}", tr!("daemon-log-rotated", path = path.display()));
+                }
+            }
+            Err(e) => println!("{}", tr!("daemon-log-error", error = e.to_string())),
+        }
+    }
+
+    /// Point the public hostname to the current address if the DNS interval has elapsed
+    ///
+    /// Offline mode skips the update, failures are printed and retried after
+    /// the next interval.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    async fn update_dns(&mut self, config: &Config) {
+        let now = Instant::now();
+        if config.is_offline() || self.next_dns_update.is_some_and(|next| now < next) {
+            return;
+        }
+        self.next_dns_update = Some(now + Duration::from_secs(config.dns.interval.max(60)));
+
+        match dyndns::update(config).await {
+            Ok(Some(ip)) => println!(
+                "{}",
+                tr!(
+                    "daemon-dns-updated",
+                    hostname = config.dns.hostname.clone().unwrap_or_default(),
+                    ip = ip
+                )
+            ),
+            Ok(None) => {}
+            Err(e) => println!("{}", tr!("daemon-dns-error", error = e.to_string())),
+        }
+    }
+
+    /// Check the disk space if the disk check interval has elapsed
+    ///
+    /// Only new warnings are printed and notified, so a full disk is not reported
+    /// again on every check.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    ///
+    /// # Returns
+    ///
+    /// Ok if the check was run or skipped
+    ///
+    /// # Errors
+    ///
+    /// If a quota is invalid or the notification could not be sent
+    async fn check_disk(&mut self, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+        let now = Instant::now();
+        if self.next_disk_check.is_some_and(|next| now < next) {
+            return Ok(());
+        }
+        self.next_disk_check = Some(now + Duration::from_secs(config.disk.check_interval.max(60)));
+
+        let warnings = disk::check(config)?;
+        let new = warnings
+            .iter()
+            .filter(|w| !self.disk_warnings.iter().any(|old| old.is_same_problem(w)));
+        for warning in new {
+            println!("{}", warning);
+            notify(config, &warning.to_string()).await?;
+        }
+        self.disk_warnings = warnings;
+
+        Ok(())
+    }
+
+    /// Check a server or instance if its check interval has elapsed
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    /// * `server` - The installed server
+    /// * `instance` - The instance, or `None` for the server itself
+    /// * `health` - The health checks
+    /// * `target` - The reference to the server or instance
+    ///
+    /// # Returns
+    ///
+    /// Ok if the check was run or skipped
+    ///
+    /// # Errors
+    ///
+    /// If the process state could not be read or a recovery action failed
+    async fn check_target(
+        &mut self,
+        config: &Config,
+        server: &InstalledServer,
+        instance: Option<&ServerInstance>,
+        health: &HealthConfig,
+        target: &ServerRef,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let process = ServerProcess::new(target)?;
+        let key = target.to_string();
+        let state = self.states.entry(key.clone()).or_default();
+
+        let now = Instant::now();
+        if state.next_check.is_some_and(|next| now < next) {
+            return Ok(());
+        }
+        state.next_check = Some(now + Duration::from_secs(health.interval));
+
+        if !process.is_running() {
+            *state = WatchState {
+                next_check: state.next_check,
+                next_sample: state.next_sample,
+                ..WatchState::default()
+            };
+            // A server stopped by the user stays down, one that exited on its
+            // own is recovered
+            return match process.take_exit() {
+                Some(pid) => {
+                    let message = tr!("daemon-server-exited", target = key.as_str(), pid = pid);
+                    println!("{}", message);
+                    crashed(config, target, &message);
+                    self.recover(config, server, instance, health, &process, &message)
+                        .await
+                }
+                None => Ok(()),
+            };
+        }
+
+        // The memory restart waits for the end of a blackout, the players are
+        // only warned once it may happen
+        let memory = health
+            .memory
+            .as_ref()
+            .filter(|_| server.blackout(config, Utc::now()).is_none());
+        if let Some(memory) = memory {
+            if let Some(reason) = check_memory(memory, server, instance, &process, state)? {
+                history::record(&HistoryEvent::new(&key, "memory-limit", &reason))?;
+                let event = match restart(config, server, instance, &process) {
+                    Ok(message) => {
+                        events::publish(Event::RecoveryFinished {
+                            target: key.clone(),
+                            message: message.clone(),
+                        });
+                        HistoryEvent::new(&key, "recovery", &message)
+                    }
+                    Err(e) => {
+                        events::publish(Event::RecoveryFailed {
+                            target: key.clone(),
+                            error: e.to_string(),
+                        });
+                        HistoryEvent::new(&key, "recovery-failed", &e.to_string())
+                    }
+                };
+                history::record(&event)?;
+                return Ok(());
+            }
+        }
+
+        let mut errors = Vec::new();
+        for probe in &health.probes {
+            if let Err(e) = run_probe(probe, server, instance, &process, state).await {
+                errors.push(e.to_string());
+            }
+        }
+
+        if errors.is_empty() {
+            state.failures = 0;
+            return Ok(());
+        }
+
+        state.failures += 1;
+        let message = errors.join("; ");
+        events::publish(Event::HealthCheckFailed {
+            target: key.clone(),
+            failures: state.failures,
+            max: health.failures,
+            error: message.clone(),
+        });
+        if state.failures < health.failures {
+            return Ok(());
+        }
+        state.failures = 0;
+        state.log_offset = None;
+
+        history::record(&HistoryEvent::new(&key, "health-check-failed", &message))?;
+        crashed(config, target, &message);
+        self.recover(config, server, instance, health, &process, &message)
+            .await
+    }
+
+    /// Match the new lines of the server log against the alerts of the server
+    ///
+    /// An alert fires once for the first matching line and then waits for its
+    /// cooldown. The failures of its actions are only printed.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    /// * `server` - The installed server with the alerts
+    /// * `instance` - The instance, or `None` for the server itself
+    /// * `target` - The reference to the server or instance
+    ///
+    /// # Returns
+    ///
+    /// Ok if the log was checked
+    ///
+    /// # Errors
+    ///
+    /// If the log could not be read, a pattern is invalid or the history could
+    /// not be written
+    async fn check_alerts(
+        &mut self,
+        config: &Config,
+        server: &InstalledServer,
+        instance: Option<&ServerInstance>,
+        target: &ServerRef,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let process = ServerProcess::new(target)?;
+        let key = target.to_string();
+        let state = self.states.entry(key.clone()).or_default();
+
+        if !process.is_running() {
+            state.alert_offset = None;
+            return Ok(());
+        }
+        let Some(content) = read_log(&process, &mut state.alert_offset)? else {
+            return Ok(());
+        };
+
+        let now = Instant::now();
+        let mut fired: Vec<(&LogAlert, String)> = Vec::new();
+        for alert in &server.alerts {
+            let regex = Regex::new(&alert.pattern).map_err(|e| {
+                tr!(
+                    "daemon-alert-invalid",
+                    pattern = alert.pattern.as_str(),
+                    error = e
+                )
+            })?;
+            let cooling = state
+                .alerts_fired
+                .get(&alert.pattern)
+                .is_some_and(|last| now < *last + Duration::from_secs(alert.cooldown));
+            if cooling {
+                continue;
+            }
+            if let Some(line) = content.lines().find(|line| regex.is_match(line)) {
+                state.alerts_fired.insert(alert.pattern.clone(), now);
+                fired.push((alert, line.trim().to_string()));
+            }
+        }
+
+        for (alert, line) in fired {
+            let name = alert.name.as_deref().unwrap_or(&alert.pattern);
+            let event = Event::LogAlert {
+                target: key.clone(),
+                alert: name.to_string(),
+                line: line.clone(),
+            };
+            let message = event.message().unwrap_or_default();
+            events::publish(event);
+            history::record(&HistoryEvent::new(&key, "log-alert", &message))?;
+
+            for action in &alert.actions {
+                let result = match action {
+                    RecoveryAction::Restart => restart(config, server, instance, &process),
+                    RecoveryAction::Hook(script) => run_hook(script, &key, &line),
+                    RecoveryAction::Notify => notify(config, &message)
+                        .await
+                        .map(|_| tr!("daemon-notified")),
+                };
+                match result {
+                    Ok(message) => println!("{}", message),
+                    Err(e) => println!(
+                        "{}",
+                        tr!("daemon-alert-failed", target = key, error = e.to_string())
+                    ),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Store a metrics sample of a running server or instance if the sample interval has elapsed
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    /// * `server` - The installed server
+    /// * `instance` - The instance, or `None` for the server itself
+    /// * `target` - The reference to the server or instance
+    ///
+    /// # Returns
+    ///
+    /// Ok if the sample was stored or skipped
+    ///
+    /// # Errors
+    ///
+    /// If the server did not answer or the sample could not be stored
+    fn sample_target(
+        &mut self,
+        config: &Config,
+        server: &InstalledServer,
+        instance: Option<&ServerInstance>,
+        target: &ServerRef,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let state = self.states.entry(target.to_string()).or_default();
+
+        let now = Instant::now();
+        if state.next_sample.is_some_and(|next| now < next) {
+            return Ok(());
+        }
+        state.next_sample = Some(now + Duration::from_secs(config.metrics.interval));
+
+        if GameProfile::find(server.app_id).is_none() || !ServerProcess::new(target)?.is_running() {
+            return Ok(());
+        }
+
+        let sample = metrics::collect_sample(server, instance)?;
+        let store = MetricsStore::open(target)?;
+        store.insert(&sample)?;
+        store.prune(Utc::now() - chrono::Duration::days(config.metrics.retention_days))?;
+
+        Ok(())
+    }
+
+    /// Run the recovery actions of a server or instance
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration
+    /// * `server` - The installed server
+    /// * `instance` - The instance, or `None` for the server itself
+    /// * `health` - The health checks with the recovery actions
+    /// * `process` - The process of the server or instance
+    /// * `reason` - The reason for the recovery
+    ///
+    /// # Returns
+    ///
+    /// Ok if all recovery actions were run
+    ///
+    /// # Errors
+    ///
+    /// If the history could not be written
+    async fn recover(
+        &self,
+        config: &Config,
+        server: &InstalledServer,
+        instance: Option<&ServerInstance>,
+        health: &HealthConfig,
+        process: &ServerProcess,
+        reason: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let key = process.target.to_string();
+
+        for action in &health.actions {
+            let result = match action {
+                RecoveryAction::Restart => restart(config, server, instance, process),
+                RecoveryAction::Hook(script) => run_hook(script, &key, reason),
+                RecoveryAction::Notify => notify(
+                    config,
+                    &tr!("daemon-notification", target = key, error = reason),
+                )
+                .await
+                .map(|_| tr!("daemon-notified")),
+            };
+
+            let event = match result {
+                Ok(message) => {
+                    events::publish(Event::RecoveryFinished {
+                        target: key.clone(),
+                        message: message.clone(),
+                    });
+                    HistoryEvent::new(&key, "recovery", &message)
+                }
+                Err(e) => {
+                    events::publish(Event::RecoveryFailed {
+                        target: key.clone(),
+                        error: e.to_string(),
+                    });
+                    HistoryEvent::new(&key, "recovery-failed", &e.to_string())
+                }
+            };
+            history::record(&event)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Publish the crash of a server or instance and collect its support bundle
+///
+/// The bundle is collected before the recovery restarts the server. A bundle
+/// that could not be written is only printed.
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+/// * `target` - The reference to the server or instance
+/// * `message` - The reason of the crash
+fn crashed(config: &Config, target: &ServerRef, message: &str) {
+    let key = target.to_string();
+    events::publish(Event::ServerCrashed {
+        target: key.clone(),
+        error: message.to_string(),
+    });
+    match bundle::create(config, target, Some(message)) {
+        Ok(path) => println!(
+            "{}",
+            tr!("daemon-bundle-created", target = key, path = path.display())
+        ),
+        Err(e) => println!(
+            "{}",
+            tr!("daemon-bundle-failed", target = key, error = e.to_string())
+        ),
+    }
+}
+
+/// Restart a server or instance
+///
+/// # Arguments
+///
+/// * `config` - The configuration
+/// * `server` - The installed server
+/// * `instance` - The instance, or `None` for the server itself
+/// * `process` - The process of the server or instance
+///
+/// # Returns
+///
+/// The message with the new process ID
+///
+/// # Errors
+///
+/// If the server is in a blackout window or could not be stopped or started
+fn restart(
+    config: &Config,
+    server: &InstalledServer,
+    instance: Option<&ServerInstance>,
+    process: &ServerProcess,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let key = process.target.to_string();
+    if let Some(blackout) = server.blackout(config, Utc::now()) {
+        return Err(tr!(
+            "daemon-restart-blackout",
+            target = key,
+            blackout = blackout.name.as_str()
+        )
+        .into());
+    }
+    println!("{}", tr!("daemon-restarting", target = key));
+    process.stop(server, instance)?;
+    let pid = process.start(config, server, instance)?;
+    Ok(tr!("daemon-restarted", target = key, pid = pid))
+}
+
+/// Check the resident memory of a running server against its limit
+///
+/// An empty server is restarted once the memory stayed above the limit long
+/// enough. With players online the restart is deferred, then announced with the
+/// warnings and restarted when the last warning has run out.
+///
+/// # Arguments
+///
+/// * `memory` - The memory limit
+/// * `server` - The installed server
+/// * `instance` - The instance, or `None` for the server itself
+/// * `process` - The process of the server or instance
+/// * `state` - The watch state, holding the progress of the restart
+///
+/// # Returns
+///
+/// The reason for the restart if it is due now
+///
+/// # Errors
+///
+/// If the limit is not a valid size
+fn check_memory(
+    memory: &MemoryLimit,
+    server: &InstalledServer,
+    instance: Option<&ServerInstance>,
+    process: &ServerProcess,
+    state: &mut WatchState,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let limit = fs::parse_size(&memory.limit)
+        .ok_or_else(|| tr!("daemon-memory-invalid", limit = memory.limit.as_str()))?;
+    let key = process.target.to_string();
+    let now = Instant::now();
+    let reason = tr!(
+        "daemon-memory-exceeded",
+        limit = fs::format_size(limit),
+        minutes = memory.minutes
+    );
+
+    // Once the players are warned, the restart happens even if the memory drops
+    if state.memory_restart.is_none() {
+        let used = process.usage().map_or(0, |usage| usage.memory);
+        if used < limit {
+            state.memory_since = None;
+            state.memory_deferred = false;
+            return Ok(None);
+        }
+        let above = now.duration_since(*state.memory_since.get_or_insert(now));
+        let minutes = Duration::from_secs(memory.minutes * 60);
+        if above < minutes {
+            return Ok(None);
+        }
+
+        let players = player_count(server, instance);
+        if players == Some(0) {
+            *state = reset_memory(state);
+            return Ok(Some(reason));
+        }
+        if players.is_some() && above < minutes + Duration::from_secs(memory.max_defer * 60) {
+            if !state.memory_deferred {
+                state.memory_deferred = true;
+                println!(
+                    "{}",
+                    tr!(
+                        "daemon-memory-deferred",
+                        target = key,
+                        players = players.unwrap_or_default(),
+                        minutes = memory.max_defer
+                    )
+                );
+            }
+            return Ok(None);
+        }
+
+        let lead = memory.warnings.iter().max().copied().unwrap_or_default();
+        state.memory_restart = Some(now + Duration::from_secs(lead * 60));
+        println!(
+            "{}",
+            tr!("daemon-memory-scheduled", target = key, minutes = lead)
+        );
+    }
+
+    let remaining = state
+        .memory_restart
+        .map_or(Duration::ZERO, |at| at.saturating_duration_since(now));
+    let due: Vec<u64> = memory
+        .warnings
+        .iter()
+        .copied()
+        .filter(|w| remaining <= Duration::from_secs(w * 60) && !state.memory_warned.contains(w))
+        .collect();
+    if let Some(minutes) = due.iter().min().filter(|_| !remaining.is_zero()) {
+        let message = match &memory.message {
+            Some(message) => message.replace("{minutes}", &minutes.to_string()),
+            None => tr!("daemon-memory-warning", minutes = *minutes),
+        };
+        if let Err(e) = rcon::broadcast(server, instance, &message, None) {
+            println!(
+                "{}",
+                tr!(
+                    "daemon-announce-failed",
+                    target = key,
+                    error = e.to_string()
+                )
+            );
+        }
+    }
+    state.memory_warned.extend(due);
+
+    if !remaining.is_zero() {
+        return Ok(None);
+    }
+    *state = reset_memory(state);
+    Ok(Some(reason))
+}
+
+/// Forget the progress of a memory restart
+///
+/// # Arguments
+///
+/// * `state` - The watch state
+///
+/// # Returns
+///
+/// The watch state without the memory restart
+fn reset_memory(state: &WatchState) -> WatchState {
+    WatchState {
+        failures: state.failures,
+        next_check: state.next_check,
+        next_sample: state.next_sample,
+        log_offset: state.log_offset,
+        ..WatchState::default()
+    }
+}
+
+/// Count the players of a running server without bots
+///
+/// # Arguments
+///
+/// * `server` - The installed server
+/// * `instance` - The instance, or `None` for the server itself
+///
+/// # Returns
+///
+/// The number of players, or `None` if the server could not be queried
+pub fn player_count(server: &InstalledServer, instance: Option<&ServerInstance>) -> Option<u32> {
+    let profile = GameProfile::find(server.app_id)?;
+    let port = LaunchCommand::port(&profile, server, instance)?;
+    let info = query::query_info("127.0.0.1", profile.query_port(port)).ok()?;
+    Some(u32::from(info.players.saturating_sub(info.bots)))
+}
+
+/// Evaluate a single health probe
+///
+/// # Arguments
+///
+/// * `probe` - The probe
+/// * `server` - The installed server
+/// * `instance` - The instance, or `None` for the server itself
+/// * `process` - The process of the server or instance
+/// * `state` - The watch state, holding the read position of the log file
+///
+/// # Returns
+///
+/// Ok if the server is healthy
+///
+/// # Errors
+///
+/// If the probe failed
+async fn run_probe(
+    probe: &HealthProbe,
+    server: &InstalledServer,
+    instance: Option<&ServerInstance>,
+    process: &ServerProcess,
+    state: &mut WatchState,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let profile = GameProfile::find(server.app_id);
+    let default_port = profile
+        .as_ref()
+        .and_then(|profile| LaunchCommand::port(profile, server, instance));
+
+    match probe {
+        HealthProbe::A2s { host, port } => {
+            let query_port = default_port
+                .zip(profile.as_ref())
+                .map(|(port, profile)| profile.query_port(port));
+            let port = port.or(query_port).ok_or_else(|| tr!("probe-no-port"))?;
+            query::query_info(host.as_deref().unwrap_or("127.0.0.1"), port)
+                .map_err(|e| tr!("probe-a2s-failed", port = port, error = e.to_string()))?;
+        }
+        HealthProbe::Tcp { host, port } => {
+            let port = port.or(default_port).ok_or_else(|| tr!("probe-no-port"))?;
+            let address = (host.as_deref().unwrap_or("127.0.0.1"), port)
+                .to_socket_addrs()?
+                .next()
+                .ok_or_else(|| tr!("probe-no-port"))?;
+            TcpStream::connect_timeout(&address, PROBE_TIMEOUT)
+                .map_err(|e| tr!("probe-tcp-failed", port = port, error = e.to_string()))?;
+        }
+        HealthProbe::Http { url, status } => {
+            let response = reqwest::Client::builder()
+                .timeout(PROBE_TIMEOUT)
+                .build()?
+                .get(url)
+                .send()
+                .await
+                .map_err(|e| tr!("probe-http-failed", url = url, error = e.to_string()))?;
+            let ok = match status {
+                Some(expected) => response.status().as_u16() == *expected,
+                None => response.status().is_success(),
+            };
+            if !ok {
+                return Err(tr!(
+                    "probe-http-failed",
+                    url = url,
+                    error = response.status().to_string()
+                )
+                .into());
+            }
+        }
+        HealthProbe::Log { pattern } => {
+            if let Some(line) = scan_log(process, state, pattern)? {
+                return Err(tr!("probe-log-matched", line = line).into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Scan the new lines of the server log for a pattern
+///
+/// # Arguments
+///
+/// * `process` - The process of the server or instance
+/// * `state` - The watch state, holding the read position of the log file
+/// * `pattern` - The wildcard pattern, matched anywhere in a line
+///
+/// # Returns
+///
+/// The first matching line since the last scan
+///
+/// # Errors
+///
+/// If the log file could not be read
+fn scan_log(
+    process: &ServerProcess,
+    state: &mut WatchState,
+    pattern: &str,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let Some(content) = read_log(process, &mut state.log_offset)? else {
+        return Ok(None);
+    };
+
+    let pattern = format!("*{}*", pattern.trim_matches('*'));
+    Ok(content
+        .lines()
+        .find(|line| matches_wildcard(&pattern, line))
+        .map(|line| line.to_string()))
+}
+
+/// Read the lines written to the server log since the last read
+///
+/// # Arguments
+///
+/// * `process` - The process of the server or instance
+/// * `offset` - The read position of the log file, `None` on the first read
+///
+/// # Returns
+///
+/// The new content, or `None` on the first read or if there is no log
+///
+/// # Errors
+///
+/// If the log file could not be read
+fn read_log(
+    process: &ServerProcess,
+    offset: &mut Option<u64>,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let Ok(mut file) = std::fs::File::open(&process.log_file) else {
+        return Ok(None);
+    };
+    let length = file.metadata()?.len();
+
+    // Lines written before the supervisor started watching are ignored
+    let start = match *offset {
+        Some(start) if start <= length => start,
+        // The log was rotated, its new lines start at the beginning
+        Some(_) => 0,
+        _ => {
+            *offset = Some(length);
+            return Ok(None);
+        }
+    };
+
+    file.seek(SeekFrom::Start(start))?;
+    let mut content = Vec::new();
+    file.read_to_end(&mut content)?;
+    *offset = Some(start + content.len() as u64);
+
+    Ok(Some(String::from_utf8_lossy(&content).to_string()))
+}
+
+/// Run a recovery hook script
+///
+/// The script gets the target and the reason in the environment variables
+/// `STEAMSERV_TARGET` and `STEAMSERV_REASON`.
+///
+/// # Arguments
+///
+/// * `script` - The shell command to run
+/// * `target` - The server or instance
+/// * `reason` - The reason for the recovery
+///
+/// # Returns
+///
+/// A description of the result
+///
+/// # Errors
+///
+/// If the script could not be run or failed
+fn run_hook(
+    script: &str,
+    target: &str,
+    reason: &str,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let status = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(script)
+        .env("STEAMSERV_TARGET", target)
+        .env("STEAMSERV_REASON", reason)
+        .status()?;
+
+    if !status.success() {
+        return Err(tr!(
+            "daemon-hook-failed",
+            script = script,
+            status = status.to_string()
+        )
+        .into());
+    }
+
+    Ok(tr!("daemon-hook-run", script = script))
+}
+
+/// Reap the exited servers that were restarted by the supervisor and the job runners
+fn reap_children() {
+    // SAFETY: waitpid with WNOHANG does not block and only collects exited children
+    unsafe { while libc::waitpid(-1, std::ptr::null_mut(), libc::WNOHANG) > 0 {} }
+}
@@ -0,0 +1,211 @@
+use std::{path::PathBuf, time::Duration};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    tr,
+    utils::{duration::format_clock, fs, Config},
+};
+
+/// What an update of all servers did with one server
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum UpdateOutcome {
+    /// The latest build was already installed
+    Current {
+        build: String,
+    },
+    Updated {
+        /// The build before the update, `None` if the app manifest could not be read
+        from: Option<String>,
+        to: String,
+        /// The estimated download size in bytes
+        download: Option<u64>,
+    },
+    Failed {
+        reason: String,
+    },
+    /// Players were connected, the server was left running on its build
+    Skipped {
+        players: u32,
+    },
+    /// Steam could not be reached, the update is tried again on the next run
+    Deferred {
+        reason: String,
+    },
+}
+
+/// The outcome of one server in an update report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerUpdate {
+    pub server: String,
+    #[serde(flatten)]
+    pub outcome: UpdateOutcome,
+}
+
+/// Consolidated report of an update of all servers
+///
+/// The last report is kept as `update-report.json` in the steamserv directory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateReport {
+    pub started: DateTime<Utc>,
+    /// The wall time of the whole run
+    pub seconds: f64,
+    pub servers: Vec<ServerUpdate>,
+}
+
+impl UpdateReport {
+    /// Count the servers with an outcome
+    ///
+    /// # Arguments
+    ///
+    /// * `matches` - Checks the outcome of a server
+    ///
+    /// # Returns
+    ///
+    /// The number of servers whose outcome matches
+    pub fn count(&self, matches: impl Fn(&UpdateOutcome) -> bool) -> usize {
+        self.servers.iter().filter(|s| matches(&s.outcome)).count()
+    }
+
+    /// Get the estimated download size of all updated servers
+    ///
+    /// # Returns
+    ///
+    /// The size in bytes, updates with an unknown size count as zero
+    pub fn download(&self) -> u64 {
+        self.servers
+            .iter()
+            .filter_map(|s| match s.outcome {
+                UpdateOutcome::Updated { download, .. } => download,
+                _ => None,
+            })
+            .sum()
+    }
+
+    /// Describe the report with a summary line and a line per server
+    ///
+    /// # Returns
+    ///
+    /// The translated report
+    pub fn summary(&self) -> String {
+        let mut lines = vec![tr!(
+            "update-report",
+            time = format_clock(Duration::from_secs_f64(self.seconds.max(0.0))),
+            updated = self.count(|o| matches!(o, UpdateOutcome::Updated { .. })),
+            current = self.count(|o| matches!(o, UpdateOutcome::Current { .. })),
+            failed = self.count(|o| matches!(o, UpdateOutcome::Failed { .. })),
+            skipped = self.count(|o| matches!(o, UpdateOutcome::Skipped { .. })),
+            deferred = self.count(|o| matches!(o, UpdateOutcome::Deferred { .. })),
+            size = fs::format_size(self.download())
+        )];
+        for update in &self.servers {
+            let server = update.server.as_str();
+            lines.push(match &update.outcome {
+                UpdateOutcome::Current { build } => {
+                    tr!("update-report-current", server = server, build = build)
+                }
+                UpdateOutcome::Updated { from, to, .. } => tr!(
+                    "update-report-updated",
+                    server = server,
+                    from = from.as_deref().unwrap_or("-"),
+                    to = to
+                ),
+                UpdateOutcome::Failed { reason } => tr!(
+                    "update-report-failed",
+                    server = server,
+                    reason = reason.lines().collect::<Vec<&str>>().join(" ")
+                ),
+                UpdateOutcome::Skipped { players } => {
+                    tr!("update-report-skipped", server = server, players = players)
+                }
+                UpdateOutcome::Deferred { .. } => tr!("update-report-deferred", server = server),
+            });
+        }
+        lines.join("\n  ")
+    }
+
+    /// Save the report as the last one
+    ///
+    /// # Returns
+    ///
+    /// Ok if the report was saved
+    ///
+    /// # Errors
+    ///
+    /// If the steamserv directory could not be found or the file could not be written
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::path()?;
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        fs::write_atomic(&path, serde_json::to_string_pretty(self)?.as_bytes())
+    }
+
+    /// Get the path to the last report
+    ///
+    /// # Returns
+    ///
+    /// The path to `update-report.json`
+    ///
+    /// # Errors
+    ///
+    /// If the steamserv directory could not be found
+    fn path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        Ok(Config::data_dir()?.join("update-report.json"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_report_totals() {
+        let update = |server: &str, outcome| ServerUpdate {
+            server: server.to_string(),
+            outcome,
+        };
+        let report = UpdateReport {
+            started: Utc::now(),
+            seconds: 95.0,
+            servers: vec![
+                update(
+                    "cs2",
+                    UpdateOutcome::Updated {
+                        from: Some("100".to_string()),
+                        to: "101".to_string(),
+                        download: Some(2048),
+                    },
+                ),
+                update(
+                    "valheim",
+                    UpdateOutcome::Updated {
+                        from: None,
+                        to: "7".to_string(),
+                        download: None,
+                    },
+                ),
+                update(
+                    "rust",
+                    UpdateOutcome::Current {
+                        build: "5".to_string(),
+                    },
+                ),
+                update("ark", UpdateOutcome::Skipped { players: 3 }),
+            ],
+        };
+
+        assert_eq!(report.download(), 2048);
+        assert_eq!(
+            report.count(|o| matches!(o, UpdateOutcome::Updated { .. })),
+            2
+        );
+        assert_eq!(
+            report.count(|o| matches!(o, UpdateOutcome::Failed { .. })),
+            0
+        );
+        let json = serde_json::to_value(&report.servers[3]).unwrap();
+        assert_eq!(json["outcome"], "skipped");
+        assert_eq!(json["players"], 3);
+    }
+}
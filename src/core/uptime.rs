@@ -0,0 +1,177 @@
+use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Utc};
+use serde::Serialize;
+
+use crate::{tr, utils::HistoryEvent};
+
+/// History kinds after which a server is running
+const UP_KINDS: &[&str] = &["server-started"];
+/// History kinds after which a server is down
+const DOWN_KINDS: &[&str] = &["server-stopped", "server-exited", "health-check-failed"];
+/// History kinds that count as a crash
+const CRASH_KINDS: &[&str] = &["server-exited", "health-check-failed"];
+
+/// Availability of a server or instance in a period
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct UptimeReport {
+    pub target: String,
+    /// Seconds the server was running
+    pub uptime: i64,
+    /// Seconds of the period covered by the history of the server
+    pub tracked: i64,
+    /// Share of the tracked time the server was running, in percent
+    pub availability: Option<f64>,
+    pub starts: usize,
+    pub crashes: usize,
+}
+
+impl UptimeReport {
+    /// Summarize the history of a server or instance in a period
+    ///
+    /// The period before the first recorded event of the server is not tracked,
+    /// so servers are not counted as down before steamserv recorded their starts.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The server or instance
+    /// * `events` - The history of the target in the order it was recorded
+    /// * `from` - The start of the period
+    /// * `to` - The end of the period, later times are cut off at `now`
+    /// * `now` - The current time
+    ///
+    /// # Returns
+    ///
+    /// The report of the target
+    pub fn build(
+        target: &str,
+        events: &[HistoryEvent],
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let to = to.min(now);
+        let events: Vec<&HistoryEvent> = events
+            .iter()
+            .filter(|e| e.target == target)
+            .filter(|e| {
+                UP_KINDS.contains(&e.kind.as_str()) || DOWN_KINDS.contains(&e.kind.as_str())
+            })
+            .collect();
+
+        let mut report = Self {
+            target: target.to_string(),
+            uptime: 0,
+            tracked: 0,
+            availability: None,
+            starts: 0,
+            crashes: 0,
+        };
+        let Some(first) = events.first() else {
+            return report;
+        };
+        let start = from.max(first.timestamp);
+        if start >= to {
+            return report;
+        }
+
+        let mut up_since: Option<DateTime<Utc>> = None;
+        for event in &events {
+            if event.timestamp >= to {
+                break;
+            }
+            let in_period = event.timestamp >= from;
+            if UP_KINDS.contains(&event.kind.as_str()) {
+                up_since.get_or_insert(event.timestamp.max(start));
+                report.starts += usize::from(in_period);
+            } else {
+                if let Some(since) = up_since.take() {
+                    report.uptime += (event.timestamp.max(start) - since).num_seconds();
+                }
+                report.crashes +=
+                    usize::from(in_period && CRASH_KINDS.contains(&event.kind.as_str()));
+            }
+        }
+        if let Some(since) = up_since {
+            report.uptime += (to - since).num_seconds();
+        }
+
+        report.tracked = (to - start).num_seconds();
+        report.availability =
+            (report.tracked > 0).then(|| report.uptime as f64 * 100.0 / report.tracked as f64);
+        report
+    }
+}
+
+/// Get the period of a month
+///
+/// # Arguments
+///
+/// * `month` - The month as `YYYY-MM`, or `None` for the current month
+///
+/// # Returns
+///
+/// The first second of the month and of the following month
+///
+/// # Errors
+///
+/// If the month is not in the form `YYYY-MM`
+pub fn month_period(
+    month: Option<&str>,
+) -> Result<(DateTime<Utc>, DateTime<Utc>), Box<dyn std::error::Error>> {
+    let first = match month {
+        Some(month) => NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d")
+            .map_err(|_| tr!("report-month-invalid", month = month))?,
+        None => Utc::now().date_naive().with_day(1).unwrap_or_default(),
+    };
+    let next = first
+        .checked_add_months(chrono::Months::new(1))
+        .ok_or_else(|| tr!("report-month-invalid", month = first.to_string()))?;
+
+    let start_of =
+        |date: NaiveDate| Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap_or_default());
+    Ok((start_of(first), start_of(next)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(target: &str, kind: &str, time: &str) -> HistoryEvent {
+        HistoryEvent {
+            timestamp: time.parse().unwrap(),
+            target: target.to_string(),
+            kind: kind.to_string(),
+            message: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_build_uptime_report() {
+        let (from, to) = month_period(Some("2026-09")).unwrap();
+        assert_eq!(to, "2026-10-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap());
+
+        let events = [
+            // Running since August, the uptime counts from the start of the month
+            event("ark", "server-started", "2026-08-20T00:00:00Z"),
+            event("ark", "server-exited", "2026-09-10T00:00:00Z"),
+            event("ark/pvp", "server-started", "2026-09-10T00:00:00Z"),
+            event("ark", "server-started", "2026-09-11T00:00:00Z"),
+            event("ark", "server-stopped", "2026-09-21T00:00:00Z"),
+            event("ark", "server-started", "2026-09-29T00:00:00Z"),
+        ];
+        let now = "2026-10-16T00:00:00Z".parse().unwrap();
+        let report = UptimeReport::build("ark", &events, from, to, now);
+        assert_eq!(report.uptime, 21 * 86400);
+        assert_eq!(report.tracked, 30 * 86400);
+        assert_eq!(report.availability, Some(70.0));
+        assert_eq!(report.starts, 2);
+        assert_eq!(report.crashes, 1);
+
+        // The instance is only tracked since its first start
+        let report = UptimeReport::build("ark/pvp", &events, from, to, now);
+        assert_eq!(report.tracked, 21 * 86400);
+        assert_eq!(report.availability, Some(100.0));
+
+        let report = UptimeReport::build("rust", &events, from, to, now);
+        assert_eq!(report.availability, None);
+    }
+}
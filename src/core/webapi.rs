@@ -0,0 +1,826 @@
+use std::time::{Duration, Instant};
+
+use chacha20poly1305::aead::{rand_core::RngCore, OsRng};
+use reqwest::{header, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::{
+    tr,
+    utils::{Config, Progress, TimeoutConfig},
+};
+
+const API_BASE_URL: &str = "https://api.steampowered.com";
+const STORE_BASE_URL: &str = "https://store.steampowered.com";
+
+/// Minimum delay between two requests to the Steam Web API
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_millis(250);
+/// Maximum number of apps per page of the store app list
+const STORE_PAGE_SIZE: u32 = 50_000;
+/// Number of retries for failed requests
+const MAX_RETRIES: u32 = 3;
+/// Base delay for the exponential retry backoff
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Client for all HTTP calls to the Steam Web API and the Steam Store
+pub struct SteamWebApi {
+    client: reqwest::Client,
+    api_key: Option<String>,
+    offline: bool,
+    last_request: Mutex<Option<Instant>>,
+}
+
+/// Validators of a cached response, sent with the next request so the server
+/// only answers with content when it changed
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct CacheValidators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+/// The body of a response with the validators to request it again
+struct Fetched {
+    content: Vec<u8>,
+    validators: CacheValidators,
+}
+
+#[derive(Debug, Deserialize)]
+struct AppListResponse {
+    applist: AppList,
+}
+
+#[derive(Debug, Deserialize)]
+struct AppList {
+    apps: Vec<App>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StoreAppListResponse {
+    response: StoreAppPage,
+}
+
+/// A page of the app list of the store service
+#[derive(Debug, Deserialize, Default)]
+pub struct StoreAppPage {
+    #[serde(default)]
+    pub apps: Vec<App>,
+    /// True if more apps follow after `last_appid`
+    #[serde(default)]
+    pub have_more_results: bool,
+    /// The last app of the page, the next page starts after it
+    pub last_appid: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct App {
+    pub appid: u32,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AppDetailsEntry {
+    success: bool,
+    data: Option<AppDetails>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct AppDetails {
+    #[serde(rename = "steam_appid")]
+    pub app_id: u32,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub app_type: String,
+    #[serde(default)]
+    pub short_description: String,
+    #[serde(default)]
+    pub platforms: AppPlatforms,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct AppPlatforms {
+    #[serde(default)]
+    pub windows: bool,
+    #[serde(default)]
+    pub mac: bool,
+    #[serde(default)]
+    pub linux: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct NewsResponse {
+    appnews: AppNews,
+}
+
+#[derive(Debug, Deserialize)]
+struct AppNews {
+    newsitems: Vec<NewsItem>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct NewsItem {
+    pub gid: String,
+    pub title: String,
+    pub url: String,
+    pub author: String,
+    pub contents: String,
+    pub date: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct PublishedFileResponse {
+    response: PublishedFileList,
+}
+
+#[derive(Debug, Deserialize)]
+struct PublishedFileList {
+    #[serde(default)]
+    publishedfiledetails: Vec<PublishedFileDetails>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct PublishedFileDetails {
+    pub publishedfileid: String,
+    pub result: i32,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub consumer_appid: u32,
+    #[serde(default)]
+    pub file_size: Option<String>,
+    #[serde(default)]
+    pub time_updated: i64,
+    #[serde(default)]
+    pub tags: Vec<PublishedFileTag>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct PublishedFileTag {
+    pub tag: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GameServerAccountResponse {
+    response: GameServerAccount,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct GameServerAccount {
+    pub steamid: String,
+    pub login_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct VanityUrlResponse {
+    response: VanityUrl,
+}
+
+#[derive(Debug, Deserialize)]
+struct VanityUrl {
+    success: u32,
+    steamid: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PlayerSummariesResponse {
+    response: PlayerSummaries,
+}
+
+#[derive(Debug, Deserialize)]
+struct PlayerSummaries {
+    #[serde(default)]
+    players: Vec<PlayerSummary>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct PlayerSummary {
+    pub steamid: String,
+    pub personaname: String,
+    pub profileurl: String,
+}
+
+impl SteamWebApi {
+    /// Create a new Steam Web API client
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration to take the API key and network settings from
+    ///
+    /// # Returns
+    ///
+    /// The created client
+    ///
+    /// # Errors
+    ///
+    /// If the HTTP client could not be created
+    pub fn new(config: &Config) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self {
+            client: config
+                .network
+                .http_client(TimeoutConfig::limit(config.timeouts.network))?,
+            api_key: config.api_key.clone(),
+            offline: config.is_offline(),
+            last_request: Mutex::new(None),
+        })
+    }
+
+    /// Get the full list of Steam apps if it changed
+    ///
+    /// # Arguments
+    ///
+    /// * `validators` - The validators of the list that was fetched last
+    /// * `progress` - An optional progress bar showing the download progress
+    ///
+    /// # Returns
+    ///
+    /// The list of all Steam apps with its validators, or `None` if the list
+    /// did not change since it was fetched with the given validators
+    ///
+    /// # Errors
+    ///
+    /// If the request failed or the response could not be parsed
+    pub async fn get_app_list(
+        &self,
+        validators: &CacheValidators,
+        progress: Option<&mut Progress>,
+    ) -> Result<Option<(Vec<App>, CacheValidators)>, Box<dyn std::error::Error>> {
+        let url = format!("{}/ISteamApps/GetAppList/v2/", API_BASE_URL);
+        let Some(fetched) = self
+            .request(reqwest::Method::GET, &url, &[], validators, progress)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let response: AppListResponse = serde_json::from_slice(&fetched.content)?;
+        Ok(Some((response.applist.apps, fetched.validators)))
+    }
+
+    /// Get a page of the apps in the store that changed since a point in time
+    ///
+    /// The store service needs an API key. Unlike the full app list it can
+    /// return only the apps that were added or changed since the last update.
+    ///
+    /// # Arguments
+    ///
+    /// * `modified_since` - Only return apps changed after this Unix timestamp, all apps if `None`
+    /// * `last_appid` - Continue after this app, from the `last_appid` of the previous page
+    /// * `progress` - An optional progress bar showing the download progress
+    ///
+    /// # Returns
+    ///
+    /// The page of apps
+    ///
+    /// # Errors
+    ///
+    /// If no API key is configured, the request failed or the response could not be parsed
+    pub async fn get_store_apps(
+        &self,
+        modified_since: Option<i64>,
+        last_appid: Option<u32>,
+        progress: Option<&mut Progress>,
+    ) -> Result<StoreAppPage, Box<dyn std::error::Error>> {
+        if self.api_key.is_none() {
+            return Err(tr!("webapi-key-store-apps").into());
+        }
+
+        let url = format!("{}/IStoreService/GetAppList/v1/", API_BASE_URL);
+        let mut query = vec![
+            ("include_games", "true".to_string()),
+            ("include_dlc", "false".to_string()),
+            ("include_software", "true".to_string()),
+            ("include_videos", "false".to_string()),
+            ("include_hardware", "false".to_string()),
+            ("max_results", STORE_PAGE_SIZE.to_string()),
+        ];
+        if let Some(modified_since) = modified_since {
+            query.push(("if_modified_since", modified_since.to_string()));
+        }
+        if let Some(last_appid) = last_appid {
+            query.push(("last_appid", last_appid.to_string()));
+        }
+
+        let content = self
+            .request_bytes(reqwest::Method::GET, &url, &query, progress)
+            .await?;
+        let response: StoreAppListResponse = serde_json::from_slice(&content)?;
+        Ok(response.response)
+    }
+
+    /// Check if an API key is configured
+    ///
+    /// # Returns
+    ///
+    /// True if requests that need an API key can be sent
+    pub fn has_api_key(&self) -> bool {
+        self.api_key.is_some()
+    }
+
+    /// Get the store details of an app
+    ///
+    /// # Arguments
+    ///
+    /// * `app_id` - The Steam App ID
+    ///
+    /// # Returns
+    ///
+    /// The app details or `None` if the store has no details for the app
+    ///
+    /// # Errors
+    ///
+    /// If the request failed or the response could not be parsed
+    pub async fn get_app_details(
+        &self,
+        app_id: u32,
+    ) -> Result<Option<AppDetails>, Box<dyn std::error::Error>> {
+        let url = format!("{}/api/appdetails", STORE_BASE_URL);
+        // The English name and a fixed region, so apps that are hidden in the
+        // store of the local region still return their details
+        let query = [
+            ("appids", app_id.to_string()),
+            ("l", "english".to_string()),
+            ("cc", "us".to_string()),
+        ];
+        let mut response: std::collections::HashMap<String, AppDetailsEntry> =
+            self.get_json(&url, &query).await?;
+
+        Ok(response
+            .remove(&app_id.to_string())
+            .filter(|entry| entry.success)
+            .and_then(|entry| entry.data))
+    }
+
+    /// Get the latest news of an app
+    ///
+    /// # Arguments
+    ///
+    /// * `app_id` - The Steam App ID
+    /// * `count` - The maximum number of news items
+    ///
+    /// # Returns
+    ///
+    /// The news items of the app
+    ///
+    /// # Errors
+    ///
+    /// If the request failed or the response could not be parsed
+    pub async fn get_news_for_app(
+        &self,
+        app_id: u32,
+        count: u32,
+    ) -> Result<Vec<NewsItem>, Box<dyn std::error::Error>> {
+        let url = format!("{}/ISteamNews/GetNewsForApp/v2/", API_BASE_URL);
+        let query = [("appid", app_id.to_string()), ("count", count.to_string())];
+        let response: NewsResponse = self.get_json(&url, &query).await?;
+        Ok(response.appnews.newsitems)
+    }
+
+    /// Get the details of workshop items
+    ///
+    /// # Arguments
+    ///
+    /// * `file_ids` - The published file IDs of the workshop items
+    ///
+    /// # Returns
+    ///
+    /// The details of the workshop items
+    ///
+    /// # Errors
+    ///
+    /// If no API key is configured, the request failed or the response could not be parsed
+    pub async fn get_published_file_details(
+        &self,
+        file_ids: &[u64],
+    ) -> Result<Vec<PublishedFileDetails>, Box<dyn std::error::Error>> {
+        if self.api_key.is_none() {
+            return Err(tr!("webapi-key-workshop").into());
+        }
+
+        let url = format!("{}/IPublishedFileService/GetDetails/v1/", API_BASE_URL);
+        let query: Vec<(String, String)> = file_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (format!("publishedfileids[{}]", i), id.to_string()))
+            .collect();
+        let mut query: Vec<(&str, String)> = query
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.clone()))
+            .collect();
+        query.push(("includetags", "true".to_string()));
+
+        let response: PublishedFileResponse = self.get_json(&url, &query).await?;
+        Ok(response.response.publishedfiledetails)
+    }
+
+    /// Create a game server account and its login token
+    ///
+    /// # Arguments
+    ///
+    /// * `app_id` - The Steam App ID of the game the token is used for
+    /// * `memo` - A note to identify the token
+    ///
+    /// # Returns
+    ///
+    /// The created game server account
+    ///
+    /// # Errors
+    ///
+    /// If no API key is configured, the request failed or the response could not be parsed
+    pub async fn create_game_server_account(
+        &self,
+        app_id: u32,
+        memo: &str,
+    ) -> Result<GameServerAccount, Box<dyn std::error::Error>> {
+        if self.api_key.is_none() {
+            return Err(tr!("webapi-key-tokens").into());
+        }
+
+        let url = format!("{}/IGameServersService/CreateAccount/v1/", API_BASE_URL);
+        let params = [("appid", app_id.to_string()), ("memo", memo.to_string())];
+        let response: GameServerAccountResponse = self.post_json(&url, &params).await?;
+        Ok(response.response)
+    }
+
+    /// Resolve the custom URL of a Steam Community profile
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The custom URL name, e.g. `gabelogannewell`
+    ///
+    /// # Returns
+    ///
+    /// The SteamID64, or `None` if no profile uses the name
+    ///
+    /// # Errors
+    ///
+    /// If no API key is configured, the request failed or the response could not be parsed
+    pub async fn resolve_vanity_url(
+        &self,
+        name: &str,
+    ) -> Result<Option<u64>, Box<dyn std::error::Error>> {
+        if self.api_key.is_none() {
+            return Err(tr!("webapi-key-vanity").into());
+        }
+
+        let url = format!("{}/ISteamUser/ResolveVanityURL/v1/", API_BASE_URL);
+        let query = [("vanityurl", name.to_string())];
+        let response: VanityUrlResponse = self.get_json(&url, &query).await?;
+        Ok(match response.response.success {
+            1 => response.response.steamid.and_then(|id| id.parse().ok()),
+            _ => None,
+        })
+    }
+
+    /// Get the public profile summaries of players
+    ///
+    /// # Arguments
+    ///
+    /// * `steam_ids` - The SteamID64s of the players
+    ///
+    /// # Returns
+    ///
+    /// The summaries of the players with a profile
+    ///
+    /// # Errors
+    ///
+    /// If no API key is configured, the request failed or the response could not be parsed
+    pub async fn get_player_summaries(
+        &self,
+        steam_ids: &[u64],
+    ) -> Result<Vec<PlayerSummary>, Box<dyn std::error::Error>> {
+        if self.api_key.is_none() {
+            return Err(tr!("webapi-key-players").into());
+        }
+
+        let url = format!("{}/ISteamUser/GetPlayerSummaries/v2/", API_BASE_URL);
+        let ids: Vec<String> = steam_ids.iter().map(u64::to_string).collect();
+        let query = [("steamids", ids.join(","))];
+        let response: PlayerSummariesResponse = self.get_json(&url, &query).await?;
+        Ok(response.response.players)
+    }
+
+    /// Send a GET request and parse the JSON response
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to request
+    /// * `query` - The query parameters
+    ///
+    /// # Returns
+    ///
+    /// The parsed response
+    ///
+    /// # Errors
+    ///
+    /// If the request failed or the response could not be parsed
+    async fn get_json<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        query: &[(&str, String)],
+    ) -> Result<T, Box<dyn std::error::Error>> {
+        let content = self
+            .request_bytes(reqwest::Method::GET, url, query, None)
+            .await?;
+        Ok(serde_json::from_slice(&content)?)
+    }
+
+    /// Send a POST request and parse the JSON response
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to request
+    /// * `params` - The form parameters
+    ///
+    /// # Returns
+    ///
+    /// The parsed response
+    ///
+    /// # Errors
+    ///
+    /// If the request failed or the response could not be parsed
+    async fn post_json<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        params: &[(&str, String)],
+    ) -> Result<T, Box<dyn std::error::Error>> {
+        let content = self
+            .request_bytes(reqwest::Method::POST, url, params, None)
+            .await?;
+        Ok(serde_json::from_slice(&content)?)
+    }
+
+    /// Send a request with rate limiting and retries
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The HTTP method, parameters are sent as form for POST requests
+    /// * `url` - The URL to request
+    /// * `query` - The query parameters
+    /// * `progress` - An optional progress bar showing the download progress
+    ///
+    /// # Returns
+    ///
+    /// The body of the response
+    ///
+    /// # Errors
+    ///
+    /// If the offline mode is enabled or the request still failed after all retries
+    async fn request_bytes(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+        query: &[(&str, String)],
+        progress: Option<&mut Progress>,
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let fetched = self
+            .request(method, url, query, &CacheValidators::default(), progress)
+            .await?;
+        // Without validators the server always answers with content
+        Ok(fetched.map(|f| f.content).unwrap_or_default())
+    }
+
+    /// Send a conditional request with rate limiting and retries
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The HTTP method, parameters are sent as form for POST requests
+    /// * `url` - The URL to request
+    /// * `query` - The query parameters
+    /// * `validators` - The validators of the cached response, empty to always get content
+    /// * `progress` - An optional progress bar showing the download progress
+    ///
+    /// # Returns
+    ///
+    /// The body of the response with its validators, or `None` if the content
+    /// did not change
+    ///
+    /// # Errors
+    ///
+    /// If the offline mode is enabled or the request still failed after all retries
+    async fn request(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+        query: &[(&str, String)],
+        validators: &CacheValidators,
+        mut progress: Option<&mut Progress>,
+    ) -> Result<Option<Fetched>, Box<dyn std::error::Error>> {
+        if self.offline {
+            return Err(tr!("webapi-offline").into());
+        }
+
+        let mut attempt = 0;
+        loop {
+            self.wait_for_rate_limit().await;
+
+            match self
+                .try_request(
+                    method.clone(),
+                    url,
+                    query,
+                    validators,
+                    progress.as_deref_mut(),
+                )
+                .await
+            {
+                Ok(fetched) => return Ok(fetched),
+                Err(e) if attempt < MAX_RETRIES && Self::is_retryable(&method, &e) => {
+                    attempt += 1;
+                    tokio::time::sleep(Self::retry_delay(attempt)).await;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// Send a single request
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The HTTP method
+    /// * `url` - The URL to request
+    /// * `query` - The query parameters
+    /// * `validators` - The validators of the cached response
+    /// * `progress` - An optional progress bar showing the download progress
+    ///
+    /// # Returns
+    ///
+    /// The body of the response with its validators, or `None` if the server
+    /// answered `304 Not Modified`
+    ///
+    /// # Errors
+    ///
+    /// If the request failed or returned an error status
+    async fn try_request(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+        query: &[(&str, String)],
+        validators: &CacheValidators,
+        mut progress: Option<&mut Progress>,
+    ) -> Result<Option<Fetched>, reqwest::Error> {
+        let mut params = query.to_vec();
+        if let Some(key) = &self.api_key {
+            if url.starts_with(API_BASE_URL) {
+                params.push(("key", key.clone()));
+            }
+        }
+
+        let mut request = if method == reqwest::Method::POST {
+            self.client.post(url).form(&params)
+        } else {
+            self.client.request(method, url).query(&params)
+        };
+        if let Some(etag) = &validators.etag {
+            request = request.header(header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            request = request.header(header::IF_MODIFIED_SINCE, last_modified);
+        }
+
+        let mut response = Self::send(request).await?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(None);
+        }
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(String::from)
+        };
+        let validators = CacheValidators {
+            etag: header(header::ETAG),
+            last_modified: header(header::LAST_MODIFIED),
+        };
+        let total_size = response.content_length();
+
+        let mut content = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(reqwest::Error::without_url)?
+        {
+            content.extend_from_slice(&chunk);
+            if let Some(progress) = progress.as_deref_mut() {
+                let _ = progress.update_bytes(content.len() as u64, total_size);
+            }
+        }
+
+        Ok(Some(Fetched {
+            content,
+            validators,
+        }))
+    }
+
+    /// Send a request and check its status
+    ///
+    /// The URL is removed from the error, it contains the API key.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request to send
+    ///
+    /// # Returns
+    ///
+    /// The response
+    ///
+    /// # Errors
+    ///
+    /// If the request failed or returned an error status
+    async fn send(request: reqwest::RequestBuilder) -> Result<reqwest::Response, reqwest::Error> {
+        request
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(reqwest::Error::without_url)
+    }
+
+    /// Wait until the minimum interval since the last request has passed
+    async fn wait_for_rate_limit(&self) {
+        let mut last_request = self.last_request.lock().await;
+        if let Some(last) = *last_request {
+            let elapsed = last.elapsed();
+            if elapsed < MIN_REQUEST_INTERVAL {
+                tokio::time::sleep(MIN_REQUEST_INTERVAL - elapsed).await;
+            }
+        }
+        *last_request = Some(Instant::now());
+    }
+
+    /// Get the delay before a retry
+    ///
+    /// The delay grows exponentially and gets a random jitter of up to the base
+    /// delay, so clients that were rate limited together do not retry together.
+    ///
+    /// # Arguments
+    ///
+    /// * `attempt` - The number of the retry, starting at 1
+    ///
+    /// # Returns
+    ///
+    /// The delay to wait
+    fn retry_delay(attempt: u32) -> Duration {
+        let jitter = RETRY_BASE_DELAY.mul_f64(f64::from(OsRng.next_u32()) / f64::from(u32::MAX));
+        RETRY_BASE_DELAY * 2u32.pow(attempt - 1) + jitter
+    }
+
+    /// Check if a failed request should be retried
+    ///
+    /// Only GET requests are idempotent. A POST request, e.g. creating a game
+    /// server account, may already have taken effect after a timeout or an error
+    /// status, so it is only retried if the connection could not be established
+    /// and the request was never sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The HTTP method of the request
+    /// * `error` - The error of the failed request
+    ///
+    /// # Returns
+    ///
+    /// True if the request failed because of a connection problem, or for a GET
+    /// request also because of a timeout, rate limiting or a server error
+    fn is_retryable(method: &reqwest::Method, error: &reqwest::Error) -> bool {
+        if error.is_connect() {
+            return true;
+        }
+        if method != reqwest::Method::GET {
+            return false;
+        }
+        if error.is_timeout() {
+            return true;
+        }
+
+        match error.status() {
+            Some(status) => status.as_u16() == 429 || status.is_server_error(),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_send_error_hides_api_key() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/ISteamUser/v1/", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = stream.read(&mut [0; 1024]);
+            let _ = stream.write_all(b"HTTP/1.1 403 Forbidden\r\ncontent-length: 0\r\n\r\n");
+        });
+
+        let request = reqwest::Client::new()
+            .get(&url)
+            .query(&[("key", "SECRETKEY")]);
+        let error = SteamWebApi::send(request).await.unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::FORBIDDEN));
+        assert!(!error.to_string().contains("SECRETKEY"));
+    }
+}
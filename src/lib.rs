@@ -3,8 +3,54 @@ pub mod core;
 pub mod utils;
 
 pub mod prelude {
+    pub use crate::cli::aliases::handle_aliases_command;
+    pub use crate::cli::bench::handle_bench_command;
+    pub use crate::cli::bundle::handle_bundle_command;
+    pub use crate::cli::clean::handle_clean_command;
+    pub use crate::cli::cluster::handle_cluster_command;
     pub use crate::cli::commands::Commands;
+    pub use crate::cli::config::handle_config_command;
+    pub use crate::cli::daemon::handle_daemon_command;
+    pub use crate::cli::deps::handle_deps_command;
+    pub use crate::cli::doctor::handle_doctor_command;
+    pub use crate::cli::env::{handle_args_command, handle_env_command};
+    pub use crate::cli::export::handle_export_command;
+    pub use crate::cli::file_sync::handle_sync_files_command;
+    pub use crate::cli::game_config::{handle_config_get_command, handle_config_set_command};
+    pub use crate::cli::history::handle_history_command;
+    pub use crate::cli::import::handle_import_command;
+    pub use crate::cli::info::handle_info_command;
+    pub use crate::cli::install::handle_install_command;
+    pub use crate::cli::instance::handle_instance_command;
+    pub use crate::cli::jobs::handle_jobs_command;
+    pub use crate::cli::lan_cache::handle_lan_cache_command;
+    pub use crate::cli::launch::handle_launch_command;
+    pub use crate::cli::lint::handle_lint_command;
     pub use crate::cli::list::handle_list_command;
+    pub use crate::cli::logs::handle_logs_command;
+    pub use crate::cli::maintenance::handle_maintenance_command;
+    pub use crate::cli::maps::handle_maps_command;
+    pub use crate::cli::migrate::handle_migrate_command;
+    pub use crate::cli::mods::handle_mods_command;
+    pub use crate::cli::nat::handle_nat_check_command;
+    pub use crate::cli::new::handle_new_command;
+    pub use crate::cli::owner::handle_owner_command;
+    pub use crate::cli::perms::handle_fix_perms_command;
+    pub use crate::cli::players::{handle_admins_command, handle_players_command};
+    pub use crate::cli::profile::handle_profile_command;
+    pub use crate::cli::rcon::handle_rcon_command;
+    pub use crate::cli::report::handle_report_command;
+    pub use crate::cli::secrets::handle_secrets_command;
+    pub use crate::cli::server::{
+        handle_restart_command, handle_start_command, handle_status_command, handle_stop_command,
+    };
+    pub use crate::cli::snapshots::{handle_rollback_command, handle_snapshots_command};
+    pub use crate::cli::stats::handle_stats_command;
+    pub use crate::cli::steamid::handle_steamid_command;
+    pub use crate::cli::timezone::handle_timezone_command;
+    pub use crate::cli::token::handle_token_command;
+    pub use crate::cli::top::handle_top_command;
+    pub use crate::cli::updates::handle_check_updates_command;
     pub use crate::cli::Cli;
     pub use crate::core::SteamCMD;
     pub use crate::utils::{default_spinner, Config, Progress, ProgressStyle, ServerCache};
@@ -1,40 +1,335 @@
-use clap::Parser;
-use steamserv_rs::prelude::*;
+use std::io::IsTerminal;
+
+use clap::{error::ErrorKind, CommandFactory, Parser};
+use steamserv_rs::{
+    prelude::*,
+    tr,
+    utils::{events, i18n, remote, runtime, ProgressFormat, StateStore},
+};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let commands: Vec<String> = Cli::command()
+        .get_subcommands()
+        .map(|command| command.get_name().to_string())
+        .collect();
+    // Only the settings are read, the secrets are not decrypted before the
+    // command line is known
+    let args: Vec<String> = std::env::args().collect();
+    let args = match Config::settings_store().and_then(|store| store.load()) {
+        Ok(config) => config.commands.expand(args, &commands),
+        Err(_) => args,
+    };
+    let cli = Cli::parse_from(&args);
+    runtime::set_offline(cli.offline);
+    runtime::set_sudo(cli.sudo);
+
     let config = Config::load()?;
+    i18n::init(config.language.as_deref());
+    let json = cli.progress == ProgressFormat::Json;
+    runtime::set_json_progress(json);
+    runtime::set_plain(json || cli.plain || config.plain || !std::io::stdout().is_terminal());
+    events::init(&config)?;
+
+    // Checked before forwarding, a remote command may change the servers too
+    if config.is_read_only() && cli.command.as_ref().is_some_and(|c| !c.is_read_only()) {
+        return Err(tr!("read-only-denied").into());
+    }
+    if let Some(name) = cli.remote.as_ref().or(config.default_remote.as_ref()) {
+        let host = config
+            .remotes
+            .get(name)
+            .ok_or_else(|| tr!("remote-not-found", host = name.as_str()))?;
+        let status = host.run(&remote::forwarded_args(&args[1..]))?;
+        std::process::exit(status.code().unwrap_or(1));
+    }
+    if !remote::is_local_supported()
+        && (!config.is_initialized || cli.command.as_ref().is_some_and(Commands::needs_local_host))
+    {
+        return Err(tr!("remote-management-only", os = std::env::consts::OS).into());
+    }
 
     if !config.is_initialized {
         config.init().await?;
-        println!("Creating initial server cache...");
-        let mut cache = ServerCache::default();
-        cache.update_cache().await?;
-        println!("Setup complete! You can now use steamserv.");
+        if config.is_offline() {
+            println!("{}", tr!("init-cache-offline"));
+        } else {
+            println!("{}", tr!("init-cache-create"));
+            let mut cache = ServerCache::default();
+            cache.update_cache().await?;
+        }
+        println!("{}", tr!("init-complete"));
     } else {
-        let cli = Cli::parse();
-        match cli.command {
-            Commands::Update { server_name } => {
-                SteamCMD::update(server_name)?;
+        // The command is optional, so a bare first run only sets up steamserv
+        let Some(command) = cli.command else {
+            Cli::command()
+                .error(ErrorKind::MissingSubcommand, tr!("command-required"))
+                .exit();
+        };
+        match command {
+            Commands::Update {
+                server_name,
+                force,
+                yes,
+                staged,
+                all,
+            } => {
+                if all {
+                    SteamCMD::update_all(force, yes, staged).await?;
+                } else {
+                    SteamCMD::update(server_name, force, yes, staged).await?;
+                }
             }
             Commands::Install {
+                app,
                 app_id,
+                from_file,
                 server_name,
                 username,
+                answers,
+                save_answers,
             } => {
-                SteamCMD::install(app_id, server_name, username)?;
+                handle_install_command(
+                    app,
+                    app_id,
+                    from_file,
+                    server_name,
+                    username,
+                    answers,
+                    save_answers,
+                )
+                .await?;
+            }
+            Commands::New {
+                save_template,
+                from_template,
+                count,
+                name_prefix,
+                answers,
+                save_answers,
+            } => {
+                handle_new_command(
+                    save_template,
+                    from_template,
+                    count,
+                    name_prefix,
+                    answers,
+                    save_answers,
+                )
+                .await?;
             }
             Commands::Uninstall { server_name } => {
                 SteamCMD::uninstall(server_name)?;
             }
-            Commands::List { installed, filter } => {
-                handle_list_command(installed, filter).await?;
+            Commands::List {
+                installed,
+                filter,
+                owner,
+                not_updated_for,
+                refresh,
+                output,
+                watch,
+            } => {
+                handle_list_command(
+                    installed,
+                    filter,
+                    owner,
+                    not_updated_for,
+                    refresh,
+                    output,
+                    watch,
+                )
+                .await?;
+            }
+            Commands::Instance { command } => {
+                handle_instance_command(command)?;
+            }
+            Commands::Token { command } => {
+                handle_token_command(command).await?;
+            }
+            Commands::Mods { command } => {
+                handle_mods_command(command).await?;
+            }
+            Commands::LaunchCommand { target } => {
+                handle_launch_command(target)?;
+            }
+            Commands::FixPerms { server } => {
+                handle_fix_perms_command(&server)?;
+            }
+            Commands::Start { target, check } => {
+                handle_start_command(target, check)?;
+            }
+            Commands::Stop { target } => {
+                handle_stop_command(target)?;
+            }
+            Commands::Restart { target } => {
+                handle_restart_command(target)?;
+            }
+            Commands::Status { owner, watch } => {
+                handle_status_command(owner, watch)?;
+            }
+            Commands::Maintenance { command } => {
+                handle_maintenance_command(command)?;
+            }
+            Commands::Rcon { command } => {
+                handle_rcon_command(command)?;
+            }
+            Commands::Daemon { command } => {
+                handle_daemon_command(command).await?;
+            }
+            Commands::History { target, limit } => {
+                handle_history_command(target, limit)?;
+            }
+            Commands::Stats { target, since } => {
+                handle_stats_command(target, since)?;
+            }
+            Commands::Secrets { command } => {
+                handle_secrets_command(command)?;
+            }
+            Commands::Config { command } => {
+                handle_config_command(command)?;
+            }
+            Commands::Doctor => {
+                handle_doctor_command().await?;
+            }
+            Commands::Aliases => {
+                handle_aliases_command()?;
+            }
+            Commands::Clean { dry_run } => {
+                handle_clean_command(dry_run)?;
+            }
+            Commands::Top { interval, once } => {
+                handle_top_command(interval, once).await?;
+            }
+            Commands::Players {
+                target,
+                output,
+                command,
+            } => {
+                handle_players_command(target, output, command).await?;
+            }
+            Commands::Steamid { id } => {
+                handle_steamid_command(id).await?;
+            }
+            Commands::Admins { command } => {
+                handle_admins_command(command).await?;
             }
-            Commands::Config => {
-                println!("Configuring...");
+            Commands::CheckUpdates {
+                server_name,
+                output,
+            } => {
+                handle_check_updates_command(server_name, output)?;
+            }
+            Commands::Jobs { command } => {
+                handle_jobs_command(command).await?;
+            }
+            Commands::Snapshots { command } => {
+                handle_snapshots_command(command)?;
+            }
+            Commands::Rollback {
+                server,
+                snapshot,
+                yes,
+            } => {
+                handle_rollback_command(server, snapshot, yes)?;
+            }
+            Commands::Import {
+                from_lgsm,
+                from_server,
+                name,
+            } => {
+                handle_import_command(from_lgsm, from_server, name)?;
+            }
+            Commands::Migrate {
+                server_name,
+                to,
+                path,
+                remove_local,
+                yes,
+            } => {
+                handle_migrate_command(server_name, to, path, remove_local, yes)?;
+            }
+            Commands::Export {
+                target,
+                format,
+                output,
+            } => {
+                handle_export_command(target, format, output)?;
+            }
+            Commands::Lint { target } => {
+                handle_lint_command(target)?;
+            }
+            Commands::NatCheck { target, upnp } => {
+                handle_nat_check_command(target, upnp).await?;
+            }
+            Commands::LanCache { apply } => {
+                handle_lan_cache_command(apply).await?;
+            }
+            Commands::Owner { command } => {
+                handle_owner_command(command)?;
+            }
+            Commands::Timezone { server, timezone } => {
+                handle_timezone_command(server, timezone)?;
+            }
+            Commands::Env { command } => {
+                handle_env_command(command)?;
+            }
+            Commands::Args { command } => {
+                handle_args_command(command)?;
+            }
+            Commands::Report { command } => {
+                handle_report_command(command)?;
+            }
+            Commands::Profile { command } => {
+                handle_profile_command(command).await?;
+            }
+            Commands::ConfigGet {
+                target,
+                key,
+                output,
+            } => {
+                handle_config_get_command(target, key, output)?;
+            }
+            Commands::ConfigSet { target, key, value } => {
+                handle_config_set_command(target, key, value)?;
+            }
+            Commands::Maps { command } => {
+                handle_maps_command(command)?;
+            }
+            Commands::SyncFiles {
+                group,
+                paths,
+                from,
+                dry_run,
+                yes,
+            } => {
+                handle_sync_files_command(group, paths, from, dry_run, yes)?;
+            }
+            Commands::Cluster { command } => {
+                handle_cluster_command(command)?;
+            }
+            Commands::Info { server } => {
+                handle_info_command(server)?;
+            }
+            Commands::Logs { command } => {
+                handle_logs_command(command)?;
+            }
+            Commands::Bundle { target } => {
+                handle_bundle_command(target)?;
+            }
+            Commands::Bench {
+                skip_network,
+                output,
+            } => {
+                handle_bench_command(skip_network, output).await?;
+            }
+            Commands::Deps { command } => {
+                handle_deps_command(command)?;
             }
         }
     }
 
+    events::flush().await;
+
     Ok(())
 }
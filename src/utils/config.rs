@@ -1,12 +1,55 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono_tz::Tz;
 use inquire::{Confirm, Text};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
-use crate::utils::{Progress, ProgressStyle};
+use crate::{
+    core::{
+        bench::{self, Verdict},
+        webapi::App,
+        CacheValidators, Cluster, DnsConfig, LogConfig, PlayersConfig, ProfileRepository,
+        SnapshotConfig, SteamWebApi,
+    },
+    tr,
+    utils::{
+        download, fs, host, privilege, runtime,
+        secrets::{self, SecretsConfig},
+        steamcmd,
+        store::{SqliteStore, StateStore, StorageBackend, TomlStore},
+        CommandsConfig, ConfirmConfig, DiskConfig, NetworkConfig, NotificationConfig,
+        PermissionsConfig, Progress, ProgressStyle, RemoteHost, ServerRef, TimeoutConfig, Watchdog,
+    },
+};
 
 use super::{run_with_output, run_with_spinner};
 
+/// Default download URL of SteamCMD
+pub const DEFAULT_STEAMCMD_URL: &str =
+    "https://steamcdn-a.akamaihd.net/client/installer/steamcmd_linux.tar.gz";
+
+/// Age after which the server cache is considered outdated
+const CACHE_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Maximum number of localized names that are looked up in the store per cache update
+const MAX_NAME_LOOKUPS: usize = 100;
+
+/// Words for "server" in the languages the app list returns localized names in
+const LOCALIZED_SERVER_WORDS: [&str; 8] = [
+    "服务器",
+    "伺服器",
+    "サーバー",
+    "서버",
+    "сервер",
+    "servidor",
+    "serveur",
+    "serwer",
+];
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
     pub steamcmd_path: PathBuf,
@@ -14,6 +57,85 @@ pub struct Config {
     pub last_cache_update: Option<DateTime<Utc>>,
     pub installed_servers: Vec<InstalledServer>,
     pub is_initialized: bool,
+    pub api_key: Option<String>,
+    #[serde(default)]
+    pub offline: bool,
+    /// Only allow commands that read, e.g. for a shared config
+    #[serde(default)]
+    pub read_only: bool,
+    /// Users that may only run the commands that read, e.g. moderators
+    #[serde(default)]
+    pub viewers: Vec<String>,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    pub steamcmd_url: Option<String>,
+    pub steamcmd_sha256: Option<String>,
+    /// Run a downloaded SteamCMD without `steamcmd_sha256` without asking
+    #[serde(default)]
+    pub steamcmd_allow_unverified: bool,
+    /// Extra SteamCMD commands for every install and update, e.g.
+    /// `+@NoPromptForPassword 1`
+    #[serde(default)]
+    pub steamcmd_args: Vec<String>,
+    pub language: Option<String>,
+    #[serde(default)]
+    pub plain: bool,
+    pub compat_tool: Option<PathBuf>,
+    /// Emulator that runs SteamCMD and the game servers on hosts that are not
+    /// x86-64, e.g. `FEXInterpreter`, not needed if it is registered with binfmt_misc
+    pub emulator: Option<PathBuf>,
+    #[serde(default)]
+    pub notifications: NotificationConfig,
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    #[serde(default)]
+    pub storage: StorageBackend,
+    #[serde(default = "default_config_backups")]
+    pub config_backups: usize,
+    #[serde(default)]
+    pub secrets: SecretsConfig,
+    #[serde(default)]
+    pub timeouts: TimeoutConfig,
+    #[serde(default)]
+    pub permissions: PermissionsConfig,
+    #[serde(default)]
+    pub disk: DiskConfig,
+    #[serde(default)]
+    pub aliases: BTreeMap<String, u32>,
+    #[serde(default)]
+    pub players: PlayersConfig,
+    pub paused: Option<Pause>,
+    #[serde(default)]
+    pub snapshots: SnapshotConfig,
+    #[serde(default)]
+    pub dns: DnsConfig,
+    /// The community repository `steamserv profile sync` pulls game profiles from
+    #[serde(default)]
+    pub profile_repository: ProfileRepository,
+    /// Groups of servers that share files, e.g. the servers of a cluster
+    #[serde(default)]
+    pub groups: BTreeMap<String, Vec<String>>,
+    /// Clusters of servers that share characters and items, by name
+    #[serde(default)]
+    pub clusters: BTreeMap<String, Cluster>,
+    #[serde(default)]
+    pub logs: LogConfig,
+    #[serde(default)]
+    pub confirm: ConfirmConfig,
+    #[serde(default)]
+    pub commands: CommandsConfig,
+    /// Windows in which the supervisor neither updates nor restarts servers
+    #[serde(default)]
+    pub blackouts: Vec<Blackout>,
+    /// Linux hosts that are managed over SSH with `--remote`, by name
+    #[serde(default)]
+    pub remotes: BTreeMap<String, RemoteHost>,
+    /// The remote host commands run on without `--remote`, e.g. on macOS
+    pub default_remote: Option<String>,
+    /// The stored servers by name as the state store loaded them, so a save
+    /// only writes the servers that changed since
+    #[serde(skip)]
+    pub(crate) loaded_servers: Option<BTreeMap<String, String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -25,6 +147,233 @@ pub struct InstalledServer {
     pub last_updated: DateTime<Utc>,
     pub port: Option<u16>,
     pub login_type: LoginType,
+    #[serde(default)]
+    pub instances: Vec<ServerInstance>,
+    pub gslt: Option<String>,
+    #[serde(default)]
+    pub mods: Vec<WorkshopMod>,
+    pub rcon: Option<RconConfig>,
+    pub health: Option<HealthConfig>,
+    pub maintenance: Option<Maintenance>,
+    pub paused: Option<Pause>,
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub announcements: Vec<Announcement>,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub update_after: Vec<String>,
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub extra_args: Vec<String>,
+    pub maps: Option<MapRotation>,
+    #[serde(default)]
+    pub alerts: Vec<LogAlert>,
+    /// Extra SteamCMD commands for the updates of this server
+    #[serde(default)]
+    pub steamcmd_args: Vec<String>,
+    /// Time zone of the schedules of the server and its instances, e.g.
+    /// `America/New_York`, the one of the system if not set
+    pub timezone: Option<String>,
+    /// Blackout windows of the server, replacing the ones of the config,
+    /// empty to ignore them
+    pub blackouts: Option<Vec<Blackout>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ServerInstance {
+    pub name: String,
+    pub port: Option<u16>,
+    pub config_file: Option<PathBuf>,
+    pub world_name: Option<String>,
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+    pub gslt: Option<String>,
+    pub rcon: Option<RconConfig>,
+    pub health: Option<HealthConfig>,
+    #[serde(default)]
+    pub announcements: Vec<Announcement>,
+    #[serde(default)]
+    pub extra_args: Vec<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    pub maps: Option<MapRotation>,
+}
+
+/// Maintenance mode of a server, set with `steamserv maintenance on`
+///
+/// The supervisor leaves servers in maintenance alone, and they can only be
+/// started as a placeholder until maintenance is turned off.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Maintenance {
+    pub since: DateTime<Utc>,
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub placeholder: bool,
+    #[serde(default)]
+    pub stopped: Vec<String>,
+}
+
+/// Window in which the supervisor neither updates nor restarts servers, e.g. a
+/// tournament weekend
+///
+/// The times are on the clock of the time zone of the server.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Blackout {
+    pub name: String,
+    /// Start of the window, e.g. `2026-05-02T00:00:00`
+    pub start: NaiveDateTime,
+    /// End of the window, not included
+    pub end: NaiveDateTime,
+    /// The servers or groups of servers, all servers if empty
+    #[serde(default)]
+    pub servers: Vec<String>,
+}
+
+/// Pause of the automated actions, set with `steamserv daemon pause`
+///
+/// While paused, the supervisor neither checks nor recovers the servers and sends
+/// no announcements, so manual interventions are not undone.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Pause {
+    pub since: DateTime<Utc>,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RconConfig {
+    pub host: Option<String>,
+    pub port: u16,
+    pub password: String,
+}
+
+/// Health checks evaluated by the supervisor (`steamserv daemon run`)
+///
+/// After `failures` consecutive failed checks the recovery actions are run.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HealthConfig {
+    #[serde(default)]
+    pub probes: Vec<HealthProbe>,
+    #[serde(default = "default_health_interval")]
+    pub interval: u64,
+    #[serde(default = "default_health_failures")]
+    pub failures: u32,
+    #[serde(default = "default_recovery_actions")]
+    pub actions: Vec<RecoveryAction>,
+    pub memory: Option<MemoryLimit>,
+}
+
+/// Restart of a server whose resident memory stays above a limit, for games that leak memory
+///
+/// Once the memory has been above `limit` for `minutes`, an empty server is
+/// restarted right away. While players are online the restart waits up to
+/// `max_defer` minutes for the server to empty, then the players are warned over
+/// RCON the given minutes before the restart. `{minutes}` in the message is
+/// replaced with the minutes left.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MemoryLimit {
+    pub limit: String,
+    #[serde(default = "default_memory_minutes")]
+    pub minutes: u64,
+    #[serde(default = "default_memory_max_defer")]
+    pub max_defer: u64,
+    #[serde(default = "default_memory_warnings")]
+    pub warnings: Vec<u64>,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum HealthProbe {
+    A2s {
+        host: Option<String>,
+        port: Option<u16>,
+    },
+    Tcp {
+        host: Option<String>,
+        port: Option<u16>,
+    },
+    Http {
+        url: String,
+        status: Option<u16>,
+    },
+    Log {
+        pattern: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RecoveryAction {
+    Restart,
+    Hook(String),
+    Notify,
+}
+
+/// Message broadcast over RCON by the supervisor on a cron-like schedule
+///
+/// The `{message}` placeholder of the command is replaced with the message. Without
+/// a command the `broadcast_command` of the game profile is used.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Announcement {
+    pub schedule: String,
+    pub message: String,
+    pub command: Option<String>,
+}
+
+/// Map rotation of a server, changed over RCON by the supervisor on a cron-like schedule
+///
+/// The `{map}` placeholder of the command and the message is replaced with the
+/// next map. Without a command the `map_command` of the game profile is used.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MapRotation {
+    #[serde(default)]
+    pub maps: Vec<String>,
+    /// When to change to the next map, `None` only changes with `steamserv maps rotate`
+    pub schedule: Option<String>,
+    pub command: Option<String>,
+    /// Message broadcast to the players before the map changes
+    pub message: Option<String>,
+    /// Index of the map that was changed to last
+    #[serde(default)]
+    pub current: usize,
+}
+
+/// Alert on a line of the captured server log, evaluated by the supervisor
+///
+/// The alerts of a server apply to its instances as well. After an alert
+/// fired, further matches are ignored for `cooldown` seconds.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LogAlert {
+    /// Regular expression, matched anywhere in a line
+    pub pattern: String,
+    /// Name shown in the notification, defaults to the pattern
+    pub name: Option<String>,
+    #[serde(default = "default_alert_actions")]
+    pub actions: Vec<RecoveryAction>,
+    #[serde(default = "default_alert_cooldown")]
+    pub cooldown: u64,
+}
+
+/// Collection of player counts and frame rates by the supervisor
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct MetricsConfig {
+    pub enabled: bool,
+    pub interval: u64,
+    pub retention_days: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorkshopMod {
+    pub id: u64,
+    pub title: Option<String>,
+    pub installed_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub links: Vec<PathBuf>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -37,16 +386,32 @@ pub enum LoginType {
 pub struct ServerCache {
     pub servers: Vec<ServerInfo>,
     pub last_update: DateTime<Utc>,
+    /// Kept in its own file, so a check that finds no changes does not rewrite the cache
+    #[serde(skip)]
+    check: CacheCheck,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// The last check of the app list for changes
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct CacheCheck {
+    checked: Option<DateTime<Utc>>,
+    validators: CacheValidators,
+    /// Unix timestamp of the last store app list update, the next one only fetches newer changes
+    #[serde(default)]
+    modified_since: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct ServerInfo {
     pub app_id: u32,
     pub name: String,
     pub plattform: Vec<Platform>,
+    /// Localized names from the app list, the name is the English store name then
+    #[serde(default)]
+    pub aliases: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum Platform {
     Windows,
     Linux,
@@ -64,45 +429,121 @@ impl Config {
     ///
     /// If the config directory could not be found or if the path could not be created
     fn get_config_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
-        let config_path = dirs::config_dir().ok_or("Could not find config directory")?;
-        Ok(config_path.join("karnes-development/steamserv/config.toml"))
+        Ok(Self::data_dir()?.join("config.toml"))
     }
 
-    /// Load the config from the config file
+    /// Get the directory for the config and the runtime files of steamserv
     ///
     /// # Returns
     ///
-    /// The loaded config
+    /// The path to the steamserv directory
     ///
     /// # Errors
     ///
-    /// If the config file could not be found or if the file could not be read
-    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
-        let path = Self::get_config_path()?;
+    /// If the config directory could not be found
+    pub fn data_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let config_path = dirs::config_dir().ok_or_else(|| tr!("config-dir-not-found"))?;
+        Ok(config_path.join("karnes-development/steamserv"))
+    }
 
-        if !path.exists() {
-            return Ok(Config::default());
+    /// Open the store that holds the config and the installed servers
+    ///
+    /// The storage backend is read from `config.toml`, which always holds the settings.
+    ///
+    /// # Returns
+    ///
+    /// The state store
+    ///
+    /// # Errors
+    ///
+    /// If the config file could not be read
+    pub fn store() -> Result<Box<dyn StateStore>, Box<dyn std::error::Error>> {
+        let toml = Self::settings_store()?;
+        match toml.load()?.storage {
+            StorageBackend::Toml => Ok(Box::new(toml)),
+            StorageBackend::Sqlite => Ok(Box::new(SqliteStore::new(
+                Self::get_config_path()?,
+                Self::data_dir()?.join("state.sqlite"),
+            ))),
         }
+    }
 
-        let content = std::fs::read_to_string(path)?;
-        Ok(toml::from_str(&content)?)
+    /// Open the store of `config.toml`, which also manages the config backups
+    ///
+    /// # Returns
+    ///
+    /// The store of the config file
+    ///
+    /// # Errors
+    ///
+    /// If the config directory could not be found
+    pub fn settings_store() -> Result<TomlStore, Box<dyn std::error::Error>> {
+        Ok(TomlStore::new(Self::get_config_path()?))
     }
 
-    /// Save the config to the config file
+    /// Load the config from the state store and decrypt its secrets
+    ///
+    /// # Returns
+    ///
+    /// The loaded config
     ///
     /// # Errors
     ///
-    /// If the config file could not be written
+    /// If the config file could not be found, if the file could not be read or
+    /// if the secrets could not be decrypted
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let mut config = Self::store()?.load()?;
+        secrets::decrypt_config(&mut config)?;
+        Ok(config)
+    }
+
+    /// Save the config to the state store, encrypting its secrets if enabled
+    ///
+    /// # Errors
+    ///
+    /// If the config file could not be written or the secrets could not be encrypted
     ///
     /// # Returns
     ///
     /// Ok if the config was saved successfully
     pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let path = Self::get_config_path()?;
-        std::fs::create_dir_all(path.parent().unwrap())?;
-        let content = toml::to_string(self)?;
-        std::fs::write(path, content)?;
-        Ok(())
+        if !self.secrets.enabled {
+            return Self::store()?.save(self);
+        }
+
+        let mut config = self.clone();
+        secrets::encrypt_config(&mut config)?;
+        Self::store()?.save(&config)
+    }
+
+    /// Get the sensitive values of the config
+    ///
+    /// These are the Steam Web API key, the webhook URL, the token of the DNS
+    /// provider, the named secrets and the login tokens and RCON passwords of all
+    /// servers and instances.
+    ///
+    /// # Returns
+    ///
+    /// Mutable references to the values
+    pub fn secret_fields_mut(&mut self) -> Vec<&mut String> {
+        let mut fields: Vec<&mut String> = Vec::new();
+        fields.extend(self.api_key.as_mut());
+        fields.extend(self.notifications.webhook_url.as_mut());
+        if let Some(provider) = &mut self.dns.provider {
+            fields.extend(provider.secrets_mut());
+        }
+        fields.extend(self.secrets.values.values_mut());
+
+        for server in &mut self.installed_servers {
+            fields.extend(server.gslt.as_mut());
+            fields.extend(server.rcon.as_mut().map(|r| &mut r.password));
+            for instance in &mut server.instances {
+                fields.extend(instance.gslt.as_mut());
+                fields.extend(instance.rcon.as_mut().map(|r| &mut r.password));
+            }
+        }
+
+        fields
     }
 
     /// Initialize the config
@@ -115,25 +556,62 @@ impl Config {
     ///
     /// Ok if the config was saved successfully
     pub async fn init(&self) -> Result<(), Box<dyn std::error::Error>> {
-        println!("You using steamserv the first time. The follow steps configure your enviroment.");
-        let select_steamcmd = Confirm::new("Do you have SteamCMD installed?")
+        println!("{}", tr!("init-welcome"));
+        let select_steamcmd = Confirm::new(&tr!("init-steamcmd-installed"))
             .with_default(false)
             .prompt()?;
         let steamcmd_path = if select_steamcmd {
-            Text::new("Please enter the path to the SteamCMD executable:")
-                .with_help_message("This is the path to your SteamCMD executable")
+            Text::new(&tr!("init-steamcmd-path"))
+                .with_help_message(&tr!("init-steamcmd-path-help"))
                 .prompt()?
+        } else if self.is_offline() {
+            return Err(tr!("init-steamcmd-offline").into());
         } else {
             let path = self.install_steamcmd().await?;
             if path.is_empty() {
-                return Err("SteamCMD is required to use steamserv".into());
+                return Err(tr!("init-steamcmd-required").into());
             }
             path
         };
-        let install_path = Text::new("Please enter the path to the server install directory:")
-            .with_help_message("This is the path to installing the servers.")
-            .with_placeholder("e.g. /home/user/servers")
-            .prompt()?;
+        let emulator = self.select_emulator()?;
+        let install_path = loop {
+            let install_path = Text::new(&tr!("init-install-path"))
+                .with_help_message(&tr!("init-install-path-help"))
+                .with_placeholder(&tr!("init-install-path-placeholder"))
+                .prompt()?;
+            if !Confirm::new(&tr!("init-bench"))
+                .with_help_message(&tr!("init-bench-help"))
+                .with_default(false)
+                .prompt()?
+            {
+                break install_path;
+            }
+
+            match bench::disk_speed(Path::new(&install_path)) {
+                Ok(speed) => {
+                    let verdict = Verdict::judge(speed, None);
+                    println!(
+                        "{}",
+                        tr!(
+                            "init-bench-result",
+                            speed = format!("{}/s", fs::format_size(speed as u64)),
+                            verdict = verdict.describe(speed, None)
+                        )
+                    );
+                    if verdict == Verdict::Ok
+                        || Confirm::new(&tr!("init-bench-keep"))
+                            .with_default(true)
+                            .prompt()?
+                    {
+                        break install_path;
+                    }
+                }
+                Err(e) => {
+                    println!("{}", e);
+                    break install_path;
+                }
+            }
+        };
 
         let config = Config {
             steamcmd_path: PathBuf::from(steamcmd_path),
@@ -141,6 +619,42 @@ impl Config {
             last_cache_update: None,
             installed_servers: Vec::new(),
             is_initialized: true,
+            api_key: self.api_key.clone(),
+            offline: self.offline,
+            read_only: self.read_only,
+            viewers: self.viewers.clone(),
+            network: self.network.clone(),
+            steamcmd_url: self.steamcmd_url.clone(),
+            steamcmd_sha256: self.steamcmd_sha256.clone(),
+            steamcmd_allow_unverified: self.steamcmd_allow_unverified,
+            steamcmd_args: self.steamcmd_args.clone(),
+            language: self.language.clone(),
+            plain: self.plain,
+            compat_tool: self.compat_tool.clone(),
+            emulator,
+            notifications: self.notifications.clone(),
+            metrics: self.metrics.clone(),
+            storage: self.storage,
+            config_backups: self.config_backups,
+            secrets: self.secrets.clone(),
+            timeouts: self.timeouts.clone(),
+            permissions: self.permissions.clone(),
+            disk: self.disk.clone(),
+            aliases: self.aliases.clone(),
+            players: self.players.clone(),
+            paused: self.paused.clone(),
+            snapshots: self.snapshots.clone(),
+            dns: self.dns.clone(),
+            profile_repository: self.profile_repository.clone(),
+            groups: self.groups.clone(),
+            clusters: self.clusters.clone(),
+            logs: self.logs.clone(),
+            confirm: self.confirm.clone(),
+            commands: self.commands.clone(),
+            blackouts: self.blackouts.clone(),
+            remotes: self.remotes.clone(),
+            default_remote: self.default_remote.clone(),
+            loaded_servers: self.loaded_servers.clone(),
         };
 
         config.save()?;
@@ -148,6 +662,213 @@ impl Config {
         Ok(())
     }
 
+    /// Find an installed server by name
+    ///
+    /// # Arguments
+    ///
+    /// - `name` - The name of the server
+    ///
+    /// # Returns
+    ///
+    /// The installed server if it exists
+    pub fn find_server(&self, name: &str) -> Option<&InstalledServer> {
+        self.installed_servers.iter().find(|s| s.name == name)
+    }
+
+    /// Find an installed server by name for modification
+    ///
+    /// # Arguments
+    ///
+    /// - `name` - The name of the server
+    ///
+    /// # Returns
+    ///
+    /// The installed server if it exists
+    pub fn find_server_mut(&mut self, name: &str) -> Option<&mut InstalledServer> {
+        self.installed_servers.iter_mut().find(|s| s.name == name)
+    }
+
+    /// Resolve a server reference to the installed server and instance
+    ///
+    /// # Arguments
+    ///
+    /// - `target` - The reference to the server or instance
+    ///
+    /// # Returns
+    ///
+    /// The installed server and the instance, if the reference names one
+    ///
+    /// # Errors
+    ///
+    /// If the server or the instance does not exist
+    pub fn resolve(
+        &self,
+        target: &ServerRef,
+    ) -> Result<(&InstalledServer, Option<&ServerInstance>), Box<dyn std::error::Error>> {
+        let server = self
+            .find_server(&target.server)
+            .ok_or_else(|| tr!("server-not-installed", server = target.server))?;
+
+        match &target.instance {
+            Some(name) => {
+                let instance = server.find_instance(name).ok_or_else(|| {
+                    tr!(
+                        "instance-not-found",
+                        instance = name,
+                        server = target.server
+                    )
+                })?;
+                Ok((server, Some(instance)))
+            }
+            None => Ok((server, None)),
+        }
+    }
+
+    /// Add an instance to an installed server
+    ///
+    /// # Arguments
+    ///
+    /// - `server` - The name of the server
+    /// - `instance` - The instance to add
+    ///
+    /// # Returns
+    ///
+    /// Ok if the instance was added
+    ///
+    /// # Errors
+    ///
+    /// If the server does not exist, the instance already exists or the port is
+    /// already used by another instance
+    pub fn add_instance(
+        &mut self,
+        server: &str,
+        instance: ServerInstance,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(port) = instance.port {
+            let used_by = self.installed_servers.iter().find_map(|s| {
+                s.instances
+                    .iter()
+                    .find(|i| i.port == Some(port))
+                    .map(|i| format!("{}/{}", s.name, i.name))
+            });
+            if let Some(used_by) = used_by {
+                return Err(tr!("instance-port-used", port = port, instance = used_by).into());
+            }
+        }
+
+        let installed = self
+            .find_server_mut(server)
+            .ok_or_else(|| tr!("server-not-installed", server = server))?;
+        if installed.find_instance(&instance.name).is_some() {
+            return Err(tr!("instance-exists", instance = instance.name).into());
+        }
+        installed.instances.push(instance);
+        Ok(())
+    }
+
+    /// Remove an instance from an installed server
+    ///
+    /// # Arguments
+    ///
+    /// - `server` - The name of the server
+    /// - `instance` - The name of the instance
+    ///
+    /// # Returns
+    ///
+    /// Ok if the instance was removed
+    ///
+    /// # Errors
+    ///
+    /// If the server or the instance does not exist
+    pub fn remove_instance(
+        &mut self,
+        server: &str,
+        instance: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let installed = self
+            .find_server_mut(server)
+            .ok_or_else(|| tr!("server-not-installed", server = server))?;
+        if installed.find_instance(instance).is_none() {
+            return Err(tr!("instance-not-found", instance = instance, server = server).into());
+        }
+        installed.instances.retain(|i| i.name != instance);
+        Ok(())
+    }
+
+    /// Check if network access is disabled
+    ///
+    /// # Returns
+    ///
+    /// True if the offline mode is enabled in the config or on the command line
+    pub fn is_offline(&self) -> bool {
+        self.offline || runtime::is_offline()
+    }
+
+    /// Check if only the commands that read may run
+    ///
+    /// # Returns
+    ///
+    /// True if the read-only mode is enabled or the user is a viewer
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+            || privilege::user_names()
+                .iter()
+                .any(|name| self.viewers.contains(name))
+    }
+
+    /// Ensure that network access is allowed
+    ///
+    /// # Arguments
+    ///
+    /// - `action` - The action that needs network access
+    ///
+    /// # Returns
+    ///
+    /// Ok if the offline mode is disabled
+    ///
+    /// # Errors
+    ///
+    /// If the offline mode is enabled
+    pub fn ensure_online(&self, action: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if self.is_offline() {
+            return Err(tr!("offline-error", action = action).into());
+        }
+        Ok(())
+    }
+
+    /// Offer an emulator for SteamCMD and the game servers on hosts that are not x86-64
+    ///
+    /// # Returns
+    ///
+    /// The emulator to wrap the binaries with, or `None` on x86-64 hosts, if the
+    /// emulator is registered with binfmt_misc or none was chosen
+    ///
+    /// # Errors
+    ///
+    /// If the prompt failed
+    fn select_emulator(&self) -> Result<Option<PathBuf>, Box<dyn std::error::Error>> {
+        if host::is_native() {
+            return Ok(self.emulator.clone());
+        }
+        println!("{}", tr!("init-host-arch", arch = host::arch()));
+        if let Some(name) = host::binfmt_emulator() {
+            println!("{}", tr!("host-binfmt", name = name));
+            return Ok(self.emulator.clone());
+        }
+        let Some(emulator) = host::find_emulator() else {
+            println!("{}", tr!("host-emulator-missing"));
+            return Ok(self.emulator.clone());
+        };
+
+        let use_emulator = Confirm::new(&tr!(
+            "init-emulator",
+            emulator = emulator.display().to_string()
+        ))
+        .with_default(true)
+        .prompt()?;
+        Ok(use_emulator.then_some(emulator).or(self.emulator.clone()))
+    }
+
     /// Install SteamCMD
     ///
     /// # Returns
@@ -158,36 +879,52 @@ impl Config {
     ///
     /// If the SteamCMD could not be installed
     async fn install_steamcmd(&self) -> Result<String, Box<dyn std::error::Error>> {
-        let install_path = Text::new("Please enter the path to the SteamCMD install directory:")
-            .with_help_message("This is the path you want to install SteamCMD.")
-            .with_placeholder("e.g. /home/user/steamcmd")
+        self.ensure_online(&tr!("action-download-steamcmd"))?;
+
+        let install_path = Text::new(&tr!("steamcmd-install-path"))
+            .with_help_message(&tr!("steamcmd-install-path-help"))
+            .with_placeholder(&tr!("steamcmd-install-path-placeholder"))
             .prompt()?;
-        let confirm = Confirm::new("Do you want to install SteamCMD now?")
+        let confirm = Confirm::new(&tr!("steamcmd-install-confirm"))
             .with_default(true)
             .prompt()?;
 
         if confirm {
-            let steamcmd_url =
-                "https://steamcdn-a.akamaihd.net/client/installer/steamcmd_linux.tar.gz";
+            let steamcmd_url = self.steamcmd_url.as_deref().unwrap_or(DEFAULT_STEAMCMD_URL);
             let steamcmd_path = PathBuf::from(install_path);
             let steamcmd_file = steamcmd_path.join("steamcmd_linux.tar.gz");
 
+            privilege::ensure_dirs(&[&steamcmd_path])?;
             std::fs::create_dir_all(&steamcmd_path)?;
 
-            let mut progress = Progress::new(100, "Downloading SteamCMD", ProgressStyle::Bar)?;
-            let mut response = reqwest::get(steamcmd_url).await?;
-            let total_size = response.content_length().unwrap_or(0) as usize;
-            let mut downloaded = 0;
+            let mut progress =
+                Progress::new(100, &tr!("steamcmd-download-progress"), ProgressStyle::Bar)?;
+            let client = self
+                .network
+                .https_client(TimeoutConfig::limit(self.timeouts.network))?;
+            let content = download::download_file(&client, steamcmd_url, &mut progress).await?;
+            progress.finish()?;
 
-            let mut content = Vec::new();
-            while let Some(chunk) = response.chunk().await? {
-                downloaded += chunk.len();
-                content.extend_from_slice(&chunk);
-                progress.update((downloaded * 100) / total_size)?;
+            match &self.steamcmd_sha256 {
+                Some(expected) => download::verify_sha256(&content, expected)?,
+                None => {
+                    // An unverified binary only runs if the user opts out
+                    let checksum = download::sha256_hex(&content);
+                    if !self.steamcmd_allow_unverified
+                        && !Confirm::new(&tr!(
+                            "steamcmd-checksum-missing",
+                            checksum = checksum.as_str()
+                        ))
+                        .with_default(false)
+                        .with_help_message(&tr!("steamcmd-checksum-missing-help"))
+                        .prompt()?
+                    {
+                        return Err(tr!("steamcmd-unverified", checksum = checksum).into());
+                    }
+                }
             }
 
             std::fs::write(&steamcmd_file, &content)?;
-            progress.finish()?;
 
             let mut extract_child = std::process::Command::new("tar")
                 .arg("-xzf")
@@ -198,26 +935,34 @@ impl Config {
                 .stderr(std::process::Stdio::piped())
                 .spawn()?;
 
-            run_with_spinner(&mut extract_child, "Extracting SteamCMD")?;
+            let bootstrap = TimeoutConfig::limit(self.timeouts.bootstrap);
+            let watchdog = Watchdog::start(&extract_child, bootstrap);
+            run_with_spinner(&mut extract_child, &tr!("steamcmd-extract-progress"))?;
 
             let status = extract_child.wait()?;
+            watchdog.finish("tar")?;
             if !status.success() {
-                return Err("Could not extract SteamCMD".into());
+                return Err(tr!("steamcmd-extract-failed").into());
             }
 
             std::fs::remove_file(&steamcmd_file)?;
 
-            let mut init_child = std::process::Command::new(steamcmd_path.join("steamcmd.sh"))
+            let mut init_child = std::process::Command::new(steamcmd_path.join("steamcmd.sh"));
+            steamcmd::normalize_env(&mut init_child);
+            let mut init_child = init_child
+                .envs(self.network.proxy_env())
                 .arg("+quit")
                 .stdout(std::process::Stdio::piped())
                 .stderr(std::process::Stdio::piped())
                 .spawn()?;
 
+            let watchdog = Watchdog::start(&init_child, bootstrap);
             run_with_output(&mut init_child)?;
 
             let init_status = init_child.wait()?;
+            watchdog.finish("steamcmd")?;
             if !init_status.success() {
-                return Err("Could not initialize SteamCMD".into());
+                return Err(tr!("steamcmd-init-failed").into());
             }
 
             Ok(steamcmd_path
@@ -225,11 +970,54 @@ impl Config {
                 .to_string_lossy()
                 .to_string())
         } else {
-            Err("SteamCMD is required to use steamserv".into())
+            Err(tr!("init-steamcmd-required").into())
         }
     }
 }
 
+/// Default number of config backups to keep
+fn default_config_backups() -> usize {
+    10
+}
+
+/// Default interval of the health checks in seconds
+fn default_health_interval() -> u64 {
+    30
+}
+
+/// Default number of consecutive failures before the recovery actions run
+fn default_health_failures() -> u32 {
+    3
+}
+
+/// Default minutes the memory has to stay above the limit before a restart
+fn default_memory_minutes() -> u64 {
+    10
+}
+
+/// Default minutes a memory restart waits for the players to leave
+fn default_memory_max_defer() -> u64 {
+    60
+}
+
+/// Default minutes before a memory restart to warn the players
+fn default_memory_warnings() -> Vec<u64> {
+    vec![10, 5, 1]
+}
+
+/// Default recovery actions after failed health checks
+fn default_recovery_actions() -> Vec<RecoveryAction> {
+    vec![RecoveryAction::Restart, RecoveryAction::Notify]
+}
+
+fn default_alert_actions() -> Vec<RecoveryAction> {
+    vec![RecoveryAction::Notify]
+}
+
+fn default_alert_cooldown() -> u64 {
+    5 * 60
+}
+
 impl Default for Config {
     /// Create a default config
     fn default() -> Self {
@@ -239,11 +1027,102 @@ impl Default for Config {
             last_cache_update: None,
             installed_servers: Vec::new(),
             is_initialized: false,
+            api_key: None,
+            offline: false,
+            read_only: false,
+            viewers: Vec::new(),
+            network: NetworkConfig::default(),
+            steamcmd_url: None,
+            steamcmd_sha256: None,
+            steamcmd_allow_unverified: false,
+            steamcmd_args: Vec::new(),
+            language: None,
+            plain: false,
+            compat_tool: None,
+            emulator: None,
+            notifications: NotificationConfig::default(),
+            metrics: MetricsConfig::default(),
+            storage: StorageBackend::default(),
+            config_backups: default_config_backups(),
+            secrets: SecretsConfig::default(),
+            timeouts: TimeoutConfig::default(),
+            permissions: PermissionsConfig::default(),
+            disk: DiskConfig::default(),
+            aliases: BTreeMap::new(),
+            players: PlayersConfig::default(),
+            paused: None,
+            snapshots: SnapshotConfig::default(),
+            dns: DnsConfig::default(),
+            profile_repository: ProfileRepository::default(),
+            groups: BTreeMap::new(),
+            clusters: BTreeMap::new(),
+            logs: LogConfig::default(),
+            confirm: ConfirmConfig::default(),
+            commands: CommandsConfig::default(),
+            blackouts: Vec::new(),
+            remotes: BTreeMap::new(),
+            default_remote: None,
+            loaded_servers: None,
+        }
+    }
+}
+
+impl Default for MetricsConfig {
+    /// Sample every minute and keep the samples for 30 days
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: 60,
+            retention_days: 30,
         }
     }
 }
 
 impl InstalledServer {
+    /// Create a server that was just installed, with the defaults for all settings
+    ///
+    /// # Arguments
+    ///
+    /// - `app_id` - The App ID
+    /// - `name` - The name of the server
+    /// - `install_path` - The install directory
+    /// - `login_type` - The login SteamCMD updates the server with
+    ///
+    /// # Returns
+    ///
+    /// The installed server, installed and updated now
+    pub fn new(app_id: u32, name: &str, install_path: PathBuf, login_type: LoginType) -> Self {
+        let now = Utc::now();
+        Self {
+            app_id,
+            name: name.to_string(),
+            install_path,
+            install_date: now,
+            last_updated: now,
+            port: None,
+            login_type,
+            instances: Vec::new(),
+            gslt: None,
+            mods: Vec::new(),
+            rcon: None,
+            health: None,
+            maintenance: None,
+            paused: None,
+            branch: None,
+            announcements: Vec::new(),
+            priority: 0,
+            update_after: Vec::new(),
+            owner: None,
+            env: BTreeMap::new(),
+            extra_args: Vec::new(),
+            maps: None,
+            alerts: Vec::new(),
+            steamcmd_args: Vec::new(),
+            timezone: None,
+            blackouts: None,
+        }
+    }
+
     /// Update the last updated timestamp
     ///
     /// # Returns
@@ -252,6 +1131,205 @@ impl InstalledServer {
     pub fn update_timestamp(&mut self) {
         self.last_updated = chrono::Local::now().to_utc();
     }
+
+    /// Find an instance of the server by name
+    ///
+    /// # Arguments
+    ///
+    /// - `name` - The name of the instance
+    ///
+    /// # Returns
+    ///
+    /// The instance if it exists
+    pub fn find_instance(&self, name: &str) -> Option<&ServerInstance> {
+        self.instances.iter().find(|i| i.name == name)
+    }
+
+    /// Get the RCON settings of the server or one of its instances
+    ///
+    /// # Arguments
+    ///
+    /// - `instance` - The instance, or `None` for the server itself
+    ///
+    /// # Returns
+    ///
+    /// The RCON settings of the instance, falling back to the settings of the server
+    pub fn rcon_for<'a>(&'a self, instance: Option<&'a ServerInstance>) -> Option<&'a RconConfig> {
+        instance
+            .and_then(|i| i.rcon.as_ref())
+            .or(self.rcon.as_ref())
+    }
+
+    /// Check if the server belongs to an owner
+    ///
+    /// # Arguments
+    ///
+    /// - `owner` - The owner to check, `None` matches every server
+    ///
+    /// # Returns
+    ///
+    /// True if no owner is given or the server has this owner
+    pub fn is_owned_by(&self, owner: Option<&str>) -> bool {
+        owner.is_none_or(|owner| self.owner.as_deref() == Some(owner))
+    }
+
+    /// Get the time zone the schedules of the server run in
+    ///
+    /// # Returns
+    ///
+    /// The time zone, or `None` for the time zone of the system
+    ///
+    /// # Errors
+    ///
+    /// If the time zone is no IANA time zone name
+    pub fn time_zone(&self) -> Result<Option<Tz>, Box<dyn std::error::Error>> {
+        let Some(name) = &self.timezone else {
+            return Ok(None);
+        };
+        match name.parse() {
+            Ok(zone) => Ok(Some(zone)),
+            Err(_) => Err(tr!(
+                "timezone-invalid",
+                server = self.name.as_str(),
+                timezone = name.as_str()
+            )
+            .into()),
+        }
+    }
+
+    /// Get the blackout window the server is in
+    ///
+    /// # Arguments
+    ///
+    /// - `config` - The configuration with the blackouts and the groups
+    /// - `now` - The point in time
+    ///
+    /// # Returns
+    ///
+    /// The blackout, or `None` if the supervisor may update and restart the server
+    pub fn blackout<'a>(&'a self, config: &'a Config, now: DateTime<Utc>) -> Option<&'a Blackout> {
+        let local = match self.time_zone() {
+            Ok(Some(zone)) => now.with_timezone(&zone).naive_local(),
+            _ => now.with_timezone(&chrono::Local).naive_local(),
+        };
+        let in_group = |name: &String| {
+            config
+                .groups
+                .get(name)
+                .is_some_and(|members| members.contains(&self.name))
+        };
+
+        self.blackouts
+            .as_ref()
+            .unwrap_or(&config.blackouts)
+            .iter()
+            .filter(|b| {
+                b.servers.is_empty()
+                    || b.servers
+                        .iter()
+                        .any(|name| *name == self.name || in_group(name))
+            })
+            .find(|b| b.start <= local && local < b.end)
+    }
+
+    /// Check if the supervisor may act on the server
+    ///
+    /// # Arguments
+    ///
+    /// - `config` - The configuration with the global pause
+    ///
+    /// # Returns
+    ///
+    /// False if the server is in maintenance or the automation is paused globally or for the server
+    pub fn is_automated(&self, config: &Config) -> bool {
+        self.maintenance.is_none() && self.paused.is_none() && config.paused.is_none()
+    }
+
+    /// Get the announcements of the server or one of its instances
+    ///
+    /// # Arguments
+    ///
+    /// - `instance` - The instance, or `None` for the server itself
+    ///
+    /// # Returns
+    ///
+    /// The announcements of the instance, falling back to the announcements of the server
+    pub fn announcements_for<'a>(
+        &'a self,
+        instance: Option<&'a ServerInstance>,
+    ) -> &'a [Announcement] {
+        match instance {
+            Some(instance) if !instance.announcements.is_empty() => &instance.announcements,
+            _ => &self.announcements,
+        }
+    }
+
+    /// Get the health checks of the server or one of its instances
+    ///
+    /// # Arguments
+    ///
+    /// - `instance` - The instance, or `None` for the server itself
+    ///
+    /// # Returns
+    ///
+    /// The health checks of the instance, falling back to the checks of the server
+    pub fn health_for<'a>(
+        &'a self,
+        instance: Option<&'a ServerInstance>,
+    ) -> Option<&'a HealthConfig> {
+        instance
+            .and_then(|i| i.health.as_ref())
+            .or(self.health.as_ref())
+    }
+}
+
+impl WorkshopMod {
+    /// Create a new workshop mod entry
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The workshop item ID
+    ///
+    /// # Returns
+    ///
+    /// The created workshop mod entry
+    pub fn new(id: u64) -> Self {
+        Self {
+            id,
+            title: None,
+            installed_at: Utc::now(),
+            updated_at: Utc::now(),
+            links: Vec::new(),
+        }
+    }
+}
+
+impl ServerInstance {
+    /// Create a new instance without any variables
+    ///
+    /// # Arguments
+    ///
+    /// - `name` - The name of the instance
+    ///
+    /// # Returns
+    ///
+    /// The created instance
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            port: None,
+            config_file: None,
+            world_name: None,
+            variables: BTreeMap::new(),
+            gslt: None,
+            rcon: None,
+            health: None,
+            announcements: Vec::new(),
+            extra_args: Vec::new(),
+            env: BTreeMap::new(),
+            maps: None,
+        }
+    }
 }
 
 impl ServerCache {
@@ -265,10 +1343,23 @@ impl ServerCache {
     ///
     /// If the cache directory could not be found or if the path could not be created
     fn get_cache_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
-        let cache_path = dirs::config_dir().ok_or("Could not find cache directory")?;
+        let cache_path = dirs::config_dir().ok_or_else(|| tr!("cache-dir-not-found"))?;
         Ok(cache_path.join("karnes-development/steamserv/cache/server_cache.json"))
     }
 
+    /// Get the path to the file with the last check of the app list
+    ///
+    /// # Returns
+    ///
+    /// The path next to the server cache file
+    ///
+    /// # Errors
+    ///
+    /// If the cache directory could not be found
+    fn get_check_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        Ok(Self::get_cache_path()?.with_file_name("server_cache_check.json"))
+    }
+
     /// Load the server cache from the cache file
     ///
     /// # Returns
@@ -286,7 +1377,13 @@ impl ServerCache {
         }
 
         let content = std::fs::read_to_string(path)?;
-        Ok(serde_json::from_str(&content)?)
+        let mut cache: Self = serde_json::from_str(&content)?;
+        // A missing or broken check only means the next update downloads the full list
+        cache.check = std::fs::read_to_string(Self::get_check_path()?)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Ok(cache)
     }
 
     /// Save the server cache to the cache file
@@ -303,63 +1400,203 @@ impl ServerCache {
         std::fs::create_dir_all(path.parent().unwrap())?;
         let content = serde_json::to_string(self)?;
         std::fs::write(path, content)?;
+        self.save_check()
+    }
+
+    /// Save the last check of the app list
+    ///
+    /// # Returns
+    ///
+    /// Ok if the check was saved
+    ///
+    /// # Errors
+    ///
+    /// If the file could not be written
+    fn save_check(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let content = serde_json::to_string(&self.check)?;
+        std::fs::write(Self::get_check_path()?, content)?;
         Ok(())
     }
 
     /// Update the server cache
     ///
+    /// The names are normalized. Apps with a localized name are looked up in the
+    /// store to get the English name, and the localized name is kept as an alias.
+    /// Names from the previous cache are reused when the app list returns an empty
+    /// or already known name, so every app is only looked up once.
+    ///
+    /// With an API key, the store app list is used and only the apps changed since
+    /// the last update are fetched and merged into the cache. Otherwise the full
+    /// app list is requested with the validators of the last download. The cache
+    /// file is only rewritten when the servers in it changed.
+    ///
     /// # Returns
     ///
-    /// Ok if the cache was updated successfully
+    /// The servers that were not in the previous cache, empty for the first update
     ///
     /// # Errors
     ///
     /// If the cache could not be updated
-    pub async fn update_cache(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        let mut progress = Progress::new(100, "Updating server cache", ProgressStyle::Bar)?;
-        let mut response =
-            reqwest::get("https://api.steampowered.com/ISteamApps/GetAppList/v2/").await?;
-        let total_size = response.content_length().unwrap_or(1) as usize;
-        let mut downloaded = 0;
-
-        let mut content = Vec::new();
-        while let Some(chunk) = response.chunk().await? {
-            downloaded += chunk.len();
-            content.extend_from_slice(&chunk);
-            if total_size > 0 {
-                let progress_value = ((downloaded * 100) / total_size).min(100);
-                progress.update(progress_value)?;
-            }
+    pub async fn update_cache(&mut self) -> Result<Vec<ServerInfo>, Box<dyn std::error::Error>> {
+        let config = Config::load()?;
+        let api = SteamWebApi::new(&config)?;
+
+        let mut progress = Progress::new(100, &tr!("cache-update-progress"), ProgressStyle::Bar)?;
+        let checked = Utc::now();
+        let fetched = if api.has_api_key() {
+            let modified_since = self
+                .check
+                .modified_since
+                .filter(|_| !self.servers.is_empty());
+            let apps = Self::fetch_store_apps(&api, modified_since, &mut progress).await?;
+            self.check.modified_since = Some(checked.timestamp());
+            Some((apps, modified_since.is_none()))
+        } else {
+            api.get_app_list(&self.check.validators, Some(&mut progress))
+                .await?
+                .map(|(apps, validators)| {
+                    self.check.validators = validators;
+                    (apps, true)
+                })
+        };
+        progress.finish()?;
+        self.check.checked = Some(checked);
+        let Some((apps, full)) = fetched else {
+            self.save_check()?;
+            return Ok(Vec::new());
+        };
+
+        let unchanged = std::mem::take(&mut self.servers);
+        let previous: HashMap<u32, ServerInfo> =
+            unchanged.iter().map(|s| (s.app_id, s.clone())).collect();
+        if !full {
+            // Only the changed apps are fetched, they replace their old entries
+            let changed: HashSet<u32> = apps.iter().map(|app| app.appid).collect();
+            self.servers = unchanged
+                .iter()
+                .filter(|s| !changed.contains(&s.app_id))
+                .cloned()
+                .collect();
         }
 
-        let app_list: serde_json::Value = serde_json::from_slice(&content)?;
-        let apps = app_list["applist"]["apps"]
-            .as_array()
-            .ok_or("Invalid API response format")?;
+        let mut localized = Vec::new();
+        for app in apps {
+            let name = normalize_name(&app.name);
+            let known = previous
+                .get(&app.appid)
+                .filter(|s| name.is_empty() || s.name == name || s.aliases.contains(&name));
+            if let Some(known) = known {
+                self.servers.push(known.clone());
+            } else if Self::is_game_server(&name) {
+                self.servers.push(ServerInfo {
+                    app_id: app.appid,
+                    name,
+                    plattform: vec![Platform::Unknown],
+                    aliases: Vec::new(),
+                });
+            } else if Self::is_localized_game_server(&name) {
+                localized.push((app.appid, name));
+            }
+        }
 
-        self.servers = apps
-            .iter()
-            .filter_map(|app| {
-                let name = app["name"].as_str()?;
-                let app_id = app["appid"].as_u64()?;
-
-                if Self::is_game_server(name) {
-                    Some(ServerInfo {
-                        app_id: app_id as u32,
-                        name: name.to_string(),
-                        plattform: vec![Platform::Unknown],
-                    })
-                } else {
-                    None
+        localized.truncate(MAX_NAME_LOOKUPS);
+        if !localized.is_empty() {
+            let mut progress = Progress::new(
+                localized.len(),
+                &tr!("cache-names-progress"),
+                ProgressStyle::Bar,
+            )?;
+            for (index, (app_id, name)) in localized.into_iter().enumerate() {
+                // A failed lookup is retried on the next update
+                if let Ok(Some(details)) = api.get_app_details(app_id).await {
+                    let english = normalize_name(&details.name);
+                    if Self::is_game_server(&english) {
+                        self.servers.push(ServerInfo {
+                            app_id,
+                            name: english,
+                            plattform: vec![Platform::Unknown],
+                            aliases: vec![name],
+                        });
+                    }
                 }
-            })
-            .collect();
-        self.last_update = Utc::now();
-        progress.finish()?;
+                progress.update(index + 1)?;
+            }
+            progress.finish()?;
+        }
 
+        self.servers.sort_by_key(|s| s.app_id);
+        if self.servers == unchanged {
+            self.save_check()?;
+            return Ok(Vec::new());
+        }
+        self.last_update = Utc::now();
         self.save()?;
 
-        Ok(())
+        if previous.is_empty() {
+            return Ok(Vec::new());
+        }
+        Ok(self
+            .servers
+            .iter()
+            .filter(|s| !previous.contains_key(&s.app_id))
+            .cloned()
+            .collect())
+    }
+
+    /// Fetch the pages of the store app list
+    ///
+    /// # Arguments
+    ///
+    /// - `api` - The Steam Web API client, it needs an API key
+    /// - `modified_since` - Only fetch apps changed after this Unix timestamp, all apps if `None`
+    /// - `progress` - The progress bar of the download
+    ///
+    /// # Returns
+    ///
+    /// The apps of all pages
+    ///
+    /// # Errors
+    ///
+    /// If a page could not be fetched
+    async fn fetch_store_apps(
+        api: &SteamWebApi,
+        modified_since: Option<i64>,
+        progress: &mut Progress,
+    ) -> Result<Vec<App>, Box<dyn std::error::Error>> {
+        let mut apps = Vec::new();
+        let mut last_appid = None;
+        loop {
+            let page = api
+                .get_store_apps(modified_since, last_appid, Some(progress))
+                .await?;
+            apps.extend(page.apps);
+            if !page.have_more_results || page.last_appid.is_none() || page.last_appid == last_appid
+            {
+                return Ok(apps);
+            }
+            last_appid = page.last_appid;
+        }
+    }
+
+    /// Get the time the app list was last checked for changes
+    ///
+    /// # Returns
+    ///
+    /// The last check, or the last change of the cache if it is newer
+    pub fn last_checked(&self) -> DateTime<Utc> {
+        self.check
+            .checked
+            .map_or(self.last_update, |checked| checked.max(self.last_update))
+    }
+
+    /// Check if the server cache is outdated
+    ///
+    /// # Returns
+    ///
+    /// True if the cache is empty or was not checked within the maximum cache age
+    pub fn is_stale(&self) -> bool {
+        let age = Utc::now().signed_duration_since(self.last_checked());
+        self.servers.is_empty() || age.to_std().is_ok_and(|age| age > CACHE_MAX_AGE)
     }
 
     /// Check if a server is a game server
@@ -382,6 +1619,38 @@ impl ServerCache {
             || name.contains("server tool")
             || name.ends_with("server")
     }
+
+    /// Check if a localized name could belong to a game server
+    ///
+    /// # Arguments
+    ///
+    /// - `name` - The localized name of the app
+    ///
+    /// # Returns
+    ///
+    /// True if the name contains a word for "server" in another language
+    fn is_localized_game_server(name: &str) -> bool {
+        let name = name.to_lowercase();
+        LOCALIZED_SERVER_WORDS
+            .iter()
+            .any(|word| name.contains(word))
+    }
+}
+
+/// Normalize an app name from the app list
+///
+/// # Arguments
+///
+/// - `name` - The name of the app
+///
+/// # Returns
+///
+/// The name without trademark signs and with single spaces
+fn normalize_name(name: &str) -> String {
+    name.replace(['™', '®', '©'], " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
 impl Default for ServerCache {
@@ -389,15 +1658,19 @@ impl Default for ServerCache {
         Self {
             servers: Vec::new(),
             last_update: Utc::now(),
+            check: CacheCheck::default(),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use chrono::TimeZone;
+
     use super::*;
 
     #[test]
+    #[allow(clippy::useless_vec)]
     fn test_is_game_server() {
         let test_names = vec![
             "PalServer",
@@ -414,4 +1687,136 @@ mod tests {
             assert_eq!(ServerCache::is_game_server(name), *expected);
         }
     }
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(
+            normalize_name("  ARK:  Survival Evolved™ Dedicated Server "),
+            "ARK: Survival Evolved Dedicated Server"
+        );
+        assert!(ServerCache::is_localized_game_server("专用服务器"));
+        assert!(!ServerCache::is_localized_game_server("Soundtrack"));
+    }
+
+    #[test]
+    fn test_cache_check_keeps_cache_fresh() {
+        let mut cache = ServerCache {
+            servers: vec![ServerInfo {
+                app_id: 896660,
+                name: "Valheim Dedicated Server".to_string(),
+                plattform: vec![Platform::Unknown],
+                aliases: Vec::new(),
+            }],
+            last_update: Utc::now() - chrono::Duration::days(30),
+            check: CacheCheck::default(),
+        };
+        assert!(cache.is_stale());
+
+        cache.check.checked = Some(Utc::now());
+        assert!(!cache.is_stale());
+        assert_eq!(cache.last_checked(), cache.check.checked.unwrap());
+    }
+
+    #[test]
+    fn test_parse_health_config() {
+        let health: HealthConfig = toml::from_str(
+            r#"
+            failures = 2
+            probes = [
+                { type = "a2s" },
+                { type = "log", pattern = "Segmentation fault" },
+            ]
+            actions = ["restart", { hook = "/usr/local/bin/alert.sh" }]
+            memory = { limit = "12G", minutes = 30 }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(health.interval, 30);
+        assert_eq!(health.probes.len(), 2);
+        assert_eq!(
+            health.actions,
+            [
+                RecoveryAction::Restart,
+                RecoveryAction::Hook("/usr/local/bin/alert.sh".to_string())
+            ]
+        );
+        let memory = health.memory.unwrap();
+        assert_eq!(memory.limit, "12G");
+        assert_eq!(memory.minutes, 30);
+        assert_eq!(memory.max_defer, 60);
+        assert_eq!(memory.warnings, [10, 5, 1]);
+    }
+
+    #[test]
+    fn test_parse_log_alerts() {
+        #[derive(Deserialize)]
+        struct Alerts {
+            alerts: Vec<LogAlert>,
+        }
+        let parsed: Alerts = toml::from_str(
+            r#"
+            alerts = [
+                { pattern = "VAC authentication (failure|error)" },
+                { pattern = "World save failed", name = "Save", actions = [{ hook = "/usr/local/bin/backup.sh" }], cooldown = 60 },
+            ]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(parsed.alerts[0].actions, [RecoveryAction::Notify]);
+        assert_eq!(parsed.alerts[0].cooldown, 300);
+        assert_eq!(parsed.alerts[1].name.as_deref(), Some("Save"));
+        assert_eq!(parsed.alerts[1].cooldown, 60);
+    }
+
+    #[test]
+    fn test_blackout() {
+        let mut config: Config = toml::from_str(
+            r#"
+            steamcmd_path = "/opt/steamcmd"
+            install_path = "/srv"
+            installed_servers = []
+            is_initialized = true
+
+            [groups]
+            production = ["cs2-eu"]
+
+            [[blackouts]]
+            name = "Spring cup"
+            start = "2026-05-02T00:00:00"
+            end = "2026-05-04T00:00:00"
+            servers = ["production"]
+            "#,
+        )
+        .unwrap();
+        let mut server: InstalledServer = toml::from_str(
+            r#"
+            app_id = 730
+            name = "cs2-eu"
+            install_path = "/srv/cs2-eu"
+            install_date = "2026-01-01T00:00:00Z"
+            last_updated = "2026-01-01T00:00:00Z"
+            login_type = "Anonymous"
+            timezone = "Europe/Berlin"
+            "#,
+        )
+        .unwrap();
+        let utc = |day: u32, hour: u32| Utc.with_ymd_and_hms(2026, 5, day, hour, 0, 0).unwrap();
+
+        // Midnight in Berlin is 22:00 UTC the day before
+        assert!(server.blackout(&config, utc(1, 21)).is_none());
+        assert_eq!(
+            server.blackout(&config, utc(1, 22)).unwrap().name,
+            "Spring cup"
+        );
+        assert!(server.blackout(&config, utc(3, 22)).is_none());
+
+        config.groups.clear();
+        assert!(server.blackout(&config, utc(2, 12)).is_none());
+
+        config.blackouts[0].servers.clear();
+        server.blackouts = Some(Vec::new());
+        assert!(server.blackout(&config, utc(2, 12)).is_none());
+    }
 }
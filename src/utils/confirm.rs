@@ -0,0 +1,111 @@
+use inquire::Confirm;
+use serde::{Deserialize, Serialize};
+
+use crate::tr;
+
+/// When an action asks for confirmation
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Confirmation {
+    Always,
+    Never,
+    /// Only while players are connected to the server
+    PlayersOnline,
+}
+
+/// Actions that ask for confirmation before they run
+///
+/// By default uninstalls and updates ask, restarts and stops do not.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ConfirmConfig {
+    pub uninstall: Confirmation,
+    pub update: Confirmation,
+    pub restart: Confirmation,
+    pub stop: Confirmation,
+}
+
+impl Default for ConfirmConfig {
+    fn default() -> Self {
+        Self {
+            uninstall: Confirmation::Always,
+            update: Confirmation::Always,
+            restart: Confirmation::Never,
+            stop: Confirmation::Never,
+        }
+    }
+}
+
+impl Confirmation {
+    /// Check if the action has to be confirmed
+    ///
+    /// # Arguments
+    ///
+    /// - `players` - The connected players, `None` if the server is not
+    ///   running or could not be queried
+    ///
+    /// # Returns
+    ///
+    /// True if the user has to be asked
+    pub fn is_required(self, players: Option<u32>) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::PlayersOnline => players.is_some_and(|players| players > 0),
+        }
+    }
+
+    /// Ask for confirmation if the policy requires it
+    ///
+    /// The players are only counted for `players-online`, as the query takes a
+    /// moment.
+    ///
+    /// # Arguments
+    ///
+    /// - `question` - The question to ask
+    /// - `default` - The answer if the user just presses enter
+    /// - `players` - Counts the connected players of the server
+    ///
+    /// # Returns
+    ///
+    /// True if the action may run
+    ///
+    /// # Errors
+    ///
+    /// If the prompt was cancelled
+    pub fn ask(
+        self,
+        question: &str,
+        default: bool,
+        players: impl FnOnce() -> Option<u32>,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        let players = match self {
+            Self::PlayersOnline => players(),
+            _ => None,
+        };
+        if !self.is_required(players) {
+            return Ok(true);
+        }
+
+        let mut prompt = Confirm::new(question).with_default(default);
+        let help = players.map(|players| tr!("confirm-players-online", players = players));
+        if let Some(help) = &help {
+            prompt = prompt.with_help_message(help);
+        }
+        Ok(prompt.prompt()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confirmation_is_required() {
+        assert!(Confirmation::Always.is_required(None));
+        assert!(!Confirmation::Never.is_required(Some(10)));
+        assert!(Confirmation::PlayersOnline.is_required(Some(3)));
+        assert!(!Confirmation::PlayersOnline.is_required(Some(0)));
+        assert!(!Confirmation::PlayersOnline.is_required(None));
+    }
+}
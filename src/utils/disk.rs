@@ -0,0 +1,502 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ffi::CString,
+    fmt,
+    os::unix::{ffi::OsStrExt, fs::MetadataExt, process::CommandExt},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    tr,
+    utils::{download, fs, Config},
+};
+
+/// Target of `ioprio_set` for a single process
+const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+
+/// Directories of `steamapps` that SteamCMD downloads and unpacks updates in
+pub const SCRATCH_DIRS: [&str; 2] = ["downloading", "temp"];
+
+/// Soft disk quotas of the install roots, the free space threshold and the
+/// scratch disk of the downloads
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct DiskConfig {
+    /// Soft quotas of the install roots, e.g. `"/srv/games" = "500G"`
+    pub quotas: BTreeMap<PathBuf, String>,
+    /// Warn when the servers in a root use this share of its quota, in percent
+    pub warn_percent: u8,
+    /// Warn when the free space of a filesystem drops below this share, in percent
+    pub min_free_percent: u8,
+    /// Interval of the checks of the supervisor in seconds
+    pub check_interval: u64,
+    /// Directory on a separate disk SteamCMD stages the downloads in, `None` keeps
+    /// them in the `steamapps` directory of the install
+    pub scratch_dir: Option<PathBuf>,
+    /// Priority of SteamCMD in the background jobs of the daemon
+    pub background: BackgroundPriority,
+}
+
+impl Default for DiskConfig {
+    /// Warn at 90% of a quota or below 10% free space, checked hourly by the supervisor
+    fn default() -> Self {
+        Self {
+            quotas: BTreeMap::new(),
+            warn_percent: 90,
+            min_free_percent: 10,
+            check_interval: 60 * 60,
+            scratch_dir: None,
+            background: BackgroundPriority::default(),
+        }
+    }
+}
+
+/// I/O scheduling class of the Linux kernel
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum IoClass {
+    /// Keep the I/O priority of the daemon
+    None,
+    /// Share the disk with the other processes, with the priority of `io_level`
+    BestEffort,
+    /// Only use the disk when no other process needs it
+    Idle,
+}
+
+/// Priority of SteamCMD when the daemon updates servers in the background
+///
+/// Lowers the CPU and disk priority of the downloads, so the game servers on
+/// the same machine do not lag while an update is written.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct BackgroundPriority {
+    /// Nice value from -20 to 19, `0` keeps the CPU priority
+    pub nice: i32,
+    pub io_class: IoClass,
+    /// Level of the best effort class from 0 (highest) to 7 (lowest)
+    pub io_level: u8,
+}
+
+impl Default for BackgroundPriority {
+    /// Nice 10 and the lowest best effort level, like `nice -n 10 ionice -c 2 -n 7`
+    fn default() -> Self {
+        Self {
+            nice: 10,
+            io_class: IoClass::BestEffort,
+            io_level: 7,
+        }
+    }
+}
+
+impl BackgroundPriority {
+    /// Lower the priority of a command once it is started
+    ///
+    /// Failures are ignored, e.g. on kernels without I/O scheduling classes.
+    ///
+    /// # Arguments
+    ///
+    /// - `command` - The command, e.g. SteamCMD
+    pub fn apply(&self, command: &mut Command) {
+        let nice = self.nice.clamp(-20, 19);
+        let ioprio = self.ioprio();
+        if nice == 0 && ioprio.is_none() {
+            return;
+        }
+
+        // SAFETY: the closure only makes syscalls, which is safe after fork
+        unsafe {
+            command.pre_exec(move || {
+                if nice != 0 {
+                    libc::setpriority(libc::PRIO_PROCESS as _, 0, nice);
+                }
+                // The I/O priority only exists on Linux
+                #[cfg(target_os = "linux")]
+                if let Some(ioprio) = ioprio {
+                    libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, 0, ioprio);
+                }
+                Ok(())
+            });
+        }
+    }
+
+    /// Get the value of the I/O priority for `ioprio_set`
+    ///
+    /// # Returns
+    ///
+    /// The class in the upper bits and the level in the lower bits, or `None`
+    /// if the I/O priority is kept
+    fn ioprio(&self) -> Option<libc::c_int> {
+        const CLASS_SHIFT: libc::c_int = 13;
+
+        match self.io_class {
+            IoClass::None => None,
+            IoClass::BestEffort => Some(2 << CLASS_SHIFT | libc::c_int::from(self.io_level.min(7))),
+            IoClass::Idle => Some(3 << CLASS_SHIFT),
+        }
+    }
+}
+
+/// A disk space problem of an install root
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiskWarning {
+    /// The servers in the root approach or exceed its quota
+    Quota {
+        root: PathBuf,
+        used: u64,
+        quota: u64,
+    },
+    /// The filesystem of the root is almost full
+    FreeSpace {
+        root: PathBuf,
+        free: u64,
+        total: u64,
+    },
+}
+
+impl DiskWarning {
+    /// Check if two warnings are about the same problem, ignoring the sizes
+    ///
+    /// # Arguments
+    ///
+    /// - `other` - The other warning
+    ///
+    /// # Returns
+    ///
+    /// True if both warnings are of the same kind and about the same root
+    pub fn is_same_problem(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Quota { root: a, .. }, Self::Quota { root: b, .. }) => a == b,
+            (Self::FreeSpace { root: a, .. }, Self::FreeSpace { root: b, .. }) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for DiskWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Quota { root, used, quota } => write!(
+                f,
+                "{}",
+                tr!(
+                    "disk-quota-warning",
+                    root = root.display(),
+                    used = fs::format_size(*used),
+                    quota = fs::format_size(*quota),
+                    percent = percent(*used, *quota)
+                )
+            ),
+            Self::FreeSpace { root, free, total } => write!(
+                f,
+                "{}",
+                tr!(
+                    "disk-free-warning",
+                    root = root.display(),
+                    free = fs::format_size(*free),
+                    percent = percent(*free, *total)
+                )
+            ),
+        }
+    }
+}
+
+/// Check the quotas of the install roots and the free space of their filesystems
+///
+/// The quotas are checked against the size of the installed servers below each
+/// root. Every filesystem is only reported once.
+///
+/// # Arguments
+///
+/// - `config` - The config
+///
+/// # Returns
+///
+/// The warnings, empty if there is enough space
+///
+/// # Errors
+///
+/// If a quota is not a valid size
+pub fn check(config: &Config) -> Result<Vec<DiskWarning>, Box<dyn std::error::Error>> {
+    let settings = &config.disk;
+    let mut warnings = Vec::new();
+
+    for (root, quota) in &settings.quotas {
+        let quota = fs::parse_size(quota)
+            .ok_or_else(|| tr!("disk-quota-invalid", root = root.display(), quota = quota))?;
+        let used: u64 = config
+            .installed_servers
+            .iter()
+            .filter(|s| s.install_path.starts_with(root))
+            .map(|s| fs::dir_size(&s.install_path))
+            .sum();
+        if used.saturating_mul(100) >= quota.saturating_mul(settings.warn_percent.into()) {
+            warnings.push(DiskWarning::Quota {
+                root: root.clone(),
+                used,
+                quota,
+            });
+        }
+    }
+
+    let mut filesystems = BTreeSet::new();
+    for root in roots(config) {
+        let Some((filesystem, free, total)) = free_space(&root) else {
+            continue;
+        };
+        if !filesystems.insert(filesystem) {
+            continue;
+        }
+        if free.saturating_mul(100) < total.saturating_mul(settings.min_free_percent.into()) {
+            warnings.push(DiskWarning::FreeSpace { root, free, total });
+        }
+    }
+
+    Ok(warnings)
+}
+
+/// Get the directories the servers and their downloads are stored in
+///
+/// # Arguments
+///
+/// - `config` - The config
+///
+/// # Returns
+///
+/// The install path, the roots with a quota, the scratch directory and the
+/// parents of the installed servers
+pub fn roots(config: &Config) -> BTreeSet<PathBuf> {
+    let settings = &config.disk;
+    settings
+        .quotas
+        .keys()
+        .cloned()
+        .chain(std::iter::once(config.install_path.clone()))
+        .chain(settings.scratch_dir.clone())
+        .chain(
+            config
+                .installed_servers
+                .iter()
+                .filter_map(|s| s.install_path.parent().map(Path::to_path_buf)),
+        )
+        .filter(|root| !root.as_os_str().is_empty())
+        .collect()
+}
+
+/// Print the disk space warnings, e.g. before an install
+///
+/// # Arguments
+///
+/// - `config` - The config
+///
+/// # Returns
+///
+/// Ok if the check ran
+///
+/// # Errors
+///
+/// If a quota is not a valid size
+pub fn warn(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    for warning in check(config)? {
+        println!("{}", warning);
+    }
+    Ok(())
+}
+
+/// Get the scratch directory of an install
+///
+/// # Arguments
+///
+/// - `disk` - The disk settings
+/// - `install_dir` - The install directory
+///
+/// # Returns
+///
+/// The directory `<scratch_dir>/<install name>-<path hash>`, or `None` without
+/// scratch disk. The hash of the full install path keeps installs with the same
+/// directory name apart.
+pub fn scratch_dir(disk: &DiskConfig, install_dir: &Path) -> Option<PathBuf> {
+    let name = install_dir.file_name()?.to_string_lossy();
+    let hash = download::sha256_hex(install_dir.as_os_str().as_bytes());
+    Some(
+        disk.scratch_dir
+            .as_ref()?
+            .join(format!("{}-{}", name, &hash[..12])),
+    )
+}
+
+/// Link the download directories of an install to its scratch directory
+///
+/// `steamapps/downloading` and `steamapps/temp` become symlinks into the scratch
+/// directory, so SteamCMD stages the downloads there and only the finished files
+/// are written to the install. A directory with an unfinished download is kept,
+/// SteamCMD resumes it in place.
+///
+/// # Arguments
+///
+/// - `disk` - The disk settings
+/// - `install_dir` - The install directory
+///
+/// # Returns
+///
+/// The scratch directory of the install, or `None` without scratch disk
+///
+/// # Errors
+///
+/// If a directory or symlink could not be created
+pub fn link_scratch(
+    disk: &DiskConfig,
+    install_dir: &Path,
+) -> Result<Option<PathBuf>, Box<dyn std::error::Error>> {
+    let Some(scratch) = scratch_dir(disk, install_dir) else {
+        return Ok(None);
+    };
+    let steamapps = install_dir.join("steamapps");
+    std::fs::create_dir_all(&steamapps)?;
+
+    for name in SCRATCH_DIRS {
+        let target = scratch.join(name);
+        std::fs::create_dir_all(&target)?;
+        let link = steamapps.join(name);
+        match std::fs::symlink_metadata(&link) {
+            Ok(meta) if meta.is_symlink() => {
+                if std::fs::read_link(&link)? == target {
+                    continue;
+                }
+                std::fs::remove_file(&link)?;
+            }
+            Ok(meta) if meta.is_dir() => {
+                if std::fs::read_dir(&link)?.next().is_some() {
+                    continue;
+                }
+                std::fs::remove_dir(&link)?;
+            }
+            Ok(_) => std::fs::remove_file(&link)?,
+            Err(_) => {}
+        }
+        std::os::unix::fs::symlink(&target, &link)?;
+    }
+
+    Ok(Some(scratch))
+}
+
+/// Get the free and total space of the filesystem of a path
+///
+/// For a path that does not exist yet, the nearest existing parent is used.
+///
+/// # Arguments
+///
+/// - `path` - The path
+///
+/// # Returns
+///
+/// The device of the filesystem, the space available to unprivileged users and the total
+/// space in bytes, or `None` if the filesystem could not be queried
+fn free_space(path: &Path) -> Option<(u64, u64, u64)> {
+    let existing = path.ancestors().find(|p| p.exists())?;
+    let device = existing.metadata().ok()?.dev();
+    let path = CString::new(existing.as_os_str().as_bytes()).ok()?;
+
+    // SAFETY: statvfs is plain old data, so a zeroed value is valid
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: the path is a valid NUL terminated string and stat is a valid buffer
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+
+    let block = stat.f_frsize as u64;
+    Some((
+        device,
+        stat.f_bavail as u64 * block,
+        stat.f_blocks as u64 * block,
+    ))
+}
+
+/// Get a share in percent
+///
+/// # Arguments
+///
+/// - `part` - The part
+/// - `whole` - The whole
+///
+/// # Returns
+///
+/// The share rounded down, `0` for an empty whole
+fn percent(part: u64, whole: u64) -> u64 {
+    if whole == 0 {
+        return 0;
+    }
+    (part as u128 * 100 / whole as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_scratch() {
+        let dir = std::env::temp_dir().join(format!("steamserv-scratch-{}", std::process::id()));
+        let install = dir.join("games/valheim");
+        std::fs::create_dir_all(install.join("steamapps/temp/partial")).unwrap();
+        let disk = DiskConfig {
+            scratch_dir: Some(dir.join("scratch")),
+            ..DiskConfig::default()
+        };
+
+        let scratch = link_scratch(&disk, &install).unwrap().unwrap();
+        assert_eq!(scratch, scratch_dir(&disk, &install).unwrap());
+        assert!(scratch
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("valheim-"));
+        let downloading = install.join("steamapps/downloading");
+        assert_eq!(
+            std::fs::read_link(&downloading).unwrap(),
+            scratch.join("downloading")
+        );
+        // The unfinished download in temp is resumed in place
+        assert!(!install.join("steamapps/temp").is_symlink());
+        // Linking again keeps the links
+        link_scratch(&disk, &install).unwrap();
+        assert!(downloading.is_symlink());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_scratch_dir_per_install() {
+        let disk = DiskConfig {
+            scratch_dir: Some(PathBuf::from("/scratch")),
+            ..DiskConfig::default()
+        };
+        let first = scratch_dir(&disk, Path::new("/srv/a/valheim")).unwrap();
+        let second = scratch_dir(&disk, Path::new("/srv/b/valheim")).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(
+            first,
+            scratch_dir(&disk, Path::new("/srv/a/valheim")).unwrap()
+        );
+        assert!(scratch_dir(&DiskConfig::default(), Path::new("/srv/a/valheim")).is_none());
+    }
+
+    #[test]
+    fn test_percent() {
+        assert_eq!(percent(45, 50), 90);
+        assert_eq!(percent(1, 3), 33);
+        assert_eq!(percent(1, 0), 0);
+    }
+
+    #[test]
+    fn test_ioprio() {
+        let mut priority = BackgroundPriority::default();
+        assert_eq!(priority.ioprio(), Some(0x4007));
+        priority.io_level = 12;
+        assert_eq!(priority.ioprio(), Some(0x4007));
+        priority.io_class = IoClass::Idle;
+        assert_eq!(priority.ioprio(), Some(0x6000));
+        priority.io_class = IoClass::None;
+        assert_eq!(priority.ioprio(), None);
+    }
+}
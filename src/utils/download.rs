@@ -0,0 +1,158 @@
+use std::time::Duration;
+
+use sha2::{Digest, Sha256};
+
+use crate::{tr, utils::Progress};
+
+/// Number of retries for interrupted downloads
+const MAX_RETRIES: u32 = 3;
+/// Delay between two download attempts
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// Download a file over HTTPS, resuming interrupted transfers
+///
+/// # Arguments
+///
+/// - `client` - The HTTP client to use, from `NetworkConfig::https_client`
+/// - `url` - The URL of the file, must use HTTPS
+/// - `progress` - The progress bar showing the download progress
+///
+/// # Returns
+///
+/// The content of the downloaded file
+///
+/// # Errors
+///
+/// If the URL does not use HTTPS, the download still failed after all retries
+/// or the downloaded size does not match the announced size
+pub async fn download_file(
+    client: &reqwest::Client,
+    url: &str,
+    progress: &mut Progress,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let parsed = reqwest::Url::parse(url)?;
+    if parsed.scheme() != "https" {
+        return Err(tr!("download-https-required", url = url).into());
+    }
+
+    let mut content: Vec<u8> = Vec::new();
+    let mut total_size: Option<usize> = None;
+    let mut attempt = 0;
+
+    loop {
+        let mut request = client.get(parsed.clone());
+        if !content.is_empty() {
+            request = request.header(reqwest::header::RANGE, format!("bytes={}-", content.len()));
+        }
+
+        let result = async {
+            let mut response = request.send().await?.error_for_status()?;
+            if response.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+                content.clear();
+                total_size = response.content_length().map(|len| len as usize);
+            }
+
+            while let Some(chunk) = response.chunk().await? {
+                content.extend_from_slice(&chunk);
+                progress
+                    .update_bytes(content.len() as u64, total_size.map(|total| total as u64))?;
+            }
+            Ok::<(), Box<dyn std::error::Error>>(())
+        }
+        .await;
+
+        match result {
+            Ok(()) => break,
+            Err(e) if attempt < MAX_RETRIES => {
+                attempt += 1;
+                println!(
+                    "\n{}",
+                    tr!(
+                        "download-retrying",
+                        error = e.to_string(),
+                        attempt = attempt,
+                        max = MAX_RETRIES
+                    )
+                );
+                tokio::time::sleep(RETRY_DELAY).await;
+            }
+            Err(e) => {
+                return Err(tr!(
+                    "download-failed",
+                    retries = MAX_RETRIES,
+                    error = e.to_string()
+                )
+                .into())
+            }
+        }
+    }
+
+    if let Some(total) = total_size {
+        if content.len() != total {
+            return Err(tr!(
+                "download-incomplete",
+                received = content.len(),
+                total = total
+            )
+            .into());
+        }
+    }
+
+    Ok(content)
+}
+
+/// Calculate the SHA-256 checksum of some data
+///
+/// # Arguments
+///
+/// - `content` - The data to hash
+///
+/// # Returns
+///
+/// The checksum as lowercase hex string
+pub fn sha256_hex(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Verify the SHA-256 checksum of some data
+///
+/// # Arguments
+///
+/// - `content` - The data to verify
+/// - `expected` - The expected checksum as hex string
+///
+/// # Returns
+///
+/// Ok if the checksum matches
+///
+/// # Errors
+///
+/// If the checksum does not match
+pub fn verify_sha256(content: &[u8], expected: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let actual = sha256_hex(content);
+    if !actual.eq_ignore_ascii_case(expected.trim()) {
+        return Err(tr!(
+            "download-checksum-mismatch",
+            expected = expected.trim(),
+            actual = actual
+        )
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_sha256() {
+        let expected = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        assert_eq!(sha256_hex(b"hello"), expected);
+        assert!(verify_sha256(b"hello", &expected.to_uppercase()).is_ok());
+        assert!(verify_sha256(b"hello!", expected).is_err());
+    }
+}
@@ -0,0 +1,90 @@
+/// Parse a duration like `30m`, `24h` or `7d`
+///
+/// # Arguments
+///
+/// - `value` - The duration with a unit of `s`, `m`, `h`, `d` or `w`
+///
+/// # Returns
+///
+/// The duration, or `None` if the value is invalid
+pub fn parse_duration(value: &str) -> Option<chrono::Duration> {
+    let value = value.trim();
+    let unit = value.chars().last()?;
+    let amount: i64 = value[..value.len() - unit.len_utf8()].parse().ok()?;
+
+    match unit {
+        's' => chrono::Duration::try_seconds(amount),
+        'm' => chrono::Duration::try_minutes(amount),
+        'h' => chrono::Duration::try_hours(amount),
+        'd' => chrono::Duration::try_days(amount),
+        'w' => chrono::Duration::try_weeks(amount),
+        _ => None,
+    }
+}
+
+/// Format a duration as a short age like `45s`, `12m`, `5h` or `3d`
+///
+/// # Arguments
+///
+/// - `age` - The duration, negative durations count as zero
+///
+/// # Returns
+///
+/// The duration in its largest unit, hours up to two days
+pub fn format_age(age: chrono::Duration) -> String {
+    let seconds = age.num_seconds().max(0);
+    match seconds {
+        s if s < 60 => format!("{}s", s),
+        s if s < 60 * 60 => format!("{}m", s / 60),
+        s if s < 48 * 60 * 60 => format!("{}h", s / (60 * 60)),
+        s => format!("{}d", s / (24 * 60 * 60)),
+    }
+}
+
+/// Format a duration like a clock, e.g. `4:05` or `1:02:03`
+///
+/// # Arguments
+///
+/// - `duration` - The duration
+///
+/// # Returns
+///
+/// The minutes and seconds, with the hours in front if there are any
+pub fn format_clock(duration: std::time::Duration) -> String {
+    let seconds = duration.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("24h"), Some(chrono::Duration::hours(24)));
+        assert_eq!(parse_duration("7d"), Some(chrono::Duration::days(7)));
+        assert_eq!(parse_duration("24"), None);
+        assert_eq!(parse_duration("h"), None);
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(chrono::Duration::seconds(45)), "45s");
+        assert_eq!(format_age(chrono::Duration::minutes(90)), "1h");
+        assert_eq!(format_age(chrono::Duration::hours(47)), "47h");
+        assert_eq!(format_age(chrono::Duration::days(3)), "3d");
+        assert_eq!(format_age(chrono::Duration::seconds(-5)), "0s");
+    }
+
+    #[test]
+    fn test_format_clock() {
+        use std::time::Duration;
+        assert_eq!(format_clock(Duration::from_secs(245)), "4:05");
+        assert_eq!(format_clock(Duration::from_secs(3723)), "1:02:03");
+    }
+}
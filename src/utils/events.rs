@@ -0,0 +1,491 @@
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::PathBuf,
+    sync::{Mutex, OnceLock},
+};
+
+use chrono::Utc;
+use serde::Serialize;
+use tokio::{sync::broadcast, task::JoinHandle};
+
+use crate::{
+    core::{
+        jobs::{self, ProgressSink},
+        UpdateReport,
+    },
+    tr,
+    utils::{notify::notify, phases::Phase, runtime, Config},
+};
+
+/// Number of events kept for subscribers that fall behind
+const CHANNEL_CAPACITY: usize = 256;
+
+/// Something that happened during an operation
+///
+/// Core operations publish events instead of printing, the registered sinks
+/// decide how they are shown, logged or forwarded.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    InstallStarted {
+        server: String,
+        app_id: u32,
+    },
+    InstallFinished {
+        server: String,
+    },
+    UpdateStarted {
+        server: String,
+    },
+    UpdateFinished {
+        server: String,
+    },
+    /// A staged update was swapped in before the server started
+    UpdateApplied {
+        server: String,
+    },
+    /// An update of all servers finished
+    UpdatesFinished {
+        report: UpdateReport,
+    },
+    /// Progress of a SteamCMD download, parsed from its output
+    ProgressUpdated {
+        state: String,
+        percent: f64,
+        current: u64,
+        total: u64,
+    },
+    /// A phase of a SteamCMD run started, e.g. the download
+    PhaseStarted {
+        phase: Phase,
+    },
+    PhaseFinished {
+        phase: Phase,
+        seconds: f64,
+    },
+    ServerStarted {
+        target: String,
+        pid: u32,
+        log: PathBuf,
+    },
+    ServerStopped {
+        target: String,
+    },
+    HealthCheckFailed {
+        target: String,
+        failures: u32,
+        max: u32,
+        error: String,
+    },
+    /// A server failed enough health checks in a row to be recovered
+    ServerCrashed {
+        target: String,
+        error: String,
+    },
+    RecoveryFinished {
+        target: String,
+        message: String,
+    },
+    RecoveryFailed {
+        target: String,
+        error: String,
+    },
+    /// A line of the server log matched an alert
+    LogAlert {
+        target: String,
+        alert: String,
+        line: String,
+    },
+}
+
+impl Event {
+    /// Get the kind of the event
+    ///
+    /// # Returns
+    ///
+    /// The kind in snake case, as used in the event log and the webhook filter
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::InstallStarted { .. } => "install_started",
+            Self::InstallFinished { .. } => "install_finished",
+            Self::UpdateStarted { .. } => "update_started",
+            Self::UpdateFinished { .. } => "update_finished",
+            Self::UpdateApplied { .. } => "update_applied",
+            Self::UpdatesFinished { .. } => "updates_finished",
+            Self::ProgressUpdated { .. } => "progress_updated",
+            Self::PhaseStarted { .. } => "phase_started",
+            Self::PhaseFinished { .. } => "phase_finished",
+            Self::ServerStarted { .. } => "server_started",
+            Self::ServerStopped { .. } => "server_stopped",
+            Self::HealthCheckFailed { .. } => "health_check_failed",
+            Self::ServerCrashed { .. } => "server_crashed",
+            Self::RecoveryFinished { .. } => "recovery_finished",
+            Self::RecoveryFailed { .. } => "recovery_failed",
+            Self::LogAlert { .. } => "log_alert",
+        }
+    }
+
+    /// Get the message to show for the event
+    ///
+    /// # Returns
+    ///
+    /// The translated message, or `None` for progress updates, the CLI shows the
+    /// output of SteamCMD instead
+    pub fn message(&self) -> Option<String> {
+        let message = match self {
+            Self::InstallStarted { server, app_id } => {
+                tr!("event-install-started", server = server, app_id = app_id)
+            }
+            Self::InstallFinished { .. } => tr!("install-success"),
+            Self::UpdateStarted { server } => tr!("event-update-started", server = server),
+            Self::UpdateFinished { .. } => tr!("update-success"),
+            Self::UpdateApplied { server } => tr!("event-update-applied", server = server),
+            Self::UpdatesFinished { report } => report.summary(),
+            Self::ProgressUpdated { .. } => return None,
+            Self::PhaseStarted { phase } => tr!("event-phase-started", phase = phase.name()),
+            Self::PhaseFinished { phase, seconds } => tr!(
+                "event-phase-finished",
+                phase = phase.name(),
+                seconds = format!("{:.1}", seconds)
+            ),
+            Self::ServerStarted { target, pid, log } => tr!(
+                "process-started",
+                target = target,
+                pid = pid,
+                log = log.display()
+            ),
+            Self::ServerStopped { target } => tr!("process-stopped", target = target),
+            Self::HealthCheckFailed {
+                target,
+                failures,
+                max,
+                error,
+            } => tr!(
+                "daemon-check-failed",
+                target = target,
+                failures = failures,
+                max = max,
+                error = error
+            ),
+            Self::ServerCrashed { target, error } => {
+                tr!("event-server-crashed", target = target, error = error)
+            }
+            Self::RecoveryFinished { target, message } => {
+                tr!(
+                    "event-recovery-finished",
+                    target = target,
+                    message = message
+                )
+            }
+            Self::RecoveryFailed { target, error } => {
+                tr!("daemon-recovery-failed", target = target, error = error)
+            }
+            Self::LogAlert {
+                target,
+                alert,
+                line,
+            } => tr!(
+                "event-log-alert",
+                target = target,
+                alert = alert,
+                line = line
+            ),
+        };
+        Some(message)
+    }
+}
+
+/// Receiver of the published events
+pub trait EventSink: Send {
+    /// Handle a published event
+    ///
+    /// # Arguments
+    ///
+    /// - `event` - The event
+    ///
+    /// # Returns
+    ///
+    /// Ok if the event was handled
+    ///
+    /// # Errors
+    ///
+    /// If the event could not be handled, the error is printed and the other sinks still get the event
+    fn handle(&mut self, event: &Event) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+/// Sink that prints the events on the terminal
+pub struct CliSink;
+
+impl EventSink for CliSink {
+    fn handle(&mut self, event: &Event) -> Result<(), Box<dyn std::error::Error>> {
+        if runtime::is_json_progress() {
+            println!("{}", json_line(event)?);
+        } else if let Some(message) = event.message() {
+            println!("{}", message);
+        }
+        Ok(())
+    }
+}
+
+/// Sink that appends the events as JSON lines to a log file
+///
+/// Progress updates are left out, they would only bloat the log.
+pub struct LogSink {
+    path: PathBuf,
+}
+
+impl LogSink {
+    /// Create a sink for a log file
+    ///
+    /// # Arguments
+    ///
+    /// - `path` - The log file, it is created on the first event
+    ///
+    /// # Returns
+    ///
+    /// The sink
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl EventSink for LogSink {
+    fn handle(&mut self, event: &Event) -> Result<(), Box<dyn std::error::Error>> {
+        if matches!(event, Event::ProgressUpdated { .. }) {
+            return Ok(());
+        }
+
+        let line = json_line(event)?;
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", line)?;
+        Ok(())
+    }
+}
+
+/// Sink that sends the events of the configured kinds to the webhook
+pub struct WebhookSink {
+    config: Config,
+}
+
+impl WebhookSink {
+    /// Create a sink for the webhook of the configuration
+    ///
+    /// # Arguments
+    ///
+    /// - `config` - The configuration with the webhook URL and the event kinds
+    ///
+    /// # Returns
+    ///
+    /// The sink
+    pub fn new(config: Config) -> Self {
+        Self { config }
+    }
+}
+
+impl EventSink for WebhookSink {
+    fn handle(&mut self, event: &Event) -> Result<(), Box<dyn std::error::Error>> {
+        if !self
+            .config
+            .notifications
+            .events
+            .iter()
+            .any(|kind| kind == event.kind())
+        {
+            return Ok(());
+        }
+        let Some(message) = event.message() else {
+            return Ok(());
+        };
+        // Webhooks are sent in the background and awaited by `flush`
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return Ok(());
+        };
+
+        let config = self.config.clone();
+        let task = runtime.spawn(async move {
+            if let Err(e) = notify(&config, &format!("steamserv: {}", message)).await {
+                println!("{}", tr!("event-webhook-failed", error = e.to_string()));
+            }
+        });
+        let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+        pending.retain(|task| !task.is_finished());
+        pending.push(task);
+        Ok(())
+    }
+}
+
+/// Sink that forwards the events to the subscribers of `subscribe`
+struct ChannelSink {
+    sender: broadcast::Sender<Event>,
+}
+
+impl EventSink for ChannelSink {
+    fn handle(&mut self, event: &Event) -> Result<(), Box<dyn std::error::Error>> {
+        // Without subscribers the event is dropped, which is fine
+        let _ = self.sender.send(event.clone());
+        Ok(())
+    }
+}
+
+static SINKS: Mutex<Vec<Box<dyn EventSink>>> = Mutex::new(Vec::new());
+static PENDING: Mutex<Vec<JoinHandle<()>>> = Mutex::new(Vec::new());
+static CHANNEL: OnceLock<broadcast::Sender<Event>> = OnceLock::new();
+
+/// Register the sinks of the command line: the terminal, the event log and the
+/// webhook, and the progress checkpoints in a background job
+///
+/// # Arguments
+///
+/// - `config` - The configuration
+///
+/// # Returns
+///
+/// Ok if the sinks were registered
+///
+/// # Errors
+///
+/// If the steamserv directory could not be found
+pub fn init(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    register(Box::new(CliSink));
+    register(Box::new(LogSink::new(
+        Config::data_dir()?.join("events.jsonl"),
+    )));
+    if config.notifications.webhook_url.is_some() && !config.notifications.events.is_empty() {
+        register(Box::new(WebhookSink::new(config.clone())));
+    }
+    if let Some(id) = jobs::current_job() {
+        register(Box::new(ProgressSink::new(id)?));
+    }
+    Ok(())
+}
+
+/// Register a sink for all events published from now on
+///
+/// # Arguments
+///
+/// - `sink` - The sink
+pub fn register(sink: Box<dyn EventSink>) {
+    SINKS.lock().unwrap_or_else(|e| e.into_inner()).push(sink);
+}
+
+/// Subscribe to the published events, e.g. to stream them to API clients
+///
+/// # Returns
+///
+/// A receiver for all events published from now on
+pub fn subscribe() -> broadcast::Receiver<Event> {
+    CHANNEL
+        .get_or_init(|| {
+            let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+            register(Box::new(ChannelSink {
+                sender: sender.clone(),
+            }));
+            sender
+        })
+        .subscribe()
+}
+
+/// Publish an event to all registered sinks
+///
+/// # Arguments
+///
+/// - `event` - The event
+pub fn publish(event: Event) {
+    let mut sinks = SINKS.lock().unwrap_or_else(|e| e.into_inner());
+    for sink in sinks.iter_mut() {
+        if let Err(e) = sink.handle(&event) {
+            println!("{}", tr!("event-sink-failed", error = e.to_string()));
+        }
+    }
+}
+
+/// Wait for the webhooks that are still being sent
+pub async fn flush() {
+    let pending = std::mem::take(&mut *PENDING.lock().unwrap_or_else(|e| e.into_inner()));
+    for task in pending {
+        let _ = task.await;
+    }
+}
+
+/// Serialize an event as a JSON line with the current time
+///
+/// # Arguments
+///
+/// - `event` - The event
+///
+/// # Returns
+///
+/// The event as a single line of JSON
+///
+/// # Errors
+///
+/// If the event could not be serialized
+fn json_line(event: &Event) -> Result<String, Box<dyn std::error::Error>> {
+    let mut line = serde_json::to_value(event)?;
+    line["timestamp"] = serde_json::json!(Utc::now());
+    Ok(line.to_string())
+}
+
+/// Parse a progress line of SteamCMD
+///
+/// # Arguments
+///
+/// - `line` - The line, e.g. ` Update state (0x61) downloading, progress: 45.23 (1234 / 5678)`
+///
+/// # Returns
+///
+/// The progress event, or `None` if the line is no progress line
+pub fn parse_progress(line: &str) -> Option<Event> {
+    let rest = line.trim().strip_prefix("Update state (")?;
+    let (_, rest) = rest.split_once(") ")?;
+    let (state, rest) = rest.split_once(", progress: ")?;
+    let (percent, rest) = rest.split_once(" (")?;
+    let (current, total) = rest.trim_end_matches(')').split_once(" / ")?;
+
+    Some(Event::ProgressUpdated {
+        state: state.to_lowercase(),
+        // A decimal comma is printed if SteamCMD did not run with the C locale
+        percent: percent.replace(',', ".").parse().ok()?,
+        current: current.parse().ok()?,
+        total: total.parse().ok()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_progress() {
+        let event =
+            parse_progress(" Update state (0x61) downloading, progress: 45.23 (1234 / 5678)")
+                .unwrap();
+        let Event::ProgressUpdated {
+            state,
+            percent,
+            current,
+            total,
+        } = event
+        else {
+            panic!("unexpected event {:?}", event);
+        };
+        assert_eq!(state, "downloading");
+        assert_eq!(percent, 45.23);
+        assert_eq!((current, total), (1234, 5678));
+        assert!(parse_progress("Success! App '896660' fully installed.").is_none());
+        let event =
+            parse_progress(" Update state (0x61) Downloading, progress: 45,23 (1234 / 5678)");
+        assert!(matches!(
+            event,
+            Some(Event::ProgressUpdated { state, percent, .. })
+                if state == "downloading" && percent == 45.23
+        ));
+    }
+}
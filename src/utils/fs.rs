@@ -0,0 +1,322 @@
+use std::{
+    fs::{File, OpenOptions, Permissions},
+    io::Write,
+    os::unix::fs::OpenOptionsExt,
+    path::{Path, PathBuf},
+};
+
+use super::pattern::matches_wildcard;
+
+/// Write a file atomically
+///
+/// The content is written to a temporary file in the same directory, synced to
+/// disk and renamed over the target, so a crash never leaves a partially written file.
+/// An existing file keeps its permissions, e.g. `0600` of a config with secrets.
+///
+/// # Arguments
+///
+/// - `path` - The path to the file
+/// - `content` - The content to write
+///
+/// # Returns
+///
+/// Ok if the file was written
+///
+/// # Errors
+///
+/// If the file could not be written or renamed
+pub fn write_atomic(path: &Path, content: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+    let permissions = std::fs::metadata(path).ok().map(|meta| meta.permissions());
+    write_atomic_with(path, content, permissions)
+}
+
+/// Write a file atomically with the permissions of another file
+///
+/// # Arguments
+///
+/// - `path` - The path to the file
+/// - `content` - The content to write
+/// - `like` - The file whose permissions the new file gets, e.g. the original of a backup
+///
+/// # Returns
+///
+/// Ok if the file was written
+///
+/// # Errors
+///
+/// If the permissions could not be read or the file could not be written or renamed
+pub fn write_atomic_like(
+    path: &Path,
+    content: &[u8],
+    like: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let permissions = std::fs::metadata(like)?.permissions();
+    write_atomic_with(path, content, Some(permissions))
+}
+
+/// Write a file atomically with the given permissions
+///
+/// With permissions, the temporary file is only readable by the owner until
+/// they are applied, so the content is never exposed to other users.
+///
+/// # Arguments
+///
+/// - `path` - The path to the file
+/// - `content` - The content to write
+/// - `permissions` - The permissions of the file, `None` for the default of the umask
+///
+/// # Returns
+///
+/// Ok if the file was written
+///
+/// # Errors
+///
+/// If the file could not be written or renamed
+fn write_atomic_with(
+    path: &Path,
+    content: &[u8],
+    permissions: Option<Permissions>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    std::fs::create_dir_all(dir)?;
+
+    let temp_path = temp_path(path);
+    let result = (|| -> Result<(), Box<dyn std::error::Error>> {
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        if permissions.is_some() {
+            options.mode(0o600);
+        }
+        let mut file = options.open(&temp_path)?;
+        if let Some(permissions) = permissions {
+            file.set_permissions(permissions)?;
+        }
+        file.write_all(content)?;
+        file.sync_all()?;
+        std::fs::rename(&temp_path, path)?;
+        // Sync the directory so the rename itself is durable
+        File::open(dir)?.sync_all()?;
+        Ok(())
+    })();
+
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Remove the files in a directory tree that match wildcard patterns
+///
+/// Only patterns relative to the directory are used, like the saves of a game
+/// profile, patterns with an absolute path or `{home}` are skipped.
+///
+/// # Arguments
+///
+/// - `root` - The directory
+/// - `patterns` - The patterns, relative to the directory
+///
+/// # Returns
+///
+/// The number of removed files
+///
+/// # Errors
+///
+/// If a matching file could not be removed
+pub fn remove_matching(
+    root: &Path,
+    patterns: &[String],
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let patterns: Vec<&String> = patterns
+        .iter()
+        .filter(|p| !p.starts_with('/') && !p.contains("{home}"))
+        .collect();
+    if patterns.is_empty() {
+        return Ok(0);
+    }
+
+    let mut removed = 0;
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                dirs.push(path);
+            } else if let Ok(relative) = path.strip_prefix(root) {
+                let relative = relative.display().to_string();
+                if patterns.iter().any(|p| matches_wildcard(p, &relative)) {
+                    std::fs::remove_file(&path)?;
+                    removed += 1;
+                }
+            }
+        }
+    }
+    Ok(removed)
+}
+
+/// Get the total size of the files in a directory tree, without following symlinks
+///
+/// Entries that cannot be read are skipped.
+///
+/// # Arguments
+///
+/// - `path` - The directory or file
+///
+/// # Returns
+///
+/// The size in bytes, `0` if the path does not exist
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(meta) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+
+    std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| dir_size(&entry.path()))
+                .sum()
+        })
+        .unwrap_or_default()
+}
+
+/// Format a size in bytes for humans
+///
+/// # Arguments
+///
+/// - `bytes` - The size in bytes
+///
+/// # Returns
+///
+/// The size with a binary unit, e.g. `1.5 GiB`
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Parse a size like `500G` or `1.5T`
+///
+/// # Arguments
+///
+/// - `value` - The size with an optional binary unit of `K`, `M`, `G` or `T`
+///
+/// # Returns
+///
+/// The size in bytes, or `None` if the value is invalid
+pub fn parse_size(value: &str) -> Option<u64> {
+    let value = value
+        .trim()
+        .trim_end_matches(['B', 'b'])
+        .trim_end_matches(['i', 'I']);
+    let (amount, factor) = match value.chars().last()? {
+        'K' | 'k' => (&value[..value.len() - 1], 1u64 << 10),
+        'M' | 'm' => (&value[..value.len() - 1], 1 << 20),
+        'G' | 'g' => (&value[..value.len() - 1], 1 << 30),
+        'T' | 't' => (&value[..value.len() - 1], 1 << 40),
+        _ => (value, 1),
+    };
+    let amount: f64 = amount.trim().parse().ok()?;
+    (amount >= 0.0).then_some((amount * factor as f64) as u64)
+}
+
+/// Get the path of the temporary file used to write a file atomically
+///
+/// # Arguments
+///
+/// - `path` - The path to the file
+///
+/// # Returns
+///
+/// The path to the temporary file next to the file
+fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}.tmp", std::process::id()));
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_atomic_replaces_file() {
+        let dir = std::env::temp_dir().join(format!("steamserv-fs-{}", std::process::id()));
+        let path = dir.join("config.toml");
+
+        write_atomic(&path, b"first").unwrap();
+        write_atomic(&path, b"second").unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "second");
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_write_atomic_keeps_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("steamserv-fs-mode-{}", std::process::id()));
+        let path = dir.join("config.toml");
+        let backup = dir.join("config-backup.toml");
+        let mode = |path: &Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+
+        write_atomic(&path, b"first").unwrap();
+        std::fs::set_permissions(&path, Permissions::from_mode(0o600)).unwrap();
+        write_atomic(&path, b"second").unwrap();
+        assert_eq!(mode(&path), 0o600);
+
+        write_atomic_like(&backup, b"second", &path).unwrap();
+        assert_eq!(mode(&backup), 0o600);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_remove_matching() {
+        let dir =
+            std::env::temp_dir().join(format!("steamserv-remove-matching-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("saves")).unwrap();
+        std::fs::write(dir.join("saves/world.db"), "played").unwrap();
+        std::fs::write(dir.join("server.bin"), "binary").unwrap();
+
+        let patterns = ["saves/*.db".to_string(), "{home}/.config/*".to_string()];
+        assert_eq!(remove_matching(&dir, &patterns).unwrap(), 1);
+        assert!(!dir.join("saves/world.db").exists());
+        assert!(dir.join("server.bin").exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("500G"), Some(500 << 30));
+        assert_eq!(parse_size("1.5TiB"), Some(3 << 39));
+        assert_eq!(parse_size("2048"), Some(2048));
+        assert_eq!(parse_size("lots"), None);
+    }
+}
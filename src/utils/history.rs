@@ -0,0 +1,136 @@
+use std::{
+    fs::OpenOptions,
+    io::{BufRead, BufReader, Write},
+    path::PathBuf,
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::utils::Config;
+
+/// An event in the history of a server, e.g. a failed health check or a restart
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HistoryEvent {
+    pub timestamp: DateTime<Utc>,
+    pub target: String,
+    pub kind: String,
+    pub message: String,
+}
+
+impl HistoryEvent {
+    /// Create a new event with the current time
+    ///
+    /// # Arguments
+    ///
+    /// - `target` - The server or instance the event belongs to
+    /// - `kind` - The kind of the event
+    /// - `message` - A description of the event
+    ///
+    /// # Returns
+    ///
+    /// The created event
+    pub fn new(target: &str, kind: &str, message: &str) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            target: target.to_string(),
+            kind: kind.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Get the path to the history file
+///
+/// # Returns
+///
+/// The path to the history file
+///
+/// # Errors
+///
+/// If the steamserv directory could not be found
+fn history_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(Config::data_dir()?.join("history.jsonl"))
+}
+
+/// Append an event to the history
+///
+/// # Arguments
+///
+/// - `event` - The event to record
+///
+/// # Returns
+///
+/// Ok if the event was recorded
+///
+/// # Errors
+///
+/// If the history file could not be written
+pub fn record(event: &HistoryEvent) -> Result<(), Box<dyn std::error::Error>> {
+    let path = history_path()?;
+    std::fs::create_dir_all(path.parent().unwrap())?;
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(event)?)?;
+    Ok(())
+}
+
+/// Load the recorded events
+///
+/// # Arguments
+///
+/// - `target` - Only load the events of this server or instance, a server includes its instances
+///
+/// # Returns
+///
+/// The events in the order they were recorded
+///
+/// # Errors
+///
+/// If the history file could not be read
+pub fn load(target: Option<&str>) -> Result<Vec<HistoryEvent>, Box<dyn std::error::Error>> {
+    let path = history_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let reader = BufReader::new(std::fs::File::open(path)?);
+    let events = reader
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<HistoryEvent>(&line).ok())
+        .filter(|event| target.is_none_or(|t| belongs_to(&event.target, t)))
+        .collect();
+
+    Ok(events)
+}
+
+/// Check if an event target belongs to a server or instance
+///
+/// # Arguments
+///
+/// - `event_target` - The target of the event
+/// - `target` - The server or instance to filter by
+///
+/// # Returns
+///
+/// True if the targets are equal or the event belongs to an instance of the server
+fn belongs_to(event_target: &str, target: &str) -> bool {
+    event_target == target
+        || event_target
+            .strip_prefix(target)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_belongs_to() {
+        assert!(belongs_to("valheim", "valheim"));
+        assert!(belongs_to("valheim/pvp", "valheim"));
+        assert!(!belongs_to("valheim2", "valheim"));
+        assert!(!belongs_to("valheim", "valheim/pvp"));
+    }
+}
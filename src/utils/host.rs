@@ -0,0 +1,79 @@
+use std::path::PathBuf;
+
+/// Architecture SteamCMD and the game servers are built for
+const NATIVE_ARCH: &str = "x86_64";
+/// Emulators that run x86 binaries on other architectures, in the order they are preferred
+///
+/// SteamCMD itself is a 32-bit binary, which FEX-Emu runs as well, while Box64
+/// only runs the 64-bit game servers and needs Box86 next to it.
+const EMULATORS: [&str; 2] = ["FEXInterpreter", "box64"];
+/// Directory of the binary formats registered with the kernel
+const BINFMT_DIR: &str = "/proc/sys/fs/binfmt_misc";
+
+/// Get the architecture of the host
+///
+/// # Returns
+///
+/// The architecture, e.g. `x86_64` or `aarch64`
+pub fn arch() -> &'static str {
+    std::env::consts::ARCH
+}
+
+/// Get the name of the host
+///
+/// # Returns
+///
+/// The host name, `localhost` if it could not be read
+pub fn hostname() -> String {
+    let mut buf = [0u8; 256];
+    // SAFETY: the buffer is valid for its length, the name is NUL-terminated
+    // unless it was truncated, which the length check below handles
+    let result = unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) };
+    if result != 0 {
+        return "localhost".to_string();
+    }
+    let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    String::from_utf8_lossy(&buf[..len]).into_owned()
+}
+
+/// Check if the host runs SteamCMD and the game servers without an emulator
+///
+/// # Returns
+///
+/// True on x86-64 hosts
+pub fn is_native() -> bool {
+    arch() == NATIVE_ARCH
+}
+
+/// Find an emulator for x86 binaries in the `PATH`
+///
+/// # Returns
+///
+/// The path to the preferred emulator, or `None` if none is installed
+pub fn find_emulator() -> Option<PathBuf> {
+    let paths = std::env::var_os("PATH")?;
+    EMULATORS.iter().find_map(|name| {
+        std::env::split_paths(&paths)
+            .map(|dir| dir.join(name))
+            .find(|path| path.is_file())
+    })
+}
+
+/// Find an emulator that is registered with `binfmt_misc`
+///
+/// The kernel then runs x86 binaries with the emulator by itself, so no
+/// wrapper has to be configured.
+///
+/// # Returns
+///
+/// The name of the registered format, e.g. `FEX-x86_64`, or `None` if there is none
+pub fn binfmt_emulator() -> Option<String> {
+    std::fs::read_dir(BINFMT_DIR)
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .find(|name| {
+            let name = name.to_lowercase();
+            name.starts_with("fex") || name.starts_with("box64") || name.starts_with("box86")
+        })
+}
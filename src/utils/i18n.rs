@@ -0,0 +1,216 @@
+use std::sync::OnceLock;
+
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource, FluentValue};
+use unic_langid::LanguageIdentifier;
+
+/// The language used when no other language is selected or a message is missing
+const FALLBACK_LANGUAGE: &str = "en";
+
+/// The bundled translations
+const LOCALES: [(&str, &str); 2] = [
+    ("en", include_str!("../../locales/en/steamserv.ftl")),
+    ("de", include_str!("../../locales/de/steamserv.ftl")),
+];
+
+/// Environment variables checked for the locale, in order of precedence
+const LOCALE_ENV_VARS: [&str; 3] = ["LC_ALL", "LC_MESSAGES", "LANG"];
+
+struct Localizer {
+    bundle: FluentBundle<FluentResource>,
+    fallback: FluentBundle<FluentResource>,
+}
+
+static LOCALIZER: OnceLock<Localizer> = OnceLock::new();
+
+/// Initialize the localization with a language
+///
+/// Without a language the locale is detected from the environment. Has no effect
+/// if the localization was already initialized.
+///
+/// # Arguments
+///
+/// - `language` - The language to use, e.g. `de`
+pub fn init(language: Option<&str>) {
+    let language = language.map(str::to_string).unwrap_or_else(detect_language);
+    let _ = LOCALIZER.set(Localizer::new(&language));
+}
+
+/// Translate a message
+///
+/// # Arguments
+///
+/// - `key` - The message ID
+/// - `args` - The arguments of the message
+///
+/// # Returns
+///
+/// The translated message, or the message ID if no translation exists
+pub fn translate(key: &str, args: &[(&str, String)]) -> String {
+    let localizer = LOCALIZER.get_or_init(|| Localizer::new(&detect_language()));
+
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, FluentValue::from(value.clone()));
+    }
+
+    localizer
+        .format(&localizer.bundle, key, &fluent_args)
+        .or_else(|| localizer.format(&localizer.fallback, key, &fluent_args))
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// Get the list of bundled languages
+///
+/// # Returns
+///
+/// The language codes of all bundled translations
+pub fn available_languages() -> Vec<&'static str> {
+    LOCALES.iter().map(|(language, _)| *language).collect()
+}
+
+/// Detect the language from the locale environment variables
+///
+/// # Returns
+///
+/// The language code, e.g. `de` for `de_DE.UTF-8`
+fn detect_language() -> String {
+    LOCALE_ENV_VARS
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| {
+            value
+                .split(['_', '.', '@'])
+                .next()
+                .map(|language| language.to_lowercase())
+        })
+        .filter(|language| language != "c" && language != "posix")
+        .unwrap_or_else(|| FALLBACK_LANGUAGE.to_string())
+}
+
+impl Localizer {
+    /// Create a localizer for a language
+    ///
+    /// # Arguments
+    ///
+    /// - `language` - The language to use
+    ///
+    /// # Returns
+    ///
+    /// The created localizer, using English if the language is not bundled
+    fn new(language: &str) -> Self {
+        let language = if available_languages().contains(&language) {
+            language
+        } else {
+            FALLBACK_LANGUAGE
+        };
+
+        Self {
+            bundle: Self::create_bundle(language),
+            fallback: Self::create_bundle(FALLBACK_LANGUAGE),
+        }
+    }
+
+    /// Create the message bundle of a language
+    ///
+    /// # Arguments
+    ///
+    /// - `language` - The bundled language
+    ///
+    /// # Returns
+    ///
+    /// The message bundle
+    fn create_bundle(language: &str) -> FluentBundle<FluentResource> {
+        let source = LOCALES
+            .iter()
+            .find(|(l, _)| *l == language)
+            .map(|(_, source)| *source)
+            .unwrap_or_default();
+        let langid: LanguageIdentifier = language.parse().unwrap_or_default();
+
+        let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+        bundle.set_use_isolating(false);
+
+        let resource =
+            FluentResource::try_new(source.to_string()).unwrap_or_else(|(resource, _)| resource);
+        let _ = bundle.add_resource(resource);
+
+        bundle
+    }
+
+    /// Format a message of a bundle
+    ///
+    /// # Arguments
+    ///
+    /// - `bundle` - The bundle containing the message
+    /// - `key` - The message ID
+    /// - `args` - The arguments of the message
+    ///
+    /// # Returns
+    ///
+    /// The formatted message, or `None` if the bundle does not contain it
+    fn format(
+        &self,
+        bundle: &FluentBundle<FluentResource>,
+        key: &str,
+        args: &FluentArgs,
+    ) -> Option<String> {
+        let pattern = bundle.get_message(key)?.value()?;
+        let mut errors = Vec::new();
+        Some(
+            bundle
+                .format_pattern(pattern, Some(args), &mut errors)
+                .to_string(),
+        )
+    }
+}
+
+/// Translate a message with optional arguments
+///
+/// ```ignore
+/// tr!("install-success");
+/// tr!("uninstall-confirm", server = server_name);
+/// ```
+#[macro_export]
+macro_rules! tr {
+    ($key:expr) => {
+        $crate::utils::i18n::translate($key, &[])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::utils::i18n::translate($key, &[$((stringify!($name), $value.to_string())),+])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundles_contain_same_messages() {
+        let ids = |source: &str| {
+            let mut ids: Vec<String> = source
+                .lines()
+                .filter(|line| !line.starts_with([' ', '#']) && line.contains(" ="))
+                .filter_map(|line| line.split(" =").next().map(str::to_string))
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        let (_, english) = LOCALES[0];
+        for (language, source) in LOCALES.iter().skip(1) {
+            assert_eq!(ids(english), ids(source), "messages of {} differ", language);
+        }
+    }
+
+    #[test]
+    fn test_translate_with_args() {
+        let localizer = Localizer::new("de");
+        let mut args = FluentArgs::new();
+        args.set("server", FluentValue::from("Valheim"));
+        let message = localizer
+            .format(&localizer.bundle, "uninstall-confirm", &args)
+            .unwrap();
+        assert!(message.contains("Valheim"));
+    }
+}
@@ -1,5 +1,34 @@
 pub mod config;
+pub mod confirm;
+pub mod disk;
+pub mod download;
+pub mod duration;
+pub mod events;
+pub mod fs;
+pub mod history;
+pub mod host;
+pub mod i18n;
+pub mod network;
+pub mod notify;
+pub mod output;
+pub mod pattern;
+pub mod permissions;
+pub mod phases;
+pub mod privilege;
 pub mod progress;
+pub mod remote;
+pub mod runtime;
+pub mod schedule;
+pub mod secrets;
+pub mod server_ref;
+pub mod shortcuts;
+pub mod sparkline;
+pub mod steamcmd;
+pub mod steamid;
+pub mod store;
+pub mod terminal;
+pub mod timeout;
+pub mod vdf;
 
 use std::{
     io::{BufRead, Write},
@@ -7,8 +36,26 @@ use std::{
     time::Duration,
 };
 
-pub use config::{Config, InstalledServer, ServerCache, ServerInfo};
-pub use progress::{default_spinner, Progress, ProgressStyle};
+pub use config::{
+    Announcement, Blackout, Config, HealthConfig, HealthProbe, InstalledServer, LogAlert,
+    Maintenance, MapRotation, MemoryLimit, MetricsConfig, Pause, RconConfig, RecoveryAction,
+    ServerCache, ServerInfo, ServerInstance, WorkshopMod,
+};
+pub use confirm::{ConfirmConfig, Confirmation};
+pub use disk::DiskConfig;
+pub use history::HistoryEvent;
+pub use network::{DepotCache, DownloadPolicy, NetworkConfig};
+pub use notify::NotificationConfig;
+pub use permissions::PermissionsConfig;
+pub use phases::{Phase, PhaseTracker};
+pub use progress::{default_spinner, Progress, ProgressFormat, ProgressStyle, TransferRate};
+pub use remote::RemoteHost;
+pub use secrets::{KeySource, SecretsConfig};
+pub use server_ref::ServerRef;
+pub use shortcuts::CommandsConfig;
+pub use steamcmd::{Capabilities, OutputLine, SteamCmdError};
+pub use store::{StateStore, StorageBackend};
+pub use timeout::{TimeoutConfig, Watchdog};
 
 /// Run a command with a spinner
 ///
@@ -50,33 +97,52 @@ pub fn run_with_spinner(
 ///
 /// # Returns
 ///
-/// Ok if the command was run successfully
+/// The first error SteamCMD reported in the output, if any
 ///
 /// # Errors
 ///
 /// If the command could not be run
 pub fn run_with_output(
     command: &mut std::process::Child,
-) -> Result<(), Box<dyn std::error::Error>> {
-    println!();
+) -> Result<Option<SteamCmdError>, Box<dyn std::error::Error>> {
+    if !runtime::is_json_progress() {
+        println!();
+    }
+    let mut phases = PhaseTracker::new();
+    let mut error = None;
     if let Some(stdout) = command.stdout.take() {
         let reader = std::io::BufReader::new(stdout);
         for line in reader.lines().map_while(Result::ok) {
-            if line.contains("Redirecting stderr")
-                || line.contains("UpdateUI")
-                || line.contains("ILocalize")
-            {
+            if OutputLine::parse(&line) == Some(OutputLine::Noise) {
                 continue;
             }
 
+            if error.is_none() {
+                error = SteamCmdError::parse(&line);
+            }
+            phases.line(&line);
+            let mut transfer = None;
+            if let Some(event) = events::parse_progress(&line) {
+                transfer = phases.transfer(&event);
+                events::publish(event);
+            }
+            if runtime::is_json_progress() {
+                // Only the events are printed, as JSON lines
+                continue;
+            }
             if line.starts_with('[') {
                 println!("Status: {}", line);
+            } else if let Some(transfer) = transfer {
+                println!("{}  {}", line, transfer);
             } else {
                 println!("{}", line);
             }
             std::io::stdout().flush()?;
         }
     }
-    println!();
-    Ok(())
+    phases.finish();
+    if !runtime::is_json_progress() {
+        println!();
+    }
+    Ok(error)
 }
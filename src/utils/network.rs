@@ -0,0 +1,399 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+
+use crate::tr;
+
+/// Environment variables used to detect a system wide proxy
+const PROXY_ENV_VARS: [&str; 4] = ["HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy"];
+
+/// Age after which a download slot of a host that stopped refreshing it is freed
+const SLOT_STALE: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct NetworkConfig {
+    pub proxy: Option<String>,
+    pub no_proxy: Option<String>,
+    pub ca_certificate: Option<PathBuf>,
+    pub probe_url: Option<String>,
+    /// When the daemon starts installs and updates
+    #[serde(default)]
+    pub downloads: DownloadPolicy,
+    /// Caches that speed up the downloads of SteamCMD
+    #[serde(default)]
+    pub depot_cache: DepotCache,
+}
+
+/// Limits for the downloads of the background jobs of the daemon
+///
+/// Jobs that run SteamCMD stay queued outside of the hours or while all slots
+/// are taken, manual installs and updates are not limited.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct DownloadPolicy {
+    /// Windows on the clock of the host, e.g. `"01:00-07:00"`, any time if empty
+    pub hours: Vec<String>,
+    /// Downloads that run at once on all hosts sharing `slots_dir`
+    pub max_concurrent: Option<u32>,
+    /// Directory with a file per running download, shared by the hosts, e.g. over NFS
+    pub slots_dir: Option<PathBuf>,
+}
+
+impl NetworkConfig {
+    /// Create an HTTP client honoring the proxy and certificate settings
+    ///
+    /// Without a configured proxy the client falls back to the `HTTPS_PROXY`
+    /// and `HTTP_PROXY` environment variables.
+    ///
+    /// # Arguments
+    ///
+    /// - `timeout` - The timeout of a whole request, or `None` for no timeout
+    ///
+    /// # Returns
+    ///
+    /// The created HTTP client
+    ///
+    /// # Errors
+    ///
+    /// If the proxy URL is invalid or the CA certificate could not be read
+    pub fn http_client(
+        &self,
+        timeout: Option<Duration>,
+    ) -> Result<reqwest::Client, Box<dyn std::error::Error>> {
+        Ok(self.client_builder(timeout)?.build()?)
+    }
+
+    /// Create an HTTP client that only sends requests over HTTPS
+    ///
+    /// A redirect to a plain HTTP URL fails, so downloads are never received
+    /// unencrypted.
+    ///
+    /// # Arguments
+    ///
+    /// - `timeout` - The timeout of a whole request, or `None` for no timeout
+    ///
+    /// # Returns
+    ///
+    /// The created HTTP client
+    ///
+    /// # Errors
+    ///
+    /// If the proxy URL is invalid or the CA certificate could not be read
+    pub fn https_client(
+        &self,
+        timeout: Option<Duration>,
+    ) -> Result<reqwest::Client, Box<dyn std::error::Error>> {
+        Ok(self.client_builder(timeout)?.https_only(true).build()?)
+    }
+
+    /// Configure an HTTP client with the proxy and certificate settings
+    ///
+    /// # Arguments
+    ///
+    /// - `timeout` - The timeout of a whole request, or `None` for no timeout
+    ///
+    /// # Returns
+    ///
+    /// The builder of the HTTP client
+    ///
+    /// # Errors
+    ///
+    /// If the proxy URL is invalid or the CA certificate could not be read
+    fn client_builder(
+        &self,
+        timeout: Option<Duration>,
+    ) -> Result<reqwest::ClientBuilder, Box<dyn std::error::Error>> {
+        let mut builder = reqwest::Client::builder()
+            .user_agent(concat!("steamserv/", env!("CARGO_PKG_VERSION")))
+            .connect_timeout(Duration::from_secs(30));
+        if let Some(timeout) = timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        if let Some(proxy) = &self.proxy {
+            let proxy = reqwest::Proxy::all(proxy)?.no_proxy(
+                self.no_proxy
+                    .as_deref()
+                    .and_then(reqwest::NoProxy::from_string),
+            );
+            builder = builder.proxy(proxy);
+        }
+
+        if let Some(path) = &self.ca_certificate {
+            let pem = std::fs::read(path).map_err(|e| {
+                tr!(
+                    "network-ca-unreadable",
+                    path = path.display().to_string(),
+                    error = e
+                )
+            })?;
+            builder = builder.add_root_certificate(reqwest::Certificate::from_pem(&pem)?);
+        }
+
+        Ok(builder)
+    }
+
+    /// Get the proxy that is used for network calls
+    ///
+    /// # Returns
+    ///
+    /// The configured proxy or the proxy from the environment
+    pub fn effective_proxy(&self) -> Option<String> {
+        self.proxy.clone().or_else(|| {
+            PROXY_ENV_VARS
+                .iter()
+                .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+        })
+    }
+
+    /// Get the environment variables to pass the proxy to subprocesses
+    ///
+    /// # Returns
+    ///
+    /// The proxy environment variables, empty if no proxy is configured
+    pub fn proxy_env(&self) -> Vec<(String, String)> {
+        let mut env = Vec::new();
+
+        if let Some(proxy) = &self.proxy {
+            for var in PROXY_ENV_VARS {
+                env.push((var.to_string(), proxy.clone()));
+            }
+            if let Some(no_proxy) = &self.no_proxy {
+                env.push(("NO_PROXY".to_string(), no_proxy.clone()));
+                env.push(("no_proxy".to_string(), no_proxy.clone()));
+            }
+        }
+
+        env
+    }
+}
+
+/// A lancache on the LAN and copies of local installs that save downloads
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct DepotCache {
+    /// Address of the lancache, e.g. `192.168.1.10`
+    pub lancache: Option<String>,
+    /// Hostnames that have to resolve to the lancache, checked and pinned by `lan-cache`
+    pub cdn_hosts: Vec<String>,
+    /// Copy the files of an installed server of the same app and branch before a new install
+    pub seed_from_local: bool,
+}
+
+impl Default for DepotCache {
+    /// SteamCMD uses a lancache when `lancache.steamcontent.com` resolves to it
+    fn default() -> Self {
+        Self {
+            lancache: None,
+            cdn_hosts: vec!["lancache.steamcontent.com".to_string()],
+            seed_from_local: false,
+        }
+    }
+}
+
+impl DownloadPolicy {
+    /// Check if downloads may start at a time
+    ///
+    /// A window may span midnight, e.g. `"22:00-06:00"`.
+    ///
+    /// # Arguments
+    ///
+    /// - `time` - The time on the clock of the host
+    ///
+    /// # Returns
+    ///
+    /// True if there are no windows or the time is in one of them
+    ///
+    /// # Errors
+    ///
+    /// If a window is not in the form `HH:MM-HH:MM`
+    pub fn allows(&self, time: NaiveTime) -> Result<bool, Box<dyn std::error::Error>> {
+        if self.hours.is_empty() {
+            return Ok(true);
+        }
+        for window in &self.hours {
+            let (start, end) = window
+                .split_once('-')
+                .and_then(|(start, end)| {
+                    Some((
+                        NaiveTime::parse_from_str(start.trim(), "%H:%M").ok()?,
+                        NaiveTime::parse_from_str(end.trim(), "%H:%M").ok()?,
+                    ))
+                })
+                .ok_or_else(|| tr!("downloads-invalid-hours", window = window.as_str()))?;
+            let inside = if start <= end {
+                start <= time && time < end
+            } else {
+                time >= start || time < end
+            };
+            if inside {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Take a download slot
+    ///
+    /// Slots that were not refreshed for ten minutes belong to hosts that are
+    /// gone and are freed.
+    ///
+    /// # Arguments
+    ///
+    /// - `name` - The name of the slot, unique across the hosts
+    ///
+    /// # Returns
+    ///
+    /// True if the slot was taken or there is no limit, false if all slots are taken
+    ///
+    /// # Errors
+    ///
+    /// If the slots directory could not be read or written
+    pub fn acquire(&self, name: &str) -> Result<bool, Box<dyn std::error::Error>> {
+        let (Some(max), Some(dir)) = (self.max_concurrent, &self.slots_dir) else {
+            return Ok(true);
+        };
+        std::fs::create_dir_all(dir)?;
+        let mut taken = 0;
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.file_name().is_some_and(|n| n == name) {
+                return Ok(true);
+            }
+            if is_stale(&path) {
+                let _ = std::fs::remove_file(&path);
+            } else {
+                taken += 1;
+            }
+        }
+        if taken >= max {
+            return Ok(false);
+        }
+        std::fs::write(dir.join(name), "")?;
+        Ok(true)
+    }
+
+    /// Mark a taken slot as still in use
+    ///
+    /// # Arguments
+    ///
+    /// - `name` - The name of the slot
+    pub fn refresh(&self, name: &str) {
+        if let Some(dir) = &self.slots_dir {
+            let _ = std::fs::File::options()
+                .append(true)
+                .open(dir.join(name))
+                .and_then(|file| file.set_modified(SystemTime::now()));
+        }
+    }
+
+    /// Free a slot
+    ///
+    /// # Arguments
+    ///
+    /// - `name` - The name of the slot
+    pub fn release(&self, name: &str) {
+        if let Some(dir) = &self.slots_dir {
+            let _ = std::fs::remove_file(dir.join(name));
+        }
+    }
+}
+
+/// Check if a download slot was not refreshed for too long
+///
+/// # Arguments
+///
+/// - `path` - The file of the slot
+///
+/// # Returns
+///
+/// True if the slot is stale
+fn is_stale(path: &Path) -> bool {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age > SLOT_STALE)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_https_client_refuses_http() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!(
+            "http://{}/steamcmd_linux.tar.gz",
+            listener.local_addr().unwrap()
+        );
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = stream.read(&mut [0; 1024]);
+            let _ = stream.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n");
+        });
+
+        let network = NetworkConfig::default();
+        let timeout = Some(Duration::from_secs(5));
+        assert!(network
+            .https_client(timeout)
+            .unwrap()
+            .get(&url)
+            .send()
+            .await
+            .is_err());
+        assert!(network
+            .http_client(timeout)
+            .unwrap()
+            .get(&url)
+            .send()
+            .await
+            .is_ok());
+    }
+
+    #[test]
+    fn test_download_hours() {
+        let time = |s| NaiveTime::parse_from_str(s, "%H:%M").unwrap();
+        let policy = DownloadPolicy {
+            hours: vec!["22:00-06:00".to_string(), "12:00-13:00".to_string()],
+            ..Default::default()
+        };
+        assert!(policy.allows(time("23:30")).unwrap());
+        assert!(policy.allows(time("05:59")).unwrap());
+        assert!(policy.allows(time("12:30")).unwrap());
+        assert!(!policy.allows(time("06:00")).unwrap());
+        assert!(!policy.allows(time("20:00")).unwrap());
+        assert!(DownloadPolicy::default().allows(time("20:00")).unwrap());
+
+        let invalid = DownloadPolicy {
+            hours: vec!["night".to_string()],
+            ..Default::default()
+        };
+        assert!(invalid.allows(time("20:00")).is_err());
+    }
+
+    #[test]
+    fn test_download_slots() {
+        let dir = std::env::temp_dir().join(format!("steamserv-slots-{}", std::process::id()));
+        let policy = DownloadPolicy {
+            max_concurrent: Some(1),
+            slots_dir: Some(dir.clone()),
+            ..Default::default()
+        };
+        assert!(policy.acquire("host-a-1").unwrap());
+        assert!(policy.acquire("host-a-1").unwrap());
+        assert!(!policy.acquire("host-b-7").unwrap());
+        policy.release("host-a-1");
+        assert!(policy.acquire("host-b-7").unwrap());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::utils::{Config, TimeoutConfig};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct NotificationConfig {
+    pub webhook_url: Option<String>,
+    /// Notify about dedicated servers that are new in the server cache
+    #[serde(default)]
+    pub new_servers: bool,
+    /// Kinds of events sent to the webhook, e.g. `server_crashed` or `update_finished`
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+/// Send a notification to the configured webhook
+///
+/// The message is sent as `text` and `content` so Slack, Mattermost and Discord
+/// compatible webhooks can display it.
+///
+/// # Arguments
+///
+/// - `config` - The configuration
+/// - `message` - The message to send
+///
+/// # Returns
+///
+/// Ok if the notification was sent or no webhook is configured
+///
+/// # Errors
+///
+/// If the webhook could not be reached or returned an error
+pub async fn notify(config: &Config, message: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(url) = &config.notifications.webhook_url else {
+        return Ok(());
+    };
+    if config.is_offline() {
+        return Ok(());
+    }
+
+    let client = config
+        .network
+        .http_client(TimeoutConfig::limit(config.timeouts.network))?;
+    client
+        .post(url)
+        .json(&json!({ "text": message, "content": message }))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(())
+}
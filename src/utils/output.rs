@@ -0,0 +1,293 @@
+use clap::ValueEnum;
+use serde_json::{Map, Value};
+
+use crate::utils::terminal;
+
+/// Format of tabular command output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned columns for the terminal
+    #[default]
+    Table,
+    /// An array of objects for scripts
+    Json,
+    /// Comma separated values for spreadsheets
+    Csv,
+    /// A Markdown table for wikis and documentation
+    Markdown,
+}
+
+/// A column of a table
+pub struct Column {
+    /// The stable name of the column, used as the key in JSON and CSV
+    pub key: &'static str,
+    /// The translated title, used in the terminal and in Markdown
+    pub title: String,
+    /// The width of the column in the terminal
+    pub width: usize,
+}
+
+impl Column {
+    /// Create a column
+    ///
+    /// # Arguments
+    ///
+    /// - `key` - The stable name of the column
+    /// - `title` - The translated title
+    /// - `width` - The width of the column in the terminal
+    ///
+    /// # Returns
+    ///
+    /// The column
+    pub fn new(key: &'static str, title: String, width: usize) -> Self {
+        Self { key, title, width }
+    }
+}
+
+/// Rows of command output that can be rendered in every output format
+pub struct Table {
+    columns: Vec<Column>,
+    rows: Vec<Vec<Value>>,
+}
+
+impl Table {
+    /// Create an empty table
+    ///
+    /// # Arguments
+    ///
+    /// - `columns` - The columns of the table
+    ///
+    /// # Returns
+    ///
+    /// The table without rows
+    pub fn new(columns: Vec<Column>) -> Self {
+        Self {
+            columns,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Add a row
+    ///
+    /// # Arguments
+    ///
+    /// - `row` - The values in the order of the columns
+    pub fn push(&mut self, row: Vec<Value>) {
+        self.rows.push(row);
+    }
+
+    /// Render the table
+    ///
+    /// # Arguments
+    ///
+    /// - `format` - The output format
+    ///
+    /// # Returns
+    ///
+    /// The rendered table
+    ///
+    /// # Errors
+    ///
+    /// If the table could not be serialized as JSON
+    pub fn render(&self, format: OutputFormat) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(match format {
+            OutputFormat::Table => self.render_table(),
+            OutputFormat::Json => self.render_json()?,
+            OutputFormat::Csv => self.render_csv(),
+            OutputFormat::Markdown => self.render_markdown(),
+        })
+    }
+
+    /// Render the table as aligned columns
+    ///
+    /// On a terminal the columns are shrunk to its width and longer cells are
+    /// cut, so lines do not wrap. Piped output keeps the full cells.
+    ///
+    /// # Returns
+    ///
+    /// The header, a separator and the rows
+    fn render_table(&self) -> String {
+        let widths: Vec<usize> = self.columns.iter().map(|c| c.width).collect();
+        let available = terminal::width();
+        let widths = match available {
+            Some(available) => terminal::fit_widths(&widths, available),
+            None => widths,
+        };
+        let line = |cells: Vec<String>| {
+            widths
+                .iter()
+                .zip(cells)
+                .map(|(width, cell)| {
+                    let cell = match available {
+                        Some(_) => terminal::truncate(&cell, *width),
+                        None => cell,
+                    };
+                    format!("{:<width$}", cell, width = width)
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+                .trim_end()
+                .to_string()
+        };
+
+        let mut lines = vec![line(self.columns.iter().map(|c| c.title.clone()).collect())];
+        let width: usize = widths.iter().map(|width| width + 1).sum();
+        lines.push("-".repeat(width.saturating_sub(1)));
+        lines.extend(
+            self.rows
+                .iter()
+                .map(|row| line(row.iter().map(text).collect())),
+        );
+        lines.join("\n")
+    }
+
+    /// Render the table as a JSON array of objects
+    ///
+    /// # Returns
+    ///
+    /// The pretty printed JSON
+    ///
+    /// # Errors
+    ///
+    /// If the rows could not be serialized
+    fn render_json(&self) -> Result<String, Box<dyn std::error::Error>> {
+        let objects: Vec<Map<String, Value>> = self
+            .rows
+            .iter()
+            .map(|row| {
+                self.columns
+                    .iter()
+                    .zip(row)
+                    .map(|(column, value)| (column.key.to_string(), value.clone()))
+                    .collect()
+            })
+            .collect();
+        Ok(serde_json::to_string_pretty(&objects)?)
+    }
+
+    /// Render the table as CSV with the column keys as header
+    ///
+    /// # Returns
+    ///
+    /// The CSV with one line per row
+    fn render_csv(&self) -> String {
+        let line = |cells: Vec<String>| {
+            cells
+                .iter()
+                .map(|cell| csv_field(cell))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+
+        let mut lines = vec![line(
+            self.columns.iter().map(|c| c.key.to_string()).collect(),
+        )];
+        lines.extend(
+            self.rows
+                .iter()
+                .map(|row| line(row.iter().map(text).collect())),
+        );
+        lines.join("\n")
+    }
+
+    /// Render the table as a Markdown table
+    ///
+    /// # Returns
+    ///
+    /// The Markdown table with the column titles as header
+    fn render_markdown(&self) -> String {
+        let line = |cells: Vec<String>| {
+            let cells: Vec<String> = cells
+                .iter()
+                .map(|cell| cell.replace('|', "\\|").replace('\n', " "))
+                .collect();
+            format!("| {} |", cells.join(" | "))
+        };
+
+        let mut lines = vec![
+            line(self.columns.iter().map(|c| c.title.clone()).collect()),
+            format!("|{}", " --- |".repeat(self.columns.len())),
+        ];
+        lines.extend(
+            self.rows
+                .iter()
+                .map(|row| line(row.iter().map(text).collect())),
+        );
+        lines.join("\n")
+    }
+}
+
+/// Get the text of a value, strings without quotes
+///
+/// # Arguments
+///
+/// - `value` - The value
+///
+/// # Returns
+///
+/// The text of the value, empty for `null`
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break
+///
+/// # Arguments
+///
+/// - `field` - The field
+///
+/// # Returns
+///
+/// The field, quoted if needed
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn table() -> Table {
+        let mut table = Table::new(vec![
+            Column::new("app_id", "App ID".to_string(), 10),
+            Column::new("name", "Name".to_string(), 20),
+        ]);
+        table.push(vec![json!(896660), json!("Valheim")]);
+        table.push(vec![json!(2394010), json!("Pal, \"World\" | Server")]);
+        table
+    }
+
+    #[test]
+    fn test_render_csv() {
+        assert_eq!(
+            table().render(OutputFormat::Csv).unwrap(),
+            "app_id,name\n896660,Valheim\n2394010,\"Pal, \"\"World\"\" | Server\""
+        );
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        assert_eq!(
+            table().render(OutputFormat::Markdown).unwrap(),
+            "| App ID | Name |\n| --- | --- |\n| 896660 | Valheim |\n| 2394010 | Pal, \"World\" \\| Server |"
+        );
+    }
+
+    #[test]
+    fn test_render_json() {
+        let json: Value =
+            serde_json::from_str(&table().render(OutputFormat::Json).unwrap()).unwrap();
+        assert_eq!(json[0]["app_id"], 896660);
+        assert_eq!(json[1]["name"], "Pal, \"World\" | Server");
+    }
+}
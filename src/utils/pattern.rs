@@ -0,0 +1,52 @@
+/// Check if a file name matches a wildcard pattern
+///
+/// Supports `*` for any number of characters and `?` for a single character.
+///
+/// # Arguments
+///
+/// - `pattern` - The wildcard pattern
+/// - `name` - The file name to check
+///
+/// # Returns
+///
+/// True if the name matches the pattern
+pub fn matches_wildcard(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            n = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_wildcard() {
+        assert!(matches_wildcard("*.bsp", "de_dust2.bsp"));
+        assert!(matches_wildcard("*", "anything"));
+        assert!(matches_wildcard("map_?.nav", "map_1.nav"));
+        assert!(matches_wildcard("*_*.gma", "addon_123.gma"));
+        assert!(!matches_wildcard("*.bsp", "de_dust2.nav"));
+        assert!(!matches_wildcard("map_?.nav", "map_10.nav"));
+    }
+}
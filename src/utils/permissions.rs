@@ -0,0 +1,211 @@
+use std::{
+    ffi::CString,
+    fs::Permissions,
+    os::unix::fs::{MetadataExt, PermissionsExt},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::GameProfile,
+    tr,
+    utils::privilege::{self, Escalation},
+};
+
+/// Ownership and permissions of the install trees
+///
+/// SteamCMD often leaves world-writable files and launch binaries without the
+/// executable bit, which breaks the start under a service manager.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct PermissionsConfig {
+    /// Normalize the install tree after every install and update
+    pub normalize: bool,
+    /// The user the servers run as, the install tree is handed over to it
+    pub service_user: Option<String>,
+}
+
+/// Normalize the install tree of a server
+///
+/// World-writable bits are removed, the launch binary of the game profile is made
+/// executable and, with a service user, the tree is handed over to that user.
+/// Changes outside the rights of the current user go through the `--sudo` escalation.
+///
+/// # Arguments
+///
+/// - `config` - The permission settings
+/// - `path` - The install path of the server
+/// - `app_id` - The app ID of the server, to find the launch binary
+///
+/// # Returns
+///
+/// The number of files and directories that were changed directly
+///
+/// # Errors
+///
+/// If the service user does not exist, the tree could not be read or a step
+/// needs root without `--sudo`
+pub fn normalize(
+    config: &PermissionsConfig,
+    path: &Path,
+    app_id: u32,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let owner = config
+        .service_user
+        .as_deref()
+        .map(lookup_user)
+        .transpose()?;
+    let executable = GameProfile::find(app_id).map(|p| path.join(p.executable));
+
+    let mut entries = Vec::new();
+    walk(path, &mut entries)?;
+
+    let root = privilege::is_root();
+    // SAFETY: geteuid has no memory safety requirements and never fails
+    let euid = unsafe { libc::geteuid() };
+
+    let mut changed = 0;
+    let mut foreign_modes = false;
+    let mut foreign_owner = false;
+    for entry in &entries {
+        let meta = std::fs::symlink_metadata(entry)?;
+        if meta.file_type().is_symlink() {
+            continue;
+        }
+
+        let is_executable = executable.as_deref() == Some(entry.as_path());
+        let mode = normalized_mode(meta.mode(), is_executable);
+        if mode != meta.mode() & 0o7777 {
+            if root || meta.uid() == euid {
+                std::fs::set_permissions(entry, Permissions::from_mode(mode))?;
+                changed += 1;
+            } else {
+                foreign_modes = true;
+            }
+        }
+
+        if let Some((uid, gid)) = owner {
+            if meta.uid() != uid || meta.gid() != gid {
+                if root {
+                    std::os::unix::fs::lchown(entry, Some(uid), Some(gid))?;
+                    changed += 1;
+                } else {
+                    foreign_owner = true;
+                }
+            }
+        }
+    }
+
+    let mut steps = Vec::new();
+    if foreign_modes {
+        steps.push(Escalation {
+            reason: tr!("perms-step-modes", path = path.display()),
+            command: vec![
+                "chmod".to_string(),
+                "-R".to_string(),
+                "o-w".to_string(),
+                path.display().to_string(),
+            ],
+        });
+    }
+    if let (true, Some(user)) = (foreign_owner, &config.service_user) {
+        steps.push(Escalation {
+            reason: tr!(
+                "perms-step-owner",
+                path = path.display(),
+                user = user.as_str()
+            ),
+            command: vec![
+                "chown".to_string(),
+                "-R".to_string(),
+                format!("{}:", user),
+                path.display().to_string(),
+            ],
+        });
+    }
+    privilege::escalate(&steps)?;
+
+    Ok(changed)
+}
+
+/// Get the normalized mode of a file or directory
+///
+/// # Arguments
+///
+/// - `mode` - The current mode
+/// - `executable` - Whether the file is the launch binary
+///
+/// # Returns
+///
+/// The mode without the world-writable bit, executable for everyone who can read
+/// the launch binary
+fn normalized_mode(mode: u32, executable: bool) -> u32 {
+    let mut mode = mode & 0o7777 & !0o002;
+    if executable {
+        mode |= (mode & 0o444) >> 2;
+    }
+    mode
+}
+
+/// Collect a path and everything below it, without following symlinks
+///
+/// # Arguments
+///
+/// - `path` - The path to start at
+/// - `entries` - The collected paths
+///
+/// # Returns
+///
+/// Ok if the tree could be read
+///
+/// # Errors
+///
+/// If a directory could not be read
+fn walk(path: &Path, entries: &mut Vec<PathBuf>) -> Result<(), Box<dyn std::error::Error>> {
+    entries.push(path.to_path_buf());
+    if std::fs::symlink_metadata(path)?.is_dir() {
+        for entry in std::fs::read_dir(path)? {
+            walk(&entry?.path(), entries)?;
+        }
+    }
+    Ok(())
+}
+
+/// Look up the user and group ID of a user
+///
+/// # Arguments
+///
+/// - `name` - The name of the user
+///
+/// # Returns
+///
+/// The user ID and the ID of the primary group
+///
+/// # Errors
+///
+/// If the user does not exist
+fn lookup_user(name: &str) -> Result<(u32, u32), Box<dyn std::error::Error>> {
+    let user = CString::new(name)?;
+    // SAFETY: the name is a valid NUL terminated string that outlives the call
+    let entry = unsafe { libc::getpwnam(user.as_ptr()) };
+    if entry.is_null() {
+        return Err(tr!("perms-user-not-found", user = name).into());
+    }
+    // SAFETY: the entry is not null and points to the static buffer of getpwnam
+    let (uid, gid) = unsafe { ((*entry).pw_uid, (*entry).pw_gid) };
+    Ok((uid, gid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalized_mode() {
+        assert_eq!(normalized_mode(0o100666, false), 0o664);
+        assert_eq!(normalized_mode(0o40777, false), 0o775);
+        assert_eq!(normalized_mode(0o100644, true), 0o755);
+        assert_eq!(normalized_mode(0o100640, true), 0o750);
+    }
+}
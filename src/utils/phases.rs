@@ -0,0 +1,181 @@
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    tr,
+    utils::{
+        duration,
+        events::{self, Event},
+        fs,
+        progress::TransferRate,
+        steamcmd::OutputLine,
+    },
+};
+
+/// A phase of a SteamCMD run, recognized from its output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    /// SteamCMD updates itself
+    SelfUpdate,
+    Login,
+    /// The space of the files is allocated on the disk
+    Preallocate,
+    Download,
+    /// The downloaded or installed files are checked
+    Validate,
+    /// The downloaded files are moved into the install
+    Commit,
+}
+
+impl Phase {
+    /// Recognize the phase a line of the SteamCMD output belongs to
+    ///
+    /// # Arguments
+    ///
+    /// - `line` - The line
+    ///
+    /// # Returns
+    ///
+    /// The phase the line starts, or `None` if the line does not start a phase
+    pub fn parse(line: &str) -> Option<Self> {
+        match OutputLine::parse(line) {
+            Some(OutputLine::SelfUpdate) => return Some(Self::SelfUpdate),
+            Some(OutputLine::Login) => return Some(Self::Login),
+            _ => {}
+        }
+
+        let Some(Event::ProgressUpdated { state, .. }) = events::parse_progress(line) else {
+            return None;
+        };
+        match state.as_str() {
+            "preallocating" => Some(Self::Preallocate),
+            "downloading" => Some(Self::Download),
+            "committing" => Some(Self::Commit),
+            state if state.starts_with("verifying") => Some(Self::Validate),
+            _ => None,
+        }
+    }
+
+    /// Get the translated name of the phase
+    ///
+    /// # Returns
+    ///
+    /// The name
+    pub fn name(&self) -> String {
+        match self {
+            Self::SelfUpdate => tr!("phase-self-update"),
+            Self::Login => tr!("phase-login"),
+            Self::Preallocate => tr!("phase-preallocate"),
+            Self::Download => tr!("phase-download"),
+            Self::Validate => tr!("phase-validate"),
+            Self::Commit => tr!("phase-commit"),
+        }
+    }
+}
+
+/// Tracker of the phases of a SteamCMD run
+///
+/// Publishes an event when a phase starts and when it is finished, with the
+/// time it took.
+#[derive(Debug, Default)]
+pub struct PhaseTracker {
+    current: Option<(Phase, Instant)>,
+    rate: TransferRate,
+}
+
+impl PhaseTracker {
+    /// Create a tracker without a phase
+    ///
+    /// # Returns
+    ///
+    /// The tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Follow a line of the SteamCMD output
+    ///
+    /// # Arguments
+    ///
+    /// - `line` - The line
+    pub fn line(&mut self, line: &str) {
+        let Some(phase) = Phase::parse(line) else {
+            return;
+        };
+        if self.current.is_some_and(|(current, _)| current == phase) {
+            return;
+        }
+        self.finish();
+        events::publish(Event::PhaseStarted { phase });
+        self.current = Some((phase, Instant::now()));
+        self.rate = TransferRate::default();
+    }
+
+    /// Follow the progress of the download phase
+    ///
+    /// # Arguments
+    ///
+    /// - `event` - The progress parsed from the SteamCMD output
+    ///
+    /// # Returns
+    ///
+    /// The transfer rate and the remaining time, e.g. `3.1 MiB/s  ETA 0:12`, or
+    /// `None` outside of the download or before the rate is known
+    pub fn transfer(&mut self, event: &Event) -> Option<String> {
+        let Event::ProgressUpdated { current, total, .. } = event else {
+            return None;
+        };
+        if !self
+            .current
+            .is_some_and(|(phase, _)| phase == Phase::Download)
+        {
+            return None;
+        }
+
+        self.rate.sample(*current, Instant::now());
+        let rate = self.rate.bytes_per_second()?;
+        let mut transfer = format!("{}/s", fs::format_size(rate as u64));
+        if let Some(eta) = self.rate.eta(*current, *total) {
+            transfer.push_str(&format!("  ETA {}", duration::format_clock(eta)));
+        }
+        Some(transfer)
+    }
+
+    /// Finish the current phase at the end of the output
+    pub fn finish(&mut self) {
+        if let Some((phase, started)) = self.current.take() {
+            events::publish(Event::PhaseFinished {
+                phase,
+                seconds: started.elapsed().as_secs_f64(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_phase() {
+        assert_eq!(
+            Phase::parse("[  0%] Checking for available updates..."),
+            Some(Phase::SelfUpdate)
+        );
+        assert_eq!(
+            Phase::parse("Connecting anonymously to Steam Public...OK"),
+            Some(Phase::Login)
+        );
+        assert_eq!(
+            Phase::parse(" Update state (0x61) downloading, progress: 45.23 (1234 / 5678)"),
+            Some(Phase::Download)
+        );
+        assert_eq!(
+            Phase::parse(" Update state (0x5) verifying install, progress: 34.00 (34 / 100)"),
+            Some(Phase::Validate)
+        );
+        assert_eq!(Phase::parse("Success! App '896660' fully installed."), None);
+    }
+}
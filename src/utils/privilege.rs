@@ -0,0 +1,241 @@
+use std::{ffi::CString, fmt, os::unix::ffi::OsStrExt, path::Path, process::Command};
+
+use crate::{tr, utils::runtime};
+
+/// A single step that needs elevated rights, with the exact command that performs it
+///
+/// steamserv never runs as a whole as root. Steps that need root are collected
+/// up front, shown to the user and only run through `sudo` with `--sudo`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Escalation {
+    pub reason: String,
+    pub command: Vec<String>,
+}
+
+impl Escalation {
+    /// Create a directory owned by the current user, or hand an existing one over
+    ///
+    /// # Arguments
+    ///
+    /// - `path` - The directory to create
+    ///
+    /// # Returns
+    ///
+    /// The step that creates the directory with `install -d`
+    pub fn create_dir(path: &Path) -> Self {
+        // SAFETY: getuid and getgid have no memory safety requirements and never fail
+        let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+        Self {
+            reason: tr!("privilege-create-dir", path = path.display()),
+            command: vec![
+                "install".to_string(),
+                "-d".to_string(),
+                "-o".to_string(),
+                uid.to_string(),
+                "-g".to_string(),
+                gid.to_string(),
+                path.display().to_string(),
+            ],
+        }
+    }
+
+    /// Add entries to `/etc/hosts`
+    ///
+    /// # Arguments
+    ///
+    /// - `address` - The address the hostnames resolve to
+    /// - `hosts` - The hostnames
+    ///
+    /// # Returns
+    ///
+    /// The step that appends a line per hostname
+    pub fn append_hosts(address: &str, hosts: &[&str]) -> Self {
+        let mut command = vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "printf '%s\\n' \"$@\" >> /etc/hosts".to_string(),
+            "sh".to_string(),
+        ];
+        command.extend(hosts.iter().map(|host| format!("{} {}", address, host)));
+        Self {
+            reason: tr!("privilege-append-hosts", count = hosts.len()),
+            command,
+        }
+    }
+
+    /// Run the step through `sudo`
+    ///
+    /// # Returns
+    ///
+    /// Ok if the command succeeded
+    ///
+    /// # Errors
+    ///
+    /// If `sudo` could not be started or the command failed
+    fn run(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let status = Command::new("sudo")
+            .arg("--")
+            .args(&self.command)
+            .status()
+            .map_err(|e| tr!("privilege-sudo-failed", error = e.to_string()))?;
+        if !status.success() {
+            return Err(tr!("privilege-step-failed", command = self.to_string()).into());
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Escalation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "sudo {}", self.command.join(" "))
+    }
+}
+
+/// Check if steamserv runs as root
+///
+/// # Returns
+///
+/// True if the effective user is root
+pub fn is_root() -> bool {
+    // SAFETY: geteuid has no memory safety requirements and never fails
+    unsafe { libc::geteuid() == 0 }
+}
+
+/// Get the names of the user running steamserv
+///
+/// Besides the real user, the user that ran `sudo` is included, so a user
+/// keeps their role when they switch to the service user.
+///
+/// # Returns
+///
+/// The user names, empty if none could be found
+pub fn user_names() -> Vec<String> {
+    let mut names = Vec::new();
+    // SAFETY: getuid never fails, getpwuid returns null or the static buffer
+    // of the entry, which is copied before the next call
+    unsafe {
+        let entry = libc::getpwuid(libc::getuid());
+        if !entry.is_null() {
+            let name = std::ffi::CStr::from_ptr((*entry).pw_name);
+            names.push(name.to_string_lossy().to_string());
+        }
+    }
+    if let Ok(name) = std::env::var("SUDO_USER") {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Check if the current user can create or write a path
+///
+/// For a path that does not exist yet, the nearest existing parent decides.
+///
+/// # Arguments
+///
+/// - `path` - The path to check
+///
+/// # Returns
+///
+/// True if the path can be written without elevated rights
+pub fn is_writable(path: &Path) -> bool {
+    let Some(existing) = path.ancestors().find(|p| p.exists()) else {
+        return false;
+    };
+    let Ok(path) = CString::new(existing.as_os_str().as_bytes()) else {
+        return false;
+    };
+    // SAFETY: the path is a valid NUL terminated string that outlives the call
+    unsafe { libc::access(path.as_ptr(), libc::W_OK) == 0 }
+}
+
+/// Get the step that creates a directory, if it cannot be created without root
+///
+/// # Arguments
+///
+/// - `path` - The directory
+///
+/// # Returns
+///
+/// The step, or `None` if the current user can create or write the directory
+pub fn dir_escalation(path: &Path) -> Option<Escalation> {
+    if is_root() || is_writable(path) {
+        return None;
+    }
+    Some(Escalation::create_dir(path))
+}
+
+/// Run the steps that need elevated rights, or explain them and stop
+///
+/// Without `--sudo`, every step is printed with its exact command and an error
+/// is returned before anything was changed. With `--sudo`, only these steps are
+/// run through `sudo`.
+///
+/// # Arguments
+///
+/// - `steps` - The steps that need elevated rights
+///
+/// # Returns
+///
+/// Ok if there is nothing to escalate or all steps succeeded
+///
+/// # Errors
+///
+/// If steps need root and `--sudo` was not given, or a step failed
+pub fn escalate(steps: &[Escalation]) -> Result<(), Box<dyn std::error::Error>> {
+    if steps.is_empty() {
+        return Ok(());
+    }
+
+    println!("{}", tr!("privilege-needed", count = steps.len()));
+    for step in steps {
+        println!("  {}", step);
+        println!("    {}", step.reason);
+    }
+
+    if !runtime::is_sudo() {
+        return Err(tr!("privilege-required").into());
+    }
+
+    for step in steps {
+        step.run()?;
+    }
+    Ok(())
+}
+
+/// Make sure directories exist, creating the ones outside the user's rights with `--sudo`
+///
+/// # Arguments
+///
+/// - `paths` - The directories
+///
+/// # Returns
+///
+/// Ok if all directories that need root exist afterwards
+///
+/// # Errors
+///
+/// If a directory needs root and `--sudo` was not given, or it could not be created
+pub fn ensure_dirs(paths: &[&Path]) -> Result<(), Box<dyn std::error::Error>> {
+    let steps: Vec<Escalation> = paths.iter().filter_map(|p| dir_escalation(p)).collect();
+    escalate(&steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_dir_command() {
+        let step = Escalation::create_dir(Path::new("/opt/steamserv"));
+        assert!(step.to_string().starts_with("sudo install -d -o "));
+        assert!(step.to_string().ends_with(" /opt/steamserv"));
+    }
+
+    #[test]
+    fn test_writable_dir_needs_no_escalation() {
+        let dir = std::env::temp_dir().join("steamserv-privilege-test");
+        assert!(dir_escalation(&dir.join("server")).is_none());
+    }
+}
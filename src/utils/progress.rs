@@ -1,15 +1,44 @@
-use std::io::{self, Write};
+use std::{
+    io::{self, Write},
+    time::{Duration, Instant},
+};
 
-#[derive(Clone, Debug)]
-pub enum ProgressStyle {
-    Bar,
-    Spinner { states: Vec<char> },
+use clap::ValueEnum;
+
+use crate::utils::{
+    duration,
+    events::{self, Event},
+    fs, runtime, terminal,
+};
+
+/// Percentage steps at which a plain progress line is printed
+const PLAIN_PERCENT_STEP: usize = 10;
+/// Interval between two plain progress lines of a spinner
+const PLAIN_SPINNER_INTERVAL: Duration = Duration::from_secs(15);
+/// Minimum interval between two samples of the transfer rate
+const RATE_INTERVAL: Duration = Duration::from_millis(500);
+/// Weight of the newest sample in the smoothed transfer rate
+const RATE_SMOOTHING: f64 = 0.3;
+/// Widest and narrowest bar in characters
+const BAR_WIDTH: (usize, usize) = (50, 10);
+
+/// Format of the progress of long operations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ProgressFormat {
+    /// Progress bars, spinners and messages for the terminal
+    #[default]
+    Text,
+    /// One JSON line per progress update and event, without other progress output
+    Json,
 }
 
-impl Default for ProgressStyle {
-    fn default() -> Self {
-        Self::Bar
-    }
+#[derive(Clone, Debug, Default)]
+pub enum ProgressStyle {
+    #[default]
+    Bar,
+    Spinner {
+        states: Vec<char>,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -18,6 +47,19 @@ pub struct Progress {
     pub total: usize,
     pub message: String,
     pub style: ProgressStyle,
+    pub plain: bool,
+    /// The transferred and the total bytes of a download, if known
+    pub bytes: Option<(u64, Option<u64>)>,
+    rate: TransferRate,
+    last_plain_percent: Option<usize>,
+    last_plain_render: Option<Instant>,
+}
+
+/// Smoothed transfer rate of a download, for the speed and the remaining time
+#[derive(Clone, Debug, Default)]
+pub struct TransferRate {
+    last_sample: Option<(Instant, u64)>,
+    bytes_per_second: Option<f64>,
 }
 
 impl Progress {
@@ -37,11 +79,17 @@ impl Progress {
     ///
     /// If the progress bar could not be created due to an IO error
     pub fn new(total: usize, message: &str, style: ProgressStyle) -> Result<Self, std::io::Error> {
+        terminal::watch_resize();
         let progress = Self {
             current: 0,
             total,
             message: message.to_string(),
             style,
+            plain: runtime::is_plain(),
+            bytes: None,
+            rate: TransferRate::default(),
+            last_plain_percent: None,
+            last_plain_render: None,
         };
 
         Ok(progress)
@@ -66,6 +114,54 @@ impl Progress {
         Ok(())
     }
 
+    /// Update the progress bar with the transferred bytes of a download
+    ///
+    /// The percentage is calculated from the bytes, and the transfer rate and
+    /// the remaining time are shown next to the bar.
+    ///
+    /// # Arguments
+    ///
+    /// - `done` - The bytes transferred so far
+    /// - `total` - The size of the download, if it is known
+    ///
+    /// # Returns
+    ///
+    /// Ok if the progress bar was updated successfully
+    ///
+    /// # Errors
+    ///
+    /// If the progress bar could not be updated due to an IO error
+    pub fn update_bytes(&mut self, done: u64, total: Option<u64>) -> Result<(), std::io::Error> {
+        self.bytes = Some((done, total));
+        self.rate.sample(done, Instant::now());
+        let percent = total
+            .and_then(|total| (done * 100).checked_div(total))
+            .unwrap_or(0)
+            .min(100);
+        self.update(percent as usize * self.total / 100)
+    }
+
+    /// Describe the transferred bytes, the rate and the remaining time
+    ///
+    /// # Returns
+    ///
+    /// The transfer, e.g. `12.0 MiB / 48.0 MiB  3.1 MiB/s  ETA 0:12`, or `None`
+    /// if the progress is not a download
+    fn transfer(&self) -> Option<String> {
+        let (done, total) = self.bytes?;
+        let mut transfer = match total {
+            Some(total) => format!("{} / {}", fs::format_size(done), fs::format_size(total)),
+            None => fs::format_size(done),
+        };
+        if let Some(rate) = self.rate.bytes_per_second() {
+            transfer.push_str(&format!("  {}/s", fs::format_size(rate as u64)));
+        }
+        if let Some(eta) = total.and_then(|total| self.rate.eta(done, total)) {
+            transfer.push_str(&format!("  ETA {}", duration::format_clock(eta)));
+        }
+        Some(transfer)
+    }
+
     /// Finish the progress bar
     ///
     /// # Returns
@@ -76,7 +172,14 @@ impl Progress {
     ///
     /// If the progress bar could not be finished due to an IO error
     pub fn finish(&self) -> Result<(), std::io::Error> {
-        println!("\n{} - Complete!", self.message);
+        if runtime::is_json_progress() {
+            return Ok(());
+        }
+        if self.plain {
+            println!("{} - Complete!", self.message);
+        } else {
+            println!("\n{} - Complete!", self.message);
+        }
         Ok(())
     }
 
@@ -89,7 +192,15 @@ impl Progress {
     /// # Errors
     ///
     /// If the progress bar could not be rendered due to an IO error
-    pub fn render(&self) -> Result<(), std::io::Error> {
+    pub fn render(&mut self) -> Result<(), std::io::Error> {
+        if runtime::is_json_progress() {
+            self.render_json();
+            return Ok(());
+        }
+        if self.plain {
+            return self.render_plain();
+        }
+
         match &self.style {
             ProgressStyle::Bar => self.render_bar()?,
             ProgressStyle::Spinner { states } => self.render_spinner(states)?,
@@ -98,6 +209,77 @@ impl Progress {
         Ok(())
     }
 
+    /// Publish the progress of a bar as an event whenever its percentage changes
+    ///
+    /// Spinners have no percentage and are left out.
+    fn render_json(&mut self) {
+        if !matches!(self.style, ProgressStyle::Bar) {
+            return;
+        }
+        let percent = (self.current * 100)
+            .checked_div(self.total)
+            .unwrap_or(0)
+            .min(100);
+        if self.last_plain_percent == Some(percent) {
+            return;
+        }
+        self.last_plain_percent = Some(percent);
+
+        let (current, total) = match self.bytes {
+            Some((done, total)) => (done, total.unwrap_or(0)),
+            None => (self.current as u64, self.total as u64),
+        };
+        events::publish(Event::ProgressUpdated {
+            state: self.message.clone(),
+            percent: percent as f64,
+            current,
+            total,
+        });
+    }
+
+    /// Render the progress as plain text lines
+    ///
+    /// Bars print a line every 10 percent, spinners print a line at the start
+    /// and then periodically while they are running.
+    ///
+    /// # Returns
+    ///
+    /// Ok if the progress was rendered successfully
+    ///
+    /// # Errors
+    ///
+    /// If the progress could not be rendered due to an IO error
+    fn render_plain(&mut self) -> Result<(), std::io::Error> {
+        match &self.style {
+            ProgressStyle::Bar => {
+                let percent = (self.current * 100)
+                    .checked_div(self.total)
+                    .unwrap_or(0)
+                    .min(100);
+                let step = percent / PLAIN_PERCENT_STEP * PLAIN_PERCENT_STEP;
+                if self.last_plain_percent.is_none_or(|last| step > last) {
+                    match self.transfer() {
+                        Some(transfer) => println!("{}: {}% ({})", self.message, step, transfer),
+                        None => println!("{}: {}%", self.message, step),
+                    }
+                    self.last_plain_percent = Some(step);
+                }
+            }
+            ProgressStyle::Spinner { .. } => {
+                if self
+                    .last_plain_render
+                    .is_none_or(|last| last.elapsed() >= PLAIN_SPINNER_INTERVAL)
+                {
+                    println!("{}...", self.message);
+                    self.last_plain_render = Some(Instant::now());
+                }
+            }
+        }
+        io::stdout().flush()?;
+
+        Ok(())
+    }
+
     /// Render a progress bar
     ///
     /// # Returns
@@ -109,10 +291,29 @@ impl Progress {
     /// If the progress bar could not be rendered due to an IO error
     fn render_bar(&self) -> Result<(), std::io::Error> {
         let progress = (self.current as f64 / self.total as f64) * 100.0;
-        let width = 50;
+        let transfer = self.transfer();
+        let suffix = match &transfer {
+            Some(transfer) => format!("] {:.1}%  {}", progress, transfer),
+            None => format!("] {:.1}%", progress),
+        };
+
+        // The bar takes the room the message and the suffix leave on the line
+        let columns = terminal::width().unwrap_or(terminal::DEFAULT_WIDTH);
+        let text = self.message.chars().count() + 2 + suffix.chars().count();
+        let width = columns
+            .saturating_sub(text + 1)
+            .clamp(BAR_WIDTH.1, BAR_WIDTH.0);
         let filled = (width as f64 * (self.current as f64 / self.total as f64)) as usize;
+        let message = terminal::truncate(
+            &self.message,
+            columns.saturating_sub(width + suffix.chars().count() + 3),
+        );
 
-        print!("\r{} [", self.message);
+        if terminal::take_resized() {
+            // The old line may have wrapped, the bar continues on a new one
+            println!();
+        }
+        print!("\r\x1b[2K{} [", message);
         for i in 0..width {
             if i <= filled {
                 print!("=");
@@ -120,7 +321,7 @@ impl Progress {
                 print!(" ");
             }
         }
-        print!("] {:.1}%", progress);
+        print!("{}", suffix);
         io::stdout().flush().unwrap();
 
         Ok(())
@@ -141,21 +342,93 @@ impl Progress {
     /// If the spinner could not be rendered due to an IO error
     fn render_spinner(&self, states: &[char]) -> Result<(), std::io::Error> {
         let state = states[self.current % states.len()];
-        print!("\r{} {}", state, self.message);
+        let columns = terminal::width().unwrap_or(terminal::DEFAULT_WIDTH);
+        let message = terminal::truncate(&self.message, columns.saturating_sub(3));
+        print!("\r\x1b[2K{} {}", state, message);
         io::stdout().flush().unwrap();
 
         Ok(())
     }
 
+    /// Advance the spinner by one state
+    ///
+    /// # Returns
+    ///
+    /// Ok if the spinner was advanced successfully
+    ///
+    /// # Errors
+    ///
+    /// If the spinner could not be rendered due to an IO error
     pub fn tick(&mut self) -> Result<(), std::io::Error> {
-        if let ProgressStyle::Spinner { states } = &self.style {
+        if let ProgressStyle::Spinner { .. } = &self.style {
             self.current += 1;
-            self.render_spinner(states)?;
+            self.render()?;
         }
         Ok(())
     }
 }
 
+impl TransferRate {
+    /// Add a sample of the transferred bytes
+    ///
+    /// Samples closer than [`RATE_INTERVAL`] to the previous one are skipped, so
+    /// the rate does not jump with every chunk.
+    ///
+    /// # Arguments
+    ///
+    /// - `done` - The bytes transferred so far
+    /// - `now` - The time of the sample
+    pub fn sample(&mut self, done: u64, now: Instant) {
+        let Some((last_time, last_done)) = self.last_sample else {
+            self.last_sample = Some((now, done));
+            return;
+        };
+        let elapsed = now.duration_since(last_time);
+        if elapsed < RATE_INTERVAL {
+            return;
+        }
+        if done < last_done {
+            // The download was restarted
+            *self = Self::default();
+            self.last_sample = Some((now, done));
+            return;
+        }
+
+        let rate = (done - last_done) as f64 / elapsed.as_secs_f64();
+        self.bytes_per_second = Some(match self.bytes_per_second {
+            Some(smoothed) => smoothed + RATE_SMOOTHING * (rate - smoothed),
+            None => rate,
+        });
+        self.last_sample = Some((now, done));
+    }
+
+    /// Get the smoothed transfer rate
+    ///
+    /// # Returns
+    ///
+    /// The bytes per second, or `None` before the second sample
+    pub fn bytes_per_second(&self) -> Option<f64> {
+        self.bytes_per_second
+    }
+
+    /// Estimate the remaining time of the transfer
+    ///
+    /// # Arguments
+    ///
+    /// - `done` - The bytes transferred so far
+    /// - `total` - The size of the transfer
+    ///
+    /// # Returns
+    ///
+    /// The remaining time, or `None` if the rate is not known or zero
+    pub fn eta(&self, done: u64, total: u64) -> Option<Duration> {
+        let rate = self.bytes_per_second.filter(|rate| *rate > 0.0)?;
+        Some(Duration::from_secs_f64(
+            total.saturating_sub(done) as f64 / rate,
+        ))
+    }
+}
+
 /// Get the default spinner style
 ///
 /// # Returns
@@ -170,3 +443,26 @@ pub fn default_spinner() -> Result<ProgressStyle, std::io::Error> {
         states: vec!['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'],
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_rate() {
+        let start = Instant::now();
+        let mut rate = TransferRate::default();
+        rate.sample(0, start);
+        assert_eq!(rate.bytes_per_second(), None);
+
+        rate.sample(1000, start + Duration::from_secs(1));
+        assert_eq!(rate.bytes_per_second(), Some(1000.0));
+        // Too close to the previous sample
+        rate.sample(5000, start + Duration::from_millis(1100));
+        assert_eq!(rate.bytes_per_second(), Some(1000.0));
+
+        rate.sample(3000, start + Duration::from_secs(2));
+        assert_eq!(rate.bytes_per_second(), Some(1300.0));
+        assert_eq!(rate.eta(3000, 16000), Some(Duration::from_secs(10)));
+    }
+}
@@ -0,0 +1,303 @@
+use std::{
+    io::Write,
+    path::Path,
+    process::{Command, ExitStatus, Stdio},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::tr;
+
+/// A Linux host that runs the game servers, managed over SSH
+///
+/// The commands run with the steamserv binary and the config of the host.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RemoteHost {
+    /// The SSH destination, e.g. `steam@eu1.example.com`
+    pub ssh: String,
+    pub port: Option<u16>,
+    /// The steamserv binary on the host
+    #[serde(default = "default_binary")]
+    pub binary: String,
+}
+
+fn default_binary() -> String {
+    "steamserv-rs".to_string()
+}
+
+impl RemoteHost {
+    /// Build the SSH command that runs steamserv on the host
+    ///
+    /// A terminal is requested, so prompts and progress bars work like locally.
+    ///
+    /// # Arguments
+    ///
+    /// - `args` - The arguments of steamserv
+    ///
+    /// # Returns
+    ///
+    /// The SSH command
+    pub fn command(&self, args: &[String]) -> Command {
+        self.ssh_command(args, true)
+    }
+
+    /// Build the SSH command that runs steamserv on the host
+    ///
+    /// # Arguments
+    ///
+    /// - `args` - The arguments of steamserv
+    /// - `tty` - Request a terminal
+    ///
+    /// # Returns
+    ///
+    /// The SSH command
+    fn ssh_command(&self, args: &[String], tty: bool) -> Command {
+        let mut command = Command::new("ssh");
+        if tty {
+            command.arg("-t");
+        }
+        command.args(self.port_args());
+        // SSH joins the arguments into one command line for the remote shell
+        let remote = std::iter::once(self.binary.as_str())
+            .chain(args.iter().map(String::as_str))
+            .map(quote)
+            .collect::<Vec<_>>()
+            .join(" ");
+        command.arg(&self.ssh).arg("--").arg(remote);
+        command
+    }
+
+    /// Run steamserv on the host
+    ///
+    /// # Arguments
+    ///
+    /// - `args` - The arguments of steamserv
+    ///
+    /// # Returns
+    ///
+    /// The exit status of the remote command
+    ///
+    /// # Errors
+    ///
+    /// If SSH could not be started
+    pub fn run(&self, args: &[String]) -> Result<ExitStatus, Box<dyn std::error::Error>> {
+        self.command(args)
+            .status()
+            .map_err(|e| tr!("remote-ssh-failed", host = self.ssh.as_str(), error = e).into())
+    }
+
+    /// Run steamserv on the host with the input on stdin
+    ///
+    /// No terminal is requested, as it would swallow the input.
+    ///
+    /// # Arguments
+    ///
+    /// - `args` - The arguments of steamserv
+    /// - `input` - The input of the remote command
+    ///
+    /// # Returns
+    ///
+    /// The exit status of the remote command
+    ///
+    /// # Errors
+    ///
+    /// If SSH could not be started
+    pub fn run_with_input(
+        &self,
+        args: &[String],
+        input: &str,
+    ) -> Result<ExitStatus, Box<dyn std::error::Error>> {
+        let mut child = self
+            .ssh_command(args, false)
+            .stdin(Stdio::piped())
+            .spawn()
+            .map_err(|e| tr!("remote-ssh-failed", host = self.ssh.as_str(), error = e))?;
+        child
+            .stdin
+            .take()
+            .ok_or("stdin of ssh is not piped")?
+            .write_all(input.as_bytes())?;
+        Ok(child.wait()?)
+    }
+
+    /// Build the rsync command that copies a directory to the host
+    ///
+    /// The target directory is created, files already in it are kept so a
+    /// wrong path on the host does not wipe it.
+    ///
+    /// # Arguments
+    ///
+    /// - `source` - The local directory
+    /// - `target` - The directory on the host
+    ///
+    /// # Returns
+    ///
+    /// The rsync command
+    pub fn sync_command(&self, source: &Path, target: &Path) -> Command {
+        let mut command = Command::new("rsync");
+        // The remote path is passed as is instead of to the shell of the host
+        command.args(["-a", "--mkpath", "--protect-args", "--info=progress2"]);
+        if self.port.is_some() {
+            command
+                .arg("-e")
+                .arg(format!("ssh {}", self.port_args().join(" ")));
+        }
+        command.arg(format!("{}/", source.display())).arg(format!(
+            "{}:{}/",
+            self.ssh,
+            target.display()
+        ));
+        command
+    }
+
+    /// Copy a directory to the host
+    ///
+    /// # Arguments
+    ///
+    /// - `source` - The local directory
+    /// - `target` - The directory on the host
+    ///
+    /// # Errors
+    ///
+    /// If rsync could not be started or failed
+    pub fn sync(&self, source: &Path, target: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let status = self
+            .sync_command(source, target)
+            .status()
+            .map_err(|e| tr!("remote-rsync-failed", host = self.ssh.as_str(), error = e))?;
+        if !status.success() {
+            return Err(tr!(
+                "remote-rsync-failed",
+                host = self.ssh.as_str(),
+                error = status
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Get the option that sets the SSH port
+    ///
+    /// # Returns
+    ///
+    /// The option and the port, empty for the default port
+    fn port_args(&self) -> Vec<String> {
+        self.port
+            .map(|port| vec!["-p".to_string(), port.to_string()])
+            .unwrap_or_default()
+    }
+}
+
+/// Check if game servers can run on this host
+///
+/// SteamCMD and the game servers only run on Linux, on other systems steamserv
+/// only manages remote hosts.
+///
+/// # Returns
+///
+/// True on Linux
+pub fn is_local_supported() -> bool {
+    cfg!(target_os = "linux")
+}
+
+/// Remove the option that selects the remote host from the arguments
+///
+/// # Arguments
+///
+/// - `args` - The arguments of steamserv, without the program name
+///
+/// # Returns
+///
+/// The arguments to run on the remote host
+pub fn forwarded_args(args: &[String]) -> Vec<String> {
+    let mut forwarded = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--remote" {
+            args.next();
+        } else if !arg.starts_with("--remote=") {
+            forwarded.push(arg.clone());
+        }
+    }
+    forwarded
+}
+
+/// Quote an argument for a POSIX shell
+///
+/// # Arguments
+///
+/// - `arg` - The argument
+///
+/// # Returns
+///
+/// The argument unchanged if it is safe, otherwise in single quotes
+fn quote(arg: &str) -> String {
+    let safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@,+%".contains(c));
+    if safe {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remote_command() {
+        let host = RemoteHost {
+            ssh: "steam@eu1".to_string(),
+            port: Some(2222),
+            binary: default_binary(),
+        };
+        let args = forwarded_args(&[
+            "--remote".to_string(),
+            "eu1".to_string(),
+            "rcon".to_string(),
+            "broadcast".to_string(),
+            "cs2".to_string(),
+            "It's restart time".to_string(),
+        ]);
+        let command = host.command(&args);
+        let args: Vec<_> = command.get_args().map(|a| a.to_string_lossy()).collect();
+        assert_eq!(
+            args,
+            [
+                "-t",
+                "-p",
+                "2222",
+                "steam@eu1",
+                "--",
+                "steamserv-rs rcon broadcast cs2 'It'\\''s restart time'"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sync_command() {
+        let host = RemoteHost {
+            ssh: "steam@eu1".to_string(),
+            port: Some(2222),
+            binary: default_binary(),
+        };
+        let command = host.sync_command(Path::new("/opt/cs2"), Path::new("/srv/game servers/cs2"));
+        let args: Vec<_> = command.get_args().map(|a| a.to_string_lossy()).collect();
+        assert_eq!(
+            args,
+            [
+                "-a",
+                "--mkpath",
+                "--protect-args",
+                "--info=progress2",
+                "-e",
+                "ssh -p 2222",
+                "/opt/cs2/",
+                "steam@eu1:/srv/game servers/cs2/"
+            ]
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+static PLAIN: AtomicBool = AtomicBool::new(false);
+static JSON_PROGRESS: AtomicBool = AtomicBool::new(false);
+static SUDO: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable the offline mode for the current process
+///
+/// # Arguments
+///
+/// - `offline` - True if all network calls should be forbidden
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+/// Check if the offline mode was enabled for the current process
+///
+/// # Returns
+///
+/// True if the offline mode was enabled on the command line
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+/// Enable or disable the plain output mode for the current process
+///
+/// # Arguments
+///
+/// - `plain` - True if progress should be printed as plain text lines
+pub fn set_plain(plain: bool) {
+    PLAIN.store(plain, Ordering::Relaxed);
+}
+
+/// Check if the plain output mode is enabled
+///
+/// # Returns
+///
+/// True if spinners and redrawn progress bars are disabled
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+/// Enable or disable the progress as JSON lines for the current process
+///
+/// # Arguments
+///
+/// - `json` - True if progress and events should be printed as JSON lines
+pub fn set_json_progress(json: bool) {
+    JSON_PROGRESS.store(json, Ordering::Relaxed);
+}
+
+/// Check if the progress is printed as JSON lines
+///
+/// # Returns
+///
+/// True if `--progress json` was given on the command line
+pub fn is_json_progress() -> bool {
+    JSON_PROGRESS.load(Ordering::Relaxed)
+}
+
+/// Enable or disable the escalation of single steps through `sudo`
+///
+/// # Arguments
+///
+/// - `sudo` - True if steps that need root may be run through `sudo`
+pub fn set_sudo(sudo: bool) {
+    SUDO.store(sudo, Ordering::Relaxed);
+}
+
+/// Check if steps that need root may be run through `sudo`
+///
+/// # Returns
+///
+/// True if `--sudo` was given on the command line
+pub fn is_sudo() -> bool {
+    SUDO.load(Ordering::Relaxed)
+}
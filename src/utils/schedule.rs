@@ -0,0 +1,260 @@
+use chrono::{DateTime, Datelike, Duration, LocalResult, Offset, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+
+/// The bits of a schedule that runs every hour
+const EVERY_HOUR: u64 = (1 << 24) - 1;
+
+/// A cron-like schedule with the fields minute, hour, day of month, month and day of week
+///
+/// Every field is `*`, a value, a range like `1-5` or a list like `0,30`, each
+/// optionally with a step like `*/15`. The day of week is `0` to `7`, where both
+/// `0` and `7` are Sunday. `@hourly`, `@daily` and `@weekly` are shortcuts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl Schedule {
+    /// Parse a schedule
+    ///
+    /// # Arguments
+    ///
+    /// - `expression` - The five fields separated by spaces, e.g. `*/30 * * * *`
+    ///
+    /// # Returns
+    ///
+    /// The schedule, or `None` if the expression is invalid
+    pub fn parse(expression: &str) -> Option<Self> {
+        let expression = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            expression => expression,
+        };
+
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minutes, hours, days, months, weekdays] = fields.as_slice() else {
+            return None;
+        };
+
+        let mut weekday_bits = parse_field(weekdays, 0, 7)?;
+        // Sunday can be written as 0 or 7
+        if weekday_bits & (1 << 7) != 0 {
+            weekday_bits |= 1;
+        }
+
+        Some(Self {
+            minutes: parse_field(minutes, 0, 59)?,
+            hours: parse_field(hours, 0, 23)?,
+            days: parse_field(days, 1, 31)?,
+            months: parse_field(months, 1, 12)?,
+            weekdays: weekday_bits,
+            any_day: *days == "*",
+            any_weekday: *weekdays == "*",
+        })
+    }
+
+    /// Check if the schedule is due at a point in time
+    ///
+    /// Like cron, a day matches the day of month or the day of week if both are restricted.
+    ///
+    /// # Arguments
+    ///
+    /// - `time` - The point in time, only the minute is compared
+    ///
+    /// # Returns
+    ///
+    /// True if the schedule is due in the minute of the point in time
+    pub fn matches(&self, time: &(impl Datelike + Timelike)) -> bool {
+        let bit = |bits: u64, value: u32| bits & (1 << value) != 0;
+
+        let day = bit(self.days, time.day());
+        let weekday = bit(self.weekdays, time.weekday().num_days_from_sunday());
+        let day_matches = match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            _ => day && weekday,
+        };
+
+        bit(self.minutes, time.minute())
+            && bit(self.hours, time.hour())
+            && bit(self.months, time.month())
+            && day_matches
+    }
+}
+
+impl Schedule {
+    /// Check if the schedule is due in a minute on the clock of a time zone
+    ///
+    /// Daylight saving time is handled like cron does: times the clock skips
+    /// when it springs forward are due in the first minute after the change, and
+    /// times the clock repeats when it falls back are only due once. Schedules
+    /// that run every hour are not caught up and keep running in the repeated hour.
+    ///
+    /// # Arguments
+    ///
+    /// - `minute` - The minute, the seconds are ignored
+    /// - `zone` - The time zone, `None` for the one of the system
+    ///
+    /// # Returns
+    ///
+    /// True if the schedule is due in the minute
+    pub fn is_due(&self, minute: DateTime<Utc>, zone: Option<Tz>) -> bool {
+        match zone {
+            Some(zone) => self.is_due_in(minute, &zone),
+            None => self.is_due_in(minute, &chrono::Local),
+        }
+    }
+
+    /// Check if the schedule is due in a minute on the clock of a time zone
+    ///
+    /// # Arguments
+    ///
+    /// - `minute` - The minute, the seconds are ignored
+    /// - `zone` - The time zone
+    ///
+    /// # Returns
+    ///
+    /// True if the schedule is due in the minute
+    fn is_due_in<Z: TimeZone>(&self, minute: DateTime<Utc>, zone: &Z) -> bool {
+        let time = minute.with_timezone(zone);
+        let local = time.naive_local();
+        let every_hour = self.hours == EVERY_HOUR;
+
+        // When the clock falls back, the first of the two times has the larger offset
+        if let LocalResult::Ambiguous(first, _) = zone.from_local_datetime(&local) {
+            if !every_hour && first.offset().fix() != time.offset().fix() {
+                return false;
+            }
+        }
+        if self.matches(&local) {
+            return true;
+        }
+        if every_hour {
+            return false;
+        }
+
+        // When the clock springs forward, the skipped times are caught up
+        let mut skipped = (minute - Duration::minutes(1))
+            .with_timezone(zone)
+            .naive_local()
+            + Duration::minutes(1);
+        while skipped < local {
+            if self.matches(&skipped) {
+                return true;
+            }
+            skipped += Duration::minutes(1);
+        }
+        false
+    }
+}
+
+/// Parse a field of a schedule
+///
+/// # Arguments
+///
+/// - `field` - The field, e.g. `*`, `5`, `1-5`, `*/15` or `0,30`
+/// - `min` - The smallest valid value
+/// - `max` - The largest valid value
+///
+/// # Returns
+///
+/// The matching values as bits, or `None` if the field is invalid
+fn parse_field(field: &str, min: u32, max: u32) -> Option<u64> {
+    let mut bits = 0;
+
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (range, step.parse().ok().filter(|s| *s > 0)?),
+            None => (item, 1),
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            range => match range.split_once('-') {
+                Some((start, end)) => (start.parse().ok()?, end.parse().ok()?),
+                None => {
+                    let value = range.parse().ok()?;
+                    // `5/10` means every 10 starting at 5
+                    (value, if item.contains('/') { max } else { value })
+                }
+            },
+        };
+        if start < min || end > max || start > end {
+            return None;
+        }
+
+        for value in (start..=end).step_by(step) {
+            bits |= 1 << value;
+        }
+    }
+
+    Some(bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        // 2024-06-03 is a Monday
+        Utc.with_ymd_and_hms(2024, 6, day, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_schedule() {
+        let every_half_hour = Schedule::parse("*/30 * * * *").unwrap();
+        assert!(every_half_hour.matches(&at(3, 10, 30)));
+        assert!(!every_half_hour.matches(&at(3, 10, 15)));
+
+        let weekdays = Schedule::parse("0 18 * * 1-5").unwrap();
+        assert!(weekdays.matches(&at(3, 18, 0)));
+        assert!(!weekdays.matches(&at(9, 18, 0)));
+
+        let sunday = Schedule::parse("@weekly").unwrap();
+        assert!(sunday.matches(&at(9, 0, 0)));
+        assert_eq!(Schedule::parse("0 0 * * 7"), Schedule::parse("0 0 * * 0,7"));
+
+        let first_or_monday = Schedule::parse("0 12 1 * 1").unwrap();
+        assert!(first_or_monday.matches(&at(1, 12, 0)));
+        assert!(first_or_monday.matches(&at(3, 12, 0)));
+        assert!(!first_or_monday.matches(&at(4, 12, 0)));
+
+        assert_eq!(Schedule::parse("60 * * * *"), None);
+        assert_eq!(Schedule::parse("* * *"), None);
+        assert_eq!(Schedule::parse("*/0 * * * *"), None);
+    }
+
+    #[test]
+    fn test_schedule_daylight_saving() {
+        let berlin = Some(chrono_tz::Europe::Berlin);
+        let utc = |month: u32, day: u32, hour: u32, minute: u32| {
+            Utc.with_ymd_and_hms(2024, month, day, hour, minute, 0)
+                .unwrap()
+        };
+
+        // 2024-03-31 02:00 CET springs forward to 03:00 CEST
+        let skipped = Schedule::parse("30 2 * * *").unwrap();
+        assert!(skipped.is_due(utc(3, 30, 1, 30), berlin));
+        assert!(skipped.is_due(utc(3, 31, 1, 0), berlin));
+        assert!(!skipped.is_due(utc(3, 31, 1, 30), berlin));
+        let hourly = Schedule::parse("30 * * * *").unwrap();
+        assert!(!hourly.is_due(utc(3, 31, 1, 0), berlin));
+        assert!(hourly.is_due(utc(3, 31, 1, 30), berlin));
+
+        // 2024-10-27 03:00 CEST falls back to 02:00 CET
+        assert!(skipped.is_due(utc(10, 27, 0, 30), berlin));
+        assert!(!skipped.is_due(utc(10, 27, 1, 30), berlin));
+        assert!(hourly.is_due(utc(10, 27, 0, 30), berlin));
+        assert!(hourly.is_due(utc(10, 27, 1, 30), berlin));
+
+        // The same schedule runs at different times in other time zones
+        let evening = Schedule::parse("0 20 * * *").unwrap();
+        assert!(evening.is_due(utc(6, 3, 18, 0), berlin));
+        assert!(evening.is_due(utc(6, 4, 0, 0), Some(chrono_tz::America::New_York)));
+    }
+}
@@ -0,0 +1,501 @@
+use std::{collections::BTreeMap, sync::Mutex};
+
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use inquire::Password;
+use serde::{Deserialize, Serialize};
+
+use crate::{tr, utils::Config};
+
+/// Prefix of encrypted values in the config
+const PREFIX: &str = "enc:v1:";
+
+/// Service name of the config key in the OS keyring
+const KEYRING_SERVICE: &str = "steamserv";
+
+/// User name of the config key in the OS keyring
+const KEYRING_USER: &str = "config-key";
+
+/// Start of a reference to a named secret in the launch settings, e.g. `${secret:rcon_password}`
+const REFERENCE_PREFIX: &str = "${secret:";
+
+/// Environment variable with the passphrase, for the supervisor and scripts
+pub const PASSPHRASE_ENV: &str = "STEAMSERV_PASSPHRASE";
+
+/// Key of the current process, so the keyring or passphrase is only asked once
+static KEY: Mutex<Option<Key>> = Mutex::new(None);
+
+/// Encrypted values the config was loaded with by their plaintext, so saving an
+/// unchanged value keeps its ciphertext
+static LOADED: Mutex<BTreeMap<String, String>> = Mutex::new(BTreeMap::new());
+
+/// Encryption of the RCON passwords, login tokens, API key and webhook URL in the config
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SecretsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub key: KeySource,
+    pub salt: Option<String>,
+    /// Named secrets that launch settings reference as `${secret:name}`
+    #[serde(default)]
+    pub values: BTreeMap<String, String>,
+}
+
+/// Where the key to encrypt the secrets comes from
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum KeySource {
+    #[default]
+    Keyring,
+    Passphrase,
+}
+
+/// Check if a config value is encrypted
+///
+/// # Arguments
+///
+/// - `value` - The config value
+///
+/// # Returns
+///
+/// True if the value was encrypted by steamserv
+pub fn is_encrypted(value: &str) -> bool {
+    value.starts_with(PREFIX)
+}
+
+/// Encrypt a config value
+///
+/// Every value gets a random 96-bit nonce from the OS, stored in front of the
+/// ciphertext.
+///
+/// # Arguments
+///
+/// - `key` - The key
+/// - `plaintext` - The value to encrypt
+///
+/// # Returns
+///
+/// The encrypted value with the `enc:v1:` prefix
+///
+/// # Errors
+///
+/// If the value could not be encrypted
+pub fn encrypt(key: &Key, plaintext: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let ciphertext = ChaCha20Poly1305::new(key)
+        .encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|_| tr!("secrets-encrypt-failed"))?;
+
+    let mut data = nonce.to_vec();
+    data.extend(ciphertext);
+    Ok(format!("{}{}", PREFIX, STANDARD.encode(data)))
+}
+
+/// Decrypt a config value
+///
+/// # Arguments
+///
+/// - `key` - The key
+/// - `value` - The encrypted value with the `enc:v1:` prefix
+///
+/// # Returns
+///
+/// The plaintext
+///
+/// # Errors
+///
+/// If the value is malformed or was encrypted with another key
+pub fn decrypt(key: &Key, value: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let data = value
+        .strip_prefix(PREFIX)
+        .and_then(|data| STANDARD.decode(data).ok())
+        .filter(|data| data.len() > 12)
+        .ok_or_else(|| tr!("secrets-value-invalid"))?;
+
+    let (nonce, ciphertext) = data.split_at(12);
+    let plaintext = ChaCha20Poly1305::new(key)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| tr!("secrets-decrypt-failed"))?;
+
+    Ok(String::from_utf8(plaintext)?)
+}
+
+/// Get the key of the config, asking the keyring or for the passphrase on first use
+///
+/// # Arguments
+///
+/// - `config` - The secrets settings
+///
+/// # Returns
+///
+/// The key
+///
+/// # Errors
+///
+/// If the keyring is not available or the passphrase could not be read
+pub fn key(config: &SecretsConfig) -> Result<Key, Box<dyn std::error::Error>> {
+    if let Some(key) = *KEY.lock().unwrap() {
+        return Ok(key);
+    }
+
+    let key = match config.key {
+        KeySource::Keyring => keyring_key()?,
+        KeySource::Passphrase => {
+            let passphrase = match std::env::var(PASSPHRASE_ENV) {
+                Ok(passphrase) => passphrase,
+                Err(_) => Password::new(&tr!("secrets-passphrase"))
+                    .without_confirmation()
+                    .prompt()?,
+            };
+            let salt = config
+                .salt
+                .as_deref()
+                .ok_or_else(|| tr!("secrets-salt-missing"))?;
+            derive_key(&passphrase, salt)?
+        }
+    };
+
+    *KEY.lock().unwrap() = Some(key);
+    Ok(key)
+}
+
+/// Use a passphrase for the rest of the process, e.g. right after it was chosen
+///
+/// # Arguments
+///
+/// - `passphrase` - The passphrase
+/// - `salt` - The salt from the secrets settings
+///
+/// # Returns
+///
+/// Ok if the key was derived
+///
+/// # Errors
+///
+/// If the salt is invalid
+pub fn unlock(passphrase: &str, salt: &str) -> Result<(), Box<dyn std::error::Error>> {
+    *KEY.lock().unwrap() = Some(derive_key(passphrase, salt)?);
+    Ok(())
+}
+
+/// Forget the key of the process, e.g. before switching to another key source
+pub fn forget_key() {
+    *KEY.lock().unwrap() = None;
+}
+
+/// Create a new random salt for a passphrase
+///
+/// # Returns
+///
+/// The base64 encoded salt
+pub fn new_salt() -> String {
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+    STANDARD.encode(salt)
+}
+
+/// Derive a key from a passphrase with Argon2
+///
+/// # Arguments
+///
+/// - `passphrase` - The passphrase
+/// - `salt` - The base64 encoded salt
+///
+/// # Returns
+///
+/// The key
+///
+/// # Errors
+///
+/// If the salt is invalid
+fn derive_key(passphrase: &str, salt: &str) -> Result<Key, Box<dyn std::error::Error>> {
+    let salt = STANDARD.decode(salt)?;
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+        .map_err(|e| e.to_string())?;
+    Ok(key)
+}
+
+/// Load the key from the OS keyring, creating it on first use
+///
+/// # Returns
+///
+/// The key
+///
+/// # Errors
+///
+/// If the keyring is not available
+fn keyring_key() -> Result<Key, Box<dyn std::error::Error>> {
+    let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)?;
+
+    match entry.get_password() {
+        Ok(encoded) => {
+            let key = STANDARD.decode(encoded)?;
+            if key.len() != 32 {
+                return Err(tr!("secrets-keyring-invalid").into());
+            }
+            Ok(*Key::from_slice(&key))
+        }
+        Err(keyring::Error::NoEntry) => {
+            let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+            entry.set_password(&STANDARD.encode(key))?;
+            Ok(key)
+        }
+        Err(e) => Err(tr!("secrets-keyring-unavailable", error = e.to_string()).into()),
+    }
+}
+
+/// Encrypt all secrets of a config that are not encrypted yet
+///
+/// # Arguments
+///
+/// - `config` - The config
+///
+/// # Returns
+///
+/// Ok if the secrets were encrypted
+///
+/// # Errors
+///
+/// If the key is not available
+pub fn encrypt_config(config: &mut Config) -> Result<(), Box<dyn std::error::Error>> {
+    let settings = config.secrets.clone();
+    let mut key_cache = None;
+
+    for value in config.secret_fields_mut() {
+        if is_encrypted(value) {
+            continue;
+        }
+        let key = match key_cache {
+            Some(key) => key,
+            None => *key_cache.insert(key(&settings)?),
+        };
+        *value = encrypt_loaded(&key, value)?;
+    }
+
+    Ok(())
+}
+
+/// Decrypt all encrypted secrets of a config
+///
+/// The key is only needed if the config contains encrypted values.
+///
+/// # Arguments
+///
+/// - `config` - The config
+///
+/// # Returns
+///
+/// Ok if the secrets were decrypted
+///
+/// # Errors
+///
+/// If the key is not available or a value could not be decrypted
+pub fn decrypt_config(config: &mut Config) -> Result<(), Box<dyn std::error::Error>> {
+    let settings = config.secrets.clone();
+    let mut key_cache = None;
+
+    for value in config.secret_fields_mut() {
+        if !is_encrypted(value) {
+            continue;
+        }
+        let key = match key_cache {
+            Some(key) => key,
+            None => *key_cache.insert(key(&settings)?),
+        };
+        *value = decrypt_loaded(&key, value)?;
+    }
+
+    Ok(())
+}
+
+/// Decrypt a config value and remember its ciphertext for the next save
+///
+/// # Arguments
+///
+/// - `key` - The key
+/// - `value` - The encrypted value with the `enc:v1:` prefix
+///
+/// # Returns
+///
+/// The plaintext
+///
+/// # Errors
+///
+/// If the value is malformed or was encrypted with another key
+fn decrypt_loaded(key: &Key, value: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let plaintext = decrypt(key, value)?;
+    LOADED
+        .lock()
+        .unwrap()
+        .insert(plaintext.clone(), value.to_string());
+    Ok(plaintext)
+}
+
+/// Encrypt a config value, keeping the ciphertext it was loaded with
+///
+/// With random nonces an unchanged value would encrypt to a new text on every
+/// save, so the loaded ciphertext is reused while it still decrypts to the value.
+///
+/// # Arguments
+///
+/// - `key` - The key
+/// - `plaintext` - The value to encrypt
+///
+/// # Returns
+///
+/// The encrypted value with the `enc:v1:` prefix
+///
+/// # Errors
+///
+/// If the value could not be encrypted
+fn encrypt_loaded(key: &Key, plaintext: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let loaded = LOADED.lock().unwrap().get(plaintext).cloned();
+    match loaded {
+        Some(value) if decrypt(key, &value).is_ok_and(|p| p == plaintext) => Ok(value),
+        _ => encrypt(key, plaintext),
+    }
+}
+
+/// Check if a secret name can be used in a reference
+///
+/// # Arguments
+///
+/// - `name` - The name of the secret
+///
+/// # Returns
+///
+/// True if the name only has letters, digits, `_` and `-`
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Replace the references to named secrets in a launch setting
+///
+/// # Arguments
+///
+/// - `values` - The decrypted named secrets
+/// - `text` - The setting with `${secret:name}` references
+/// - `used` - The names of the replaced secrets are added to it
+///
+/// # Returns
+///
+/// The setting with the values of the secrets
+///
+/// # Errors
+///
+/// If a referenced secret does not exist
+pub fn resolve_references(
+    values: &BTreeMap<String, String>,
+    text: &str,
+    used: &mut Vec<String>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut resolved = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find(REFERENCE_PREFIX) {
+        let name_start = start + REFERENCE_PREFIX.len();
+        let Some(end) = rest[name_start..].find('}').map(|end| end + name_start) else {
+            break;
+        };
+        let name = &rest[name_start..end];
+        let value = values
+            .get(name)
+            .ok_or_else(|| tr!("secrets-unknown", name = name))?;
+        resolved.push_str(&rest[..start]);
+        resolved.push_str(value);
+        if !used.iter().any(|n| n == name) {
+            used.push(name.to_string());
+        }
+        rest = &rest[end + 1..];
+    }
+    resolved.push_str(rest);
+
+    Ok(resolved)
+}
+
+/// Check if a setting references a named secret
+///
+/// # Arguments
+///
+/// - `text` - The setting
+///
+/// # Returns
+///
+/// True if the setting contains a `${secret:name}` reference
+pub fn has_reference(text: &str) -> bool {
+    text.contains(REFERENCE_PREFIX)
+}
+
+/// Get the reference to a named secret
+///
+/// # Arguments
+///
+/// - `name` - The name of the secret
+///
+/// # Returns
+///
+/// The reference, e.g. `${secret:rcon_password}`
+pub fn reference(name: &str) -> String {
+    format!("{}{}}}", REFERENCE_PREFIX, name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let key = derive_key("correct horse", &new_salt()).unwrap();
+        let encrypted = encrypt(&key, "hunter2").unwrap();
+
+        assert!(is_encrypted(&encrypted));
+        assert!(!encrypted.contains("hunter2"));
+        assert_ne!(encrypted, encrypt(&key, "hunter2").unwrap());
+        assert_eq!(decrypt(&key, &encrypted).unwrap(), "hunter2");
+
+        let other = derive_key("wrong horse", &new_salt()).unwrap();
+        assert!(decrypt(&other, &encrypted).is_err());
+    }
+
+    #[test]
+    fn test_encrypt_keeps_loaded_value() {
+        let key = derive_key("correct horse", &new_salt()).unwrap();
+        let encrypted = encrypt(&key, "loaded-secret").unwrap();
+
+        assert_eq!(decrypt_loaded(&key, &encrypted).unwrap(), "loaded-secret");
+        assert_eq!(encrypt_loaded(&key, "loaded-secret").unwrap(), encrypted);
+        assert_ne!(encrypt_loaded(&key, "changed-secret").unwrap(), encrypted);
+
+        // A new key encrypts the value again
+        let other = derive_key("wrong horse", &new_salt()).unwrap();
+        let reencrypted = encrypt_loaded(&other, "loaded-secret").unwrap();
+        assert_ne!(reencrypted, encrypted);
+        assert_eq!(decrypt(&other, &reencrypted).unwrap(), "loaded-secret");
+    }
+
+    #[test]
+    fn test_resolve_references() {
+        let values = BTreeMap::from([("rcon".to_string(), "hunter2".to_string())]);
+        let mut used = Vec::new();
+
+        assert_eq!(
+            resolve_references(&values, "-pw=${secret:rcon}", &mut used).unwrap(),
+            "-pw=hunter2"
+        );
+        assert_eq!(used, ["rcon"]);
+        assert_eq!(reference("rcon"), "${secret:rcon}");
+        assert!(resolve_references(&values, "${secret:missing}", &mut used).is_err());
+        assert!(!is_valid_name("rcon password"));
+    }
+}
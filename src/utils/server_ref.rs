@@ -0,0 +1,64 @@
+use std::fmt;
+
+/// Reference to a server or to one of its instances, written as `server` or `server/instance`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerRef {
+    pub server: String,
+    pub instance: Option<String>,
+}
+
+impl ServerRef {
+    /// Parse a server reference
+    ///
+    /// # Arguments
+    ///
+    /// - `value` - The reference in the form `server` or `server/instance`
+    ///
+    /// # Returns
+    ///
+    /// The parsed server reference
+    pub fn parse(value: &str) -> Self {
+        match value.split_once('/') {
+            Some((server, instance)) if !instance.is_empty() => Self {
+                server: server.to_string(),
+                instance: Some(instance.to_string()),
+            },
+            Some((server, _)) => Self {
+                server: server.to_string(),
+                instance: None,
+            },
+            None => Self {
+                server: value.to_string(),
+                instance: None,
+            },
+        }
+    }
+}
+
+impl fmt::Display for ServerRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.instance {
+            Some(instance) => write!(f, "{}/{}", self.server, instance),
+            None => write!(f, "{}", self.server),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_server_ref() {
+        let server = ServerRef::parse("valheim");
+        assert_eq!(server.server, "valheim");
+        assert_eq!(server.instance, None);
+
+        let instance = ServerRef::parse("valheim/pvp");
+        assert_eq!(instance.server, "valheim");
+        assert_eq!(instance.instance.as_deref(), Some("pvp"));
+        assert_eq!(instance.to_string(), "valheim/pvp");
+
+        assert_eq!(ServerRef::parse("valheim/").instance, None);
+    }
+}
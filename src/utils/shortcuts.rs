@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::launch::split_args;
+
+/// Global options of steamserv that take a value
+const GLOBAL_OPTIONS_WITH_VALUE: [&str; 2] = ["--progress", "--remote"];
+
+/// Own commands and default arguments, expanded before the command line is parsed
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct CommandsConfig {
+    /// New commands that stand for a command with arguments, e.g.
+    /// `up = "update --yes"`
+    pub aliases: BTreeMap<String, String>,
+    /// Arguments added to every call of a command, e.g. `list = "--installed"`
+    /// or `"logs search" = "--since 1d"`
+    pub defaults: BTreeMap<String, String>,
+}
+
+impl CommandsConfig {
+    /// Expand the aliases and add the default arguments to a command line
+    ///
+    /// Built-in commands always win over an alias of the same name. A default
+    /// argument is left out if the command line already has that option.
+    ///
+    /// # Arguments
+    ///
+    /// - `args` - The command line, starting with the program
+    /// - `commands` - The names of the built-in commands
+    ///
+    /// # Returns
+    ///
+    /// The expanded command line
+    pub fn expand(&self, mut args: Vec<String>, commands: &[String]) -> Vec<String> {
+        let Some(index) = command_index(&args) else {
+            return args;
+        };
+
+        if let Some(alias) = self
+            .aliases
+            .get(&args[index])
+            .filter(|_| !commands.contains(&args[index]))
+        {
+            args.splice(index..=index, split_args(alias));
+        }
+
+        // The longest key wins, e.g. `logs search` over `logs`
+        let Some((words, defaults)) = self
+            .defaults
+            .iter()
+            .map(|(key, defaults)| (key.split_whitespace().collect::<Vec<&str>>(), defaults))
+            .filter(|(key, _)| {
+                !key.is_empty()
+                    && args.len() >= index + key.len()
+                    && key.iter().zip(&args[index..]).all(|(k, arg)| k == arg)
+            })
+            .map(|(key, defaults)| (key.len(), split_args(defaults)))
+            .max_by_key(|(words, _)| *words)
+        else {
+            return args;
+        };
+
+        let given = &args[index + words..];
+        let mut added = Vec::new();
+        for group in option_groups(&defaults) {
+            let name = group[0].split('=').next().unwrap_or_default();
+            let present = name.starts_with('-')
+                && given
+                    .iter()
+                    .any(|arg| arg == name || arg.starts_with(&format!("{}=", name)));
+            if !present {
+                added.extend(group);
+            }
+        }
+        let at = index + words;
+        args.splice(at..at, added);
+        args
+    }
+}
+
+/// Find the command in a command line
+///
+/// # Arguments
+///
+/// - `args` - The command line, starting with the program
+///
+/// # Returns
+///
+/// The index of the first argument that is not a global option
+fn command_index(args: &[String]) -> Option<usize> {
+    let mut index = 1;
+    while let Some(arg) = args.get(index) {
+        if arg == "--" {
+            return None;
+        }
+        if !arg.starts_with('-') {
+            return Some(index);
+        }
+        index += if GLOBAL_OPTIONS_WITH_VALUE.contains(&arg.as_str()) {
+            2
+        } else {
+            1
+        };
+    }
+    None
+}
+
+/// Group arguments into options with their values
+///
+/// # Arguments
+///
+/// - `args` - The arguments, e.g. `--output json --yes`
+///
+/// # Returns
+///
+/// The groups, e.g. `[--output json]` and `[--yes]`
+fn option_groups(args: &[String]) -> Vec<Vec<String>> {
+    let mut groups: Vec<Vec<String>> = Vec::new();
+    for arg in args {
+        match groups.last_mut() {
+            Some(group) if !arg.starts_with('-') => group.push(arg.clone()),
+            _ => groups.push(vec![arg.clone()]),
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        split_args(line)
+    }
+
+    #[test]
+    fn test_expand_commands() {
+        let config = CommandsConfig {
+            aliases: BTreeMap::from([
+                ("up".to_string(), "update --yes".to_string()),
+                ("list".to_string(), "status".to_string()),
+            ]),
+            defaults: BTreeMap::from([
+                ("list".to_string(), "--installed --output json".to_string()),
+                ("logs search".to_string(), "--since 1d".to_string()),
+            ]),
+        };
+        let commands = ["list".to_string(), "update".to_string(), "logs".to_string()];
+
+        assert_eq!(
+            config.expand(args("steamserv --offline up -s cs2"), &commands),
+            args("steamserv --offline update --yes -s cs2")
+        );
+        assert_eq!(
+            config.expand(args("steamserv --progress json list"), &commands),
+            args("steamserv --progress json list --installed --output json")
+        );
+        assert_eq!(
+            config.expand(args("steamserv --remote eu1 up"), &commands),
+            args("steamserv --remote eu1 update --yes")
+        );
+        assert_eq!(
+            config.expand(args("steamserv list --output=csv"), &commands),
+            args("steamserv list --installed --output=csv")
+        );
+        assert_eq!(
+            config.expand(args("steamserv logs search cs2 error"), &commands),
+            args("steamserv logs search --since 1d cs2 error")
+        );
+        assert_eq!(
+            config.expand(args("steamserv --plain"), &commands),
+            args("steamserv --plain")
+        );
+    }
+}
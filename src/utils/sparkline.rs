@@ -0,0 +1,60 @@
+/// Characters of a sparkline from the lowest to the highest value
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Render values as a sparkline
+///
+/// If there are more values than the width, neighbouring values are averaged.
+///
+/// # Arguments
+///
+/// - `values` - The values, oldest first
+/// - `width` - The maximum number of characters
+///
+/// # Returns
+///
+/// The sparkline, empty if there are no values
+pub fn sparkline(values: &[f64], width: usize) -> String {
+    if values.is_empty() || width == 0 {
+        return String::new();
+    }
+
+    let buckets: Vec<f64> = if values.len() > width {
+        (0..width)
+            .map(|i| {
+                let start = i * values.len() / width;
+                let end = ((i + 1) * values.len() / width).max(start + 1);
+                values[start..end].iter().sum::<f64>() / (end - start) as f64
+            })
+            .collect()
+    } else {
+        values.to_vec()
+    };
+
+    let min = buckets.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = buckets.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+
+    buckets
+        .iter()
+        .map(|value| {
+            if range <= f64::EPSILON {
+                BARS[0]
+            } else {
+                let index = ((value - min) / range * (BARS.len() - 1) as f64).round() as usize;
+                BARS[index.min(BARS.len() - 1)]
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[0.0, 7.0, 14.0], 10), "▁▅█");
+        assert_eq!(sparkline(&[1.0, 1.0, 5.0, 5.0], 2), "▁█");
+        assert_eq!(sparkline(&[], 10), "");
+    }
+}
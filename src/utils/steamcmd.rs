@@ -0,0 +1,738 @@
+use std::{fmt, process::Command};
+
+use chrono::{DateTime, Datelike, Duration, Local, Timelike, Utc, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use crate::tr;
+
+/// Locale SteamCMD runs with, so its output can be matched
+///
+/// In other locales numbers can be printed with a decimal comma and messages
+/// can be translated.
+const LOCALE: &str = "C";
+
+/// A known line of the SteamCMD output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputLine {
+    /// A message of the Steam client that is not meant for the user
+    Noise,
+    /// SteamCMD checks for or downloads an update of itself
+    SelfUpdate,
+    Login,
+    /// The app is not available for the platform SteamCMD was forced to
+    InvalidPlatform,
+}
+
+/// The texts of the known lines, with the variants of different SteamCMD
+/// versions, matched anywhere in a line and ignoring case
+const PATTERNS: &[(OutputLine, &[&str])] = &[
+    (
+        OutputLine::Noise,
+        &["redirecting stderr", "updateui", "ilocalize"],
+    ),
+    (
+        OutputLine::SelfUpdate,
+        &[
+            "checking for available update",
+            "downloading update (",
+            "verifying installation...",
+        ],
+    ),
+    (
+        OutputLine::Login,
+        &[
+            "logging in user",
+            "logging in using",
+            "connecting anonymously",
+        ],
+    ),
+    (
+        OutputLine::InvalidPlatform,
+        &["invalid platform", "unknown"],
+    ),
+];
+
+impl OutputLine {
+    /// Recognize a line of the SteamCMD output
+    ///
+    /// # Arguments
+    ///
+    /// - `line` - The line
+    ///
+    /// # Returns
+    ///
+    /// The kind of the line, or `None` if it is not a known line
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = line.trim().to_lowercase();
+        PATTERNS
+            .iter()
+            .find(|(_, texts)| texts.iter().any(|text| line.contains(text)))
+            .map(|(kind, _)| *kind)
+    }
+
+    /// Check if the output of SteamCMD contains a line of this kind
+    ///
+    /// # Arguments
+    ///
+    /// - `output` - The output
+    ///
+    /// # Returns
+    ///
+    /// True if one of the lines is of this kind
+    pub fn is_in(self, output: &str) -> bool {
+        output.lines().any(|line| Self::parse(line) == Some(self))
+    }
+}
+
+/// Why SteamCMD could not log in with a Steam account
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginFailure {
+    /// The username or the password is wrong
+    InvalidPassword,
+    /// Steam refuses logins after too many attempts
+    RateLimited,
+    /// A Steam Guard code is missing, expired or wrong
+    SteamGuard,
+}
+
+/// Results of a failed login by the failure they stand for, matched in the
+/// reason and ignoring case
+const LOGIN_FAILURES: &[(LoginFailure, &[&str])] = &[
+    (
+        LoginFailure::InvalidPassword,
+        &["invalid password", "account not found"],
+    ),
+    (
+        LoginFailure::RateLimited,
+        &["rate limit exceeded", "too many"],
+    ),
+    (
+        LoginFailure::SteamGuard,
+        &[
+            "account logon denied",
+            "account login denied",
+            "two-factor",
+            "two factor",
+            "steam guard",
+            "auth code",
+        ],
+    ),
+];
+
+/// Results of a failed login or connection that mean Steam could not be
+/// reached, unlike wrong credentials
+const OUTAGE_REASONS: [&str; 5] = [
+    "no connection",
+    "service unavailable",
+    "timeout",
+    "try another cm",
+    "busy",
+];
+
+/// Time zone of the weekly Steam maintenance
+const MAINTENANCE_ZONE: Tz = chrono_tz::America::Los_Angeles;
+/// Start of the weekly Steam maintenance on Tuesdays, in minutes after midnight
+const MAINTENANCE_START: u32 = 16 * 60;
+/// Length of the weekly Steam maintenance in minutes, with some margin
+const MAINTENANCE_MINUTES: u32 = 90;
+
+/// Flags of the state of an app in the error lines of SteamCMD
+const APP_STATE_FLAGS: [(u32, &str); 12] = [
+    (0x2, "update required"),
+    (0x4, "fully installed"),
+    (0x8, "encrypted"),
+    (0x10, "locked"),
+    (0x20, "files missing"),
+    (0x80, "files corrupt"),
+    (0x100, "update running"),
+    (0x200, "update paused"),
+    (0x400, "update started"),
+    (0x10000, "reconfiguring"),
+    (0x20000, "validating"),
+    (0x100000, "downloading"),
+];
+
+/// An error SteamCMD reported in its output or with its exit code
+#[derive(Clone, PartialEq, Eq)]
+pub enum SteamCmdError {
+    /// `Error! App '896660' state is 0x202 after update job.`
+    AppState { app_id: u32, state: u32 },
+    /// `ERROR! Failed to install app '896660' (No subscription)`
+    InstallFailed { app_id: u32, reason: String },
+    /// `FAILED login with result code Service Unavailable`, Steam could not be reached
+    Outage { reason: String },
+    /// `FAILED login with result code Invalid Password`
+    Login {
+        failure: LoginFailure,
+        reason: String,
+    },
+    /// SteamCMD failed without an error line
+    Exit { code: Option<i32> },
+}
+
+impl SteamCmdError {
+    /// Recognize an error line of the SteamCMD output
+    ///
+    /// # Arguments
+    ///
+    /// - `line` - The line
+    ///
+    /// # Returns
+    ///
+    /// The error, or `None` if the line is no error line
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+        // Only ASCII is lowered, so the positions stay the same in the line
+        let lower = line.to_ascii_lowercase();
+
+        if let Some(start) = lower.find("error! app '") {
+            let rest = &line[start + "error! app '".len()..];
+            let (app_id, rest) = rest.split_once('\'')?;
+            let state = rest.trim_start().strip_prefix("state is 0x")?;
+            let state = state.split(|c: char| !c.is_ascii_hexdigit()).next()?;
+            return Some(Self::AppState {
+                app_id: app_id.parse().ok()?,
+                state: u32::from_str_radix(state, 16).ok()?,
+            });
+        }
+        if let Some(start) = lower.find("error! failed to install app '") {
+            let rest = &line[start + "error! failed to install app '".len()..];
+            let (app_id, rest) = rest.split_once('\'')?;
+            let reason = rest
+                .trim()
+                .trim_start_matches('(')
+                .trim_end_matches(['.', ')'])
+                .to_string();
+            return Some(Self::InstallFailed {
+                app_id: app_id.parse().ok()?,
+                reason,
+            });
+        }
+
+        // SteamCMD asks for the code when the account is protected by Steam
+        // Guard, which fails without a terminal
+        if lower.contains("steam guard code:") || lower.contains("two-factor code:") {
+            return Some(Self::Login {
+                failure: LoginFailure::SteamGuard,
+                reason: line.trim_end_matches(':').to_string(),
+            });
+        }
+        let reason = [
+            "failed login with result code",
+            "login failure:",
+            "...failed (",
+        ]
+        .iter()
+        .find_map(|prefix| {
+            lower
+                .find(prefix)
+                .map(|start| &line[start + prefix.len()..])
+        })?;
+        let reason = reason.trim().trim_end_matches(['.', ')']).to_string();
+        let lower = reason.to_ascii_lowercase();
+        if let Some((failure, _)) = LOGIN_FAILURES
+            .iter()
+            .find(|(_, texts)| texts.iter().any(|text| lower.contains(text)))
+        {
+            return Some(Self::Login {
+                failure: *failure,
+                reason,
+            });
+        }
+        OUTAGE_REASONS
+            .iter()
+            .any(|outage| lower.contains(outage))
+            .then_some(Self::Outage { reason })
+    }
+
+    /// Check if the login failed, which must not be retried automatically
+    ///
+    /// Every failed login counts towards the lockout of the account, and
+    /// trying again while rate limited extends the limit.
+    ///
+    /// # Returns
+    ///
+    /// True if the credentials were refused or SteamCMD exited with the code of a failed login
+    pub fn is_login_failure(&self) -> bool {
+        matches!(self, Self::Login { .. } | Self::Exit { code: Some(5) })
+    }
+
+    /// Check if the error means that Steam could not be reached
+    ///
+    /// # Returns
+    ///
+    /// True if the error is an outage of Steam, which is worth retrying later
+    pub fn is_outage(&self) -> bool {
+        matches!(self, Self::Outage { .. })
+    }
+
+    /// Explain what to do about the error
+    ///
+    /// # Returns
+    ///
+    /// The translated remedy
+    pub fn remedy(&self) -> String {
+        match self {
+            Self::AppState { state: 0x202, .. } => tr!("steamcmd-remedy-disk-space"),
+            Self::AppState { state: 0x206, .. } => tr!("steamcmd-remedy-content"),
+            Self::AppState {
+                state: 0x402 | 0x602,
+                ..
+            } => tr!("steamcmd-remedy-network"),
+            Self::AppState { state, .. } if state & (0x20 | 0x80) != 0 => {
+                tr!("steamcmd-remedy-validate")
+            }
+            Self::AppState { state, .. } => {
+                let flags: Vec<&str> = APP_STATE_FLAGS
+                    .iter()
+                    .filter(|(flag, _)| state & flag != 0)
+                    .map(|(_, name)| *name)
+                    .collect();
+                tr!("steamcmd-remedy-state", flags = flags.join(", "))
+            }
+            Self::InstallFailed { reason, .. } => {
+                let reason = reason.to_lowercase();
+                if reason.contains("subscription") {
+                    tr!("steamcmd-remedy-subscription")
+                } else if reason.contains("disk write") {
+                    tr!("steamcmd-remedy-disk-write")
+                } else if reason.contains("missing configuration") || reason.contains("platform") {
+                    tr!("steamcmd-remedy-platform")
+                } else if reason.contains("connection") || reason.contains("timeout") {
+                    tr!("steamcmd-remedy-network")
+                } else {
+                    tr!("steamcmd-remedy-output")
+                }
+            }
+            Self::Outage { .. } => match maintenance_until(Utc::now()) {
+                Some(until) => tr!(
+                    "steamcmd-remedy-maintenance",
+                    until = until.with_timezone(&Local).format("%H:%M").to_string()
+                ),
+                None => tr!("steamcmd-remedy-outage"),
+            },
+            Self::Login { failure, .. } => match failure {
+                LoginFailure::InvalidPassword => tr!("steamcmd-remedy-password"),
+                LoginFailure::RateLimited => tr!("steamcmd-remedy-rate-limit"),
+                LoginFailure::SteamGuard => tr!("steamcmd-remedy-steam-guard"),
+            },
+            Self::Exit { code: Some(5) } => tr!("steamcmd-remedy-login"),
+            Self::Exit { .. } => tr!("steamcmd-remedy-output"),
+        }
+    }
+}
+
+impl fmt::Display for SteamCmdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let error = match self {
+            Self::AppState { app_id, state } => tr!(
+                "steamcmd-error-state",
+                app_id = app_id,
+                state = format!("0x{:X}", state)
+            ),
+            Self::InstallFailed { app_id, reason } => tr!(
+                "steamcmd-error-install",
+                app_id = app_id,
+                reason = reason.as_str()
+            ),
+            Self::Outage { reason } => {
+                tr!("steamcmd-error-outage", reason = reason.as_str())
+            }
+            Self::Login { reason, .. } => tr!("steamcmd-error-login", reason = reason.as_str()),
+            Self::Exit { code } => tr!(
+                "steamcmd-error-exit",
+                code = code.map_or_else(|| "-".to_string(), |c| c.to_string())
+            ),
+        };
+        write!(f, "{}\n{}", error, self.remedy())
+    }
+}
+
+impl fmt::Debug for SteamCmdError {
+    /// Print the message like the other errors, which are strings
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.to_string())
+    }
+}
+
+impl std::error::Error for SteamCmdError {}
+
+/// Get the end of the weekly Steam maintenance, if it is ongoing
+///
+/// Steam is usually down for maintenance on Tuesday afternoons, Pacific time.
+///
+/// # Arguments
+///
+/// - `now` - The point in time
+///
+/// # Returns
+///
+/// When the maintenance is expected to end, or `None` outside of it
+pub fn maintenance_until(now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let local = now.with_timezone(&MAINTENANCE_ZONE);
+    let minute = local.hour() * 60 + local.minute();
+    let end = MAINTENANCE_START + MAINTENANCE_MINUTES;
+    if local.weekday() != Weekday::Tue || !(MAINTENANCE_START..end).contains(&minute) {
+        return None;
+    }
+    Some(
+        now + Duration::minutes(i64::from(end - minute)) - Duration::seconds(local.second().into()),
+    )
+}
+
+/// Read the apps the licenses of an account grant from the output of `licenses_print`
+///
+/// # Arguments
+///
+/// - `output` - The output of SteamCMD
+///
+/// # Returns
+///
+/// The App IDs, or `None` if the output has no licenses
+pub fn licensed_apps(output: &str) -> Option<Vec<u32>> {
+    if !output.contains("License packageID") {
+        return None;
+    }
+
+    // ` - Apps    : 10, 80, 100, (3 in total)`
+    let apps = output
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("- Apps"))
+        .filter_map(|apps| apps.split_once(':'))
+        .flat_map(|(_, apps)| apps.split(','))
+        .filter_map(|app| app.trim().parse().ok())
+        .collect();
+    Some(apps)
+}
+
+/// How a SteamCMD build selects the branch of an app
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BetaSyntax {
+    /// `+app_update <id> -beta <branch>`
+    #[default]
+    Flag,
+    /// `+app_set_config <id> betakey <branch>` before the update, for builds without `-beta`
+    Config,
+}
+
+/// Features of a SteamCMD build, detected with `+find`
+///
+/// Without a probe, a current build is assumed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// The version SteamCMD prints on start, e.g. `1716584372`
+    pub version: Option<String>,
+    pub beta: BetaSyntax,
+    /// `app_license_request` exists to check the licenses before an install
+    pub license_request: bool,
+    /// `workshop_download_item` exists to download workshop mods
+    pub workshop_download: bool,
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self {
+            version: None,
+            beta: BetaSyntax::Flag,
+            license_request: true,
+            workshop_download: true,
+        }
+    }
+}
+
+impl Capabilities {
+    /// Read the capabilities from the output of `+find app_ +find workshop_`
+    ///
+    /// # Arguments
+    ///
+    /// - `output` - The output of SteamCMD
+    ///
+    /// # Returns
+    ///
+    /// The capabilities, or `None` if the output does not list `app_update`
+    pub fn parse(output: &str) -> Option<Self> {
+        // `Steam Console Client (c) Valve Corporation - version 1716584372`
+        let version = output
+            .lines()
+            .find(|line| line.contains("Steam Console Client"))
+            .and_then(|line| line.split_once("version "))
+            .map(|(_, version)| version.trim().to_string());
+        // `   app_update : <app_id> [-beta <betaname>] [-betapassword <password>] [validate]`
+        let command = |name: &str| {
+            output.lines().find(|line| {
+                line.trim_start()
+                    .strip_prefix(name)
+                    .is_some_and(|rest| rest.trim_start().starts_with(':'))
+            })
+        };
+
+        let app_update = command("app_update")?;
+        Some(Self {
+            version,
+            beta: match app_update.contains("-beta") {
+                true => BetaSyntax::Flag,
+                false => BetaSyntax::Config,
+            },
+            license_request: command("app_license_request").is_some(),
+            workshop_download: command("workshop_download_item").is_some(),
+        })
+    }
+
+    /// Build the commands that download an app
+    ///
+    /// # Arguments
+    ///
+    /// - `app_id` - The App ID
+    /// - `branch` - The branch, the public branch if `None`
+    ///
+    /// # Returns
+    ///
+    /// The SteamCMD commands, run after the login
+    pub fn app_update(&self, app_id: u32, branch: Option<&str>) -> Vec<String> {
+        match (branch, self.beta) {
+            (Some(branch), BetaSyntax::Flag) => {
+                vec![format!("+app_update {} -beta {} validate", app_id, branch)]
+            }
+            (Some(branch), BetaSyntax::Config) => vec![
+                format!("+app_set_config {} betakey {}", app_id, branch),
+                format!("+app_update {} validate", app_id),
+            ],
+            (None, _) => vec![format!("+app_update {} validate", app_id)],
+        }
+    }
+}
+
+/// Run a SteamCMD command with a fixed locale
+///
+/// # Arguments
+///
+/// - `command` - The SteamCMD command
+pub fn normalize_env(command: &mut Command) {
+    command
+        .env("LC_ALL", LOCALE)
+        .env("LANG", LOCALE)
+        .env_remove("LANGUAGE");
+}
+
+/// Split extra SteamCMD commands into the ones before and after the login
+///
+/// Settings like `+@NoPromptForPassword 1` only take effect before the login,
+/// all other commands, e.g. `+app_set_config 90 mod cstrike`, run after it and
+/// before the download. A missing `+` is added.
+///
+/// # Arguments
+///
+/// - `commands` - The extra commands of the config and the server
+///
+/// # Returns
+///
+/// The commands before the login and the commands after it
+pub fn extra_commands<'a>(
+    commands: impl IntoIterator<Item = &'a String>,
+) -> (Vec<String>, Vec<String>) {
+    commands
+        .into_iter()
+        .map(|command| command.trim())
+        .filter(|command| !command.is_empty())
+        .map(|command| match command.starts_with('+') {
+            true => command.to_string(),
+            false => format!("+{}", command),
+        })
+        .partition(|command| command.starts_with("+@"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_output_line() {
+        let lines = [
+            (
+                "Redirecting stderr to '/home/steam/Steam/logs/stderr.txt'",
+                Some(OutputLine::Noise),
+            ),
+            (
+                "[  0%] Checking for available updates...",
+                Some(OutputLine::SelfUpdate),
+            ),
+            (
+                "[----] Verifying installation...",
+                Some(OutputLine::SelfUpdate),
+            ),
+            (
+                "Logging in user 'steam' to Steam Public...OK",
+                Some(OutputLine::Login),
+            ),
+            (
+                "Connecting anonymously to Steam Public...OK",
+                Some(OutputLine::Login),
+            ),
+            (
+                "AppID 896660 (Valheim): - install state: Invalid Platform,",
+                Some(OutputLine::InvalidPlatform),
+            ),
+            (
+                "AppID 896660 : - install state: invalid platform",
+                Some(OutputLine::InvalidPlatform),
+            ),
+            ("Success! App '896660' fully installed.", None),
+        ];
+        for (line, kind) in lines {
+            assert_eq!(OutputLine::parse(line), kind, "{}", line);
+        }
+        assert!(OutputLine::InvalidPlatform
+            .is_in("Loading Steam API...OK\n - install state: Invalid Platform,\n"));
+        assert!(!OutputLine::InvalidPlatform.is_in("Loading Steam API...OK\n"));
+    }
+
+    #[test]
+    fn test_parse_steamcmd_error() {
+        assert_eq!(
+            SteamCmdError::parse("Error! App '896660' state is 0x202 after update job."),
+            Some(SteamCmdError::AppState {
+                app_id: 896660,
+                state: 0x202
+            })
+        );
+        assert_eq!(
+            SteamCmdError::parse("ERROR! Failed to install app '376030' (No subscription)"),
+            Some(SteamCmdError::InstallFailed {
+                app_id: 376030,
+                reason: "No subscription".to_string()
+            })
+        );
+        assert_eq!(
+            SteamCmdError::parse(" Update state (0x61) downloading, progress: 45.23 (1 / 2)"),
+            None
+        );
+        assert_eq!(
+            SteamCmdError::parse("Success! App '896660' fully installed."),
+            None
+        );
+        assert_eq!(
+            SteamCmdError::parse("FAILED login with result code Service Unavailable"),
+            Some(SteamCmdError::Outage {
+                reason: "Service Unavailable".to_string()
+            })
+        );
+        assert_eq!(
+            SteamCmdError::parse("Connecting anonymously to Steam Public...FAILED (No Connection)"),
+            Some(SteamCmdError::Outage {
+                reason: "No Connection".to_string()
+            })
+        );
+        assert_eq!(
+            SteamCmdError::parse("FAILED login with result code Invalid Password"),
+            Some(SteamCmdError::Login {
+                failure: LoginFailure::InvalidPassword,
+                reason: "Invalid Password".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_login_failure() {
+        let failure = |line| match SteamCmdError::parse(line) {
+            Some(SteamCmdError::Login { failure, .. }) => Some(failure),
+            _ => None,
+        };
+        assert_eq!(
+            failure("Login Failure: Rate Limit Exceeded"),
+            Some(LoginFailure::RateLimited)
+        );
+        assert_eq!(
+            failure("Logging in user 'steam' to Steam Public...FAILED (Account Logon Denied)"),
+            Some(LoginFailure::SteamGuard)
+        );
+        assert_eq!(
+            failure("FAILED login with result code Two-factor code mismatch"),
+            Some(LoginFailure::SteamGuard)
+        );
+        assert_eq!(failure("Steam Guard code:"), Some(LoginFailure::SteamGuard));
+        assert_eq!(failure("FAILED login with result code No Connection"), None);
+
+        assert!(SteamCmdError::Exit { code: Some(5) }.is_login_failure());
+        assert!(!SteamCmdError::Exit { code: Some(8) }.is_login_failure());
+    }
+
+    #[test]
+    fn test_maintenance_until() {
+        use chrono::TimeZone;
+
+        // Tuesday 2026-10-13, 16:00 PDT is 23:00 UTC
+        let at = |day: u32, hour: u32, minute: u32| {
+            Utc.with_ymd_and_hms(2026, 10, day, hour, minute, 0)
+                .unwrap()
+        };
+        assert_eq!(maintenance_until(at(13, 22, 59)), None);
+        assert_eq!(maintenance_until(at(13, 23, 0)), Some(at(14, 0, 30)));
+        assert_eq!(maintenance_until(at(14, 0, 15)), Some(at(14, 0, 30)));
+        assert_eq!(maintenance_until(at(14, 0, 30)), None);
+        assert_eq!(maintenance_until(at(20, 0, 15)), None);
+    }
+
+    #[test]
+    fn test_licensed_apps() {
+        let output = "License packageID 0:\n \
+             - State   : Active( flags 0 ) - Purchased : Thu Jan  1 00:00:00 1970 in \"\", Steam\n \
+             - Apps    : 7, 760, (2 in total)\n \
+             - Depots  : 7, 760, (2 in total)\n\
+             License packageID 17906:\n \
+             - Apps    : 346110, 376030, (2 in total)\n";
+        assert_eq!(licensed_apps(output), Some(vec![7, 760, 346110, 376030]));
+        assert_eq!(licensed_apps("Loading Steam API...OK\n"), None);
+    }
+
+    #[test]
+    fn test_extra_commands() {
+        let commands = [
+            "+@NoPromptForPassword 1".to_string(),
+            "app_set_config 90 mod cstrike".to_string(),
+            " ".to_string(),
+            "+@sSteamCmdForcePlatformType linux".to_string(),
+        ];
+        let (before_login, after_login) = extra_commands(&commands);
+        assert_eq!(
+            before_login,
+            [
+                "+@NoPromptForPassword 1",
+                "+@sSteamCmdForcePlatformType linux"
+            ]
+        );
+        assert_eq!(after_login, ["+app_set_config 90 mod cstrike"]);
+    }
+
+    #[test]
+    fn test_parse_capabilities() {
+        let output = "Steam Console Client (c) Valve Corporation - version 1716584372\n\
+                      -- type 'quit' to exit --\n\
+                      Commands:\n   \
+                      app_license_request : <app_id>\n   \
+                      app_update : <app_id> [-beta <betaname>] [-betapassword <password>] [validate]\n   \
+                      workshop_download_item : <app_id> <workshop_id> [validate]\n";
+        let capabilities = Capabilities::parse(output).unwrap();
+        assert_eq!(capabilities.version.as_deref(), Some("1716584372"));
+        assert_eq!(capabilities.beta, BetaSyntax::Flag);
+        assert!(capabilities.license_request && capabilities.workshop_download);
+        assert_eq!(
+            capabilities.app_update(740, Some("beta")),
+            ["+app_update 740 -beta beta validate"]
+        );
+
+        let old = Capabilities::parse("   app_update : <app_id> [validate]\n").unwrap();
+        assert_eq!(old.beta, BetaSyntax::Config);
+        assert!(!old.license_request && !old.workshop_download);
+        assert_eq!(
+            old.app_update(740, Some("beta")),
+            [
+                "+app_set_config 740 betakey beta",
+                "+app_update 740 validate"
+            ]
+        );
+
+        assert!(Capabilities::parse("Connecting anonymously to Steam Public...OK").is_none());
+    }
+}
@@ -0,0 +1,218 @@
+use std::fmt;
+
+/// SteamID64 of the first individual account, the account number is added to it
+const INDIVIDUAL_BASE: u64 = 76561197960265728;
+/// Base URL of the Steam Community profiles
+const COMMUNITY_URL: &str = "https://steamcommunity.com";
+
+/// The SteamID of an individual Steam account
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SteamId(u64);
+
+impl SteamId {
+    /// Parse a SteamID
+    ///
+    /// # Arguments
+    ///
+    /// - `value` - A SteamID64 like `76561197960287930`, a SteamID2 like `STEAM_0:0:11101`,
+    ///   a SteamID3 like `[U:1:22202]` or a profile URL with a SteamID64
+    ///
+    /// # Returns
+    ///
+    /// The SteamID, or `None` if the value is not a SteamID of an individual account
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if let Some(id) =
+            profile_path(value).and_then(|(kind, id)| (kind == "profiles").then_some(id))
+        {
+            return Self::parse(id);
+        }
+        if let Some(account) = value
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .strip_prefix("U:1:")
+        {
+            let account: u64 = account.parse().ok()?;
+            return (account <= u64::from(u32::MAX)).then_some(Self(INDIVIDUAL_BASE + account));
+        }
+        if let Some(rest) = value
+            .strip_prefix("STEAM_")
+            .or_else(|| value.strip_prefix("steam_"))
+        {
+            let mut parts = rest.splitn(3, ':');
+            let universe: u8 = parts.next()?.parse().ok()?;
+            let low: u64 = parts.next()?.parse().ok()?;
+            let high: u64 = parts.next()?.parse().ok()?;
+            if universe > 1 || low > 1 {
+                return None;
+            }
+            return Some(Self(INDIVIDUAL_BASE + high * 2 + low));
+        }
+
+        let id: u64 = value.parse().ok()?;
+        (id > INDIVIDUAL_BASE && id - INDIVIDUAL_BASE <= u64::from(u32::MAX)).then_some(Self(id))
+    }
+
+    /// Get the SteamID64
+    ///
+    /// # Returns
+    ///
+    /// The 64-bit SteamID, e.g. `76561197960287930`
+    pub fn steam64(&self) -> u64 {
+        self.0
+    }
+
+    /// Get the account number
+    ///
+    /// # Returns
+    ///
+    /// The 32-bit account number, e.g. `22202`
+    pub fn account(&self) -> u64 {
+        self.0 - INDIVIDUAL_BASE
+    }
+
+    /// Get the SteamID2 in the universe 0 form used by most Source servers
+    ///
+    /// # Returns
+    ///
+    /// The SteamID2, e.g. `STEAM_0:0:11101`
+    pub fn steam2(&self) -> String {
+        format!("STEAM_0:{}:{}", self.account() % 2, self.account() / 2)
+    }
+
+    /// Get the SteamID3
+    ///
+    /// # Returns
+    ///
+    /// The SteamID3, e.g. `[U:1:22202]`
+    pub fn steam3(&self) -> String {
+        format!("[U:1:{}]", self.account())
+    }
+
+    /// Get the URL of the Steam Community profile
+    ///
+    /// # Returns
+    ///
+    /// The profile URL, e.g. `https://steamcommunity.com/profiles/76561197960287930`
+    pub fn profile_url(&self) -> String {
+        format!("{}/profiles/{}", COMMUNITY_URL, self.0)
+    }
+
+    /// Check if a word of a ban list or config file refers to this account
+    ///
+    /// # Arguments
+    ///
+    /// - `word` - The word, e.g. `76561197960287930`, `STEAM_1:0:11101` or `steam_76561197960287930`
+    ///
+    /// # Returns
+    ///
+    /// True if the word is this SteamID in any of its forms
+    pub fn matches(&self, word: &str) -> bool {
+        let word = word.trim_matches(|c| c == '"' || c == '\'' || c == ',');
+        word == self.steam3()
+            || word
+                .strip_prefix("steam_")
+                .unwrap_or(word)
+                .parse::<u64>()
+                .is_ok_and(|id| id == self.0)
+            || Self::parse(word).is_some_and(|id| id == *self)
+    }
+
+    /// Replace the SteamID placeholders of a template
+    ///
+    /// # Arguments
+    ///
+    /// - `template` - The template with `{steamid64}`, `{steamid2}` or `{steamid3}`
+    ///
+    /// # Returns
+    ///
+    /// The rendered template
+    pub fn render(&self, template: &str) -> String {
+        template
+            .replace("{steamid64}", &self.0.to_string())
+            .replace("{steamid2}", &self.steam2())
+            .replace("{steamid3}", &self.steam3())
+    }
+}
+
+/// Get the custom URL name of a profile
+///
+/// # Arguments
+///
+/// - `value` - A profile URL like `https://steamcommunity.com/id/gabelogannewell` or
+///   the bare name
+///
+/// # Returns
+///
+/// The name to resolve with the Steam Web API, or `None` if the value is no custom URL
+pub fn vanity_name(value: &str) -> Option<&str> {
+    let value = value.trim();
+    let name = match profile_path(value) {
+        Some(("id", name)) => name,
+        Some(_) => return None,
+        None if value.contains(['/', ':', ' ']) => return None,
+        None => value,
+    };
+    (!name.is_empty()).then_some(name)
+}
+
+/// Split a Steam Community profile URL
+///
+/// # Arguments
+///
+/// - `value` - The URL, with or without scheme
+///
+/// # Returns
+///
+/// The kind (`id` or `profiles`) and the last path segment, or `None` if the value is no profile URL
+fn profile_path(value: &str) -> Option<(&str, &str)> {
+    let path = value
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("www.")
+        .strip_prefix("steamcommunity.com/")?;
+    let mut segments = path.split('/').filter(|s| !s.is_empty());
+    Some((segments.next()?, segments.next()?))
+}
+
+impl fmt::Display for SteamId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steam_id_forms() {
+        let id = SteamId::parse("76561197960287930").unwrap();
+        assert_eq!(id.steam2(), "STEAM_0:0:11101");
+        assert_eq!(id.steam3(), "[U:1:22202]");
+        assert_eq!(SteamId::parse("STEAM_1:0:11101"), Some(id));
+        assert!(id.matches("\"steam_76561197960287930\""));
+        assert!(id.matches("[U:1:22202]"));
+        assert!(!id.matches("76561197960287931"));
+        assert_eq!(SteamId::parse("12345"), None);
+        assert_eq!(SteamId::parse("[U:1:22202]"), Some(id));
+        assert_eq!(
+            SteamId::parse("https://steamcommunity.com/profiles/76561197960287930/"),
+            Some(id)
+        );
+    }
+
+    #[test]
+    fn test_vanity_name() {
+        assert_eq!(
+            vanity_name("https://steamcommunity.com/id/gabelogannewell/"),
+            Some("gabelogannewell")
+        );
+        assert_eq!(vanity_name("gabelogannewell"), Some("gabelogannewell"));
+        assert_eq!(
+            vanity_name("steamcommunity.com/profiles/76561197960287930"),
+            None
+        );
+        assert_eq!(vanity_name("https://example.com/id/gaben"), None);
+    }
+}
@@ -0,0 +1,477 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, TransactionBehavior};
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{fs, Config, InstalledServer};
+
+/// Time to wait for a lock held by another steamserv process
+const BUSY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Backend to store the installed servers in
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    #[default]
+    Toml,
+    Sqlite,
+}
+
+/// Storage of the config and the installed servers
+pub trait StateStore {
+    /// Load the config with the installed servers
+    ///
+    /// # Returns
+    ///
+    /// The loaded config
+    ///
+    /// # Errors
+    ///
+    /// If the state could not be read
+    fn load(&self) -> Result<Config, Box<dyn std::error::Error>>;
+
+    /// Save the config with the installed servers
+    ///
+    /// # Arguments
+    ///
+    /// - `config` - The config to save
+    ///
+    /// # Returns
+    ///
+    /// Ok if the config was saved
+    ///
+    /// # Errors
+    ///
+    /// If the state could not be written
+    fn save(&self, config: &Config) -> Result<(), Box<dyn std::error::Error>>;
+
+    /// Find the installed servers that were not updated since a point in time
+    ///
+    /// # Arguments
+    ///
+    /// - `before` - The point in time
+    ///
+    /// # Returns
+    ///
+    /// The servers with an older last update
+    ///
+    /// # Errors
+    ///
+    /// If the state could not be read
+    fn servers_updated_before(
+        &self,
+        before: DateTime<Utc>,
+    ) -> Result<Vec<InstalledServer>, Box<dyn std::error::Error>>;
+}
+
+/// Store that keeps everything in `config.toml`
+pub struct TomlStore {
+    path: PathBuf,
+}
+
+impl TomlStore {
+    /// Create a store for a config file
+    ///
+    /// # Arguments
+    ///
+    /// - `path` - The path to the config file
+    ///
+    /// # Returns
+    ///
+    /// The store
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Get the backups of the config file
+    ///
+    /// # Returns
+    ///
+    /// The paths to the backups, newest first
+    ///
+    /// # Errors
+    ///
+    /// If the backup directory could not be read
+    pub fn backups(&self) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
+        let dir = self.backup_dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut backups: Vec<PathBuf> = std::fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        // The timestamp in the file name sorts chronologically
+        backups.sort();
+        backups.reverse();
+
+        Ok(backups)
+    }
+
+    /// Restore the config file from a backup
+    ///
+    /// The current config file is backed up first, so a restore can be undone.
+    ///
+    /// # Arguments
+    ///
+    /// - `backup` - The path to the backup
+    ///
+    /// # Returns
+    ///
+    /// Ok if the config was restored
+    ///
+    /// # Errors
+    ///
+    /// If the backup is not a valid config or could not be written
+    pub fn restore(&self, backup: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let content = std::fs::read_to_string(backup)?;
+        let config: Config = toml::from_str(&content)?;
+
+        if self.path.exists() {
+            self.backup(config.config_backups.max(1))?;
+        }
+        fs::write_atomic(&self.path, content.as_bytes())
+    }
+
+    /// Get the directory of the config backups
+    ///
+    /// # Returns
+    ///
+    /// The path to the backup directory next to the config file
+    fn backup_dir(&self) -> PathBuf {
+        self.path.with_file_name("config-backups")
+    }
+
+    /// Copy the current config file to the backups and remove the oldest backups
+    ///
+    /// # Arguments
+    ///
+    /// - `keep` - The number of backups to keep
+    ///
+    /// # Returns
+    ///
+    /// Ok if the backup was created
+    ///
+    /// # Errors
+    ///
+    /// If the backup could not be written
+    fn backup(&self, keep: usize) -> Result<(), Box<dyn std::error::Error>> {
+        if keep == 0 {
+            return Ok(());
+        }
+
+        let content = std::fs::read(&self.path)?;
+        let backups = self.backups()?;
+        if let Some(latest) = backups.first() {
+            if std::fs::read(latest).is_ok_and(|latest| latest == content) {
+                return Ok(());
+            }
+        }
+
+        let name = format!("config-{}.toml", Utc::now().format("%Y%m%d-%H%M%S-%6f"));
+        // The backup is as private as the config, it holds the same secrets
+        fs::write_atomic_like(&self.backup_dir().join(name), &content, &self.path)?;
+
+        for old in self.backups()?.iter().skip(keep) {
+            std::fs::remove_file(old)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl StateStore for TomlStore {
+    fn load(&self) -> Result<Config, Box<dyn std::error::Error>> {
+        if !self.path.exists() {
+            return Ok(Config::default());
+        }
+
+        let content = std::fs::read_to_string(&self.path)?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    fn save(&self, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+        let content = toml::to_string(config)?;
+
+        if self.path.exists() {
+            if std::fs::read_to_string(&self.path).is_ok_and(|current| current == content) {
+                return Ok(());
+            }
+            self.backup(config.config_backups)?;
+        }
+
+        fs::write_atomic(&self.path, content.as_bytes())
+    }
+
+    fn servers_updated_before(
+        &self,
+        before: DateTime<Utc>,
+    ) -> Result<Vec<InstalledServer>, Box<dyn std::error::Error>> {
+        Ok(self
+            .load()?
+            .installed_servers
+            .into_iter()
+            .filter(|s| s.last_updated < before)
+            .collect())
+    }
+}
+
+/// Store that keeps the settings in `config.toml` and the installed servers in SQLite
+///
+/// Servers that are still listed in `config.toml` are moved to the database on
+/// the first save.
+pub struct SqliteStore {
+    toml: TomlStore,
+    path: PathBuf,
+}
+
+impl SqliteStore {
+    /// Create a store for a config file and a database
+    ///
+    /// # Arguments
+    ///
+    /// - `config_path` - The path to the config file
+    /// - `path` - The path to the database
+    ///
+    /// # Returns
+    ///
+    /// The store
+    pub fn new(config_path: PathBuf, path: PathBuf) -> Self {
+        Self {
+            toml: TomlStore::new(config_path),
+            path,
+        }
+    }
+
+    /// Open the database and create the tables if needed
+    ///
+    /// # Returns
+    ///
+    /// The connection to the database
+    ///
+    /// # Errors
+    ///
+    /// If the database could not be opened
+    fn connect(&self) -> Result<Connection, Box<dyn std::error::Error>> {
+        std::fs::create_dir_all(self.path.parent().unwrap())?;
+
+        let connection = Connection::open(&self.path)?;
+        connection.busy_timeout(BUSY_TIMEOUT)?;
+        connection.execute_batch(
+            "PRAGMA journal_mode = WAL;
+            CREATE TABLE IF NOT EXISTS servers (
+                name TEXT PRIMARY KEY,
+                app_id INTEGER NOT NULL,
+                last_updated INTEGER NOT NULL,
+                data TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS servers_last_updated ON servers (last_updated);",
+        )?;
+
+        Ok(connection)
+    }
+
+    /// Load the installed servers matching a condition
+    ///
+    /// # Arguments
+    ///
+    /// - `connection` - The connection to the database
+    /// - `before` - Only load servers updated before this point in time
+    ///
+    /// # Returns
+    ///
+    /// The installed servers ordered by name
+    ///
+    /// # Errors
+    ///
+    /// If the servers could not be read
+    fn query_servers(
+        connection: &Connection,
+        before: Option<DateTime<Utc>>,
+    ) -> Result<Vec<InstalledServer>, Box<dyn std::error::Error>> {
+        let mut statement = connection.prepare(
+            "SELECT data FROM servers WHERE ?1 IS NULL OR last_updated < ?1 ORDER BY name",
+        )?;
+
+        let rows = statement
+            .query_map(params![before.map(|b| b.timestamp())], |row| {
+                row.get::<_, String>(0)
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        rows.iter()
+            .map(|data| Ok(serde_json::from_str(data)?))
+            .collect()
+    }
+}
+
+impl StateStore for SqliteStore {
+    fn load(&self) -> Result<Config, Box<dyn std::error::Error>> {
+        let mut config = self.toml.load()?;
+        let servers = Self::query_servers(&self.connect()?, None)?;
+
+        config.loaded_servers = Some(
+            servers
+                .iter()
+                .map(|server| Ok((server.name.clone(), serde_json::to_string(server)?)))
+                .collect::<Result<_, serde_json::Error>>()?,
+        );
+        if !servers.is_empty() || config.installed_servers.is_empty() {
+            config.installed_servers = servers;
+        }
+
+        Ok(config)
+    }
+
+    fn save(&self, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+        let mut connection = self.connect()?;
+        let transaction = connection.transaction_with_behavior(TransactionBehavior::Immediate)?;
+
+        // Only the servers changed since the load are written, so the changes
+        // another process saved in the meantime are kept
+        let loaded = config.loaded_servers.as_ref();
+        for server in &config.installed_servers {
+            let data = serde_json::to_string(server)?;
+            if loaded.and_then(|loaded| loaded.get(&server.name)) == Some(&data) {
+                continue;
+            }
+            transaction.execute(
+                "INSERT INTO servers (name, app_id, last_updated, data) VALUES (?1, ?2, ?3, ?4)
+                ON CONFLICT (name) DO UPDATE SET
+                    app_id = excluded.app_id,
+                    last_updated = excluded.last_updated,
+                    data = excluded.data",
+                params![
+                    server.name,
+                    server.app_id,
+                    server.last_updated.timestamp(),
+                    data
+                ],
+            )?;
+        }
+
+        // Without a load all other servers are replaced
+        let stored = match loaded {
+            Some(loaded) => loaded.keys().cloned().collect(),
+            None => transaction
+                .prepare("SELECT name FROM servers")?
+                .query_map([], |row| row.get::<_, String>(0))?
+                .collect::<Result<Vec<_>, _>>()?,
+        };
+        for name in stored {
+            if !config.installed_servers.iter().any(|s| s.name == name) {
+                transaction.execute("DELETE FROM servers WHERE name = ?1", params![name])?;
+            }
+        }
+        transaction.commit()?;
+
+        let settings = Config {
+            installed_servers: Vec::new(),
+            ..config.clone()
+        };
+        self.toml.save(&settings)
+    }
+
+    fn servers_updated_before(
+        &self,
+        before: DateTime<Utc>,
+    ) -> Result<Vec<InstalledServer>, Box<dyn std::error::Error>> {
+        Self::query_servers(&self.connect()?, Some(before))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::utils::{config::LoginType, Pause};
+
+    fn server(name: &str, days_ago: i64) -> InstalledServer {
+        let mut server = InstalledServer::new(
+            896660,
+            name,
+            PathBuf::from("/srv").join(name),
+            LoginType::Anonymous,
+        );
+        server.last_updated = Utc::now() - chrono::Duration::days(days_ago);
+        server
+    }
+
+    #[test]
+    fn test_sqlite_store_round_trip() {
+        let dir = std::env::temp_dir().join(format!("steamserv-store-{}", std::process::id()));
+        let store = SqliteStore::new(dir.join("config.toml"), dir.join("state.sqlite"));
+
+        let config = Config {
+            installed_servers: vec![server("fresh", 1), server("stale", 60)],
+            ..Default::default()
+        };
+        store.save(&config).unwrap();
+
+        assert_eq!(store.load().unwrap().installed_servers.len(), 2);
+        let stale = store
+            .servers_updated_before(Utc::now() - chrono::Duration::days(30))
+            .unwrap();
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].name, "stale");
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_sqlite_store_keeps_concurrent_saves() {
+        let dir =
+            std::env::temp_dir().join(format!("steamserv-store-merge-{}", std::process::id()));
+        let store = SqliteStore::new(dir.join("config.toml"), dir.join("state.sqlite"));
+        store
+            .save(&Config {
+                installed_servers: vec![server("cs2", 1), server("rust", 1), server("tf2", 1)],
+                ..Default::default()
+            })
+            .unwrap();
+
+        let mut first = store.load().unwrap();
+        let mut second = store.load().unwrap();
+        first.installed_servers[0].paused = Some(Pause {
+            since: Utc::now(),
+            reason: None,
+        });
+        first.installed_servers.retain(|s| s.name != "tf2");
+        second.installed_servers[1].steamcmd_args = vec!["+quit".to_string()];
+        store.save(&first).unwrap();
+        store.save(&second).unwrap();
+
+        let servers = store.load().unwrap().installed_servers;
+        assert_eq!(servers.len(), 2);
+        assert!(servers[0].paused.is_some());
+        assert_eq!(servers[1].steamcmd_args, ["+quit"]);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_toml_store_keeps_backups() {
+        let dir = std::env::temp_dir().join(format!("steamserv-backups-{}", std::process::id()));
+        let store = TomlStore::new(dir.join("config.toml"));
+
+        for i in 0..4 {
+            let config = Config {
+                install_path: PathBuf::from(format!("/srv/{}", i)),
+                config_backups: 2,
+                ..Default::default()
+            };
+            store.save(&config).unwrap();
+        }
+
+        let backups = store.backups().unwrap();
+        assert_eq!(backups.len(), 2);
+
+        store.restore(&backups[0]).unwrap();
+        assert_eq!(store.load().unwrap().install_path, PathBuf::from("/srv/2"));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
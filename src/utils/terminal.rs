@@ -0,0 +1,167 @@
+use std::{
+    io::Write,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Once,
+    },
+    thread,
+    time::Duration,
+};
+
+use chrono::Local;
+
+use crate::{tr, utils::runtime};
+
+/// Width of the terminal if it could not be detected
+pub const DEFAULT_WIDTH: usize = 80;
+/// Escape sequence that clears the terminal and moves the cursor to the top left
+pub const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+static RESIZED: AtomicBool = AtomicBool::new(false);
+static WATCH: Once = Once::new();
+
+/// Get the width of the terminal that stdout is connected to
+///
+/// # Returns
+///
+/// The number of columns, or `None` if stdout is not a terminal
+pub fn width() -> Option<usize> {
+    // SAFETY: TIOCGWINSZ only writes the window size to the zeroed struct
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    let result = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) };
+    if result != 0 || size.ws_col == 0 {
+        return None;
+    }
+    Some(usize::from(size.ws_col))
+}
+
+/// Start watching for changes of the terminal size
+///
+/// Installs a handler for `SIGWINCH` on the first call, later calls do nothing.
+pub fn watch_resize() {
+    WATCH.call_once(|| {
+        // SAFETY: the handler only stores to an atomic, which is signal safe
+        unsafe {
+            libc::signal(libc::SIGWINCH, on_resize as *const () as libc::sighandler_t);
+        }
+    });
+}
+
+/// Check if the terminal was resized since the last check
+///
+/// # Returns
+///
+/// True once after every resize, if [`watch_resize`] was called
+pub fn take_resized() -> bool {
+    RESIZED.swap(false, Ordering::Relaxed)
+}
+
+/// Show a view again and again until Ctrl+C, like `watch`
+///
+/// The terminal is cleared before every frame. In plain mode the frames are
+/// printed below each other, e.g. for a log file.
+///
+/// # Arguments
+///
+/// - `interval` - The seconds between the frames
+/// - `render` - Prints one frame
+///
+/// # Returns
+///
+/// Only returns if a frame could not be printed
+///
+/// # Errors
+///
+/// The error of the frame
+pub fn watch(
+    interval: u64,
+    mut render: impl FnMut() -> Result<(), Box<dyn std::error::Error>>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    loop {
+        if runtime::is_plain() {
+            println!();
+        } else {
+            print!("{}", CLEAR_SCREEN);
+        }
+        println!(
+            "{}\n",
+            tr!(
+                "watch-title",
+                interval = interval,
+                time = Local::now().format("%H:%M:%S").to_string()
+            )
+        );
+        render()?;
+        std::io::stdout().flush()?;
+        thread::sleep(Duration::from_secs(interval));
+    }
+}
+
+/// Handler of `SIGWINCH`
+extern "C" fn on_resize(_: libc::c_int) {
+    RESIZED.store(true, Ordering::Relaxed);
+}
+
+/// Shrink column widths until they fit into the available width
+///
+/// The widest column is shrunk first, no column gets narrower than four
+/// characters.
+///
+/// # Arguments
+///
+/// - `widths` - The widths of the columns
+/// - `available` - The available width, including one space between the columns
+///
+/// # Returns
+///
+/// The fitted widths
+pub fn fit_widths(widths: &[usize], available: usize) -> Vec<usize> {
+    const MIN_WIDTH: usize = 4;
+
+    let mut widths = widths.to_vec();
+    let total = |widths: &[usize]| widths.iter().sum::<usize>() + widths.len().saturating_sub(1);
+    while total(&widths) > available {
+        let Some(widest) = widths
+            .iter_mut()
+            .filter(|width| **width > MIN_WIDTH)
+            .max_by_key(|width| **width)
+        else {
+            break;
+        };
+        *widest -= 1;
+    }
+    widths
+}
+
+/// Cut a text to a width, marking the cut with `…`
+///
+/// # Arguments
+///
+/// - `text` - The text
+/// - `width` - The maximum number of characters
+///
+/// # Returns
+///
+/// The text, shortened if it is longer than the width
+pub fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(width.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_widths() {
+        assert_eq!(fit_widths(&[10, 30, 20], 80), vec![10, 30, 20]);
+        assert_eq!(fit_widths(&[10, 30, 20], 50), vec![10, 19, 19]);
+        assert_eq!(fit_widths(&[10, 10], 5), vec![4, 4]);
+        assert_eq!(truncate("de_dust2", 10), "de_dust2");
+        assert_eq!(truncate("de_dust2_night", 8), "de_dust…");
+    }
+}
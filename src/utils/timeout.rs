@@ -0,0 +1,192 @@
+use std::{
+    process::Child,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::tr;
+
+/// Timeouts of SteamCMD operations and network calls in seconds, `0` disables a timeout
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct TimeoutConfig {
+    pub bootstrap: u64,
+    pub platform_check: u64,
+    pub install: u64,
+    pub network: u64,
+}
+
+impl Default for TimeoutConfig {
+    /// Generous limits that only catch a hung SteamCMD or server
+    fn default() -> Self {
+        Self {
+            bootstrap: 600,
+            platform_check: 120,
+            install: 4 * 60 * 60,
+            network: 120,
+        }
+    }
+}
+
+impl TimeoutConfig {
+    /// Convert a timeout in seconds to a duration
+    ///
+    /// # Arguments
+    ///
+    /// - `seconds` - The timeout in seconds, `0` for no timeout
+    ///
+    /// # Returns
+    ///
+    /// The duration, or `None` if the timeout is disabled
+    pub fn limit(seconds: u64) -> Option<Duration> {
+        (seconds > 0).then(|| Duration::from_secs(seconds))
+    }
+}
+
+/// Kills a child process and its descendants when it runs longer than a timeout
+///
+/// The watchdog runs on its own thread, so it also fires while the caller is
+/// blocked reading the output of the child.
+pub struct Watchdog {
+    cancel: Option<mpsc::Sender<()>>,
+    expired: Arc<AtomicBool>,
+    timeout: Option<Duration>,
+}
+
+impl Watchdog {
+    /// Start watching a child process
+    ///
+    /// # Arguments
+    ///
+    /// - `child` - The child process
+    /// - `timeout` - The timeout, or `None` to never kill the child
+    ///
+    /// # Returns
+    ///
+    /// The running watchdog
+    pub fn start(child: &Child, timeout: Option<Duration>) -> Self {
+        let expired = Arc::new(AtomicBool::new(false));
+        let Some(limit) = timeout else {
+            return Self {
+                cancel: None,
+                expired,
+                timeout,
+            };
+        };
+
+        let (cancel, cancelled) = mpsc::channel::<()>();
+        let pid = child.id();
+        let flag = Arc::clone(&expired);
+        thread::spawn(move || {
+            if let Err(mpsc::RecvTimeoutError::Timeout) = cancelled.recv_timeout(limit) {
+                flag.store(true, Ordering::SeqCst);
+                kill_tree(pid);
+            }
+        });
+
+        Self {
+            cancel: Some(cancel),
+            expired,
+            timeout,
+        }
+    }
+
+    /// Stop watching and report whether the child was killed
+    ///
+    /// # Arguments
+    ///
+    /// - `operation` - The name of the operation for the error message
+    ///
+    /// # Returns
+    ///
+    /// Ok if the child finished in time
+    ///
+    /// # Errors
+    ///
+    /// If the child was killed because it ran into the timeout
+    pub fn finish(mut self, operation: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(cancel) = self.cancel.take() {
+            let _ = cancel.send(());
+        }
+
+        if self.expired.load(Ordering::SeqCst) {
+            let seconds = self.timeout.map(|t| t.as_secs()).unwrap_or_default();
+            return Err(tr!("timeout-expired", operation = operation, seconds = seconds).into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Kill a process and all of its descendants
+///
+/// SteamCMD is started through `steamcmd.sh`, which keeps the actual binary
+/// as a child, so killing only the script would leave the download running.
+///
+/// # Arguments
+///
+/// - `pid` - The process ID
+fn kill_tree(pid: u32) {
+    let mut pids = vec![pid];
+    let mut index = 0;
+    while index < pids.len() {
+        pids.extend(children(pids[index]));
+        index += 1;
+    }
+
+    for pid in pids {
+        // SAFETY: kill has no memory safety requirements
+        unsafe {
+            libc::kill(pid as libc::pid_t, libc::SIGKILL);
+        }
+    }
+}
+
+/// Get the direct children of a process from `/proc`
+///
+/// # Arguments
+///
+/// - `pid` - The process ID
+///
+/// # Returns
+///
+/// The process IDs of the children, empty if they could not be read
+fn children(pid: u32) -> Vec<u32> {
+    let Ok(tasks) = std::fs::read_dir(format!("/proc/{}/task", pid)) else {
+        return Vec::new();
+    };
+
+    tasks
+        .filter_map(|task| std::fs::read_to_string(task.ok()?.path().join("children")).ok())
+        .flat_map(|children| {
+            children
+                .split_whitespace()
+                .filter_map(|c| c.parse().ok())
+                .collect::<Vec<u32>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watchdog_kills_hung_child() {
+        let mut child = std::process::Command::new("sh")
+            .arg("-c")
+            .arg("sleep 30")
+            .spawn()
+            .unwrap();
+
+        let watchdog = Watchdog::start(&child, Some(Duration::from_millis(100)));
+        child.wait().unwrap();
+        assert!(watchdog.finish("test").is_err());
+    }
+}
@@ -0,0 +1,221 @@
+/// A node of a Valve KeyValues (VDF) document, like the app info printed by
+/// SteamCMD or an app manifest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Vdf {
+    Value(String),
+    Object(Vec<(String, Vdf)>),
+}
+
+impl Vdf {
+    /// Parse a document
+    ///
+    /// # Arguments
+    ///
+    /// - `text` - The key value pairs, e.g. `"AppState" { "buildid" "123" }`
+    ///
+    /// # Returns
+    ///
+    /// An object with the top level pairs, or `None` if the document is invalid
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut tokens = tokenize(text)?.into_iter();
+        let entries = parse_entries(&mut tokens, false)?;
+        Some(Self::Object(entries))
+    }
+
+    /// Find an object in text with other output around it, like the output of SteamCMD
+    ///
+    /// # Arguments
+    ///
+    /// - `text` - The text
+    /// - `key` - The key of the object, e.g. the app ID for `+app_info_print`
+    ///
+    /// # Returns
+    ///
+    /// The object, or `None` if the key is not followed by a valid object
+    pub fn find(text: &str, key: &str) -> Option<Self> {
+        let start = text.find(&format!("\"{}\"", key))?;
+        let mut tokens = tokenize(&text[start..])?.into_iter();
+        tokens.next()?;
+        if tokens.next()? != Token::Open {
+            return None;
+        }
+        Some(Self::Object(parse_entries(&mut tokens, true)?))
+    }
+
+    /// Get a node by its path
+    ///
+    /// # Arguments
+    ///
+    /// - `path` - The keys separated by `/`, compared case-insensitively, e.g. `depots/branches/public`
+    ///
+    /// # Returns
+    ///
+    /// The node, or `None` if a key does not exist
+    pub fn get(&self, path: &str) -> Option<&Vdf> {
+        path.split('/')
+            .filter(|key| !key.is_empty())
+            .try_fold(self, |node, key| {
+                node.entries()
+                    .iter()
+                    .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                    .map(|(_, value)| value)
+            })
+    }
+
+    /// Get a value by its path
+    ///
+    /// # Arguments
+    ///
+    /// - `path` - The keys separated by `/`
+    ///
+    /// # Returns
+    ///
+    /// The value, or `None` if it does not exist or is an object
+    pub fn value(&self, path: &str) -> Option<&str> {
+        match self.get(path)? {
+            Self::Value(value) => Some(value),
+            Self::Object(_) => None,
+        }
+    }
+
+    /// Get the pairs of an object
+    ///
+    /// # Returns
+    ///
+    /// The pairs in document order, empty for a value
+    pub fn entries(&self) -> &[(String, Vdf)] {
+        match self {
+            Self::Value(_) => &[],
+            Self::Object(entries) => entries,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    Text(String),
+}
+
+/// Split a document into braces and strings
+///
+/// # Arguments
+///
+/// - `text` - The document
+///
+/// # Returns
+///
+/// The tokens, or `None` if a quoted string is not closed
+fn tokenize(text: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => tokens.push(Token::Open),
+            '}' => tokens.push(Token::Close),
+            '"' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => match chars.next()? {
+                            'n' => value.push('\n'),
+                            't' => value.push('\t'),
+                            c => value.push(c),
+                        },
+                        c => value.push(c),
+                    }
+                }
+                tokens.push(Token::Text(value));
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            c if c.is_whitespace() => {}
+            c => {
+                let mut value = c.to_string();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || matches!(c, '{' | '}' | '"') {
+                        break;
+                    }
+                    value.push(c);
+                    chars.next();
+                }
+                // Platform conditions like `[$WIN32]` only apply to the game client
+                if !value.starts_with("[$") {
+                    tokens.push(Token::Text(value));
+                }
+            }
+        }
+    }
+
+    Some(tokens)
+}
+
+/// Parse key value pairs until the end of the object or the document
+///
+/// # Arguments
+///
+/// - `tokens` - The remaining tokens
+/// - `nested` - True inside an object, which has to be closed with a brace
+///
+/// # Returns
+///
+/// The pairs, or `None` if the structure is invalid
+fn parse_entries(
+    tokens: &mut impl Iterator<Item = Token>,
+    nested: bool,
+) -> Option<Vec<(String, Vdf)>> {
+    let mut entries = Vec::new();
+
+    loop {
+        let key = match tokens.next() {
+            Some(Token::Text(key)) => key,
+            Some(Token::Close) if nested => return Some(entries),
+            None if !nested => return Some(entries),
+            _ => return None,
+        };
+        let value = match tokens.next()? {
+            Token::Text(value) => Vdf::Value(value),
+            Token::Open => Vdf::Object(parse_entries(tokens, true)?),
+            Token::Close => return None,
+        };
+        entries.push((key, value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let text = r#"
+            "AppState"
+            {
+                "appid"		"896660"
+                "buildid"		"14512345"
+                // Comment
+                "UserConfig" { "language" "english" }
+                "name" "Say \"hi\""
+            }
+        "#;
+        let vdf = Vdf::parse(text).unwrap();
+        assert_eq!(vdf.value("AppState/buildid"), Some("14512345"));
+        assert_eq!(vdf.value("appstate/userconfig/language"), Some("english"));
+        assert_eq!(vdf.value("AppState/name"), Some("Say \"hi\""));
+        assert_eq!(vdf.value("AppState/UserConfig"), None);
+        assert_eq!(vdf.get("AppState/missing"), None);
+        assert_eq!(Vdf::parse("\"a\" { \"b\" \"c\""), None);
+
+        let output = "Loading Steam API...OK\n\"730\" { \"common\" { \"name\" \"CS2\" } }\nUnloading Steam API...OK";
+        let info = Vdf::find(output, "730").unwrap();
+        assert_eq!(info.value("common/name"), Some("CS2"));
+    }
+}